@@ -40,6 +40,8 @@ pub fn spawn_command(
                 damage_threshold: 0.03,
                 blast_radius: 120.0,
                 blast_strength: 60.0,
+                ignites: true,
+                thermal_radius: 160.0,
                 detonated: false,
               });
             },
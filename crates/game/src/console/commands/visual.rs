@@ -12,6 +12,10 @@ pub enum VisualTarget {
   DirtyRects,
   /// Toggle collision mesh visualization
   Collision,
+  /// Toggle pixel body AABB and origin visualization
+  BodyBounds,
+  /// Toggle pixel body shape mask visualization
+  BodyMasks,
 }
 
 #[derive(Parser, ConsoleCommand)]
@@ -50,6 +54,24 @@ pub fn visual_command(
         };
         reply!(log, "Collision meshes: {}", state);
       }
+      VisualTarget::BodyBounds => {
+        settings.show_pixel_body_bounds = !settings.show_pixel_body_bounds;
+        let state = if settings.show_pixel_body_bounds {
+          "on"
+        } else {
+          "off"
+        };
+        reply!(log, "Pixel body bounds: {}", state);
+      }
+      VisualTarget::BodyMasks => {
+        settings.show_pixel_body_masks = !settings.show_pixel_body_masks;
+        let state = if settings.show_pixel_body_masks {
+          "on"
+        } else {
+          "off"
+        };
+        reply!(log, "Pixel body masks: {}", state);
+      }
     }
   }
 }
@@ -15,17 +15,20 @@ use crate::pixel_world::collision::{
   invalidate_dirty_tiles, poll_collision_tasks,
 };
 use crate::pixel_world::pixel_body::{
-  PixelBodyIdGenerator, apply_readback_changes, check_bomb_damage, detect_external_erasure,
-  finalize_pending_pixel_bodies, init_bomb_state, process_detonations, readback_pixel_bodies,
-  split_pixel_bodies, sync_simulation_to_bodies, update_pixel_bodies,
+  ColliderRegenConfig, DetonationConfig, DetonationQueue, PixelBodyIdGenerator,
+  PixelBodySpawnFailed, Shockwaves, apply_readback_changes, check_bomb_damage,
+  detect_external_erasure, finalize_pending_pixel_bodies, init_bomb_state, process_detonations,
+  readback_pixel_bodies, split_pixel_bodies, sync_simulation_to_bodies, update_pixel_bodies,
 };
-use crate::pixel_world::schedule::{PixelWorldSet, SimulationPhase};
+use crate::pixel_world::render::sync_pixel_body_sprites;
+use crate::pixel_world::schedule::{PixelSimulation, PixelWorldSet, SimulationPhase};
 use crate::pixel_world::world::body_loader::spawn_pending_pixel_bodies;
 use crate::pixel_world::world::persistence_systems::{
   save_pixel_bodies_on_chunk_unload, save_pixel_bodies_on_request,
 };
 use crate::pixel_world::world::streaming::{
-  PendingPixelBodies, queue_pixel_bodies_on_chunk_seed, update_simulation_bounds,
+  PendingPixelBodies, queue_pixel_bodies_on_chunk_seed, update_body_keep_alive,
+  update_simulation_bounds, update_streaming_windows,
 };
 
 /// Plugin for pixel body systems: spawning, simulation integration, collision,
@@ -51,7 +54,12 @@ impl Plugin for PixelBodiesPlugin {
       .init_resource::<CollisionConfig>()
       .init_resource::<PendingPixelBodies>()
       .init_resource::<PixelBodyIdGenerator>()
-      .init_resource::<crate::pixel_world::diagnostics::CollisionMetrics>();
+      .init_resource::<DetonationConfig>()
+      .init_resource::<ColliderRegenConfig>()
+      .init_resource::<DetonationQueue>()
+      .init_resource::<Shockwaves>()
+      .init_resource::<crate::pixel_world::diagnostics::CollisionMetrics>()
+      .add_message::<PixelBodySpawnFailed>();
 
     #[cfg(physics)]
     app.init_resource::<PhysicsColliderRegistry>();
@@ -71,6 +79,14 @@ impl Plugin for PixelBodiesPlugin {
         .in_set(PixelWorldSet::PreSimulation),
     );
 
+    // Chunks kept alive around bodies are applied by the window update
+    app.add_systems(
+      Update,
+      update_body_keep_alive
+        .before(update_streaming_windows)
+        .in_set(PixelWorldSet::PreSimulation),
+    );
+
     // ApplyDeferred between pre-sim and sim so new bodies are visible
     app.add_systems(
       Update,
@@ -79,9 +95,9 @@ impl Plugin for PixelBodiesPlugin {
         .before(PixelWorldSet::Simulation),
     );
 
-    // Before CA tick: blit bodies and detect erasure
+    // Before CA tick: blit bodies and detect erasure, once per simulation tick
     app.add_systems(
-      Update,
+      PixelSimulation,
       (detect_external_erasure, update_pixel_bodies)
         .chain()
         .in_set(SimulationPhase::BeforeCATick),
@@ -89,7 +105,7 @@ impl Plugin for PixelBodiesPlugin {
 
     // After CA tick: readback, shape changes, split, invalidate
     app.add_systems(
-      Update,
+      PixelSimulation,
       (
         sync_simulation_to_bodies,
         init_bomb_state,
@@ -104,6 +120,42 @@ impl Plugin for PixelBodiesPlugin {
         .in_set(SimulationPhase::AfterCATick),
     );
 
+    // Rest merging sees bodies blitted at their current transform
+    #[cfg(physics)]
+    app.add_systems(
+      PixelSimulation,
+      crate::pixel_world::pixel_body::update_body_rest
+        .after(split_pixel_bodies)
+        .in_set(SimulationPhase::AfterCATick),
+    );
+
+    // Imprinting sees bodies blitted at their current transform
+    app.add_systems(
+      PixelSimulation,
+      crate::pixel_world::pixel_body::imprint_soft_terrain
+        .after(split_pixel_bodies)
+        .in_set(SimulationPhase::AfterCATick),
+    );
+
+    // Sinking edits terrain under bodies, so it goes before tile invalidation
+    #[cfg(physics)]
+    app.add_systems(
+      PixelSimulation,
+      crate::pixel_world::pixel_body::sink_bodies_into_powder
+        .after(split_pixel_bodies)
+        .before(invalidate_dirty_tiles)
+        .in_set(SimulationPhase::AfterCATick),
+    );
+
+    // Collider rebuilds pick up shape changes from readback and splitting
+    #[cfg(physics)]
+    app.add_systems(
+      PixelSimulation,
+      crate::pixel_world::pixel_body::regenerate_pixel_body_colliders
+        .after(split_pixel_bodies)
+        .in_set(SimulationPhase::AfterCATick),
+    );
+
     // Post-simulation: collision, spawning, body persistence
     app.add_systems(
       Update,
@@ -126,6 +178,12 @@ impl Plugin for PixelBodiesPlugin {
         .in_set(PixelWorldSet::PostSimulation),
     );
 
+    // Body sprites, for worlds drawing bodies apart from chunk textures
+    app.add_systems(
+      Update,
+      sync_pixel_body_sprites.in_set(PixelWorldSet::PostSimulation),
+    );
+
     // Debug collision gizmos (only when rendering is available)
     app.add_systems(
       PostUpdate,
@@ -2,6 +2,12 @@
 //!
 //! Computes buoyancy forces based on submersion state and applies them
 //! to physics bodies.
+//!
+//! With [`BuoyancyConfig::per_pixel_density`], the physics engine still
+//! treats the body as uniform, so the lift is scaled down by how much denser
+//! the body's pixels are than the reference density, which matches the
+//! acceleration a body of that density would get, and torque is taken about
+//! the density-weighted center of mass.
 
 #[cfg(physics)]
 use bevy::prelude::*;
@@ -11,12 +17,46 @@ use super::BuoyancyConfig;
 #[cfg(physics)]
 use super::submersion::SubmersionState;
 #[cfg(physics)]
+use crate::pixel_world::pixel_awareness::LiquidFractionState;
+#[cfg(physics)]
 use crate::pixel_world::pixel_body::PixelBody;
 
 /// Default gravity magnitude (matches typical physics engine defaults).
 #[cfg(physics)]
 const GRAVITY: f32 = 9.81 * 10.0; // Scaled for pixel world
 
+/// Returns the buoyancy force and torque on a body of `body_volume` solid
+/// pixels whose origin is at `body_center`.
+#[cfg(physics)]
+pub fn buoyancy_force(
+  config: &BuoyancyConfig,
+  body_volume: f32,
+  body_center: Vec2,
+  state: &SubmersionState,
+  liquid: &LiquidFractionState,
+) -> (Vec2, f32) {
+  if state.submerged_fraction <= 0.0 {
+    return (Vec2::ZERO, 0.0);
+  }
+
+  let submerged_volume = body_volume * state.submerged_fraction;
+  let mut buoyancy_magnitude = submerged_volume * GRAVITY * config.liquid_density_scale;
+  let mut mass_center = body_center;
+  if config.per_pixel_density && liquid.body_density > 0.0 {
+    buoyancy_magnitude *= config.reference_density as f32 / liquid.body_density;
+    mass_center = liquid.mass_center;
+  }
+
+  let torque = if config.torque_enabled {
+    let lever_arm = state.submerged_center - mass_center;
+    lever_arm.x * buoyancy_magnitude
+  } else {
+    0.0
+  };
+
+  (Vec2::new(0.0, buoyancy_magnitude), torque)
+}
+
 /// Computes and applies buoyancy forces to submerged bodies.
 #[cfg(physics)]
 #[allow(clippy::type_complexity)]
@@ -26,27 +66,21 @@ pub fn compute_buoyancy_forces(
     &PixelBody,
     &GlobalTransform,
     &SubmersionState,
+    &LiquidFractionState,
     &mut bevy_rapier2d::prelude::ExternalForce,
   )>,
 ) {
-  for (body, transform, state, mut force) in bodies.iter_mut() {
-    if state.submerged_fraction <= 0.0 {
-      force.force = Vec2::ZERO;
-      force.torque = 0.0;
-      continue;
-    }
-
-    let body_volume = body.solid_count() as f32;
-    let submerged_volume = body_volume * state.submerged_fraction;
-    let buoyancy_magnitude = submerged_volume * GRAVITY * config.liquid_density_scale;
-
-    force.force = Vec2::new(0.0, buoyancy_magnitude);
-
-    if config.torque_enabled {
-      let body_center = transform.translation().truncate();
-      let buoyancy_center = state.submerged_center;
-      let lever_arm = buoyancy_center - body_center;
-      force.torque = lever_arm.x * buoyancy_magnitude;
+  for (body, transform, state, liquid, mut force) in bodies.iter_mut() {
+    let (linear, torque) = buoyancy_force(
+      &config,
+      body.solid_count() as f32,
+      transform.translation().truncate(),
+      state,
+      liquid,
+    );
+    force.force = linear;
+    if config.torque_enabled || state.submerged_fraction <= 0.0 {
+      force.torque = torque;
     }
   }
 }
@@ -54,3 +88,4 @@ pub fn compute_buoyancy_forces(
 /// No-op when physics is not enabled.
 #[cfg(not(physics))]
 pub fn compute_buoyancy_forces() {}
+
@@ -6,6 +6,8 @@
 //!   with edge-detection events.
 //! - **Buoyancy forces**: Archimedes-principle forces for bodies marked
 //!   [`Buoyant`].
+//! - **Splashes**: Surface liquid thrown up when a body hits it fast, see
+//!   [`SplashConfig`].
 //!
 //! # Usage
 //!
@@ -26,13 +28,17 @@ pub mod events;
 mod force;
 #[cfg(physics)]
 pub mod physics;
+pub mod splash;
 pub mod submersion;
 
 use bevy::prelude::*;
 pub use events::emit_submersion_events;
+#[cfg(physics)]
+pub use force::buoyancy_force;
 pub use force::compute_buoyancy_forces;
 #[cfg(physics)]
 pub use physics::{SubmersionPhysicsConfig, apply_submersion_physics};
+pub use splash::{SplashConfig, SplashState, Splashed, spawn_splashes};
 pub use submersion::{
   Submerged, Submergent, SubmersionConfig, SubmersionState, Surfaced, derive_submersion_state,
 };
@@ -51,6 +57,14 @@ pub struct BuoyancyConfig {
   /// Whether to apply rotational forces (torque) based on
   /// center of buoyancy offset. Default: true.
   pub torque_enabled: bool,
+  /// Whether to derive each body's average density and center of mass from
+  /// the material densities of its pixels, instead of treating it as a
+  /// uniform body centered on its origin. Default: false.
+  pub per_pixel_density: bool,
+  /// Average material density at which a body gets the same lift as in the
+  /// uniform model when `per_pixel_density` is set; denser bodies get
+  /// proportionally less. Default: 80 (wood).
+  pub reference_density: u8,
 }
 
 impl Default for BuoyancyConfig {
@@ -59,6 +73,8 @@ impl Default for BuoyancyConfig {
       sample_grid_size: 4,
       liquid_density_scale: 0.1,
       torque_enabled: true,
+      per_pixel_density: false,
+      reference_density: 80,
     }
   }
 }
@@ -88,6 +104,7 @@ pub struct BuoyancyState {
 /// Adds systems for:
 /// - Deriving submersion state from liquid fraction (threshold + events)
 /// - Applying buoyancy forces to [`Buoyant`] bodies
+/// - Splashing liquid when bodies hit it fast
 /// - Modifying gravity/damping for submerged bodies (when physics enabled)
 ///
 /// Requires [`PixelAwarenessPlugin`](crate::pixel_world::pixel_awareness::PixelAwarenessPlugin)
@@ -110,6 +127,8 @@ pub struct Buoyancy2dPlugin {
   pub config: BuoyancyConfig,
   /// Configuration for submersion threshold.
   pub submersion: SubmersionConfig,
+  /// Configuration for impact splashes.
+  pub splash: SplashConfig,
   /// Configuration for physics effects (gravity, damping).
   #[cfg(physics)]
   pub physics: SubmersionPhysicsConfig,
@@ -130,6 +149,12 @@ impl Buoyancy2dPlugin {
     self
   }
 
+  /// Sets the splash configuration.
+  pub fn with_splash(mut self, config: SplashConfig) -> Self {
+    self.splash = config;
+    self
+  }
+
   /// Sets the physics configuration.
   #[cfg(physics)]
   pub fn with_physics(mut self, physics: SubmersionPhysicsConfig) -> Self {
@@ -142,8 +167,10 @@ impl Plugin for Buoyancy2dPlugin {
   fn build(&self, app: &mut App) {
     app.insert_resource(self.config.clone());
     app.insert_resource(self.submersion.clone());
+    app.insert_resource(self.splash.clone());
     app.add_message::<Submerged>();
     app.add_message::<Surfaced>();
+    app.add_message::<Splashed>();
 
     app.add_systems(
       Update,
@@ -157,6 +184,8 @@ impl Plugin for Buoyancy2dPlugin {
       compute_buoyancy_forces.after(derive_submersion_state),
     );
 
+    app.add_systems(Update, spawn_splashes.after(sample_liquid_fraction));
+
     #[cfg(physics)]
     {
       app.insert_resource(self.physics.clone());
@@ -0,0 +1,212 @@
+//! Liquid splashes from fast impacts.
+//!
+//! When a pixel body first touches liquid while falling faster than
+//! [`SplashConfig::min_impact_speed`], liquid at the surface beside the body
+//! is thrown up and outward. Droplets are moved rather than created, so the
+//! liquid's mass is conserved, and the CA simulation lets them fall back.
+//! Placement is hashed from the body id and world tick, so the same impact
+//! always splashes the same way.
+
+use bevy::prelude::*;
+
+use crate::pixel_world::coords::WorldPos;
+use crate::pixel_world::debug_shim::DebugGizmos;
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::pixel_awareness::LiquidFractionState;
+use crate::pixel_world::pixel_body::{PixelBody, PixelBodyId, compute_world_aabb};
+use crate::pixel_world::simulation::hash::hash41uu64;
+use crate::pixel_world::world::PixelWorld;
+
+/// Configuration for impact splashes.
+#[derive(Resource, Clone, Debug)]
+pub struct SplashConfig {
+  /// Downward speed in pixels per second a body needs when it first touches
+  /// liquid to splash. Default: 150.0.
+  pub min_impact_speed: f32,
+  /// Maximum droplets thrown per impact, split between both sides of the
+  /// body. Default: 12.
+  pub droplets: u32,
+  /// Maximum height above the surface a droplet is thrown to. Default: 12.
+  pub max_height: u32,
+  /// Maximum distance a droplet is thrown outward. Default: 6.
+  pub max_spread: u32,
+  /// How far below the body's bottom edge the liquid surface is searched
+  /// for. Default: 16.
+  pub surface_search_depth: u32,
+}
+
+impl Default for SplashConfig {
+  fn default() -> Self {
+    Self {
+      min_impact_speed: 150.0,
+      droplets: 12,
+      max_height: 12,
+      max_spread: 6,
+      surface_search_depth: 16,
+    }
+  }
+}
+
+/// Tracks the motion and liquid contact of a body between frames.
+///
+/// Automatically added to pixel bodies once their liquid fraction is
+/// sampled.
+#[derive(Component, Default)]
+pub struct SplashState {
+  /// Vertical position at the previous check.
+  last_y: Option<f32>,
+  /// Whether the body touched liquid at the previous check.
+  touching: bool,
+}
+
+/// Message sent when a body splashes into liquid.
+#[derive(bevy::prelude::Message, Clone, Debug)]
+pub struct Splashed {
+  /// The body that hit the liquid.
+  pub entity: Entity,
+  /// World position of the liquid the body touched.
+  pub position: Vec2,
+  /// Downward speed at impact, in pixels per second.
+  pub speed: f32,
+  /// Number of liquid pixels thrown.
+  pub droplets: u32,
+}
+
+/// Returns true if `pixel` is free liquid, not part of a body.
+fn is_liquid(pixel: &Pixel, materials: &Materials) -> bool {
+  !pixel.flags.contains(PixelFlags::PIXEL_BODY)
+    && materials.get(pixel.material).state == PhysicsState::Liquid
+}
+
+/// Returns the topmost liquid pixel in column `x`, scanning down from `top`
+/// through void and gas to `bottom`.
+fn find_surface(
+  world: &PixelWorld,
+  materials: &Materials,
+  x: i64,
+  top: i64,
+  bottom: i64,
+) -> Option<WorldPos> {
+  for y in (bottom..=top).rev() {
+    let pos = WorldPos::new(x, y);
+    let pixel = world.get_pixel(pos)?;
+    if is_liquid(pixel, materials) {
+      return Some(pos);
+    }
+    if !pixel.is_void() && materials.get(pixel.material).state != PhysicsState::Gas {
+      return None;
+    }
+  }
+  None
+}
+
+/// Throws surface liquid beside a body up and outward, returning the number
+/// of droplets moved.
+fn splash(
+  world: &mut PixelWorld,
+  materials: &Materials,
+  config: &SplashConfig,
+  body: &PixelBody,
+  transform: &GlobalTransform,
+  id: PixelBodyId,
+) -> u32 {
+  const CH_SPLASH: u64 = 0x5eed_5b1a_54ed_0001;
+
+  let aabb = compute_world_aabb(body, transform);
+  let top = aabb.y + aabb.height as i64 - 1;
+  let bottom = aabb.y - config.surface_search_depth as i64;
+  let tick = world.tick();
+
+  let mut moved = 0;
+  for i in 0..config.droplets {
+    // Alternate sides, moving one column further out every pair
+    let (side, x) = if i % 2 == 0 {
+      (-1, aabb.x - 1 - (i / 2) as i64)
+    } else {
+      (1, aabb.x + aabb.width as i64 + (i / 2) as i64)
+    };
+    let Some(source) = find_surface(world, materials, x, top, bottom) else {
+      continue;
+    };
+
+    let hash = hash41uu64(id.value() ^ CH_SPLASH, tick, i as u64, 0);
+    let dx = (hash & 0xFFFF) % (config.max_spread as u64 + 1);
+    let dy = ((hash >> 16) & 0xFFFF) % config.max_height.max(1) as u64 + 1;
+    let target = WorldPos::new(x + side * dx as i64, source.y + dy as i64);
+    if !world.get_pixel(target).is_some_and(|p| p.is_void()) {
+      continue;
+    }
+
+    let Some(&droplet) = world.get_pixel(source) else {
+      continue;
+    };
+    world.set_pixel(target, droplet, DebugGizmos::none());
+    world.set_pixel(source, Pixel::VOID, DebugGizmos::none());
+    world.mark_pixel_sim_dirty(target);
+    world.mark_pixel_sim_dirty(source);
+    moved += 1;
+  }
+  moved
+}
+
+/// Splashes liquid for bodies that hit it fast.
+///
+/// A body splashes on the first frame its liquid fraction rises above zero,
+/// if it moved down faster than the configured threshold since the previous
+/// frame.
+pub fn spawn_splashes(
+  mut commands: Commands,
+  config: Res<SplashConfig>,
+  time: Res<Time>,
+  materials: Option<Res<Materials>>,
+  mut worlds: Query<&mut PixelWorld>,
+  mut bodies: Query<(
+    Entity,
+    &PixelBody,
+    &GlobalTransform,
+    &PixelBodyId,
+    &LiquidFractionState,
+    Option<&mut SplashState>,
+  )>,
+  mut splashed: MessageWriter<Splashed>,
+) {
+  let (Ok(mut world), Some(materials)) = (worlds.single_mut(), materials) else {
+    return;
+  };
+  let dt = time.delta_secs();
+
+  for (entity, body, transform, id, liquid, state) in bodies.iter_mut() {
+    let y = transform.translation().y;
+    let touching = liquid.liquid_fraction > 0.0;
+    let Some(mut state) = state else {
+      commands.entity(entity).insert(SplashState {
+        last_y: Some(y),
+        touching,
+      });
+      continue;
+    };
+
+    let speed = match state.last_y {
+      Some(last_y) if dt > 0.0 => (last_y - y) / dt,
+      _ => 0.0,
+    };
+    let entered = touching && !state.touching;
+    state.last_y = Some(y);
+    state.touching = touching;
+
+    if !entered || speed < config.min_impact_speed {
+      continue;
+    }
+
+    let droplets = splash(&mut world, &materials, &config, body, transform, *id);
+    if droplets > 0 {
+      splashed.write(Splashed {
+        entity,
+        position: liquid.liquid_center,
+        speed,
+        droplets,
+      });
+    }
+  }
+}
@@ -5,8 +5,8 @@ use std::collections::{HashMap, HashSet};
 use bevy::prelude::*;
 use bevy::tasks::Task;
 
-use super::mesh::TileCollisionMesh;
-use crate::pixel_world::coords::TilePos;
+use super::mesh::{PolygonMesh, TileCollisionMesh};
+use crate::pixel_world::coords::{TilePos, WorldRect};
 
 /// Cached collision meshes per tile.
 #[derive(Resource, Default)]
@@ -90,6 +90,23 @@ impl CollisionCache {
     }
   }
 
+  /// Returns the cached terrain polygons of tiles overlapping `rect`.
+  ///
+  /// Polygons are the simplified marching-squares contours in world
+  /// coordinates, split at tile boundaries and not clipped to `rect`. Only
+  /// tiles already in the cache contribute: tiles that were never generated
+  /// (far from every [`CollisionQueryPoint`](super::CollisionQueryPoint)),
+  /// are in flight, or were invalidated by terrain changes are missing, so
+  /// callers should check [`contains`](Self::contains) when they need full
+  /// coverage.
+  pub fn polygons_in(&self, rect: WorldRect) -> Vec<PolygonMesh> {
+    rect
+      .to_tile_range()
+      .filter_map(|tile| self.meshes.get(&tile))
+      .flat_map(|mesh| mesh.triangles.iter().cloned())
+      .collect()
+  }
+
   /// Returns iterator over all cached tile positions.
   pub fn cached_tiles(&self) -> impl Iterator<Item = TilePos> + '_ {
     self.meshes.keys().copied()
@@ -136,4 +153,12 @@ impl CollisionTasks {
   pub fn is_empty(&self) -> bool {
     self.tasks.is_empty()
   }
+
+  /// Drops all active tasks and invalidates their tiles in `cache`, so the
+  /// tiles are generated again.
+  pub fn cancel_all(&mut self, cache: &mut CollisionCache) {
+    for task in self.tasks.drain(..) {
+      cache.invalidate(task.tile);
+    }
+  }
 }
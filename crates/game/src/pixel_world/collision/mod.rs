@@ -27,6 +27,7 @@
 //!     simplification_tolerance: 1.0,
 //!     proximity_radius: 3,
 //!     debug_gizmos: true,
+//!     ..default()
 //! });
 //! ```
 
@@ -100,6 +101,14 @@ pub struct CollisionConfig {
   /// Whether to render collision meshes as debug gizmos.
   /// Default: true
   pub debug_gizmos: bool,
+
+  /// Kinematic rest for grounded pixel bodies.
+  /// Default: disabled
+  pub rest_merge: RestMergeConfig,
+
+  /// Pixel bodies sinking into the powder they rest on.
+  /// Default: disabled
+  pub powder_sink: PowderSinkConfig,
 }
 
 impl Default for CollisionConfig {
@@ -108,6 +117,71 @@ impl Default for CollisionConfig {
       simplification_tolerance: 1.0,
       proximity_radius: 3,
       debug_gizmos: true,
+      rest_merge: RestMergeConfig::default(),
+      powder_sink: PowderSinkConfig::default(),
+    }
+  }
+}
+
+/// Configuration for settling grounded pixel bodies into kinematic rest.
+///
+/// A body's collider and the terrain beneath it are solved separately, which
+/// makes stacked bodies jitter at the seam. A resting body is held kinematic,
+/// so it acts as part of the terrain for bodies on top of it until its own
+/// support disappears.
+#[derive(Clone, Debug)]
+pub struct RestMergeConfig {
+  /// Whether grounded bodies may come to rest.
+  /// Default: false
+  pub enabled: bool,
+
+  /// Maximum movement per frame in pixels for a body to count as still.
+  /// Default: 0.5
+  pub max_step: f32,
+
+  /// Maximum rotation per frame in radians for a body to count as still.
+  /// Default: 0.01
+  pub max_rotation: f32,
+
+  /// Consecutive still, grounded frames before a body comes to rest.
+  /// Default: 30
+  pub settle_frames: u32,
+}
+
+impl Default for RestMergeConfig {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      max_step: 0.5,
+      max_rotation: 0.01,
+      settle_frames: 30,
+    }
+  }
+}
+
+/// Configuration for pixel bodies sinking into powder.
+///
+/// A body denser than the powder under it pushes that powder aside, layer by
+/// layer, until enough of the body is buried to carry it. The buried share
+/// of the body's rows grows with how much denser the body is:
+/// `strength * (body_density / powder_density - 1)`, capped at the whole
+/// body. Bodies no denser than the powder stay on top.
+#[derive(Clone, Debug)]
+pub struct PowderSinkConfig {
+  /// Whether bodies may sink into powder.
+  /// Default: false
+  pub enabled: bool,
+
+  /// Scales how deep a body sinks for a given density ratio.
+  /// Default: 1.0
+  pub strength: f32,
+}
+
+impl Default for PowderSinkConfig {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      strength: 1.0,
     }
   }
 }
@@ -130,4 +204,16 @@ impl CollisionConfig {
     self.debug_gizmos = enabled;
     self
   }
+
+  /// Sets the rest merge configuration.
+  pub fn with_rest_merge(mut self, rest_merge: RestMergeConfig) -> Self {
+    self.rest_merge = rest_merge;
+    self
+  }
+
+  /// Sets the powder sink configuration.
+  pub fn with_powder_sink(mut self, powder_sink: PowderSinkConfig) -> Self {
+    self.powder_sink = powder_sink;
+    self
+  }
 }
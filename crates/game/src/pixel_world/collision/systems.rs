@@ -53,11 +53,10 @@ fn extract_tile_grid(
   materials: &Materials,
 ) -> [[bool; GRID_SIZE]; GRID_SIZE] {
   let mut grid = [[false; GRID_SIZE]; GRID_SIZE];
-  let tile_size = TILE_SIZE as i64;
-
   // The tile origin in world coordinates
-  let tile_origin_x = tile.x * tile_size;
-  let tile_origin_y = tile.y * tile_size;
+  let tile_rect = tile.to_world_rect();
+  let tile_origin_x = tile_rect.x;
+  let tile_origin_y = tile_rect.y;
 
   // Sample a 34x34 area: the 32x32 tile plus 1px border on each side
   for (gy, row) in grid.iter_mut().enumerate() {
@@ -6,6 +6,11 @@
 //! - [`LocalPos`]: Position within a chunk (u16)
 //! - [`MaterialId`]: Material registry index (0-255)
 //! - [`ColorIndex`]: Palette color index (0-255)
+//!
+//! Conversions between them use Euclidean division, so negative
+//! coordinates map to the chunk or tile containing them.
+
+use bevy::math::Vec2;
 
 /// Size of a chunk in pixels (width and height).
 pub const CHUNK_SIZE: u32 = 512;
@@ -133,15 +138,30 @@ impl WorldPos {
 
     (ChunkPos::new(cx, cy), LocalPos::new(lx, ly))
   }
+
+  /// Returns the normalized position within the containing chunk.
+  ///
+  /// Each axis is 0.0 at the chunk's left/bottom edge and approaches 1.0 at
+  /// its right/top edge, measured at the pixel's bottom-left corner. World
+  /// position -1 lies in chunk -1 at `(CHUNK_SIZE - 1) / CHUNK_SIZE`.
+  pub fn uv_in_chunk(self) -> Vec2 {
+    let (_, local) = self.to_chunk_and_local();
+    Vec2::new(local.x as f32, local.y as f32) / CHUNK_SIZE as f32
+  }
 }
 
 impl ChunkPos {
+  /// Returns the bottom-left pixel of the chunk in world coordinates.
+  pub const fn origin_world(self) -> WorldPos {
+    let chunk_size = CHUNK_SIZE as i64;
+    WorldPos::new(self.x as i64 * chunk_size, self.y as i64 * chunk_size)
+  }
+
   /// Convert chunk origin to world position.
   ///
-  /// Returns the bottom-left corner of the chunk in world coordinates.
+  /// Same as [`origin_world`](Self::origin_world).
   pub fn to_world(self) -> WorldPos {
-    let chunk_size = CHUNK_SIZE as i64;
-    WorldPos::new(self.x as i64 * chunk_size, self.y as i64 * chunk_size)
+    self.origin_world()
   }
 }
 
@@ -167,6 +187,12 @@ impl TilePos {
   pub const fn new(x: i64, y: i64) -> Self {
     Self { x, y }
   }
+
+  /// Returns the pixels covered by this tile in world coordinates.
+  pub const fn to_world_rect(self) -> WorldRect {
+    let tile_size = TILE_SIZE as i64;
+    WorldRect::new(self.x * tile_size, self.y * tile_size, TILE_SIZE, TILE_SIZE)
+  }
 }
 
 /// World-coordinate axis-aligned bounding box.
@@ -208,6 +234,14 @@ impl WorldRect {
       && pos.y < self.y + self.height as i64
   }
 
+  /// Returns true if the two rects overlap.
+  pub fn intersects(&self, other: &WorldRect) -> bool {
+    self.x < other.x + other.width as i64
+      && other.x < self.x + self.width as i64
+      && self.y < other.y + other.height as i64
+      && other.y < self.y + self.height as i64
+  }
+
   /// Returns the range of tile positions that overlap this rect.
   pub fn to_tile_range(&self) -> impl Iterator<Item = TilePos> {
     let tile_size = TILE_SIZE as i64;
@@ -228,8 +262,9 @@ impl WorldRect {
   /// pixels to iterate, or None if the tile doesn't overlap the rect.
   pub fn clip_tile(&self, tile: TilePos) -> Option<(u32, u32, u32, u32)> {
     let tile_size = TILE_SIZE as i64;
-    let tile_x_start = tile.x * tile_size;
-    let tile_y_start = tile.y * tile_size;
+    let tile_rect = tile.to_world_rect();
+    let tile_x_start = tile_rect.x;
+    let tile_y_start = tile_rect.y;
     let tile_x_end = tile_x_start + tile_size;
     let tile_y_end = tile_y_start + tile_size;
 
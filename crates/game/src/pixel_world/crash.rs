@@ -0,0 +1,253 @@
+//! Crash snapshots: dump recent world state when the game panics.
+//!
+//! [`CrashSnapshotPlugin`] keeps a recent [`SimulationSnapshot`] of the
+//! first `PixelWorld` and a short log of recent chunk changes, refreshed
+//! at a safe point after each frame's simulation. It installs a panic hook
+//! that, when a panic happens while the world's systems run (streaming,
+//! simulation and persistence), writes both to disk so the crash can be
+//! reproduced by restoring the snapshot into a fresh world.
+//!
+//! The hook never touches the ECS world, which may be mid-mutation when a
+//! system panics; it only reads what the plugin captured beforehand. It
+//! writes at most once per app, locks nothing it could wait on, and hands
+//! every panic on to the previously installed hook.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, TryLockError, Weak};
+
+use bevy::prelude::*;
+
+use crate::pixel_world::coords::ChunkPos;
+use crate::pixel_world::schedule::PixelWorldSet;
+use crate::pixel_world::world::{PixelWorld, SimulationSnapshot};
+
+/// Installs the crash snapshot panic hook and keeps its state fresh.
+///
+/// Add after `PixelWorldPlugin`:
+///
+/// ```ignore
+/// app.add_plugins(CrashSnapshotPlugin::at("crash.snapshot"));
+/// ```
+pub struct CrashSnapshotPlugin {
+  /// Snapshot file written on a panic. The log is written next to it, with
+  /// the `log` extension.
+  pub path: PathBuf,
+  /// Simulation ticks between snapshots. Lower catches state closer to the
+  /// crash at the cost of copying every loaded chunk more often.
+  pub interval: u64,
+  /// Number of recent log lines kept.
+  pub log_capacity: usize,
+}
+
+impl CrashSnapshotPlugin {
+  /// Writes crash snapshots to `path`, with default interval and log size.
+  pub fn at(path: impl Into<PathBuf>) -> Self {
+    Self {
+      path: path.into(),
+      interval: 60,
+      log_capacity: 256,
+    }
+  }
+}
+
+impl Plugin for CrashSnapshotPlugin {
+  fn build(&self, app: &mut App) {
+    let recorder = Arc::new(CrashRecorder {
+      path: self.path.clone(),
+      log_capacity: self.log_capacity,
+      armed: AtomicBool::new(false),
+      fired: AtomicBool::new(false),
+      state: Mutex::new(CrashState::default()),
+    });
+    install_hook(Arc::downgrade(&recorder));
+
+    app.insert_resource(CrashReporter {
+      recorder,
+      interval: self.interval.max(1),
+    });
+    app.add_systems(
+      Update,
+      (
+        arm_crash_snapshot.before(PixelWorldSet::PreSimulation),
+        (
+          record_chunk_changes,
+          capture_crash_snapshot,
+          disarm_crash_snapshot,
+        )
+          .chain()
+          .after(PixelWorldSet::PostSimulation),
+      ),
+    );
+  }
+}
+
+/// Handle to the crash snapshot state, inserted by [`CrashSnapshotPlugin`].
+#[derive(Resource)]
+pub struct CrashReporter {
+  recorder: Arc<CrashRecorder>,
+  interval: u64,
+}
+
+impl CrashReporter {
+  /// Appends a line to the crash log, e.g. a gameplay event that helps
+  /// reproduce a crash. The oldest line is dropped once the log is full.
+  pub fn record(&self, line: impl Into<String>) {
+    self.recorder.record(line.into());
+  }
+
+  /// Returns the tick of the snapshot a crash would dump, if one was taken.
+  pub fn snapshot_tick(&self) -> Option<u64> {
+    let state = self.recorder.state.lock().ok()?;
+    state.snapshot.as_ref().map(SimulationSnapshot::tick)
+  }
+
+  /// Returns the path of the log written next to a snapshot at `path`.
+  pub fn log_path(path: &Path) -> PathBuf {
+    path.with_extension("log")
+  }
+}
+
+/// State shared between the systems and the panic hook.
+struct CrashRecorder {
+  path: PathBuf,
+  log_capacity: usize,
+  /// True while the world's systems run this frame.
+  armed: AtomicBool,
+  /// Set by the first dump, so nested or later panics don't overwrite it.
+  fired: AtomicBool,
+  state: Mutex<CrashState>,
+}
+
+#[derive(Default)]
+struct CrashState {
+  snapshot: Option<SimulationSnapshot>,
+  log: VecDeque<String>,
+}
+
+impl CrashRecorder {
+  fn record(&self, line: String) {
+    let Ok(mut state) = self.state.lock() else {
+      return;
+    };
+    if state.log.len() >= self.log_capacity {
+      state.log.pop_front();
+    }
+    state.log.push_back(line);
+  }
+
+  /// Writes the snapshot and log. Runs inside the panic hook, so it must
+  /// not panic: every error is returned instead.
+  fn dump(&self, info: &PanicHookInfo<'_>) -> io::Result<()> {
+    // A panic while the systems held the lock leaves it poisoned, but the
+    // captured state is still whole; a held lock means we'd deadlock.
+    let state = match self.state.try_lock() {
+      Ok(state) => state,
+      Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+      Err(TryLockError::WouldBlock) => {
+        return Err(io::Error::new(
+          io::ErrorKind::WouldBlock,
+          "crash state is locked",
+        ));
+      }
+    };
+
+    if let Some(snapshot) = &state.snapshot {
+      let mut file = BufWriter::new(File::create(&self.path)?);
+      snapshot.write_to(&mut file)?;
+      file.flush()?;
+    }
+
+    let mut log = BufWriter::new(File::create(CrashReporter::log_path(&self.path))?);
+    writeln!(log, "panic: {info}")?;
+    match &state.snapshot {
+      Some(snapshot) => writeln!(log, "snapshot tick: {}", snapshot.tick())?,
+      None => writeln!(log, "snapshot tick: none")?,
+    }
+    for line in &state.log {
+      writeln!(log, "{line}")?;
+    }
+    log.flush()
+  }
+}
+
+/// Chains a hook that dumps `recorder` in front of the current one.
+///
+/// The hook holds a weak reference, so it goes quiet once the app that
+/// owns the recorder is dropped.
+fn install_hook(recorder: Weak<CrashRecorder>) {
+  let previous = std::panic::take_hook();
+  std::panic::set_hook(Box::new(move |info| {
+    if let Some(recorder) = recorder.upgrade()
+      && recorder.armed.load(Ordering::Acquire)
+      && !recorder.fired.swap(true, Ordering::AcqRel)
+    {
+      // Ignoring errors: there is nowhere safe to report them from here
+      let _ = recorder.dump(info);
+    }
+    previous(info);
+  }));
+}
+
+fn arm_crash_snapshot(reporter: Res<CrashReporter>) {
+  reporter.recorder.armed.store(true, Ordering::Release);
+}
+
+fn disarm_crash_snapshot(reporter: Res<CrashReporter>) {
+  reporter.recorder.armed.store(false, Ordering::Release);
+}
+
+/// Logs chunks whose content generation changed since the previous frame.
+fn record_chunk_changes(
+  reporter: Res<CrashReporter>,
+  worlds: Query<&PixelWorld>,
+  mut generations: Local<HashMap<ChunkPos, u64>>,
+) {
+  let Some(world) = worlds.iter().next() else {
+    return;
+  };
+  // Rebuilt every frame, so chunks that streamed out are forgotten
+  let previous = std::mem::take(&mut *generations);
+  for pos in world.visible_positions() {
+    let Some(generation) = world.chunk_generation(pos) else {
+      continue;
+    };
+    generations.insert(pos, generation);
+    if previous.get(&pos) != Some(&generation) {
+      reporter.record(format!(
+        "tick {}: chunk ({}, {}) modified, generation {generation}",
+        world.tick(),
+        pos.x,
+        pos.y
+      ));
+    }
+  }
+}
+
+/// Refreshes the snapshot every `interval` ticks, after the frame's
+/// simulation and persistence have finished.
+fn capture_crash_snapshot(
+  reporter: Res<CrashReporter>,
+  worlds: Query<&PixelWorld>,
+  mut last_tick: Local<Option<u64>>,
+) {
+  let Some(world) = worlds.iter().next() else {
+    return;
+  };
+  let tick = world.tick();
+  if last_tick.is_some_and(|last| tick.saturating_sub(last) < reporter.interval) {
+    return;
+  }
+  let snapshot = world.snapshot();
+  if snapshot.chunk_positions().next().is_none() {
+    return;
+  }
+  *last_tick = Some(tick);
+  if let Ok(mut state) = reporter.recorder.state.lock() {
+    state.snapshot = Some(snapshot);
+  }
+}
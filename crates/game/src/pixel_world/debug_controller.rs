@@ -3,8 +3,14 @@ use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
 use crate::pixel_world::collision::CollisionQueryPoint;
+use crate::pixel_world::debug_shim::DebugGizmos;
+use crate::pixel_world::material::Materials;
 use crate::pixel_world::pixel_camera::LogicalCameraPosition;
-use crate::pixel_world::{MaterialId, StreamingCamera, material_ids};
+use crate::pixel_world::simulation::hash::hash21uu64;
+use crate::pixel_world::{
+  DigConfig, MaterialId, Pixel, PixelWorld, SimulationState, StreamingCamera, WorldPos, WorldRect,
+  material_ids,
+};
 
 pub const MIN_RADIUS: u32 = 2;
 pub const MAX_RADIUS: u32 = 100;
@@ -41,6 +47,15 @@ pub struct BrushState {
   pub heat_painting: bool,
   /// Heat value to paint (0-255).
   pub heat_value: u8,
+  /// Fraction of the radius feathered at the rim (0.0 = hard edge, 1.0 =
+  /// feathered from the center). Pixels closer to the rim are less likely to
+  /// be painted.
+  pub softness: f32,
+  /// When true, RMB digs instead of erasing: part of the removed solid
+  /// terrain is left behind as loose, falling pixels.
+  pub dig_mode: bool,
+  /// How much dug terrain is knocked loose in dig mode.
+  pub dig: DigConfig,
   /// When false, brush painting is disabled (e.g., in level editor mode).
   pub enabled: bool,
 }
@@ -56,11 +71,112 @@ impl Default for BrushState {
       material: material_ids::SAND,
       heat_painting: false,
       heat_value: 100,
+      softness: 0.0,
+      dig_mode: false,
+      dig: DigConfig::default(),
       enabled: true,
     }
   }
 }
 
+impl BrushState {
+  /// Returns true if the brush centered at `center` paints `pos`.
+  ///
+  /// Inside the hard core every pixel is painted. In the feathered rim the
+  /// chance falls linearly to zero at the radius, rolled with per-position
+  /// noise so repeated strokes paint the same pixels.
+  pub fn covers(&self, center: (i64, i64), pos: WorldPos) -> bool {
+    const CH_SOFT_EDGE: u64 = 0xb7a5_5ed6_e000_0001;
+
+    let dx = (pos.x - center.0) as f32;
+    let dy = (pos.y - center.1) as f32;
+    let dist = (dx * dx + dy * dy).sqrt();
+    let radius = self.radius as f32;
+    if dist > radius {
+      return false;
+    }
+
+    let core = radius * (1.0 - self.softness.clamp(0.0, 1.0));
+    if dist <= core {
+      return true;
+    }
+
+    let chance = (radius - dist) / (radius - core);
+    let hash = hash21uu64(pos.x as u64 ^ CH_SOFT_EDGE, pos.y as u64);
+    let roll = (hash & 0xFFFF) as f32 / 65535.0;
+    roll < chance
+  }
+
+  /// Paints `pixel` over the brush footprint centered at `center`.
+  ///
+  /// Goes through [`PixelWorld::blit`], so painted pixels are marked for
+  /// simulation.
+  pub fn stamp(
+    &self,
+    world: &mut PixelWorld,
+    center: (i64, i64),
+    pixel: Pixel,
+    debug_gizmos: DebugGizmos<'_>,
+  ) {
+    let rect = WorldRect::centered(center.0, center.1, self.radius);
+    world.blit(
+      rect,
+      |frag| {
+        self
+          .covers(center, WorldPos::new(frag.x, frag.y))
+          .then_some(pixel)
+      },
+      debug_gizmos,
+    );
+  }
+
+  /// Paints `pixel` over the brush footprint centered at `center`, leaving
+  /// it at rest.
+  ///
+  /// Goes through [`PixelWorld::blit_settled`], so neither the painted
+  /// pixels nor their neighbors are woken. Used while the simulation is
+  /// paused with clean edits on.
+  pub fn stamp_settled(
+    &self,
+    world: &mut PixelWorld,
+    center: (i64, i64),
+    pixel: Pixel,
+    debug_gizmos: DebugGizmos<'_>,
+  ) {
+    let rect = WorldRect::centered(center.0, center.1, self.radius);
+    world.blit_settled(
+      rect,
+      |frag| {
+        self
+          .covers(center, WorldPos::new(frag.x, frag.y))
+          .then_some(pixel)
+      },
+      debug_gizmos,
+    );
+  }
+
+  /// Digs the brush footprint centered at `center` with the brush's
+  /// [`DigConfig`].
+  ///
+  /// Returns the number of pixels knocked loose.
+  pub fn dig(
+    &self,
+    world: &mut PixelWorld,
+    center: (i64, i64),
+    materials: &Materials,
+    debug_gizmos: DebugGizmos<'_>,
+  ) -> usize {
+    let rect = WorldRect::centered(center.0, center.1, self.radius);
+    world.dig(
+      rect,
+      |pos| self.covers(center, pos),
+      &self.dig,
+      materials,
+      debug_gizmos,
+    )
+  }
+}
+
 fn spawn_collision_query_point(mut commands: Commands) {
   commands.spawn((Transform::default(), CollisionQueryPoint));
 }
@@ -150,7 +266,9 @@ fn input_system(
 fn paint_system(
   brush: Res<BrushState>,
   ui_over: Option<Res<UiPointerState>>,
-  mut worlds: Query<&mut crate::pixel_world::PixelWorld>,
+  materials: Option<Res<Materials>>,
+  simulation: Option<Res<SimulationState>>,
+  mut worlds: Query<&mut PixelWorld>,
   gizmos: crate::pixel_world::debug_shim::GizmosParam,
 ) {
   if !brush.enabled {
@@ -178,40 +296,32 @@ fn paint_system(
     return;
   };
 
+  if brush.erasing
+    && brush.dig_mode
+    && let Some(materials) = materials
+  {
+    brush.dig(&mut world, (center_x, center_y), &materials, gizmos.get());
+    return;
+  }
+
   let (material, color) = if brush.erasing {
     (material_ids::VOID, crate::pixel_world::ColorIndex(0))
   } else {
     (brush.material, crate::pixel_world::ColorIndex(128))
   };
-  let brush_pixel = crate::pixel_world::Pixel::new(material, color);
-
-  let radius = brush.radius;
-  let radius_i64 = radius as i64;
-  let radius_sq = (radius_i64 * radius_i64) as f32;
-
-  let rect = crate::pixel_world::WorldRect::centered(center_x, center_y, radius);
+  let brush_pixel = Pixel::new(material, color);
 
-  world.blit(
-    rect,
-    |frag| {
-      let dx = frag.x - center_x;
-      let dy = frag.y - center_y;
-      let dist_sq = (dx * dx + dy * dy) as f32;
-
-      if dist_sq <= radius_sq {
-        Some(brush_pixel)
-      } else {
-        None
-      }
-    },
-    gizmos.get(),
-  );
+  if simulation.is_some_and(|s| s.settles_edits()) {
+    brush.stamp_settled(&mut world, (center_x, center_y), brush_pixel, gizmos.get());
+  } else {
+    brush.stamp(&mut world, (center_x, center_y), brush_pixel, gizmos.get());
+  }
 }
 
 fn heat_paint_system(
   brush: Res<BrushState>,
   ui_over: Option<Res<UiPointerState>>,
-  mut worlds: Query<&mut crate::pixel_world::PixelWorld>,
+  mut worlds: Query<&mut PixelWorld>,
 ) {
   if !brush.enabled {
     return;
@@ -237,7 +347,7 @@ fn heat_paint_system(
     for dx in -radius..=radius {
       let dist_sq = (dx * dx + dy * dy) as f32;
       if dist_sq <= radius_sq {
-        let pos = crate::pixel_world::WorldPos::new(center_x + dx, center_y + dy);
+        let pos = WorldPos::new(center_x + dx, center_y + dy);
         world.set_heat_at(pos, heat);
       }
     }
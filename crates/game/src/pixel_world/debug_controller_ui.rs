@@ -59,6 +59,38 @@ pub fn brush_controls_ui(
     changed = true;
   }
 
+  // Soft edge slider
+  ui.label(format!("Softness: {:.0}%", brush.softness * 100.0));
+  if ui
+    .add(egui::Slider::new(&mut brush.softness, 0.0..=1.0).show_value(false))
+    .changed()
+  {
+    changed = true;
+  }
+
+  ui.add_space(8.0);
+
+  // Dig mode toggle
+  if ui
+    .checkbox(&mut brush.dig_mode, "Dig (erase leaves loose pixels)")
+    .changed()
+  {
+    changed = true;
+  }
+
+  if brush.dig_mode {
+    ui.label(format!(
+      "Loose fraction: {:.0}%",
+      brush.dig.loose_fraction * 100.0
+    ));
+    if ui
+      .add(egui::Slider::new(&mut brush.dig.loose_fraction, 0.0..=1.0).show_value(false))
+      .changed()
+    {
+      changed = true;
+    }
+  }
+
   ui.add_space(8.0);
 
   // Heat painting toggle
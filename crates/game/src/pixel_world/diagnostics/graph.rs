@@ -122,3 +122,44 @@ pub fn time_series_graph(
     Color32::from_rgb(180, 180, 180), // Light gray for label
   );
 }
+
+/// Segment colors for [`pass_breakdown_bar`], cycled by pass index.
+const PASS_COLORS: [Color32; 5] = [
+  Color32::from_rgb(255, 150, 100),
+  Color32::from_rgb(255, 90, 60),
+  Color32::from_rgb(255, 210, 90),
+  Color32::from_rgb(200, 100, 255),
+  Color32::from_rgb(100, 255, 200),
+];
+
+/// Renders per-pass times as a single stacked bar with a legend.
+pub fn pass_breakdown_bar(ui: &mut egui::Ui, breakdown: &[(&str, f32)], size: Vec2) {
+  let total: f32 = breakdown.iter().map(|(_, ms)| ms).sum();
+  let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+  let rect = response.rect;
+
+  painter.rect_filled(rect, 2.0, Color32::from_rgb(30, 30, 35));
+
+  if total > 0.0 {
+    let mut x = rect.min.x;
+    for (i, &(_, ms)) in breakdown.iter().enumerate() {
+      let width = ms / total * rect.width();
+      painter.rect_filled(
+        egui::Rect::from_min_size(Pos2::new(x, rect.min.y), Vec2::new(width, rect.height())),
+        0.0,
+        PASS_COLORS[i % PASS_COLORS.len()],
+      );
+      x += width;
+    }
+  }
+
+  for (i, &(label, ms)) in breakdown.iter().enumerate() {
+    let text = format!("{:>6.2}ms  {}", ms, label);
+    ui.label(
+      egui::RichText::new(text)
+        .color(PASS_COLORS[i % PASS_COLORS.len()])
+        .monospace()
+        .size(9.0),
+    );
+  }
+}
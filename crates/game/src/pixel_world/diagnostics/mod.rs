@@ -4,8 +4,9 @@ mod time_series;
 
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, EguiPlugin, EguiPrimaryContextPass, egui};
+use graph::pass_breakdown_bar;
 pub use graph::{TimeSeriesGraphConfig, time_series_graph};
-pub use profiler::{ProfilerMetrics, profile};
+pub use profiler::{ProfilerMetrics, ProfilerSample, profile};
 pub use time_series::TimeSeries;
 
 const SAMPLE_CAPACITY: usize = 300;
@@ -63,6 +64,68 @@ impl Default for CollisionMetrics {
   }
 }
 
+/// Which panels the diagnostics overlay shows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagnosticsPanels {
+  pub frame_time: bool,
+  pub fps: bool,
+  pub simulation: bool,
+  pub upload: bool,
+  pub collision: bool,
+  pub pass_breakdown: bool,
+  pub slowest: bool,
+}
+
+impl Default for DiagnosticsPanels {
+  fn default() -> Self {
+    Self {
+      frame_time: true,
+      fps: true,
+      simulation: true,
+      upload: true,
+      collision: true,
+      pass_breakdown: true,
+      slowest: true,
+    }
+  }
+}
+
+/// Controls the diagnostics overlay.
+///
+/// Metrics are collected regardless; this only affects the egui window.
+/// Set `visible` to false (e.g. in release builds) to hide it until the
+/// toggle key is pressed.
+#[derive(Resource, Clone, Debug)]
+pub struct DiagnosticsConfig {
+  /// Whether the overlay is shown.
+  pub visible: bool,
+  /// Key that toggles `visible`. `None` disables the toggle.
+  pub toggle_key: Option<KeyCode>,
+  /// Screen corner or edge the window is anchored to.
+  pub anchor: egui::Align2,
+  /// Offset from the anchor in screen points.
+  pub offset: egui::Vec2,
+  /// Which panels are shown.
+  pub panels: DiagnosticsPanels,
+}
+
+impl Default for DiagnosticsConfig {
+  fn default() -> Self {
+    Self {
+      visible: true,
+      toggle_key: Some(KeyCode::F3),
+      anchor: egui::Align2::RIGHT_TOP,
+      offset: egui::vec2(-10.0, 10.0),
+      panels: DiagnosticsPanels::default(),
+    }
+  }
+}
+
+/// Run condition: Returns true while the diagnostics overlay is visible.
+pub fn diagnostics_visible(config: Res<DiagnosticsConfig>) -> bool {
+  config.visible
+}
+
 pub struct DiagnosticsPlugin;
 
 impl Plugin for DiagnosticsPlugin {
@@ -75,11 +138,29 @@ impl Plugin for DiagnosticsPlugin {
       .init_resource::<SimulationMetrics>()
       .init_resource::<CollisionMetrics>()
       .init_resource::<ProfilerMetrics>()
+      .init_resource::<DiagnosticsConfig>()
       .add_systems(
         First,
         (profiler::aggregate_profiler_samples, collect_frame_metrics).chain(),
       )
-      .add_systems(EguiPrimaryContextPass, render_diagnostics_ui);
+      .add_systems(PreUpdate, toggle_diagnostics)
+      .add_systems(
+        EguiPrimaryContextPass,
+        render_diagnostics_ui.run_if(diagnostics_visible),
+      );
+  }
+}
+
+/// Flips [`DiagnosticsConfig::visible`] when the toggle key is pressed.
+pub fn toggle_diagnostics(
+  keys: Option<Res<ButtonInput<KeyCode>>>,
+  mut config: ResMut<DiagnosticsConfig>,
+) {
+  let (Some(keys), Some(key)) = (keys, config.toggle_key) else {
+    return;
+  };
+  if keys.just_pressed(key) {
+    config.visible = !config.visible;
   }
 }
 
@@ -98,6 +179,7 @@ fn collect_frame_metrics(time: Res<Time>, mut metrics: ResMut<FrameTimeMetrics>)
 
 fn render_diagnostics_ui(
   mut contexts: EguiContexts,
+  config: Res<DiagnosticsConfig>,
   mut metrics: ResMut<FrameTimeMetrics>,
   mut sim_metrics: ResMut<SimulationMetrics>,
   mut collision_metrics: ResMut<CollisionMetrics>,
@@ -106,14 +188,19 @@ fn render_diagnostics_ui(
   let Ok(ctx) = contexts.ctx_mut() else {
     return;
   };
-  egui::Window::new("Diagnostics")
-    .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
-    .default_width(220.0)
-    .title_bar(false)
-    .resizable(false)
-    .movable(false)
-    .frame(egui::Frame::NONE.fill(egui::Color32::from_rgba_unmultiplied(20, 20, 25, 200)))
-    .show(ctx, |ui| {
+  let panels = &config.panels;
+  diagnostics_window(&config).show(ctx, |ui| {
+    // Space goes between panels, not before the first one
+    let mut first = true;
+    let mut space = |ui: &mut egui::Ui, amount: f32| {
+      if !first {
+        ui.add_space(amount);
+      }
+      first = false;
+    };
+
+    if panels.frame_time {
+      space(ui, 4.0);
       time_series_graph(
         ui,
         &mut metrics.frame_time,
@@ -124,9 +211,10 @@ fn render_diagnostics_ui(
           ..Default::default()
         },
       );
+    }
 
-      ui.add_space(4.0);
-
+    if panels.fps {
+      space(ui, 4.0);
       time_series_graph(
         ui,
         &mut metrics.fps,
@@ -138,9 +226,10 @@ fn render_diagnostics_ui(
           ..Default::default()
         },
       );
+    }
 
-      ui.add_space(4.0);
-
+    if panels.simulation {
+      space(ui, 4.0);
       time_series_graph(
         ui,
         &mut sim_metrics.sim_time,
@@ -151,9 +240,10 @@ fn render_diagnostics_ui(
           ..Default::default()
         },
       );
+    }
 
-      ui.add_space(4.0);
-
+    if panels.upload {
+      space(ui, 4.0);
       time_series_graph(
         ui,
         &mut sim_metrics.upload_time,
@@ -164,9 +254,10 @@ fn render_diagnostics_ui(
           ..Default::default()
         },
       );
+    }
 
-      ui.add_space(4.0);
-
+    if panels.collision {
+      space(ui, 4.0);
       time_series_graph(
         ui,
         &mut collision_metrics.generation_time,
@@ -177,30 +268,65 @@ fn render_diagnostics_ui(
           ..Default::default()
         },
       );
+    }
+
+    // Per-pass breakdown widget
+    if panels.pass_breakdown {
+      space(ui, 8.0);
+
+      ui.label(
+        egui::RichText::new("Pass Breakdown")
+          .color(egui::Color32::from_rgb(180, 180, 180))
+          .monospace()
+          .size(10.0),
+      );
+
+      ui.add_space(2.0);
+
+      pass_breakdown_bar(
+        ui,
+        &profiler_metrics.pass_breakdown(),
+        egui::Vec2::new(200.0, 10.0),
+      );
+    }
+
+    // Slowest samples widget
+    let slowest = profiler_metrics.slowest();
+    if panels.slowest && !slowest.is_empty() {
+      space(ui, 8.0);
+
+      ui.label(
+        egui::RichText::new("Slowest This Frame")
+          .color(egui::Color32::from_rgb(180, 180, 180))
+          .monospace()
+          .size(10.0),
+      );
 
-      // Slowest samples widget
-      let slowest = profiler_metrics.slowest();
-      if !slowest.is_empty() {
-        ui.add_space(8.0);
+      ui.add_space(2.0);
 
+      for sample in slowest {
+        let text = format!("{:>6.2}ms  {}", sample.time_ms, sample.tag);
         ui.label(
-          egui::RichText::new("Slowest This Frame")
-            .color(egui::Color32::from_rgb(180, 180, 180))
+          egui::RichText::new(text)
+            .color(egui::Color32::from_rgb(255, 255, 180))
             .monospace()
-            .size(10.0),
+            .size(9.0),
         );
-
-        ui.add_space(2.0);
-
-        for sample in slowest {
-          let text = format!("{:>6.2}ms  {}", sample.time_ms, sample.tag);
-          ui.label(
-            egui::RichText::new(text)
-              .color(egui::Color32::from_rgb(255, 255, 180))
-              .monospace()
-              .size(9.0),
-          );
-        }
       }
-    });
+    }
+  });
+}
+
+/// Builds the overlay window, placed according to `config`.
+///
+/// Custom panels can use this to match the built-in overlay's placement.
+pub fn diagnostics_window(config: &DiagnosticsConfig) -> egui::Window<'static> {
+  egui::Window::new("Diagnostics")
+    .anchor(config.anchor, config.offset)
+    .default_width(220.0)
+    .title_bar(false)
+    .resizable(false)
+    .movable(false)
+    .frame(egui::Frame::NONE.fill(egui::Color32::from_rgba_unmultiplied(20, 20, 25, 200)))
 }
+
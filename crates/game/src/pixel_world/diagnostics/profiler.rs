@@ -1,6 +1,7 @@
 //! Lightweight in-app profiler for tracking named spans.
 //!
-//! Accumulates samples over 1 second, showing the worst (max) time per tag.
+//! Accumulates samples over 1 second, showing the worst (max) time per tag
+//! and the average per-frame time of each simulation pass.
 
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -9,6 +10,15 @@ use bevy::prelude::*;
 // WASM compat: std::time::Instant panics on wasm32
 use web_time::Instant;
 
+/// Simulation passes shown in the breakdown, as (label, span tag).
+const PASSES: [(&str, &str); 5] = [
+  ("physics", "physics"),
+  ("burning", "burning"),
+  ("heat", "heat"),
+  ("upload", "upload_chunks"),
+  ("collision", "collision_dispatch"),
+];
+
 /// A single profiler sample.
 #[derive(Clone, Copy)]
 pub struct ProfilerSample {
@@ -30,6 +40,12 @@ pub struct ProfilerMetrics {
   update_interval_secs: f32,
   /// Max entries to display.
   capacity: usize,
+  /// Displayed average milliseconds per frame for each entry in `PASSES`.
+  breakdown: [f32; PASSES.len()],
+  /// Accumulator: summed milliseconds per pass since last display update.
+  pass_totals: [f32; PASSES.len()],
+  /// Frames recorded since last display update.
+  frames: u32,
 }
 
 impl Default for ProfilerMetrics {
@@ -40,23 +56,59 @@ impl Default for ProfilerMetrics {
       last_update: Instant::now(),
       update_interval_secs: 1.0,
       capacity: 10,
+      breakdown: [0.0; PASSES.len()],
+      pass_totals: [0.0; PASSES.len()],
+      frames: 0,
     }
   }
 }
 
 impl ProfilerMetrics {
+  /// Creates metrics that refresh the display every `secs` seconds.
+  pub fn with_update_interval(secs: f32) -> Self {
+    Self {
+      update_interval_secs: secs,
+      ..Default::default()
+    }
+  }
+
   /// Returns the slowest samples, sorted by time descending.
   pub fn slowest(&self) -> &[ProfilerSample] {
     &self.display
   }
 
-  /// Accumulates a sample, keeping max time per tag.
+  /// Returns the average milliseconds per frame spent in each simulation
+  /// pass, in a fixed pass order.
+  ///
+  /// Passes that ran several times in a frame (multiple ticks) are summed.
+  pub fn pass_breakdown(&self) -> Vec<(&'static str, f32)> {
+    PASSES
+      .iter()
+      .zip(self.breakdown)
+      .map(|(&(label, _), time_ms)| (label, time_ms))
+      .collect()
+  }
+
+  /// Records all samples collected during one frame.
+  pub fn record_frame(&mut self, samples: impl IntoIterator<Item = ProfilerSample>) {
+    for sample in samples {
+      self.accumulate(sample);
+    }
+    self.frames += 1;
+    self.maybe_refresh_display();
+  }
+
+  /// Accumulates a sample, keeping max time per tag and summing pass time.
   fn accumulate(&mut self, sample: ProfilerSample) {
     self
       .accumulator
       .entry(sample.tag)
       .and_modify(|max| *max = max.max(sample.time_ms))
       .or_insert(sample.time_ms);
+
+    if let Some(i) = PASSES.iter().position(|&(_, tag)| tag == sample.tag) {
+      self.pass_totals[i] += sample.time_ms;
+    }
   }
 
   /// Checks if it's time to refresh the display, and if so, rebuilds it from
@@ -81,6 +133,13 @@ impl ProfilerMetrics {
     // Truncate to capacity
     self.display.truncate(self.capacity);
 
+    let frames = self.frames.max(1) as f32;
+    for (shown, total) in self.breakdown.iter_mut().zip(&mut self.pass_totals) {
+      *shown = *total / frames;
+      *total = 0.0;
+    }
+    self.frames = 0;
+
     // Reset accumulator and timer
     self.accumulator.clear();
     self.last_update = Instant::now();
@@ -130,11 +189,6 @@ thread_local! {
 /// Accumulates samples each frame, updating the display every second.
 pub fn aggregate_profiler_samples(mut metrics: ResMut<ProfilerMetrics>) {
   FRAME_SAMPLES.with(|samples| {
-    let mut samples = samples.borrow_mut();
-    for sample in samples.drain(..) {
-      metrics.accumulate(sample);
-    }
+    metrics.record_frame(samples.borrow_mut().drain(..));
   });
-
-  metrics.maybe_refresh_display();
 }
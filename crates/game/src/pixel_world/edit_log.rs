@@ -0,0 +1,418 @@
+//! Edit logs: record the external mutations of a world and replay them.
+//!
+//! [`EditRecorderPlugin`] keeps an [`EditLog`] of the external mutations of
+//! a world and of every move of the [`StreamingCamera`], each stamped with
+//! the tick it happened in: the number of app updates since the plugin was
+//! added. Edits are logged where they reach the world, whether queued as
+//! [`WorldEdit`] commands or made by game code:
+//!
+//! - every [`PixelWorld::blit`] and [`PixelWorld::blit_settled`], which covers
+//!   brushes and digging, with the pixels it wrote
+//! - every [`SpawnPixelBody`], with the [`Bomb`] it was spawned with
+//! - every detonation of a [`Bomb`] that is not a pixel body; bomb bodies
+//!   replay through their spawn and the simulation
+//!
+//! [`replay`] re-applies them in order to a fresh app whose world was
+//! spawned with the same seeder and config, running the same number of
+//! updates, so a deterministic simulation ends in exactly the recorded state
+//! (compare with [`PixelWorld::state_hash`]).
+//!
+//! Replay applies an edit before the update of its tick, so edits queued
+//! between updates or early in a frame, before the simulation runs, are
+//! reproduced exactly. Logs are saved as TOML, so a bug report can ship the
+//! log that reproduces it.
+
+use std::io;
+use std::path::Path;
+
+use bevy::ecs::system::Command;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::pixel_world::StreamingCamera;
+use crate::pixel_world::coords::{ColorIndex, MaterialId, WorldFragment, WorldRect};
+use crate::pixel_world::debug_shim::DebugGizmos;
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::pixel_body::{Bomb, SpawnPixelBody};
+use crate::pixel_world::world::PixelWorld;
+
+/// Encoded blit pixel that leaves the world pixel unchanged.
+const UNCHANGED: i64 = -1;
+
+/// An external mutation of a world that can be recorded and replayed.
+///
+/// Queue edits as commands to apply them; while an [`EditRecorder`] exists,
+/// they are logged like any other edit reaching the world.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WorldEdit {
+  /// Pixels written into a world rect, row by row from its bottom-left
+  /// corner. Each pixel is encoded as its four bytes in little-endian
+  /// order, or -1 to leave the world pixel unchanged.
+  Blit {
+    x: i64,
+    y: i64,
+    width: u32,
+    height: u32,
+    pixels: Vec<i64>,
+    /// Whether the pixels were left at rest, like
+    /// [`PixelWorld::blit_settled`].
+    #[serde(default)]
+    settled: bool,
+  },
+  /// A pixel body spawned from an image asset, like [`SpawnPixelBody`].
+  SpawnBody {
+    path: String,
+    material: u8,
+    position: [f32; 2],
+    /// Area searched for a free position, as `[x, y, width, height]`.
+    #[serde(default)]
+    search_rect: Option<[i64; 4]>,
+    /// Bomb the body was spawned with.
+    #[serde(default)]
+    bomb: Option<LoggedBomb>,
+  },
+  /// A bomb detonated at a world position.
+  Detonate {
+    position: [f32; 2],
+    bomb: LoggedBomb,
+  },
+  /// The [`StreamingCamera`] moved to a world position.
+  MoveCamera { position: [f32; 2] },
+}
+
+impl WorldEdit {
+  /// Creates a blit edit by evaluating `f` over `rect`, like
+  /// [`PixelWorld::blit`].
+  ///
+  /// The written pixels are captured when the edit is created, so replaying
+  /// it does not need `f`.
+  pub fn blit(rect: WorldRect, f: impl Fn(WorldFragment) -> Option<Pixel>) -> Self {
+    Self::capture_blit(rect, f, false)
+  }
+
+  /// Captures a blit, settled like [`PixelWorld::blit_settled`] if
+  /// `settled` is set.
+  pub(crate) fn capture_blit(
+    rect: WorldRect,
+    f: impl Fn(WorldFragment) -> Option<Pixel>,
+    settled: bool,
+  ) -> Self {
+    let w_recip = 1.0 / rect.width.max(1) as f32;
+    let h_recip = 1.0 / rect.height.max(1) as f32;
+    let mut pixels = Vec::with_capacity(rect.width as usize * rect.height as usize);
+    for dy in 0..rect.height as i64 {
+      for dx in 0..rect.width as i64 {
+        let frag = WorldFragment {
+          x: rect.x + dx,
+          y: rect.y + dy,
+          u: dx as f32 * w_recip,
+          v: dy as f32 * h_recip,
+        };
+        pixels.push(f(frag).map_or(UNCHANGED, encode_pixel));
+      }
+    }
+    Self::Blit {
+      x: rect.x,
+      y: rect.y,
+      width: rect.width,
+      height: rect.height,
+      pixels,
+      settled,
+    }
+  }
+
+  /// Creates an edit spawning a pixel body from the image at `path`.
+  pub fn spawn_body(path: impl Into<String>, material: MaterialId, position: Vec2) -> Self {
+    Self::SpawnBody {
+      path: path.into(),
+      material: material.0,
+      position: position.to_array(),
+      search_rect: None,
+      bomb: None,
+    }
+  }
+
+  /// Captures a body spawn and the bomb it was spawned with.
+  pub(crate) fn capture_spawn(spawn: &SpawnPixelBody, bomb: Option<LoggedBomb>) -> Self {
+    Self::SpawnBody {
+      path: spawn.path.clone(),
+      material: spawn.material.0,
+      position: spawn.position.to_array(),
+      search_rect: spawn
+        .search_rect
+        .map(|rect| [rect.x, rect.y, rect.width as i64, rect.height as i64]),
+      bomb,
+    }
+  }
+
+  /// Creates an edit detonating a bomb with the blast of `bomb` at
+  /// `position`.
+  pub fn detonate(position: Vec2, bomb: &Bomb) -> Self {
+    Self::Detonate {
+      position: position.to_array(),
+      bomb: LoggedBomb::from(bomb),
+    }
+  }
+
+  /// Applies the edit to the ECS world.
+  ///
+  /// Blits go to the first [`PixelWorld`]; detonations need
+  /// `PixelBodiesPlugin` to go off. While an [`EditRecorder`] exists, the
+  /// edit is logged by the world, spawn or detonation it goes through.
+  pub fn apply_to(&self, world: &mut World) {
+    match self {
+      Self::Blit {
+        x,
+        y,
+        width,
+        height,
+        pixels,
+        settled,
+      } => {
+        let mut query = world.query::<&mut PixelWorld>();
+        let Some(mut pixel_world) = query.iter_mut(world).next() else {
+          return;
+        };
+        let rect = WorldRect::new(*x, *y, *width, *height);
+        let f = |frag: WorldFragment| {
+          let index = (frag.y - y) as usize * *width as usize + (frag.x - x) as usize;
+          pixels.get(index).copied().and_then(decode_pixel)
+        };
+        if *settled {
+          pixel_world.blit_settled(rect, f, DebugGizmos::none());
+        } else {
+          pixel_world.blit(rect, f, DebugGizmos::none());
+        }
+      }
+      Self::SpawnBody {
+        path,
+        material,
+        position,
+        search_rect,
+        bomb,
+      } => {
+        let mut spawn = SpawnPixelBody::new(
+          path.clone(),
+          MaterialId(*material),
+          Vec2::from_array(*position),
+        );
+        if let Some([x, y, width, height]) = *search_rect {
+          spawn = spawn.find_free_space(WorldRect::new(x, y, width as u32, height as u32));
+        }
+        if let Some(bomb) = *bomb {
+          spawn = spawn.with_extra(move |entity| {
+            entity.insert(bomb.to_bomb(false));
+          });
+        }
+        spawn.apply(world);
+      }
+      Self::Detonate { position, bomb } => {
+        let translation = Vec2::from_array(*position).extend(0.0);
+        world.spawn((
+          bomb.to_bomb(true),
+          Transform::from_translation(translation),
+          GlobalTransform::from_translation(translation),
+        ));
+      }
+      Self::MoveCamera { position } => {
+        let mut query = world.query_filtered::<&mut Transform, With<StreamingCamera>>();
+        if let Some(mut transform) = query.iter_mut(world).next() {
+          transform.translation = Vec2::from_array(*position).extend(transform.translation.z);
+        }
+      }
+    }
+  }
+}
+
+impl Command for WorldEdit {
+  fn apply(self, world: &mut World) {
+    self.apply_to(world);
+  }
+}
+
+/// Settings of a recorded [`Bomb`].
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoggedBomb {
+  pub damage_threshold: f32,
+  pub blast_radius: f32,
+  pub blast_strength: f32,
+  pub ignites: bool,
+  pub thermal_radius: f32,
+}
+
+impl From<&Bomb> for LoggedBomb {
+  fn from(bomb: &Bomb) -> Self {
+    Self {
+      damage_threshold: bomb.damage_threshold,
+      blast_radius: bomb.blast_radius,
+      blast_strength: bomb.blast_strength,
+      ignites: bomb.ignites,
+      thermal_radius: bomb.thermal_radius,
+    }
+  }
+}
+
+impl LoggedBomb {
+  fn to_bomb(self, detonated: bool) -> Bomb {
+    Bomb {
+      damage_threshold: self.damage_threshold,
+      blast_radius: self.blast_radius,
+      blast_strength: self.blast_strength,
+      ignites: self.ignites,
+      thermal_radius: self.thermal_radius,
+      detonated,
+    }
+  }
+}
+
+fn encode_pixel(pixel: Pixel) -> i64 {
+  u32::from_le_bytes([
+    pixel.material.0,
+    pixel.color.0,
+    pixel.damage,
+    pixel.flags_bits(),
+  ]) as i64
+}
+
+fn decode_pixel(encoded: i64) -> Option<Pixel> {
+  let [material, color, damage, flags] = u32::try_from(encoded).ok()?.to_le_bytes();
+  Some(Pixel {
+    material: MaterialId(material),
+    color: ColorIndex(color),
+    damage,
+    flags: PixelFlags::from_bits_truncate(flags),
+  })
+}
+
+/// A recorded edit and the tick it happened in.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoggedEdit {
+  /// App updates completed before the edit.
+  pub tick: u64,
+  /// The edit.
+  pub edit: WorldEdit,
+}
+
+/// Recorded edits of a run, in the order they happened.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditLog {
+  /// App updates the recording spans.
+  pub ticks: u64,
+  /// Edits ordered by tick.
+  pub entries: Vec<LoggedEdit>,
+}
+
+impl EditLog {
+  /// Writes the log to `path` as TOML.
+  pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    let text = toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    std::fs::write(path, text)
+  }
+
+  /// Reads a log written by [`save`](Self::save).
+  pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+    let text = std::fs::read_to_string(path)?;
+    toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+  }
+}
+
+/// Records world edits, inserted by [`EditRecorderPlugin`].
+#[derive(Resource, Default)]
+pub struct EditRecorder {
+  log: EditLog,
+  /// Last recorded camera position.
+  camera: Option<Vec2>,
+}
+
+impl EditRecorder {
+  /// Appends an edit at the current tick.
+  fn record(&mut self, edit: WorldEdit) {
+    self.log.entries.push(LoggedEdit {
+      tick: self.log.ticks,
+      edit,
+    });
+  }
+
+  /// Returns the current tick: the number of app updates recorded so far.
+  pub fn tick(&self) -> u64 {
+    self.log.ticks
+  }
+
+  /// Returns the log recorded so far.
+  pub fn log(&self) -> &EditLog {
+    &self.log
+  }
+}
+
+/// Records world edits and camera moves into an [`EditRecorder`].
+///
+/// Needs `PixelBodiesPlugin` for body spawns and detonations to be logged.
+///
+/// Add together with `PixelWorldPlugin`, before spawning the world, so the
+/// recording covers the whole run:
+///
+/// ```ignore
+/// app.add_plugins(EditRecorderPlugin);
+/// // ... later
+/// app.world().resource::<EditRecorder>().log().save("bug.edits.toml")?;
+/// ```
+pub struct EditRecorderPlugin;
+
+impl Plugin for EditRecorderPlugin {
+  fn build(&self, app: &mut App) {
+    app.init_resource::<EditRecorder>();
+    app.add_systems(First, attach_edit_recorder);
+    app.add_systems(Last, advance_edit_recorder);
+  }
+}
+
+/// System: Starts buffering the edits of newly spawned worlds.
+fn attach_edit_recorder(mut worlds: Query<&mut PixelWorld, Added<PixelWorld>>) {
+  for mut world in &mut worlds {
+    world.record_edits();
+  }
+}
+
+/// System: Logs the edits and camera moves made this update and advances
+/// the tick.
+fn advance_edit_recorder(
+  mut recorder: ResMut<EditRecorder>,
+  mut worlds: Query<&mut PixelWorld>,
+  cameras: Query<&Transform, With<StreamingCamera>>,
+) {
+  for mut world in &mut worlds {
+    for edit in world.take_recorded_edits() {
+      recorder.record(edit);
+    }
+  }
+  if let Some(transform) = cameras.iter().next() {
+    let position = transform.translation.truncate();
+    if recorder.camera != Some(position) {
+      recorder.camera = Some(position);
+      recorder.record(WorldEdit::MoveCamera {
+        position: position.to_array(),
+      });
+    }
+  }
+  recorder.log.ticks += 1;
+}
+
+/// Replays `log` into `app`.
+///
+/// `app` must be set up like the recorded one, with its world spawn queued
+/// but no updates run yet. Runs `log.ticks` updates, applying each edit
+/// right before the update of its tick.
+pub fn replay(log: &EditLog, app: &mut App) {
+  let mut entries = log.entries.iter().peekable();
+  for tick in 0..log.ticks {
+    while let Some(entry) = entries.next_if(|entry| entry.tick <= tick) {
+      entry.edit.apply_to(app.world_mut());
+    }
+    app.update();
+  }
+  // Edits queued after the last recorded update
+  for entry in entries {
+    entry.edit.apply_to(app.world_mut());
+  }
+}
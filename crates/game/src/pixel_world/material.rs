@@ -4,7 +4,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::pixel_world::coords::MaterialId;
+use crate::pixel_world::coords::{ColorIndex, MaterialId};
+use crate::pixel_world::pixel::{MAX_CRACK, MAX_SATURATION, Pixel, PixelFlags};
 use crate::pixel_world::render::{Rgba, rgb};
 
 /// What happens to a pixel under a given effect (burning, detonation, etc.).
@@ -26,7 +27,98 @@ pub struct MaterialEffects {
   pub on_burn: Option<(PixelEffect, f32)>,
   /// How much blast strength this material absorbs per pixel.
   /// Higher = harder to blast through. 0 = no resistance (void/air).
+  /// A blast only removes the pixel if the ray still carries at least this
+  /// much energy; otherwise the pixel survives and the ray stops.
   pub blast_resistance: f32,
+  /// Blasts never remove this material and stop at it, shielding pixels
+  /// behind (bedrock, reinforced walls).
+  pub indestructible: bool,
+  /// Hits from blasts or digging a solid pixel of this material takes
+  /// before it breaks, cracking further with each hit (0 or 1 = breaks on
+  /// the first hit, at most 16). Cracks are kept in `Pixel::damage`, so
+  /// durability is ignored for absorbent and ephemeral materials.
+  pub durability: u8,
+  /// Heat released into the heat layer by each burning pixel, on top of
+  /// `HeatConfig::burning_heat`. Positive = exothermic, negative =
+  /// endothermic (absorbs heat from its cell). 0 = none.
+  pub reaction_heat: i16,
+}
+
+/// Side of a pixel checked by a [`ContextVariant`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NeighborSide {
+  Above,
+  Below,
+  Left,
+  Right,
+}
+
+impl NeighborSide {
+  /// Returns the world offset of the neighbor on this side (+y is up).
+  #[must_use]
+  pub const fn offset(self) -> (i64, i64) {
+    match self {
+      NeighborSide::Above => (0, 1),
+      NeighborSide::Below => (0, -1),
+      NeighborSide::Left => (-1, 0),
+      NeighborSide::Right => (1, 0),
+    }
+  }
+}
+
+/// Cosmetic rule recoloring a pixel by its neighbor's material.
+///
+/// Applied by the variant pass, which only changes color indices and never
+/// materials (e.g. soil with air above it shows a grass color).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContextVariant {
+  /// Side of the pixel the neighbor is on.
+  pub side: NeighborSide,
+  /// Neighbor material that triggers the rule. `None` matches air (void or
+  /// gas).
+  pub neighbor: Option<MaterialId>,
+  /// Color index given to matching pixels.
+  pub color: ColorIndex,
+}
+
+/// Most depth ramp stops of a material the chunk shader draws; further
+/// stops are ignored.
+pub const MAX_DEPTH_STOPS: usize = 8;
+
+/// Stop of a liquid depth ramp.
+///
+/// Drawn by the chunk shader: a liquid pixel at least `depth` pixels below
+/// the surface of its body takes `color`, unless a deeper stop also applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DepthColor {
+  /// Same-material liquid pixels above the pixel.
+  pub depth: u8,
+  /// Color index given to pixels at least this deep.
+  pub color: ColorIndex,
+}
+
+/// Liquid storage of an absorbent material (sponges).
+///
+/// Applied by the absorption pass: a pixel soaks up touching pixels of
+/// `liquid` until it holds `capacity` of them, wicks them through touching
+/// pixels of its material, and drips them into the air below once no liquid
+/// touches it. The stored amount is kept in `Pixel::damage` (see
+/// [`Pixel::saturation`]).
+///
+/// [`Pixel::saturation`]: crate::pixel_world::Pixel::saturation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Absorbent {
+  /// Liquid soaked up and released.
+  pub liquid: MaterialId,
+  /// Liquid pixels one pixel can hold, at most
+  /// [`MAX_SATURATION`](crate::pixel_world::pixel::MAX_SATURATION).
+  pub capacity: u8,
+  /// Liquid pixels soaked up, or wicked from a neighbor, per second.
+  pub absorb_rate: f32,
+  /// Stored pixels dripped per second into the air below. A pixel squeezed
+  /// by a pixel body releases one every pass.
+  pub release_rate: f32,
 }
 
 /// Physics state determines movement behavior.
@@ -60,14 +152,128 @@ pub struct Material {
   /// Air drift: 1/N chance to drift horizontally while falling (0 =
   /// disabled).
   pub air_drift: u8,
+  /// Extra pixels a wet powder can slide sideways before settling (0 =
+  /// wetness has no effect). Dry piles settle at 45°; wet ones at a slope
+  /// of one pixel down per `1 + wet_slip` across.
+  pub wet_slip: u8,
+  /// How far a pixel body passing over a solid or powder pixel of this
+  /// material pushes its color index toward the end of the palette, once,
+  /// leaving tracks (0 = hard, takes no imprints). Imprinted pixels are
+  /// marked in `Pixel::damage`, so softness is ignored for ephemeral
+  /// materials.
+  pub softness: u8,
+  /// Priority when several pixels want to move into the same cell in one
+  /// tick. The highest priority wins regardless of iteration order; equal
+  /// priorities fall back to iteration order.
+  pub swap_priority: u8,
   /// Heat level at which this material ignites (0 = non-flammable).
   pub ignition_threshold: u8,
   /// Heat emitted to the heat layer by this material (0 = none).
   pub base_temperature: u8,
+  /// How readily heat diffuses through this material (0.0 = insulator,
+  /// 1.0 = full diffusion).
+  pub thermal_conductivity: f32,
+  /// Chance per second that a pixel of this liquid bordering air evaporates
+  /// and vanishes (0 = never).
+  pub evaporation_chance: f32,
+  /// How strongly heat speeds up evaporation: the chance is scaled by
+  /// `1 + evaporation_heat_coupling * heat / 255`, using the pixel's heat
+  /// cell (0 = heat has no effect).
+  pub evaporation_heat_coupling: f32,
+  /// Ticks a pixel of this material lives before vanishing (0 = permanent).
+  /// Ephemeral pixels count their age in `Pixel::damage` and walk their
+  /// palette from the first entry to the last as they age.
+  pub lifetime: u8,
+  /// Material left in the cell a pixel of this material vacates each time
+  /// it falls into air (None = no trail). Usually an ephemeral material.
+  pub trail: Option<MaterialId>,
+  /// Context rules recoloring pixels of this material by their neighbors,
+  /// checked in order; the first match wins (empty = never recolored).
+  pub variants: Vec<ContextVariant>,
+  /// Stops recoloring liquid pixels of this material by depth, sorted by
+  /// depth; the deepest stop a pixel reaches wins (empty = never
+  /// recolored). Only the first [`MAX_DEPTH_STOPS`] are drawn, and depths
+  /// saturate at [`MAX_DEPTH`](crate::pixel_world::pixel::MAX_DEPTH). The
+  /// depth is kept in `Pixel::damage`, so ramps are ignored for ephemeral
+  /// materials.
+  pub depth_ramp: Vec<DepthColor>,
+  /// Fraction per second by which a liquid pixel of this material moves its
+  /// color toward the rendered colors of touching pixels of other miscible
+  /// liquids (0.0 = never mixes), using the nearest shade of its own
+  /// palette. Only colors blend; materials stay put. Ignored for ephemeral
+  /// materials.
+  pub color_mix_rate: f32,
+  /// Keeps this liquid's colors apart from every other liquid, like oil and
+  /// water: neither side of an interface with it mixes.
+  pub immiscible: bool,
+  /// Liquid this material soaks up and stores (None = not absorbent).
+  /// Stored liquid is kept in `Pixel::damage`, so absorbency is ignored for
+  /// ephemeral materials.
+  pub absorbent: Option<Absorbent>,
   /// Per-material effect responses (burning, detonation, etc.).
   pub effects: MaterialEffects,
 }
 
+impl Material {
+  /// Returns true if pixels of this material crack under hits before they
+  /// break, see [`MaterialEffects::durability`].
+  #[must_use]
+  pub fn cracks(&self) -> bool {
+    self.effects.durability > 1
+      && self.state == PhysicsState::Solid
+      && self.absorbent.is_none()
+      && self.lifetime == 0
+  }
+
+  /// Applies one blast or dig hit to a pixel of this material.
+  ///
+  /// Returns the pixel cracked one hit further, or `None` once the hit
+  /// breaks it. The crack level spreads the hits over the full crack
+  /// nibble, so the last hit before breaking always shows as
+  /// [`MAX_CRACK`] or close to it.
+  #[must_use]
+  pub fn chip(&self, pixel: &Pixel) -> Option<Pixel> {
+    if !self.cracks() {
+      return None;
+    }
+    const STEPS: u32 = MAX_CRACK as u32 + 1;
+    let durability = (self.effects.durability as u32).min(STEPS);
+    let hits = (pixel.crack() as u32 * durability).div_ceil(STEPS) + 1;
+    if hits >= durability {
+      return None;
+    }
+    let mut chipped = *pixel;
+    chipped.set_crack((hits * STEPS / durability) as u8);
+    Some(chipped)
+  }
+
+  /// Returns true if liquid pixels of this material record their depth and
+  /// are drawn with its [`depth_ramp`](Self::depth_ramp).
+  #[must_use]
+  pub fn has_depth_ramp(&self) -> bool {
+    self.state == PhysicsState::Liquid && !self.depth_ramp.is_empty() && self.lifetime == 0
+  }
+
+  /// Returns the color index the chunk shader draws `pixel` of this
+  /// material with: the color of the deepest depth ramp stop it reaches,
+  /// else its own.
+  ///
+  /// Mirrors `depth_ramp_color` in `chunk.wgsl`.
+  #[must_use]
+  pub fn shaded_color(&self, pixel: &Pixel) -> ColorIndex {
+    if !self.has_depth_ramp() || pixel.flags.contains(PixelFlags::PIXEL_BODY) || pixel.mixing() {
+      return pixel.color;
+    }
+    self
+      .depth_ramp
+      .iter()
+      .take(MAX_DEPTH_STOPS)
+      .rev()
+      .find(|stop| stop.depth <= pixel.depth())
+      .map_or(pixel.color, |stop| stop.color)
+  }
+}
+
 /// Built-in material IDs.
 pub mod ids {
   use super::MaterialId;
@@ -86,6 +292,9 @@ use ids::*;
 #[derive(bevy::prelude::Resource)]
 pub struct Materials {
   entries: Vec<Material>,
+  /// Highest `swap_priority` of any entry, so conflict checks can be skipped
+  /// for pixels nothing outranks.
+  max_swap_priority: u8,
 }
 
 impl Materials {
@@ -101,11 +310,27 @@ impl Materials {
           dispersion: 0,
           air_resistance: 0,
           air_drift: 0,
+          wet_slip: 0,
+          softness: 0,
+          swap_priority: 0,
           ignition_threshold: 0,
           base_temperature: 0,
+          thermal_conductivity: 1.0,
+          evaporation_chance: 0.0,
+          evaporation_heat_coupling: 0.0,
+          lifetime: 0,
+          trail: None,
+          variants: Vec::new(),
+          depth_ramp: Vec::new(),
+          color_mix_rate: 0.0,
+          immiscible: false,
+          absorbent: None,
           effects: MaterialEffects {
             on_burn: None,
             blast_resistance: 0.0,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         },
         // SOIL (brown gradient) - powder that falls and piles
@@ -126,11 +351,27 @@ impl Materials {
           dispersion: 0,
           air_resistance: 12, // heavier, less floaty
           air_drift: 6,
+          wet_slip: 1,
+          softness: 0,
+          swap_priority: 0,
           ignition_threshold: 0,
           base_temperature: 0,
+          thermal_conductivity: 0.4,
+          evaporation_chance: 0.0,
+          evaporation_heat_coupling: 0.0,
+          lifetime: 0,
+          trail: None,
+          variants: Vec::new(),
+          depth_ramp: Vec::new(),
+          color_mix_rate: 0.0,
+          immiscible: false,
+          absorbent: None,
           effects: MaterialEffects {
             on_burn: None,
             blast_resistance: 0.5,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         },
         // STONE (gray gradient) - solid, does not move
@@ -151,11 +392,27 @@ impl Materials {
           dispersion: 0,
           air_resistance: 0,
           air_drift: 0,
+          wet_slip: 0,
+          softness: 0,
+          swap_priority: 0,
           ignition_threshold: 0,
           base_temperature: 0,
+          thermal_conductivity: 0.7,
+          evaporation_chance: 0.0,
+          evaporation_heat_coupling: 0.0,
+          lifetime: 0,
+          trail: None,
+          variants: Vec::new(),
+          depth_ramp: Vec::new(),
+          color_mix_rate: 0.0,
+          immiscible: false,
+          absorbent: None,
           effects: MaterialEffects {
             on_burn: None,
             blast_resistance: 5.0,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         },
         // SAND (tan/yellow gradient) - powder that falls and piles
@@ -176,11 +433,27 @@ impl Materials {
           dispersion: 0,
           air_resistance: 8, // light particles float a bit
           air_drift: 4,      // blown around by wind
+          wet_slip: 2,
+          softness: 0,
+          swap_priority: 0,
           ignition_threshold: 0,
           base_temperature: 0,
+          thermal_conductivity: 0.3,
+          evaporation_chance: 0.0,
+          evaporation_heat_coupling: 0.0,
+          lifetime: 0,
+          trail: None,
+          variants: Vec::new(),
+          depth_ramp: Vec::new(),
+          color_mix_rate: 0.0,
+          immiscible: false,
+          absorbent: None,
           effects: MaterialEffects {
             on_burn: None,
             blast_resistance: 0.3,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         },
         // WATER (blue gradient) - liquid that flows
@@ -201,11 +474,27 @@ impl Materials {
           dispersion: 5,      // flows horizontally
           air_resistance: 16, // subtle splash effect
           air_drift: 12,
+          wet_slip: 0,
+          softness: 0,
+          swap_priority: 0,
           ignition_threshold: 0,
           base_temperature: 0,
+          thermal_conductivity: 0.8,
+          evaporation_chance: 0.0,
+          evaporation_heat_coupling: 0.0,
+          lifetime: 0,
+          trail: None,
+          variants: Vec::new(),
+          depth_ramp: Vec::new(),
+          color_mix_rate: 0.0,
+          immiscible: false,
+          absorbent: None,
           effects: MaterialEffects {
             on_burn: None,
             blast_resistance: 0.1,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         },
         // WOOD (brown gradient) - solid, does not move
@@ -226,11 +515,27 @@ impl Materials {
           dispersion: 0,
           air_resistance: 0,
           air_drift: 0,
+          wet_slip: 0,
+          softness: 0,
+          swap_priority: 0,
           ignition_threshold: 40,
           base_temperature: 0,
+          thermal_conductivity: 0.2,
+          evaporation_chance: 0.0,
+          evaporation_heat_coupling: 0.0,
+          lifetime: 0,
+          trail: None,
+          variants: Vec::new(),
+          depth_ramp: Vec::new(),
+          color_mix_rate: 0.0,
+          immiscible: false,
+          absorbent: None,
           effects: MaterialEffects {
             on_burn: Some((PixelEffect::Transform(ASH), 0.005)),
             blast_resistance: 1.0,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         },
         // ASH (gray powder) - product of burning
@@ -251,14 +556,32 @@ impl Materials {
           dispersion: 0,
           air_resistance: 4, // light, floaty
           air_drift: 3,
+          wet_slip: 0,
+          softness: 0,
+          swap_priority: 0,
           ignition_threshold: 0,
           base_temperature: 0,
+          thermal_conductivity: 0.1,
+          evaporation_chance: 0.0,
+          evaporation_heat_coupling: 0.0,
+          lifetime: 0,
+          trail: None,
+          variants: Vec::new(),
+          depth_ramp: Vec::new(),
+          color_mix_rate: 0.0,
+          immiscible: false,
+          absorbent: None,
           effects: MaterialEffects {
             on_burn: None,
             blast_resistance: 0.1,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         },
       ],
+      // Built-in materials don't contend by priority
+      max_swap_priority: 0,
     }
   }
 
@@ -266,6 +589,12 @@ impl Materials {
     &self.entries[id.0 as usize]
   }
 
+  /// Returns the highest `swap_priority` of any registered material.
+  #[must_use]
+  pub fn max_swap_priority(&self) -> u8 {
+    self.max_swap_priority
+  }
+
   /// Returns the number of registered materials.
   #[must_use]
   pub fn len(&self) -> usize {
@@ -277,6 +606,61 @@ impl Materials {
   pub fn is_empty(&self) -> bool {
     self.entries.is_empty()
   }
+
+  /// Returns true if any liquid can evaporate, so the evaporation pass can
+  /// be skipped otherwise.
+  #[must_use]
+  pub fn any_evaporates(&self) -> bool {
+    self
+      .entries
+      .iter()
+      .any(|m| m.state == PhysicsState::Liquid && m.evaporation_chance > 0.0)
+  }
+
+  /// Returns true if any material is ephemeral, so the ephemeral pass can
+  /// be skipped otherwise.
+  #[must_use]
+  pub fn any_ephemeral(&self) -> bool {
+    self.entries.iter().any(|m| m.lifetime > 0)
+  }
+
+  /// Returns true if any material takes imprints from pixel bodies, so
+  /// imprinting can be skipped otherwise.
+  #[must_use]
+  pub fn any_soft(&self) -> bool {
+    self.entries.iter().any(|m| m.softness > 0)
+  }
+
+  /// Returns true if any material is absorbent, so the absorption pass can
+  /// be skipped otherwise.
+  #[must_use]
+  pub fn any_absorbent(&self) -> bool {
+    self.entries.iter().any(|m| m.absorbent.is_some())
+  }
+
+  /// Returns true if any material has context variants, so the variant pass
+  /// can be skipped otherwise.
+  #[must_use]
+  pub fn any_variants(&self) -> bool {
+    self.entries.iter().any(|m| !m.variants.is_empty())
+  }
+
+  /// Returns true if any liquid has a depth ramp, so the depth pass can be
+  /// skipped otherwise.
+  #[must_use]
+  pub fn any_depth_ramp(&self) -> bool {
+    self.entries.iter().any(Material::has_depth_ramp)
+  }
+
+  /// Returns true if any liquid mixes colors, so the mixing pass can be
+  /// skipped otherwise.
+  #[must_use]
+  pub fn any_color_mix(&self) -> bool {
+    self
+      .entries
+      .iter()
+      .any(|m| m.state == PhysicsState::Liquid && m.color_mix_rate > 0.0)
+  }
 }
 
 impl Default for Materials {
@@ -309,6 +693,46 @@ pub struct EffectsConfig {
   pub on_burn: Option<BurnConfig>,
   #[serde(default)]
   pub blast_resistance: f32,
+  #[serde(default)]
+  pub indestructible: bool,
+  #[serde(default)]
+  pub durability: u8,
+  #[serde(default)]
+  pub reaction_heat: i16,
+}
+
+/// Context variant configuration, using material names instead of IDs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VariantConfig {
+  pub side: NeighborSide,
+  /// Neighbor material name. Omitted = air (void or gas).
+  #[serde(default)]
+  pub neighbor: Option<String>,
+  /// Palette color index given to matching pixels.
+  pub color: u8,
+}
+
+/// Liquid depth ramp stop in config form.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DepthColorConfig {
+  /// Same-material liquid pixels above the pixel.
+  pub depth: u8,
+  /// Palette color index given to pixels at least this deep.
+  pub color: u8,
+}
+
+/// Absorbent material configuration, using a material name instead of an
+/// ID.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AbsorbentConfig {
+  /// Name of the liquid soaked up.
+  pub liquid: String,
+  /// Liquid pixels one pixel can hold, clamped to 15.
+  pub capacity: u8,
+  /// Liquid pixels soaked up or wicked per second.
+  pub absorb_rate: f32,
+  /// Stored pixels dripped per second into the air below.
+  pub release_rate: f32,
 }
 
 /// A single material definition in config form.
@@ -327,13 +751,99 @@ pub struct MaterialConfig {
   #[serde(default)]
   pub air_drift: u8,
   #[serde(default)]
+  pub wet_slip: u8,
+  #[serde(default)]
+  pub softness: u8,
+  #[serde(default)]
+  pub swap_priority: u8,
+  #[serde(default)]
   pub ignition_threshold: u8,
   #[serde(default)]
   pub base_temperature: u8,
+  #[serde(default = "default_thermal_conductivity")]
+  pub thermal_conductivity: f32,
+  #[serde(default)]
+  pub evaporation_chance: f32,
+  #[serde(default)]
+  pub evaporation_heat_coupling: f32,
+  #[serde(default)]
+  pub lifetime: u8,
+  /// Trail material name. Omitted = no trail.
+  #[serde(default)]
+  pub trail: Option<String>,
+  #[serde(default)]
+  pub variants: Vec<VariantConfig>,
+  /// Depth ramp stops, in any order. Only used by liquids.
+  #[serde(default)]
+  pub depth_ramp: Vec<DepthColorConfig>,
+  #[serde(default)]
+  pub color_mix_rate: f32,
+  #[serde(default)]
+  pub immiscible: bool,
+  /// Liquid storage. Omitted = not absorbent.
+  #[serde(default)]
+  pub absorbent: Option<AbsorbentConfig>,
   #[serde(default)]
   pub effects: Option<EffectsConfig>,
 }
 
+fn default_thermal_conductivity() -> f32 {
+  1.0
+}
+
+impl MaterialConfig {
+  /// Creates a material named `name` in `state`, with every other field at
+  /// its default.
+  ///
+  /// ```ignore
+  /// let lava = MaterialConfig {
+  ///   density: 180,
+  ///   base_temperature: 255,
+  ///   ..MaterialConfig::new("Lava", PhysicsState::Liquid)
+  /// };
+  /// ```
+  pub fn new(name: impl Into<String>, state: PhysicsState) -> Self {
+    Self {
+      name: name.into(),
+      state,
+      ..Default::default()
+    }
+  }
+}
+
+/// An unnamed solid with an opaque black palette and the values omitted
+/// fields deserialize to.
+impl Default for MaterialConfig {
+  fn default() -> Self {
+    Self {
+      name: String::new(),
+      // Matches unused palette slots, so the built-in palette LUT is reused
+      palette: vec![[0, 0, 0, 255]; 8],
+      state: PhysicsState::Solid,
+      density: 0,
+      dispersion: 0,
+      air_resistance: 0,
+      air_drift: 0,
+      wet_slip: 0,
+      softness: 0,
+      swap_priority: 0,
+      ignition_threshold: 0,
+      base_temperature: 0,
+      thermal_conductivity: default_thermal_conductivity(),
+      evaporation_chance: 0.0,
+      evaporation_heat_coupling: 0.0,
+      lifetime: 0,
+      trail: None,
+      variants: Vec::new(),
+      depth_ramp: Vec::new(),
+      color_mix_rate: 0.0,
+      immiscible: false,
+      absorbent: None,
+      effects: None,
+    }
+  }
+}
+
 /// Format-agnostic materials configuration. Deserialize from TOML, JSON, YAML,
 /// etc.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -365,15 +875,49 @@ impl MaterialsConfig {
         BurnConfig { effect, chance }
       });
 
-      let effects = if on_burn.is_some() || entry.effects.blast_resistance != 0.0 {
+      let effects = if on_burn.is_some()
+        || entry.effects.blast_resistance != 0.0
+        || entry.effects.indestructible
+        || entry.effects.durability != 0
+        || entry.effects.reaction_heat != 0
+      {
         Some(EffectsConfig {
           on_burn,
           blast_resistance: entry.effects.blast_resistance,
+          indestructible: entry.effects.indestructible,
+          durability: entry.effects.durability,
+          reaction_heat: entry.effects.reaction_heat,
         })
       } else {
         None
       };
 
+      let variants = entry
+        .variants
+        .iter()
+        .map(|v| VariantConfig {
+          side: v.side,
+          neighbor: v.neighbor.map(|id| defaults.get(id).name.to_string()),
+          color: v.color.0,
+        })
+        .collect();
+
+      let depth_ramp = entry
+        .depth_ramp
+        .iter()
+        .map(|stop| DepthColorConfig {
+          depth: stop.depth,
+          color: stop.color.0,
+        })
+        .collect();
+
+      let absorbent = entry.absorbent.map(|a| AbsorbentConfig {
+        liquid: defaults.get(a.liquid).name.to_string(),
+        capacity: a.capacity,
+        absorb_rate: a.absorb_rate,
+        release_rate: a.release_rate,
+      });
+
       materials.push(MaterialConfig {
         name: entry.name.to_string(),
         palette,
@@ -382,8 +926,21 @@ impl MaterialsConfig {
         dispersion: entry.dispersion,
         air_resistance: entry.air_resistance,
         air_drift: entry.air_drift,
+        wet_slip: entry.wet_slip,
+        softness: entry.softness,
+        swap_priority: entry.swap_priority,
         ignition_threshold: entry.ignition_threshold,
         base_temperature: entry.base_temperature,
+        thermal_conductivity: entry.thermal_conductivity,
+        evaporation_chance: entry.evaporation_chance,
+        evaporation_heat_coupling: entry.evaporation_heat_coupling,
+        lifetime: entry.lifetime,
+        trail: entry.trail.map(|id| defaults.get(id).name.to_string()),
+        variants,
+        depth_ramp,
+        color_mix_rate: entry.color_mix_rate,
+        immiscible: entry.immiscible,
+        absorbent,
         effects,
       });
     }
@@ -401,7 +958,7 @@ impl From<MaterialsConfig> for Materials {
       .map(|(i, m)| (m.name.clone(), i as u8))
       .collect();
 
-    let entries = config
+    let entries: Vec<Material> = config
       .materials
       .into_iter()
       .map(|mc| {
@@ -427,14 +984,64 @@ impl From<MaterialsConfig> for Materials {
             MaterialEffects {
               on_burn,
               blast_resistance: ec.blast_resistance,
+              indestructible: ec.indestructible,
+              durability: ec.durability,
+              reaction_heat: ec.reaction_heat,
             }
           }
           None => MaterialEffects {
             on_burn: None,
             blast_resistance: 0.0,
+            indestructible: false,
+            durability: 0,
+            reaction_heat: 0,
           },
         };
 
+        let variants = mc
+          .variants
+          .iter()
+          .map(|vc| ContextVariant {
+            side: vc.side,
+            neighbor: vc.neighbor.as_ref().map(|name| {
+              let idx = name_to_index
+                .get(name)
+                .unwrap_or_else(|| panic!("unknown material in variant neighbor: {name:?}"));
+              MaterialId(*idx)
+            }),
+            color: ColorIndex(vc.color),
+          })
+          .collect();
+
+        let mut depth_ramp: Vec<DepthColor> = mc
+          .depth_ramp
+          .iter()
+          .map(|stop| DepthColor {
+            depth: stop.depth,
+            color: ColorIndex(stop.color),
+          })
+          .collect();
+        depth_ramp.sort_by_key(|stop| stop.depth);
+
+        let trail = mc.trail.as_ref().map(|name| {
+          let idx = name_to_index
+            .get(name)
+            .unwrap_or_else(|| panic!("unknown material in trail: {name:?}"));
+          MaterialId(*idx)
+        });
+
+        let absorbent = mc.absorbent.as_ref().map(|ac| {
+          let idx = name_to_index
+            .get(&ac.liquid)
+            .unwrap_or_else(|| panic!("unknown material in absorbent liquid: {:?}", ac.liquid));
+          Absorbent {
+            liquid: MaterialId(*idx),
+            capacity: ac.capacity.min(MAX_SATURATION),
+            absorb_rate: ac.absorb_rate,
+            release_rate: ac.release_rate,
+          }
+        });
+
         // Leak name to get &'static str (one-time allocation per material).
         let name: &'static str = Box::leak(mc.name.into_boxed_str());
 
@@ -446,13 +1053,30 @@ impl From<MaterialsConfig> for Materials {
           dispersion: mc.dispersion,
           air_resistance: mc.air_resistance,
           air_drift: mc.air_drift,
+          wet_slip: mc.wet_slip,
+          softness: mc.softness,
+          swap_priority: mc.swap_priority,
           ignition_threshold: mc.ignition_threshold,
           base_temperature: mc.base_temperature,
+          thermal_conductivity: mc.thermal_conductivity,
+          evaporation_chance: mc.evaporation_chance,
+          evaporation_heat_coupling: mc.evaporation_heat_coupling,
+          lifetime: mc.lifetime,
+          trail,
+          variants,
+          depth_ramp,
+          color_mix_rate: mc.color_mix_rate,
+          immiscible: mc.immiscible,
+          absorbent,
           effects,
         }
       })
       .collect();
 
-    Self { entries }
+    let max_swap_priority = entries.iter().map(|m| m.swap_priority).max().unwrap_or(0);
+    Self {
+      entries,
+      max_swap_priority,
+    }
   }
 }
@@ -4,6 +4,7 @@
 //! worlds.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use bevy::prelude::*;
 use bevy::sprite_render::Material2dPlugin;
@@ -13,12 +14,14 @@ pub mod bodies_plugin;
 pub mod buoyancy;
 pub mod collision;
 pub mod coords;
+pub mod crash;
 pub mod creative_mode;
 pub mod debug_camera;
 pub mod debug_controller;
 pub mod debug_controller_ui;
 pub mod debug_shim;
 pub mod diagnostics;
+pub mod edit_log;
 pub use diagnostics::profile;
 pub mod material;
 pub mod palette;
@@ -45,64 +48,99 @@ pub use basic_persistence::BasicPersistencePlugin;
 pub use bodies_plugin::PixelBodiesPlugin;
 pub use buoyancy::BuoyancyConfig;
 pub use buoyancy::SubmersionConfig;
-pub use collision::{CollisionCache, CollisionConfig, CollisionQueryPoint, CollisionTasks};
+pub use buoyancy::{SplashConfig, Splashed};
+pub use collision::{
+  CollisionCache, CollisionConfig, CollisionQueryPoint, CollisionTasks, PowderSinkConfig,
+  RestMergeConfig,
+};
 pub use coords::{
   CHUNK_SIZE, ChunkPos, ColorIndex, LocalPos, MaterialId, TILE_SIZE, TilePos, WorldFragment,
   WorldPos, WorldRect,
 };
+pub use crash::{CrashReporter, CrashSnapshotPlugin};
 pub use creative_mode::CreativeModePlugins;
 pub use debug_camera::{CameraZoom, DebugVirtualCamera, PixelDebugControllerCameraPlugin};
 pub use debug_controller::{BrushState, PixelDebugControllerPlugin, UiPointerState};
 pub use debug_controller_ui::{BrushUiPlugin, BrushUiVisible, brush_controls_ui};
-pub use material::{Material, Materials, MaterialsConfig, PhysicsState, ids as material_ids};
+pub use edit_log::{
+  EditLog, EditRecorder, EditRecorderPlugin, LoggedBomb, LoggedEdit, WorldEdit, replay,
+};
+pub use material::{
+  Absorbent, ContextVariant, DepthColor, Material, Materials, MaterialsConfig, NeighborSide,
+  PhysicsState, ids as material_ids,
+};
 pub use palette::{
   DistanceFunction, DitherMode, GlobalPalette, LutCacheAsset, LutConfig, PaletteConfig,
   PalettePlugin, PaletteSource, PalettizeOnLoad, palettize_image, palettize_image_in_place,
 };
 pub use persistence::{PixelBodyRecord, WorldSave};
-pub use pixel::{Pixel, PixelFlags, PixelSurface};
+pub use pixel::{MAX_CRACK, MAX_DEPTH, MAX_SATURATION, Pixel, PixelFlags, PixelSurface};
 pub use pixel_awareness::GridSampleConfig;
 pub use pixel_body::{
-  Bomb, BombInitialState, DisplacementState, LastBlitTransform, PendingPixelBody, Persistable,
-  PixelBody, PixelBodyId, PixelBodyIdGenerator, PixelBodyLoader, SpawnPixelBody,
-  SpawnPixelBodyFromImage, finalize_pending_pixel_bodies, generate_collider, update_pixel_bodies,
+  AnchorId, Bomb, BombInitialState, ColliderRegenConfig, DetonationConfig, DetonationQueue,
+  DisplacementState, IMPRINTED, LastBlitTransform, PendingPixelBody, Persistable, PixelBody,
+  PixelBodyId, PixelBodyIdGenerator, PixelBodyLoader, PixelBodySpawnFailed, RestState, Resting,
+  Shockwaves, SpawnPixelBody, SpawnPixelBodyFromImage, finalize_pending_pixel_bodies,
+  generate_collider, update_pixel_bodies,
 };
 pub use pixel_camera::{
   FULLRES_SPRITE_LAYER, LogicalCameraPosition, PixelBlitMaterial, PixelCamera, PixelCameraConfig,
-  PixelCameraPlugin, PixelCameraSet, PixelCameraState, PixelSizeMode,
+  PixelCameraPlugin, PixelCameraSet, PixelCameraState, PixelRenderTargetInfo, PixelSizeMode,
+  pixel_camera_publish_target_info,
 };
 pub use plugin_bundle::PixelWorldFullBundle;
-pub use primitives::{Chunk, Surface};
+pub use primitives::{Chunk, ChunkDiff, Surface};
 pub use render::{
-  ChunkMaterial, Rgba, create_chunk_quad, create_palette_texture, create_pixel_texture,
-  create_texture, materialize, rgb, spawn_static_chunk, upload_palette, upload_pixels,
-  upload_surface,
+  BodyRenderMode, ChunkMaterial, CrackOverlayConfig, CrackUniform, DepthRampUniform,
+  HeatTextureConfig, HeatTextureFormat, PixelBodySprite, Rgba, create_chunk_quad,
+  create_palette_texture, create_pixel_texture, create_texture, materialize, rgb,
+  spawn_static_chunk, surface_to_image, upload_palette, upload_pixels,
+  upload_pixels_without_bodies, upload_surface,
+};
+pub use schedule::{CATickStep, PixelSimulation, PixelWorldSet, SimulationPhase};
+pub use seeding::{
+  ChunkSeeder, LayeredSeeder, MaterialSeeder, NoiseSeeder, OreSeeder, RegionClip, StructurePlacer,
+  presets as noise_presets,
+};
+pub use simulation::{
+  GravityWell, GravityWells, HeatConfig, SimulationConfig, TickPacing, Wind, simulate_tick,
+};
+pub use text::{
+  CpuFont, ScrollingText, TextMask, TextStyle, draw_text, rasterize_text, stamp_text,
+  update_scrolling_text,
 };
-pub use schedule::{PixelWorldSet, SimulationPhase};
-pub use seeding::{ChunkSeeder, MaterialSeeder, NoiseSeeder, presets as noise_presets};
-pub use simulation::{HeatConfig, SimulationConfig, simulate_tick};
-pub use text::{CpuFont, TextMask, TextStyle, draw_text, rasterize_text, stamp_text};
 #[cfg(feature = "tracy")]
 pub use tracy_init::init_tracy;
 pub use virtual_camera::{ActiveVirtualCamera, VirtualCamera, VirtualCameraPlugin};
 pub use world::control::{
-  ClearPersistence, FreshReseedAllChunks, PersistenceComplete, PersistenceControl,
-  PersistenceFuture, PersistenceHandle, ReloadAllChunks, RequestPersistence, ReseedAllChunks,
-  SimulationState, UpdateSeeder,
+  CancelPendingTasks, ClearPersistence, DryRunWrite, FreshReseedAllChunks, PersistenceComplete,
+  PersistenceControl, PersistenceFuture, PersistenceHandle, ReloadAllChunks, RequestPersistence,
+  ReseedAllChunks, SimulationState, UpdateSeeder,
+};
+pub use world::fog::{ExploredChunks, FogOfWarConfig};
+pub use world::plugin::{
+  AsyncTaskBehavior, SeededChunks, SimulationViewport, StreamingCamera, UnloadingChunks,
 };
-pub use world::plugin::{AsyncTaskBehavior, SeededChunks, StreamingCamera, UnloadingChunks};
 // Re-export culling types from streaming module for backward compatibility
-pub use world::streaming::{CullingConfig, StreamCulled};
+pub use world::streaming::{
+  ChunkSeededMsg, CullingConfig, SeedDeterminismCheck, SeedMismatch, StreamCulled,
+};
 pub use world::{
+  AsyncTaskCounts,
+  CellView,
+  DigConfig,
+  NEIGHBOR_OFFSETS,
   PersistenceInitialized,
   PixelWorld,
   PixelWorldBundle,
   PixelWorldConfig,
+  SimulationSnapshot,
   // World initialization state and progress tracking
   SpawnPixelWorld,
   WorldInitState,
   WorldLoadingProgress,
   WorldReady,
+  WorldTransaction,
   world_is_loading,
   world_is_ready,
 };
@@ -115,12 +153,44 @@ pub use world::{
 /// ```ignore
 /// let config = PersistenceConfig::at("/home/user/saves/world.save");
 /// ```
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct PersistenceConfig {
   /// Path to save file.
   pub path: PathBuf,
   /// World seed for procedural generation.
   pub world_seed: u64,
+  /// Whether chunks wait for the save index before seeding (default: true).
+  ///
+  /// When disabled, chunks streamed in while persistence is initializing are
+  /// seeded procedurally right away and re-fetched from the save once the
+  /// index is available. This shows terrain sooner, but persisted chunks
+  /// visibly flash when their saved data replaces the procedural one.
+  pub defer_seeding: bool,
+  /// Pixel flags cleared from chunks loaded from the save (default: none).
+  ///
+  /// Transient state like `BURNING` or `FALLING` is saved as-is, so a chunk
+  /// saved mid-fire resumes burning on load. Listing a flag here drops it
+  /// instead, e.g. `PixelFlags::BURNING | PixelFlags::FALLING` to extinguish
+  /// fires while keeping `WET`.
+  pub clear_flags_on_load: PixelFlags,
+  /// Custom storage backend for the save (default: `None`, native files).
+  ///
+  /// When set, only the file name of `path` is used and every read and
+  /// write goes through this backend, e.g. to keep saves in cloud storage.
+  /// Ignored on WASM, which always uses OPFS.
+  pub storage: Option<Arc<dyn persistence::backend::StorageFs>>,
+}
+
+impl std::fmt::Debug for PersistenceConfig {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("PersistenceConfig")
+      .field("path", &self.path)
+      .field("world_seed", &self.world_seed)
+      .field("defer_seeding", &self.defer_seeding)
+      .field("clear_flags_on_load", &self.clear_flags_on_load)
+      .field("storage", &self.storage.as_ref().map(|_| "custom"))
+      .finish()
+  }
 }
 
 impl PersistenceConfig {
@@ -129,6 +199,9 @@ impl PersistenceConfig {
     Self {
       path: path.into(),
       world_seed: 42,
+      defer_seeding: true,
+      clear_flags_on_load: PixelFlags::empty(),
+      storage: None,
     }
   }
 
@@ -137,6 +210,26 @@ impl PersistenceConfig {
     self.world_seed = seed;
     self
   }
+
+  /// Sets whether seeding waits for the save index to load.
+  pub fn with_deferred_seeding(mut self, defer: bool) -> Self {
+    self.defer_seeding = defer;
+    self
+  }
+
+  /// Sets the pixel flags cleared from chunks loaded from the save.
+  pub fn with_flags_cleared_on_load(mut self, flags: PixelFlags) -> Self {
+    self.clear_flags_on_load = flags;
+    self
+  }
+
+  /// Stores the save through a custom backend instead of native files.
+  ///
+  /// See [`persistence::backend`] for the contract the backend must uphold.
+  pub fn with_storage(mut self, fs: Arc<dyn persistence::backend::StorageFs>) -> Self {
+    self.storage = Some(fs);
+    self
+  }
 }
 
 /// Plugin for infinite cellular automata simulation.
@@ -229,12 +322,15 @@ impl Plugin for PixelWorldPlugin {
 
       // Create IoDispatcher (spawns worker thread on native, Web Worker on WASM)
       #[cfg(not(target_family = "wasm"))]
-      let io_dispatcher = {
-        let base_dir = path
-          .parent()
-          .unwrap_or(std::path::Path::new("."))
-          .to_path_buf();
-        persistence::IoDispatcher::new(base_dir)
+      let io_dispatcher = match &self.persistence.storage {
+        Some(fs) => persistence::IoDispatcher::with_storage(Arc::clone(fs)),
+        None => {
+          let base_dir = path
+            .parent()
+            .unwrap_or(std::path::Path::new("."))
+            .to_path_buf();
+          persistence::IoDispatcher::new(base_dir)
+        }
       };
       #[cfg(target_family = "wasm")]
       let io_dispatcher = persistence::IoDispatcher::new();
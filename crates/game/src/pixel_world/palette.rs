@@ -135,6 +135,12 @@ pub struct GlobalPalette {
   pub lut_config: LutConfig,
   /// Hash of colors + config for the current LUT (for cache validation).
   lut_hash: Option<u64>,
+  /// Indices changed by `set_color` since the last texture upload. Patched
+  /// into the palette texture individually unless `dirty` forces a full
+  /// upload.
+  changed_colors: Vec<u8>,
+  /// Indices changed by `set_color` since the LUT was last built or patched.
+  stale_lut_entries: Vec<u8>,
 }
 
 impl Default for GlobalPalette {
@@ -153,6 +159,8 @@ impl Default for GlobalPalette {
       dirty: true,
       lut_config: LutConfig::default(),
       lut_hash: None,
+      changed_colors: Vec::new(),
+      stale_lut_entries: Vec::new(),
     }
   }
 }
@@ -171,6 +179,8 @@ impl GlobalPalette {
       dirty: true,
       lut_config,
       lut_hash: None,
+      changed_colors: Vec::new(),
+      stale_lut_entries: Vec::new(),
     }
   }
 
@@ -183,29 +193,76 @@ impl GlobalPalette {
   /// The LUT is not built immediately - call `start_lut_build()` to begin
   /// async computation.
   pub fn from_materials(materials: &Materials, lut_config: LutConfig) -> Self {
-    let mut colors = [Rgba::new(0, 0, 0, 255); 256];
+    Self::from_colors(material_colors(materials), lut_config)
+  }
 
-    let count = materials.len().min(32);
-    for material_id in 0..count {
-      let material = materials.get(crate::pixel_world::coords::MaterialId(material_id as u8));
-      let base = material_id * 8;
+  /// Sets the color at a palette index, tracking the change so the palette
+  /// texture and LUT can be patched instead of rebuilt.
+  ///
+  /// Returns `false` if the index already had this color.
+  pub fn set_color(&mut self, index: u8, color: Rgba) -> bool {
+    if self.colors[index as usize] == color {
+      return false;
+    }
+    self.colors[index as usize] = color;
+    if !self.changed_colors.contains(&index) {
+      self.changed_colors.push(index);
+    }
+    if !self.stale_lut_entries.contains(&index) {
+      self.stale_lut_entries.push(index);
+    }
+    true
+  }
 
-      for (color_idx, color) in material.palette.iter().enumerate() {
-        let palette_idx = base + color_idx;
-        if palette_idx < 256 {
-          colors[palette_idx] = *color;
-        }
-      }
+  /// Updates the palette from a Materials registry, using the layout of
+  /// [`from_materials`](Self::from_materials).
+  ///
+  /// Only entries whose color differs are changed. Returns the changed
+  /// indices; call [`patch_lut`](Self::patch_lut) to bring the LUT up to
+  /// date.
+  pub fn update_from_materials(&mut self, materials: &Materials) -> Vec<u8> {
+    let colors = material_colors(materials);
+    (0..=255u8)
+      .filter(|&i| self.set_color(i, colors[i as usize]))
+      .collect()
+  }
+
+  /// Takes the indices changed by [`set_color`](Self::set_color) since the
+  /// last call, for patching the palette texture.
+  pub fn take_changed_colors(&mut self) -> Vec<u8> {
+    std::mem::take(&mut self.changed_colors)
+  }
+
+  /// Brings the LUT up to date with colors changed by
+  /// [`set_color`](Self::set_color).
+  ///
+  /// In `Nearest` mode with a ready LUT, only cells that mapped to a changed
+  /// entry or now lie closer to one are rewritten (see [`patch_lut`]).
+  /// Dithered LUTs and in-flight builds fall back to a full async rebuild.
+  /// Returns `true` if the LUT was patched in place.
+  pub fn patch_lut(&mut self) -> bool {
+    if self.stale_lut_entries.is_empty() {
+      return self.lut.is_some();
     }
 
-    Self {
-      colors,
-      lut: None,
-      pending_lut: None,
-      config_handle: None,
-      dirty: true,
-      lut_config,
-      lut_hash: None,
+    match (&mut self.lut, self.lut_config.mode) {
+      (Some(lut), DitherMode::Nearest) if self.pending_lut.is_none() => {
+        patch_lut(
+          lut,
+          &self.colors,
+          &self.stale_lut_entries,
+          self.lut_config.distance,
+        );
+        self.stale_lut_entries.clear();
+        self.lut_hash = Some(self.compute_hash());
+        true
+      }
+      // The initial build picks up the current colors
+      (None, _) if self.pending_lut.is_none() => false,
+      _ => {
+        self.start_lut_build();
+        false
+      }
     }
   }
 
@@ -261,6 +318,7 @@ impl GlobalPalette {
     let task = task_pool.spawn(async move { build_lut(&colors, distance_fn, mode) });
 
     self.pending_lut = Some(LutTask { task, config });
+    self.stale_lut_entries.clear();
   }
 
   /// Polls the pending LUT task.
@@ -312,6 +370,7 @@ impl GlobalPalette {
     ));
     self.lut_hash = Some(palette_hash(&self.colors, &lut_config));
     self.pending_lut = None;
+    self.stale_lut_entries.clear();
     self.dirty = true;
   }
 
@@ -327,6 +386,7 @@ impl GlobalPalette {
     self.lut = Some(lut);
     self.lut_hash = Some(hash);
     self.pending_lut = None;
+    self.stale_lut_entries.clear();
     self.dirty = true;
   }
 
@@ -336,6 +396,27 @@ impl GlobalPalette {
   }
 }
 
+/// Lays out material colors as palette entries: material N takes indices
+/// `N * 8..N * 8 + 8`. Unused entries are opaque black.
+fn material_colors(materials: &Materials) -> [Rgba; 256] {
+  let mut colors = [Rgba::new(0, 0, 0, 255); 256];
+
+  let count = materials.len().min(32);
+  for material_id in 0..count {
+    let material = materials.get(crate::pixel_world::coords::MaterialId(material_id as u8));
+    let base = material_id * 8;
+
+    for (color_idx, color) in material.palette.iter().enumerate() {
+      let palette_idx = base + color_idx;
+      if palette_idx < 256 {
+        colors[palette_idx] = *color;
+      }
+    }
+  }
+
+  colors
+}
+
 /// Computes a hash of palette colors and LUT configuration.
 ///
 /// Used to detect when the cached LUT needs rebuilding.
@@ -482,15 +563,7 @@ pub fn build_lut_parallel(
   mode: DitherMode,
 ) -> Box<[u8; 16_777_216]> {
   // Precompute palette colors in OkLab space (used for OkLab distance)
-  let palette_oklab: [Oklab; 256] = std::array::from_fn(|i| {
-    let c = &colors[i];
-    let srgb = Srgb::new(
-      c.red as f32 / 255.0,
-      c.green as f32 / 255.0,
-      c.blue as f32 / 255.0,
-    );
-    srgb.into_color()
-  });
+  let palette_oklab = palette_to_oklab(&colors);
 
   let bayer_2x2 = [[0.0f32, 0.5], [0.75, 0.25]];
 
@@ -524,38 +597,108 @@ pub fn build_lut_parallel(
   unsafe { Box::from_raw(ptr) }
 }
 
-/// Finds the nearest palette index to an RGB color.
-fn find_nearest(
-  r: u8,
-  g: u8,
-  b: u8,
+/// Patches a nearest-color LUT after the palette entries in `changed` were
+/// recolored.
+///
+/// A cell can only change if it mapped to a changed entry, in which case it
+/// is recomputed against the whole palette, or if a changed entry now lies
+/// closer than its current match. Every other cell is left untouched, and
+/// the result matches a full `DitherMode::Nearest` build with the new
+/// colors.
+pub fn patch_lut(
+  lut: &mut [u8; 16_777_216],
   colors: &[Rgba; 256],
-  palette_oklab: &[Oklab],
+  changed: &[u8],
   distance_fn: DistanceFunction,
-) -> u8 {
-  let mut best_idx = 0u8;
-  let mut best_dist = f32::MAX;
-
-  match distance_fn {
-    DistanceFunction::Rgb => {
-      let rf = r as f32;
-      let gf = g as f32;
-      let bf = b as f32;
-      for (i, c) in colors.iter().enumerate() {
+) {
+  if changed.is_empty() {
+    return;
+  }
+
+  let palette_oklab = palette_to_oklab(colors);
+  let mut is_changed = [false; 256];
+  for &i in changed {
+    is_changed[i as usize] = true;
+  }
+
+  // Parallel over R dimension (256 independent 65536-byte planes)
+  lut
+    .par_chunks_mut(65_536)
+    .enumerate()
+    .for_each(|(r, plane)| {
+      for (gb, cell) in plane.iter_mut().enumerate() {
+        let (g, b) = ((gb >> 8) as u8, gb as u8);
+        let probe = ColorProbe::new(r as u8, g, b, distance_fn);
+
+        if is_changed[*cell as usize] {
+          *cell = probe.nearest(colors, &palette_oklab);
+          continue;
+        }
+
+        // Ties go to the lower index, as in a full build
+        let mut best = *cell;
+        let mut best_dist = probe.distance(best as usize, colors, &palette_oklab);
+        for &i in changed {
+          let dist = probe.distance(i as usize, colors, &palette_oklab);
+          if dist < best_dist || (dist == best_dist && i < best) {
+            best = i;
+            best_dist = dist;
+          }
+        }
+        *cell = best;
+      }
+    });
+}
+
+/// Converts palette colors to OkLab space.
+fn palette_to_oklab(colors: &[Rgba; 256]) -> [Oklab; 256] {
+  std::array::from_fn(|i| {
+    let c = &colors[i];
+    let srgb = Srgb::new(
+      c.red as f32 / 255.0,
+      c.green as f32 / 255.0,
+      c.blue as f32 / 255.0,
+    );
+    srgb.into_color()
+  })
+}
+
+/// An RGB color prepared for distance queries against palette entries.
+enum ColorProbe {
+  Rgb(f32, f32, f32),
+  Hsl(f32, f32, f32),
+  Oklab(Oklab),
+}
+
+impl ColorProbe {
+  fn new(r: u8, g: u8, b: u8, distance_fn: DistanceFunction) -> Self {
+    match distance_fn {
+      DistanceFunction::Rgb => Self::Rgb(r as f32, g as f32, b as f32),
+      DistanceFunction::Hsl => {
+        let (h, s, l) = rgb_to_hsl(r, g, b);
+        Self::Hsl(h, s, l)
+      }
+      DistanceFunction::Oklab => {
+        let srgb = Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        Self::Oklab(srgb.into_color())
+      }
+    }
+  }
+
+  /// Distance to palette entry `index`.
+  #[inline]
+  fn distance(&self, index: usize, colors: &[Rgba; 256], palette_oklab: &[Oklab]) -> f32 {
+    match *self {
+      Self::Rgb(rf, gf, bf) => {
+        let c = &colors[index];
         let dr = rf - c.red as f32;
         let dg = gf - c.green as f32;
         let db = bf - c.blue as f32;
-        let dist = dr * dr + dg * dg + db * db;
-        if dist < best_dist {
-          best_dist = dist;
-          best_idx = i as u8;
-        }
+        dr * dr + dg * dg + db * db
       }
-    }
-    DistanceFunction::Hsl => {
-      // Convert source to HSL-ish (simplified: use hue angle distance + lightness)
-      let (h, s, l) = rgb_to_hsl(r, g, b);
-      for (i, c) in colors.iter().enumerate() {
+      Self::Hsl(h, s, l) => {
+        // Simplified HSL: hue angle distance + lightness
+        let c = &colors[index];
         let (h2, s2, l2) = rgb_to_hsl(c.red, c.green, c.blue);
         // Hue is circular, compute minimal angular distance
         let dh = {
@@ -566,30 +709,43 @@ fn find_nearest(
         let dl = l - l2;
         // Weight hue more heavily when saturation is high
         let hue_weight = (s + s2) / 2.0;
-        let dist = dh * dh * hue_weight * 4.0 + ds * ds + dl * dl * 2.0;
-        if dist < best_dist {
-          best_dist = dist;
-          best_idx = i as u8;
-        }
+        dh * dh * hue_weight * 4.0 + ds * ds + dl * dl * 2.0
       }
-    }
-    DistanceFunction::Oklab => {
-      let srgb = Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
-      let oklab: Oklab = srgb.into_color();
-      for (i, p) in palette_oklab.iter().enumerate() {
+      Self::Oklab(oklab) => {
+        let p = &palette_oklab[index];
         let dl = oklab.l - p.l;
         let da = oklab.a - p.a;
         let db = oklab.b - p.b;
-        let dist = dl * dl + da * da + db * db;
-        if dist < best_dist {
-          best_dist = dist;
-          best_idx = i as u8;
-        }
+        dl * dl + da * da + db * db
       }
     }
   }
 
-  best_idx
+  /// Nearest palette index; ties go to the lower index.
+  fn nearest(&self, colors: &[Rgba; 256], palette_oklab: &[Oklab]) -> u8 {
+    let mut best_idx = 0u8;
+    let mut best_dist = f32::MAX;
+    for i in 0..colors.len() {
+      let dist = self.distance(i, colors, palette_oklab);
+      if dist < best_dist {
+        best_dist = dist;
+        best_idx = i as u8;
+      }
+    }
+    best_idx
+  }
+}
+
+/// Finds the nearest palette index to an RGB color.
+fn find_nearest(
+  r: u8,
+  g: u8,
+  b: u8,
+  colors: &[Rgba; 256],
+  palette_oklab: &[Oklab],
+  distance_fn: DistanceFunction,
+) -> u8 {
+  ColorProbe::new(r, g, b, distance_fn).nearest(colors, palette_oklab)
 }
 
 /// Finds the 4 nearest palette indices for dithering.
@@ -604,45 +760,10 @@ fn find_nearest_candidates(
   // Track the 4 best candidates
   let mut candidates: [(u8, f32); 4] = [(0, f32::MAX); 4];
 
-  match distance_fn {
-    DistanceFunction::Rgb => {
-      let rf = r as f32;
-      let gf = g as f32;
-      let bf = b as f32;
-      for (i, c) in colors.iter().enumerate() {
-        let dr = rf - c.red as f32;
-        let dg = gf - c.green as f32;
-        let db = bf - c.blue as f32;
-        let dist = dr * dr + dg * dg + db * db;
-        insert_candidate(&mut candidates, i as u8, dist);
-      }
-    }
-    DistanceFunction::Hsl => {
-      let (h, s, l) = rgb_to_hsl(r, g, b);
-      for (i, c) in colors.iter().enumerate() {
-        let (h2, s2, l2) = rgb_to_hsl(c.red, c.green, c.blue);
-        let dh = {
-          let diff = (h - h2).abs();
-          if diff > 0.5 { 1.0 - diff } else { diff }
-        };
-        let ds = s - s2;
-        let dl = l - l2;
-        let hue_weight = (s + s2) / 2.0;
-        let dist = dh * dh * hue_weight * 4.0 + ds * ds + dl * dl * 2.0;
-        insert_candidate(&mut candidates, i as u8, dist);
-      }
-    }
-    DistanceFunction::Oklab => {
-      let srgb = Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
-      let oklab: Oklab = srgb.into_color();
-      for (i, p) in palette_oklab.iter().enumerate() {
-        let dl = oklab.l - p.l;
-        let da = oklab.a - p.a;
-        let db = oklab.b - p.b;
-        let dist = dl * dl + da * da + db * db;
-        insert_candidate(&mut candidates, i as u8, dist);
-      }
-    }
+  let probe = ColorProbe::new(r, g, b, distance_fn);
+  for i in 0..colors.len() {
+    let dist = probe.distance(i, colors, palette_oklab);
+    insert_candidate(&mut candidates, i as u8, dist);
   }
 
   candidates
@@ -792,6 +913,22 @@ pub fn upload_palette(palette: &GlobalPalette, image: &mut Image) {
   }
 }
 
+/// Uploads only the given GlobalPalette entries to a GPU texture, leaving
+/// the rest of the texture untouched.
+pub fn upload_palette_entries(palette: &GlobalPalette, image: &mut Image, indices: &[u8]) {
+  let Some(ref mut data) = image.data else {
+    return;
+  };
+
+  for &i in indices {
+    let color = palette.colors[i as usize];
+    let offset = i as usize * 4;
+    if offset + 4 <= data.len() {
+      data[offset..offset + 4].copy_from_slice(&[color.red, color.green, color.blue, color.alpha]);
+    }
+  }
+}
+
 /// Converts an image's colors to the nearest palette colors.
 ///
 /// Creates a new image with the same dimensions where each pixel's RGB
@@ -988,4 +1125,96 @@ mod tests {
       "Different colors should produce different hash"
     );
   }
+
+  /// Builtin materials with one Wood color changed.
+  fn recolored_materials() -> Materials {
+    let mut config = crate::pixel_world::material::MaterialsConfig::builtin();
+    config.materials[5].palette[2] = [255, 0, 255, 255];
+    Materials::from(config)
+  }
+
+  /// Palette index of the recolored Wood entry.
+  const RECOLORED: u8 = 5 * 8 + 2;
+
+  #[test]
+  fn material_color_change_touches_only_its_palette_bytes() {
+    let mut palette = GlobalPalette::from_materials(&Materials::new(), LutConfig::default());
+    let mut image = Image::new_fill(
+      Extent3d {
+        width: 256,
+        height: 1,
+        depth_or_array_layers: 1,
+      },
+      TextureDimension::D2,
+      &[0, 0, 0, 255],
+      TextureFormat::Rgba8UnormSrgb,
+      RenderAssetUsages::MAIN_WORLD,
+    );
+    upload_palette(&palette, &mut image);
+    let before = image.data.clone().unwrap();
+
+    let changed = palette.update_from_materials(&recolored_materials());
+    assert_eq!(changed, vec![RECOLORED]);
+    assert_eq!(palette.take_changed_colors(), vec![RECOLORED]);
+    assert!(palette.take_changed_colors().is_empty());
+
+    upload_palette_entries(&palette, &mut image, &changed);
+    let after = image.data.unwrap();
+    let offset = RECOLORED as usize * 4;
+    assert_eq!(&after[offset..offset + 4], &[255, 0, 255, 255]);
+    for (i, (a, b)) in before.iter().zip(&after).enumerate() {
+      if !(offset..offset + 4).contains(&i) {
+        assert_eq!(a, b, "Byte {} of an unchanged entry was rewritten", i);
+      }
+    }
+
+    // Applying the same materials again changes nothing
+    assert!(
+      palette
+        .update_from_materials(&recolored_materials())
+        .is_empty()
+    );
+  }
+
+  #[test]
+  fn patched_lut_matches_full_rebuild() {
+    for distance in [DistanceFunction::Rgb, DistanceFunction::Oklab] {
+      let config = LutConfig {
+        distance,
+        mode: DitherMode::Nearest,
+      };
+      let mut palette = GlobalPalette::from_materials(&Materials::new(), config.clone());
+      let lut = build_lut_parallel(palette.colors, distance, DitherMode::Nearest);
+      let original = lut.to_vec();
+      let hash = palette.compute_hash();
+      palette.set_lut_from_cache(lut, hash);
+
+      palette.update_from_materials(&recolored_materials());
+      assert!(palette.patch_lut(), "Nearest LUTs patch in place");
+      assert_eq!(palette.lut_hash, Some(palette.compute_hash()));
+
+      let patched = palette.lut_data().unwrap();
+      let rebuilt = build_lut_parallel(palette.colors, distance, DitherMode::Nearest);
+      assert!(
+        patched[..] == rebuilt[..],
+        "{:?}: patched LUT differs from a full rebuild",
+        distance
+      );
+
+      // Only cells mapping to or from the recolored entry changed
+      let mut touched = 0;
+      for (old, new) in original.iter().zip(patched.iter()) {
+        if old != new {
+          assert!(*old == RECOLORED || *new == RECOLORED);
+          touched += 1;
+        }
+      }
+      assert!(
+        touched > 0,
+        "{:?}: recoloring should move some cells",
+        distance
+      );
+      assert!(touched < 16_777_216 / 2);
+    }
+  }
 }
@@ -3,6 +3,96 @@
 //! Provides [`StorageFile`] and [`StorageFs`] abstractions over random-access
 //! file I/O so that `WorldSave` can work on desktop (native files), WASM
 //! (OPFS), and iOS without changing its own logic.
+//!
+//! # Custom backends
+//!
+//! On native targets a game can store saves somewhere other than the local
+//! filesystem (a cloud bucket, a remote service) by implementing
+//! [`StorageFs`] and passing it to
+//! [`PersistenceConfig::with_storage`](crate::pixel_world::PersistenceConfig::with_storage).
+//! The I/O worker awaits every future on its own thread, so operations may
+//! complete later on another thread as long as they wake the task when done.
+//!
+//! The contract backends must uphold:
+//!
+//! - **Names** are flat keys such as `world.save` and `world.save.journal`.
+//!   There are no directories.
+//! - **Positioned writes** past the end of a file extend it, zero-filling any
+//!   gap. Writes to different ranges of the same file may be issued
+//!   back-to-back and must both land.
+//! - **Reads** fill the whole buffer or fail; a short read is an error.
+//! - **Ordering**: a future that resolved is visible to every later call. The
+//!   worker never issues two operations at once, so backends don't need to
+//!   order concurrent calls.
+//! - **`sync`** resolves once earlier writes are durable. Flushes and journal
+//!   appends end with a sync, so saves are only as crash-safe as the backend's
+//!   sync.
+//! - **`open`** and **`delete`** of a missing file fail with
+//!   [`BackendError::NotFound`].
+//!
+//! A remote object store has no positioned writes, so a typical backend
+//! keeps open files in memory and uploads them on `sync`:
+//!
+//! ```ignore
+//! struct BucketFs {
+//!   client: Arc<BucketClient>,
+//!   prefix: String,
+//! }
+//!
+//! struct BucketFile {
+//!   client: Arc<BucketClient>,
+//!   key: String,
+//!   data: Mutex<Vec<u8>>,
+//! }
+//!
+//! impl StorageFile for BucketFile {
+//!   fn read_at<'a>(
+//!     &'a self,
+//!     offset: u64,
+//!     buf: &'a mut [u8],
+//!   ) -> BoxFuture<'a, Result<(), BackendError>> {
+//!     Box::pin(async move {
+//!       let data = self.data.lock().unwrap();
+//!       let range = offset as usize..offset as usize + buf.len();
+//!       let src = data.get(range).ok_or(io::Error::from(io::ErrorKind::UnexpectedEof))?;
+//!       buf.copy_from_slice(src);
+//!       Ok(())
+//!     })
+//!   }
+//!
+//!   fn sync(&self) -> BoxFuture<'_, Result<(), BackendError>> {
+//!     Box::pin(async move {
+//!       let body = self.data.lock().unwrap().clone();
+//!       self
+//!         .client
+//!         .put_object(&self.key, body)
+//!         .await
+//!         .map_err(|e| BackendError::Other(e.into()))
+//!     })
+//!   }
+//!
+//!   // write_at, len and set_len edit `data` in place
+//! }
+//!
+//! impl StorageFs for BucketFs {
+//!   fn open<'a>(
+//!     &'a self,
+//!     name: &'a str,
+//!   ) -> BoxFuture<'a, Result<Box<dyn StorageFile>, BackendError>> {
+//!     Box::pin(async move {
+//!       let key = format!("{}/{}", self.prefix, name);
+//!       match self.client.get_object(&key).await {
+//!         Ok(Some(data)) => Ok(Box::new(BucketFile::new(&self.client, key, data)) as _),
+//!         Ok(None) => Err(BackendError::NotFound),
+//!         Err(e) => Err(BackendError::Other(e.into())),
+//!       }
+//!     })
+//!   }
+//!
+//!   // create, exists, delete, list and copy map to put, head, delete,
+//!   // list and copy requests
+//! }
+//! ```
 
 use std::error::Error;
 use std::future::Future;
@@ -69,12 +159,20 @@ impl From<BackendError> for io::Error {
 /// All methods take `&self` (not `&mut self`) because positioned I/O
 /// (`pread`/`pwrite`) is safe to share. Backends handle internal
 /// synchronization as needed.
+///
+/// The returned futures may borrow the buffers they are given, so reads can
+/// fill `buf` after they resume.
 pub trait StorageFile: Send + Sync {
   /// Reads exactly `buf.len()` bytes starting at `offset`.
-  fn read_at(&self, offset: u64, buf: &mut [u8]) -> BoxFuture<'_, Result<(), BackendError>>;
+  fn read_at<'a>(
+    &'a self,
+    offset: u64,
+    buf: &'a mut [u8],
+  ) -> BoxFuture<'a, Result<(), BackendError>>;
 
   /// Writes `data` starting at `offset`.
-  fn write_at(&self, offset: u64, data: &[u8]) -> BoxFuture<'_, Result<(), BackendError>>;
+  fn write_at<'a>(&'a self, offset: u64, data: &'a [u8])
+  -> BoxFuture<'a, Result<(), BackendError>>;
 
   /// Returns the current file size in bytes.
   fn len(&self) -> BoxFuture<'_, Result<u64, BackendError>>;
@@ -92,28 +190,36 @@ pub trait StorageFile: Send + Sync {
 }
 
 /// Async filesystem operations scoped to a directory.
+///
+/// See the [module docs](self) for the contract custom backends must uphold.
 pub trait StorageFs: Send + Sync {
   /// Opens an existing file by name.
-  fn open(&self, name: &str) -> BoxFuture<'_, Result<Box<dyn StorageFile>, BackendError>>;
+  fn open<'a>(&'a self, name: &'a str)
+  -> BoxFuture<'a, Result<Box<dyn StorageFile>, BackendError>>;
 
   /// Creates a new file, truncating if it already exists.
-  fn create(&self, name: &str) -> BoxFuture<'_, Result<Box<dyn StorageFile>, BackendError>>;
+  fn create<'a>(
+    &'a self,
+    name: &'a str,
+  ) -> BoxFuture<'a, Result<Box<dyn StorageFile>, BackendError>>;
 
   /// Opens an existing file or creates a new one.
-  fn open_or_create(&self, name: &str)
-  -> BoxFuture<'_, Result<Box<dyn StorageFile>, BackendError>>;
+  fn open_or_create<'a>(
+    &'a self,
+    name: &'a str,
+  ) -> BoxFuture<'a, Result<Box<dyn StorageFile>, BackendError>>;
 
   /// Returns true if a file with the given name exists.
-  fn exists(&self, name: &str) -> BoxFuture<'_, Result<bool, BackendError>>;
+  fn exists<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<bool, BackendError>>;
 
   /// Deletes a file by name.
-  fn delete(&self, name: &str) -> BoxFuture<'_, Result<(), BackendError>>;
+  fn delete<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<(), BackendError>>;
 
   /// Lists all file names in this directory.
   fn list(&self) -> BoxFuture<'_, Result<Vec<String>, BackendError>>;
 
   /// Copies a file from one name to another.
-  fn copy(&self, from: &str, to: &str) -> BoxFuture<'_, Result<(), BackendError>>;
+  fn copy<'a>(&'a self, from: &'a str, to: &'a str) -> BoxFuture<'a, Result<(), BackendError>>;
 }
 
 use super::WorldSave;
@@ -86,22 +86,14 @@ pub fn compute_delta<S: ChunkSeeder>(chunk: &Chunk, pos: ChunkPos, seeder: &S) -
   baseline.set_pos(pos);
   seeder.seed(pos, &mut baseline);
 
-  let mut deltas = Vec::new();
-  let width = CHUNK_SIZE as usize;
-
-  for y in 0..CHUNK_SIZE {
-    for x in 0..CHUNK_SIZE {
-      let current = chunk.pixels[(x, y)];
-      let base = baseline.pixels[(x, y)];
-
-      if current != base {
-        let position = (y as usize * width + x as usize) as u32;
-        deltas.push(DeltaEntry::new(position, current));
-      }
-    }
-  }
-
-  deltas
+  baseline
+    .diff(chunk)
+    .into_iter()
+    .map(|(local, pixel)| {
+      let position = local.y as u32 * CHUNK_SIZE + local.x as u32;
+      DeltaEntry::new(position, pixel)
+    })
+    .collect()
 }
 
 /// Encodes delta entries to compressed bytes.
@@ -5,9 +5,11 @@
 //! - [`PageTableEntry`]: 24-byte index entry mapping chunk position to data
 //!   offset
 //! - [`StorageType`]: Compression strategy (Empty, Delta, Full)
+//! - [`ExploredSectionHeader`]: Header of the optional explored-chunks section
 
 use std::io::{self, Read, Write};
 
+use super::index::PixelBodyIndexEntry;
 use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos, TILE_SIZE};
 use crate::pixel_world::pixel::Pixel;
 
@@ -17,6 +19,10 @@ pub const MAGIC: u32 = 0x5053_5857;
 /// Current format version.
 pub const VERSION: u16 = 1;
 
+/// Header flag: an explored-chunks section follows the committed page table
+/// and entity section.
+pub const FLAG_EXPLORED_SECTION: u16 = 1 << 0;
+
 /// File header (64 bytes, fixed size).
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
@@ -155,6 +161,19 @@ impl Header {
   }
 }
 
+impl Header {
+  /// Returns the file offset just past the committed page table and entity
+  /// section, where the explored section starts when present.
+  pub fn metadata_end(&self, entity_count: u32) -> u64 {
+    if self.entity_section_ptr != 0 {
+      self.entity_section_ptr
+        + (EntitySectionHeader::SIZE + entity_count as usize * PixelBodyIndexEntry::SIZE) as u64
+    } else {
+      self.data_region_ptr + self.page_table_size as u64
+    }
+  }
+}
+
 /// Header validation errors.
 #[derive(Debug)]
 pub enum HeaderError {
@@ -226,8 +245,9 @@ pub struct PageTableEntry {
   pub storage_type: StorageType,
   /// CRC8 checksum for corruption detection.
   pub checksum: u8,
-  /// Alignment padding.
-  pub _reserved: [u8; 2],
+  /// Low 16 bits of the CRC32 of the chunk data, never zero. Zero in saves
+  /// written before data checksums, which skip verification.
+  pub data_checksum: u16,
 }
 
 /// Updates a CRC8 value with a new byte using polynomial 0x07 (CRC-8-CCITT).
@@ -242,6 +262,22 @@ fn crc8_update(crc: &mut u8, byte: u8) {
   }
 }
 
+/// Computes the CRC32 (IEEE, reflected polynomial 0xEDB88320) of `data`.
+pub fn crc32(data: &[u8]) -> u32 {
+  let mut crc = !0u32;
+  for &byte in data {
+    crc ^= byte as u32;
+    for _ in 0..8 {
+      crc = if crc & 1 != 0 {
+        (crc >> 1) ^ 0xEDB8_8320
+      } else {
+        crc >> 1
+      };
+    }
+  }
+  !crc
+}
+
 /// Computes CRC8 checksum over multiple byte slices.
 fn checksum_fields(fields: &[&[u8]]) -> u8 {
   let mut crc: u8 = 0;
@@ -253,6 +289,12 @@ fn checksum_fields(fields: &[&[u8]]) -> u8 {
   crc
 }
 
+/// Folds the CRC32 of chunk data into a page table entry's 16-bit data
+/// checksum, skipping zero, which marks entries without one.
+fn data_checksum(data: &[u8]) -> u16 {
+  (crc32(data) as u16).max(1)
+}
+
 impl PageTableEntry {
   /// Entry size in bytes.
   pub const SIZE: usize = 24;
@@ -266,25 +308,49 @@ impl PageTableEntry {
       data_size,
       storage_type,
       checksum: 0,
-      _reserved: [0; 2],
+      data_checksum: 0,
     };
     entry.checksum = entry.compute_checksum();
     entry
   }
 
+  /// Stores the checksum of the chunk data this entry points to, verified by
+  /// [`verify_data`](Self::verify_data) on load.
+  pub fn with_data_checksum(mut self, data: &[u8]) -> Self {
+    self.data_checksum = data_checksum(data);
+    self.checksum = self.compute_checksum();
+    self
+  }
+
+  /// Returns false if `data` read for this entry does not match its stored
+  /// checksum. Entries without a data checksum always pass.
+  pub fn verify_data(&self, data: &[u8]) -> bool {
+    self.data_checksum == 0 || self.data_checksum == data_checksum(data)
+  }
+
   /// Returns the chunk position for this entry.
   pub fn pos(&self) -> ChunkPos {
     ChunkPos::new(self.chunk_x, self.chunk_y)
   }
 
   /// Computes CRC8 checksum of the entry (excluding checksum field).
+  ///
+  /// Covers the data checksum when there is one, so entries written before
+  /// data checksums keep validating.
   pub fn compute_checksum(&self) -> u8 {
+    let data_checksum = self.data_checksum.to_le_bytes();
+    let data_checksum: &[u8] = if self.data_checksum == 0 {
+      &[]
+    } else {
+      &data_checksum
+    };
     checksum_fields(&[
       &self.chunk_x.to_le_bytes(),
       &self.chunk_y.to_le_bytes(),
       &self.data_offset.to_le_bytes(),
       &self.data_size.to_le_bytes(),
       &[self.storage_type as u8],
+      data_checksum,
     ])
   }
 
@@ -301,7 +367,7 @@ impl PageTableEntry {
     writer.write_all(&self.data_size.to_le_bytes())?;
     writer.write_all(&[self.storage_type as u8])?;
     writer.write_all(&[self.checksum])?;
-    writer.write_all(&self._reserved)?;
+    writer.write_all(&self.data_checksum.to_le_bytes())?;
     Ok(())
   }
 
@@ -319,7 +385,7 @@ impl PageTableEntry {
       data_size: u32::from_le_bytes(buf[16..20].try_into().unwrap()),
       storage_type,
       checksum: buf[21],
-      _reserved: [buf[22], buf[23]],
+      data_checksum: u16::from_le_bytes([buf[22], buf[23]]),
     })
   }
 }
@@ -358,6 +424,66 @@ impl EntitySectionHeader {
   }
 }
 
+/// Explored section header (8 bytes).
+///
+/// Precedes `chunk_count` explored chunk positions, each stored as two
+/// little-endian `i32` (x, y).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExploredSectionHeader {
+  /// Number of explored chunk positions in this section.
+  pub chunk_count: u32,
+  /// Reserved for future use.
+  pub _reserved: u32,
+}
+
+impl ExploredSectionHeader {
+  /// Header size in bytes.
+  pub const SIZE: usize = 8;
+  /// Size of one chunk position entry in bytes.
+  pub const ENTRY_SIZE: usize = 8;
+
+  /// Returns the size of the whole section for `chunk_count` positions.
+  pub fn section_size(chunk_count: usize) -> usize {
+    Self::SIZE + chunk_count * Self::ENTRY_SIZE
+  }
+
+  /// Writes the header followed by the chunk positions.
+  pub fn write_section<W: Write>(writer: &mut W, chunks: &[ChunkPos]) -> io::Result<()> {
+    writer.write_all(&(chunks.len() as u32).to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+    for pos in chunks {
+      writer.write_all(&pos.x.to_le_bytes())?;
+      writer.write_all(&pos.y.to_le_bytes())?;
+    }
+    Ok(())
+  }
+
+  /// Reads a header from a reader.
+  pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+    let mut buf = [0u8; Self::SIZE];
+    reader.read_exact(&mut buf)?;
+    Ok(Self {
+      chunk_count: u32::from_le_bytes(buf[0..4].try_into().unwrap()),
+      _reserved: u32::from_le_bytes(buf[4..8].try_into().unwrap()),
+    })
+  }
+
+  /// Reads the chunk positions following a header.
+  pub fn read_chunks<R: Read>(&self, reader: &mut R) -> io::Result<Vec<ChunkPos>> {
+    let mut chunks = Vec::with_capacity(self.chunk_count as usize);
+    let mut buf = [0u8; Self::ENTRY_SIZE];
+    for _ in 0..self.chunk_count {
+      reader.read_exact(&mut buf)?;
+      chunks.push(ChunkPos::new(
+        i32::from_le_bytes(buf[0..4].try_into().unwrap()),
+        i32::from_le_bytes(buf[4..8].try_into().unwrap()),
+      ));
+    }
+    Ok(chunks)
+  }
+}
+
 /// Fixed-size header for a pixel body record (64 bytes).
 ///
 /// The variable-size data (pixel data, shape mask, extension data) follows
@@ -513,6 +639,52 @@ mod tests {
     assert!(read_entry.validate_checksum());
   }
 
+  #[test]
+  fn explored_section_round_trip() {
+    let chunks = vec![
+      ChunkPos::new(-3, 7),
+      ChunkPos::new(0, 0),
+      ChunkPos::new(12, -1),
+    ];
+    let mut buf = Vec::new();
+    ExploredSectionHeader::write_section(&mut buf, &chunks).unwrap();
+    assert_eq!(buf.len(), ExploredSectionHeader::section_size(chunks.len()));
+
+    let mut cursor = std::io::Cursor::new(&buf);
+    let header = ExploredSectionHeader::read_from(&mut cursor).unwrap();
+    assert_eq!(header.chunk_count, 3);
+    assert_eq!(header.read_chunks(&mut cursor).unwrap(), chunks);
+  }
+
+  #[test]
+  fn crc32_matches_reference() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+  }
+
+  #[test]
+  fn data_checksum_detects_corrupted_data() {
+    let data = vec![7u8; 64];
+    let entry = PageTableEntry::new(ChunkPos::new(0, 0), 100, 64, StorageType::Full)
+      .with_data_checksum(&data);
+    assert!(entry.verify_data(&data));
+    assert!(entry.validate_checksum());
+
+    // The entry checksum covers the data checksum
+    let mut tampered = entry;
+    tampered.data_checksum ^= 0x0100;
+    assert!(!tampered.validate_checksum());
+
+    let mut corrupted = data.clone();
+    corrupted[10] ^= 0x40;
+    assert!(!entry.verify_data(&corrupted));
+
+    // Entries from older saves carry no data checksum
+    let legacy = PageTableEntry::new(ChunkPos::new(0, 0), 100, 64, StorageType::Full);
+    assert_eq!(legacy.data_checksum, 0);
+    assert!(legacy.verify_data(&corrupted));
+  }
+
   #[test]
   fn checksum_detects_corruption() {
     let entry = PageTableEntry::new(ChunkPos::new(1, 2), 100, 50, StorageType::Full);
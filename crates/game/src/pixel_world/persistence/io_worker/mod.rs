@@ -38,6 +38,8 @@ pub enum IoCommand {
   },
   /// Remove a pixel body from persistence.
   RemoveBody { stable_id: u64 },
+  /// Replace the explored chunk set, written on the next flush.
+  SaveExplored { chunks: Vec<IVec2> },
   /// Flush all pending writes to disk.
   Flush,
   /// Delete the current save file and reinitialize empty.
@@ -54,6 +56,8 @@ pub enum IoResult {
     chunk_count: usize,
     body_count: usize,
     world_seed: u64,
+    /// Explored chunk positions stored in the save.
+    explored: Vec<IVec2>,
   },
   /// Chunk data and bodies loaded.
   ChunkLoaded {
@@ -69,6 +73,8 @@ pub enum IoResult {
   BodySaveComplete { stable_id: u64 },
   /// Body removal completed.
   BodyRemoveComplete { stable_id: u64 },
+  /// Explored chunk set stored.
+  ExploredSaveComplete,
   /// Flush completed.
   FlushComplete,
   /// Save file deleted and reinitialized.
@@ -116,6 +122,17 @@ impl IoDispatcher {
     }
   }
 
+  /// Creates a new IoDispatcher that stores saves through a custom backend.
+  ///
+  /// See [`backend`](super::backend) for the contract the backend must
+  /// uphold.
+  #[cfg(not(target_family = "wasm"))]
+  pub fn with_storage(fs: std::sync::Arc<dyn super::backend::StorageFs>) -> Self {
+    Self {
+      inner: NativeIoDispatcher::with_storage(fs),
+    }
+  }
+
   /// Creates a new IoDispatcher for WASM (uses OPFS).
   #[cfg(target_family = "wasm")]
   pub fn new() -> Self {
@@ -12,8 +12,9 @@ use super::{BodyLoadData, ChunkLoadData, IoCommand, IoResult};
 use crate::pixel_world::persistence::backend::StorageFs;
 use crate::pixel_world::persistence::format::{PageTableEntry, StorageType};
 use crate::pixel_world::persistence::index::{ChunkIndex, PixelBodyIndex, PixelBodyIndexEntry};
+use crate::pixel_world::persistence::journal::Journal;
 use crate::pixel_world::persistence::native::NativeFs;
-use crate::pixel_world::persistence::{PixelBodyRecord, WorldSave};
+use crate::pixel_world::persistence::{JournalEntry, PixelBodyRecord, WorldSave};
 
 /// Native I/O dispatcher using a background thread.
 pub struct NativeIoDispatcher {
@@ -27,8 +28,19 @@ pub struct NativeIoDispatcher {
 }
 
 impl NativeIoDispatcher {
-  /// Creates a new native I/O dispatcher with a worker thread.
+  /// Creates a new native I/O dispatcher with a worker thread storing saves
+  /// in `save_dir`.
   pub fn new(save_dir: PathBuf) -> Self {
+    Self::spawn(move || Ok(Arc::new(NativeFs::new(save_dir)?)))
+  }
+
+  /// Creates a new native I/O dispatcher with a worker thread storing saves
+  /// through a custom backend.
+  pub fn with_storage(fs: Arc<dyn StorageFs>) -> Self {
+    Self::spawn(move || Ok(fs))
+  }
+
+  fn spawn(open_fs: impl FnOnce() -> std::io::Result<Arc<dyn StorageFs>> + Send + 'static) -> Self {
     let (cmd_tx, cmd_rx) = async_channel::unbounded::<IoCommand>();
     let (result_tx, result_rx) = async_channel::unbounded::<IoResult>();
     let ready = Arc::new(AtomicBool::new(false));
@@ -37,7 +49,7 @@ impl NativeIoDispatcher {
     let body_count = Arc::new(AtomicU64::new(0));
 
     let worker_handle = thread::spawn(move || {
-      worker_loop(save_dir, cmd_rx, result_tx);
+      bevy::tasks::block_on(worker_loop(open_fs, cmd_rx, result_tx));
     });
 
     Self {
@@ -110,7 +122,7 @@ impl NativeIoDispatcher {
 
 /// Worker state maintained across commands.
 struct WorkerState {
-  fs: NativeFs,
+  fs: Arc<dyn StorageFs>,
   save: Option<WorldSave>,
   chunk_index: ChunkIndex,
   body_index: PixelBodyIndex,
@@ -118,37 +130,45 @@ struct WorkerState {
 }
 
 impl WorkerState {
-  fn new(save_dir: PathBuf) -> std::io::Result<Self> {
-    let fs = NativeFs::new(save_dir)?;
-    Ok(Self {
+  fn new(fs: Arc<dyn StorageFs>) -> Self {
+    Self {
       fs,
       save: None,
       chunk_index: ChunkIndex::new(),
       body_index: PixelBodyIndex::new(),
       data_write_pos: 0,
-    })
+    }
   }
 }
 
 /// Main worker loop running in dedicated thread.
-fn worker_loop(save_dir: PathBuf, cmd_rx: Receiver<IoCommand>, result_tx: Sender<IoResult>) {
-  let mut state = match WorkerState::new(save_dir) {
-    Ok(s) => s,
+///
+/// Backend futures are awaited rather than polled in a loop, so a backend
+/// that completes I/O elsewhere parks the worker until it wakes it.
+async fn worker_loop(
+  open_fs: impl FnOnce() -> std::io::Result<Arc<dyn StorageFs>>,
+  cmd_rx: Receiver<IoCommand>,
+  result_tx: Sender<IoResult>,
+) {
+  let mut state = match open_fs() {
+    Ok(fs) => WorkerState::new(fs),
     Err(e) => {
-      let _ = result_tx.send_blocking(IoResult::Error {
-        message: format!("Failed to initialize worker: {}", e),
-      });
+      let _ = result_tx
+        .send(IoResult::Error {
+          message: format!("Failed to initialize worker: {}", e),
+        })
+        .await;
       return;
     }
   };
 
-  while let Ok(cmd) = cmd_rx.recv_blocking() {
-    let result = handle_command(&mut state, cmd);
+  while let Ok(cmd) = cmd_rx.recv().await {
+    let result = handle_command(&mut state, cmd).await;
 
     // Check if we should shutdown
     let should_shutdown = matches!(result, IoResult::Error { .. }) && result_tx.is_closed();
 
-    let _ = result_tx.send_blocking(result);
+    let _ = result_tx.send(result).await;
 
     if should_shutdown {
       break;
@@ -157,27 +177,32 @@ fn worker_loop(save_dir: PathBuf, cmd_rx: Receiver<IoCommand>, result_tx: Sender
 }
 
 /// Handles a single command and returns the result.
-fn handle_command(state: &mut WorkerState, cmd: IoCommand) -> IoResult {
+async fn handle_command(state: &mut WorkerState, cmd: IoCommand) -> IoResult {
   match cmd {
-    IoCommand::Initialize { path, seed } => handle_initialize(state, path, seed),
-    IoCommand::LoadChunk { chunk_pos } => handle_load_chunk(state, chunk_pos),
-    IoCommand::WriteChunk { chunk_pos, data } => handle_write_chunk(state, chunk_pos, data),
+    IoCommand::Initialize { path, seed } => handle_initialize(state, path, seed).await,
+    IoCommand::LoadChunk { chunk_pos } => handle_load_chunk(state, chunk_pos).await,
+    IoCommand::WriteChunk { chunk_pos, data } => handle_write_chunk(state, chunk_pos, data).await,
     IoCommand::SaveBody {
       record_data,
       stable_id,
-    } => handle_save_body(state, record_data, stable_id),
-    IoCommand::RemoveBody { stable_id } => handle_remove_body(state, stable_id),
-    IoCommand::Flush => handle_flush(state),
-    IoCommand::DeleteSave => handle_delete_save(state),
+    } => handle_save_body(state, record_data, stable_id).await,
+    IoCommand::RemoveBody { stable_id } => handle_remove_body(state, stable_id).await,
+    IoCommand::SaveExplored { chunks } => handle_save_explored(state, chunks),
+    IoCommand::Flush => handle_flush(state).await,
+    IoCommand::DeleteSave => handle_delete_save(state).await,
     IoCommand::Shutdown => {
       // Flush before shutdown
-      let _ = handle_flush(state);
+      let _ = handle_flush(state).await;
       IoResult::FlushComplete
     }
   }
 }
 
-fn handle_initialize(state: &mut WorkerState, path: std::path::PathBuf, seed: u64) -> IoResult {
+async fn handle_initialize(
+  state: &mut WorkerState,
+  path: std::path::PathBuf,
+  seed: u64,
+) -> IoResult {
   // Extract filename from path
   let file_name = path
     .file_name()
@@ -185,11 +210,16 @@ fn handle_initialize(state: &mut WorkerState, path: std::path::PathBuf, seed: u6
     .unwrap_or("world.save")
     .to_string();
 
-  match WorldSave::open_or_create(&state.fs, &file_name, seed) {
+  match WorldSave::open_or_create_async(&*state.fs, &file_name, seed).await {
     Ok(save) => {
       let chunk_count = save.chunk_count();
       let body_count = save.body_count();
       let world_seed = save.world_seed();
+      let explored = save
+        .explored_chunks()
+        .iter()
+        .map(|pos| bevy::math::IVec2::new(pos.x, pos.y))
+        .collect();
 
       // Copy indices to worker state
       state.chunk_index = save.chunk_index().clone();
@@ -201,6 +231,7 @@ fn handle_initialize(state: &mut WorkerState, path: std::path::PathBuf, seed: u6
         chunk_count,
         body_count,
         world_seed,
+        explored,
       }
     }
     Err(e) => IoResult::Error {
@@ -209,7 +240,7 @@ fn handle_initialize(state: &mut WorkerState, path: std::path::PathBuf, seed: u6
   }
 }
 
-fn handle_load_chunk(state: &mut WorkerState, chunk_pos: bevy::math::IVec2) -> IoResult {
+async fn handle_load_chunk(state: &mut WorkerState, chunk_pos: bevy::math::IVec2) -> IoResult {
   let pos = crate::pixel_world::coords::ChunkPos::new(chunk_pos.x, chunk_pos.y);
 
   let Some(ref save) = state.save else {
@@ -221,18 +252,22 @@ fn handle_load_chunk(state: &mut WorkerState, chunk_pos: bevy::math::IVec2) -> I
   // Load chunk data if present
   let chunk_data = if let Some(entry) = state.chunk_index.get(pos) {
     let mut data = vec![0u8; entry.data_size as usize];
-    if let Err(e) =
-      crate::pixel_world::persistence::block_on(save.file.read_at(entry.data_offset, &mut data))
-    {
+    if let Err(e) = save.file.read_at(entry.data_offset, &mut data).await {
       return IoResult::Error {
         message: format!("Failed to read chunk {:?}: {}", pos, e),
       };
     }
-    Some(ChunkLoadData {
-      storage_type: entry.storage_type as u8,
-      data,
-      seeder_needed: entry.storage_type == StorageType::Delta,
-    })
+    if !entry.verify_data(&data) {
+      // Loading nothing makes the chunk generate procedurally
+      warn!("Chunk {:?} failed its checksum, regenerating", pos);
+      None
+    } else {
+      Some(ChunkLoadData {
+        storage_type: entry.storage_type as u8,
+        data,
+        seeder_needed: entry.storage_type == StorageType::Delta,
+      })
+    }
   } else {
     None
   };
@@ -242,9 +277,7 @@ fn handle_load_chunk(state: &mut WorkerState, chunk_pos: bevy::math::IVec2) -> I
 
   for entry in state.body_index.get_chunk(pos) {
     let mut data = vec![0u8; entry.data_size as usize];
-    if let Err(e) =
-      crate::pixel_world::persistence::block_on(save.file.read_at(entry.data_offset, &mut data))
-    {
+    if let Err(e) = save.file.read_at(entry.data_offset, &mut data).await {
       warn!("Failed to read body {}: {}", entry.stable_id, e);
       continue;
     }
@@ -258,14 +291,14 @@ fn handle_load_chunk(state: &mut WorkerState, chunk_pos: bevy::math::IVec2) -> I
   }
 }
 
-fn handle_write_chunk(
+async fn handle_write_chunk(
   state: &mut WorkerState,
   chunk_pos: bevy::math::IVec2,
   data: Vec<u8>,
 ) -> IoResult {
   let pos = crate::pixel_world::coords::ChunkPos::new(chunk_pos.x, chunk_pos.y);
 
-  let Some(ref save) = state.save else {
+  let Some(ref mut save) = state.save else {
     return IoResult::Error {
       message: "No save loaded".to_string(),
     };
@@ -277,9 +310,7 @@ fn handle_write_chunk(
   write_buf.extend_from_slice(&size_bytes);
   write_buf.extend_from_slice(&data);
 
-  if let Err(e) =
-    crate::pixel_world::persistence::block_on(save.file.write_at(state.data_write_pos, &write_buf))
-  {
+  if let Err(e) = save.file.write_at(state.data_write_pos, &write_buf).await {
     return IoResult::Error {
       message: format!("Failed to write chunk {:?}: {}", pos, e),
     };
@@ -291,17 +322,35 @@ fn handle_write_chunk(
     state.data_write_pos + 4, // Skip size prefix
     data.len() as u32,
     StorageType::Full,
-  );
+  )
+  .with_data_checksum(&data);
 
   // Update state
   state.chunk_index.insert(entry);
   state.data_write_pos += 4 + data.len() as u64;
 
+  if let Err(e) = save
+    .append_journal_async(JournalEntry::Chunk {
+      pos,
+      storage_type: StorageType::Full,
+      data,
+    })
+    .await
+  {
+    return IoResult::Error {
+      message: format!("Failed to journal chunk {:?}: {}", pos, e),
+    };
+  }
+
   IoResult::WriteComplete { chunk_pos }
 }
 
-fn handle_save_body(state: &mut WorkerState, record_data: Vec<u8>, stable_id: u64) -> IoResult {
-  let Some(ref save) = state.save else {
+async fn handle_save_body(
+  state: &mut WorkerState,
+  record_data: Vec<u8>,
+  stable_id: u64,
+) -> IoResult {
+  let Some(ref mut save) = state.save else {
     return IoResult::Error {
       message: "No save loaded".to_string(),
     };
@@ -318,9 +367,7 @@ fn handle_save_body(state: &mut WorkerState, record_data: Vec<u8>, stable_id: u6
   };
 
   // Write to file
-  if let Err(e) = crate::pixel_world::persistence::block_on(
-    save.file.write_at(state.data_write_pos, &record_data),
-  ) {
+  if let Err(e) = save.file.write_at(state.data_write_pos, &record_data).await {
     return IoResult::Error {
       message: format!("Failed to write body {}: {}", stable_id, e),
     };
@@ -338,15 +385,47 @@ fn handle_save_body(state: &mut WorkerState, record_data: Vec<u8>, stable_id: u6
   state.body_index.insert(entry);
   state.data_write_pos += record_data.len() as u64;
 
+  if let Err(e) = save
+    .append_journal_async(JournalEntry::Body { record_data })
+    .await
+  {
+    return IoResult::Error {
+      message: format!("Failed to journal body {}: {}", stable_id, e),
+    };
+  }
+
   IoResult::BodySaveComplete { stable_id }
 }
 
-fn handle_remove_body(state: &mut WorkerState, stable_id: u64) -> IoResult {
-  state.body_index.remove(stable_id);
+async fn handle_remove_body(state: &mut WorkerState, stable_id: u64) -> IoResult {
+  if state.body_index.remove(stable_id).is_some()
+    && let Some(ref mut save) = state.save
+    && let Err(e) = save
+      .append_journal_async(JournalEntry::RemoveBody { stable_id })
+      .await
+  {
+    warn!("Failed to journal removal of body {}: {}", stable_id, e);
+  }
   IoResult::BodyRemoveComplete { stable_id }
 }
 
-fn handle_flush(state: &mut WorkerState) -> IoResult {
+fn handle_save_explored(state: &mut WorkerState, chunks: Vec<bevy::math::IVec2>) -> IoResult {
+  let Some(ref mut save) = state.save else {
+    return IoResult::Error {
+      message: "No save loaded".to_string(),
+    };
+  };
+
+  save.set_explored_chunks(
+    chunks
+      .into_iter()
+      .map(|pos| crate::pixel_world::coords::ChunkPos::new(pos.x, pos.y))
+      .collect(),
+  );
+  IoResult::ExploredSaveComplete
+}
+
+async fn handle_flush(state: &mut WorkerState) -> IoResult {
   let Some(ref mut save) = state.save else {
     return IoResult::Error {
       message: "No save loaded".to_string(),
@@ -359,16 +438,18 @@ fn handle_flush(state: &mut WorkerState) -> IoResult {
   save.data_write_pos = state.data_write_pos;
   save.dirty = true;
 
-  if let Err(e) = save.flush() {
+  if let Err(e) = save.flush_async().await {
     return IoResult::Error {
       message: format!("Failed to flush: {}", e),
     };
   }
+  // Flushing moves the write position past metadata it appends
+  state.data_write_pos = save.data_write_pos();
 
   IoResult::FlushComplete
 }
 
-fn handle_delete_save(state: &mut WorkerState) -> IoResult {
+async fn handle_delete_save(state: &mut WorkerState) -> IoResult {
   let Some(ref save) = state.save else {
     return IoResult::Error {
       message: "No save loaded".to_string(),
@@ -381,8 +462,14 @@ fn handle_delete_save(state: &mut WorkerState) -> IoResult {
   // Close the current save first (releases file handle)
   state.save = None;
 
+  if let Err(e) = Journal::delete(&*state.fs, &file_name).await {
+    return IoResult::Error {
+      message: format!("Failed to delete save journal: {}", e),
+    };
+  }
+
   // Delete the save file
-  if let Err(e) = crate::pixel_world::persistence::block_on(state.fs.delete(&file_name)) {
+  if let Err(e) = state.fs.delete(&file_name).await {
     // If file doesn't exist, that's fine - just continue
     if !matches!(
       e,
@@ -395,7 +482,7 @@ fn handle_delete_save(state: &mut WorkerState) -> IoResult {
   }
 
   // Reinitialize with the same seed
-  match WorldSave::open_or_create(&state.fs, &file_name, seed) {
+  match WorldSave::open_or_create_async(&*state.fs, &file_name, seed).await {
     Ok(new_save) => {
       // Reset worker state to fresh
       state.chunk_index = new_save.chunk_index().clone();
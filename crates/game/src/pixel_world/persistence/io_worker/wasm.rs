@@ -200,6 +200,12 @@ fn command_to_js(cmd: &IoCommand) -> JsValue {
       )
       .unwrap();
     }
+    IoCommand::SaveExplored { chunks } => {
+      js_sys::Reflect::set(&obj, &"type".into(), &"SaveExplored".into()).unwrap();
+      let coords: Vec<i32> = chunks.iter().flat_map(|pos| [pos.x, pos.y]).collect();
+      let arr = js_sys::Int32Array::from(coords.as_slice());
+      js_sys::Reflect::set(&obj, &"chunks".into(), &arr).unwrap();
+    }
     IoCommand::Flush => {
       js_sys::Reflect::set(&obj, &"type".into(), &"Flush".into()).unwrap();
     }
@@ -233,10 +239,23 @@ fn parse_worker_message(event: &MessageEvent) -> Option<IoResult> {
       let world_seed = js_sys::Reflect::get(obj, &"worldSeed".into())
         .ok()?
         .as_f64()? as u64;
+      // Flat [x0, y0, x1, y1, ...] pairs; older workers omit it
+      let explored = js_sys::Reflect::get(obj, &"explored".into())
+        .ok()
+        .and_then(|val| val.dyn_into::<js_sys::Int32Array>().ok())
+        .map(|arr| {
+          arr
+            .to_vec()
+            .chunks_exact(2)
+            .map(|pair| IVec2::new(pair[0], pair[1]))
+            .collect()
+        })
+        .unwrap_or_default();
       Some(IoResult::Initialized {
         chunk_count,
         body_count,
         world_seed,
+        explored,
       })
     }
     "ChunkLoaded" => {
@@ -310,6 +329,7 @@ fn parse_worker_message(event: &MessageEvent) -> Option<IoResult> {
         .as_f64()? as u64;
       Some(IoResult::BodyRemoveComplete { stable_id })
     }
+    "ExploredSaveComplete" => Some(IoResult::ExploredSaveComplete),
     "FlushComplete" => Some(IoResult::FlushComplete),
     "DeleteComplete" => Some(IoResult::DeleteComplete),
     "Error" => {
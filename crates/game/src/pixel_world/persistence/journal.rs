@@ -0,0 +1,265 @@
+//! Append-only change journal for crash-safe incremental saves.
+//!
+//! Edits made to a [`WorldSave`](super::WorldSave) between two flushes are
+//! appended to `<save name>.journal`. The journal header records the main
+//! file generation it extends. On open, a journal whose base generation still
+//! matches the main header holds edits the main file never committed and is
+//! replayed. After every successful flush the journal is truncated and
+//! rebased onto the new generation, so a stale journal is never replayed.
+//!
+//! Entries are checksummed; reading stops at the first torn or corrupt entry.
+
+use std::io;
+
+use super::backend::{BackendError, StorageFile, StorageFs};
+use super::format::{Header, StorageType};
+use crate::pixel_world::coords::ChunkPos;
+
+/// Magic number: "PXJL" in little-endian.
+const MAGIC: u32 = 0x4C4A_5850;
+
+/// Current journal format version.
+const VERSION: u16 = 1;
+
+/// Journal header size in bytes.
+const HEADER_SIZE: usize = 24;
+
+/// Entry prefix size: tag (1) + payload length (4) + checksum (4).
+const ENTRY_PREFIX_SIZE: usize = 9;
+
+const TAG_CHUNK: u8 = 1;
+const TAG_BODY: u8 = 2;
+const TAG_REMOVE_BODY: u8 = 3;
+
+/// Identifies one committed state of the main save file.
+///
+/// Every flush writes the page table outside the committed one, so the data
+/// region pointer changes from one committed state to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Generation {
+  data_region_ptr: u64,
+  modified_time: u64,
+}
+
+impl Generation {
+  /// Returns the generation described by a main file header.
+  pub(crate) fn of(header: &Header) -> Self {
+    Self {
+      data_region_ptr: header.data_region_ptr,
+      modified_time: header.modified_time,
+    }
+  }
+}
+
+/// A single journaled edit.
+#[derive(Debug)]
+pub(crate) enum JournalEntry {
+  /// Encoded chunk data, as stored in the data region.
+  Chunk {
+    pos: ChunkPos,
+    storage_type: StorageType,
+    data: Vec<u8>,
+  },
+  /// Serialized pixel body record.
+  Body { record_data: Vec<u8> },
+  /// Removal of a pixel body from the index.
+  RemoveBody { stable_id: u64 },
+}
+
+impl JournalEntry {
+  fn tag(&self) -> u8 {
+    match self {
+      Self::Chunk { .. } => TAG_CHUNK,
+      Self::Body { .. } => TAG_BODY,
+      Self::RemoveBody { .. } => TAG_REMOVE_BODY,
+    }
+  }
+
+  fn payload(&self) -> Vec<u8> {
+    match self {
+      Self::Chunk {
+        pos,
+        storage_type,
+        data,
+      } => {
+        let mut buf = Vec::with_capacity(9 + data.len());
+        buf.extend_from_slice(&pos.x.to_le_bytes());
+        buf.extend_from_slice(&pos.y.to_le_bytes());
+        buf.push(*storage_type as u8);
+        buf.extend_from_slice(data);
+        buf
+      }
+      Self::Body { record_data } => record_data.clone(),
+      Self::RemoveBody { stable_id } => stable_id.to_le_bytes().to_vec(),
+    }
+  }
+
+  fn from_payload(tag: u8, payload: &[u8]) -> Option<Self> {
+    match tag {
+      TAG_CHUNK => {
+        let x = i32::from_le_bytes(payload.get(0..4)?.try_into().ok()?);
+        let y = i32::from_le_bytes(payload.get(4..8)?.try_into().ok()?);
+        let storage_type = StorageType::from_u8(*payload.get(8)?)?;
+        Some(Self::Chunk {
+          pos: ChunkPos::new(x, y),
+          storage_type,
+          data: payload[9..].to_vec(),
+        })
+      }
+      TAG_BODY => Some(Self::Body {
+        record_data: payload.to_vec(),
+      }),
+      TAG_REMOVE_BODY => Some(Self::RemoveBody {
+        stable_id: u64::from_le_bytes(payload.try_into().ok()?),
+      }),
+      _ => None,
+    }
+  }
+}
+
+/// FNV-1a hash used to detect torn or corrupt entries.
+fn checksum(tag: u8, payload: &[u8]) -> u32 {
+  let mut hash: u32 = 0x811C_9DC5;
+  for &byte in std::iter::once(&tag).chain(payload) {
+    hash ^= byte as u32;
+    hash = hash.wrapping_mul(0x0100_0193);
+  }
+  hash
+}
+
+fn encode_header(base: Generation) -> [u8; HEADER_SIZE] {
+  let mut buf = [0u8; HEADER_SIZE];
+  buf[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+  buf[4..6].copy_from_slice(&VERSION.to_le_bytes());
+  buf[8..16].copy_from_slice(&base.data_region_ptr.to_le_bytes());
+  buf[16..24].copy_from_slice(&base.modified_time.to_le_bytes());
+  buf
+}
+
+fn decode_header(buf: &[u8]) -> Option<Generation> {
+  let magic = u32::from_le_bytes(buf.get(0..4)?.try_into().ok()?);
+  let version = u16::from_le_bytes(buf.get(4..6)?.try_into().ok()?);
+  if magic != MAGIC || version != VERSION {
+    return None;
+  }
+  Some(Generation {
+    data_region_ptr: u64::from_le_bytes(buf.get(8..16)?.try_into().ok()?),
+    modified_time: u64::from_le_bytes(buf.get(16..24)?.try_into().ok()?),
+  })
+}
+
+/// Decodes entries following the header, stopping at the first invalid one.
+///
+/// Returns the entries and the offset just past the last valid entry.
+fn decode_entries(buf: &[u8]) -> (Vec<JournalEntry>, usize) {
+  let mut entries = Vec::new();
+  let mut pos = HEADER_SIZE;
+
+  while let Some(prefix) = buf.get(pos..pos + ENTRY_PREFIX_SIZE) {
+    let tag = prefix[0];
+    let len = u32::from_le_bytes(prefix[1..5].try_into().unwrap()) as usize;
+    let expected = u32::from_le_bytes(prefix[5..9].try_into().unwrap());
+    let start = pos + ENTRY_PREFIX_SIZE;
+    // A corrupt length may overflow on 32-bit targets
+    let Some(end) = start.checked_add(len) else {
+      break;
+    };
+    let Some(payload) = buf.get(start..end) else {
+      break;
+    };
+    if checksum(tag, payload) != expected {
+      break;
+    }
+    let Some(entry) = JournalEntry::from_payload(tag, payload) else {
+      break;
+    };
+    entries.push(entry);
+    pos = end;
+  }
+
+  (entries, pos)
+}
+
+/// Open change journal of a save file.
+pub(crate) struct Journal {
+  file: Box<dyn StorageFile>,
+  write_pos: u64,
+}
+
+impl Journal {
+  /// Returns the journal file name for a save file.
+  pub(crate) fn file_name(save_name: &str) -> String {
+    format!("{}.journal", save_name)
+  }
+
+  /// Opens the journal of `save_name`, creating it if missing.
+  ///
+  /// Returns the entries to replay if the journal extends `base`. Otherwise
+  /// the journal is stale and is reset onto `base`.
+  pub(crate) async fn open(
+    fs: &dyn StorageFs,
+    save_name: &str,
+    base: Generation,
+  ) -> io::Result<(Self, Vec<JournalEntry>)> {
+    let file = fs.open_or_create(&Self::file_name(save_name)).await?;
+    let len = file.len().await?;
+    let mut buf = vec![0u8; len as usize];
+    file.read_at(0, &mut buf).await?;
+
+    let mut journal = Self { file, write_pos: 0 };
+    if decode_header(&buf) == Some(base) {
+      let (entries, end) = decode_entries(&buf);
+      journal.write_pos = end as u64;
+      return Ok((journal, entries));
+    }
+
+    journal.reset(base).await?;
+    Ok((journal, Vec::new()))
+  }
+
+  /// Creates an empty journal for a freshly created save file.
+  pub(crate) async fn create(
+    fs: &dyn StorageFs,
+    save_name: &str,
+    base: Generation,
+  ) -> io::Result<Self> {
+    let file = fs.create(&Self::file_name(save_name)).await?;
+    let mut journal = Self { file, write_pos: 0 };
+    journal.reset(base).await?;
+    Ok(journal)
+  }
+
+  /// Deletes the journal of `save_name`, if any.
+  pub(crate) async fn delete(fs: &dyn StorageFs, save_name: &str) -> io::Result<()> {
+    match fs.delete(&Self::file_name(save_name)).await {
+      Ok(()) | Err(BackendError::NotFound) => Ok(()),
+      Err(e) => Err(e.into()),
+    }
+  }
+
+  /// Appends an entry and syncs it to disk.
+  pub(crate) async fn append(&mut self, entry: &JournalEntry) -> io::Result<()> {
+    let tag = entry.tag();
+    let payload = entry.payload();
+
+    let mut buf = Vec::with_capacity(ENTRY_PREFIX_SIZE + payload.len());
+    buf.push(tag);
+    buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&checksum(tag, &payload).to_le_bytes());
+    buf.extend_from_slice(&payload);
+
+    self.file.write_at(self.write_pos, &buf).await?;
+    self.file.sync().await?;
+    self.write_pos += buf.len() as u64;
+    Ok(())
+  }
+
+  /// Discards all entries and rebases the journal onto `base`.
+  pub(crate) async fn reset(&mut self, base: Generation) -> io::Result<()> {
+    self.file.set_len(0).await?;
+    self.file.write_at(0, &encode_header(base)).await?;
+    self.file.sync().await?;
+    self.write_pos = HEADER_SIZE as u64;
+    Ok(())
+  }
+}
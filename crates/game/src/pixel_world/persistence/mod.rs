@@ -11,6 +11,7 @@ pub mod compression;
 pub mod format;
 pub mod index;
 pub mod io_worker;
+mod journal;
 #[cfg(not(target_family = "wasm"))]
 pub mod native;
 #[cfg(target_family = "wasm")]
@@ -28,9 +29,14 @@ use compression::{
   apply_delta, compute_delta, decode_delta, decode_full, encode_delta, encode_full,
   should_use_delta,
 };
-use format::{EntitySectionHeader, Header, HeaderError, PageTableEntry, StorageType};
+use format::{
+  EntitySectionHeader, ExploredSectionHeader, FLAG_EXPLORED_SECTION, Header, HeaderError,
+  PageTableEntry, StorageType,
+};
 use index::{ChunkIndex, PixelBodyIndex, PixelBodyIndexEntry};
 pub use io_worker::{IoCommand, IoDispatcher, IoResult};
+pub(crate) use journal::JournalEntry;
+use journal::{Generation, Journal};
 // Re-export backend implementations
 #[cfg(not(target_family = "wasm"))]
 pub use native::NativePersistence;
@@ -38,7 +44,8 @@ pub use native::NativePersistence;
 pub use opfs::WasmPersistence;
 pub use pixel_body::{PixelBodyReadError, PixelBodyRecord};
 
-use crate::pixel_world::coords::ChunkPos;
+use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos, WorldRect};
+use crate::pixel_world::pixel::PixelFlags;
 use crate::pixel_world::primitives::Chunk;
 use crate::pixel_world::seeding::ChunkSeeder;
 
@@ -65,10 +72,12 @@ pub fn default_save_dir(_app_name: &str) -> PathBuf {
   PathBuf::from(".")
 }
 
-/// Polls a future that is expected to be immediately ready.
+/// Polls a future that is expected to be ready soon.
 ///
-/// All native backend futures resolve on first poll. This helper avoids
-/// pulling in a full async runtime for what is synchronous I/O.
+/// All built-in native backend futures resolve on first poll. This helper
+/// avoids pulling in a full async runtime for what is synchronous I/O. The
+/// I/O worker awaits backend futures instead, so only the synchronous
+/// `WorldSave` API goes through here.
 pub(crate) fn block_on<T>(fut: backend::BoxFuture<'_, T>) -> T {
   use std::task::{Context, Poll, Waker};
 
@@ -132,10 +141,21 @@ pub struct WorldSave {
   pub(crate) index: ChunkIndex,
   /// Runtime index for pixel bodies.
   pub(crate) body_index: PixelBodyIndex,
+  /// Chunks the player has explored, for fog of war.
+  pub(crate) explored: Vec<ChunkPos>,
   /// Current write position in data region (for append).
   pub(crate) data_write_pos: u64,
+  /// Region `(offset, len)` holding the committed page table, entity
+  /// section and explored section.
+  committed_metadata: (u64, u64),
+  /// Region `(offset, len)` of the metadata committed before that, free for
+  /// the next flush to reuse.
+  spare_metadata: Option<(u64, u64)>,
   /// Whether the save has been modified since last flush.
   pub(crate) dirty: bool,
+  /// Change journal of edits since the last flush.
+  /// `None` until the save has been opened or created.
+  pub(crate) journal: Option<Journal>,
 }
 
 impl WorldSave {
@@ -164,6 +184,13 @@ impl WorldSave {
     .map_err(OpenError::from)
   }
 
+  /// Parses explored chunk positions from explored section bytes.
+  fn parse_explored(buf: &[u8]) -> Result<Vec<ChunkPos>, OpenError> {
+    let mut cursor = Cursor::new(buf);
+    let header = ExploredSectionHeader::read_from(&mut cursor)?;
+    Ok(header.read_chunks(&mut cursor)?)
+  }
+
   /// Constructs a new WorldSave for a freshly created file.
   fn new_empty(name: &str, file: Box<dyn StorageFile>, world_seed: u64) -> Self {
     Self {
@@ -172,80 +199,186 @@ impl WorldSave {
       header: Header::new(world_seed),
       index: ChunkIndex::new(),
       body_index: PixelBodyIndex::new(),
+      explored: Vec::new(),
       data_write_pos: Header::SIZE as u64,
+      committed_metadata: (Header::SIZE as u64, 0),
+      spare_metadata: None,
       dirty: false,
+      journal: None,
     }
   }
 
   /// Constructs a WorldSave from parsed components.
+  ///
+  /// Appends continue at `file_len`, past the committed page table and
+  /// entity section.
   fn from_parsed(
     name: &str,
     file: Box<dyn StorageFile>,
     header: Header,
     index: ChunkIndex,
     body_index: PixelBodyIndex,
+    explored: Vec<ChunkPos>,
+    file_len: u64,
   ) -> Self {
-    let data_write_pos = header.data_region_ptr;
-    Self {
+    let data_write_pos = file_len.max(header.data_region_ptr);
+    let mut save = Self {
       name: name.to_string(),
       file: Arc::from(file),
       header,
       index,
       body_index,
+      explored,
       data_write_pos,
+      committed_metadata: (0, 0),
+      spare_metadata: None,
       dirty: false,
-    }
+      journal: None,
+    };
+    save.committed_metadata = (save.header.data_region_ptr, save.metadata_size());
+    save
   }
 
   /// Creates a new save file with the given name via a storage backend.
   pub fn create(fs: &dyn StorageFs, name: &str, world_seed: u64) -> io::Result<Self> {
-    let file = block_on(fs.create(name)).map_err(io::Error::from)?;
+    block_on(Box::pin(Self::create_async(fs, name, world_seed)))
+  }
+
+  /// Creates a new save file asynchronously.
+  ///
+  /// Awaits every backend operation instead of blocking on it, for backends
+  /// whose futures don't resolve immediately (OPFS, remote storage).
+  pub async fn create_async(fs: &dyn StorageFs, name: &str, world_seed: u64) -> io::Result<Self> {
+    let file = fs.create(name).await?;
 
-    let save = Self::new_empty(name, file, world_seed);
+    let mut save = Self::new_empty(name, file, world_seed);
 
     // Serialize and write initial header
     let mut buf = Vec::new();
     save.header.write_to(&mut buf)?;
-    block_on(save.file.write_at(0, &buf)).map_err(io::Error::from)?;
-    block_on(save.file.sync()).map_err(io::Error::from)?;
+    save.file.write_at(0, &buf).await?;
+    save.file.sync().await?;
+
+    save.journal = Some(Journal::create(fs, name, Generation::of(&save.header)).await?);
 
     Ok(save)
   }
 
   /// Opens an existing save file via a storage backend.
+  ///
+  /// Edits journaled after the last flush are replayed and committed.
   pub fn open(fs: &dyn StorageFs, name: &str) -> Result<Self, OpenError> {
-    let file = block_on(fs.open(name)).map_err(|e| OpenError::Io(io::Error::from(e)))?;
+    block_on(Box::pin(Self::open_async(fs, name)))
+  }
+
+  /// Opens an existing save file asynchronously.
+  ///
+  /// Edits journaled after the last flush are replayed and committed.
+  pub async fn open_async(fs: &dyn StorageFs, name: &str) -> Result<Self, OpenError> {
+    let file = fs.open(name).await.map_err(io::Error::from)?;
 
     // Read and parse header
     let mut header_buf = [0u8; Header::SIZE];
-    block_on(file.read_at(0, &mut header_buf)).map_err(|e| OpenError::Io(io::Error::from(e)))?;
+    file
+      .read_at(0, &mut header_buf)
+      .await
+      .map_err(io::Error::from)?;
     let header = Self::parse_header(&header_buf)?;
 
     // Read and parse page table
     let page_table_size = header.chunk_count as usize * PageTableEntry::SIZE;
     let mut page_table_buf = vec![0u8; page_table_size];
-    block_on(file.read_at(header.data_region_ptr, &mut page_table_buf))
-      .map_err(|e| OpenError::Io(io::Error::from(e)))?;
+    file
+      .read_at(header.data_region_ptr, &mut page_table_buf)
+      .await
+      .map_err(io::Error::from)?;
     let index = Self::parse_chunk_index(&page_table_buf, header.chunk_count as usize)?;
 
     // Read and parse entity section if present
-    let body_index = if header.entity_section_ptr != 0 {
+    let (body_index, entity_count) = if header.entity_section_ptr != 0 {
       let mut entity_header_buf = [0u8; EntitySectionHeader::SIZE];
-      block_on(file.read_at(header.entity_section_ptr, &mut entity_header_buf))
-        .map_err(|e| OpenError::Io(io::Error::from(e)))?;
+      file
+        .read_at(header.entity_section_ptr, &mut entity_header_buf)
+        .await
+        .map_err(io::Error::from)?;
       let entity_header = EntitySectionHeader::read_from(&mut Cursor::new(&entity_header_buf))?;
 
       let body_index_size = entity_header.entity_count as usize * PixelBodyIndexEntry::SIZE;
       let mut body_index_buf = vec![0u8; body_index_size];
       let body_data_offset = header.entity_section_ptr + EntitySectionHeader::SIZE as u64;
-      block_on(file.read_at(body_data_offset, &mut body_index_buf))
-        .map_err(|e| OpenError::Io(io::Error::from(e)))?;
-      Self::parse_body_index(&entity_header_buf, &body_index_buf)?
+      file
+        .read_at(body_data_offset, &mut body_index_buf)
+        .await
+        .map_err(io::Error::from)?;
+      (
+        Self::parse_body_index(&entity_header_buf, &body_index_buf)?,
+        entity_header.entity_count,
+      )
+    } else {
+      (PixelBodyIndex::new(), 0)
+    };
+
+    // Read explored section if flagged; it follows the entity section
+    let explored = if header.flags & FLAG_EXPLORED_SECTION != 0 {
+      let explored_ptr = header.metadata_end(entity_count);
+      let mut explored_header_buf = [0u8; ExploredSectionHeader::SIZE];
+      file
+        .read_at(explored_ptr, &mut explored_header_buf)
+        .await
+        .map_err(io::Error::from)?;
+      let explored_header =
+        ExploredSectionHeader::read_from(&mut Cursor::new(&explored_header_buf))?;
+      let mut explored_buf =
+        vec![0u8; ExploredSectionHeader::section_size(explored_header.chunk_count as usize)];
+      file
+        .read_at(explored_ptr, &mut explored_buf)
+        .await
+        .map_err(io::Error::from)?;
+      Self::parse_explored(&explored_buf)?
     } else {
-      PixelBodyIndex::new()
+      Vec::new()
     };
 
-    Ok(Self::from_parsed(name, file, header, index, body_index))
+    let file_len = file.len().await.map_err(io::Error::from)?;
+    let mut save = Self::from_parsed(name, file, header, index, body_index, explored, file_len);
+
+    let (journal, entries) = Journal::open(fs, name, Generation::of(&save.header)).await?;
+    save.journal = Some(journal);
+    save.replay_journal(entries).await?;
+
+    Ok(save)
+  }
+
+  /// Applies journaled edits the main file never committed, then flushes.
+  async fn replay_journal(&mut self, entries: Vec<JournalEntry>) -> io::Result<()> {
+    if entries.is_empty() {
+      return Ok(());
+    }
+
+    let count = entries.len();
+    for entry in entries {
+      match entry {
+        JournalEntry::Chunk {
+          pos,
+          storage_type,
+          data,
+        } => self.append_chunk_data(pos, storage_type, &data).await?,
+        JournalEntry::Body { record_data } => {
+          let record = PixelBodyRecord::read_from(&mut Cursor::new(&record_data))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+          self
+            .append_body_data(record.stable_id, record.chunk_pos(), &record_data)
+            .await?;
+        }
+        JournalEntry::RemoveBody { stable_id } => {
+          self.body_index.remove(stable_id);
+        }
+      }
+    }
+
+    info!("Replayed {} journaled edits into '{}'", count, self.name);
+    self.dirty = true;
+    self.flush_async().await
   }
 
   /// Opens an existing save file or creates a new one.
@@ -254,119 +387,26 @@ impl WorldSave {
     name: &str,
     world_seed: u64,
   ) -> Result<Self, OpenError> {
-    let exists = block_on(fs.exists(name)).map_err(|e| OpenError::Io(io::Error::from(e)))?;
-    if exists {
-      Self::open(fs, name)
-    } else {
-      Ok(Self::create(fs, name, world_seed)?)
-    }
+    block_on(Box::pin(Self::open_or_create_async(fs, name, world_seed)))
   }
 
   /// Opens an existing save file or creates a new one asynchronously.
   ///
-  /// This is the WASM-compatible version that uses `.await` instead of
-  /// `block_on()`. Required because OPFS operations return actual async
-  /// futures that cannot be polled to completion synchronously.
-  #[cfg(target_family = "wasm")]
+  /// Used by the I/O worker and by OPFS, whose operations return actual
+  /// async futures that cannot be polled to completion synchronously.
   pub async fn open_or_create_async(
     fs: &dyn StorageFs,
     name: &str,
     world_seed: u64,
-  ) -> Result<Self, String> {
-    let exists = fs
-      .exists(name)
-      .await
-      .map_err(|e| format!("Failed to check file existence: {}", e))?;
-
+  ) -> Result<Self, OpenError> {
+    let exists = fs.exists(name).await.map_err(io::Error::from)?;
     if exists {
       Self::open_async(fs, name).await
     } else {
-      Self::create_async(fs, name, world_seed).await
+      Ok(Self::create_async(fs, name, world_seed).await?)
     }
   }
 
-  /// Creates a new save file asynchronously (WASM).
-  #[cfg(target_family = "wasm")]
-  async fn create_async(fs: &dyn StorageFs, name: &str, world_seed: u64) -> Result<Self, String> {
-    let file = fs
-      .create(name)
-      .await
-      .map_err(|e| format!("Failed to create file: {}", e))?;
-
-    let save = Self::new_empty(name, file, world_seed);
-
-    // Serialize and write initial header
-    let mut buf = Vec::new();
-    save
-      .header
-      .write_to(&mut buf)
-      .map_err(|e| format!("Failed to serialize header: {}", e))?;
-    save
-      .file
-      .write_at(0, &buf)
-      .await
-      .map_err(|e| format!("Failed to write header: {}", e))?;
-    save
-      .file
-      .sync()
-      .await
-      .map_err(|e| format!("Failed to sync file: {}", e))?;
-
-    Ok(save)
-  }
-
-  /// Opens an existing save file asynchronously (WASM).
-  #[cfg(target_family = "wasm")]
-  async fn open_async(fs: &dyn StorageFs, name: &str) -> Result<Self, String> {
-    let file = fs
-      .open(name)
-      .await
-      .map_err(|e| format!("Failed to open file: {}", e))?;
-
-    // Read and parse header
-    let mut header_buf = [0u8; Header::SIZE];
-    file
-      .read_at(0, &mut header_buf)
-      .await
-      .map_err(|e| format!("Failed to read header: {}", e))?;
-    let header = Self::parse_header(&header_buf).map_err(|e| format!("Invalid header: {}", e))?;
-
-    // Read and parse page table
-    let page_table_size = header.chunk_count as usize * PageTableEntry::SIZE;
-    let mut page_table_buf = vec![0u8; page_table_size];
-    file
-      .read_at(header.data_region_ptr, &mut page_table_buf)
-      .await
-      .map_err(|e| format!("Failed to read page table: {}", e))?;
-    let index = Self::parse_chunk_index(&page_table_buf, header.chunk_count as usize)
-      .map_err(|e| format!("Invalid page table: {}", e))?;
-
-    // Read and parse entity section if present
-    let body_index = if header.entity_section_ptr != 0 {
-      let mut entity_header_buf = [0u8; EntitySectionHeader::SIZE];
-      file
-        .read_at(header.entity_section_ptr, &mut entity_header_buf)
-        .await
-        .map_err(|e| format!("Failed to read entity header: {}", e))?;
-      let entity_header = EntitySectionHeader::read_from(&mut Cursor::new(&entity_header_buf))
-        .map_err(|e| format!("Invalid entity header: {}", e))?;
-
-      let body_index_size = entity_header.entity_count as usize * PixelBodyIndexEntry::SIZE;
-      let mut body_index_buf = vec![0u8; body_index_size];
-      let body_data_offset = header.entity_section_ptr + EntitySectionHeader::SIZE as u64;
-      file
-        .read_at(body_data_offset, &mut body_index_buf)
-        .await
-        .map_err(|e| format!("Failed to read body index: {}", e))?;
-      Self::parse_body_index(&entity_header_buf, &body_index_buf)
-        .map_err(|e| format!("Invalid body index: {}", e))?
-    } else {
-      PixelBodyIndex::new()
-    };
-
-    Ok(Self::from_parsed(name, file, header, index, body_index))
-  }
-
   /// Returns the save file name.
   pub fn name(&self) -> &str {
     &self.name
@@ -387,6 +427,31 @@ impl WorldSave {
     self.index.len()
   }
 
+  /// Returns true if any persisted chunk overlaps `rect`.
+  ///
+  /// Only consults the in-memory chunk index, so no chunk data is read.
+  /// Chunks whose save is still in flight are reported once it completes.
+  pub fn has_edits_in(&self, rect: WorldRect) -> bool {
+    self.edited_chunks_in(rect).next().is_some()
+  }
+
+  /// Returns the positions of persisted chunks overlapping `rect`, in no
+  /// particular order.
+  ///
+  /// Like [`Self::has_edits_in`], this scans the chunk index without reading
+  /// chunk data.
+  pub fn edited_chunk_positions_in(&self, rect: WorldRect) -> Vec<ChunkPos> {
+    self.edited_chunks_in(rect).collect()
+  }
+
+  /// Iterates persisted chunk positions whose area overlaps `rect`.
+  fn edited_chunks_in(&self, rect: WorldRect) -> impl Iterator<Item = ChunkPos> + '_ {
+    self.index.iter().map(|(&pos, _)| pos).filter(move |pos| {
+      let origin = pos.to_world();
+      WorldRect::new(origin.x, origin.y, CHUNK_SIZE, CHUNK_SIZE).intersects(&rect)
+    })
+  }
+
   /// Returns the number of persisted pixel bodies.
   pub fn body_count(&self) -> usize {
     self.body_index.len()
@@ -397,6 +462,17 @@ impl WorldSave {
     self.body_index.contains(stable_id)
   }
 
+  /// Returns the explored chunk positions stored in the save.
+  pub fn explored_chunks(&self) -> &[ChunkPos] {
+    &self.explored
+  }
+
+  /// Replaces the explored chunk positions; written on the next flush.
+  pub fn set_explored_chunks(&mut self, chunks: Vec<ChunkPos>) {
+    self.explored = chunks;
+    self.dirty = true;
+  }
+
   /// Returns all pixel body records for a given chunk.
   pub fn load_bodies_for_chunk(&self, pos: ChunkPos) -> Vec<PixelBodyRecord> {
     let mut records = Vec::new();
@@ -430,20 +506,32 @@ impl WorldSave {
     let mut buf = Vec::new();
     record.write_to(&mut buf)?;
 
-    // Write record data at current write position
-    block_on(self.file.write_at(self.data_write_pos, &buf)).map_err(io::Error::from)?;
+    block_on(Box::pin(self.append_body_data(
+      record.stable_id,
+      record.chunk_pos(),
+      &buf,
+    )))?;
+    self.append_journal(JournalEntry::Body { record_data: buf })
+  }
+
+  /// Writes serialized body data at the write position and indexes it.
+  async fn append_body_data(
+    &mut self,
+    stable_id: u64,
+    chunk_pos: ChunkPos,
+    data: &[u8],
+  ) -> io::Result<()> {
+    self.file.write_at(self.data_write_pos, data).await?;
 
-    // Create index entry
     let entry = PixelBodyIndexEntry {
-      stable_id: record.stable_id,
+      stable_id,
       data_offset: self.data_write_pos,
-      data_size: buf.len() as u32,
-      chunk_pos: record.chunk_pos(),
+      data_size: data.len() as u32,
+      chunk_pos,
     };
 
-    // Update state
     self.body_index.insert(entry);
-    self.data_write_pos += buf.len() as u64;
+    self.data_write_pos += data.len() as u64;
     self.dirty = true;
 
     Ok(())
@@ -456,6 +544,7 @@ impl WorldSave {
   pub fn remove_body(&mut self, stable_id: u64) {
     if self.body_index.remove(stable_id).is_some() {
       self.dirty = true;
+      self.journal_removal(stable_id);
     }
   }
 
@@ -465,12 +554,38 @@ impl WorldSave {
     if !removed.is_empty() {
       self.dirty = true;
     }
+    for entry in removed {
+      self.journal_removal(entry.stable_id);
+    }
+  }
+
+  fn journal_removal(&mut self, stable_id: u64) {
+    if let Err(e) = self.append_journal(JournalEntry::RemoveBody { stable_id }) {
+      warn!(
+        "Failed to journal removal of pixel body {}: {}",
+        stable_id, e
+      );
+    }
+  }
+
+  /// Records an edit in the change journal until the next flush.
+  pub(crate) fn append_journal(&mut self, entry: JournalEntry) -> io::Result<()> {
+    block_on(Box::pin(self.append_journal_async(entry)))
+  }
+
+  /// Records an edit in the change journal asynchronously.
+  pub(crate) async fn append_journal_async(&mut self, entry: JournalEntry) -> io::Result<()> {
+    match &mut self.journal {
+      Some(journal) => journal.append(&entry).await,
+      None => Ok(()),
+    }
   }
 
   /// Loads a chunk from the save file.
   ///
   /// Returns None if the chunk is not persisted.
-  /// On error, returns None and logs a warning.
+  /// On error or checksum mismatch, returns None and logs a warning, so the
+  /// chunk is generated procedurally instead.
   pub fn load_chunk<S: ChunkSeeder>(&self, pos: ChunkPos, _seeder: &S) -> Option<LoadedChunk> {
     let entry = self.index.get(pos)?;
 
@@ -480,12 +595,17 @@ impl WorldSave {
       warn!("Failed to read chunk {:?}: {}", pos, e);
       return None;
     }
+    if !entry.verify_data(&data) {
+      warn!("Chunk {:?} failed its checksum, regenerating", pos);
+      return None;
+    }
 
     Some(LoadedChunk {
       storage_type: entry.storage_type,
       data,
       pos,
       seeder_needed: entry.storage_type == StorageType::Delta,
+      clear_flags: PixelFlags::empty(),
     })
   }
 
@@ -506,12 +626,27 @@ impl WorldSave {
       (StorageType::Full, encode_full(chunk))
     };
 
+    block_on(Box::pin(self.append_chunk_data(pos, storage_type, &data)))?;
+    self.append_journal(JournalEntry::Chunk {
+      pos,
+      storage_type,
+      data,
+    })
+  }
+
+  /// Writes encoded chunk data at the write position and indexes it.
+  async fn append_chunk_data(
+    &mut self,
+    pos: ChunkPos,
+    storage_type: StorageType,
+    data: &[u8],
+  ) -> io::Result<()> {
     // Write size prefix + data
     let size_bytes = (data.len() as u32).to_le_bytes();
     let mut write_buf = Vec::with_capacity(4 + data.len());
     write_buf.extend_from_slice(&size_bytes);
-    write_buf.extend_from_slice(&data);
-    block_on(self.file.write_at(self.data_write_pos, &write_buf)).map_err(io::Error::from)?;
+    write_buf.extend_from_slice(data);
+    self.file.write_at(self.data_write_pos, &write_buf).await?;
 
     // Create page table entry
     let entry = PageTableEntry::new(
@@ -519,7 +654,8 @@ impl WorldSave {
       self.data_write_pos + 4, // Skip size prefix
       data.len() as u32,
       storage_type,
-    );
+    )
+    .with_data_checksum(data);
 
     // Update state
     self.index.insert(entry);
@@ -573,16 +709,21 @@ impl WorldSave {
     self.header.page_table_size = self.index.serialized_size() as u32;
   }
 
-  /// Writes the page table to the file at the current data write position.
-  fn write_page_table(&self) -> io::Result<()> {
+  /// Writes the page table to the file at `page_table_start`.
+  async fn write_page_table(&self, page_table_start: u64) -> io::Result<()> {
     let mut page_table_buf = Vec::new();
     self.index.write_to(&mut page_table_buf)?;
-    block_on(self.file.write_at(self.data_write_pos, &page_table_buf)).map_err(io::Error::from)
+    Ok(
+      self
+        .file
+        .write_at(page_table_start, &page_table_buf)
+        .await?,
+    )
   }
 
   /// Writes the entity section if bodies exist, returns the section start
   /// offset.
-  fn write_entity_section(&self, entity_section_start: u64) -> io::Result<()> {
+  async fn write_entity_section(&self, entity_section_start: u64) -> io::Result<()> {
     if self.body_index.is_empty() {
       return Ok(());
     }
@@ -596,41 +737,118 @@ impl WorldSave {
     entity_header.write_to(&mut entity_buf)?;
     self.body_index.write_to(&mut entity_buf)?;
 
-    block_on(self.file.write_at(entity_section_start, &entity_buf)).map_err(io::Error::from)
+    Ok(
+      self
+        .file
+        .write_at(entity_section_start, &entity_buf)
+        .await?,
+    )
+  }
+
+  /// Writes the explored section if any chunks are explored.
+  async fn write_explored_section(&self, explored_section_start: u64) -> io::Result<()> {
+    if self.explored.is_empty() {
+      return Ok(());
+    }
+
+    let mut explored_buf = Vec::new();
+    ExploredSectionHeader::write_section(&mut explored_buf, &self.explored)?;
+    Ok(
+      self
+        .file
+        .write_at(explored_section_start, &explored_buf)
+        .await?,
+    )
+  }
+
+  /// Returns the size of the page table, entity section, and explored
+  /// section as written by a flush.
+  fn metadata_size(&self) -> u64 {
+    let mut size = self.index.serialized_size();
+    if !self.body_index.is_empty() {
+      size += EntitySectionHeader::SIZE + self.body_index.serialized_size();
+    }
+    if !self.explored.is_empty() {
+      size += ExploredSectionHeader::section_size(self.explored.len());
+    }
+    size as u64
   }
 
-  /// Flushes the page table, entity section, and header to disk.
+  /// Flushes the page table, entity section, explored section, and header to
+  /// disk.
   ///
-  /// Rewrites header in-place and appends page table and entity section at
-  /// end of file. The page table and entity section locations are stored
-  /// in the header.
+  /// Rewrites header in-place and writes page table, entity section, and
+  /// explored section into the region of the metadata committed before the
+  /// current one when they fit, or appends them at end of file otherwise.
+  /// The page table and entity section locations are stored in the header,
+  /// which also flags whether an explored section follows them. Neither the
+  /// committed metadata nor data it references is overwritten, so a crash
+  /// before the header is synced leaves the committed state intact. Once
+  /// the header is synced the change journal is truncated.
   pub fn flush(&mut self) -> io::Result<()> {
+    block_on(Box::pin(self.flush_async()))
+  }
+
+  /// Flushes the save asynchronously. See [`flush`](Self::flush).
+  pub async fn flush_async(&mut self) -> io::Result<()> {
     if !self.dirty {
       return Ok(());
     }
 
     self.update_timestamps();
 
-    // Page table goes after data region
-    self.header.data_region_ptr = self.data_write_pos;
-    self.write_page_table()?;
+    // Reuse the spare region if the metadata fits, else append it
+    let size = self.metadata_size();
+    let region = match self.spare_metadata {
+      Some((offset, len)) if size <= len => (offset, len),
+      _ => {
+        let region = (self.data_write_pos, size);
+        self.data_write_pos += size;
+        region
+      }
+    };
+
+    // Page table goes first
+    self.header.data_region_ptr = region.0;
+    self.write_page_table(region.0).await?;
 
     // Entity section goes after page table
-    let entity_section_start = self.data_write_pos + self.index.serialized_size() as u64;
+    let entity_section_start = region.0 + self.index.serialized_size() as u64;
     self.header.entity_section_ptr = if self.body_index.is_empty() {
       0
     } else {
       entity_section_start
     };
-    self.write_entity_section(entity_section_start)?;
+    self.write_entity_section(entity_section_start).await?;
+    let explored_section_start = if self.body_index.is_empty() {
+      entity_section_start
+    } else {
+      entity_section_start + (EntitySectionHeader::SIZE + self.body_index.serialized_size()) as u64
+    };
+
+    // Explored section goes after entity section, flagged in the header
+    self.write_explored_section(explored_section_start).await?;
+    if self.explored.is_empty() {
+      self.header.flags &= !FLAG_EXPLORED_SECTION;
+    } else {
+      self.header.flags |= FLAG_EXPLORED_SECTION;
+    }
 
     // Write updated header
     let mut header_buf = Vec::new();
     self.header.write_to(&mut header_buf)?;
-    block_on(self.file.write_at(0, &header_buf)).map_err(io::Error::from)?;
+    self.file.write_at(0, &header_buf).await?;
 
-    block_on(self.file.sync()).map_err(io::Error::from)?;
+    self.file.sync().await?;
     self.dirty = false;
+
+    // The previously committed metadata is no longer referenced
+    let previous = std::mem::replace(&mut self.committed_metadata, region);
+    self.spare_metadata = (previous.1 > 0).then_some(previous);
+
+    if let Some(journal) = &mut self.journal {
+      journal.reset(Generation::of(&self.header)).await?;
+    }
     Ok(())
   }
 
@@ -695,12 +913,16 @@ pub struct LoadedChunk {
   pub pos: ChunkPos,
   /// Whether the seeder is needed to apply delta.
   pub seeder_needed: bool,
+  /// Pixel flags cleared after applying the data (see
+  /// `PersistenceConfig::clear_flags_on_load`).
+  pub clear_flags: PixelFlags,
 }
 
 impl LoadedChunk {
   /// Applies the loaded data to a chunk.
   ///
-  /// For delta storage, the chunk should be pre-seeded.
+  /// For delta storage, the chunk should be pre-seeded. Flags in
+  /// `clear_flags` are removed from every pixel afterwards.
   pub fn apply_to(&self, chunk: &mut Chunk) -> Result<(), LoadError> {
     match self.storage_type {
       StorageType::Empty => {
@@ -714,6 +936,11 @@ impl LoadedChunk {
         decode_full(&self.data, chunk).map_err(LoadError::FullDecode)?;
       }
     }
+    if !self.clear_flags.is_empty() {
+      for pixel in chunk.pixels.as_slice_mut() {
+        pixel.flags.remove(self.clear_flags);
+      }
+    }
     Ok(())
   }
 }
@@ -828,6 +1055,8 @@ pub struct PersistenceTasks {
   pub body_save_queue: Vec<BodySaveTask>,
   /// Pixel bodies queued for removal.
   pub body_remove_queue: Vec<BodyRemoveTask>,
+  /// Explored chunk set queued for saving.
+  pub explored_save: Option<Vec<ChunkPos>>,
 }
 
 impl PersistenceTasks {
@@ -849,4 +1078,9 @@ impl PersistenceTasks {
   pub fn queue_body_remove(&mut self, stable_id: u64) {
     self.body_remove_queue.push(BodyRemoveTask { stable_id });
   }
+
+  /// Queues the explored chunk set for saving, replacing any queued set.
+  pub fn queue_explored_save(&mut self, chunks: Vec<ChunkPos>) {
+    self.explored_save = Some(chunks);
+  }
 }
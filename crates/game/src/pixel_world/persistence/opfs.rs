@@ -346,7 +346,11 @@ impl PersistenceBackend for WasmPersistence {
     name: &'a str,
     seed: u64,
   ) -> BoxFuture<'a, Result<WorldSave, String>> {
-    Box::pin(async move { WorldSave::open_or_create_async(&*self.fs, name, seed).await })
+    Box::pin(async move {
+      WorldSave::open_or_create_async(&*self.fs, name, seed)
+        .await
+        .map_err(|e| e.to_string())
+    })
   }
 
   fn save_copy(&self, save: &mut WorldSave, to_name: &str) -> io::Result<WorldSave> {
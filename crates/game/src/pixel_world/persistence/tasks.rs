@@ -17,6 +17,7 @@ use super::format::{PageTableEntry, StorageType};
 use super::index::{ChunkIndex, PixelBodyIndex, PixelBodyIndexEntry};
 use super::{BodyRemoveTask, BodySaveTask, LoadedChunk, SaveTask};
 use crate::pixel_world::coords::ChunkPos;
+use crate::pixel_world::pixel::PixelFlags;
 
 /// Resource tracking in-flight chunk load tasks.
 ///
@@ -29,6 +30,9 @@ pub struct LoadingChunks {
   /// Native-only: async tasks for in-flight loads.
   #[cfg(not(target_family = "wasm"))]
   pub(crate) tasks: HashMap<ChunkPos, Task<LoadResult>>,
+  /// Number of cancelled loads per position whose results are still to
+  /// arrive from the I/O worker.
+  cancelled: std::collections::HashMap<ChunkPos, usize>,
 }
 
 impl LoadingChunks {
@@ -41,6 +45,36 @@ impl LoadingChunks {
   pub fn len(&self) -> usize {
     self.pending.len()
   }
+
+  /// Cancels all in-flight loads.
+  ///
+  /// Load tasks are dropped. Loads sent to the I/O worker can't be
+  /// recalled, so their results are discarded when they arrive instead.
+  pub(crate) fn cancel_all(&mut self) {
+    #[cfg(not(target_family = "wasm"))]
+    for (pos, _task) in self.tasks.drain() {
+      self.pending.remove(&pos);
+    }
+    for pos in self.pending.drain() {
+      *self.cancelled.entry(pos).or_default() += 1;
+    }
+  }
+
+  /// Returns true if a load result arriving for `pos` belongs to a
+  /// cancelled load and must be discarded.
+  ///
+  /// The worker answers loads in order, so the oldest cancelled load of a
+  /// position answers first.
+  pub(crate) fn take_cancelled(&mut self, pos: ChunkPos) -> bool {
+    let Some(count) = self.cancelled.get_mut(&pos) else {
+      return false;
+    };
+    *count -= 1;
+    if *count == 0 {
+      self.cancelled.remove(&pos);
+    }
+    true
+  }
 }
 
 /// Resource tracking the in-flight batch save task.
@@ -153,6 +187,10 @@ pub async fn load_chunk_async(
   if let Err(e) = file.read_at(entry.data_offset, &mut data).await {
     return LoadResult::error(pos, format!("Failed to read chunk {:?}: {}", pos, e));
   }
+  if !entry.verify_data(&data) {
+    warn!("Chunk {:?} failed its checksum, regenerating", pos);
+    return LoadResult::not_found(pos);
+  }
 
   LoadResult::success(
     pos,
@@ -161,6 +199,7 @@ pub async fn load_chunk_async(
       data,
       pos,
       seeder_needed: entry.storage_type == StorageType::Delta,
+      clear_flags: PixelFlags::empty(),
     },
   )
 }
@@ -247,7 +286,8 @@ async fn save_single_chunk(
     *write_pos + 4, // Skip size prefix
     task.data.len() as u32,
     task.storage_type,
-  );
+  )
+  .with_data_checksum(&task.data);
 
   // Update state
   index.insert(entry);
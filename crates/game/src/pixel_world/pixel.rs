@@ -14,13 +14,30 @@ bitflags::bitflags! {
     const FALLING = 0b0000_0100;
     /// Pixel is burning (reserved for future use).
     const BURNING = 0b0000_1000;
-    /// Pixel is wet (reserved for future use).
+    /// Pixel is wet; wet powders slide further (see `Material::wet_slip`).
     const WET = 0b0001_0000;
     /// Pixel belongs to a pixel body (excluded from terrain collision).
     const PIXEL_BODY = 0b0010_0000;
+    /// Liquid surface pixel was recently disturbed; rendered lighter.
+    const FOAM = 0b0100_0000;
+    /// Solid-material pixel knocked loose (e.g. by digging); moves like a
+    /// powder of its material.
+    const LOOSE = 0b1000_0000;
   }
 }
 
+/// Most liquid pixels an absorbent pixel can store, the largest value of
+/// its saturation nibble.
+pub const MAX_SATURATION: u8 = 0x0F;
+
+/// Highest crack level of a chipped pixel, the largest value of its crack
+/// nibble.
+pub const MAX_CRACK: u8 = 0x0F;
+
+/// Deepest depth a liquid pixel records, the largest value of its depth
+/// bits.
+pub const MAX_DEPTH: u8 = 0x7F;
+
 /// Simulation pixel - 4 bytes for cache efficiency.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -60,6 +77,59 @@ impl Pixel {
     self.material.0 == 0
   }
 
+  /// Returns the liquid pixels stored by an absorbent pixel, kept in the
+  /// low nibble of `damage`.
+  #[inline]
+  pub fn saturation(&self) -> u8 {
+    self.damage & MAX_SATURATION
+  }
+
+  /// Sets the stored liquid of an absorbent pixel, leaving the high nibble
+  /// of `damage` untouched.
+  #[inline]
+  pub fn set_saturation(&mut self, saturation: u8) {
+    self.damage = (self.damage & !MAX_SATURATION) | saturation.min(MAX_SATURATION);
+  }
+
+  /// Returns how far a chipped pixel is cracked (0 = intact), kept in the
+  /// high nibble of `damage`.
+  ///
+  /// Only meaningful for materials that crack (see `Material::cracks`).
+  #[inline]
+  pub fn crack(&self) -> u8 {
+    self.damage >> 4
+  }
+
+  /// Sets the crack level of a chipped pixel, leaving the low nibble of
+  /// `damage` untouched.
+  #[inline]
+  pub fn set_crack(&mut self, crack: u8) {
+    self.damage = (self.damage & 0x0F) | (crack.min(MAX_CRACK) << 4);
+  }
+
+  /// Returns how many pixels of the same liquid are stacked above a liquid
+  /// pixel, kept in the low seven bits of `damage`.
+  ///
+  /// Only meaningful for materials with a depth ramp (see
+  /// `Material::has_depth_ramp`).
+  #[inline]
+  pub fn depth(&self) -> u8 {
+    self.damage & MAX_DEPTH
+  }
+
+  /// Returns true if a liquid pixel blends colors with another liquid,
+  /// marked in the high bit of `damage`. Its depth ramp is not drawn.
+  #[inline]
+  pub fn mixing(&self) -> bool {
+    self.damage & !MAX_DEPTH != 0
+  }
+
+  /// Sets the depth of a liquid pixel and whether it is mixing.
+  #[inline]
+  pub fn set_depth(&mut self, depth: u8, mixing: bool) {
+    self.damage = depth.min(MAX_DEPTH) | if mixing { !MAX_DEPTH } else { 0 };
+  }
+
   /// Returns the flags as a raw u8 for serialization.
   #[inline]
   pub fn flags_bits(&self) -> u8 {
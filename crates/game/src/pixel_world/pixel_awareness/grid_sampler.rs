@@ -20,6 +20,11 @@ pub struct GridSampleResult {
   /// Sum of world positions of matched samples (divide by `matched_samples`
   /// to get center).
   pub matched_center_sum: Vec2,
+  /// Sum of the material densities of the body pixels under all samples.
+  pub density_sum: f32,
+  /// Sum of world positions of all samples, each weighted by the density of
+  /// its body pixel (divide by `density_sum` to get the center of mass).
+  pub mass_center_sum: Vec2,
 }
 
 /// Configuration for grid sampling.
@@ -42,7 +47,8 @@ impl Default for GridSampleConfig {
 ///
 /// For each sample point inside the body, checks adjacent pixels (below, above,
 /// left, right) with the predicate. This handles the case where body pixels
-/// have replaced the underlying terrain.
+/// have replaced the underlying terrain. Every sample inside the body also
+/// accumulates the material density of the body pixel under it.
 pub fn sample_body_grid(
   world: &PixelWorld,
   materials: &Materials,
@@ -59,6 +65,8 @@ pub fn sample_body_grid(
     matched_samples: 0,
     total_samples: 0,
     matched_center_sum: Vec2::ZERO,
+    density_sum: 0.0,
+    mass_center_sum: Vec2::ZERO,
   };
 
   let inverse = transform.affine().inverse();
@@ -69,11 +77,16 @@ pub fn sample_body_grid(
       let sample_y = aabb.y as f32 + (gy as f32 + 0.5) * step_y;
 
       let world_point = Vec3::new(sample_x, sample_y, 0.0);
-      if body.world_to_solid_local(world_point, &inverse).is_none() {
+      let Some((lx, ly)) = body.world_to_solid_local(world_point, &inverse) else {
         continue;
-      }
+      };
 
       result.total_samples += 1;
+      if let Some(pixel) = body.get_pixel(lx, ly) {
+        let density = materials.get(pixel.material).density as f32;
+        result.density_sum += density;
+        result.mass_center_sum += Vec2::new(sample_x, sample_y) * density;
+      }
 
       // Check adjacent pixels (the body pixel itself won't match for overlap queries)
       let sx = sample_x as i64;
@@ -23,6 +23,12 @@ pub struct LiquidFractionState {
   pub liquid_fraction: f32,
   /// World position of the center of liquid-adjacent samples.
   pub liquid_center: Vec2,
+  /// Mean material density of the body's sampled pixels (0.0 when no
+  /// sample hit the body).
+  pub body_density: f32,
+  /// World position of the body's center of mass, weighting each sample by
+  /// the material density of its pixel.
+  pub mass_center: Vec2,
   /// Debug: number of sample points that hit liquid.
   pub debug_liquid_samples: u32,
   /// Debug: total number of sample points that hit solid body pixels.
@@ -44,6 +50,8 @@ struct BodyLiquidResult {
   has_existing_state: bool,
   liquid_fraction: f32,
   liquid_center: Vec2,
+  body_density: f32,
+  mass_center: Vec2,
   liquid_samples: u32,
   total_samples: u32,
 }
@@ -52,7 +60,7 @@ struct BodyLiquidResult {
 ///
 /// Creates an NxN sample grid across each body's AABB and queries the world
 /// for liquid pixels. Updates [`LiquidFractionState`] with the fraction,
-/// center, and debug statistics.
+/// center, the body's density and center of mass, and debug statistics.
 ///
 /// Sampling is parallelized across bodies since each body's computation is
 /// independent and the world access is read-only.
@@ -109,11 +117,25 @@ pub fn sample_liquid_fraction(
         transform.translation().truncate()
       };
 
+      let body_density = if result.total_samples > 0 {
+        result.density_sum / result.total_samples as f32
+      } else {
+        0.0
+      };
+
+      let mass_center = if result.density_sum > 0.0 {
+        result.mass_center_sum / result.density_sum
+      } else {
+        transform.translation().truncate()
+      };
+
       BodyLiquidResult {
         entity,
         has_existing_state,
         liquid_fraction,
         liquid_center,
+        body_density,
+        mass_center,
         liquid_samples: result.matched_samples,
         total_samples: result.total_samples,
       }
@@ -126,6 +148,8 @@ pub fn sample_liquid_fraction(
       if let Ok((_, _, _, Some(mut state))) = bodies.get_mut(result.entity) {
         state.liquid_fraction = result.liquid_fraction;
         state.liquid_center = result.liquid_center;
+        state.body_density = result.body_density;
+        state.mass_center = result.mass_center;
         state.debug_liquid_samples = result.liquid_samples;
         state.debug_total_samples = result.total_samples;
       }
@@ -133,6 +157,8 @@ pub fn sample_liquid_fraction(
       commands.entity(result.entity).insert(LiquidFractionState {
         liquid_fraction: result.liquid_fraction,
         liquid_center: result.liquid_center,
+        body_density: result.body_density,
+        mass_center: result.mass_center,
         debug_liquid_samples: result.liquid_samples,
         debug_total_samples: result.total_samples,
       });
@@ -4,9 +4,11 @@
 //! and clear it afterward. Displacement is integrated: clear collects void
 //! positions, blit swaps displaced pixels into those voids.
 
+use std::collections::{HashSet, VecDeque};
+
 use bevy::prelude::*;
 
-use super::PixelBody;
+use super::{DisplacementState, PixelBody};
 use crate::pixel_world::collision::AwaitingCollision;
 use crate::pixel_world::coords::{WorldPos, WorldRect};
 
@@ -72,6 +74,20 @@ pub struct LastBlitTransform {
   pub written_positions: Vec<WrittenPixel>,
 }
 
+/// Query for bodies ready to be blitted.
+type BlitBodyQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    Entity,
+    &'static PixelBody,
+    &'static GlobalTransform,
+    Option<&'static mut LastBlitTransform>,
+    Option<&'static DisplacementState>,
+  ),
+  Without<AwaitingCollision>,
+>;
+
 /// Clears and blits all pixel bodies with proper per-body displacement.
 ///
 /// For each body:
@@ -83,15 +99,7 @@ pub struct LastBlitTransform {
 pub fn update_pixel_bodies(
   mut commands: Commands,
   mut worlds: Query<&mut PixelWorld>,
-  mut bodies: Query<
-    (
-      Entity,
-      &PixelBody,
-      &GlobalTransform,
-      Option<&mut LastBlitTransform>,
-    ),
-    Without<AwaitingCollision>,
-  >,
+  mut bodies: BlitBodyQuery,
   materials: Res<Materials>,
   gizmos: GizmosParam,
 ) {
@@ -99,9 +107,10 @@ pub fn update_pixel_bodies(
     return;
   };
 
-  for (entity, body, transform, blitted) in bodies.iter_mut() {
+  for (entity, body, transform, blitted, displacement) in bodies.iter_mut() {
     // Per-body displacement tracking: cleared positions become displacement targets
     let mut displacement_targets = Vec::new();
+    let conserve_liquids = displacement.is_some_and(|d| d.conserve_liquids);
 
     // Clear at old position using tracked written positions
     if let Some(ref bt) = blitted {
@@ -118,7 +127,10 @@ pub fn update_pixel_bodies(
       &mut world,
       body,
       transform,
-      Some(&mut displacement_targets),
+      Some(Displacement {
+        targets: &mut displacement_targets,
+        conserve_liquids,
+      }),
       &materials,
       gizmos.get(),
     );
@@ -185,15 +197,68 @@ pub(crate) fn compute_world_aabb(body: &PixelBody, transform: &GlobalTransform)
   )
 }
 
-/// Attempts to displace a fluid pixel at `pos` into one of the
-/// `displacement_targets`.
+/// Maximum number of cells visited when searching for a void to relocate
+/// fluid into.
+const MAX_RELOCATION_SEARCH: usize = 4096;
+
+/// Where fluid pixels under a blitted body are moved to.
+pub(super) struct Displacement<'a> {
+  /// Positions cleared at the old transform, used as swap targets.
+  pub targets: &'a mut Vec<WorldPos>,
+  /// Fall back to the nearest reachable void once `targets` is exhausted.
+  pub conserve_liquids: bool,
+}
+
+/// Finds the nearest void reachable from `pos` through fluid pixels.
+///
+/// Searches breadth-first so fluid is only pushed through connected fluid,
+/// never through walls.
+fn find_reachable_void(
+  world: &PixelWorld,
+  pos: WorldPos,
+  materials: &Materials,
+) -> Option<WorldPos> {
+  let mut visited = HashSet::from([pos]);
+  let mut queue = VecDeque::from([pos]);
+
+  while let Some(current) = queue.pop_front() {
+    for (dx, dy) in [(0, 1), (-1, 0), (1, 0), (0, -1)] {
+      let next = WorldPos::new(current.x + dx, current.y + dy);
+      if visited.len() >= MAX_RELOCATION_SEARCH {
+        return None;
+      }
+      if !visited.insert(next) {
+        continue;
+      }
+      let Some(pixel) = world.get_pixel(next) else {
+        continue;
+      };
+      if pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+        continue;
+      }
+      if pixel.is_void() {
+        return Some(next);
+      }
+      if matches!(
+        materials.get(pixel.material).state,
+        PhysicsState::Liquid | PhysicsState::Gas
+      ) {
+        queue.push_back(next);
+      }
+    }
+  }
+  None
+}
+
+/// Attempts to displace a fluid pixel at `pos` into one of the displacement
+/// targets, or the nearest void when conserving liquids.
 ///
 /// Returns true if displacement occurred, false if the pixel wasn't a fluid or
 /// no valid target was available.
 fn try_displace_fluid(
   world: &mut PixelWorld,
   pos: WorldPos,
-  displacement_targets: &mut Vec<WorldPos>,
+  displacement: &mut Displacement,
   materials: &Materials,
   debug_gizmos: crate::pixel_world::debug_shim::DebugGizmos<'_>,
 ) -> bool {
@@ -211,27 +276,37 @@ fn try_displace_fluid(
     return false;
   }
 
+  let existing = *existing;
+
   // Find a void that isn't already occupied by a body pixel
-  while let Some(void_pos) = displacement_targets.pop() {
+  let mut target = None;
+  while let Some(void_pos) = displacement.targets.pop() {
     if let Some(void_pixel) = world.get_pixel(void_pos)
       && void_pixel.flags.contains(PixelFlags::PIXEL_BODY)
     {
       continue; // Skip - already has a body pixel
     }
-    world.set_pixel(void_pos, *existing, debug_gizmos);
-    // Mark displaced pixel as simulation-dirty so it participates in CA
-    world.mark_pixel_sim_dirty(void_pos);
-    return true;
+    target = Some(void_pos);
+    break;
+  }
+  if target.is_none() && displacement.conserve_liquids {
+    target = find_reachable_void(world, pos, materials);
   }
 
-  false
+  let Some(void_pos) = target else {
+    return false;
+  };
+  world.set_pixel(void_pos, existing, debug_gizmos);
+  // Mark displaced pixel as simulation-dirty so it participates in CA
+  world.mark_pixel_sim_dirty(void_pos);
+  true
 }
 
 /// Writes a single pixel body and returns the positions that were written.
 ///
-/// If `displacement_targets` is Some, fluid pixels will be displaced into
-/// available void positions collected during the clear phase. If None,
-/// no displacement occurs (useful for fragment spawning).
+/// If `displacement` is Some, fluid pixels will be displaced into available
+/// void positions collected during the clear phase. If None, no
+/// displacement occurs (useful for fragment spawning).
 ///
 /// This is used by update_pixel_bodies to know exactly which positions to
 /// clear on the next frame, preventing same-material bodies from accidentally
@@ -240,7 +315,7 @@ pub(super) fn blit_single_body(
   world: &mut PixelWorld,
   body: &PixelBody,
   transform: &GlobalTransform,
-  mut displacement_targets: Option<&mut Vec<WorldPos>>,
+  mut displacement: Option<Displacement>,
   materials: &Materials,
   debug_gizmos: crate::pixel_world::debug_shim::DebugGizmos<'_>,
 ) -> Vec<WrittenPixel> {
@@ -261,8 +336,8 @@ pub(super) fn blit_single_body(
       continue;
     }
 
-    if let Some(ref mut targets) = displacement_targets {
-      try_displace_fluid(world, pos, targets, materials, debug_gizmos);
+    if let Some(ref mut displacement) = displacement {
+      try_displace_fluid(world, pos, displacement, materials, debug_gizmos);
     }
 
     let mut pixel_with_flag = pixel;
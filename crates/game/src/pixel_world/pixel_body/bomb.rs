@@ -2,16 +2,25 @@
 //!
 //! Pixel bodies tagged with `Bomb` detonate when enough of their pixels are
 //! destroyed (burned, erased, blasted, etc.). Detonation destroys/transforms
-//! pixels in a blast radius, releases heat, and chain-detonates nearby bombs.
+//! pixels in a blast radius, releases heat, optionally sets flammable
+//! surfaces on fire, and chain-detonates nearby bombs.
+//!
+//! Triggered bombs go through a [`DetonationQueue`] so chain reactions spread
+//! their blit and collision-invalidation cost over several frames. Blast
+//! radii are capped, and blasts above a size threshold expand as a
+//! shockwave over several frames instead of in one giant pass.
+
+use std::collections::{HashSet, VecDeque};
 
 use bevy::prelude::*;
 
 use super::PixelBody;
-use crate::pixel_world::coords::ColorIndex;
+use crate::pixel_world::coords::{ColorIndex, WorldPos};
+use crate::pixel_world::edit_log::WorldEdit;
 use crate::pixel_world::material::Materials;
 use crate::pixel_world::pixel::{Pixel, PixelFlags};
 use crate::pixel_world::simulation::hash::hash41uu64;
-use crate::pixel_world::world::{BlastHit, BlastParams, PixelWorld};
+use crate::pixel_world::world::{BlastHit, BlastParams, BlastWave, PixelWorld};
 
 /// Marks a pixel body as a bomb that detonates when enough pixels are
 /// destroyed.
@@ -22,8 +31,13 @@ pub struct Bomb {
   /// Maximum blast radius in world pixels (caps ray length).
   pub blast_radius: f32,
   /// Initial explosion energy. Dissipated by material blast_resistance per
-  /// pixel.
+  /// pixel; a pixel whose resistance exceeds the energy left survives.
   pub blast_strength: f32,
+  /// Whether the explosion sets flammable pixels on fire.
+  pub ignites: bool,
+  /// Radius in world pixels within which flammable pixels in line of sight
+  /// of the blast center start burning. Only used when `ignites` is set.
+  pub thermal_radius: f32,
   /// Whether this bomb has been triggered.
   pub detonated: bool,
 }
@@ -35,6 +49,106 @@ pub struct BombInitialState {
   pub initial_pixels: u32,
 }
 
+/// Configuration for bomb detonation processing.
+#[derive(Resource, Clone, Debug)]
+pub struct DetonationConfig {
+  /// Maximum number of detonations processed per frame. Triggered bombs
+  /// beyond this limit stay queued and fire on following frames. Values
+  /// below 1 are treated as 1. Default: 8
+  pub max_per_frame: usize,
+  /// Upper bound on [`Bomb::blast_radius`] and [`Bomb::thermal_radius`];
+  /// larger radii are clamped. Default: 256.0
+  pub max_blast_radius: f32,
+  /// Blasts with a (clamped) radius above this expand as a shockwave over
+  /// several frames instead of resolving at once. Default: 64.0
+  pub shockwave_threshold: f32,
+  /// Radius a shockwave grows by each frame, in world pixels. Default: 32.0
+  pub shockwave_speed: f32,
+}
+
+impl Default for DetonationConfig {
+  fn default() -> Self {
+    Self {
+      max_per_frame: 8,
+      max_blast_radius: 256.0,
+      shockwave_threshold: 64.0,
+      shockwave_speed: 32.0,
+    }
+  }
+}
+
+/// FIFO of triggered bombs waiting to detonate.
+///
+/// Bombs are enqueued in the order they trigger and processed at most
+/// [`DetonationConfig::max_per_frame`] at a time.
+#[derive(Resource, Default)]
+pub struct DetonationQueue {
+  pending: VecDeque<Entity>,
+  /// Entities in `pending`, for constant-time membership checks.
+  queued: HashSet<Entity>,
+}
+
+impl DetonationQueue {
+  /// Appends `entity` unless it is already queued.
+  fn push(&mut self, entity: Entity) -> bool {
+    if !self.queued.insert(entity) {
+      return false;
+    }
+    self.pending.push_back(entity);
+    true
+  }
+
+  /// Removes and returns the bomb at the front of the queue.
+  fn pop(&mut self) -> Option<Entity> {
+    let entity = self.pending.pop_front()?;
+    self.queued.remove(&entity);
+    Some(entity)
+  }
+
+  /// Returns the number of bombs waiting to detonate.
+  pub fn len(&self) -> usize {
+    self.pending.len()
+  }
+
+  /// Returns true if no bombs are waiting to detonate.
+  pub fn is_empty(&self) -> bool {
+    self.pending.is_empty()
+  }
+}
+
+/// Shockwaves of detonated bombs that are still expanding.
+#[derive(Resource, Default)]
+pub struct Shockwaves {
+  waves: Vec<Shockwave>,
+}
+
+/// An expanding bomb blast.
+struct Shockwave {
+  blast: BlastWave,
+  /// Line-of-sight ignition cast once the blast has fully expanded.
+  ignition: Option<BlastParams>,
+}
+
+impl Shockwaves {
+  /// Returns the number of shockwaves still expanding.
+  pub fn len(&self) -> usize {
+    self.waves.len()
+  }
+
+  /// Returns true if no shockwave is expanding.
+  pub fn is_empty(&self) -> bool {
+    self.waves.is_empty()
+  }
+
+  /// Returns the center and current radius of each expanding shockwave.
+  pub fn fronts(&self) -> impl Iterator<Item = (Vec2, f32)> + '_ {
+    self
+      .waves
+      .iter()
+      .map(|wave| (wave.blast.params().center, wave.blast.radius()))
+  }
+}
+
 /// Initializes bomb state by counting solid pixels on spawn.
 pub fn init_bomb_state(
   mut commands: Commands,
@@ -75,28 +189,40 @@ pub fn check_bomb_damage(mut query: Query<(&mut Bomb, &BombInitialState, &PixelB
 ///
 /// Delegates the ray-march to `PixelWorld::blast()`, providing a callback
 /// that consumes energy by `blast_resistance` and converts pixels to
-/// 90% void / 10% ash.
+/// 90% void / 10% ash. Rays stop at pixels they lack the energy to remove
+/// and at indestructible materials, which shield what lies behind them.
+///
+/// Bombs with [`Bomb::ignites`] then cast a second set of rays over their
+/// `thermal_radius`, setting the first flammable pixel each ray reaches on
+/// fire. Non-flammable pixels block the rays, so fire only starts in line of
+/// sight; the burning pass takes over from there.
+///
+/// Newly triggered bombs are appended to the [`DetonationQueue`]; at most
+/// [`DetonationConfig::max_per_frame`] are taken from its front each frame.
+/// Radii are clamped to [`DetonationConfig::max_blast_radius`]. Blasts above
+/// [`DetonationConfig::shockwave_threshold`] become [`Shockwaves`] that grow
+/// by [`DetonationConfig::shockwave_speed`] per frame, chain-detonating
+/// bombs as their front reaches them and igniting once fully expanded.
 pub fn process_detonations(
   mut commands: Commands,
-  mut bombs: Query<(Entity, &mut Bomb, &GlobalTransform)>,
+  mut bombs: Query<(Entity, &mut Bomb, &GlobalTransform, Has<PixelBody>)>,
   mut worlds: Query<&mut PixelWorld>,
   materials: Res<Materials>,
+  config: Res<DetonationConfig>,
+  mut queue: ResMut<DetonationQueue>,
+  mut shockwaves: ResMut<Shockwaves>,
 ) {
-  // Collect detonated bomb data
-  let detonations: Vec<(Entity, f32, f32, Vec2)> = bombs
-    .iter()
-    .filter(|(_, bomb, _)| bomb.detonated)
-    .map(|(entity, bomb, transform)| {
-      (
-        entity,
-        bomb.blast_radius,
-        bomb.blast_strength,
-        transform.translation().xy(),
-      )
-    })
-    .collect();
+  // Enqueue newly triggered bombs
+  for (entity, bomb, transform, is_body) in &bombs {
+    if bomb.detonated && queue.push(entity) {
+      // Bomb bodies are replayed through their spawn
+      if !is_body && let Ok(mut world) = worlds.single_mut() {
+        world.record_edit(|| WorldEdit::detonate(transform.translation().xy(), bomb));
+      }
+    }
+  }
 
-  if detonations.is_empty() {
+  if queue.is_empty() && shockwaves.is_empty() {
     return;
   }
 
@@ -104,45 +230,70 @@ pub fn process_detonations(
     return;
   };
 
+  // Take this frame's batch, skipping bombs despawned while queued
+  let mut detonations: Vec<(Entity, f32, f32, Vec2)> = Vec::new();
+  let mut ignitions: Vec<BlastParams> = Vec::new();
+  while detonations.len() < config.max_per_frame.max(1) {
+    let Some(entity) = queue.pop() else {
+      break;
+    };
+    let Ok((_, bomb, transform, _)) = bombs.get(entity) else {
+      continue;
+    };
+    let center = transform.translation().xy();
+    let radius = bomb.blast_radius.min(config.max_blast_radius);
+    let ignition = bomb.ignites.then(|| BlastParams {
+      center,
+      strength: 1.0,
+      max_radius: bomb.thermal_radius.min(config.max_blast_radius),
+      heat_radius: 0.0,
+    });
+
+    if radius > config.shockwave_threshold {
+      shockwaves.waves.push(Shockwave {
+        blast: BlastWave::new(blast_params(center, radius, bomb.blast_strength)),
+        ignition,
+      });
+      commands.entity(entity).despawn();
+      continue;
+    }
+
+    ignitions.extend(ignition);
+    detonations.push((entity, radius, bomb.blast_strength, center));
+  }
+
   // Build blast params for all detonations
   let blast_params: Vec<BlastParams> = detonations
     .iter()
-    .map(|&(_, radius, strength, center)| BlastParams {
-      center,
-      strength,
-      max_radius: radius,
-      heat_radius: radius * 4.0,
-    })
+    .map(|&(_, radius, strength, center)| blast_params(center, radius, strength))
     .collect();
 
   // Process all blasts in a single batched operation
-  world.blast_many(&blast_params, |pixel, pos| {
-    let mat = materials.get(pixel.material);
-    let cost = mat.effects.blast_resistance;
-
-    // 90% void, 10% ash
-    let roll = hash41uu64(0xB00B, pos.x as u64, pos.y as u64, 0xDEAD);
-    let new_pixel = if roll.is_multiple_of(10) {
-      let color_idx = (roll / 10 % 256) as u8;
-      Pixel {
-        material: crate::pixel_world::material::ids::ASH,
-        color: ColorIndex(color_idx),
-        damage: 0,
-        flags: PixelFlags::DIRTY | PixelFlags::SOLID | PixelFlags::FALLING,
-      }
-    } else {
-      Pixel::VOID
-    };
+  world.blast_many(&blast_params, |pixel, pos| destroy(&materials, pixel, pos));
 
-    BlastHit::Hit {
-      pixel: new_pixel,
-      cost,
+  // Expand shockwaves, igniting the ones that finish
+  let mut fronts: Vec<(f32, Vec2)> = Vec::new();
+  for wave in &mut shockwaves.waves {
+    world.advance_blast_wave(&mut wave.blast, config.shockwave_speed, |pixel, pos| {
+      destroy(&materials, pixel, pos)
+    });
+    fronts.push((wave.blast.radius(), wave.blast.params().center));
+    if wave.blast.is_complete() {
+      ignitions.extend(wave.ignition.take());
     }
-  });
+  }
+  shockwaves.waves.retain(|wave| !wave.blast.is_complete());
+
+  // Ignite flammable surfaces left exposed by the blast
+  world.blast_many(&ignitions, |pixel, _| ignite(&materials, pixel));
 
   // Chain-detonate nearby bombs
-  let centers: Vec<(f32, Vec2)> = detonations.iter().map(|&(_, r, _, c)| (r, c)).collect();
-  for (_, mut bomb, transform) in &mut bombs {
+  let centers = detonations
+    .iter()
+    .map(|&(_, r, _, c)| (r, c))
+    .chain(fronts)
+    .collect::<Vec<_>>();
+  for (_, mut bomb, transform, _) in &mut bombs {
     if bomb.detonated {
       continue;
     }
@@ -160,3 +311,71 @@ pub fn process_detonations(
     commands.entity(*entity).despawn();
   }
 }
+
+/// Returns the destructive blast parameters of a bomb.
+fn blast_params(center: Vec2, radius: f32, strength: f32) -> BlastParams {
+  BlastParams {
+    center,
+    strength,
+    max_radius: radius,
+    heat_radius: radius * 4.0,
+  }
+}
+
+/// Blast callback: turns a pixel into 90% void / 10% ash, resisted by its
+/// material's `blast_resistance`. Pixels with durability left are only
+/// cracked further.
+fn destroy(materials: &Materials, pixel: &Pixel, pos: WorldPos) -> BlastHit {
+  let mat = materials.get(pixel.material);
+  if mat.effects.indestructible {
+    return BlastHit::Stop;
+  }
+  if let Some(chipped) = mat.chip(pixel) {
+    return BlastHit::Resist {
+      pixel: chipped,
+      resistance: mat.effects.blast_resistance,
+    };
+  }
+
+  // 90% void, 10% ash
+  let roll = hash41uu64(0xB00B, pos.x as u64, pos.y as u64, 0xDEAD);
+  let new_pixel = if roll.is_multiple_of(10) {
+    let color_idx = (roll / 10 % 256) as u8;
+    Pixel {
+      material: crate::pixel_world::material::ids::ASH,
+      color: ColorIndex(color_idx),
+      damage: 0,
+      flags: PixelFlags::DIRTY | PixelFlags::SOLID | PixelFlags::FALLING,
+    }
+  } else {
+    Pixel::VOID
+  };
+
+  BlastHit::Resist {
+    pixel: new_pixel,
+    resistance: mat.effects.blast_resistance,
+  }
+}
+
+/// Ignition callback: sets the first flammable pixel a ray reaches on fire.
+fn ignite(materials: &Materials, pixel: &Pixel) -> BlastHit {
+  // Ash scattered by the blast does not block the heat flash
+  if pixel.material == crate::pixel_world::material::ids::ASH {
+    return BlastHit::Skip;
+  }
+  if pixel.flags.contains(PixelFlags::BURNING) {
+    return BlastHit::Stop;
+  }
+  if materials.get(pixel.material).ignition_threshold == 0 {
+    return BlastHit::Stop;
+  }
+
+  let mut burning = *pixel;
+  burning
+    .flags
+    .insert(PixelFlags::BURNING | PixelFlags::DIRTY);
+  BlastHit::Hit {
+    pixel: burning,
+    cost: f32::INFINITY,
+  }
+}
@@ -1,13 +1,18 @@
 //! Collider generation from pixel body shape masks.
 //!
 //! Uses marching squares to extract contours from the shape mask, then
-//! triangulates for physics collision.
+//! triangulates for physics collision. Bodies marked with
+//! [`NeedsColliderRegen`] are rebuilt at most once per
+//! [`ColliderRegenConfig::debounce_frames`].
 
 #[cfg(physics)]
 use bevy::math::Vec2;
+use bevy::prelude::*;
 #[cfg(physics)]
 use bevy_rapier2d::prelude::Collider;
 
+#[cfg(physics)]
+use super::NeedsColliderRegen;
 use super::PixelBody;
 #[cfg(physics)]
 use crate::pixel_world::collision::{
@@ -136,3 +141,63 @@ fn extract_contours(grid: &[Vec<bool>], width: usize, height: usize) -> Vec<Vec<
 pub fn generate_collider(_body: &PixelBody) -> Option<()> {
   None
 }
+
+/// Configuration for pixel body collider regeneration.
+#[derive(Resource, Clone, Debug)]
+pub struct ColliderRegenConfig {
+  /// Minimum number of frames between two collider rebuilds of the same
+  /// body. Shape changes within the window are coalesced into one rebuild
+  /// when it ends. 1 rebuilds on every changed frame.
+  /// Default: 4
+  pub debounce_frames: u32,
+}
+
+impl Default for ColliderRegenConfig {
+  fn default() -> Self {
+    Self { debounce_frames: 4 }
+  }
+}
+
+/// Frames left before a body's collider may be rebuilt again.
+#[derive(Component, Default)]
+pub struct ColliderRegenCooldown(u32);
+
+/// Rebuilds colliders of bodies marked with [`NeedsColliderRegen`].
+///
+/// A rebuild starts a cooldown of
+/// [`ColliderRegenConfig::debounce_frames`]. Bodies changed during the
+/// cooldown keep their marker and are rebuilt once it expires, so the last
+/// change is always reflected.
+#[cfg(physics)]
+pub fn regenerate_pixel_body_colliders(
+  mut commands: Commands,
+  config: Res<ColliderRegenConfig>,
+  mut bodies: Query<(
+    Entity,
+    &PixelBody,
+    Option<&mut ColliderRegenCooldown>,
+    Has<NeedsColliderRegen>,
+  )>,
+) {
+  for (entity, body, cooldown, needs_regen) in &mut bodies {
+    if let Some(mut cooldown) = cooldown
+      && cooldown.0 > 0
+    {
+      cooldown.0 -= 1;
+      continue;
+    }
+    if !needs_regen {
+      continue;
+    }
+
+    let mut entity_commands = commands.entity(entity);
+    entity_commands.remove::<NeedsColliderRegen>();
+    if let Some(collider) = generate_collider(body) {
+      entity_commands.insert(collider);
+    }
+    // The rebuild frame itself counts towards the window
+    entity_commands.insert(ColliderRegenCooldown(
+      config.debounce_frames.saturating_sub(1),
+    ));
+  }
+}
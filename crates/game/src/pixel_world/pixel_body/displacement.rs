@@ -3,15 +3,200 @@
 //! Displacement is handled during the clear/blit phases. When a body moves:
 //! - Clear at position A creates voids
 //! - Blit at position B swaps existing pixels into those voids
+//! - With [`DisplacementState::conserve_liquids`], fluid left without a swap
+//!   target is relocated to the nearest void instead of being erased
+//!
+//! With [`PowderSinkConfig`](crate::pixel_world::collision::PowderSinkConfig)
+//! enabled, bodies resting on powder also push the powder beneath them aside
+//! after each CA tick, so heavy bodies settle into sand instead of sitting
+//! on its surface.
+
+use std::collections::HashSet;
 
 use bevy::prelude::*;
 
+use super::blit::for_each_body_pixel;
+use super::{PixelBody, Resting};
+use crate::pixel_world::collision::{AwaitingCollision, CollisionConfig};
+use crate::pixel_world::coords::WorldPos;
+use crate::pixel_world::debug_shim::GizmosParam;
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::world::PixelWorld;
+
 /// Tracks the previous transform for displacement calculations.
 ///
 /// Added automatically to pixel bodies during spawn. Used to determine
 /// movement direction for pixel displacement.
 #[derive(Component, Default)]
 pub struct DisplacementState {
-  /// Transform from the previous frame.
+  /// Transform at the previous simulation tick, recorded by
+  /// [`imprint_soft_terrain`](super::imprint_soft_terrain) to sweep the
+  /// path the body moved along.
   pub previous_transform: Option<GlobalTransform>,
+  /// Relocates fluid pixels under the body to the nearest void when no
+  /// cleared position is left to swap into, conserving liquid mass (e.g.
+  /// when a body spawns inside a pool). Off by default.
+  pub conserve_liquids: bool,
+}
+
+/// Rows above a displaced powder pixel searched for a place to pile it.
+const MAX_PILE_SEARCH: i64 = 64;
+
+/// Query for bodies that may sink into powder.
+type SinkBodyQuery<'w, 's> = Query<
+  'w,
+  's,
+  (&'static PixelBody, &'static GlobalTransform),
+  (
+    With<DisplacementState>,
+    Without<Resting>,
+    Without<AwaitingCollision>,
+  ),
+>;
+
+/// Returns whether `pixel` is loose powder that isn't part of a body.
+fn is_powder(pixel: &Pixel, materials: &Materials) -> bool {
+  !pixel.is_void()
+    && !pixel.flags.contains(PixelFlags::PIXEL_BODY)
+    && materials.get(pixel.material).state == PhysicsState::Powder
+}
+
+/// Finds where powder pushed out from under a body at `from` goes: the first
+/// void in `column`, searching upward from the row of `from`.
+///
+/// Fluids are searched through, so the powder settles through them later;
+/// solids and other bodies end the search.
+fn find_pile_target(
+  world: &PixelWorld,
+  from: WorldPos,
+  column: i64,
+  materials: &Materials,
+) -> Option<WorldPos> {
+  for y in from.y..from.y + MAX_PILE_SEARCH {
+    let pos = WorldPos::new(column, y);
+    let pixel = world.get_pixel(pos)?;
+    if pixel.is_void() {
+      return Some(pos);
+    }
+    if pixel.flags.contains(PixelFlags::PIXEL_BODY)
+      || materials.get(pixel.material).state == PhysicsState::Solid
+    {
+      return None;
+    }
+  }
+  None
+}
+
+/// Pushes powder out from under bodies that are denser than it.
+///
+/// Each tick, a body that is buried less deeply than its density calls for
+/// moves one layer of the powder directly beneath it out to either side.
+/// Physics then lowers the body into the gap once the terrain collider is
+/// rebuilt, and the pushed-out powder settles through the CA.
+///
+/// Runs after the CA tick, while bodies are blitted at their current
+/// transform.
+pub fn sink_bodies_into_powder(
+  config: Res<CollisionConfig>,
+  materials: Option<Res<Materials>>,
+  mut worlds: Query<&mut PixelWorld>,
+  bodies: SinkBodyQuery,
+  gizmos: GizmosParam,
+) {
+  let sink = &config.powder_sink;
+  if !sink.enabled {
+    return;
+  }
+  let (Ok(mut world), Some(materials)) = (worlds.single_mut(), materials) else {
+    return;
+  };
+
+  for (body, transform) in bodies.iter() {
+    let mut own = HashSet::new();
+    let mut density_sum = 0u32;
+    for_each_body_pixel(body, transform, |mapping| {
+      if let Some(pixel) = body.get_pixel(mapping.local_x, mapping.local_y) {
+        own.insert(mapping.world_pos);
+        density_sum += materials.get(pixel.material).density as u32;
+      }
+    });
+    if own.is_empty() {
+      continue;
+    }
+    let body_density = density_sum as f32 / own.len() as f32;
+
+    // Powder directly beneath the body's bottom pixels
+    let mut contacts: Vec<WorldPos> = own
+      .iter()
+      .map(|pos| WorldPos::new(pos.x, pos.y - 1))
+      .filter(|below| {
+        !own.contains(below)
+          && world
+            .get_pixel(*below)
+            .is_some_and(|pixel| is_powder(pixel, &materials))
+      })
+      .collect();
+    if contacts.is_empty() {
+      continue;
+    }
+    let powder_density = contacts
+      .iter()
+      .filter_map(|pos| world.get_pixel(*pos))
+      .map(|pixel| materials.get(pixel.material).density as f32)
+      .sum::<f32>()
+      / contacts.len() as f32;
+    if body_density <= powder_density {
+      continue;
+    }
+
+    // A row is buried once powder presses against either side of it
+    let rows: HashSet<i64> = own.iter().map(|pos| pos.y).collect();
+    let buried: HashSet<i64> = own
+      .iter()
+      .filter(|pos| {
+        [-1, 1].into_iter().any(|dx| {
+          let side = WorldPos::new(pos.x + dx, pos.y);
+          !own.contains(&side)
+            && world
+              .get_pixel(side)
+              .is_some_and(|pixel| is_powder(pixel, &materials))
+        })
+      })
+      .map(|pos| pos.y)
+      .collect();
+    let share = (sink.strength * (body_density / powder_density - 1.0)).clamp(0.0, 1.0);
+    let target_rows = (rows.len() as f32 * share).round() as usize;
+    if buried.len() >= target_rows {
+      continue;
+    }
+
+    // Push each contact out past the nearer side of the body, spreading a
+    // layer over as many columns as it has pixels on that side
+    let min_x = own.iter().map(|pos| pos.x).min().unwrap_or_default();
+    let max_x = own.iter().map(|pos| pos.x).max().unwrap_or_default();
+    let (mut left, mut right) = (0, 0);
+    contacts.sort_by_key(|pos| (pos.y, pos.x));
+    for contact in contacts {
+      let column = if contact.x * 2 < min_x + max_x {
+        left += 1;
+        min_x - left
+      } else {
+        right += 1;
+        max_x + right
+      };
+      let Some(target) = find_pile_target(&world, contact, column, &materials) else {
+        continue;
+      };
+      let Some(&powder) = world.get_pixel(contact) else {
+        continue;
+      };
+      world.set_pixel(target, powder, gizmos.get());
+      world.set_pixel(contact, Pixel::VOID, gizmos.get());
+      for pos in [target, contact] {
+        world.mark_pixel_sim_dirty(pos);
+        world.mark_pixel_collision_dirty(pos);
+      }
+    }
+  }
 }
@@ -0,0 +1,117 @@
+//! Tracks left by pixel bodies on soft terrain.
+//!
+//! Each tick, the footprint of every body is swept from where it was blitted
+//! the previous tick to where it is now, and terrain pixels directly beneath
+//! the footprint are imprinted if their material has a
+//! [`softness`](crate::pixel_world::material::Material::softness): their
+//! color index is pushed toward the end of the palette, once. Sweeping keeps
+//! the trail continuous when a body moves several pixels per tick.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use super::blit::for_each_body_pixel;
+use super::{DisplacementState, PixelBody};
+use crate::pixel_world::coords::{ColorIndex, WorldPos};
+use crate::pixel_world::debug_shim::GizmosParam;
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::world::PixelWorld;
+
+/// Marker kept in `Pixel::damage` for imprinted pixels.
+pub const IMPRINTED: u8 = 1;
+
+/// Bits of `Pixel::damage` holding [`IMPRINTED`]; the high nibble keeps
+/// the cracks of chipped pixels.
+const IMPRINT_MASK: u8 = 0x0F;
+
+/// Most footprints sampled along a body's path in one tick.
+const MAX_SWEEP_STEPS: u32 = 32;
+
+/// Returns whether a body passing over `pixel` would imprint it.
+fn is_imprintable(pixel: &Pixel, materials: &Materials) -> bool {
+  if pixel.is_void() || pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+    return false;
+  }
+  let material = materials.get(pixel.material);
+  material.softness > 0
+    && material.lifetime == 0
+    && matches!(material.state, PhysicsState::Solid | PhysicsState::Powder)
+    && pixel.damage & IMPRINT_MASK != IMPRINTED
+}
+
+/// Returns the transforms sampled between `from` and `to`, at most one
+/// pixel of travel apart, ending at `to`.
+fn sweep(from: &GlobalTransform, to: &GlobalTransform) -> Vec<GlobalTransform> {
+  let (a, b) = (from.compute_transform(), to.compute_transform());
+  let distance = a.translation.truncate().distance(b.translation.truncate());
+  let steps = (distance.ceil() as u32).clamp(1, MAX_SWEEP_STEPS);
+
+  (1..=steps)
+    .map(|i| {
+      let t = i as f32 / steps as f32;
+      GlobalTransform::from(Transform {
+        translation: a.translation.lerp(b.translation, t),
+        rotation: a.rotation.slerp(b.rotation, t),
+        scale: b.scale,
+      })
+    })
+    .collect()
+}
+
+/// Imprints soft terrain beneath each body along the path it moved since
+/// the previous tick, then records its transform for the next sweep.
+///
+/// Runs after the CA tick, while bodies are blitted at their current
+/// transform.
+pub fn imprint_soft_terrain(
+  materials: Option<Res<Materials>>,
+  mut worlds: Query<&mut PixelWorld>,
+  mut bodies: Query<(&PixelBody, &GlobalTransform, &mut DisplacementState)>,
+  gizmos: GizmosParam,
+) {
+  let (Ok(mut world), Some(materials)) = (worlds.single_mut(), materials) else {
+    return;
+  };
+  // Transforms are recorded even when nothing can be imprinted
+  let any_soft = materials.any_soft();
+
+  for (body, transform, mut displacement) in bodies.iter_mut() {
+    let previous = displacement.previous_transform.replace(*transform);
+    if !any_soft {
+      continue;
+    }
+
+    let mut imprints = HashSet::new();
+    for sample in sweep(&previous.unwrap_or(*transform), transform) {
+      let mut footprint = HashSet::new();
+      for_each_body_pixel(body, &sample, |mapping| {
+        footprint.insert(mapping.world_pos);
+      });
+      for pos in &footprint {
+        let below = WorldPos::new(pos.x, pos.y - 1);
+        if !footprint.contains(&below)
+          && world
+            .get_pixel(below)
+            .is_some_and(|pixel| is_imprintable(pixel, &materials))
+        {
+          imprints.insert(below);
+        }
+      }
+    }
+
+    for pos in imprints {
+      let Some(&pixel) = world.get_pixel(pos) else {
+        continue;
+      };
+      let softness = materials.get(pixel.material).softness;
+      let imprinted = Pixel {
+        color: ColorIndex(pixel.color.0.saturating_add(softness)),
+        damage: (pixel.damage & !IMPRINT_MASK) | IMPRINTED,
+        ..pixel
+      };
+      world.set_pixel(pos, imprinted, gizmos.get());
+    }
+  }
+}
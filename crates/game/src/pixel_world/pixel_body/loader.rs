@@ -15,7 +15,7 @@ use crate::pixel_world::pixel::Pixel;
 ///
 /// Returns a ColorIndex that will map to the best palette offset when
 /// the shader computes `material_id * 8 + (color_index * 7 / 255)`.
-fn find_best_material_color(
+pub(crate) fn find_best_material_color(
   r: u8,
   g: u8,
   b: u8,
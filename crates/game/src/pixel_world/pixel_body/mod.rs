@@ -29,24 +29,36 @@ mod blit;
 mod bomb;
 mod collider;
 mod displacement;
+mod imprint;
 mod loader;
 mod readback;
+mod rest;
 mod spawn;
 mod split;
 
 use bevy::prelude::*;
 pub use blit::{LastBlitTransform, WrittenPixel, update_pixel_bodies};
 pub(crate) use blit::{compute_transformed_aabb, compute_world_aabb};
-pub use bomb::{Bomb, BombInitialState, check_bomb_damage, init_bomb_state, process_detonations};
-pub use collider::generate_collider;
-pub use displacement::DisplacementState;
+pub use bomb::{
+  Bomb, BombInitialState, DetonationConfig, DetonationQueue, Shockwaves, check_bomb_damage,
+  init_bomb_state, process_detonations,
+};
+#[cfg(physics)]
+pub use collider::regenerate_pixel_body_colliders;
+pub use collider::{ColliderRegenConfig, generate_collider};
+pub use displacement::{DisplacementState, sink_bodies_into_powder};
+pub use imprint::{IMPRINTED, imprint_soft_terrain};
 pub use loader::PixelBodyLoader;
+pub(crate) use loader::find_best_material_color;
 pub use readback::{
   apply_readback_changes, detect_external_erasure, readback_pixel_bodies, sync_simulation_to_bodies,
 };
+#[cfg(physics)]
+pub use rest::update_body_rest;
+pub use rest::{RestState, Resting};
 pub use spawn::{
-  PendingPixelBody, PixelBodyIdGenerator, SpawnPixelBody, SpawnPixelBodyFromImage,
-  finalize_pending_pixel_bodies,
+  PendingPixelBody, PixelBodyIdGenerator, PixelBodySpawnFailed, SpawnPixelBody,
+  SpawnPixelBodyFromImage, finalize_pending_pixel_bodies,
 };
 pub use split::split_pixel_bodies;
 
@@ -76,9 +88,14 @@ impl PixelBodyId {
 #[derive(Component, Default)]
 pub struct Persistable;
 
+use crate::pixel_world::coords::LocalPos;
 use crate::pixel_world::pixel::Pixel;
 use crate::pixel_world::primitives::Surface;
 
+/// Game-defined identifier for an anchor point on a pixel body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AnchorId(pub u32);
+
 /// A physics object composed of pixels.
 ///
 /// The surface buffer contains object-local pixel data. The shape mask tracks
@@ -92,6 +109,9 @@ pub struct PixelBody {
   pub shape_mask: Vec<bool>,
   /// Offset from entity transform origin to pixel grid center.
   pub origin: IVec2,
+  /// Named pixels that games attach visuals or logic to. Anchors follow
+  /// their pixel into fragments when the body splits.
+  pub anchors: Vec<(LocalPos, AnchorId)>,
 }
 
 impl PixelBody {
@@ -104,6 +124,7 @@ impl PixelBody {
       surface: Surface::new(width, height),
       shape_mask: vec![false; len],
       origin: IVec2::new(-(width as i32) / 2, -(height as i32) / 2),
+      anchors: Vec::new(),
     }
   }
 
@@ -171,6 +192,23 @@ impl PixelBody {
     !self.shape_mask.iter().any(|&s| s)
   }
 
+  /// Returns the world-space center of the anchored pixel.
+  ///
+  /// Returns `None` if no anchor has this id or its pixel has been destroyed.
+  pub fn anchor_world_pos(&self, id: AnchorId, transform: &GlobalTransform) -> Option<Vec2> {
+    let (pos, _) = self.anchors.iter().find(|(_, anchor)| *anchor == id)?;
+    let (x, y) = (pos.x as u32, pos.y as u32);
+    if !self.is_solid(x, y) {
+      return None;
+    }
+    let local = Vec3::new(
+      self.origin.x as f32 + x as f32 + 0.5,
+      self.origin.y as f32 + y as f32 + 0.5,
+      0.0,
+    );
+    Some(transform.transform_point(local).truncate())
+  }
+
   /// Maps a world-space point to local pixel coordinates if it hits a solid
   /// pixel.
   ///
@@ -201,6 +239,42 @@ impl PixelBody {
 
     Some((lx, ly))
   }
+
+  /// Mirrors the surface, shape mask and anchors in place.
+  ///
+  /// The dimensions don't change, so the origin still centers the grid on
+  /// the entity origin.
+  pub fn flip(&mut self, horizontal: bool, vertical: bool) {
+    if !horizontal && !vertical {
+      return;
+    }
+
+    let (width, height) = (self.width(), self.height());
+    let mirror = |x: u32, y: u32| {
+      (
+        if horizontal { width - 1 - x } else { x },
+        if vertical { height - 1 - y } else { y },
+      )
+    };
+
+    let mut surface = Surface::new(width, height);
+    let mut shape_mask = vec![false; self.shape_mask.len()];
+    for y in 0..height {
+      for x in 0..width {
+        let (mx, my) = mirror(x, y);
+        surface[(mx, my)] = self.surface[(x, y)];
+        shape_mask[(my as usize) * (width as usize) + (mx as usize)] =
+          self.shape_mask[(y as usize) * (width as usize) + (x as usize)];
+      }
+    }
+    self.surface = surface;
+    self.shape_mask = shape_mask;
+
+    for (pos, _) in &mut self.anchors {
+      let (mx, my) = mirror(pos.x as u32, pos.y as u32);
+      *pos = LocalPos::new(mx as u16, my as u16);
+    }
+  }
 }
 
 /// Marker component indicating this pixel body needs its collider regenerated.
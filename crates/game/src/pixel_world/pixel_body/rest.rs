@@ -0,0 +1,141 @@
+//! Kinematic rest for grounded pixel bodies.
+//!
+//! A body's collider and the terrain collider beneath it are solved
+//! separately, so stacked bodies keep nudging each other at the seam. When
+//! [`RestMergeConfig`](crate::pixel_world::collision::RestMergeConfig) is
+//! enabled, a body that stays slow and supported long enough is switched to
+//! kinematic and treated like terrain by everything resting on it.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+#[cfg(physics)]
+use bevy_rapier2d::prelude::RigidBody;
+
+use super::PixelBody;
+use super::blit::for_each_body_pixel;
+use crate::pixel_world::collision::CollisionConfig;
+use crate::pixel_world::coords::WorldPos;
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::world::PixelWorld;
+
+/// Tracks how long a pixel body has been still while grounded.
+#[derive(Component, Default)]
+pub struct RestState {
+  /// Translation and rotation at the previous check.
+  last: Option<(Vec2, f32)>,
+  /// Consecutive frames the body has been still and grounded.
+  still_frames: u32,
+}
+
+/// Marker for pixel bodies held kinematic by rest merging.
+///
+/// Removed, and the body made dynamic again, once nothing solid remains
+/// beneath it.
+#[derive(Component)]
+pub struct Resting;
+
+/// Returns whether any pixel of the body sits directly on solid or powder
+/// pixels that aren't part of the body itself.
+///
+/// Other bodies count as support, since their pixels are blitted into the
+/// world.
+fn is_grounded(
+  body: &PixelBody,
+  transform: &GlobalTransform,
+  world: &PixelWorld,
+  materials: &Materials,
+) -> bool {
+  let mut own = HashSet::new();
+  for_each_body_pixel(body, transform, |mapping| {
+    own.insert(mapping.world_pos);
+  });
+
+  own.iter().any(|pos| {
+    let below = WorldPos::new(pos.x, pos.y - 1);
+    !own.contains(&below)
+      && world.get_pixel(below).is_some_and(|pixel| {
+        !pixel.is_void()
+          && matches!(
+            materials.get(pixel.material).state,
+            PhysicsState::Solid | PhysicsState::Powder
+          )
+      })
+  })
+}
+
+/// Query for bodies considered for kinematic rest.
+#[cfg(physics)]
+type RestBodyQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    Entity,
+    &'static PixelBody,
+    &'static GlobalTransform,
+    &'static mut RigidBody,
+    Option<&'static mut RestState>,
+    Has<Resting>,
+  ),
+>;
+
+/// Switches slow, grounded bodies to kinematic rest and wakes them when
+/// their support disappears.
+#[cfg(physics)]
+pub fn update_body_rest(
+  mut commands: Commands,
+  config: Res<CollisionConfig>,
+  materials: Option<Res<Materials>>,
+  worlds: Query<&PixelWorld>,
+  mut bodies: RestBodyQuery,
+) {
+  let rest = &config.rest_merge;
+  let (Ok(world), Some(materials)) = (worlds.single(), materials) else {
+    return;
+  };
+
+  for (entity, body, transform, mut rigid_body, state, resting) in bodies.iter_mut() {
+    if !rest.enabled {
+      if resting {
+        *rigid_body = RigidBody::Dynamic;
+        commands.entity(entity).remove::<(Resting, RestState)>();
+      }
+      continue;
+    }
+    if !resting && *rigid_body != RigidBody::Dynamic {
+      continue;
+    }
+    let Some(mut state) = state else {
+      commands.entity(entity).insert(RestState::default());
+      continue;
+    };
+
+    let grounded = is_grounded(body, transform, world, &materials);
+    if resting {
+      if !grounded {
+        *rigid_body = RigidBody::Dynamic;
+        commands.entity(entity).remove::<Resting>();
+        *state = RestState::default();
+      }
+      continue;
+    }
+
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    let current = (translation.truncate(), rotation.to_euler(EulerRot::ZYX).0);
+    let still = state.last.is_some_and(|(position, angle)| {
+      position.distance(current.0) <= rest.max_step
+        && (angle - current.1).abs() <= rest.max_rotation
+    });
+    state.last = Some(current);
+
+    if !(still && grounded) {
+      state.still_frames = 0;
+      continue;
+    }
+    state.still_frames += 1;
+    if state.still_frames >= rest.settle_frames {
+      *rigid_body = RigidBody::KinematicPositionBased;
+      commands.entity(entity).insert(Resting);
+    }
+  }
+}
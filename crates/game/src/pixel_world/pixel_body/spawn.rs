@@ -6,11 +6,16 @@ use bevy::prelude::*;
 #[cfg(physics)]
 use bevy_rapier2d::prelude::Collider;
 
-use super::{DisplacementState, LastBlitTransform, Persistable, PixelBodyId, PixelBodyLoader};
+use super::{
+  Bomb, DisplacementState, LastBlitTransform, Persistable, PixelBody, PixelBodyId, PixelBodyLoader,
+};
 #[cfg(physics)]
 use crate::pixel_world::collision::CollisionQueryPoint;
-use crate::pixel_world::coords::MaterialId;
+use crate::pixel_world::coords::{ChunkPos, MaterialId, WorldPos, WorldRect};
+use crate::pixel_world::edit_log::{LoggedBomb, WorldEdit};
+use crate::pixel_world::material::{Materials, PhysicsState};
 use crate::pixel_world::palette::GlobalPalette;
+use crate::pixel_world::world::PixelWorld;
 #[cfg(physics)]
 use crate::pixel_world::world::streaming::culling::StreamCulled;
 
@@ -108,6 +113,12 @@ pub struct SpawnPixelBody {
   pub material: MaterialId,
   /// World position to spawn at.
   pub position: Vec2,
+  /// Area searched for a position free of solid terrain, replacing
+  /// `position` when set.
+  pub search_rect: Option<WorldRect>,
+  /// World searched for free space; `None` uses the world that has the
+  /// search rect loaded.
+  pub world: Option<Entity>,
   /// Extra components to insert on the spawned entity.
   extra: Option<Box<dyn FnOnce(&mut bevy::ecs::world::EntityWorldMut) + Send + Sync>>,
 }
@@ -121,10 +132,32 @@ impl SpawnPixelBody {
       path: path.into(),
       material,
       position,
+      search_rect: None,
+      world: None,
       extra: None,
     }
   }
 
+  /// Spawns the body at the first position within `search_rect` where its
+  /// shape doesn't overlap solid terrain.
+  ///
+  /// Positions are scanned bottom-to-top, left-to-right, so the same terrain
+  /// always yields the same placement. The body stays pending until every
+  /// chunk the rect overlaps is seeded, in the world set with
+  /// [`in_world`](Self::in_world) or else the first world that has them
+  /// all. If no position fits, the body is not spawned and a
+  /// [`PixelBodySpawnFailed`] message is written.
+  pub fn find_free_space(mut self, search_rect: WorldRect) -> Self {
+    self.search_rect = Some(search_rect);
+    self
+  }
+
+  /// Searches for free space in the world on `world`.
+  pub fn in_world(mut self, world: Entity) -> Self {
+    self.world = Some(world);
+    self
+  }
+
   /// Adds extra components to the spawned entity.
   ///
   /// The closure receives a mutable reference to the entity and can insert
@@ -170,6 +203,10 @@ pub struct SpawnPixelBodyFromImage {
   pub material: MaterialId,
   /// World position to spawn at.
   pub position: Vec2,
+  /// Mirror the body left-to-right.
+  pub flip_x: bool,
+  /// Mirror the body top-to-bottom.
+  pub flip_y: bool,
 }
 
 impl SpawnPixelBodyFromImage {
@@ -179,8 +216,20 @@ impl SpawnPixelBodyFromImage {
       image,
       material,
       position,
+      flip_x: false,
+      flip_y: false,
     }
   }
+
+  /// Mirrors the image before the body is built, so one asset serves both
+  /// facings.
+  ///
+  /// The shape mask, collider and origin all come from the mirrored pixels.
+  pub fn flipped(mut self, horizontal: bool, vertical: bool) -> Self {
+    self.flip_x = horizontal;
+    self.flip_y = vertical;
+    self
+  }
 }
 
 impl bevy::ecs::system::Command for SpawnPixelBodyFromImage {
@@ -190,12 +239,16 @@ impl bevy::ecs::system::Command for SpawnPixelBodyFromImage {
       image: self.image,
       material: self.material,
       position: self.position,
+      search_rect: None,
+      world: None,
+      flip_x: self.flip_x,
+      flip_y: self.flip_y,
     });
   }
 }
 
 impl bevy::ecs::system::Command for SpawnPixelBody {
-  fn apply(self, world: &mut bevy::ecs::world::World) {
+  fn apply(mut self, world: &mut bevy::ecs::world::World) {
     // Load the image asset
     let asset_server = world.resource::<AssetServer>();
     let image_handle: Handle<Image> = asset_server.load(&self.path);
@@ -205,12 +258,22 @@ impl bevy::ecs::system::Command for SpawnPixelBody {
       image: image_handle,
       material: self.material,
       position: self.position,
+      search_rect: self.search_rect,
+      world: self.world,
+      flip_x: false,
+      flip_y: false,
     });
 
     // Apply extra components if provided
-    if let Some(extra) = self.extra {
+    if let Some(extra) = self.extra.take() {
       extra(&mut entity);
     }
+
+    let bomb = entity.get::<Bomb>().map(LoggedBomb::from);
+    let mut worlds = world.query::<&mut PixelWorld>();
+    if let Some(mut pixel_world) = worlds.iter_mut(world).next() {
+      pixel_world.record_edit(|| WorldEdit::capture_spawn(&self, bomb));
+    }
   }
 }
 
@@ -223,18 +286,101 @@ pub struct PendingPixelBody {
   pub material: MaterialId,
   /// World position to spawn at.
   pub position: Vec2,
+  /// Area searched for a free position, see
+  /// [`SpawnPixelBody::find_free_space`].
+  pub search_rect: Option<WorldRect>,
+  /// World searched for free space, see [`SpawnPixelBody::in_world`].
+  pub world: Option<Entity>,
+  /// Mirror the body left-to-right, see
+  /// [`SpawnPixelBodyFromImage::flipped`].
+  pub flip_x: bool,
+  /// Mirror the body top-to-bottom.
+  pub flip_y: bool,
+}
+
+/// Message written when a pixel body finds no free position in its search
+/// rect and is discarded.
+#[derive(Message, Clone, Debug)]
+pub struct PixelBodySpawnFailed {
+  /// Handle to the body's image.
+  pub image: Handle<Image>,
+  /// Material of the body.
+  pub material: MaterialId,
+  /// Area that was searched.
+  pub search_rect: WorldRect,
+}
+
+/// Returns true if every chunk overlapped by `rect` is seeded in `world`.
+fn is_rect_seeded(world: &PixelWorld, rect: WorldRect) -> bool {
+  let (min, _) = WorldPos::new(rect.x, rect.y).to_chunk_and_local();
+  let (max, _) = WorldPos::new(
+    rect.x + rect.width.max(1) as i64 - 1,
+    rect.y + rect.height.max(1) as i64 - 1,
+  )
+  .to_chunk_and_local();
+  (min.y..=max.y)
+    .all(|y| (min.x..=max.x).all(|x| world.chunk_generation(ChunkPos::new(x, y)).is_some()))
+}
+
+/// Returns the first entity translation within `rect` at which no solid
+/// pixel of `body` overlaps solid or powder terrain.
+///
+/// Assumes an unrotated body, as spawned bodies are.
+fn find_free_position(
+  body: &PixelBody,
+  rect: WorldRect,
+  world: &PixelWorld,
+  materials: &Materials,
+) -> Option<Vec2> {
+  let solid: Vec<(i64, i64)> = (0..body.height())
+    .flat_map(|y| (0..body.width()).map(move |x| (x, y)))
+    .filter(|&(x, y)| body.is_solid(x, y))
+    .map(|(x, y)| (x as i64, y as i64))
+    .collect();
+
+  let is_free = |pos: WorldPos| {
+    world.get_pixel(pos).is_some_and(|pixel| {
+      pixel.is_void()
+        || matches!(
+          materials.get(pixel.material).state,
+          PhysicsState::Liquid | PhysicsState::Gas
+        )
+    })
+  };
+
+  let max_x = rect.x + rect.width as i64 - body.width() as i64;
+  let max_y = rect.y + rect.height as i64 - body.height() as i64;
+  for y in rect.y..=max_y {
+    for x in rect.x..=max_x {
+      if solid
+        .iter()
+        .all(|&(lx, ly)| is_free(WorldPos::new(x + lx, y + ly)))
+      {
+        // Pixel (lx, ly) lands at translation + origin + (lx, ly)
+        return Some(Vec2::new(
+          (x - body.origin.x as i64) as f32,
+          (y - body.origin.y as i64) as f32,
+        ));
+      }
+    }
+  }
+  None
 }
 
 /// System that finalizes pending pixel body spawns when their images are
 /// loaded.
 ///
 /// This system should be added to your app when using `SpawnPixelBody`.
+#[allow(clippy::too_many_arguments)]
 pub fn finalize_pending_pixel_bodies(
   mut commands: Commands,
   pending: Query<(Entity, &PendingPixelBody)>,
   images: Option<Res<Assets<Image>>>,
   palette: Option<Res<GlobalPalette>>,
+  materials: Option<Res<Materials>>,
+  worlds: Query<(Entity, &PixelWorld)>,
   mut id_generator: ResMut<PixelBodyIdGenerator>,
+  mut spawn_failed: MessageWriter<PixelBodySpawnFailed>,
 ) {
   let Some(images) = images else { return };
   let Some(palette) = palette else { return };
@@ -245,12 +391,40 @@ pub fn finalize_pending_pixel_bodies(
     };
 
     // Create pixel body from image using global palette for color mapping
-    let Some(body) =
+    let Some(mut body) =
       PixelBodyLoader::from_image_with_material(image, pending_body.material, &palette)
     else {
       commands.entity(entity).despawn();
       continue;
     };
+    body.flip(pending_body.flip_x, pending_body.flip_y);
+
+    let mut position = pending_body.position;
+    if let Some(search_rect) = pending_body.search_rect {
+      let Some(materials) = materials.as_deref() else {
+        continue;
+      };
+      // Wait for the search rect to load, so unloaded terrain isn't taken
+      // for occupied
+      let Some((_, world)) = worlds.iter().find(|&(world_entity, world)| {
+        pending_body
+          .world
+          .is_none_or(|target| target == world_entity)
+          && is_rect_seeded(world, search_rect)
+      }) else {
+        continue;
+      };
+      let Some(free) = find_free_position(&body, search_rect, world, materials) else {
+        spawn_failed.write(PixelBodySpawnFailed {
+          image: pending_body.image.clone(),
+          material: pending_body.material,
+          search_rect,
+        });
+        commands.entity(entity).despawn();
+        continue;
+      };
+      position = free;
+    }
 
     // Generate collider (physics only)
     #[cfg(physics)]
@@ -263,7 +437,7 @@ pub fn finalize_pending_pixel_bodies(
 
     // Replace pending entity with full pixel body
     let mut entity_commands = commands.entity(entity);
-    let translation = pending_body.position.extend(0.0);
+    let translation = position.extend(0.0);
     entity_commands.remove::<PendingPixelBody>().insert((
       body,
       LastBlitTransform::default(),
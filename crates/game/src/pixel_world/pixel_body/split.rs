@@ -14,6 +14,7 @@ use super::{
 #[cfg(physics)]
 use crate::pixel_world::collision::CollisionQueryPoint;
 use crate::pixel_world::collision::Stabilizing;
+use crate::pixel_world::coords::LocalPos;
 use crate::pixel_world::debug_shim::GizmosParam;
 use crate::pixel_world::material::Materials;
 use crate::pixel_world::persistence::PersistenceTasks;
@@ -425,6 +426,21 @@ fn create_fragment(
     }
   }
 
+  // Keep anchors whose pixel ended up in this fragment
+  for &(pos, id) in &parent.anchors {
+    let (Some(local_x), Some(local_y)) = (
+      (pos.x as u32).checked_sub(component.min_x),
+      (pos.y as u32).checked_sub(component.min_y),
+    ) else {
+      continue;
+    };
+    if fragment_body.is_solid(local_x, local_y) {
+      fragment_body
+        .anchors
+        .push((LocalPos::new(local_x as u16, local_y as u16), id));
+    }
+  }
+
   // Compute centroid in parent-local coords
   let centroid_x = component.min_x as f32 + (width as f32 / 2.0) + parent.origin.x as f32;
   let centroid_y = component.min_y as f32 + (height as f32 / 2.0) + parent.origin.y as f32;
@@ -28,6 +28,10 @@
 //!
 //! Chunks must be on `RenderLayers::layer(1)` to be rendered by the scene
 //! camera.
+//!
+//! The visible size of the low-res target is published as
+//! [`PixelRenderTargetInfo`] for screen effects that need the source
+//! resolution.
 
 mod components;
 mod config;
@@ -45,7 +49,8 @@ pub use material::PixelBlitMaterial;
 pub use setup::{
   FULLRES_SPRITE_LAYER, PixelBlitCamera, PixelBlitQuad, PixelFullresCamera, PixelSceneCamera,
 };
-pub use state::PixelCameraState;
+pub use state::{PixelCameraState, PixelRenderTargetInfo};
+pub use systems::pixel_camera_publish_target_info;
 
 /// System set for pixel camera systems.
 ///
@@ -76,6 +81,7 @@ impl Plugin for PixelCameraPlugin {
     // Initialize resources
     app.init_resource::<PixelCameraConfig>();
     app.init_resource::<PixelCameraState>();
+    app.init_resource::<PixelRenderTargetInfo>();
 
     // Configure PixelCameraSet to run after transform propagation
     app.configure_sets(
@@ -101,6 +107,7 @@ impl Plugin for PixelCameraPlugin {
         systems::pixel_camera_snap,
         systems::pixel_camera_sync_state,
         systems::pixel_camera_handle_resize,
+        systems::pixel_camera_publish_target_info,
         systems::configure_egui_camera,
       )
         .chain()
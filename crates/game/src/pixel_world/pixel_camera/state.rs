@@ -23,3 +23,15 @@ pub struct PixelCameraState {
   /// Last snapped camera position (used to detect external camera movement).
   pub last_snapped_pos: Vec2,
 }
+
+/// Low-res render target size advertised to screen-space post-processing.
+///
+/// Published by the pixel camera once it is set up and again on every
+/// resize, for effects that need the source resolution. Nothing in this
+/// crate consumes it yet.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PixelRenderTargetInfo {
+  /// Visible resolution of the low-res target, excluding the subpixel
+  /// margin. Zero until the pixel camera is initialized.
+  pub logical_size: UVec2,
+}
@@ -7,7 +7,7 @@ use super::components::LogicalCameraPosition;
 use super::config::PixelCameraConfig;
 use super::material::PixelBlitMaterial;
 use super::setup::{PixelBlitCamera, PixelBlitQuad, PixelFullresCamera, PixelSceneCamera};
-use super::state::PixelCameraState;
+use super::state::{PixelCameraState, PixelRenderTargetInfo};
 
 /// System: Stores the logical camera position before snapping.
 ///
@@ -127,6 +127,25 @@ pub fn pixel_camera_sync_state(
   }
 }
 
+/// System: Publishes the low-res target size to [`PixelRenderTargetInfo`].
+///
+/// Only writes the resource when the size changes, so consumers can react
+/// with `is_changed`.
+pub fn pixel_camera_publish_target_info(
+  config: Res<PixelCameraConfig>,
+  state: Res<PixelCameraState>,
+  mut info: ResMut<PixelRenderTargetInfo>,
+) {
+  if !state.initialized {
+    return;
+  }
+
+  let margin = UVec2::splat(config.margin * 2);
+  info.set_if_neq(PixelRenderTargetInfo {
+    logical_size: state.target_size.saturating_sub(margin),
+  });
+}
+
 /// System: Handles viewport resize by recreating the render target.
 #[allow(clippy::too_many_arguments)]
 pub fn pixel_camera_handle_resize(
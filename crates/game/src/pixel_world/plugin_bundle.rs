@@ -8,7 +8,10 @@ use crate::pixel_world::PixelWorldPlugin;
 use crate::pixel_world::bodies_plugin::PixelBodiesPlugin;
 #[cfg(physics)]
 use crate::pixel_world::buoyancy::SubmersionPhysicsConfig;
-use crate::pixel_world::buoyancy::{Buoyancy2dPlugin, BuoyancyConfig, SubmersionConfig};
+use crate::pixel_world::buoyancy::{
+  Buoyancy2dPlugin, BuoyancyConfig, SplashConfig, SubmersionConfig,
+};
+use crate::pixel_world::collision::CollisionConfig;
 use crate::pixel_world::diagnostics::DiagnosticsPlugin;
 use crate::pixel_world::pixel_awareness::{GridSampleConfig, PixelAwarenessPlugin};
 use crate::pixel_world::world::streaming::CullingConfig;
@@ -16,7 +19,9 @@ use crate::pixel_world::world::streaming::CullingConfig;
 /// Plugin group that adds [`PixelWorldPlugin`] and all optional sub-plugins
 /// based on enabled features.
 ///
-/// Persistence is always enabled - you must provide a save path.
+/// Persistence is always enabled - you must provide a save path. Every
+/// sub-plugin is enabled by default; `without_*` methods opt out of
+/// individual ones, and configuring a sub-plugin enables it again.
 ///
 /// # Usage
 ///
@@ -24,19 +29,22 @@ use crate::pixel_world::world::streaming::CullingConfig;
 /// app.add_plugins(
 ///     PixelWorldFullBundle::new(PersistenceConfig::at("/path/to/save.save"))
 ///         .submersion(SubmersionConfig { submersion_threshold: 0.5, ..default() })
+///         .without_diagnostics()
 /// );
 /// ```
 pub struct PixelWorldFullBundle {
   /// Core world plugin (streaming, CA, rendering, persistence).
   pub world: PixelWorldPlugin,
   /// Pixel bodies plugin (spawning, collision, body persistence).
-  pub bodies: PixelBodiesPlugin,
+  pub bodies: Option<PixelBodiesPlugin>,
+  /// Collision configuration override for the bodies plugin.
+  pub collision: Option<CollisionConfig>,
   /// Pixel awareness plugin (liquid detection sampling).
-  pub awareness: PixelAwarenessPlugin,
+  pub awareness: Option<PixelAwarenessPlugin>,
   /// Buoyancy and submersion plugin (threshold, events, forces, physics).
-  pub buoyancy: Buoyancy2dPlugin,
+  pub buoyancy: Option<Buoyancy2dPlugin>,
   /// Diagnostics plugin (frame time, simulation metrics).
-  pub diagnostics: DiagnosticsPlugin,
+  pub diagnostics: Option<DiagnosticsPlugin>,
 }
 
 impl PixelWorldFullBundle {
@@ -47,13 +55,45 @@ impl PixelWorldFullBundle {
   pub fn new(persistence: PersistenceConfig) -> Self {
     Self {
       world: PixelWorldPlugin::new(persistence),
-      bodies: PixelBodiesPlugin,
-      awareness: PixelAwarenessPlugin::default(),
-      buoyancy: Buoyancy2dPlugin::default(),
-      diagnostics: DiagnosticsPlugin,
+      bodies: Some(PixelBodiesPlugin),
+      collision: None,
+      awareness: Some(PixelAwarenessPlugin::default()),
+      buoyancy: Some(Buoyancy2dPlugin::default()),
+      diagnostics: Some(DiagnosticsPlugin),
     }
   }
 
+  /// Excludes the pixel bodies plugin.
+  pub fn without_bodies(mut self) -> Self {
+    self.bodies = None;
+    self
+  }
+
+  /// Excludes the pixel awareness plugin.
+  pub fn without_awareness(mut self) -> Self {
+    self.awareness = None;
+    self
+  }
+
+  /// Excludes the buoyancy and submersion plugin.
+  pub fn without_buoyancy(mut self) -> Self {
+    self.buoyancy = None;
+    self
+  }
+
+  /// Excludes the diagnostics plugin.
+  pub fn without_diagnostics(mut self) -> Self {
+    self.diagnostics = None;
+    self
+  }
+
+  /// Sets the collision configuration and enables the bodies plugin.
+  pub fn with_collision(mut self, config: CollisionConfig) -> Self {
+    self.bodies = Some(PixelBodiesPlugin);
+    self.collision = Some(config);
+    self
+  }
+
   /// Sets the culling configuration.
   pub fn culling(mut self, config: CullingConfig) -> Self {
     self.world = self.world.culling(config);
@@ -62,37 +102,64 @@ impl PixelWorldFullBundle {
 
   /// Sets the grid sampling configuration for pixel awareness.
   pub fn awareness(mut self, config: GridSampleConfig) -> Self {
-    self.awareness = PixelAwarenessPlugin::new(config);
+    self.awareness = Some(PixelAwarenessPlugin::new(config));
     self
   }
 
   /// Sets the submersion threshold configuration.
   pub fn submersion(mut self, config: SubmersionConfig) -> Self {
-    self.buoyancy = self.buoyancy.with_submersion(config);
+    self.buoyancy = Some(self.buoyancy.unwrap_or_default().with_submersion(config));
+    self
+  }
+
+  /// Sets the impact splash configuration.
+  pub fn splash(mut self, config: SplashConfig) -> Self {
+    self.buoyancy = Some(self.buoyancy.unwrap_or_default().with_splash(config));
     self
   }
 
   /// Sets the buoyancy configuration.
   pub fn buoyancy(mut self, config: BuoyancyConfig) -> Self {
-    self.buoyancy = Buoyancy2dPlugin::new(config);
+    self.buoyancy = Some(Buoyancy2dPlugin::new(config));
     self
   }
 
   /// Sets the submersion physics configuration.
   #[cfg(physics)]
   pub fn submersion_physics(mut self, config: SubmersionPhysicsConfig) -> Self {
-    self.buoyancy = self.buoyancy.with_physics(config);
+    self.buoyancy = Some(self.buoyancy.unwrap_or_default().with_physics(config));
     self
   }
 }
 
 impl PluginGroup for PixelWorldFullBundle {
   fn build(self) -> PluginGroupBuilder {
-    PluginGroupBuilder::start::<Self>()
-      .add(self.world)
-      .add(self.bodies)
-      .add(self.awareness)
-      .add(self.buoyancy)
-      .add(self.diagnostics)
+    let mut group = PluginGroupBuilder::start::<Self>().add(self.world);
+    if let Some(bodies) = self.bodies {
+      // Inserted first so the bodies plugin keeps it instead of the default
+      if let Some(config) = self.collision {
+        group = group.add(CollisionConfigPlugin(config));
+      }
+      group = group.add(bodies);
+    }
+    if let Some(awareness) = self.awareness {
+      group = group.add(awareness);
+    }
+    if let Some(buoyancy) = self.buoyancy {
+      group = group.add(buoyancy);
+    }
+    if let Some(diagnostics) = self.diagnostics {
+      group = group.add(diagnostics);
+    }
+    group
+  }
+}
+
+/// Inserts a [`CollisionConfig`] override ahead of [`PixelBodiesPlugin`].
+struct CollisionConfigPlugin(CollisionConfig);
+
+impl Plugin for CollisionConfigPlugin {
+  fn build(&self, app: &mut App) {
+    app.insert_resource(self.0.clone());
   }
 }
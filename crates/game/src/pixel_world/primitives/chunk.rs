@@ -6,8 +6,8 @@
 //! organization. See `docs/architecture/chunk-pooling.md` for the pooling
 //! lifecycle.
 
-use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos, TILE_SIZE, TILES_PER_CHUNK};
-use crate::pixel_world::pixel::PixelSurface;
+use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos, LocalPos, TILE_SIZE, TILES_PER_CHUNK};
+use crate::pixel_world::pixel::{Pixel, PixelSurface};
 
 /// Pixels per heat cell edge.
 pub const HEAT_CELL_SIZE: u32 = 4;
@@ -132,6 +132,38 @@ impl TileDirtyRect {
     }
   }
 
+  /// Size of the encoded form in bytes.
+  pub const ENCODED_SIZE: usize = 11;
+
+  /// Encodes the rect, including its cooldown, for simulation snapshots.
+  pub fn encode(&self) -> [u8; Self::ENCODED_SIZE] {
+    let mut out = [0u8; Self::ENCODED_SIZE];
+    for (i, bounds) in [self.next, self.current].into_iter().enumerate() {
+      if let Some(b) = bounds {
+        out[i * 5..i * 5 + 5].copy_from_slice(&[1, b.min_x, b.min_y, b.max_x, b.max_y]);
+      }
+    }
+    out[10] = self.cooldown;
+    out
+  }
+
+  /// Decodes a rect produced by [`encode`](Self::encode).
+  pub fn decode(bytes: &[u8; Self::ENCODED_SIZE]) -> Self {
+    let bounds = |b: &[u8]| {
+      (b[0] != 0).then_some(TileBounds {
+        min_x: b[1],
+        min_y: b[2],
+        max_x: b[3],
+        max_y: b[4],
+      })
+    };
+    Self {
+      next: bounds(&bytes[0..5]),
+      current: bounds(&bytes[5..10]),
+      cooldown: bytes[10],
+    }
+  }
+
   /// Returns the bounds, or None if sleeping.
   pub fn bounds(&self) -> Option<TileBounds> {
     if self.cooldown > 0 {
@@ -264,6 +296,58 @@ impl HeatDirtyTracker {
     self.active = [0; 4];
     self.cooldown = [0; HEAT_TILE_COUNT];
   }
+
+  /// Size of the encoded form in bytes.
+  pub(crate) const ENCODED_SIZE: usize = 32 + HEAT_TILE_COUNT;
+
+  /// Encodes active bits and cooldowns for simulation snapshots.
+  pub(crate) fn encode(&self) -> Vec<u8> {
+    let mut out = Vec::with_capacity(Self::ENCODED_SIZE);
+    for word in self.active {
+      out.extend_from_slice(&word.to_le_bytes());
+    }
+    out.extend_from_slice(&self.cooldown);
+    out
+  }
+
+  /// Decodes a tracker produced by [`encode`](Self::encode).
+  ///
+  /// `bytes` must be exactly [`ENCODED_SIZE`](Self::ENCODED_SIZE) long.
+  pub(crate) fn decode(bytes: &[u8]) -> Self {
+    let mut active = [0u64; 4];
+    for (i, word) in active.iter_mut().enumerate() {
+      *word = u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap());
+    }
+    let mut cooldown = [0u8; HEAT_TILE_COUNT];
+    cooldown.copy_from_slice(&bytes[32..32 + HEAT_TILE_COUNT]);
+    Self { active, cooldown }
+  }
+}
+
+/// Changed pixels of a chunk with their new values, in row-major order.
+///
+/// Produced by [`Chunk::diff`] and applied with [`Chunk::apply_diff`].
+pub type ChunkDiff = Vec<(LocalPos, Pixel)>;
+
+/// Returns the pixels of `new` that differ from `old`, both row-major
+/// buffers of a surface `width` pixels wide.
+pub(crate) fn diff_pixels(width: u32, old: &[Pixel], new: &[Pixel]) -> ChunkDiff {
+  assert_eq!(old.len(), new.len(), "Diffed surfaces must match in size");
+  old
+    .chunks_exact(width as usize)
+    .zip(new.chunks_exact(width as usize))
+    .enumerate()
+    // Unchanged rows compare as a whole
+    .filter(|(_, (old_row, new_row))| old_row != new_row)
+    .flat_map(|(y, (old_row, new_row))| {
+      old_row
+        .iter()
+        .zip(new_row)
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(move |(x, (_, &pixel))| (LocalPos::new(x as u16, y as u16), pixel))
+    })
+    .collect()
 }
 
 /// A chunk of the world containing pixel data.
@@ -288,6 +372,12 @@ pub struct Chunk {
 }
 
 impl Chunk {
+  /// Approximate bytes owned by a full-size chunk (pixels, tile state and
+  /// heat layer).
+  pub const MEMORY_SIZE: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize * size_of::<Pixel>()
+    + TILE_COUNT * (size_of::<TileDirtyRect>() + size_of::<bool>())
+    + HEAT_CELL_COUNT;
+
   /// Creates a new chunk with the given dimensions.
   pub fn new(width: u32, height: u32) -> Self {
     Self {
@@ -328,6 +418,16 @@ impl Chunk {
     &mut self.tile_dirty_rects[idx]
   }
 
+  /// Returns all tile dirty rects in row-major tile order.
+  pub(crate) fn tile_dirty_rects(&self) -> &[TileDirtyRect] {
+    &self.tile_dirty_rects
+  }
+
+  /// Replaces all tile dirty rects. `rects` must be in row-major tile order.
+  pub(crate) fn restore_tile_dirty_rects(&mut self, rects: &[TileDirtyRect]) {
+    self.tile_dirty_rects.copy_from_slice(rects);
+  }
+
   /// Marks a pixel as dirty, expanding the appropriate tile's dirty rect.
   ///
   /// Also handles boundary propagation: if the pixel is at a tile edge,
@@ -365,6 +465,34 @@ impl Chunk {
     }
   }
 
+  /// Returns the pixels of `other` that differ from this chunk.
+  ///
+  /// Applying the result to a copy of this chunk with
+  /// [`apply_diff`](Self::apply_diff) makes its pixels match `other`. Unlike
+  /// persistence deltas, the baseline is any chunk rather than the seeder
+  /// output, so diffs can be sent to peers holding the same baseline.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the chunks differ in size.
+  pub fn diff(&self, other: &Chunk) -> ChunkDiff {
+    diff_pixels(
+      self.pixels.width(),
+      self.pixels.as_slice(),
+      other.pixels.as_slice(),
+    )
+  }
+
+  /// Writes the pixels of `diff`, marking each dirty so simulation picks
+  /// them up.
+  pub fn apply_diff(&mut self, diff: &[(LocalPos, Pixel)]) {
+    for &(pos, pixel) in diff {
+      let (x, y) = (pos.x as u32, pos.y as u32);
+      self.pixels[(x, y)] = pixel;
+      self.mark_pixel_dirty(x, y);
+    }
+  }
+
   /// Sets all tile dirty rects to full (entire tile needs simulation).
   pub fn set_all_dirty_rects_full(&mut self) {
     for rect in self.tile_dirty_rects.iter_mut() {
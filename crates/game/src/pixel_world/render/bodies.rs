@@ -0,0 +1,162 @@
+//! Drawing pixel bodies as sprites.
+//!
+//! Bodies are blitted into chunk pixels every tick so the simulation sees
+//! them. By default those blitted pixels are also what gets drawn, which
+//! ties the body's look to the CA grid and to chunk uploads. In
+//! [`BodyRenderMode::Sprite`] chunk textures leave body pixels out, and each
+//! body is drawn from its own surface by a child quad following its
+//! transform instead.
+
+use bevy::prelude::*;
+use bevy::sprite_render::MeshMaterial2d;
+
+use super::material::ChunkMaterial;
+use super::pipeline::{create_chunk_quad, create_pixel_texture, upload_pixels};
+use crate::pixel_world::pixel::{PixelFlags, PixelSurface};
+use crate::pixel_world::pixel_body::PixelBody;
+use crate::pixel_world::world::PixelWorld;
+use crate::pixel_world::world::plugin::SharedPaletteTexture;
+
+/// How pixel bodies are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BodyRenderMode {
+  /// Bodies are drawn as part of the chunk textures they are blitted into.
+  #[default]
+  Blit,
+  /// Bodies are drawn as sprites from their own surface, and left out of
+  /// chunk textures. They still take part in the simulation.
+  Sprite,
+}
+
+/// Quad drawing a pixel body in [`BodyRenderMode::Sprite`], spawned as a
+/// child of the body.
+#[derive(Component)]
+pub struct PixelBodySprite {
+  /// Raw pixel texture mirroring the body's surface.
+  pub texture: Handle<Image>,
+  /// Material drawing `texture`.
+  pub material: Handle<ChunkMaterial>,
+}
+
+/// Z offset of body sprites, in front of the chunks behind them.
+const SPRITE_Z: f32 = 0.5;
+
+/// Uploads raw pixel data to a pixel texture like [`upload_pixels`], with
+/// pixels belonging to pixel bodies cleared to void.
+pub fn upload_pixels_without_bodies(pixels: &PixelSurface, image: &mut Image) {
+  upload_pixels(pixels, image);
+  let Some(ref mut data) = image.data else {
+    return;
+  };
+  // Bytes are [material, color, damage, flags]; void is all zero
+  for texel in data.chunks_exact_mut(4) {
+    if texel[3] & PixelFlags::PIXEL_BODY.bits() != 0 {
+      texel.fill(0);
+    }
+  }
+}
+
+/// Spawns the sprite quad for a body as its child.
+fn spawn_body_sprite(
+  commands: &mut Commands,
+  entity: Entity,
+  body: &PixelBody,
+  images: &mut Assets<Image>,
+  meshes: &mut Assets<Mesh>,
+  materials: &mut Assets<ChunkMaterial>,
+  palette: Option<Handle<Image>>,
+) {
+  let texture = create_pixel_texture(images, body.width(), body.height());
+  if let Some(image) = images.get_mut(&texture) {
+    upload_pixels(&body.surface, image);
+  }
+  let material = materials.add(ChunkMaterial {
+    pixel_texture: Some(texture.clone()),
+    palette_texture: palette,
+    brightness: 1.0,
+    cracks: Default::default(),
+    heat_texture: None,
+    heat_glow: 0.0,
+    depth_ramps: Default::default(),
+  });
+  let mesh = meshes.add(create_chunk_quad(body.width() as f32, body.height() as f32));
+
+  // The quad's bottom-left corner is the body's pixel grid origin
+  let sprite = commands
+    .spawn((
+      Mesh2d(mesh),
+      MeshMaterial2d(material.clone()),
+      Transform::from_xyz(body.origin.x as f32, body.origin.y as f32, SPRITE_Z),
+      PixelBodySprite { texture, material },
+    ))
+    .id();
+  commands
+    .entity(entity)
+    .insert_if_new(Visibility::default())
+    .add_child(sprite);
+}
+
+/// System: Keeps a sprite on every pixel body while any world draws bodies
+/// as sprites, uploading a body's surface whenever it changes.
+///
+/// Despawns all body sprites once no world uses
+/// [`BodyRenderMode::Sprite`]. Does nothing without image, mesh and chunk
+/// material assets.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sync_pixel_body_sprites(
+  mut commands: Commands,
+  worlds: Query<&PixelWorld>,
+  bodies: Query<(Entity, Ref<PixelBody>, Option<&Children>)>,
+  sprites: Query<(Entity, &PixelBodySprite)>,
+  images: Option<ResMut<Assets<Image>>>,
+  meshes: Option<ResMut<Assets<Mesh>>>,
+  materials: Option<ResMut<Assets<ChunkMaterial>>>,
+  palette: Option<Res<SharedPaletteTexture>>,
+) {
+  let sprite_mode = worlds
+    .iter()
+    .any(|world| world.config().body_render_mode == BodyRenderMode::Sprite);
+  if !sprite_mode {
+    for (entity, _) in &sprites {
+      commands.entity(entity).despawn();
+    }
+    return;
+  }
+  let (Some(mut images), Some(mut meshes), Some(mut materials)) = (images, meshes, materials)
+  else {
+    return;
+  };
+  let palette = palette.map(|palette| palette.handle.clone());
+
+  for (entity, body, children) in &bodies {
+    let sprite =
+      children.and_then(|children| children.iter().find_map(|child| sprites.get(child).ok()));
+
+    if let Some((sprite_entity, sprite)) = sprite {
+      if !body.is_changed() {
+        continue;
+      }
+      if let Some(image) = images.get_mut(&sprite.texture)
+        && image.width() == body.width()
+        && image.height() == body.height()
+      {
+        upload_pixels(&body.surface, image);
+        // Touch material to force bind group refresh (Bevy workaround)
+        let _ = materials.get_mut(&sprite.material);
+        continue;
+      }
+      // The body changed size, so its quad is rebuilt
+      commands.entity(sprite_entity).despawn();
+    }
+
+    spawn_body_sprite(
+      &mut commands,
+      entity,
+      &body,
+      &mut images,
+      &mut meshes,
+      &mut materials,
+      palette.clone(),
+    );
+  }
+}
@@ -0,0 +1,110 @@
+//! Crack overlay for chipped pixels.
+//!
+//! Blasts and digging crack pixels of materials with durability before
+//! breaking them (see `Material::chip`), storing the crack level in the high
+//! nibble of `Pixel::damage`. The chunk shader draws cracked pixels darker
+//! the further they are cracked, with dark crack lines spreading over them.
+//!
+//! Other passes reuse the damage byte for their own state, so the shader
+//! only reads cracks of the materials flagged in the [`CrackUniform`] mask.
+
+use bevy::prelude::*;
+use bevy::render::render_resource::ShaderType;
+
+use super::material::ChunkMaterial;
+use crate::pixel_world::coords::MaterialId;
+use crate::pixel_world::material::Materials;
+use crate::pixel_world::pixel::MAX_CRACK;
+use crate::pixel_world::world::PixelWorld;
+
+/// Configuration for drawing cracked pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CrackOverlayConfig {
+  /// Draw cracked pixels darker and with crack lines.
+  /// Default: true
+  pub enabled: bool,
+  /// How much darker a pixel at the highest crack level is drawn
+  /// (0.0 = not darkened, 1.0 = black). Crack lines are drawn darker still.
+  /// Default: 0.4
+  pub darkening: f32,
+}
+
+impl Default for CrackOverlayConfig {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      darkening: 0.4,
+    }
+  }
+}
+
+impl CrackOverlayConfig {
+  /// Returns the color multiplier the chunk shader applies to a pixel at
+  /// crack level `crack`, not counting crack lines.
+  ///
+  /// Mirrors `crack_factor` in `chunk.wgsl`.
+  pub fn darkening_factor(&self, crack: u8) -> f32 {
+    if !self.enabled {
+      return 1.0;
+    }
+    let level = crack.min(MAX_CRACK) as f32 / MAX_CRACK as f32;
+    1.0 - self.darkening.clamp(0.0, 1.0) * level
+  }
+
+  /// Returns the shader uniform for this config and the given materials.
+  pub fn uniform(&self, materials: &Materials) -> CrackUniform {
+    let mut uniform = CrackUniform::default();
+    if !self.enabled {
+      return uniform;
+    }
+    for id in 0..materials.len().min(256) {
+      if materials.get(MaterialId(id as u8)).cracks() {
+        uniform.materials[id / 128][id / 32 % 4] |= 1 << (id % 32);
+      }
+    }
+    uniform.darkening = self.darkening.clamp(0.0, 1.0);
+    uniform
+  }
+}
+
+/// Crack overlay parameters of a [`ChunkMaterial`].
+///
+/// The default draws no cracks.
+#[derive(ShaderType, Clone, Copy, Debug, Default, PartialEq)]
+pub struct CrackUniform {
+  /// Bit set of material IDs whose pixels keep cracks in `Pixel::damage`.
+  pub materials: [UVec4; 2],
+  /// Darkening at the highest crack level.
+  pub darkening: f32,
+}
+
+/// System: Sets each chunk material's crack overlay from its world's
+/// config and the material registry.
+///
+/// Materials are only touched when their overlay changes, since every
+/// mutation re-uploads the material.
+pub(crate) fn update_crack_overlay(
+  worlds: Query<&PixelWorld>,
+  registry: Option<Res<Materials>>,
+  materials: Option<ResMut<Assets<ChunkMaterial>>>,
+) {
+  let (Some(registry), Some(mut materials)) = (registry, materials) else {
+    return;
+  };
+
+  for world in worlds.iter() {
+    let cracks = world.config().crack_overlay.uniform(&registry);
+    for (_, idx) in world.active_chunks() {
+      let Some(handle) = world.slot(idx).material.as_ref() else {
+        continue;
+      };
+      if materials
+        .get(handle)
+        .is_some_and(|mat| mat.cracks != cracks)
+        && let Some(mat) = materials.get_mut(handle)
+      {
+        mat.cracks = cracks;
+      }
+    }
+  }
+}
@@ -0,0 +1,95 @@
+//! Liquid depth ramps drawn by the chunk shader.
+//!
+//! The depth pass records how far each liquid pixel sits below the surface
+//! in `Pixel::damage` (see `Pixel::depth`). The chunk shader draws pixels of
+//! materials with a depth ramp in the color of the deepest stop they reach,
+//! so no simulation pass rewrites their colors. Pixels marked as mixing keep
+//! their own color.
+//!
+//! Other passes reuse the damage byte for their own state, so the shader
+//! only reads depths of the materials flagged in the [`DepthRampUniform`]
+//! mask.
+
+use bevy::prelude::*;
+use bevy::render::render_resource::ShaderType;
+
+use super::material::ChunkMaterial;
+use crate::pixel_world::coords::MaterialId;
+use crate::pixel_world::material::{MAX_DEPTH_STOPS, Materials};
+use crate::pixel_world::world::PixelWorld;
+
+/// Depth ramp parameters of a [`ChunkMaterial`].
+///
+/// The default draws no ramps.
+#[derive(ShaderType, Clone, Copy, Debug, PartialEq)]
+pub struct DepthRampUniform {
+  /// Bit set of material IDs whose pixels keep their depth in
+  /// `Pixel::damage`.
+  pub materials: [UVec4; 2],
+  /// Ramp stops per material ID, sorted by depth: stop `i` is packed as
+  /// `depth << 8 | color` in the low or high half of component `i / 2`.
+  /// Unused slots repeat the last stop.
+  pub stops: [UVec4; 256],
+}
+
+impl Default for DepthRampUniform {
+  fn default() -> Self {
+    Self {
+      materials: [UVec4::ZERO; 2],
+      stops: [UVec4::ZERO; 256],
+    }
+  }
+}
+
+impl DepthRampUniform {
+  /// Returns the shader uniform for the depth ramps of `materials`.
+  pub fn new(materials: &Materials) -> Self {
+    let mut uniform = Self::default();
+    for id in 0..materials.len().min(256) {
+      let material = materials.get(MaterialId(id as u8));
+      if !material.has_depth_ramp() {
+        continue;
+      }
+      uniform.materials[id / 128][id / 32 % 4] |= 1 << (id % 32);
+
+      let ramp = &material.depth_ramp[..material.depth_ramp.len().min(MAX_DEPTH_STOPS)];
+      for slot in 0..MAX_DEPTH_STOPS {
+        let stop = ramp[slot.min(ramp.len() - 1)];
+        let packed = ((stop.depth as u32) << 8) | stop.color.0 as u32;
+        uniform.stops[id][slot / 2] |= packed << ((slot % 2) * 16);
+      }
+    }
+    uniform
+  }
+}
+
+/// System: Sets each chunk material's depth ramps from the material
+/// registry.
+///
+/// Materials are only touched when their ramps change, since every mutation
+/// re-uploads the material.
+pub(crate) fn update_depth_ramps(
+  worlds: Query<&PixelWorld>,
+  registry: Option<Res<Materials>>,
+  materials: Option<ResMut<Assets<ChunkMaterial>>>,
+) {
+  let (Some(registry), Some(mut materials)) = (registry, materials) else {
+    return;
+  };
+
+  let ramps = DepthRampUniform::new(&registry);
+  for world in worlds.iter() {
+    for (_, idx) in world.active_chunks() {
+      let Some(handle) = world.slot(idx).material.as_ref() else {
+        continue;
+      };
+      if materials
+        .get(handle)
+        .is_some_and(|mat| mat.depth_ramps != ramps)
+        && let Some(mat) = materials.get_mut(handle)
+      {
+        mat.depth_ramps = ramps;
+      }
+    }
+  }
+}
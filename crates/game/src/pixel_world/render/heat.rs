@@ -0,0 +1,138 @@
+//! Heat layer texture for shader effects.
+//!
+//! Each chunk can mirror its heat grid into a single-channel texture so
+//! shaders can draw glow or heat haze. The texture is opt-in: nothing is
+//! allocated or uploaded unless [`HeatTextureConfig::enabled`] is set. An
+//! `R8Unorm` texture is bound to the chunk material, whose shader draws a
+//! glow over hot pixels; an `R8Uint` texture is left to custom shaders.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::pixel_world::primitives::HEAT_GRID_SIZE;
+
+/// Texel format of the heat texture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeatTextureFormat {
+  /// `R8Unorm`: heat is sampled as a 0.0-1.0 float, with linear filtering.
+  /// Drives the chunk shader's heat glow.
+  #[default]
+  Unorm,
+  /// `R8Uint`: raw heat values, read with `textureLoad`. Not bound to the
+  /// chunk material; read the handle from
+  /// [`PixelWorld::heat_texture`](crate::pixel_world::PixelWorld::heat_texture).
+  Uint,
+}
+
+impl HeatTextureFormat {
+  fn texture_format(self) -> TextureFormat {
+    match self {
+      Self::Unorm => TextureFormat::R8Unorm,
+      Self::Uint => TextureFormat::R8Uint,
+    }
+  }
+}
+
+/// Configuration for the per-chunk heat texture.
+#[derive(Clone, Copy, Debug)]
+pub struct HeatTextureConfig {
+  /// Allocate and upload a heat texture for each chunk. Enable only when a
+  /// shader reads it. Only read when chunk entities are spawned.
+  /// Default: false
+  pub enabled: bool,
+  /// Heat cells per texel edge. 1 uploads the full heat grid, 2 halves its
+  /// resolution, and so on; each texel keeps the hottest cell it covers.
+  /// Default: 1
+  pub downsample: u32,
+  /// Texel format of the texture.
+  pub format: HeatTextureFormat,
+  /// Strength of the glow the chunk shader draws over hot pixels; 0.0
+  /// disables it. Only drawn with [`HeatTextureFormat::Unorm`].
+  /// Default: 0.5
+  pub glow: f32,
+}
+
+impl Default for HeatTextureConfig {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      downsample: 1,
+      format: HeatTextureFormat::default(),
+      glow: 0.5,
+    }
+  }
+}
+
+impl HeatTextureConfig {
+  /// Returns the texture edge length in texels.
+  pub fn resolution(&self) -> u32 {
+    HEAT_GRID_SIZE.div_ceil(self.downsample.max(1))
+  }
+
+  /// Returns the glow strength the chunk shader should use, or 0.0 when no
+  /// heat texture is bound to the chunk material.
+  pub(crate) fn shader_glow(&self) -> f32 {
+    if self.enabled && self.format == HeatTextureFormat::Unorm {
+      self.glow
+    } else {
+      0.0
+    }
+  }
+}
+
+/// Creates a heat texture sized and formatted per `config`.
+pub(crate) fn create_heat_texture(
+  images: &mut Assets<Image>,
+  config: &HeatTextureConfig,
+) -> Handle<Image> {
+  let resolution = config.resolution();
+  let size = Extent3d {
+    width: resolution,
+    height: resolution,
+    depth_or_array_layers: 1,
+  };
+
+  let mut image = Image::new_fill(
+    size,
+    TextureDimension::D2,
+    &[0],
+    config.format.texture_format(),
+    RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+  );
+
+  image.sampler = match config.format {
+    HeatTextureFormat::Unorm => ImageSampler::linear(),
+    HeatTextureFormat::Uint => ImageSampler::nearest(),
+  };
+
+  images.add(image)
+}
+
+/// Uploads a chunk heat grid to a heat texture created with the same
+/// `config`.
+pub(crate) fn upload_heat(heat: &[u8], config: &HeatTextureConfig, image: &mut Image) {
+  let Some(ref mut data) = image.data else {
+    return;
+  };
+
+  let factor = config.downsample.max(1);
+  if factor == 1 {
+    data.copy_from_slice(heat);
+    return;
+  }
+
+  let resolution = config.resolution();
+  for ty in 0..resolution {
+    for tx in 0..resolution {
+      let mut hottest = 0;
+      for hy in ty * factor..((ty + 1) * factor).min(HEAT_GRID_SIZE) {
+        for hx in tx * factor..((tx + 1) * factor).min(HEAT_GRID_SIZE) {
+          hottest = hottest.max(heat[(hy * HEAT_GRID_SIZE + hx) as usize]);
+        }
+      }
+      data[(ty * resolution + tx) as usize] = hottest;
+    }
+  }
+}
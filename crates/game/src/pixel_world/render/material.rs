@@ -5,6 +5,9 @@ use bevy::render::render_resource::AsBindGroup;
 use bevy::shader::ShaderRef;
 use bevy::sprite_render::{AlphaMode2d, Material2d};
 
+use super::cracks::CrackUniform;
+use super::depth_ramp::DepthRampUniform;
+
 /// Material for rendering chunks with GPU-side palette lookup.
 ///
 /// Uses raw pixel data (material/color indices) and a palette texture
@@ -19,6 +22,29 @@ pub struct ChunkMaterial {
   #[texture(1)]
   #[sampler(2)]
   pub palette_texture: Option<Handle<Image>>,
+
+  /// Color multiplier, lowered by fog of war for unexplored chunks.
+  #[uniform(3)]
+  pub brightness: f32,
+
+  /// Crack overlay for chipped pixels, disabled by default.
+  #[uniform(4)]
+  pub cracks: CrackUniform,
+
+  /// Per-chunk heat (R8Unorm), see
+  /// [`HeatTextureConfig`](super::HeatTextureConfig). Only sampled while
+  /// `heat_glow` is above zero.
+  #[texture(5)]
+  #[sampler(6)]
+  pub heat_texture: Option<Handle<Image>>,
+
+  /// Strength of the glow drawn over hot pixels; 0.0 disables it.
+  #[uniform(7)]
+  pub heat_glow: f32,
+
+  /// Liquid depth ramps, none drawn by default.
+  #[uniform(8)]
+  pub depth_ramps: DepthRampUniform,
 }
 
 impl Material2d for ChunkMaterial {
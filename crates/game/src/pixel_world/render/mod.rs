@@ -1,10 +1,22 @@
+mod bodies;
+mod cracks;
+mod depth_ramp;
+mod heat;
 mod material;
 mod pipeline;
 
+pub(crate) use bodies::sync_pixel_body_sprites;
+pub use bodies::{BodyRenderMode, PixelBodySprite, upload_pixels_without_bodies};
+pub(crate) use cracks::update_crack_overlay;
+pub use cracks::{CrackOverlayConfig, CrackUniform};
+pub use depth_ramp::DepthRampUniform;
+pub(crate) use depth_ramp::update_depth_ramps;
+pub use heat::{HeatTextureConfig, HeatTextureFormat};
+pub(crate) use heat::{create_heat_texture, upload_heat};
 pub use material::ChunkMaterial;
 pub use pipeline::{
   create_chunk_quad, create_palette_texture, create_pixel_texture, create_texture, materialize,
-  spawn_static_chunk, upload_palette, upload_pixels, upload_surface,
+  spawn_static_chunk, surface_to_image, upload_palette, upload_pixels, upload_surface,
 };
 
 /// RGBA pixel with 8 bits per channel, using sRGB color space.
@@ -10,6 +10,7 @@ use bevy::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 use bevy::sprite_render::MeshMaterial2d;
 
+use super::Rgba;
 use super::material::ChunkMaterial;
 use crate::pixel_world::palette::GlobalPalette;
 use crate::pixel_world::pixel::PixelSurface;
@@ -161,6 +162,11 @@ pub fn spawn_static_chunk(
   let material_handle = materials.add(ChunkMaterial {
     pixel_texture: Some(pixel_texture),
     palette_texture: Some(palette_texture),
+    brightness: 1.0,
+    cracks: Default::default(),
+    heat_texture: None,
+    heat_glow: 0.0,
+    depth_ramps: Default::default(),
   });
 
   // Spawn entity
@@ -179,3 +185,45 @@ pub fn materialize(pixels: &PixelSurface, palette: &GlobalPalette, output: &mut
     }
   }
 }
+
+/// Converts simulation pixels to an RGBA8 Bevy [`Image`].
+///
+/// Color indices are mapped through `palette` like [`materialize`]. Void
+/// pixels, and indices past the end of `palette`, become transparent. Rows
+/// are flipped so the image is top-down like any other Bevy image, making the
+/// result usable for sprites, UI icons and screenshots.
+pub fn surface_to_image(pixels: &PixelSurface, palette: &[Rgba]) -> Image {
+  let width = pixels.width();
+  let height = pixels.height();
+  let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+
+  // Surface row 0 is the bottom, image row 0 is the top
+  for y in (0..height).rev() {
+    for x in 0..width {
+      let pixel = pixels[(x, y)];
+      let rgba = if pixel.is_void() {
+        Rgba::new(0, 0, 0, 0)
+      } else {
+        palette
+          .get(pixel.color.0 as usize)
+          .copied()
+          .unwrap_or(Rgba::new(0, 0, 0, 0))
+      };
+      data.extend_from_slice(&[rgba.red, rgba.green, rgba.blue, rgba.alpha]);
+    }
+  }
+
+  let mut image = Image::new(
+    Extent3d {
+      width,
+      height,
+      depth_or_array_layers: 1,
+    },
+    TextureDimension::D2,
+    data,
+    TextureFormat::Rgba8UnormSrgb,
+    RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+  );
+  image.sampler = ImageSampler::nearest();
+  image
+}
@@ -5,6 +5,92 @@
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var pixel_texture: texture_2d<u32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var palette_texture: texture_2d<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var palette_sampler: sampler;
+// Color multiplier, < 1.0 for chunks dimmed by fog of war
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<uniform> brightness: f32;
+
+// Mirrors CrackUniform: materials whose damage high nibble holds cracks
+struct Cracks {
+    materials: array<vec4<u32>, 2>,
+    darkening: f32,
+}
+@group(#{MATERIAL_BIND_GROUP}) @binding(4) var<uniform> cracks: Cracks;
+
+// Per-chunk heat (R8Unorm), only sampled while heat_glow > 0
+@group(#{MATERIAL_BIND_GROUP}) @binding(5) var heat_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(6) var heat_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(7) var<uniform> heat_glow: f32;
+
+// Mirrors DepthRampUniform: materials whose damage holds liquid depth, and
+// up to 8 packed (depth << 8 | color) stops per material
+struct DepthRamps {
+    materials: array<vec4<u32>, 2>,
+    stops: array<vec4<u32>, 256>,
+}
+@group(#{MATERIAL_BIND_GROUP}) @binding(8) var<uniform> depth_ramps: DepthRamps;
+
+// PixelFlags::FOAM
+const FLAG_FOAM: u32 = 0x40u;
+// PixelFlags::PIXEL_BODY
+const FLAG_PIXEL_BODY: u32 = 0x20u;
+// MAX_DEPTH: the damage bits holding depth, the bit above marks mixing
+const DEPTH_MASK: u32 = 0x7fu;
+// MAX_DEPTH_STOPS
+const MAX_DEPTH_STOPS: u32 = 8u;
+// How far foamy liquid is lightened toward white
+const FOAM_LIGHTEN: f32 = 0.45;
+// Pixel::crack() range
+const MAX_CRACK: f32 = 15.0;
+// Extra darkening of pixels on a crack line
+const CRACK_LINE_SHADE: f32 = 0.5;
+// Color added over hot pixels, scaled by heat_glow
+const HEAT_GLOW_COLOR: vec3<f32> = vec3<f32>(1.0, 0.35, 0.05);
+// Heat below this fraction does not glow
+const HEAT_GLOW_START: f32 = 0.25;
+
+// Returns true if pixels of the material keep cracks
+fn cracks_material(material_id: u32) -> bool {
+    let word = cracks.materials[material_id / 128u][(material_id / 32u) % 4u];
+    return (word & (1u << (material_id % 32u))) != 0u;
+}
+
+// Crack overlay pattern: a fixed per-pixel threshold, so the crack lines
+// spread over a pixel's neighborhood as its crack level rises instead of
+// flickering. Lines run along a coarse diagonal lattice with hashed jitter.
+fn crack_pattern(coord: vec2<i32>) -> f32 {
+    let p = vec2<u32>(coord);
+    var h = (p.x * 0x27d4eb2du) ^ (p.y * 0x165667b1u);
+    h = (h ^ (h >> 15u)) * 0x2c1b3c6du;
+    h = h ^ (h >> 12u);
+    let jitter = f32(h & 0xffu) / 255.0;
+    let lattice = f32((coord.x + coord.y) & 3) / 3.0;
+    return mix(lattice, jitter, 0.6);
+}
+
+// Color index of a liquid pixel: the deepest depth ramp stop it reaches,
+// else its own. Mirrors Material::shaded_color.
+fn depth_ramp_color(material_id: u32, color_index: u32, damage: u32, flags: u32) -> u32 {
+    let word = depth_ramps.materials[material_id / 128u][(material_id / 32u) % 4u];
+    let mixing = (damage & ~DEPTH_MASK) != 0u;
+    if (word & (1u << (material_id % 32u))) == 0u || mixing || (flags & FLAG_PIXEL_BODY) != 0u {
+        return color_index;
+    }
+    let depth = damage & DEPTH_MASK;
+    let stops = depth_ramps.stops[material_id];
+    var color = color_index;
+    for (var i = 0u; i < MAX_DEPTH_STOPS; i++) {
+        let stop = (stops[i / 2u] >> ((i % 2u) * 16u)) & 0xffffu;
+        if (stop >> 8u) <= depth {
+            color = stop & 0xffu;
+        }
+    }
+    return color;
+}
+
+// Color multiplier for a pixel at crack level `crack` (0-15).
+// Mirrors CrackOverlayConfig::darkening_factor.
+fn crack_factor(crack: u32) -> f32 {
+    return 1.0 - cracks.darkening * f32(crack) / MAX_CRACK;
+}
 
 @fragment
 fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
@@ -19,11 +105,29 @@ fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
     // Palette layout: material_id * 8 + (color_index * 7 / 255)
     // Maps color_index 0-255 to palette entry 0-7 within the material's color range
     let material_id = pixel.r;
-    let color_index = pixel.g;
+    let color_index = depth_ramp_color(material_id, pixel.g, pixel.b, pixel.a);
     let palette_idx = material_id * 8u + (color_index * 7u / 255u);
     let palette_uv = vec2<f32>(f32(palette_idx) + 0.5, 0.5) / vec2<f32>(256.0, 1.0);
 
     let color = textureSample(palette_texture, palette_sampler, palette_uv);
 
-    return color;
+    var rgb = color.rgb;
+    if (pixel.a & FLAG_FOAM) != 0u {
+        rgb = mix(rgb, vec3<f32>(1.0), FOAM_LIGHTEN);
+    }
+
+    let crack = pixel.b >> 4u;
+    if crack != 0u && cracks_material(material_id) {
+        rgb *= crack_factor(crack);
+        if crack_pattern(coord) < f32(crack) / MAX_CRACK {
+            rgb *= CRACK_LINE_SHADE;
+        }
+    }
+
+    if heat_glow > 0.0 {
+        let heat = textureSample(heat_texture, heat_sampler, clamped_uv).r;
+        rgb += HEAT_GLOW_COLOR * smoothstep(HEAT_GLOW_START, 1.0, heat) * heat_glow;
+    }
+
+    return vec4<f32>(rgb * brightness, color.a);
 }
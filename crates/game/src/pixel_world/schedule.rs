@@ -1,9 +1,12 @@
 //! Shared schedule labels for pixel world systems.
 //!
 //! All pixel world systems run in [`Update`] within one of the three
-//! [`PixelWorldSet`] phases. External consumers can order their own systems
-//! relative to these sets.
+//! [`PixelWorldSet`] phases, except the per-tick systems in
+//! [`PixelSimulation`], which [`PixelWorldSet::Simulation`] runs once per
+//! simulation tick. External consumers can order their own systems relative
+//! to these sets.
 
+use bevy::ecs::schedule::ScheduleLabel;
 use bevy::prelude::*;
 
 /// System sets for the pixel world update loop.
@@ -24,19 +27,43 @@ use bevy::prelude::*;
 pub enum PixelWorldSet {
   /// Chunk streaming, body finalization, persistence message handling.
   PreSimulation,
-  /// Cellular automata tick, body blit/readback, splitting.
+  /// Runs the [`PixelSimulation`] schedule once per simulation tick.
   Simulation,
   /// Collision generation, body spawning, persistence flush.
   PostSimulation,
 }
 
-/// Sub-phases within [`PixelWorldSet::Simulation`].
+/// Schedule holding a single simulation tick.
+///
+/// Run by [`PixelWorldSet::Simulation`] as many times per frame as
+/// [`SimulationConfig::pacing`](crate::pixel_world::SimulationConfig::pacing)
+/// dictates, so body blit and readback stay aligned with CA ticks: every
+/// tick blits bodies exactly once, right before the CA runs, and reads them
+/// back right after.
+///
+/// ```ignore
+/// app.add_systems(PixelSimulation, my_system.in_set(SimulationPhase::AfterCATick));
+/// ```
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PixelSimulation;
+
+/// Sub-phases within the [`PixelSimulation`] schedule.
 ///
 /// These allow body systems to order themselves relative to the CA tick:
 ///
 /// ```text
 /// BeforeCATick → CATick → AfterCATick
 /// ```
+///
+/// `CATick` is further split into [`CATickStep`]s for systems that need to
+/// run between the CA passes.
+///
+/// The phases are also configured in [`Update`], inside
+/// [`PixelWorldSet::Simulation`], where `CATick` holds the system running
+/// the frame's ticks. Systems added there run once per frame, before or
+/// after all of the frame's ticks, as they did before the phases moved to
+/// [`PixelSimulation`]; add them to [`PixelSimulation`] instead to run once
+/// per tick, in step with the built-in body systems.
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SimulationPhase {
   /// Body blit and erasure detection, before CA runs.
@@ -46,3 +73,45 @@ pub enum SimulationPhase {
   /// Readback, shape changes, splitting, tile invalidation.
   AfterCATick,
 }
+
+/// Steps of the CA tick, chained within [`SimulationPhase::CATick`].
+///
+/// ```text
+/// Physics → AfterPhysics → Burning → AfterBurning → Heat
+/// ```
+///
+/// `Physics`, `Burning` and `Heat` hold the built-in passes; `AfterPhysics`
+/// and `AfterBurning` are empty slots for gameplay systems. All steps run
+/// once per simulation tick, only while the simulation is running and the
+/// world is ready. Frozen worlds and worlds with
+/// [`PixelWorld::simulate`](crate::pixel_world::PixelWorld::simulate) off are
+/// skipped by the built-in passes but not by slot systems. Systems in a slot
+/// observe:
+///
+/// - `AfterPhysics`: pixel swaps and wetting/drying of this tick applied,
+///   burning and heat not yet. Runs every tick, including ticks where the
+///   burning pass is skipped.
+/// - `AfterBurning`: fire spread and ash of this tick applied (on burning
+///   ticks), heat not yet.
+///
+/// [`PixelWorld::tick`](crate::pixel_world::PixelWorld::tick) has already
+/// advanced past the tick being simulated in every step. Pixel edits made in
+/// a slot are seen by the later passes of the same tick.
+///
+/// ```ignore
+/// app.add_systems(PixelSimulation, my_hook.in_set(CATickStep::AfterPhysics));
+/// ```
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CATickStep {
+  /// Pixel swaps and wetness.
+  Physics,
+  /// Slot for gameplay systems between physics and burning.
+  AfterPhysics,
+  /// Fire spread and ash transformation (every Nth tick).
+  Burning,
+  /// Slot for gameplay systems between burning and heat.
+  AfterBurning,
+  /// Heat diffusion, heat ignition, evaporation and context variants (every
+  /// Mth tick).
+  Heat,
+}
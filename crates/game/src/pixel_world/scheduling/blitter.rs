@@ -39,6 +39,13 @@
 //!
 //! - [`parallel_blit`] - Paint operations with custom pixel shaders
 //! - [`parallel_simulate`] - Cellular automata physics simulation
+//! - [`parallel_burning`] / [`parallel_wetness`] / [`parallel_foam`] /
+//!   [`parallel_ephemeral`] - Per-pixel state passes
+//! - [`parallel_evaporation`] - Whole-tile liquid evaporation
+//! - [`parallel_absorption`] - Whole-tile liquid absorption by sponges
+//! - [`parallel_variants`] / [`parallel_mixing`] - Whole-tile cosmetic
+//!   recoloring
+//! - [`parallel_depth`] - Whole-tile liquid depth for the chunk shader
 //!
 //! See `docs/architecture/scheduling.md` for detailed design rationale.
 
@@ -56,10 +63,19 @@ use crate::pixel_world::coords::{
   ChunkPos, LocalPos, Phase, TILE_SIZE, TilePos, WorldFragment, WorldPos, WorldRect,
 };
 use crate::pixel_world::debug_shim::{self, DebugGizmos};
+use crate::pixel_world::material::Materials;
 use crate::pixel_world::pixel::Pixel;
 use crate::pixel_world::primitives::Chunk;
+use crate::pixel_world::simulation::absorption::{self, AbsorptionContext};
 use crate::pixel_world::simulation::burning::{self, BurningContext};
+use crate::pixel_world::simulation::depth;
+use crate::pixel_world::simulation::ephemeral;
+use crate::pixel_world::simulation::evaporation::{self, EvaporationContext};
+use crate::pixel_world::simulation::foam::{self, FoamContext};
 use crate::pixel_world::simulation::hash::hash21uu64;
+use crate::pixel_world::simulation::mixing::{self, MixingContext};
+use crate::pixel_world::simulation::variants;
+use crate::pixel_world::simulation::wetness::{self, WetnessContext};
 
 /// Context for tile-based blit operations.
 ///
@@ -71,6 +87,7 @@ struct TileContext<'a> {
   h_recip: f32,
   dirty_chunks: &'a Mutex<HashSet<ChunkPos>>,
   dirty_tiles: Option<&'a Mutex<HashSet<TilePos>>>,
+  wake: bool,
 }
 
 /// Context for tile-based simulation operations.
@@ -119,12 +136,16 @@ impl<'a> DirtyCollector<'a> {
 
 /// Executes a blit operation across tiles in parallel using 2x2 checkerboard
 /// scheduling.
+///
+/// When `wake` is false, written pixels are not marked in the tile dirty
+/// rects, so the simulation does not pick them or their neighbors up.
 pub fn parallel_blit<F>(
   chunks: &Canvas<'_>,
   rect: WorldRect,
   f: F,
   dirty_chunks: &Mutex<HashSet<ChunkPos>>,
   dirty_tiles: Option<&Mutex<HashSet<TilePos>>>,
+  wake: bool,
 ) where
   F: Fn(WorldFragment) -> Option<Pixel> + Sync,
 {
@@ -155,6 +176,7 @@ pub fn parallel_blit<F>(
     h_recip,
     dirty_chunks,
     dirty_tiles,
+    wake,
   };
 
   // Execute each phase sequentially, tiles within phase in parallel
@@ -278,6 +300,291 @@ fn burn_tile(
   }
 }
 
+/// Executes wetting and drying across tiles in parallel using 2x2
+/// checkerboard scheduling.
+///
+/// For each pixel in dirty bounds, wets powders touching liquid and dries
+/// wet ones that aren't. Uses the same tile/phase infrastructure as burning.
+pub fn parallel_wetness(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  wet_ctx: &WetnessContext<'_>,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_wetness").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      wet_tile(chunks, tile, wet_ctx, dirty_chunks, jitter);
+    });
+  }
+}
+
+/// Process a single tile for wetting and drying.
+///
+/// Only processes pixels within the tile's dirty rect bounds.
+fn wet_tile(
+  chunks: &Canvas<'_>,
+  tile: TilePos,
+  wet_ctx: &WetnessContext<'_>,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  let Some(bounds) = union_dirty_bounds(chunks, tile, jitter) else {
+    return;
+  };
+
+  let mut collector = DirtyCollector::new(dirty_chunks);
+
+  wetness::process_tile_wetness(
+    chunks,
+    tile,
+    bounds,
+    jitter,
+    wet_ctx,
+    &mut collector.local_chunks,
+    &mut collector.pixels,
+  );
+
+  collector.flush(chunks);
+}
+
+/// Executes foam decay across tiles in parallel using 2x2 checkerboard
+/// scheduling.
+///
+/// For each pixel in dirty bounds, keeps foam on disturbed liquid bordering
+/// air and decays the rest. Uses the same tile/phase infrastructure as
+/// wetness.
+pub fn parallel_foam(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  foam_ctx: &FoamContext<'_>,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_foam").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      foam_tile(chunks, tile, foam_ctx, dirty_chunks, jitter);
+    });
+  }
+}
+
+/// Process a single tile for foam decay.
+///
+/// Only processes pixels within the tile's dirty rect bounds.
+fn foam_tile(
+  chunks: &Canvas<'_>,
+  tile: TilePos,
+  foam_ctx: &FoamContext<'_>,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  let Some(bounds) = union_dirty_bounds(chunks, tile, jitter) else {
+    return;
+  };
+
+  let mut collector = DirtyCollector::new(dirty_chunks);
+
+  foam::process_tile_foam(
+    chunks,
+    tile,
+    bounds,
+    jitter,
+    foam_ctx,
+    &mut collector.local_chunks,
+    &mut collector.pixels,
+  );
+
+  collector.flush(chunks);
+}
+
+/// Executes ephemeral pixel aging across tiles in parallel using 2x2
+/// checkerboard scheduling.
+///
+/// For each pixel in dirty bounds, ages ephemeral pixels and removes the
+/// expired ones. Uses the same tile/phase infrastructure as foam.
+pub fn parallel_ephemeral(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  materials: &Materials,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_ephemeral").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      let Some(bounds) = union_dirty_bounds(chunks, tile, jitter) else {
+        return;
+      };
+
+      let mut collector = DirtyCollector::new(dirty_chunks);
+
+      ephemeral::process_tile_ephemeral(
+        chunks,
+        tile,
+        bounds,
+        jitter,
+        materials,
+        &mut collector.local_chunks,
+        &mut collector.pixels,
+      );
+
+      collector.flush(chunks);
+    });
+  }
+}
+
+/// Executes evaporation across tiles in parallel using 2x2 checkerboard
+/// scheduling.
+///
+/// Unlike the other per-pixel passes, scans whole tiles: calm liquid sleeps
+/// outside dirty rects but can still evaporate.
+pub fn parallel_evaporation(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  evap_ctx: &EvaporationContext<'_>,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_evaporation").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      let mut collector = DirtyCollector::new(dirty_chunks);
+
+      evaporation::process_tile_evaporation(
+        chunks,
+        tile,
+        jitter,
+        evap_ctx,
+        &mut collector.local_chunks,
+        &mut collector.pixels,
+      );
+
+      collector.flush(chunks);
+    });
+  }
+}
+
+/// Executes absorption across tiles in parallel using 2x2 checkerboard
+/// scheduling.
+///
+/// Scans whole tiles like evaporation. Absorbent pixels soak up or release
+/// one neighboring liquid pixel each.
+pub fn parallel_absorption(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  abs_ctx: &AbsorptionContext<'_>,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_absorption").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      let mut collector = DirtyCollector::new(dirty_chunks);
+
+      absorption::process_tile_absorption(
+        chunks,
+        tile,
+        jitter,
+        abs_ctx,
+        &mut collector.local_chunks,
+        &mut collector.pixels,
+      );
+
+      collector.flush(chunks);
+    });
+  }
+}
+
+/// Executes context variants across tiles in parallel using 2x2
+/// checkerboard scheduling.
+///
+/// Scans whole tiles like evaporation. Only recolors pixels, so no pixels
+/// are marked dirty for the next pass.
+pub fn parallel_variants(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  materials: &Materials,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_variants").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      let mut collector = DirtyCollector::new(dirty_chunks);
+
+      variants::process_tile_variants(chunks, tile, jitter, materials, &mut collector.local_chunks);
+
+      collector.flush(chunks);
+    });
+  }
+}
+
+/// Executes liquid depth tracking across tiles in parallel using 2x2
+/// checkerboard scheduling.
+///
+/// Scans whole tiles top row first like variants. Only records depths, so
+/// no pixels are marked dirty for the next pass.
+pub fn parallel_depth(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  materials: &Materials,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_depth").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      let mut collector = DirtyCollector::new(dirty_chunks);
+
+      depth::process_tile_depth(chunks, tile, jitter, materials, &mut collector.local_chunks);
+
+      collector.flush(chunks);
+    });
+  }
+}
+
+/// Executes liquid color mixing across tiles in parallel using 2x2
+/// checkerboard scheduling.
+///
+/// Scans whole tiles like depth shading. Only recolors pixels, so no pixels
+/// are marked dirty for the next pass.
+pub fn parallel_mixing(
+  chunks: &Canvas<'_>,
+  tiles_by_phase: [Vec<TilePos>; 4],
+  mix_ctx: &MixingContext<'_>,
+  dirty_chunks: &Mutex<HashSet<ChunkPos>>,
+  jitter: (i64, i64),
+) {
+  #[cfg(feature = "tracy")]
+  let _span = tracing::info_span!("parallel_mixing").entered();
+
+  for phase_tiles in &tiles_by_phase {
+    phase_tiles.par_iter().for_each(|&tile| {
+      let mut collector = DirtyCollector::new(dirty_chunks);
+
+      mixing::process_tile_mixing(chunks, tile, jitter, mix_ctx, &mut collector.local_chunks);
+
+      collector.flush(chunks);
+    });
+  }
+}
+
 /// Iterates over pixel positions within dirty bounds with row-alternating
 /// direction.
 ///
@@ -535,5 +842,8 @@ where
     tiles.insert(tile);
   }
 
+  if !ctx.wake {
+    collector.pixels.clear();
+  }
   collector.flush(chunks);
 }
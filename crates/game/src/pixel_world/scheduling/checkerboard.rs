@@ -4,6 +4,8 @@
 //! based on their position modulo 2. Tiles in the same phase are never
 //! adjacent, enabling safe parallel processing.
 
+use std::collections::HashSet;
+
 use super::canvas::Canvas;
 use crate::pixel_world::coords::{LocalPos, TILE_SIZE, TILES_PER_CHUNK, TilePos, WorldPos};
 use crate::pixel_world::primitives::TileBounds;
@@ -214,3 +216,58 @@ pub(super) fn tick_owned_tile(chunks: &Canvas<'_>, tile: TilePos) {
     chunk.tile_dirty_rect_mut(tx, ty).tick();
   }
 }
+
+/// Two tiles in the same phase bucket that could race each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhaseConflict {
+  /// Index of the offending phase bucket.
+  pub phase: usize,
+  pub a: TilePos,
+  pub b: TilePos,
+}
+
+impl std::fmt::Display for PhaseConflict {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "tiles {:?} and {:?} share phase {} but are not independent",
+      self.a, self.b, self.phase
+    )
+  }
+}
+
+/// Checks the invariant that makes parallel tile passes safe.
+///
+/// Jittered tiles reach into their right and top neighbors and pixels move
+/// across tile edges, so two tiles of the same phase may only run together
+/// if they are not 8-neighbors. A tile listed twice in one bucket counts as
+/// a conflict with itself.
+pub fn validate_phase_partition(tiles_by_phase: &[Vec<TilePos>; 4]) -> Result<(), PhaseConflict> {
+  for (phase, tiles) in tiles_by_phase.iter().enumerate() {
+    let mut seen = HashSet::with_capacity(tiles.len());
+    for &tile in tiles {
+      if !seen.insert(tile) {
+        return Err(PhaseConflict {
+          phase,
+          a: tile,
+          b: tile,
+        });
+      }
+    }
+    for &tile in tiles {
+      for dy in -1..=1 {
+        for dx in -1..=1 {
+          let neighbor = TilePos::new(tile.x + dx, tile.y + dy);
+          if (dx, dy) != (0, 0) && seen.contains(&neighbor) {
+            return Err(PhaseConflict {
+              phase,
+              a: tile,
+              b: neighbor,
+            });
+          }
+        }
+      }
+    }
+  }
+  Ok(())
+}
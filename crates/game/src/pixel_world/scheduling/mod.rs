@@ -3,3 +3,5 @@
 pub mod blitter;
 mod canvas;
 mod checkerboard;
+
+pub use checkerboard::{PhaseConflict, validate_phase_partition};
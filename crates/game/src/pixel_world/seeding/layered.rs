@@ -0,0 +1,36 @@
+//! Layered chunk seeding.
+
+use super::ChunkSeeder;
+use crate::pixel_world::{Chunk, ChunkPos};
+
+/// Runs a base seeder followed by layers that refine its output.
+///
+/// Layers see the chunk as left by the previous seeder, so they can place
+/// features (ores, structures) into existing terrain.
+#[derive(bevy::prelude::Resource)]
+pub struct LayeredSeeder {
+  layers: Vec<Box<dyn ChunkSeeder>>,
+}
+
+impl LayeredSeeder {
+  /// Creates a layered seeder starting from the given base seeder.
+  pub fn new(base: impl ChunkSeeder + 'static) -> Self {
+    Self {
+      layers: vec![Box::new(base)],
+    }
+  }
+
+  /// Adds a layer applied after all previous layers.
+  pub fn with_layer(mut self, layer: impl ChunkSeeder + 'static) -> Self {
+    self.layers.push(Box::new(layer));
+    self
+  }
+}
+
+impl ChunkSeeder for LayeredSeeder {
+  fn seed(&self, pos: ChunkPos, chunk: &mut Chunk) {
+    for layer in &self.layers {
+      layer.seed(pos, chunk);
+    }
+  }
+}
@@ -5,10 +5,16 @@
 //!
 //! See `docs/architecture/chunk-seeding.md` for the seeder trait design.
 
+mod layered;
 mod noise;
+mod ore;
 pub(crate) mod sdf;
+mod structure;
 
+pub use layered::LayeredSeeder;
 pub use noise::{MaterialSeeder, NoiseSeeder, presets};
+pub use ore::OreSeeder;
+pub use structure::{RegionClip, StructurePlacer};
 
 use crate::pixel_world::persistence::LoadedChunk;
 use crate::pixel_world::{Chunk, ChunkPos};
@@ -16,8 +22,10 @@ use crate::pixel_world::{Chunk, ChunkPos};
 /// Trait for populating chunk buffers with initial data.
 ///
 /// Implementations generate procedural content ([`NoiseSeeder`],
-/// [`MaterialSeeder`]). Persistence loading is handled separately by the
-/// streaming system (`dispatch_chunk_loads` and `seed_chunk_with_loaded`).
+/// [`MaterialSeeder`]) or refine it as [`LayeredSeeder`] layers
+/// ([`OreSeeder`], [`StructurePlacer`]). Persistence loading is handled
+/// separately by the streaming system (`dispatch_chunk_loads` and
+/// `seed_chunk_with_loaded`).
 ///
 /// The `Send + Sync` bounds enable async seeding on background threads.
 pub trait ChunkSeeder: Send + Sync {
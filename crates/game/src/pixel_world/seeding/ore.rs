@@ -0,0 +1,196 @@
+//! Ore vein placement.
+//!
+//! Veins are generated per fixed-size region from a hash of the region
+//! coordinates, independent of which chunks are loaded. Each chunk rasterizes
+//! every vein that can reach it, so veins stay continuous across chunk
+//! boundaries.
+
+use super::ChunkSeeder;
+use crate::pixel_world::coords::CHUNK_SIZE;
+use crate::pixel_world::material::ids as material_ids;
+use crate::pixel_world::pixel::Pixel;
+use crate::pixel_world::simulation::hash::{hash21uu64, hash41uu64};
+use crate::pixel_world::{Chunk, ChunkPos, MaterialId};
+
+/// Side length of a vein generation region in pixels.
+const REGION_SIZE: i64 = 256;
+
+/// Number of segments per vein; each bends slightly from the previous one.
+const SEGMENTS: usize = 4;
+
+/// Maximum direction change between consecutive segments, in radians.
+const MAX_BEND: f32 = 0.6;
+
+/// Seeder layer that places elongated ore veins into a host material.
+///
+/// Intended as a [`LayeredSeeder`](super::LayeredSeeder) layer: only pixels of
+/// the host material (stone by default) are replaced, keeping their color.
+#[derive(bevy::prelude::Resource)]
+pub struct OreSeeder {
+  ore: MaterialId,
+  host: MaterialId,
+  seed: i32,
+  rarity: f32,
+  length: u32,
+  thickness: u32,
+}
+
+/// A single straight piece of a vein, in world coordinates.
+struct Segment {
+  ax: f32,
+  ay: f32,
+  bx: f32,
+  by: f32,
+}
+
+impl OreSeeder {
+  const DEFAULT_RARITY: f32 = 0.02;
+  const DEFAULT_LENGTH: u32 = 48;
+  const DEFAULT_THICKNESS: u32 = 4;
+
+  /// Creates an ore seeder placing `ore` into stone.
+  pub fn new(ore: MaterialId, seed: i32) -> Self {
+    Self {
+      ore,
+      host: material_ids::STONE,
+      seed,
+      rarity: Self::DEFAULT_RARITY,
+      length: Self::DEFAULT_LENGTH,
+      thickness: Self::DEFAULT_THICKNESS,
+    }
+  }
+
+  /// Sets the material veins are placed into.
+  pub fn host(mut self, host: MaterialId) -> Self {
+    self.host = host;
+    self
+  }
+
+  /// Sets the expected fraction of host pixels converted to ore (0.0-1.0).
+  pub fn rarity(mut self, rarity: f32) -> Self {
+    self.rarity = rarity.clamp(0.0, 1.0);
+    self
+  }
+
+  /// Sets the vein length in pixels.
+  pub fn length(mut self, length: u32) -> Self {
+    self.length = length.max(1);
+    self
+  }
+
+  /// Sets the vein thickness in pixels.
+  pub fn thickness(mut self, thickness: u32) -> Self {
+    self.thickness = thickness.max(1);
+    self
+  }
+
+  fn radius(&self) -> f32 {
+    self.thickness as f32 * 0.5
+  }
+
+  /// Expected number of veins per region, derived from rarity and vein area.
+  fn veins_per_region(&self) -> f32 {
+    let r = self.radius();
+    let vein_area = self.length as f32 * self.thickness as f32 + std::f32::consts::PI * r * r;
+    self.rarity * (REGION_SIZE * REGION_SIZE) as f32 / vein_area
+  }
+
+  /// Generates the segments of every vein starting in the given region.
+  fn region_veins(&self, rx: i64, ry: i64, out: &mut Vec<Segment>) {
+    let region_hash = hash41uu64(self.seed as u64, rx as u64, ry as u64, 0);
+    let expected = self.veins_per_region();
+    let mut count = expected as u32;
+    if unit(hash21uu64(region_hash, 0)) < expected.fract() {
+      count += 1;
+    }
+
+    let segment_len = self.length as f32 / SEGMENTS as f32;
+    for vein in 0..count {
+      let h = hash41uu64(self.seed as u64, rx as u64, ry as u64, vein as u64 + 1);
+      let mut x = (rx * REGION_SIZE) as f32 + unit(hash21uu64(h, 1)) * REGION_SIZE as f32;
+      let mut y = (ry * REGION_SIZE) as f32 + unit(hash21uu64(h, 2)) * REGION_SIZE as f32;
+      let mut angle = unit(hash21uu64(h, 3)) * std::f32::consts::TAU;
+
+      for s in 0..SEGMENTS {
+        let bend = (unit(hash21uu64(h, 4 + s as u64)) * 2.0 - 1.0) * MAX_BEND;
+        angle += bend;
+        let (nx, ny) = (x + angle.cos() * segment_len, y + angle.sin() * segment_len);
+        out.push(Segment {
+          ax: x,
+          ay: y,
+          bx: nx,
+          by: ny,
+        });
+        x = nx;
+        y = ny;
+      }
+    }
+  }
+
+  fn rasterize(&self, segment: &Segment, base_x: i64, base_y: i64, chunk: &mut Chunk) {
+    let r = self.radius();
+    let size = CHUNK_SIZE as i64;
+    let min_x = ((segment.ax.min(segment.bx) - r).floor() as i64 - base_x).max(0);
+    let max_x = ((segment.ax.max(segment.bx) + r).ceil() as i64 - base_x).min(size - 1);
+    let min_y = ((segment.ay.min(segment.by) - r).floor() as i64 - base_y).max(0);
+    let max_y = ((segment.ay.max(segment.by) + r).ceil() as i64 - base_y).min(size - 1);
+
+    for ly in min_y..=max_y {
+      for lx in min_x..=max_x {
+        let px = (base_x + lx) as f32 + 0.5;
+        let py = (base_y + ly) as f32 + 0.5;
+        if distance_sq(segment, px, py) > r * r {
+          continue;
+        }
+        let pixel = &mut chunk.pixels[(lx as u32, ly as u32)];
+        if pixel.material == self.host {
+          *pixel = Pixel::new(self.ore, pixel.color);
+        }
+      }
+    }
+  }
+}
+
+impl ChunkSeeder for OreSeeder {
+  fn seed(&self, pos: ChunkPos, chunk: &mut Chunk) {
+    let size = CHUNK_SIZE as i64;
+    let base_x = pos.x as i64 * size;
+    let base_y = pos.y as i64 * size;
+
+    // Veins extend at most `length + radius` from their start
+    let reach = self.length as i64 + self.thickness as i64;
+    let rx0 = (base_x - reach).div_euclid(REGION_SIZE);
+    let rx1 = (base_x + size + reach).div_euclid(REGION_SIZE);
+    let ry0 = (base_y - reach).div_euclid(REGION_SIZE);
+    let ry1 = (base_y + size + reach).div_euclid(REGION_SIZE);
+
+    let mut segments = Vec::new();
+    for ry in ry0..=ry1 {
+      for rx in rx0..=rx1 {
+        self.region_veins(rx, ry, &mut segments);
+      }
+    }
+
+    for segment in &segments {
+      self.rasterize(segment, base_x, base_y, chunk);
+    }
+  }
+}
+
+/// Maps a hash to a float in `[0, 1)`.
+fn unit(h: u64) -> f32 {
+  (h >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Squared distance from a point to a segment.
+fn distance_sq(s: &Segment, px: f32, py: f32) -> f32 {
+  let (dx, dy) = (s.bx - s.ax, s.by - s.ay);
+  let len_sq = dx * dx + dy * dy;
+  let t = if len_sq > 0.0 {
+    (((px - s.ax) * dx + (py - s.ay) * dy) / len_sq).clamp(0.0, 1.0)
+  } else {
+    0.0
+  };
+  let (cx, cy) = (s.ax + dx * t - px, s.ay + dy * t - py);
+  cx * cx + cy * cy
+}
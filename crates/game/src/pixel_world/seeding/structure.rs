@@ -0,0 +1,189 @@
+//! Structure placement.
+//!
+//! Structures are placed per fixed-size region from a hash of the region
+//! coordinates, independent of which chunks are loaded. Each chunk stamps the
+//! part of every structure overlapping it, so structures spanning several
+//! chunks come out identical whichever chunk is seeded first.
+
+use super::ChunkSeeder;
+use crate::pixel_world::coords::CHUNK_SIZE;
+use crate::pixel_world::pixel::Pixel;
+use crate::pixel_world::simulation::hash::{hash21uu64, hash41uu64};
+use crate::pixel_world::{Chunk, ChunkPos};
+
+/// A rectangular prefab of pixels stamped into terrain.
+///
+/// Cells left empty are transparent and keep the terrain underneath.
+/// Coordinates are local to the clip, with (0, 0) at the bottom-left.
+#[derive(Clone, Debug)]
+pub struct RegionClip {
+  width: u32,
+  height: u32,
+  pixels: Vec<Option<Pixel>>,
+}
+
+impl RegionClip {
+  /// Creates a fully transparent clip.
+  pub fn new(width: u32, height: u32) -> Self {
+    Self {
+      width,
+      height,
+      pixels: vec![None; (width * height) as usize],
+    }
+  }
+
+  /// Creates a clip filled with a single pixel.
+  pub fn filled(width: u32, height: u32, pixel: Pixel) -> Self {
+    Self {
+      width,
+      height,
+      pixels: vec![Some(pixel); (width * height) as usize],
+    }
+  }
+
+  /// Returns the clip width in pixels.
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  /// Returns the clip height in pixels.
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  /// Returns the pixel at local coordinates, or `None` if transparent or out
+  /// of bounds.
+  pub fn get(&self, x: u32, y: u32) -> Option<Pixel> {
+    if x >= self.width || y >= self.height {
+      return None;
+    }
+    self.pixels[(y * self.width + x) as usize]
+  }
+
+  /// Sets the pixel at local coordinates; `None` makes it transparent.
+  ///
+  /// Out-of-bounds coordinates are ignored.
+  pub fn set(&mut self, x: u32, y: u32, pixel: Option<Pixel>) {
+    if x < self.width && y < self.height {
+      self.pixels[(y * self.width + x) as usize] = pixel;
+    }
+  }
+}
+
+/// A structure placed in the world: which clip and where its bottom-left
+/// corner lands, in world coordinates.
+struct Placement {
+  clip: usize,
+  x: i64,
+  y: i64,
+}
+
+/// Seeder layer that stamps prefab structures into existing terrain.
+///
+/// Intended as a [`LayeredSeeder`](super::LayeredSeeder) layer. Each region
+/// holds at most one structure, picked from the configured clips and placed
+/// inside the region; it may extend into neighboring regions and chunks.
+#[derive(bevy::prelude::Resource)]
+pub struct StructurePlacer {
+  clips: Vec<RegionClip>,
+  seed: i32,
+  chance: f32,
+  region_size: u32,
+}
+
+impl StructurePlacer {
+  const DEFAULT_CHANCE: f32 = 0.25;
+  const DEFAULT_REGION_SIZE: u32 = 512;
+
+  /// Creates a structure placer choosing among `clips`.
+  pub fn new(clips: Vec<RegionClip>, seed: i32) -> Self {
+    Self {
+      clips,
+      seed,
+      chance: Self::DEFAULT_CHANCE,
+      region_size: Self::DEFAULT_REGION_SIZE,
+    }
+  }
+
+  /// Sets the probability that a region contains a structure (0.0-1.0).
+  pub fn chance(mut self, chance: f32) -> Self {
+    self.chance = chance.clamp(0.0, 1.0);
+    self
+  }
+
+  /// Sets the side length of a placement region in pixels.
+  pub fn region_size(mut self, region_size: u32) -> Self {
+    self.region_size = region_size.max(1);
+    self
+  }
+
+  /// Decides the structure placed in the given region, if any.
+  fn region_placement(&self, rx: i64, ry: i64) -> Option<Placement> {
+    if self.clips.is_empty() {
+      return None;
+    }
+    let h = hash41uu64(self.seed as u64, rx as u64, ry as u64, 0);
+    if unit(hash21uu64(h, 0)) >= self.chance {
+      return None;
+    }
+
+    let size = self.region_size as i64;
+    let clip = (hash21uu64(h, 1) % self.clips.len() as u64) as usize;
+    Some(Placement {
+      clip,
+      x: rx * size + (hash21uu64(h, 2) % size as u64) as i64,
+      y: ry * size + (hash21uu64(h, 3) % size as u64) as i64,
+    })
+  }
+
+  /// Stamps the part of a placed structure overlapping the chunk.
+  fn stamp(&self, placement: &Placement, base_x: i64, base_y: i64, chunk: &mut Chunk) {
+    let clip = &self.clips[placement.clip];
+    let size = CHUNK_SIZE as i64;
+    let min_x = (placement.x - base_x).max(0);
+    let max_x = (placement.x + clip.width as i64 - base_x).min(size);
+    let min_y = (placement.y - base_y).max(0);
+    let max_y = (placement.y + clip.height as i64 - base_y).min(size);
+
+    for ly in min_y..max_y {
+      for lx in min_x..max_x {
+        let cx = (base_x + lx - placement.x) as u32;
+        let cy = (base_y + ly - placement.y) as u32;
+        if let Some(pixel) = clip.get(cx, cy) {
+          chunk.pixels[(lx as u32, ly as u32)] = pixel;
+        }
+      }
+    }
+  }
+}
+
+impl ChunkSeeder for StructurePlacer {
+  fn seed(&self, pos: ChunkPos, chunk: &mut Chunk) {
+    let size = CHUNK_SIZE as i64;
+    let base_x = pos.x as i64 * size;
+    let base_y = pos.y as i64 * size;
+    let region = self.region_size as i64;
+
+    // Structures start inside their region and extend up/right by their size
+    let reach_x = self.clips.iter().map(|c| c.width).max().unwrap_or(0) as i64;
+    let reach_y = self.clips.iter().map(|c| c.height).max().unwrap_or(0) as i64;
+    let rx0 = (base_x - reach_x).div_euclid(region);
+    let rx1 = (base_x + size - 1).div_euclid(region);
+    let ry0 = (base_y - reach_y).div_euclid(region);
+    let ry1 = (base_y + size - 1).div_euclid(region);
+
+    // Fixed region order keeps overlapping structures consistent per chunk
+    for ry in ry0..=ry1 {
+      for rx in rx0..=rx1 {
+        if let Some(placement) = self.region_placement(rx, ry) {
+          self.stamp(&placement, base_x, base_y, chunk);
+        }
+      }
+    }
+  }
+}
+
+/// Maps a hash to a float in `[0, 1)`.
+fn unit(h: u64) -> f32 {
+  (h >> 40) as f32 / (1u64 << 24) as f32
+}
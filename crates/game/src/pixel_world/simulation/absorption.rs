@@ -0,0 +1,233 @@
+//! Absorbent materials soaking up and releasing liquid.
+//!
+//! Pixels of a material with an [`absorbent`] config soak up touching
+//! pixels of their liquid, one at a time, until they hold `capacity` of
+//! them, and wick it on to neighboring pixels of the same material. The
+//! stored amount is the pixel's saturation, kept in the low nibble of
+//! `Pixel::damage`. Once no liquid touches a soaked pixel with air below it,
+//! it drains: it drips its liquid into the air below, or presses it out
+//! below or beside itself one pixel per pass when a pixel body squeezes it,
+//! and soaks up nothing until it is empty. Every absorbed pixel is removed
+//! from the world and every released one written back, so liquid is
+//! conserved.
+//!
+//! Runs with the heat pass. Soaked pixels rest outside dirty rects, so
+//! whole tiles are scanned; the pass is skipped when no material is
+//! absorbent.
+//!
+//! [`absorbent`]: crate::pixel_world::material::Material::absorbent
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, ColorIndex, LocalPos, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::{Absorbent, Materials};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::scheduling::blitter::Canvas;
+use crate::pixel_world::simulation::SimContext;
+use crate::pixel_world::simulation::hash::hash41uu64;
+
+/// Cardinal neighbor offsets, the cell above first.
+const CARDINAL: [(i64, i64); 4] = [(0, 1), (-1, 0), (1, 0), (0, -1)];
+/// Cells liquid drips into on its own.
+const DRIP_ORDER: [(i64, i64); 1] = [(0, -1)];
+/// Cells liquid is pressed into by a pixel body. Never up, where it would
+/// fall straight back.
+const SQUEEZE_ORDER: [(i64, i64); 3] = [(0, -1), (-1, 0), (1, 0)];
+/// Bit of `Pixel::damage`, above the saturation nibble, set while a pixel
+/// drains its liquid.
+const DRAINING: u8 = 0x10;
+
+/// Context for absorption within a tile.
+pub struct AbsorptionContext<'a> {
+  pub materials: &'a Materials,
+  pub ctx: SimContext,
+  /// Rate of the heat pass, used to turn per-second rates into per-tick
+  /// chances.
+  pub heat_tps: f32,
+}
+
+/// Reads the pixel at `pos`, if its chunk is loaded.
+fn pixel_at(canvas: &Canvas<'_>, pos: WorldPos) -> Option<Pixel> {
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  canvas
+    .get(chunk_pos)
+    .map(|chunk| chunk.pixels[(local.x as u32, local.y as u32)])
+}
+
+/// Returns the first neighbor of `pos` in `order` matching `f`.
+fn find_neighbor(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  order: &[(i64, i64)],
+  mut f: impl FnMut(Pixel) -> bool,
+) -> Option<WorldPos> {
+  order
+    .iter()
+    .map(|&(dx, dy)| WorldPos::new(pos.x + dx, pos.y + dy))
+    .find(|&neighbor| pixel_at(canvas, neighbor).is_some_and(&mut f))
+}
+
+/// Rolls a per-second `rate` for the pixel at `pos` this pass.
+fn roll(abs_ctx: &AbsorptionContext<'_>, channel: u64, pos: WorldPos, rate: f32) -> bool {
+  let chance = (rate / abs_ctx.heat_tps).min(1.0);
+  let hash = hash41uu64(
+    abs_ctx.ctx.seed ^ channel,
+    abs_ctx.ctx.tick,
+    pos.x as u64,
+    pos.y as u64,
+  );
+  ((hash & 0xFFFF) as f32 / 65535.0) < chance
+}
+
+/// Writes `pixel` at `pos` and marks it for the next physics pass.
+fn write_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  pixel: Pixel,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  if let Some(chunk) = canvas.get_mut(chunk_pos) {
+    chunk.pixels[(local.x as u32, local.y as u32)] = pixel;
+  }
+  dirty_pixels.push((chunk_pos, local));
+  dirty_chunks.insert(chunk_pos);
+}
+
+/// Soaks up, wicks or releases one liquid pixel for the absorbent pixel at
+/// `pos`.
+fn process_absorbent_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  abs_ctx: &AbsorptionContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  const CH_ABSORB: u64 = 0x5eed_ab50_2b00_0001;
+  const CH_RELEASE: u64 = 0x5eed_ab50_2b00_0002;
+
+  let Some(mut pixel) = pixel_at(canvas, pos) else {
+    return;
+  };
+  if pixel.is_void() || pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+    return;
+  }
+  let material = abs_ctx.materials.get(pixel.material);
+  let Some(Absorbent {
+    liquid,
+    capacity,
+    absorb_rate,
+    release_rate,
+  }) = material.absorbent
+  else {
+    return;
+  };
+  if material.lifetime > 0 {
+    return;
+  }
+
+  let saturation = pixel.saturation();
+  let draining = pixel.damage & DRAINING != 0;
+
+  let source = find_neighbor(canvas, pos, &CARDINAL, |n| {
+    n.material == liquid && !n.flags.contains(PixelFlags::PIXEL_BODY)
+  });
+  if let Some(source) = source
+    && !draining
+    && saturation < capacity
+  {
+    if roll(abs_ctx, CH_ABSORB, pos, absorb_rate) {
+      write_pixel(canvas, source, Pixel::VOID, dirty_chunks, dirty_pixels);
+      pixel.set_saturation(saturation + 1);
+      write_pixel(canvas, pos, pixel, dirty_chunks, dirty_pixels);
+    }
+    return;
+  }
+
+  // Wick liquid through the material: down freely, sideways and up only
+  // against a difference of two, so it settles instead of sloshing
+  let donor = (saturation < capacity)
+    .then(|| {
+      CARDINAL.iter().find_map(|&(dx, dy)| {
+        let neighbor = WorldPos::new(pos.x + dx, pos.y + dy);
+        let other = pixel_at(canvas, neighbor)?;
+        let gap = if dy > 0 { 1 } else { 2 };
+        (other.material == pixel.material
+          && !other.flags.contains(PixelFlags::PIXEL_BODY)
+          && other.saturation() >= saturation + gap)
+          .then_some((neighbor, other))
+      })
+    })
+    .flatten();
+  if let Some((neighbor, mut other)) = donor {
+    if roll(abs_ctx, CH_ABSORB, pos, absorb_rate) {
+      other.set_saturation(other.saturation() - 1);
+      write_pixel(canvas, neighbor, other, dirty_chunks, dirty_pixels);
+      pixel.set_saturation(saturation + 1);
+      write_pixel(canvas, pos, pixel, dirty_chunks, dirty_pixels);
+    }
+    return;
+  }
+
+  // Soaked pixels hold their liquid while they are in it
+  if saturation == 0 || (source.is_some() && !draining) {
+    if draining {
+      pixel.damage &= !DRAINING;
+      write_pixel(canvas, pos, pixel, dirty_chunks, dirty_pixels);
+    }
+    return;
+  }
+
+  let squeezed = find_neighbor(canvas, pos, &CARDINAL, |n| {
+    n.flags.contains(PixelFlags::PIXEL_BODY)
+  })
+  .is_some();
+  let order: &[(i64, i64)] = if squeezed {
+    &SQUEEZE_ORDER
+  } else {
+    &DRIP_ORDER
+  };
+  let Some(target) = find_neighbor(canvas, pos, order, |n| n.is_void()) else {
+    // Nowhere to drain to, so it may soak up liquid again
+    if draining {
+      pixel.damage &= !DRAINING;
+      write_pixel(canvas, pos, pixel, dirty_chunks, dirty_pixels);
+    }
+    return;
+  };
+
+  // Drains until empty, ignoring the liquid it releases
+  pixel.damage |= DRAINING;
+  if squeezed || roll(abs_ctx, CH_RELEASE, pos, release_rate) {
+    let mut released = Pixel::new(liquid, ColorIndex(0));
+    released.flags.insert(PixelFlags::DIRTY);
+    write_pixel(canvas, target, released, dirty_chunks, dirty_pixels);
+    pixel.set_saturation(saturation - 1);
+  }
+  // Keeps draining pixels visited until they are empty
+  write_pixel(canvas, pos, pixel, dirty_chunks, dirty_pixels);
+}
+
+/// Processes absorption for every pixel of a single tile.
+///
+/// Respects checkerboard scheduling for thread safety.
+pub fn process_tile_absorption(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  jitter: (i64, i64),
+  abs_ctx: &AbsorptionContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  for local_y in 0..tile_size {
+    for local_x in 0..tile_size {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_absorbent_pixel(canvas, pos, abs_ctx, dirty_chunks, dirty_pixels);
+    }
+  }
+}
@@ -7,6 +7,12 @@ use bevy::prelude::Resource;
 /// Each system can run at a different TPS (ticks per second). The physics
 /// system runs at the base rate, while burning and heat systems run at lower
 /// rates determined by the ratio `physics_tps / system_tps`.
+///
+/// Durations of the per-pixel state passes that run with physics, like
+/// drying and foam decay, are given in seconds and converted to per-tick
+/// probabilities at `physics_tps`.
+///
+/// [`pacing`](Self::pacing) decides how physics ticks map to render frames.
 #[derive(Resource, Clone)]
 pub struct SimulationConfig {
   /// Physics simulation TPS (pixel swaps, falling sand).
@@ -15,6 +21,36 @@ pub struct SimulationConfig {
   pub burning_tps: f32,
   /// Heat simulation TPS (diffusion, ignition checks).
   pub heat_tps: f32,
+  /// How simulation ticks are paced against render frames.
+  pub pacing: TickPacing,
+  /// Average time a wet powder pixel out of contact with liquid takes to
+  /// dry (seconds).
+  /// (default 10.0)
+  pub dry_duration_secs: f32,
+  /// Average time foam stays on a liquid surface after it was last
+  /// disturbed (seconds).
+  /// (default 0.1)
+  pub foam_duration_secs: f32,
+}
+
+/// Pacing of simulation ticks relative to render frames.
+///
+/// Each tick runs the whole
+/// [`PixelSimulation`](crate::pixel_world::PixelSimulation) schedule: body
+/// blit, CA tick, then body readback.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TickPacing {
+  /// One tick per render frame; the simulation speed follows the frame rate.
+  #[default]
+  PerFrame,
+  /// Ticks at `physics_tps` of real time, independent of the frame rate.
+  /// Fast frames may run no tick; slow frames run several, up to
+  /// `max_ticks_per_frame`. Time beyond that cap is dropped rather than
+  /// caught up later.
+  Fixed {
+    /// Maximum ticks run in a single frame.
+    max_ticks_per_frame: u32,
+  },
 }
 
 impl Default for SimulationConfig {
@@ -23,6 +59,27 @@ impl Default for SimulationConfig {
       physics_tps: 60.0,
       burning_tps: 20.0,
       heat_tps: 3.0,
+      pacing: TickPacing::PerFrame,
+      dry_duration_secs: 10.0,
+      foam_duration_secs: 0.1,
     }
   }
 }
+
+impl SimulationConfig {
+  /// Converts dry_duration_secs to per-tick probability of a wet pixel
+  /// drying.
+  ///
+  /// Uses Poisson process: p = 1 / (duration * tps)
+  pub fn dry_chance_per_tick(&self) -> f32 {
+    (1.0 / (self.dry_duration_secs * self.physics_tps)).min(1.0)
+  }
+
+  /// Converts foam_duration_secs to per-tick probability of a foamy pixel
+  /// losing its foam.
+  ///
+  /// Uses Poisson process: p = 1 / (duration * tps)
+  pub fn foam_decay_chance_per_tick(&self) -> f32 {
+    (1.0 / (self.foam_duration_secs * self.physics_tps)).min(1.0)
+  }
+}
@@ -0,0 +1,95 @@
+//! Liquid depth: how far each liquid pixel sits below the surface.
+//!
+//! A liquid pixel's depth is the number of pixels of the same liquid
+//! stacked directly above it, kept in `Pixel::damage` (see
+//! [`Pixel::depth`]). Each pass walks a tile top to bottom, so a pixel reads
+//! the depth its upper neighbor got in the same pass and columns settle in
+//! one sweep per tile. Depth crossing tile edges lags by a pass per tile,
+//! which is invisible at heat rates.
+//!
+//! The chunk shader draws pixels of a material with a [`depth_ramp`] in the
+//! color of the deepest stop they reach, so bodies of liquid darken toward
+//! the bottom as the palette runs from surface to deep. This pass only
+//! records depths and never touches colors. Pixels blending colors with
+//! another liquid are marked as mixing, and the shader draws them in the
+//! color the mixing pass gives them. Whole tiles are scanned since settled
+//! liquid sleeps outside dirty rects, and the pass is skipped when no
+//! liquid has a ramp.
+//!
+//! [`Pixel::depth`]: crate::pixel_world::pixel::Pixel::depth
+//! [`depth_ramp`]: crate::pixel_world::material::Material::depth_ramp
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::Materials;
+use crate::pixel_world::pixel::PixelFlags;
+use crate::pixel_world::scheduling::blitter::Canvas;
+use crate::pixel_world::simulation::mixing;
+
+/// Updates the depth of the liquid pixel at `pos`.
+fn process_depth_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  materials: &Materials,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+) {
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let lx = local.x as u32;
+  let ly = local.y as u32;
+
+  let Some(chunk) = canvas.get(chunk_pos) else {
+    return;
+  };
+
+  let pixel = chunk.pixels[(lx, ly)];
+  if pixel.is_void() || pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+    return;
+  }
+  if !materials.get(pixel.material).has_depth_ramp() {
+    return;
+  }
+
+  let (above_chunk, above_local) = WorldPos::new(pos.x, pos.y + 1).to_chunk_and_local();
+  let depth = canvas
+    .get(above_chunk)
+    .map(|chunk| chunk.pixels[(above_local.x as u32, above_local.y as u32)])
+    .filter(|above| {
+      above.material == pixel.material && !above.flags.contains(PixelFlags::PIXEL_BODY)
+    })
+    .map_or(0, |above| above.depth().saturating_add(1));
+
+  // Colors at a mixing interface belong to the mixing pass
+  let mut updated = pixel;
+  updated.set_depth(depth, mixing::is_mixing(canvas, pos, &pixel, materials));
+  if updated.damage == pixel.damage {
+    return;
+  }
+
+  if let Some(chunk) = canvas.get_mut(chunk_pos) {
+    chunk.pixels[(lx, ly)].damage = updated.damage;
+  }
+  dirty_chunks.insert(chunk_pos);
+}
+
+/// Processes liquid depth for every pixel of a single tile, top row first.
+///
+/// Respects checkerboard scheduling for thread safety.
+pub fn process_tile_depth(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  jitter: (i64, i64),
+  materials: &Materials,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  for local_y in (0..tile_size).rev() {
+    for local_x in 0..tile_size {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_depth_pixel(canvas, pos, materials, dirty_chunks);
+    }
+  }
+}
@@ -0,0 +1,123 @@
+//! Ephemeral pixels and falling trails.
+//!
+//! Pixels of a material with a nonzero [`lifetime`] are ephemeral: each
+//! tick they age by one, counted in `Pixel::damage`, and walk their palette
+//! from the first entry to the last. Once their age reaches the lifetime
+//! they vanish. Aging is deterministic, so an ephemeral pixel lives exactly
+//! `lifetime` ticks while its tile is active, and keeps the tile active
+//! until it is gone.
+//!
+//! A material with a [`trail`] leaves one pixel of the trail material
+//! behind each time one of its pixels falls into air, which draws streaks
+//! behind embers or drips when the trail is ephemeral.
+//!
+//! [`lifetime`]: crate::pixel_world::material::Material::lifetime
+//! [`trail`]: crate::pixel_world::material::Material::trail
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, ColorIndex, LocalPos, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::Materials;
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::scheduling::blitter::Canvas;
+
+/// Leaves a trail pixel behind the pixel at `pos` before it swaps to
+/// `target`.
+///
+/// Called from the physics pass with a pixel that is about to move. When it
+/// falls into void and its material has a trail, the trail pixel is written
+/// at `target`, so the swap moves it into the vacated cell. At most one
+/// trail pixel is left per move.
+pub(crate) fn leave_trail(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  target: WorldPos,
+  materials: &Materials,
+) {
+  if target.y >= pos.y {
+    return;
+  }
+
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let Some(chunk) = canvas.get(chunk_pos) else {
+    return;
+  };
+  let pixel = chunk.pixels[(local.x as u32, local.y as u32)];
+  let Some(trail) = materials.get(pixel.material).trail else {
+    return;
+  };
+
+  let (chunk_pos, local) = target.to_chunk_and_local();
+  let Some(chunk) = canvas.get_mut(chunk_pos) else {
+    return;
+  };
+  let cell = &mut chunk.pixels[(local.x as u32, local.y as u32)];
+  if cell.is_void() {
+    *cell = Pixel::new(trail, ColorIndex(0));
+  }
+}
+
+/// Ages a single ephemeral pixel, removing it once its lifetime is up.
+fn process_ephemeral_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  materials: &Materials,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let lx = local.x as u32;
+  let ly = local.y as u32;
+
+  let Some(chunk) = canvas.get_mut(chunk_pos) else {
+    return;
+  };
+
+  let pixel = &mut chunk.pixels[(lx, ly)];
+  if pixel.is_void() || pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+    return;
+  }
+  let lifetime = materials.get(pixel.material).lifetime;
+  if lifetime == 0 {
+    return;
+  }
+
+  let age = pixel.damage.saturating_add(1);
+  if age >= lifetime {
+    *pixel = Pixel::VOID;
+  } else {
+    pixel.damage = age;
+    pixel.color = ColorIndex((age as u32 * 255 / lifetime as u32) as u8);
+  }
+
+  // Keep visiting until the pixel is gone, and wake whatever rests on it
+  dirty_chunks.insert(chunk_pos);
+  dirty_pixels.push((chunk_pos, local));
+}
+
+/// Ages ephemeral pixels for a single tile using dirty bounds.
+///
+/// Only processes pixels within the tile's dirty rect, respecting
+/// checkerboard scheduling for thread safety.
+pub fn process_tile_ephemeral(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  bounds: (u8, u8, u8, u8),
+  jitter: (i64, i64),
+  materials: &Materials,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  let (min_x, min_y, max_x, max_y) = bounds;
+
+  for local_y in (min_y as i64)..=(max_y as i64) {
+    for local_x in (min_x as i64)..=(max_x as i64) {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_ephemeral_pixel(canvas, pos, materials, dirty_chunks, dirty_pixels);
+    }
+  }
+}
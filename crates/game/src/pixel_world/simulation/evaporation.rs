@@ -0,0 +1,108 @@
+//! Evaporation of liquids at the air interface.
+//!
+//! Liquid pixels bordering air vanish with their material's
+//! `evaporation_chance` per second, scaled up by the heat of their heat cell
+//! through `evaporation_heat_coupling`. Keeps stray puddles from lasting
+//! forever while enclosed bodies of liquid stay put.
+//!
+//! Runs with the heat pass. Calm liquid sleeps outside dirty rects, so whole
+//! tiles are scanned; the pass is skipped when no material evaporates.
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, LocalPos, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::primitives::HEAT_CELL_SIZE;
+use crate::pixel_world::scheduling::blitter::Canvas;
+use crate::pixel_world::simulation::SimContext;
+use crate::pixel_world::simulation::foam::borders_air;
+use crate::pixel_world::simulation::hash::hash41uu64;
+
+/// Context for evaporation within a tile.
+pub struct EvaporationContext<'a> {
+  pub materials: &'a Materials,
+  pub ctx: SimContext,
+  /// Rate of the heat pass, used to turn per-second chances into per-tick
+  /// ones.
+  pub heat_tps: f32,
+}
+
+/// Evaporates the pixel at `pos` if it is a liquid at the air interface and
+/// its roll succeeds.
+fn process_evaporation_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  evap_ctx: &EvaporationContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  const CH_EVAPORATE: u64 = 0x5eed_e7a9_0a7e_0001;
+
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let lx = local.x as u32;
+  let ly = local.y as u32;
+
+  let Some(chunk) = canvas.get(chunk_pos) else {
+    return;
+  };
+
+  let pixel = chunk.pixels[(lx, ly)];
+  if pixel.is_void() || pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+    return;
+  }
+
+  let material = evap_ctx.materials.get(pixel.material);
+  if material.state != PhysicsState::Liquid || material.evaporation_chance <= 0.0 {
+    return;
+  }
+  if !borders_air(canvas, pos, evap_ctx.materials) {
+    return;
+  }
+
+  let heat = chunk.heat_cell(lx / HEAT_CELL_SIZE, ly / HEAT_CELL_SIZE) as f32 / 255.0;
+  let chance = (material.evaporation_chance / evap_ctx.heat_tps
+    * (1.0 + material.evaporation_heat_coupling * heat))
+    .min(1.0);
+
+  let hash = hash41uu64(
+    evap_ctx.ctx.seed ^ CH_EVAPORATE,
+    evap_ctx.ctx.tick,
+    pos.x as u64,
+    pos.y as u64,
+  );
+  let roll = (hash & 0xFFFF) as f32 / 65535.0;
+  if roll >= chance {
+    return;
+  }
+
+  if let Some(chunk) = canvas.get_mut(chunk_pos) {
+    chunk.pixels[(lx, ly)] = Pixel::VOID;
+  }
+  // Wake the liquid around the gap
+  dirty_pixels.push((chunk_pos, local));
+  dirty_chunks.insert(chunk_pos);
+}
+
+/// Processes evaporation for every pixel of a single tile.
+///
+/// Respects checkerboard scheduling for thread safety.
+pub fn process_tile_evaporation(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  jitter: (i64, i64),
+  evap_ctx: &EvaporationContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  for local_y in 0..tile_size {
+    for local_x in 0..tile_size {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_evaporation_pixel(canvas, pos, evap_ctx, dirty_chunks, dirty_pixels);
+    }
+  }
+}
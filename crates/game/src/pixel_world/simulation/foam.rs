@@ -0,0 +1,131 @@
+//! Foam on disturbed liquid surfaces.
+//!
+//! Liquid pixels that move during the physics pass are marked with the `FOAM`
+//! flag. This pass keeps foam only where the liquid borders air and lets it
+//! decay probabilistically, so turbulent water renders lighter than calm
+//! water. Uses checkerboard scheduling and dirty rects like wetness.
+//!
+//! Foamy pixels keep their tile's dirty rect alive until the foam clears.
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, LocalPos, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::PixelFlags;
+use crate::pixel_world::scheduling::blitter::Canvas;
+use crate::pixel_world::simulation::SimContext;
+use crate::pixel_world::simulation::hash::hash41uu64;
+
+/// Cardinal neighbor offsets.
+const CARDINAL: [(i64, i64); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Context for foam simulation within a tile.
+pub struct FoamContext<'a> {
+  pub materials: &'a Materials,
+  pub ctx: SimContext,
+  /// Per-tick probability of a foamy pixel losing its foam.
+  /// Derived from 1 / (foam_duration_secs * physics_tps).
+  pub decay_chance: f32,
+}
+
+/// Marks the liquid pixel at `pos` as disturbed before it is swapped away.
+///
+/// Called from the physics pass with the position of a pixel that is about
+/// to move, which lies in the tile being processed.
+pub(crate) fn mark_disturbed(canvas: &Canvas<'_>, pos: WorldPos, materials: &Materials) {
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let Some(chunk) = canvas.get_mut(chunk_pos) else {
+    return;
+  };
+
+  let pixel = &mut chunk.pixels[(local.x as u32, local.y as u32)];
+  if materials.get(pixel.material).state == PhysicsState::Liquid {
+    pixel.flags.insert(PixelFlags::FOAM);
+  }
+}
+
+/// Returns true if any cardinal neighbor of `pos` is void or gas.
+pub(crate) fn borders_air(canvas: &Canvas<'_>, pos: WorldPos, materials: &Materials) -> bool {
+  CARDINAL.iter().any(|&(dx, dy)| {
+    let (chunk_pos, local) = WorldPos::new(pos.x + dx, pos.y + dy).to_chunk_and_local();
+    canvas.get(chunk_pos).is_some_and(|chunk| {
+      let neighbor = chunk.pixels[(local.x as u32, local.y as u32)];
+      neighbor.is_void() || materials.get(neighbor.material).state == PhysicsState::Gas
+    })
+  })
+}
+
+/// Keeps or clears the foam on a single pixel.
+fn process_foam_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  foam_ctx: &FoamContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  const CH_FOAM_DECAY: u64 = 0x5eed_f0a3_dec4_0001;
+
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let lx = local.x as u32;
+  let ly = local.y as u32;
+
+  let Some(chunk) = canvas.get(chunk_pos) else {
+    return;
+  };
+
+  let pixel = chunk.pixels[(lx, ly)];
+  if !pixel.flags.contains(PixelFlags::FOAM) {
+    return;
+  }
+
+  let on_surface = foam_ctx.materials.get(pixel.material).state == PhysicsState::Liquid
+    && borders_air(canvas, pos, foam_ctx.materials);
+  let keep = on_surface && {
+    let decay_hash = hash41uu64(
+      foam_ctx.ctx.seed ^ CH_FOAM_DECAY,
+      foam_ctx.ctx.tick,
+      pos.x as u64,
+      pos.y as u64,
+    );
+    let decay_roll = (decay_hash & 0xFFFF) as f32 / 65535.0;
+    decay_roll >= foam_ctx.decay_chance
+  };
+
+  if keep {
+    // Keep visiting until the foam decays
+    dirty_pixels.push((chunk_pos, local));
+    return;
+  }
+
+  if let Some(chunk) = canvas.get_mut(chunk_pos) {
+    chunk.pixels[(lx, ly)].flags.remove(PixelFlags::FOAM);
+  }
+  dirty_chunks.insert(chunk_pos);
+}
+
+/// Processes foam for a single tile using dirty bounds.
+///
+/// Only processes pixels within the tile's dirty rect, respecting
+/// checkerboard scheduling for thread safety.
+pub fn process_tile_foam(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  bounds: (u8, u8, u8, u8),
+  jitter: (i64, i64),
+  foam_ctx: &FoamContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  let (min_x, min_y, max_x, max_y) = bounds;
+
+  for local_y in (min_y as i64)..=(max_y as i64) {
+    for local_x in (min_x as i64)..=(max_x as i64) {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_foam_pixel(canvas, pos, foam_ctx, dirty_chunks, dirty_pixels);
+    }
+  }
+}
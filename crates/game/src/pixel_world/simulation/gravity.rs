@@ -0,0 +1,199 @@
+//! Gravity wells.
+//!
+//! Gravity points straight down everywhere, except within the radius of a
+//! [`GravityWell`]. There the pull is blended from down toward the well's
+//! direction, the more so the closer a pixel is to the well's center, and
+//! rounded to one of the eight neighbor steps. The physics pass falls,
+//! slides and flows relative to that step instead of down.
+//!
+//! Wells are collected once per tick into [`GravityWells`], the lookup the
+//! physics pass consults, so the result only depends on pixel positions and
+//! stays deterministic.
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+
+use crate::pixel_world::coords::{TILE_SIZE, WorldPos};
+use crate::pixel_world::world::PixelWorld;
+
+/// Unit steps to the eight neighbors of a pixel, counter-clockwise from +X.
+const STEPS: [(i64, i64); 8] = [
+  (1, 0),
+  (1, 1),
+  (0, 1),
+  (-1, 1),
+  (-1, 0),
+  (-1, -1),
+  (0, -1),
+  (1, -1),
+];
+
+/// Index of the straight down step in [`STEPS`].
+pub(crate) const DOWN: usize = 6;
+
+/// Blended pull below which pixels are weightless and stay put.
+const MIN_PULL: f32 = 0.05;
+
+/// Returns `pos` moved one step in direction `dir`, turned by `turn`
+/// eighths of a full circle counter-clockwise.
+///
+/// With `dir` = [`DOWN`], turns of ±1 are the diagonals below and ±2 the
+/// sideways neighbors.
+#[inline]
+pub(crate) fn step(pos: WorldPos, dir: usize, turn: i64) -> WorldPos {
+  let (dx, dy) = STEPS[(dir as i64 + turn).rem_euclid(8) as usize];
+  WorldPos::new(pos.x + dx, pos.y + dy)
+}
+
+/// Local influence overriding the direction of gravity.
+///
+/// Within `radius` of `center`, gravity is blended from down toward the
+/// well's pull by `strength * (1 - distance / radius)`. A strength of 1
+/// fully overrides gravity at the center only; larger strengths widen the
+/// fully overridden area.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GravityWell {
+  /// Center in world pixel coordinates.
+  pub center: Vec2,
+  /// Radius of influence in pixels.
+  pub radius: f32,
+  /// Blend weight at the center.
+  pub strength: f32,
+  /// Direction of the pull, or `None` to pull toward `center`.
+  pub direction: Option<Vec2>,
+}
+
+impl GravityWell {
+  /// Creates a well pulling everything in its radius along `direction`.
+  pub fn directional(center: Vec2, radius: f32, strength: f32, direction: Vec2) -> Self {
+    Self {
+      center,
+      radius,
+      strength,
+      direction: Some(direction),
+    }
+  }
+
+  /// Creates a well pulling everything in its radius toward its center.
+  pub fn point(center: Vec2, radius: f32, strength: f32) -> Self {
+    Self {
+      center,
+      radius,
+      strength,
+      direction: None,
+    }
+  }
+
+  fn is_active(&self) -> bool {
+    self.radius > 0.0 && self.strength > 0.0
+  }
+
+  /// Returns the world rect of pixels within the well's radius, as
+  /// inclusive min and max corners.
+  fn bounds(&self) -> (WorldPos, WorldPos) {
+    let min = (self.center - self.radius).floor();
+    let max = (self.center + self.radius).ceil();
+    (
+      WorldPos::new(min.x as i64, min.y as i64),
+      WorldPos::new(max.x as i64, max.y as i64),
+    )
+  }
+}
+
+/// Active gravity wells, collected each simulation tick.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct GravityWells {
+  wells: Vec<GravityWell>,
+}
+
+impl GravityWells {
+  /// Creates a lookup of the given wells, skipping ones without radius or
+  /// strength.
+  pub fn new(wells: impl IntoIterator<Item = GravityWell>) -> Self {
+    Self {
+      wells: wells.into_iter().filter(GravityWell::is_active).collect(),
+    }
+  }
+
+  /// Returns true if gravity points down everywhere.
+  pub fn is_empty(&self) -> bool {
+    self.wells.is_empty()
+  }
+
+  /// Returns the neighbor a pixel at `pos` falls toward, or `None` where
+  /// it's weightless.
+  pub fn fall_target(&self, pos: WorldPos) -> Option<WorldPos> {
+    self.direction_at(pos).map(|dir| step(pos, dir, 0))
+  }
+
+  /// Returns the [`STEPS`] index gravity points along at `pos`, or `None`
+  /// where the wells cancel it out.
+  pub(crate) fn direction_at(&self, pos: WorldPos) -> Option<usize> {
+    if self.wells.is_empty() {
+      return Some(DOWN);
+    }
+
+    let point = Vec2::new(pos.x as f32 + 0.5, pos.y as f32 + 0.5);
+    let mut pull = Vec2::ZERO;
+    let mut weight = 0.0;
+    for well in &self.wells {
+      let offset = well.center - point;
+      let distance = offset.length();
+      if distance >= well.radius {
+        continue;
+      }
+      let w = well.strength * (1.0 - distance / well.radius);
+      let direction = well.direction.unwrap_or(offset).normalize_or_zero();
+      pull += direction * w;
+      weight += w;
+    }
+    if weight == 0.0 {
+      return Some(DOWN);
+    }
+
+    let blend = weight.min(1.0);
+    let gravity = Vec2::NEG_Y * (1.0 - blend) + pull / weight * blend;
+    if gravity.length() < MIN_PULL {
+      return None;
+    }
+    let octant = (gravity.y.atan2(gravity.x) / FRAC_PI_4).round() as i64;
+    Some(octant.rem_euclid(8) as usize)
+  }
+
+  /// Wakes the tiles under every well, so resting pixels react to it.
+  fn wake(&self, world: &mut PixelWorld) {
+    let tile = TILE_SIZE as i64;
+    for well in &self.wells {
+      let (min, max) = well.bounds();
+      for ty in min.y.div_euclid(tile)..=max.y.div_euclid(tile) {
+        for tx in min.x.div_euclid(tile)..=max.x.div_euclid(tile) {
+          // Opposite corners expand the dirty rect to the whole tile
+          world.mark_pixel_sim_dirty(WorldPos::new(tx * tile, ty * tile));
+          world.mark_pixel_sim_dirty(WorldPos::new(tx * tile + tile - 1, ty * tile + tile - 1));
+        }
+      }
+    }
+  }
+}
+
+/// System: Collects the gravity wells for this tick.
+///
+/// When the wells changed since the last tick, the tiles under both the old
+/// and the new wells are woken up.
+pub(crate) fn collect_gravity_wells(
+  query: Query<&GravityWell>,
+  mut wells: ResMut<GravityWells>,
+  mut worlds: Query<&mut PixelWorld>,
+) {
+  let current = GravityWells::new(query.iter().copied());
+  if current == *wells {
+    return;
+  }
+  for mut world in &mut worlds {
+    wells.wake(&mut world);
+    current.wake(&mut world);
+  }
+  *wells = current;
+}
+
@@ -2,12 +2,16 @@
 //!
 //! The heat layer is a downsampled grid (1/4 resolution) per chunk. Each cell
 //! accumulates heat from burning pixels and material base temperatures, then
-//! diffuses to neighbors with a cooling factor.
+//! diffuses to neighbors at a rate set by the lower thermal conductivity of
+//! each pair of cells, with a cooling factor. Burning materials with
+//! endothermic reactions draw heat back out of their cell.
+
+use std::collections::HashMap;
 
 use crate::pixel_world::coords::ChunkPos;
 use crate::pixel_world::debug_shim::{DebugGizmos, emit_heat_dirty_tile};
 use crate::pixel_world::material::Materials;
-use crate::pixel_world::pixel::PixelFlags;
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
 use crate::pixel_world::primitives::{Chunk, HEAT_CELL_SIZE, HEAT_CELLS_PER_TILE, HEAT_GRID_SIZE};
 use crate::pixel_world::scheduling::blitter::Canvas;
 
@@ -60,19 +64,28 @@ impl HeatConfig {
   }
 }
 
+/// Heat sources gathered from the pixels of one heat cell.
+struct CellSources {
+  /// Heat emitted into the cell.
+  source: u32,
+  /// Heat drawn out of the cell by endothermic reactions.
+  absorbed: u32,
+  /// Number of non-void pixels.
+  solid_count: u32,
+}
+
 /// Accumulates heat from pixel sources within a single heat cell's 4x4 region.
-/// Returns (source_heat, solid_count) where solid_count is the number of
-/// non-void pixels.
 fn accumulate_cell_heat_sources(
   chunk: &Chunk,
   hx: u32,
   hy: u32,
   materials: &Materials,
   burning_heat: u8,
-) -> (u32, u32) {
+) -> CellSources {
   let px_base_x = hx * HEAT_CELL_SIZE;
   let px_base_y = hy * HEAT_CELL_SIZE;
   let mut source: u32 = 0;
+  let mut absorbed: u32 = 0;
   let mut solid_count: u32 = 0;
 
   for dy in 0..HEAT_CELL_SIZE {
@@ -86,75 +99,158 @@ fn accumulate_cell_heat_sources(
       source += mat.base_temperature as u32;
       if pixel.flags.contains(PixelFlags::BURNING) {
         source += burning_heat as u32;
+        let reaction = mat.effects.reaction_heat;
+        if reaction > 0 {
+          source += reaction as u32;
+        } else {
+          absorbed += reaction.unsigned_abs() as u32;
+        }
       }
     }
   }
 
-  (source, solid_count)
+  CellSources {
+    source,
+    absorbed,
+    solid_count,
+  }
+}
+
+/// Returns the thermal conductivity of a pixel; void conducts fully.
+#[inline]
+fn pixel_conductivity(pixel: Pixel, materials: &Materials) -> f32 {
+  if pixel.is_void() {
+    return 1.0;
+  }
+  materials
+    .get(pixel.material)
+    .thermal_conductivity
+    .clamp(0.0, 1.0)
+}
+
+/// Returns the mean thermal conductivity of the pixels in a heat cell.
+fn cell_conductivity(chunk: &Chunk, hx: u32, hy: u32, materials: &Materials) -> f32 {
+  let px_base_x = hx * HEAT_CELL_SIZE;
+  let px_base_y = hy * HEAT_CELL_SIZE;
+  let mut conductivity = 0.0;
+  for dy in 0..HEAT_CELL_SIZE {
+    for dx in 0..HEAT_CELL_SIZE {
+      conductivity += pixel_conductivity(chunk.pixels[(px_base_x + dx, px_base_y + dy)], materials);
+    }
+  }
+  conductivity / (HEAT_CELL_SIZE * HEAT_CELL_SIZE) as f32
+}
+
+/// Cell conductivities computed during one heat tick.
+///
+/// Diffusion reads each cell's conductivity once for the cell itself and
+/// once per cardinal neighbor. Pixels don't change during a heat tick, so
+/// each cell is averaged on first use and reused for the rest of the tick.
+#[derive(Default)]
+struct ConductivityCache {
+  /// Per-chunk cell conductivities, `NaN` where not yet computed.
+  chunks: HashMap<ChunkPos, Vec<f32>>,
+}
+
+impl ConductivityCache {
+  /// Returns the mean thermal conductivity of heat cell `(hx, hy)` of the
+  /// chunk at `chunk_pos`.
+  fn get(
+    &mut self,
+    chunk: &Chunk,
+    chunk_pos: ChunkPos,
+    hx: u32,
+    hy: u32,
+    materials: &Materials,
+  ) -> f32 {
+    let cells = self
+      .chunks
+      .entry(chunk_pos)
+      .or_insert_with(|| vec![f32::NAN; (HEAT_GRID_SIZE * HEAT_GRID_SIZE) as usize]);
+    let cell = &mut cells[(hy * HEAT_GRID_SIZE + hx) as usize];
+    if cell.is_nan() {
+      *cell = cell_conductivity(chunk, hx, hy, materials);
+    }
+    *cell
+  }
 }
 
 /// Cardinal offsets for heat neighbor sampling: (dx, dy).
 const HEAT_CARDINAL: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
 
 /// Samples heat from cardinal neighbors, handling both interior and cross-chunk
-/// boundaries. Returns `(neighbor_sum, neighbor_count)`.
+/// boundaries.
+///
+/// Returns `(pull, neighbor_count)`, where `pull` sums each neighbor's heat
+/// difference to the cell's heat weighted by the conductivity of the pair: the
+/// lower of both cells' conductivities, so an insulator slows heat flowing
+/// in as well as out.
 fn sample_heat_neighbors(
   hx: u32,
   hy: u32,
   chunk: &Chunk,
   canvas: &Canvas<'_>,
   chunk_pos: ChunkPos,
-) -> (u32, u32) {
-  let mut sum: u32 = 0;
+  materials: &Materials,
+  conductivities: &mut ConductivityCache,
+) -> (f32, u32) {
+  let self_heat = chunk.heat_cell(hx, hy) as f32;
+  let conductivity = conductivities.get(chunk, chunk_pos, hx, hy, materials);
+  let mut pull: f32 = 0.0;
   let mut count: u32 = 0;
 
   for (dx, dy) in HEAT_CARDINAL {
     let nx = hx as i32 + dx;
     let ny = hy as i32 + dy;
 
-    let heat = if nx >= 0 && nx < HEAT_GRID_SIZE as i32 && ny >= 0 && ny < HEAT_GRID_SIZE as i32 {
-      // Interior neighbor
-      chunk.heat_cell(nx as u32, ny as u32)
-    } else {
-      // Cross-chunk neighbor
-      let neighbor_chunk_pos = ChunkPos::new(
-        chunk_pos.x
-          + if nx < 0 {
-            -1
-          } else if nx >= HEAT_GRID_SIZE as i32 {
-            1
-          } else {
-            0
-          },
-        chunk_pos.y
-          + if ny < 0 {
-            -1
-          } else if ny >= HEAT_GRID_SIZE as i32 {
-            1
-          } else {
-            0
-          },
-      );
-      let Some(n) = canvas.get(neighbor_chunk_pos) else {
-        continue;
+    let (neighbor, neighbor_pos, cx, cy) =
+      if nx >= 0 && nx < HEAT_GRID_SIZE as i32 && ny >= 0 && ny < HEAT_GRID_SIZE as i32 {
+        // Interior neighbor
+        (chunk, chunk_pos, nx as u32, ny as u32)
+      } else {
+        // Cross-chunk neighbor
+        let neighbor_chunk_pos = ChunkPos::new(
+          chunk_pos.x
+            + if nx < 0 {
+              -1
+            } else if nx >= HEAT_GRID_SIZE as i32 {
+              1
+            } else {
+              0
+            },
+          chunk_pos.y
+            + if ny < 0 {
+              -1
+            } else if ny >= HEAT_GRID_SIZE as i32 {
+              1
+            } else {
+              0
+            },
+        );
+        let Some(n) = canvas.get(neighbor_chunk_pos) else {
+          continue;
+        };
+        (
+          n,
+          neighbor_chunk_pos,
+          nx.rem_euclid(HEAT_GRID_SIZE as i32) as u32,
+          ny.rem_euclid(HEAT_GRID_SIZE as i32) as u32,
+        )
       };
-      n.heat_cell(
-        nx.rem_euclid(HEAT_GRID_SIZE as i32) as u32,
-        ny.rem_euclid(HEAT_GRID_SIZE as i32) as u32,
-      )
-    };
 
-    sum += heat as u32;
+    let pair = conductivity.min(conductivities.get(neighbor, neighbor_pos, cx, cy, materials));
+    pull += (neighbor.heat_cell(cx, cy) as f32 - self_heat) * pair;
     count += 1;
   }
 
-  (sum, count)
+  (pull, count)
 }
 
 /// Propagates heat across all chunks accessible through the canvas.
 ///
 /// For each heat cell: accumulate source heat from pixels, diffuse with
-/// cardinal neighbors, apply cooling. Uses a scratch buffer per chunk to
+/// cardinal neighbors weighted by the thermal conductivity between each
+/// pair of cells, apply cooling. Uses a scratch buffer per chunk to
 /// avoid read-write conflicts.
 ///
 /// Only processes active heat tiles (those marked dirty or in cooldown).
@@ -168,6 +264,7 @@ pub fn propagate_heat(
   let grid_size = HEAT_GRID_SIZE as usize;
   let cell_count = grid_size * grid_size;
   let mut scratch = vec![0u8; cell_count];
+  let mut conductivities = ConductivityCache::default();
 
   for &chunk_pos in chunk_positions {
     // Borrow immutably first to collect active tiles
@@ -190,18 +287,22 @@ pub fn propagate_heat(
 
       for hy in hy_start..hy_start + HEAT_CELLS_PER_TILE {
         for hx in hx_start..hx_start + HEAT_CELLS_PER_TILE {
-          let (source, solid_count) =
-            accumulate_cell_heat_sources(chunk, hx, hy, materials, config.burning_heat);
-
-          let self_heat = chunk.heat_cell(hx, hy) as u32;
-          let (neighbor_sum, neighbor_count) =
-            sample_heat_neighbors(hx, hy, chunk, canvas, chunk_pos);
-
-          let neighbor_avg = if neighbor_count > 0 {
-            neighbor_sum / neighbor_count
-          } else {
-            0
-          };
+          let CellSources {
+            source,
+            absorbed,
+            solid_count,
+          } = accumulate_cell_heat_sources(chunk, hx, hy, materials, config.burning_heat);
+
+          let self_heat = chunk.heat_cell(hx, hy) as f32;
+          let (pull, neighbor_count) = sample_heat_neighbors(
+            hx,
+            hy,
+            chunk,
+            canvas,
+            chunk_pos,
+            materials,
+            &mut conductivities,
+          );
 
           // Heat in air (no solid pixels) dissipates 10x faster
           let effective_cooling = if solid_count == 0 {
@@ -210,8 +311,16 @@ pub fn propagate_heat(
             config.cooling_factor
           };
 
-          let diffused = ((self_heat + neighbor_avg) as f32 / 2.0 * effective_cooling) as u32;
-          let new_temp = source.max(diffused).min(255) as u8;
+          // Move toward each neighbor at a rate scaled by the pair's
+          // conductivity; fully conductive cells land halfway between self
+          // and the neighbor average
+          let blended = if neighbor_count > 0 {
+            self_heat + pull * 0.5 / neighbor_count as f32
+          } else {
+            self_heat
+          };
+          let diffused = (blended * effective_cooling) as u32;
+          let new_temp = source.max(diffused).saturating_sub(absorbed).min(255) as u8;
 
           scratch[(hy * HEAT_GRID_SIZE + hx) as usize] = new_temp;
         }
@@ -0,0 +1,188 @@
+//! Liquid color mixing: cosmetic blending where different liquids touch.
+//!
+//! Color indices pick a shade within a pixel's own palette, so liquids mix
+//! by rendered color: a liquid pixel with a [`color_mix_rate`] looks for the
+//! shade of its own palette nearest to halfway between its current color
+//! and the mean rendered color of its cardinal neighbors that are pixels of
+//! other liquids, and moves its color index toward that shade by that
+//! fraction per second, at least one step per pass. Both sides of an
+//! interface, like dye meeting water, so drift toward a shared hue as far as
+//! their palettes reach. Pixels of the same liquid are not mixed with each
+//! other, and an [`immiscible`] liquid neither mixes nor lends its color to
+//! its neighbors.
+//!
+//! Like context variants, only color indices change and whole tiles are
+//! scanned since settled liquid sleeps outside dirty rects. The depth pass
+//! marks pixels at an interface as mixing, so the chunk shader draws them in
+//! the color this pass gives them instead of their depth ramp, starting
+//! from the shade the ramp drew, and the ramp takes over again once the
+//! interface is gone. Ephemeral liquids keep the colors their age gives
+//! them. The pass is skipped when no liquid mixes.
+//!
+//! [`color_mix_rate`]: crate::pixel_world::material::Material::color_mix_rate
+//! [`immiscible`]: crate::pixel_world::material::Material::immiscible
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, ColorIndex, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::scheduling::blitter::Canvas;
+
+/// Context for color mixing within a tile.
+pub struct MixingContext<'a> {
+  pub materials: &'a Materials,
+  /// Rate of the heat pass, used to turn per-second rates into per-tick
+  /// steps.
+  pub heat_tps: f32,
+}
+
+/// Returns true if `pixel` is a free pixel of a liquid that mixes colors
+/// with other liquids.
+fn is_miscible_liquid(pixel: &Pixel, materials: &Materials) -> bool {
+  if pixel.is_void() || pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+    return false;
+  }
+  let material = materials.get(pixel.material);
+  material.state == PhysicsState::Liquid && !material.immiscible
+}
+
+/// Returns true if `pixel` mixes colors at all.
+fn mixes(pixel: &Pixel, materials: &Materials) -> bool {
+  let material = materials.get(pixel.material);
+  is_miscible_liquid(pixel, materials) && material.color_mix_rate > 0.0 && material.lifetime == 0
+}
+
+/// Returns the RGB the chunk shader renders for `pixel`.
+fn rendered_rgb(pixel: &Pixel, materials: &Materials) -> [i32; 3] {
+  let material = materials.get(pixel.material);
+  let rgba = material.palette[material.shaded_color(pixel).0 as usize * 7 / 255];
+  [rgba.red as i32, rgba.green as i32, rgba.blue as i32]
+}
+
+/// Returns the mean rendered RGB of the cardinal neighbors of `pixel` at
+/// `pos` that are pixels of other miscible liquids, or `None` if there are
+/// none.
+fn other_liquids_rgb(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  pixel: &Pixel,
+  materials: &Materials,
+) -> Option<[i32; 3]> {
+  let mut sum = [0i32; 3];
+  let mut count = 0i32;
+  for (dx, dy) in [(0, 1), (0, -1), (-1, 0), (1, 0)] {
+    let (neighbor_chunk, neighbor_local) =
+      WorldPos::new(pos.x + dx, pos.y + dy).to_chunk_and_local();
+    let Some(neighbor) = canvas
+      .get(neighbor_chunk)
+      .map(|chunk| chunk.pixels[(neighbor_local.x as u32, neighbor_local.y as u32)])
+    else {
+      continue;
+    };
+    if neighbor.material != pixel.material && is_miscible_liquid(&neighbor, materials) {
+      let rgb = rendered_rgb(&neighbor, materials);
+      for (total, channel) in sum.iter_mut().zip(rgb) {
+        *total += channel;
+      }
+      count += 1;
+    }
+  }
+  (count > 0).then(|| sum.map(|total| (total + count / 2) / count))
+}
+
+/// Returns true if `pixel` at `pos` mixes and touches another miscible
+/// liquid, so its color belongs to this pass rather than depth shading.
+pub(crate) fn is_mixing(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  pixel: &Pixel,
+  materials: &Materials,
+) -> bool {
+  mixes(pixel, materials) && other_liquids_rgb(canvas, pos, pixel, materials).is_some()
+}
+
+/// Returns the smallest color index the shader maps to palette `shade`.
+fn shade_start(shade: usize) -> i32 {
+  ((shade * 255 + 6) / 7) as i32
+}
+
+/// Blends the color of the liquid pixel at `pos` toward its neighbors of
+/// other liquids.
+fn process_mixing_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  mix_ctx: &MixingContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+) {
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let lx = local.x as u32;
+  let ly = local.y as u32;
+
+  let Some(chunk) = canvas.get(chunk_pos) else {
+    return;
+  };
+
+  let pixel = chunk.pixels[(lx, ly)];
+  if !mixes(&pixel, mix_ctx.materials) {
+    return;
+  }
+  let Some(others) = other_liquids_rgb(canvas, pos, &pixel, mix_ctx.materials) else {
+    return;
+  };
+
+  let own = rendered_rgb(&pixel, mix_ctx.materials);
+  let target: [i32; 3] = std::array::from_fn(|i| (own[i] + others[i]) / 2);
+  let material = mix_ctx.materials.get(pixel.material);
+  // A pixel just reaching an interface starts from its depth ramp color
+  let current = material.shaded_color(&pixel);
+  let shade = (0..material.palette.len())
+    .min_by_key(|&shade| {
+      let rgba = material.palette[shade];
+      let rgb = [rgba.red as i32, rgba.green as i32, rgba.blue as i32];
+      (0..3).map(|i| (rgb[i] - target[i]).pow(2)).sum::<i32>()
+    })
+    .unwrap_or(0);
+
+  let color = if shade == current.0 as usize * 7 / 255 {
+    current
+  } else {
+    let delta = shade_start(shade) - current.0 as i32;
+    let fraction = (material.color_mix_rate / mix_ctx.heat_tps).min(1.0);
+    let mut step = (delta as f32 * fraction).round() as i32;
+    if step == 0 {
+      step = delta.signum();
+    }
+    ColorIndex((current.0 as i32 + step).clamp(0, 255) as u8)
+  };
+  if color == pixel.color {
+    return;
+  }
+
+  if let Some(chunk) = canvas.get_mut(chunk_pos) {
+    chunk.pixels[(lx, ly)].color = color;
+  }
+  dirty_chunks.insert(chunk_pos);
+}
+
+/// Processes color mixing for every pixel of a single tile.
+///
+/// Respects checkerboard scheduling for thread safety.
+pub fn process_tile_mixing(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  jitter: (i64, i64),
+  mix_ctx: &MixingContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  for local_y in 0..tile_size {
+    for local_x in 0..tile_size {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_mixing_pixel(canvas, pos, mix_ctx, dirty_chunks);
+    }
+  }
+}
@@ -1,31 +1,59 @@
 //! Cellular automata simulation.
 //!
-//! Implements falling sand physics, burning propagation, and heat diffusion
+//! Implements falling sand physics, wetness, liquid foam, ephemeral pixels,
+//! burning propagation, heat diffusion, evaporation, liquid absorption,
+//! cosmetic context variants, liquid depth shading and liquid color mixing
 //! using checkerboard scheduling for parallel processing.
 //!
 //! # Simulation Passes
 //!
-//! Three independent simulation systems run at different tick rates:
+//! Eleven independent simulation systems run at different tick rates:
 //!
 //! | System | Tick Rate | Scheduling | Description |
 //! |--------|-----------|------------|-------------|
-//! | Physics | every tick | Checkerboard | Pixel swaps, falling sand |
+//! | Physics | every tick | Checkerboard | Pixel swaps, falling sand, gravity wells, wind |
+//! | Wetness | every tick | Checkerboard | Powders wetted by liquid, drying |
+//! | Foam | every tick | Checkerboard | Disturbed liquid surfaces, decay |
+//! | Ephemeral | every tick | Checkerboard | Short-lived pixels aging out |
 //! | Burning | every Nth tick | Checkerboard | Fire spread, ash transformation |
 //! | Heat | every Mth tick | Sequential | Heat diffusion on downsampled grid |
+//! | Evaporation | every Mth tick | Checkerboard | Liquids vanishing at the air interface |
+//! | Absorption | every Mth tick | Checkerboard | Sponges soaking up and releasing liquid |
+//! | Variants | every Mth tick | Checkerboard | Color variants by neighbor material |
+//! | Mixing | every Mth tick | Checkerboard | Colors blending where different liquids touch |
+//! | Depth | every Mth tick | Checkerboard | Liquid depth below the surface, shaded on the GPU |
 
+pub(crate) mod absorption;
 pub(crate) mod burning;
 mod config;
+pub(crate) mod depth;
+pub(crate) mod ephemeral;
+pub(crate) mod evaporation;
+pub(crate) mod foam;
+mod gravity;
 pub(crate) mod hash;
 mod heat;
+pub(crate) mod mixing;
 pub(crate) mod physics;
+pub(crate) mod variants;
+pub(crate) mod wetness;
+mod wind;
 
 use std::collections::HashSet;
 use std::sync::Mutex;
 
+use absorption::AbsorptionContext;
 use burning::BurningContext;
-pub use config::SimulationConfig;
+pub use config::{SimulationConfig, TickPacing};
+use evaporation::EvaporationContext;
+use foam::FoamContext;
+pub(crate) use gravity::collect_gravity_wells;
+pub use gravity::{GravityWell, GravityWells};
 use hash::hash21uu64;
 pub use heat::HeatConfig;
+use mixing::MixingContext;
+use wetness::WetnessContext;
+pub use wind::Wind;
 
 use crate::pixel_world::coords::{
   ChunkPos, Phase, TILE_SIZE, TILES_PER_CHUNK, TilePos, WINDOW_HEIGHT, WINDOW_WIDTH, WorldRect,
@@ -33,8 +61,12 @@ use crate::pixel_world::coords::{
 use crate::pixel_world::debug_shim::DebugGizmos;
 use crate::pixel_world::diagnostics::profile;
 use crate::pixel_world::material::Materials;
-use crate::pixel_world::scheduling::blitter::{Canvas, parallel_burning, parallel_simulate};
-use crate::pixel_world::world::PixelWorld;
+use crate::pixel_world::scheduling::blitter::{
+  Canvas, parallel_absorption, parallel_burning, parallel_depth, parallel_ephemeral,
+  parallel_evaporation, parallel_foam, parallel_mixing, parallel_simulate, parallel_variants,
+  parallel_wetness,
+};
+use crate::pixel_world::world::{PixelWorld, in_thread_pool};
 
 /// Context passed to simulation rules for deterministic randomness.
 #[derive(Clone, Copy)]
@@ -49,22 +81,71 @@ pub struct SimContext {
   pub jitter_y: i64,
 }
 
+/// Per-tick state shared by the steps of one simulation tick.
+///
+/// Created by [`begin_tick`] and passed to each step, so jitter and tile
+/// phases are computed once per tick even when the steps run as separate
+/// systems.
+pub(crate) struct TickPlan {
+  ctx: SimContext,
+  tiles_by_phase: [Vec<TilePos>; 4],
+}
+
 /// Runs one simulation tick on the world using parallel tile processing.
 ///
-/// Orchestrates three simulation passes at different tick rates:
+/// Orchestrates eleven simulation passes at different tick rates:
 /// - Physics (every tick): Pixel swaps using dirty rects
+/// - Wetness (every tick): Wetting and drying of powders using dirty rects
+/// - Foam (every tick): Foam on disturbed liquid surfaces using dirty rects
+/// - Ephemeral (every tick): Aging of short-lived pixels using dirty rects
 /// - Burning (every Nth tick): Fire spread using dirty rects
 /// - Heat (every Mth tick): Heat diffusion on downsampled grid
+/// - Evaporation (every Mth tick): Liquids at the air interface vanishing
+/// - Absorption (every Mth tick): Sponges soaking up and releasing liquid
+/// - Variants (every Mth tick): Cosmetic recoloring by neighbor material
+/// - Mixing (every Mth tick): Colors blending where different liquids touch
+/// - Depth (every Mth tick): Liquid depth below the surface for shading
+///
+/// Physics falls toward the wells in `gravity` where they override the
+/// default down direction, and gases and ephemeral pixels drift with `wind`.
+///
+/// The plugin runs the same steps as separate systems in
+/// [`CATickStep`](crate::pixel_world::CATickStep) sets.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all, fields(tick = world.tick())))]
 pub fn simulate_tick(
   world: &mut PixelWorld,
   materials: &Materials,
+  gravity: &GravityWells,
+  wind: &Wind,
   debug_gizmos: DebugGizmos<'_>,
   sim_config: &SimulationConfig,
   heat_config: &HeatConfig,
 ) {
   let _span = profile("simulate_tick");
 
+  let plan = begin_tick(world);
+  step_physics(
+    world,
+    &plan,
+    materials,
+    gravity,
+    wind,
+    debug_gizmos,
+    sim_config,
+  );
+  step_burning(world, &plan, materials, sim_config, heat_config);
+  step_heat(
+    world,
+    &plan,
+    materials,
+    debug_gizmos,
+    sim_config,
+    heat_config,
+  );
+}
+
+/// Computes the per-tick context and advances the world's tick counter.
+pub(crate) fn begin_tick(world: &mut PixelWorld) -> TickPlan {
   // Get context before borrowing chunks
   let center = world.center();
   let tick = world.tick();
@@ -89,12 +170,34 @@ pub fn simulate_tick(
   let simulation_bounds = world.simulation_bounds();
   let tiles_by_phase = {
     let _span = profile("collect_tiles");
-    collect_tiles_by_phase(center, simulation_bounds)
+    let mut phases = collect_tiles_by_phase(center, simulation_bounds);
+    add_kept_alive_tiles(&mut phases, world.kept_alive_chunks());
+    #[cfg(debug_assertions)]
+    if let Err(conflict) = crate::pixel_world::scheduling::validate_phase_partition(&phases) {
+      panic!("Checkerboard invariant violated: {conflict}");
+    }
+    phases
   };
 
   // Increment tick for next frame
   world.increment_tick();
 
+  TickPlan {
+    ctx,
+    tiles_by_phase,
+  }
+}
+
+/// Runs `f` over a canvas of the world's seeded chunks, then marks the
+/// chunks it reports dirty for GPU upload.
+///
+/// `f` runs on the world's dedicated thread pool when one is configured.
+fn with_canvas(
+  world: &mut PixelWorld,
+  f: impl FnOnce(&Canvas<'_>, &Mutex<HashSet<ChunkPos>>) + Send,
+) {
+  let thread_pool = world.thread_pool();
+
   // Collect seeded chunks for parallel access
   let chunks_map = {
     let _span = profile("collect_chunks");
@@ -106,27 +209,116 @@ pub fn simulate_tick(
 
   let chunk_access = Canvas::new(chunks_map);
   let dirty = Mutex::new(HashSet::new());
+  in_thread_pool(thread_pool.as_deref(), || f(&chunk_access, &dirty));
 
-  // === Pass 1: Physics simulation (every tick, ~60 TPS) ===
-  {
-    let _span = profile("physics");
-    parallel_simulate(
-      &chunk_access,
-      tiles_by_phase.clone(),
-      |pos, chunks| physics::compute_swap(pos, chunks, materials, ctx),
-      &dirty,
-      debug_gizmos,
-      ctx.tick,
-      (jitter_x, jitter_y),
-    );
+  // Drop canvas before using world again
+  drop(chunk_access);
+
+  // Mark dirty chunks for GPU upload
+  for pos in dirty.into_inner().unwrap() {
+    world.mark_dirty(pos);
   }
+}
+
+/// Physics, wetness, foam and ephemeral passes (every tick).
+pub(crate) fn step_physics(
+  world: &mut PixelWorld,
+  plan: &TickPlan,
+  materials: &Materials,
+  gravity: &GravityWells,
+  wind: &Wind,
+  debug_gizmos: DebugGizmos<'_>,
+  sim_config: &SimulationConfig,
+) {
+  let ctx = plan.ctx;
+  let jitter = (ctx.jitter_x, ctx.jitter_y);
+  let wind = wind.at(ctx.seed, ctx.tick);
+
+  with_canvas(world, |chunk_access, dirty| {
+    // === Pass 1: Physics simulation (every tick, ~60 TPS) ===
+    {
+      let _span = profile("physics");
+      parallel_simulate(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        |pos, chunks| {
+          let target = physics::compute_swap(pos, chunks, materials, gravity, wind, ctx)?;
+          // Moving liquid carries the foam flag to its target
+          foam::mark_disturbed(chunks, pos, materials);
+          ephemeral::leave_trail(chunks, pos, target, materials);
+          Some(target)
+        },
+        dirty,
+        debug_gizmos,
+        ctx.tick,
+        jitter,
+      );
+    }
 
-  // Compute tick intervals from TPS ratios
+    // === Pass 2: Wetness (every tick) ===
+    {
+      let _span = profile("wetness");
+      let wet_ctx = WetnessContext {
+        materials,
+        ctx,
+        dry_chance: sim_config.dry_chance_per_tick(),
+      };
+      parallel_wetness(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        &wet_ctx,
+        dirty,
+        jitter,
+      );
+    }
+
+    // === Pass 3: Foam (every tick) ===
+    {
+      let _span = profile("foam");
+      let foam_ctx = FoamContext {
+        materials,
+        ctx,
+        decay_chance: sim_config.foam_decay_chance_per_tick(),
+      };
+      parallel_foam(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        &foam_ctx,
+        dirty,
+        jitter,
+      );
+    }
+
+    // === Pass 4: Ephemeral aging (every tick) ===
+    if materials.any_ephemeral() {
+      let _span = profile("ephemeral");
+      parallel_ephemeral(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        materials,
+        dirty,
+        jitter,
+      );
+    }
+  });
+}
+
+/// Burning pass (every Nth tick, ~20 TPS).
+pub(crate) fn step_burning(
+  world: &mut PixelWorld,
+  plan: &TickPlan,
+  materials: &Materials,
+  sim_config: &SimulationConfig,
+  heat_config: &HeatConfig,
+) {
+  let ctx = plan.ctx;
   let burning_interval = (sim_config.physics_tps / sim_config.burning_tps).round() as u64;
-  let heat_interval = (sim_config.physics_tps / sim_config.heat_tps).round() as u64;
+  if !ctx.tick.is_multiple_of(burning_interval) {
+    return;
+  }
 
-  // === Pass 2: Burning propagation (every Nth tick, ~20 TPS) ===
-  if tick.is_multiple_of(burning_interval) {
+  with_canvas(world, |chunk_access, dirty| {
+    // === Pass 5: Burning propagation ===
     let _span = profile("burning");
     let burning_ctx = BurningContext {
       materials,
@@ -136,36 +328,125 @@ pub fn simulate_tick(
       ash_chance: heat_config.ash_chance_per_tick(sim_config.burning_tps),
     };
     parallel_burning(
-      &chunk_access,
-      tiles_by_phase,
+      chunk_access,
+      plan.tiles_by_phase.clone(),
       &burning_ctx,
-      &dirty,
-      (jitter_x, jitter_y),
+      dirty,
+      (ctx.jitter_x, ctx.jitter_y),
     );
-  }
+  });
+}
 
-  // === Pass 3: Heat propagation (every Mth tick) ===
-  // Operates on downsampled heat grid, no checkerboard needed
-  let chunk_positions: Vec<ChunkPos> = chunk_access.positions().collect();
-  if tick.is_multiple_of(heat_interval) {
-    let _span = profile("heat");
-    heat::propagate_heat(
-      &chunk_access,
-      &chunk_positions,
-      materials,
-      heat_config,
-      debug_gizmos,
-    );
-    heat::ignite_from_heat(&chunk_access, &chunk_positions, materials);
+/// Heat, evaporation, absorption, variant, depth and mixing passes (every
+/// Mth tick).
+pub(crate) fn step_heat(
+  world: &mut PixelWorld,
+  plan: &TickPlan,
+  materials: &Materials,
+  debug_gizmos: DebugGizmos<'_>,
+  sim_config: &SimulationConfig,
+  heat_config: &HeatConfig,
+) {
+  let heat_interval = (sim_config.physics_tps / sim_config.heat_tps).round() as u64;
+  if !plan.ctx.tick.is_multiple_of(heat_interval) {
+    return;
   }
 
-  // Drop canvas before using world again
-  drop(chunk_access);
+  let ctx = plan.ctx;
+  with_canvas(world, |chunk_access, dirty| {
+    // === Pass 6: Heat propagation ===
+    // Operates on downsampled heat grid, no checkerboard needed
+    {
+      let _span = profile("heat");
+      let chunk_positions: Vec<ChunkPos> = chunk_access.positions().collect();
+      heat::propagate_heat(
+        chunk_access,
+        &chunk_positions,
+        materials,
+        heat_config,
+        debug_gizmos,
+      );
+      heat::ignite_from_heat(chunk_access, &chunk_positions, materials);
+    }
 
-  // Mark dirty chunks for GPU upload
-  for pos in dirty.into_inner().unwrap() {
-    world.mark_dirty(pos);
-  }
+    // === Pass 7: Evaporation ===
+    // Reads the heat just propagated
+    if materials.any_evaporates() {
+      let _span = profile("evaporation");
+      let evap_ctx = EvaporationContext {
+        materials,
+        ctx,
+        heat_tps: sim_config.heat_tps,
+      };
+      parallel_evaporation(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        &evap_ctx,
+        dirty,
+        (ctx.jitter_x, ctx.jitter_y),
+      );
+    }
+
+    // === Pass 8: Absorption ===
+    // Before variants and depth, so they see the liquid it moved
+    if materials.any_absorbent() {
+      let _span = profile("absorption");
+      let abs_ctx = AbsorptionContext {
+        materials,
+        ctx,
+        heat_tps: sim_config.heat_tps,
+      };
+      parallel_absorption(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        &abs_ctx,
+        dirty,
+        (ctx.jitter_x, ctx.jitter_y),
+      );
+    }
+
+    // === Pass 9: Context variants ===
+    // Cosmetic, sees the pixels left by every other pass
+    if materials.any_variants() {
+      let _span = profile("variants");
+      parallel_variants(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        materials,
+        dirty,
+        (ctx.jitter_x, ctx.jitter_y),
+      );
+    }
+
+    // === Pass 10: Liquid color mixing ===
+    // Before depth, so a pixel reaching an interface starts from its ramp color
+    if materials.any_color_mix() {
+      let _span = profile("mixing");
+      let mix_ctx = MixingContext {
+        materials,
+        heat_tps: sim_config.heat_tps,
+      };
+      parallel_mixing(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        &mix_ctx,
+        dirty,
+        (ctx.jitter_x, ctx.jitter_y),
+      );
+    }
+
+    // === Pass 11: Liquid depth ===
+    if materials.any_depth_ramp() {
+      let _span = profile("depth");
+      parallel_depth(
+        chunk_access,
+        plan.tiles_by_phase.clone(),
+        materials,
+        dirty,
+        (ctx.jitter_x, ctx.jitter_y),
+      );
+    }
+  });
 }
 
 /// Collects tiles grouped by phase for the current visible region.
@@ -215,3 +496,21 @@ fn collect_tiles_by_phase(center: ChunkPos, bounds: Option<WorldRect>) -> [Vec<T
 
   phases
 }
+
+/// Adds every tile of chunks kept alive outside the streaming window.
+///
+/// These chunks are off-camera by definition, so simulation bounds do not
+/// apply to them.
+fn add_kept_alive_tiles(phases: &mut [Vec<TilePos>; 4], chunks: impl Iterator<Item = ChunkPos>) {
+  let tiles_per_chunk = TILES_PER_CHUNK as i64;
+  for chunk in chunks {
+    let min_tx = chunk.x as i64 * tiles_per_chunk;
+    let min_ty = chunk.y as i64 * tiles_per_chunk;
+    for tile_y in min_ty..min_ty + tiles_per_chunk {
+      for tile_x in min_tx..min_tx + tiles_per_chunk {
+        let tile = TilePos::new(tile_x, tile_y);
+        phases[Phase::from_tile(tile).index()].push(tile);
+      }
+    }
+  }
+}
@@ -1,20 +1,107 @@
 //! Per-pixel physics simulation.
 //!
 //! Implements movement behavior for different material states (powder, liquid,
-//! gas).
+//! gas). Solid pixels flagged `LOOSE` move like powder. Movement is relative
+//! to the local direction of gravity, which is down outside gravity wells.
+//! Gases only move with the wind, which also pushes falling ephemeral pixels
+//! sideways.
+//!
+//! Each pixel first proposes a target. When a higher-priority pixel (see
+//! `Material::swap_priority`) proposes the same target, the lower-priority
+//! one waits a tick, so contention resolves the same way whichever pixel is
+//! visited first.
+
+use bevy::math::Vec2;
 
 use super::SimContext;
+use super::gravity::{GravityWells, step};
 use super::hash::hash41uu64;
 use crate::pixel_world::coords::WorldPos;
 use crate::pixel_world::material::{Materials, PhysicsState};
-use crate::pixel_world::pixel::Pixel;
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
 use crate::pixel_world::scheduling::blitter::Canvas;
 
+/// Offsets from a target cell to the pixels that can move into it in one
+/// tick: falling from above, sliding down diagonally and flowing sideways.
+const CONTENDERS: [(i64, i64); 5] = [(0, 1), (-1, 1), (1, 1), (-1, 0), (1, 0)];
+
+/// Offsets to every neighbor, the contenders when gravity wells or wind may
+/// move pixels in any direction.
+const NEIGHBORS: [(i64, i64); 8] = [
+  (-1, -1),
+  (0, -1),
+  (1, -1),
+  (-1, 0),
+  (1, 0),
+  (-1, 1),
+  (0, 1),
+  (1, 1),
+];
+
 /// Returns the position to swap with, or None if pixel stays.
+///
+/// Yields the proposed target to a higher-priority contender that proposes
+/// it too. `wind` is the wind velocity this tick.
 pub fn compute_swap(
   pos: WorldPos,
   chunks: &Canvas<'_>,
   materials: &Materials,
+  gravity: &GravityWells,
+  wind: Vec2,
+  ctx: SimContext,
+) -> Option<WorldPos> {
+  let Some(target) = propose_swap(pos, chunks, materials, gravity, wind, ctx) else {
+    hold_in_wind(pos, chunks, materials, wind);
+    return None;
+  };
+
+  let priority = materials
+    .get(get_pixel(chunks, pos)?.material)
+    .swap_priority;
+  if priority < materials.max_swap_priority()
+    && is_outranked(pos, target, priority, chunks, materials, gravity, wind, ctx)
+  {
+    return None;
+  }
+
+  Some(target)
+}
+
+/// Returns true if a pixel next to `target` with a priority above
+/// `priority` proposes to move into it too.
+#[allow(clippy::too_many_arguments)]
+fn is_outranked(
+  pos: WorldPos,
+  target: WorldPos,
+  priority: u8,
+  chunks: &Canvas<'_>,
+  materials: &Materials,
+  gravity: &GravityWells,
+  wind: Vec2,
+  ctx: SimContext,
+) -> bool {
+  let contenders: &[(i64, i64)] = if gravity.is_empty() && wind.y == 0.0 {
+    &CONTENDERS
+  } else {
+    &NEIGHBORS
+  };
+  contenders.iter().any(|&(dx, dy)| {
+    let other = WorldPos::new(target.x + dx, target.y + dy);
+    other != pos
+      && get_pixel(chunks, other).is_some_and(|pixel| {
+        !pixel.is_void() && materials.get(pixel.material).swap_priority > priority
+      })
+      && propose_swap(other, chunks, materials, gravity, wind, ctx) == Some(target)
+  })
+}
+
+/// Returns the position the pixel would move to, ignoring contention.
+fn propose_swap(
+  pos: WorldPos,
+  chunks: &Canvas<'_>,
+  materials: &Materials,
+  gravity: &GravityWells,
+  wind: Vec2,
   ctx: SimContext,
 ) -> Option<WorldPos> {
   let pixel = get_pixel(chunks, pos)?;
@@ -25,24 +112,122 @@ pub fn compute_swap(
   }
 
   let material = materials.get(pixel.material);
+  if material.state == PhysicsState::Gas {
+    return compute_gas_swap(pos, chunks, materials, material.density, wind, ctx);
+  }
+  let falls = match material.state {
+    PhysicsState::Solid => pixel.flags.contains(PixelFlags::LOOSE),
+    PhysicsState::Powder | PhysicsState::Liquid => true,
+    PhysicsState::Gas => false,
+  };
+  if !falls {
+    return None;
+  }
+
+  // Weightless pixels stay put
+  let down = gravity.direction_at(pos)?;
+
+  // Ephemeral pixels are light enough for the wind to push them aside
+  let gust = if material.lifetime > 0 {
+    wind_turn(pos, down, wind.x, ctx)
+  } else {
+    0
+  };
 
   match material.state {
-    PhysicsState::Solid => None,
-    PhysicsState::Powder => compute_powder_swap(pos, chunks, materials, ctx),
-    PhysicsState::Liquid => compute_liquid_swap(pos, chunks, materials, ctx),
-    PhysicsState::Gas => None,
+    PhysicsState::Liquid => compute_liquid_swap(pos, chunks, materials, down, gust, ctx),
+    _ => compute_powder_swap(pos, chunks, materials, down, gust, ctx),
   }
 }
 
 // Hash channels for independent random streams
 const CH_AIR_RESISTANCE: u64 = 0x9e37_79b9_7f4a_7c15;
 const CH_AIR_DRIFT: u64 = 0x3c6e_f372_fe94_f82a;
+const CH_WIND_X: u64 = 0x5be0_cd19_137e_2179;
+const CH_WIND_Y: u64 = 0x1f83_d9ab_fb41_bd6b;
+
+/// Returns true with chance `|speed|` for the pixel at `pos` this tick.
+fn wind_roll(channel: u64, speed: f32, pos: WorldPos, ctx: SimContext) -> bool {
+  if speed == 0.0 {
+    return false;
+  }
+  let hash = hash41uu64(ctx.seed ^ channel, ctx.tick, pos.x as u64, pos.y as u64);
+  ((hash & 0xFFFF) as f32 / 65536.0) < speed.abs()
+}
+
+/// Returns the turn away from `down` that pushes a falling pixel at `pos`
+/// along the wind's horizontal speed this tick, or 0 if it is not pushed.
+fn wind_turn(pos: WorldPos, down: usize, speed: f32, ctx: SimContext) -> i64 {
+  if !wind_roll(CH_WIND_X, speed, pos, ctx) {
+    return 0;
+  }
+  let leeward = speed.signum() as i64;
+  [1, -1]
+    .into_iter()
+    .find(|&turn| step(pos, down, turn).x - pos.x == leeward)
+    .unwrap_or(0)
+}
+
+/// Keeps a gas pixel that missed its wind roll dirty while the wind could
+/// still move it, so its tile does not fall asleep between drifts.
+fn hold_in_wind(pos: WorldPos, chunks: &Canvas<'_>, materials: &Materials, wind: Vec2) {
+  if wind == Vec2::ZERO {
+    return;
+  }
+  let Some(pixel) = get_pixel(chunks, pos) else {
+    return;
+  };
+  let material = materials.get(pixel.material);
+  if pixel.is_void() || material.state != PhysicsState::Gas {
+    return;
+  }
+  let leeward = [
+    WorldPos::new(pos.x + wind.x.signum() as i64, pos.y),
+    WorldPos::new(pos.x, pos.y + wind.y.signum() as i64),
+  ];
+  if leeward
+    .into_iter()
+    .any(|target| target != pos && can_swap_into(chunks, materials, material.density, target))
+  {
+    let (chunk_pos, local) = pos.to_chunk_and_local();
+    if let Some(chunk) = chunks.get_mut(chunk_pos) {
+      chunk.mark_pixel_dirty(local.x as u32, local.y as u32);
+    }
+  }
+}
+
+/// Computes swap target for a gas pixel drifting with the wind.
+///
+/// Gases have no weight: each axis of the wind gets its own chance to move
+/// the pixel one step, horizontal first, into anything it can displace.
+fn compute_gas_swap(
+  pos: WorldPos,
+  chunks: &Canvas<'_>,
+  materials: &Materials,
+  density: u8,
+  wind: Vec2,
+  ctx: SimContext,
+) -> Option<WorldPos> {
+  let steps = [
+    (CH_WIND_X, wind.x, (wind.x.signum() as i64, 0)),
+    (CH_WIND_Y, wind.y, (0, wind.y.signum() as i64)),
+  ];
+  steps
+    .into_iter()
+    .filter(|&(channel, speed, _)| wind_roll(channel, speed, pos, ctx))
+    .map(|(_, _, (dx, dy))| WorldPos::new(pos.x + dx, pos.y + dy))
+    .find(|&target| can_swap_into(chunks, materials, density, target))
+}
 
 /// Computes swap target for powder (sand, soil) behavior.
+///
+/// A nonzero `gust` replaces air drift with a push turned that way.
 fn compute_powder_swap(
   pos: WorldPos,
   chunks: &Canvas<'_>,
   materials: &Materials,
+  down: usize,
+  gust: i64,
   ctx: SimContext,
 ) -> Option<WorldPos> {
   let src_pixel = get_pixel(chunks, pos)?;
@@ -70,7 +255,9 @@ fn compute_powder_swap(
   };
 
   // Air drift: 1/N chance to drift horizontally while falling
-  let drift: i64 = if src_material.air_drift > 0
+  let drift: i64 = if gust != 0 {
+    gust
+  } else if src_material.air_drift > 0
     && hash41uu64(
       ctx.seed ^ CH_AIR_DRIFT,
       ctx.tick,
@@ -84,14 +271,35 @@ fn compute_powder_swap(
     0
   };
 
-  try_fall_and_slide(pos, chunks, materials, src_density, drift, flip)
+  if let Some(target) = try_fall_and_slide(pos, chunks, materials, src_density, down, drift, flip) {
+    return Some(target);
+  }
+
+  // Wet powder slumps further than the dry angle of repose
+  if src_material.wet_slip > 0 && src_pixel.flags.contains(PixelFlags::WET) {
+    return try_wet_slide(
+      pos,
+      chunks,
+      materials,
+      src_density,
+      src_material.wet_slip,
+      down,
+      flip,
+    );
+  }
+
+  None
 }
 
 /// Computes swap target for liquid (water) behavior.
+///
+/// A nonzero `gust` replaces air drift with a push turned that way.
 fn compute_liquid_swap(
   pos: WorldPos,
   chunks: &Canvas<'_>,
   materials: &Materials,
+  down: usize,
+  gust: i64,
   ctx: SimContext,
 ) -> Option<WorldPos> {
   let src_pixel = get_pixel(chunks, pos)?;
@@ -119,7 +327,9 @@ fn compute_liquid_swap(
   };
 
   // Air drift: 1/N chance to drift horizontally while falling
-  let drift: i64 = if src_material.air_drift > 0
+  let drift: i64 = if gust != 0 {
+    gust
+  } else if src_material.air_drift > 0
     && hash41uu64(
       ctx.seed ^ CH_AIR_DRIFT,
       ctx.tick,
@@ -134,15 +344,15 @@ fn compute_liquid_swap(
   };
 
   // Try falling and diagonal sliding (shared with powder)
-  if let Some(target) = try_fall_and_slide(pos, chunks, materials, src_density, drift, flip) {
+  if let Some(target) = try_fall_and_slide(pos, chunks, materials, src_density, down, drift, flip) {
     return Some(target);
   }
 
   // Try horizontal flow (liquid-specific)
   let dispersion = src_material.dispersion;
   if dispersion > 0 {
-    let first_h = WorldPos::new(pos.x + flip, pos.y);
-    let second_h = WorldPos::new(pos.x - flip, pos.y);
+    let first_h = step(pos, down, 2 * flip);
+    let second_h = step(pos, down, -2 * flip);
 
     if can_swap_into(chunks, materials, src_density, first_h) {
       return Some(first_h);
@@ -159,33 +369,34 @@ fn compute_liquid_swap(
 ///
 /// This encapsulates the common movement logic shared between powder and
 /// liquid. The caller computes drift and flip based on material-specific
-/// behavior.
+/// behavior. `down` is the local direction of gravity.
 fn try_fall_and_slide(
   pos: WorldPos,
   chunks: &Canvas<'_>,
   materials: &Materials,
   src_density: u8,
+  down: usize,
   drift: i64,
   flip: i64,
 ) -> Option<WorldPos> {
-  let down = WorldPos::new(pos.x + drift, pos.y - 1);
+  let fall = step(pos, down, drift);
 
   // Try falling (possibly with horizontal drift)
-  if can_swap_into(chunks, materials, src_density, down) {
-    return Some(down);
+  if can_swap_into(chunks, materials, src_density, fall) {
+    return Some(fall);
   }
 
   // If drift failed, try straight down
   if drift != 0 {
-    let straight_down = WorldPos::new(pos.x, pos.y - 1);
+    let straight_down = step(pos, down, 0);
     if can_swap_into(chunks, materials, src_density, straight_down) {
       return Some(straight_down);
     }
   }
 
   // Try sliding diagonally
-  let first = WorldPos::new(pos.x + flip, pos.y - 1);
-  let second = WorldPos::new(pos.x - flip, pos.y - 1);
+  let first = step(pos, down, flip);
+  let second = step(pos, down, -flip);
 
   if can_swap_into(chunks, materials, src_density, first) {
     return Some(first);
@@ -197,6 +408,38 @@ fn try_fall_and_slide(
   None
 }
 
+/// Attempts a long diagonal slide for a wet powder pixel.
+///
+/// Reaches up to `1 + wet_slip` pixels sideways and one down, as long as the
+/// pixel's own row is free along the way. Piles of wet powder therefore
+/// settle at a shallower slope than the 45° of dry ones.
+fn try_wet_slide(
+  pos: WorldPos,
+  chunks: &Canvas<'_>,
+  materials: &Materials,
+  src_density: u8,
+  wet_slip: u8,
+  down: usize,
+  flip: i64,
+) -> Option<WorldPos> {
+  let fall = step(WorldPos::new(0, 0), down, 0);
+  for dir in [flip, -flip] {
+    let lateral = step(WorldPos::new(0, 0), down, 2 * dir);
+    for reach in 1..=wet_slip as i64 {
+      let side = WorldPos::new(pos.x + lateral.x * reach, pos.y + lateral.y * reach);
+      if !can_swap_into(chunks, materials, src_density, side) {
+        break;
+      }
+      let below = WorldPos::new(side.x + lateral.x + fall.x, side.y + lateral.y + fall.y);
+      if can_swap_into(chunks, materials, src_density, below) {
+        return Some(below);
+      }
+    }
+  }
+
+  None
+}
+
 /// Reads a pixel from chunks.
 #[inline]
 fn get_pixel(chunks: &Canvas<'_>, pos: WorldPos) -> Option<Pixel> {
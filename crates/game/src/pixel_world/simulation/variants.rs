@@ -0,0 +1,98 @@
+//! Context variants: cosmetic recoloring by neighbor materials.
+//!
+//! A pixel whose material has [`ContextVariant`] rules takes the color index
+//! of the first rule its neighbor matches, e.g. soil with air above it shows
+//! a grass color. Only color indices change, never materials or flags, so
+//! the pass has no effect on the simulation. Rules depend on neighbor
+//! materials alone, so the result is deterministic regardless of tile
+//! order.
+//!
+//! Pixels that stop matching keep their last color. Runs after the heat
+//! pass and scans whole tiles, since settled terrain sleeps outside dirty
+//! rects; the pass is skipped when no material has variants.
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::{ContextVariant, Materials, PhysicsState};
+use crate::pixel_world::pixel::PixelFlags;
+use crate::pixel_world::scheduling::blitter::Canvas;
+
+/// Returns true if the neighbor on the rule's side matches it.
+fn matches(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  rule: &ContextVariant,
+  materials: &Materials,
+) -> bool {
+  let (dx, dy) = rule.side.offset();
+  let (chunk_pos, local) = WorldPos::new(pos.x + dx, pos.y + dy).to_chunk_and_local();
+  canvas.get(chunk_pos).is_some_and(|chunk| {
+    let neighbor = chunk.pixels[(local.x as u32, local.y as u32)];
+    match rule.neighbor {
+      Some(material) => !neighbor.is_void() && neighbor.material == material,
+      None => neighbor.is_void() || materials.get(neighbor.material).state == PhysicsState::Gas,
+    }
+  })
+}
+
+/// Recolors the pixel at `pos` by the first of its material's rules that
+/// matches.
+fn process_variant_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  materials: &Materials,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+) {
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let lx = local.x as u32;
+  let ly = local.y as u32;
+
+  let Some(chunk) = canvas.get(chunk_pos) else {
+    return;
+  };
+
+  let pixel = chunk.pixels[(lx, ly)];
+  if pixel.is_void() || pixel.flags.contains(PixelFlags::PIXEL_BODY) {
+    return;
+  }
+
+  let Some(rule) = materials
+    .get(pixel.material)
+    .variants
+    .iter()
+    .find(|rule| matches(canvas, pos, rule, materials))
+  else {
+    return;
+  };
+  if pixel.color == rule.color {
+    return;
+  }
+
+  if let Some(chunk) = canvas.get_mut(chunk_pos) {
+    chunk.pixels[(lx, ly)].color = rule.color;
+  }
+  dirty_chunks.insert(chunk_pos);
+}
+
+/// Processes context variants for every pixel of a single tile.
+///
+/// Respects checkerboard scheduling for thread safety.
+pub fn process_tile_variants(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  jitter: (i64, i64),
+  materials: &Materials,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  for local_y in 0..tile_size {
+    for local_x in 0..tile_size {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_variant_pixel(canvas, pos, materials, dirty_chunks);
+    }
+  }
+}
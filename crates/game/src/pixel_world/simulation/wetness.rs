@@ -0,0 +1,123 @@
+//! Wetting and drying of powders.
+//!
+//! Powders touching a liquid take on the `WET` flag, which lets them slide
+//! further (see `Material::wet_slip`). Wet pixels out of contact with liquid
+//! dry probabilistically. Uses checkerboard scheduling and dirty rects like
+//! burning.
+//!
+//! Wet pixels keep their tile's dirty rect alive, so resting wet piles keep
+//! being visited until they dry.
+
+use std::collections::HashSet;
+
+use crate::pixel_world::coords::{ChunkPos, LocalPos, TILE_SIZE, TilePos, WorldPos};
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::PixelFlags;
+use crate::pixel_world::scheduling::blitter::Canvas;
+use crate::pixel_world::simulation::SimContext;
+use crate::pixel_world::simulation::hash::hash41uu64;
+
+/// Cardinal neighbor offsets.
+const CARDINAL: [(i64, i64); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Context for wetness simulation within a tile.
+pub struct WetnessContext<'a> {
+  pub materials: &'a Materials,
+  pub ctx: SimContext,
+  /// Per-tick probability of a wet pixel drying.
+  /// Derived from 1 / (dry_duration_secs * physics_tps).
+  pub dry_chance: f32,
+}
+
+/// Returns true if any cardinal neighbor of `pos` is a liquid.
+fn touches_liquid(canvas: &Canvas<'_>, pos: WorldPos, materials: &Materials) -> bool {
+  CARDINAL.iter().any(|&(dx, dy)| {
+    let (chunk_pos, local) = WorldPos::new(pos.x + dx, pos.y + dy).to_chunk_and_local();
+    canvas.get(chunk_pos).is_some_and(|chunk| {
+      let neighbor = chunk.pixels[(local.x as u32, local.y as u32)];
+      materials.get(neighbor.material).state == PhysicsState::Liquid
+    })
+  })
+}
+
+/// Wets or dries a single powder pixel.
+fn process_wet_pixel(
+  canvas: &Canvas<'_>,
+  pos: WorldPos,
+  wet_ctx: &WetnessContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  const CH_DRY: u64 = 0x5eed_d2e5_0a7e_0001;
+
+  let (chunk_pos, local) = pos.to_chunk_and_local();
+  let lx = local.x as u32;
+  let ly = local.y as u32;
+
+  let Some(chunk) = canvas.get(chunk_pos) else {
+    return;
+  };
+
+  let pixel = chunk.pixels[(lx, ly)];
+  let mat = wet_ctx.materials.get(pixel.material);
+  if mat.state != PhysicsState::Powder || mat.wet_slip == 0 {
+    return;
+  }
+
+  let was_wet = pixel.flags.contains(PixelFlags::WET);
+  let is_wet = if touches_liquid(canvas, pos, wet_ctx.materials) {
+    true
+  } else if was_wet {
+    let dry_hash = hash41uu64(
+      wet_ctx.ctx.seed ^ CH_DRY,
+      wet_ctx.ctx.tick,
+      pos.x as u64,
+      pos.y as u64,
+    );
+    let dry_roll = (dry_hash & 0xFFFF) as f32 / 65535.0;
+    dry_roll >= wet_ctx.dry_chance
+  } else {
+    false
+  };
+
+  if !was_wet && !is_wet {
+    return;
+  }
+
+  if was_wet != is_wet {
+    if let Some(chunk) = canvas.get_mut(chunk_pos) {
+      chunk.pixels[(lx, ly)].flags.set(PixelFlags::WET, is_wet);
+    }
+    dirty_chunks.insert(chunk_pos);
+  }
+
+  // Drying pixels may start sliding again, wet ones must keep being visited
+  dirty_pixels.push((chunk_pos, local));
+}
+
+/// Processes wetness for a single tile using dirty bounds.
+///
+/// Only processes pixels within the tile's dirty rect, respecting
+/// checkerboard scheduling for thread safety.
+pub fn process_tile_wetness(
+  canvas: &Canvas<'_>,
+  tile: TilePos,
+  bounds: (u8, u8, u8, u8),
+  jitter: (i64, i64),
+  wet_ctx: &WetnessContext<'_>,
+  dirty_chunks: &mut HashSet<ChunkPos>,
+  dirty_pixels: &mut Vec<(ChunkPos, LocalPos)>,
+) {
+  let tile_size = TILE_SIZE as i64;
+  let base_x = tile.x * tile_size + jitter.0;
+  let base_y = tile.y * tile_size + jitter.1;
+
+  let (min_x, min_y, max_x, max_y) = bounds;
+
+  for local_y in (min_y as i64)..=(max_y as i64) {
+    for local_x in (min_x as i64)..=(max_x as i64) {
+      let pos = WorldPos::new(base_x + local_x, base_y + local_y);
+      process_wet_pixel(canvas, pos, wet_ctx, dirty_chunks, dirty_pixels);
+    }
+  }
+}
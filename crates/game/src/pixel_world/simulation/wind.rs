@@ -0,0 +1,75 @@
+//! Wind.
+//!
+//! A global [`Wind`] blows gas pixels and ephemeral pixels (smoke, embers)
+//! sideways: gases drift along it through void, and falling ephemeral
+//! pixels are pushed toward it as they fall. All other pixels ignore it.
+//!
+//! The wind gusts around its mean velocity, following smooth noise over
+//! ticks keyed by the world seed, so a replay of the same ticks blows the
+//! same way.
+
+use bevy::prelude::*;
+
+use super::hash::hash21uu64;
+
+/// Hash channel of the gust noise.
+const CH_GUST: u64 = 0x71d0_5eed_9a3c_4b21;
+
+/// Global wind blowing gases and ephemeral pixels.
+///
+/// Speeds are chances per tick that an affected pixel drifts one pixel
+/// along an axis, so components are clamped to -1.0..=1.0 where used.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct Wind {
+  /// Mean wind velocity; +X blows right. Zero is calm.
+  pub velocity: Vec2,
+  /// How far gusts swing the speed around `velocity`, as a fraction of it
+  /// (0.0 = steady, 1.0 = between calm and twice as strong).
+  pub gustiness: f32,
+  /// Ticks between independent gust strengths; the wind eases smoothly
+  /// from one to the next.
+  pub gust_period: u32,
+}
+
+impl Default for Wind {
+  fn default() -> Self {
+    Self {
+      velocity: Vec2::ZERO,
+      gustiness: 0.5,
+      gust_period: 240,
+    }
+  }
+}
+
+impl Wind {
+  /// Creates a steady wind with the given velocity.
+  pub fn steady(velocity: Vec2) -> Self {
+    Self {
+      velocity,
+      gustiness: 0.0,
+      ..Default::default()
+    }
+  }
+
+  /// Returns true if the wind never blows.
+  pub fn is_calm(&self) -> bool {
+    self.velocity == Vec2::ZERO
+  }
+
+  /// Returns the wind velocity at `tick` of a world with the given seed.
+  pub fn at(&self, seed: u64, tick: u64) -> Vec2 {
+    if self.is_calm() || self.gustiness == 0.0 || self.gust_period == 0 {
+      return self.velocity;
+    }
+
+    let period = self.gust_period as u64;
+    let (cell, t) = (tick / period, (tick % period) as f32 / period as f32);
+    let noise = |cell: u64| {
+      let hash = hash21uu64(seed ^ CH_GUST, cell);
+      (hash & 0xFFFF) as f32 / 65535.0 * 2.0 - 1.0
+    };
+    let t = t * t * (3.0 - 2.0 * t);
+    let gust = noise(cell) + (noise(cell + 1) - noise(cell)) * t;
+    self.velocity * (1.0 + self.gustiness * gust)
+  }
+}
@@ -1,9 +1,11 @@
 //! Font rendering for surfaces.
 //!
 //! Uses `ab_glyph` for font rasterization. Provides functions to render text
-//! as a coverage mask and stamp it onto an [`RgbaSurface`].
+//! as a coverage mask and stamp it onto an [`RgbaSurface`], and to measure
+//! the mask's size up front for layout.
 
 use ab_glyph::{Font, FontRef, Glyph, PxScale, ScaleFont};
+use bevy::math::UVec2;
 
 use crate::pixel_world::primitives::RgbaSurface;
 use crate::pixel_world::render::Rgba;
@@ -30,6 +32,43 @@ impl CpuFont {
   pub fn from_bytes(data: &'static [u8]) -> Option<Self> {
     FontRef::try_from_slice(data).ok().map(|font| Self { font })
   }
+
+  /// Rasterizes text into a coverage mask, breaking lines at newlines and
+  /// wrapping them to `style.max_width`.
+  ///
+  /// Returns `None` if the text is empty or contains no renderable glyphs.
+  pub fn rasterize(&self, text: &str, style: &TextStyle) -> Option<TextMask> {
+    let scale = PxScale::from(style.font_scale);
+    let scaled_font = self.font.as_scaled(scale);
+    let glyphs = layout_text(&scaled_font, text, scale, style);
+    let (min_x, min_y, max_x, max_y) = compute_glyph_bounds(&scaled_font, &glyphs)?;
+
+    let width = (max_x - min_x) as u32;
+    let height = (max_y - min_y) as u32;
+    let data = rasterize_glyphs(&scaled_font, glyphs, min_x, min_y, width, height);
+
+    Some(TextMask {
+      data,
+      width,
+      height,
+    })
+  }
+
+  /// Returns the size of the mask [`rasterize`](Self::rasterize) would
+  /// produce, without rasterizing.
+  ///
+  /// Returns zero if the text has no renderable glyphs.
+  pub fn measure(&self, text: &str, style: &TextStyle) -> UVec2 {
+    let scale = PxScale::from(style.font_scale);
+    let scaled_font = self.font.as_scaled(scale);
+    let glyphs = layout_text(&scaled_font, text, scale, style);
+    match compute_glyph_bounds(&scaled_font, &glyphs) {
+      Some((min_x, min_y, max_x, max_y)) => {
+        UVec2::new((max_x - min_x) as u32, (max_y - min_y) as u32)
+      }
+      None => UVec2::ZERO,
+    }
+  }
 }
 
 /// A boolean coverage mask from rasterized text.
@@ -64,23 +103,91 @@ impl TextMask {
   }
 }
 
-/// Positions glyphs along the baseline for the given text.
+/// Positions glyphs along the baseline at `baseline` for one line of text.
 fn layout_glyphs<SF: ScaleFont<F>, F: Font>(
   scaled_font: &SF,
   text: &str,
   scale: PxScale,
   char_spacing: f32,
-) -> Vec<Glyph> {
-  let mut glyphs = Vec::new();
+  baseline: f32,
+  glyphs: &mut Vec<Glyph>,
+) {
   let mut cursor_x = 0.0f32;
 
   for ch in text.chars() {
     let glyph_id = scaled_font.glyph_id(ch);
-    let glyph =
-      glyph_id.with_scale_and_position(scale, ab_glyph::point(cursor_x, scaled_font.ascent()));
+    let glyph = glyph_id.with_scale_and_position(scale, ab_glyph::point(cursor_x, baseline));
     cursor_x += scaled_font.h_advance(glyph_id) + char_spacing;
     glyphs.push(glyph);
   }
+}
+
+/// Splits text into lines at newlines and, with `max_width`, at the last
+/// space that keeps a line's advance within it.
+///
+/// A word wider than `max_width` is left whole on its own line.
+fn wrap_lines<'t, SF: ScaleFont<F>, F: Font>(
+  scaled_font: &SF,
+  text: &'t str,
+  char_spacing: f32,
+  max_width: Option<u32>,
+) -> Vec<&'t str> {
+  let advance = |ch: char| scaled_font.h_advance(scaled_font.glyph_id(ch)) + char_spacing;
+  let mut lines = Vec::new();
+
+  for line in text.split('\n') {
+    let Some(max_width) = max_width else {
+      lines.push(line);
+      continue;
+    };
+
+    let mut start = 0;
+    let mut width = 0.0f32;
+    let mut last_space = None;
+    for (i, ch) in line.char_indices() {
+      if ch == ' ' {
+        last_space = Some(i);
+      }
+      width += advance(ch);
+      if width > max_width as f32
+        && ch != ' '
+        && let Some(space) = last_space
+      {
+        lines.push(&line[start..space]);
+        start = space + 1;
+        last_space = None;
+        width = line[start..i + ch.len_utf8()].chars().map(advance).sum();
+      }
+    }
+    lines.push(&line[start..]);
+  }
+
+  lines
+}
+
+/// Lays out every line of the text, one line height plus
+/// `style.line_spacing` apart.
+fn layout_text<SF: ScaleFont<F>, F: Font>(
+  scaled_font: &SF,
+  text: &str,
+  scale: PxScale,
+  style: &TextStyle,
+) -> Vec<Glyph> {
+  let line_height = scaled_font.height() + scaled_font.line_gap() + style.line_spacing;
+  let mut glyphs = Vec::new();
+
+  let lines = wrap_lines(scaled_font, text, style.char_spacing, style.max_width);
+  for (i, line) in lines.into_iter().enumerate() {
+    let baseline = scaled_font.ascent() + i as f32 * line_height;
+    layout_glyphs(
+      scaled_font,
+      line,
+      scale,
+      style.char_spacing,
+      baseline,
+      &mut glyphs,
+    );
+  }
 
   glyphs
 }
@@ -148,6 +255,7 @@ fn rasterize_glyphs<SF: ScaleFont<F>, F: Font>(
 /// - `font_scale`: Font size in pixels (e.g., 16.0 for 16px).
 /// - `char_spacing`: Extra spacing between characters in pixels.
 ///
+/// Lines break at newlines only; use [`CpuFont::rasterize`] to wrap.
 /// Returns `None` if the text is empty or contains no renderable glyphs.
 pub fn rasterize_text(
   font: &CpuFont,
@@ -155,39 +263,29 @@ pub fn rasterize_text(
   font_scale: f32,
   char_spacing: f32,
 ) -> Option<TextMask> {
-  if text.is_empty() {
-    return None;
-  }
-
-  let scale = PxScale::from(font_scale);
-  let scaled_font = font.font.as_scaled(scale);
-
-  let glyphs = layout_glyphs(&scaled_font, text, scale, char_spacing);
-  if glyphs.is_empty() {
-    return None;
-  }
-
-  let (min_x, min_y, max_x, max_y) = compute_glyph_bounds(&scaled_font, &glyphs)?;
-
-  let width = (max_x - min_x) as u32;
-  let height = (max_y - min_y) as u32;
-  let data = rasterize_glyphs(&scaled_font, glyphs, min_x, min_y, width, height);
-
-  Some(TextMask {
-    data,
-    width,
-    height,
-  })
+  font.rasterize(
+    text,
+    &TextStyle {
+      font_scale,
+      char_spacing,
+      ..TextStyle::default()
+    },
+  )
 }
 
 /// Text rendering style configuration.
 ///
-/// Bundles font scale, character spacing, and color for text rendering.
+/// Bundles font scale, spacing, wrapping, and color for text rendering.
 pub struct TextStyle {
   /// Font size in pixels (e.g., 16.0 for 16px).
   pub font_scale: f32,
   /// Extra spacing between characters in pixels.
   pub char_spacing: f32,
+  /// Extra spacing between lines in pixels.
+  pub line_spacing: f32,
+  /// Width in pixels to wrap lines at, breaking at spaces. `None` breaks
+  /// lines at newlines only.
+  pub max_width: Option<u32>,
   /// Color to use for the text.
   pub color: Rgba,
 }
@@ -197,6 +295,8 @@ impl Default for TextStyle {
     Self {
       font_scale: 16.0,
       char_spacing: 0.0,
+      line_spacing: 0.0,
+      max_width: None,
       color: Rgba::new(255, 255, 255, 255),
     }
   }
@@ -232,10 +332,10 @@ pub fn stamp_text(surface: &mut RgbaSurface, mask: &TextMask, x: i32, y: i32, co
 
 /// Renders text directly onto a surface.
 ///
-/// Convenience function combining [`rasterize_text`] and [`stamp_text`].
+/// Convenience function combining [`CpuFont::rasterize`] and [`stamp_text`].
 ///
 /// - `x, y`: Position of the bottom-left corner of the text.
-/// - `style`: Text style (font scale, spacing, color).
+/// - `style`: Text style (font scale, spacing, wrapping, color).
 pub fn draw_text(
   surface: &mut RgbaSurface,
   font: &CpuFont,
@@ -244,7 +344,7 @@ pub fn draw_text(
   y: i32,
   style: &TextStyle,
 ) {
-  if let Some(mask) = rasterize_text(font, text, style.font_scale, style.char_spacing) {
+  if let Some(mask) = font.rasterize(text, style) {
     stamp_text(surface, &mask, x, y, style.color);
   }
 }
@@ -1,3 +1,5 @@
 mod font;
+mod scrolling;
 
 pub use font::{CpuFont, TextMask, TextStyle, draw_text, rasterize_text, stamp_text};
+pub use scrolling::{ScrollingText, update_scrolling_text};
@@ -0,0 +1,167 @@
+//! Marquee signs stamped into the world as simulation pixels.
+//!
+//! A [`ScrollingText`] entity owns a world region. Each frame the text is
+//! scrolled right-to-left by its speed and re-stamped into the region after
+//! the pixels it stamped the previous frame are cleared. The text is only
+//! rasterized again when it or its font settings change.
+
+use bevy::prelude::*;
+
+use super::font::{CpuFont, TextMask, TextStyle, rasterize_text};
+use crate::pixel_world::coords::{MaterialId, WorldPos, WorldRect};
+use crate::pixel_world::debug_shim::DebugGizmos;
+use crate::pixel_world::palette::GlobalPalette;
+use crate::pixel_world::pixel::Pixel;
+use crate::pixel_world::pixel_body::find_best_material_color;
+use crate::pixel_world::world::PixelWorld;
+
+/// Text that scrolls through a world region, made of simulation pixels.
+///
+/// The text enters at the right edge of `region`, leaves at the left edge,
+/// and wraps once fully out of view. It is stamped along the bottom of the
+/// region using the material's shade closest to `style.color`, into the world
+/// set with [`in_world`](Self::in_world), or the only world if none is set.
+#[derive(Component)]
+pub struct ScrollingText {
+  /// Text to display.
+  pub text: String,
+  /// Font scale, spacing, and color.
+  pub style: TextStyle,
+  /// Material of the stamped pixels.
+  pub material: MaterialId,
+  /// Scroll speed in pixels per second.
+  pub speed: f32,
+  /// World region the text scrolls through.
+  pub region: WorldRect,
+  /// Distance scrolled since the text last entered the region.
+  offset: f32,
+  /// World the sign is stamped into; `None` uses the only world.
+  world: Option<Entity>,
+  /// Rasterized text and the text, scale, and spacing it was made from.
+  mask: Option<(String, f32, f32, TextMask)>,
+  /// Positions stamped last frame, cleared before the next stamp.
+  stamped: Vec<WorldPos>,
+}
+
+impl ScrollingText {
+  /// Creates a sign scrolling `text` through `region` at 32 pixels per
+  /// second with the default style.
+  pub fn new(text: impl Into<String>, material: MaterialId, region: WorldRect) -> Self {
+    Self {
+      text: text.into(),
+      style: TextStyle::default(),
+      material,
+      speed: 32.0,
+      region,
+      offset: 0.0,
+      world: None,
+      mask: None,
+      stamped: Vec::new(),
+    }
+  }
+
+  /// Sets the text style.
+  pub fn with_style(mut self, style: TextStyle) -> Self {
+    self.style = style;
+    self
+  }
+
+  /// Sets the scroll speed in pixels per second.
+  pub fn with_speed(mut self, speed: f32) -> Self {
+    self.speed = speed;
+    self
+  }
+
+  /// Stamps the sign into the world on `world` instead of the only world.
+  pub fn in_world(mut self, world: Entity) -> Self {
+    self.world = Some(world);
+    self
+  }
+
+  /// Returns the distance scrolled since the text last entered the region.
+  pub fn offset(&self) -> f32 {
+    self.offset
+  }
+}
+
+/// Returns whether `pos` lies inside `rect`.
+fn rect_contains(rect: WorldRect, pos: WorldPos) -> bool {
+  pos.x >= rect.x
+    && pos.x < rect.x + rect.width as i64
+    && pos.y >= rect.y
+    && pos.y < rect.y + rect.height as i64
+}
+
+/// Scrolls every [`ScrollingText`] and re-stamps it into its world.
+///
+/// Only the positions stamped the previous frame are cleared, and only
+/// where they still hold the sign's material, so other content passing
+/// through the sign is left alone.
+pub fn update_scrolling_text(
+  time: Res<Time>,
+  palette: Option<Res<GlobalPalette>>,
+  mut font: Local<Option<CpuFont>>,
+  mut worlds: Query<&mut PixelWorld>,
+  mut signs: Query<&mut ScrollingText>,
+) {
+  let Some(palette) = palette else { return };
+  let font = font.get_or_insert_with(CpuFont::default_font);
+
+  for mut sign in signs.iter_mut() {
+    let world = match sign.world {
+      Some(entity) => worlds.get_mut(entity).ok(),
+      None => worlds.single_mut().ok(),
+    };
+    let Some(mut world) = world else {
+      continue;
+    };
+    let sign = &mut *sign;
+
+    for pos in sign.stamped.drain(..) {
+      if world
+        .get_pixel(pos)
+        .is_some_and(|pixel| pixel.material == sign.material)
+      {
+        world.set_pixel(pos, Pixel::VOID, DebugGizmos::none());
+      }
+    }
+
+    let (scale, spacing) = (sign.style.font_scale, sign.style.char_spacing);
+    let stale = sign
+      .mask
+      .as_ref()
+      .is_none_or(|(text, s, c, _)| *text != sign.text || *s != scale || *c != spacing);
+    if stale {
+      sign.mask = rasterize_text(font, &sign.text, scale, spacing)
+        .map(|mask| (sign.text.clone(), scale, spacing, mask));
+    }
+    let Some((_, _, _, mask)) = &sign.mask else {
+      continue;
+    };
+
+    let period = (sign.region.width + mask.width()) as f32;
+    sign.offset = (sign.offset + sign.speed * time.delta_secs()).rem_euclid(period);
+
+    let color = sign.style.color;
+    let pixel = Pixel::new(
+      sign.material,
+      find_best_material_color(color.red, color.green, color.blue, sign.material, &palette),
+    );
+    let region = sign.region;
+    let left = region.x + region.width as i64 - sign.offset.floor() as i64;
+    let mask_height = mask.height() as i64;
+
+    for my in 0..mask.height() {
+      for mx in 0..mask.width() {
+        if !mask.get(mx, my) {
+          continue;
+        }
+        // Mask rows run top-down; world Y is up
+        let pos = WorldPos::new(left + mx as i64, region.y + mask_height - 1 - my as i64);
+        if rect_contains(region, pos) && world.set_pixel(pos, pixel, DebugGizmos::none()) {
+          sign.stamped.push(pos);
+        }
+      }
+    }
+  }
+}
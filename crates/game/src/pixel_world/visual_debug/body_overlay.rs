@@ -0,0 +1,95 @@
+//! Pixel body bounds and shape mask overlay.
+//!
+//! Draws each body's transformed AABB (the rect its pixels are blitted
+//! into), its entity origin and, optionally, its solid mask pixels. Useful
+//! when debugging erosion and splitting.
+
+use bevy::prelude::*;
+
+use super::colors;
+use super::settings::VisualDebugSettings;
+use crate::pixel_world::coords::WorldRect;
+use crate::pixel_world::pixel_body::{PixelBody, compute_world_aabb};
+
+/// Half-length of the origin cross arms, in pixels.
+const ORIGIN_ARM: f32 = 3.0;
+
+/// World-space geometry drawn by the overlay for one pixel body.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PixelBodyOverlay {
+  /// AABB of the transformed pixel grid, as computed for blitting.
+  pub aabb: WorldRect,
+  /// Position of the entity origin.
+  pub origin: Vec2,
+}
+
+impl PixelBodyOverlay {
+  /// Computes the overlay geometry of `body` at `transform`.
+  pub fn compute(body: &PixelBody, transform: &GlobalTransform) -> Self {
+    Self {
+      aabb: compute_world_aabb(body, transform),
+      origin: transform.translation().truncate(),
+    }
+  }
+
+  /// Returns the AABB corners counter-clockwise from the bottom-left.
+  pub fn aabb_corners(&self) -> [Vec2; 4] {
+    let min = Vec2::new(self.aabb.x as f32, self.aabb.y as f32);
+    let max = min + Vec2::new(self.aabb.width as f32, self.aabb.height as f32);
+    [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)]
+  }
+}
+
+/// Returns the world-space centers of the body's solid mask pixels.
+pub fn solid_pixel_centers<'a>(
+  body: &'a PixelBody,
+  transform: &'a GlobalTransform,
+) -> impl Iterator<Item = Vec2> + 'a {
+  (0..body.height())
+    .flat_map(move |y| (0..body.width()).map(move |x| (x, y)))
+    .filter(|&(x, y)| body.is_solid(x, y))
+    .map(|(x, y)| {
+      let local = Vec3::new(
+        body.origin.x as f32 + x as f32 + 0.5,
+        body.origin.y as f32 + y as f32 + 0.5,
+        0.0,
+      );
+      transform.transform_point(local).truncate()
+    })
+}
+
+/// Draws pixel body AABBs, origins and solid mask pixels.
+pub fn draw_pixel_body_overlay(
+  mut gizmos: Gizmos,
+  settings: Option<Res<VisualDebugSettings>>,
+  bodies: Query<(&PixelBody, &GlobalTransform)>,
+) {
+  let Some(settings) = settings else { return };
+  if !settings.show_pixel_body_bounds && !settings.show_pixel_body_masks {
+    return;
+  }
+
+  for (body, transform) in bodies.iter() {
+    if settings.show_pixel_body_bounds {
+      let overlay = PixelBodyOverlay::compute(body, transform);
+      let [a, b, c, d] = overlay.aabb_corners();
+      gizmos.linestrip_2d([a, b, c, d, a], colors::GOLD);
+
+      let (o, dx, dy) = (overlay.origin, Vec2::X * ORIGIN_ARM, Vec2::Y * ORIGIN_ARM);
+      gizmos.line_2d(o - dx, o + dx, colors::CORAL);
+      gizmos.line_2d(o - dy, o + dy, colors::CORAL);
+    }
+
+    if settings.show_pixel_body_masks {
+      let (_, rotation, _) = transform.to_scale_rotation_translation();
+      let angle = rotation.to_euler(EulerRot::ZYX).0;
+      for center in solid_pixel_centers(body, transform) {
+        gizmos.rect_2d(
+          Isometry2d::new(center, Rot2::radians(angle)),
+          Vec2::ONE,
+          colors::MINT,
+        );
+      }
+    }
+  }
+}
@@ -5,7 +5,7 @@ use std::sync::Mutex;
 use bevy::prelude::*;
 
 use super::colors;
-use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos, TILE_SIZE, TilePos, WorldRect};
+use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos, TilePos, WorldRect};
 use crate::pixel_world::primitives::{HEAT_CELL_SIZE, HEAT_CELLS_PER_TILE};
 
 /// Kind of debug gizmo with associated duration.
@@ -64,10 +64,9 @@ impl PendingGizmo {
 
   /// Creates a gizmo for a tile position.
   pub fn tile(pos: TilePos) -> Self {
-    let tile_size = TILE_SIZE as i64;
     Self {
       kind: GizmoKind::Tile,
-      rect: WorldRect::new(pos.x * tile_size, pos.y * tile_size, TILE_SIZE, TILE_SIZE),
+      rect: pos.to_world_rect(),
     }
   }
 
@@ -84,9 +83,9 @@ impl PendingGizmo {
   /// Takes the tile position and the dirty rect bounds (min_x, min_y, max_x,
   /// max_y) relative to the tile origin.
   pub fn dirty_rect(tile: TilePos, bounds: (u8, u8, u8, u8)) -> Self {
-    let tile_size = TILE_SIZE as i64;
-    let tile_origin_x = tile.x * tile_size;
-    let tile_origin_y = tile.y * tile_size;
+    let tile_rect = tile.to_world_rect();
+    let tile_origin_x = tile_rect.x;
+    let tile_origin_y = tile_rect.y;
 
     let (min_x, min_y, max_x, max_y) = bounds;
     let x = tile_origin_x + min_x as i64;
@@ -1,8 +1,10 @@
 //! Visual debug suite for pixel_world.
 //!
-//! Provides debug gizmo rendering for chunk updates, tile updates, and blit
-//! operations. Enable with the `visual-debug` feature flag.
+//! Provides debug gizmo rendering for chunk updates, tile updates, blit
+//! operations and pixel body bounds. Enable with the `visual-debug` feature
+//! flag.
 
+mod body_overlay;
 pub(super) mod colors;
 mod gizmos;
 pub mod persistence;
@@ -11,6 +13,8 @@ mod systems;
 mod ui;
 
 use bevy::prelude::*;
+use body_overlay::draw_pixel_body_overlay;
+pub use body_overlay::{PixelBodyOverlay, solid_pixel_centers};
 pub use gizmos::{ActiveGizmos, GizmoKind, PendingDebugGizmos, PendingGizmo};
 pub use persistence::SettingsPersistence;
 pub use settings::VisualDebugSettings;
@@ -31,6 +35,7 @@ impl Plugin for VisualDebugPlugin {
         (
           render_debug_gizmos,
           draw_pixel_body_centers,
+          draw_pixel_body_overlay,
           debug_persistence_keyboard,
         ),
       )
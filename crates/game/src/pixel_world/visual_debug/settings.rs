@@ -23,6 +23,10 @@ pub struct VisualDebugSettings {
   pub show_blit_rects: bool,
   /// Show red circles at pixel body centers.
   pub show_pixel_body_centers: bool,
+  /// Show pixel body AABBs and entity origins.
+  pub show_pixel_body_bounds: bool,
+  /// Highlight the solid shape mask pixels of pixel bodies.
+  pub show_pixel_body_masks: bool,
 }
 
 impl VisualDebugSettings {
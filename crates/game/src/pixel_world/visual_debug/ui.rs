@@ -27,6 +27,12 @@ pub fn visual_debug_checkboxes(ui: &mut egui::Ui, settings: &mut VisualDebugSett
   changed |= ui
     .checkbox(&mut settings.show_pixel_body_centers, "Pixel body centers")
     .changed();
+  changed |= ui
+    .checkbox(&mut settings.show_pixel_body_bounds, "Pixel body bounds")
+    .changed();
+  changed |= ui
+    .checkbox(&mut settings.show_pixel_body_masks, "Pixel body masks")
+    .changed();
 
   changed
 }
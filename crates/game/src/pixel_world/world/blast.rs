@@ -5,6 +5,11 @@
 //! energy consumption and pixel replacement, making the ray-march
 //! infrastructure reusable for different blast behaviors.
 //!
+//! A [`BlastWave`] runs the same ray-march as an expanding shockwave: each
+//! call to `advance_blast_wave` extends every ray a little further, carrying
+//! its remaining energy over, so a large blast is spread across frames
+//! without changing what it destroys.
+//!
 //! # Parallelization Strategy
 //!
 //! All operations use parallel iteration via rayon:
@@ -30,7 +35,8 @@ pub struct BlastParams {
   pub strength: f32,
   /// Maximum ray length in pixels.
   pub max_radius: f32,
-  /// Radius for heat injection (smooth parabolic falloff).
+  /// Radius for heat injection (smooth parabolic falloff). 0 injects no
+  /// heat.
   pub heat_radius: f32,
 }
 
@@ -40,10 +46,66 @@ pub enum BlastHit {
   Skip,
   /// Replace pixel and consume energy. Ray stops if energy drops to zero.
   Hit { pixel: Pixel, cost: f32 },
+  /// Replace pixel and consume `resistance` energy, but only if the ray
+  /// still carries at least that much. Otherwise the pixel is left intact
+  /// and the ray stops, shielding everything behind it.
+  Resist { pixel: Pixel, resistance: f32 },
   /// Stop the ray immediately.
   Stop,
 }
 
+/// A blast that expands over several calls to
+/// [`PixelWorld::advance_blast_wave`].
+pub struct BlastWave {
+  params: BlastParams,
+  rays: Vec<WaveRay>,
+  /// Radius reached so far. Negative until the first advance.
+  radius: f32,
+}
+
+/// Progress of a single shockwave ray.
+#[derive(Clone, Copy)]
+struct WaveRay {
+  /// Energy left.
+  remaining: f32,
+  /// Next step to march.
+  next_step: i32,
+  /// Whether the ray has stopped.
+  stopped: bool,
+}
+
+impl BlastWave {
+  /// Creates a wave that has not reached any pixel yet.
+  pub fn new(params: BlastParams) -> Self {
+    let ray = WaveRay {
+      remaining: params.strength,
+      next_step: 0,
+      stopped: false,
+    };
+    Self {
+      rays: vec![ray; ray_count(params.max_radius)],
+      params,
+      radius: -1.0,
+    }
+  }
+
+  /// Returns the blast parameters.
+  pub fn params(&self) -> &BlastParams {
+    &self.params
+  }
+
+  /// Returns the radius the wave has reached, or 0 before the first
+  /// advance.
+  pub fn radius(&self) -> f32 {
+    self.radius.max(0.0)
+  }
+
+  /// Returns true once the wave has reached its maximum radius.
+  pub fn is_complete(&self) -> bool {
+    self.radius >= self.params.max_radius
+  }
+}
+
 /// A mutation collected during the parallel compute phase.
 struct BlastMutation {
   pos: WorldPos,
@@ -103,26 +165,89 @@ impl PixelWorld {
     };
 
     // Mark dirty chunks (after Canvas dropped)
+    self.mark_blasted(dirty_chunks);
+
+    // Awaken boundary pixels for all blasts
+    for params in blasts {
+      self.awaken_boundary_pixels(params.center, params.max_radius);
+    }
+  }
+
+  /// Extends a [`BlastWave`] by `step` pixels, up to its maximum radius.
+  ///
+  /// Each ray resumes where the previous advance left it, with the energy it
+  /// had left, so the union of all advances matches a single
+  /// [`blast`](Self::blast) with the same parameters. Heat is injected on
+  /// the first advance; pixels at the new wave front are awakened.
+  pub fn advance_blast_wave<F>(&mut self, wave: &mut BlastWave, step: f32, f: F)
+  where
+    F: Fn(&Pixel, WorldPos) -> BlastHit + Sync,
+  {
+    if wave.is_complete() {
+      return;
+    }
+    let first = wave.radius < 0.0;
+    let radius = (wave.radius() + step.max(1.0)).min(wave.params.max_radius);
+    let params = &wave.params;
+
+    let dirty_chunks = {
+      let chunks = self.collect_seeded_chunks();
+      let canvas = Canvas::new(chunks);
+
+      let num_rays = wave.rays.len();
+      let mutations: Vec<BlastMutation> = wave
+        .rays
+        .par_iter_mut()
+        .enumerate()
+        .flat_map(|(ray_idx, ray)| {
+          let mut hits = Vec::new();
+          if ray.stopped {
+            return hits;
+          }
+          let end = radius as i32;
+          let dir = ray_direction(ray_idx, num_rays);
+          ray.stopped = !march_ray(
+            params.center,
+            dir,
+            ray.next_step..=end,
+            &mut ray.remaining,
+            &f,
+            &canvas,
+            &mut hits,
+          );
+          ray.next_step = end + 1;
+          hits
+        })
+        .collect();
+
+      let dirty = apply_mutations_parallel(&canvas, mutations);
+      if first {
+        apply_heat_parallel(&canvas, compute_heat_values(params, &canvas));
+      }
+
+      dirty
+    };
+
+    self.mark_blasted(dirty_chunks);
+    self.awaken_boundary_pixels(wave.params.center, radius);
+    wave.radius = radius;
+  }
+
+  /// Marks chunks changed by a blast for upload, saving and caches.
+  fn mark_blasted(&mut self, dirty_chunks: Vec<ChunkPos>) {
     for pos in dirty_chunks {
       if let Some(idx) = self.pool.index_for(pos) {
         let slot = self.pool.get_mut(idx);
         slot.dirty = true;
         slot.modified = true;
         slot.persisted = false;
+        self.pool.bump_generation(idx);
       }
     }
-
-    // Awaken boundary pixels for all blasts
-    for params in blasts {
-      self.awaken_boundary_pixels(params);
-    }
   }
 
   /// Awaken boundary pixels so exposed material falls/flows.
-  fn awaken_boundary_pixels(&mut self, params: &BlastParams) {
-    let center = params.center;
-    let radius = params.max_radius;
-
+  fn awaken_boundary_pixels(&mut self, center: Vec2, radius: f32) {
     for ray_idx in 0..(2.0 * std::f32::consts::PI * (radius + 2.0)).ceil() as usize {
       let angle = 2.0 * std::f32::consts::PI * ray_idx as f32
         / (2.0 * std::f32::consts::PI * (radius + 2.0)).ceil();
@@ -136,60 +261,108 @@ impl PixelWorld {
   }
 }
 
+/// Returns the number of rays cast for a blast of `radius`.
+fn ray_count(radius: f32) -> usize {
+  (2.0 * std::f32::consts::PI * radius).ceil() as usize
+}
+
+/// Returns the unit direction of ray `ray_idx` out of `num_rays`.
+fn ray_direction(ray_idx: usize, num_rays: usize) -> Vec2 {
+  let angle = 2.0 * std::f32::consts::PI * ray_idx as f32 / num_rays as f32;
+  Vec2::new(angle.cos(), angle.sin())
+}
+
 /// Parallel ray march phase - collects mutations without modifying world.
 fn parallel_ray_march<F>(params: &BlastParams, f: &F, canvas: &Canvas<'_>) -> Vec<BlastMutation>
 where
   F: Fn(&Pixel, WorldPos) -> BlastHit + Sync,
 {
-  let center = params.center;
-  let radius = params.max_radius;
-  let r = radius as i32;
-  let num_rays = (2.0 * std::f32::consts::PI * radius).ceil() as usize;
+  let num_rays = ray_count(params.max_radius);
 
   (0..num_rays)
     .into_par_iter()
     .flat_map(|ray_idx| {
-      let angle = 2.0 * std::f32::consts::PI * ray_idx as f32 / num_rays as f32;
-      let dir_x = angle.cos();
-      let dir_y = angle.sin();
       let mut remaining = params.strength;
       let mut ray_hits = Vec::new();
+      march_ray(
+        params.center,
+        ray_direction(ray_idx, num_rays),
+        0..=params.max_radius as i32,
+        &mut remaining,
+        f,
+        canvas,
+        &mut ray_hits,
+      );
+      ray_hits
+    })
+    .collect()
+}
 
-      for step in 0..=r {
-        let wx = center.x as i64 + (dir_x * step as f32).round() as i64;
-        let wy = center.y as i64 + (dir_y * step as f32).round() as i64;
-        let pos = WorldPos::new(wx, wy);
+/// Marches one ray over `steps`, pushing the pixels it replaces to `hits`.
+///
+/// Returns false if the ray stopped, true if it ran through all steps and
+/// can be continued.
+fn march_ray<F>(
+  center: Vec2,
+  dir: Vec2,
+  steps: std::ops::RangeInclusive<i32>,
+  remaining: &mut f32,
+  f: &F,
+  canvas: &Canvas<'_>,
+  hits: &mut Vec<BlastMutation>,
+) -> bool
+where
+  F: Fn(&Pixel, WorldPos) -> BlastHit + Sync,
+{
+  for step in steps {
+    let wx = center.x as i64 + (dir.x * step as f32).round() as i64;
+    let wy = center.y as i64 + (dir.y * step as f32).round() as i64;
+    let pos = WorldPos::new(wx, wy);
 
-        let Some(pixel) = get_pixel_from_canvas(canvas, pos) else {
-          break; // unloaded chunk
-        };
+    let Some(pixel) = get_pixel_from_canvas(canvas, pos) else {
+      return false; // unloaded chunk
+    };
 
-        if pixel.is_void() {
-          continue;
-        }
+    if pixel.is_void() {
+      continue;
+    }
 
-        match f(&pixel, pos) {
-          BlastHit::Skip => continue,
-          BlastHit::Stop => break,
-          BlastHit::Hit {
-            pixel: new_pixel,
-            cost,
-          } => {
-            remaining -= cost;
-            ray_hits.push(BlastMutation {
-              pos,
-              pixel: new_pixel,
-            });
-            if remaining <= 0.0 {
-              break;
-            }
-          }
+    match f(&pixel, pos) {
+      BlastHit::Skip => continue,
+      BlastHit::Stop => return false,
+      BlastHit::Hit {
+        pixel: new_pixel,
+        cost,
+      } => {
+        *remaining -= cost;
+        hits.push(BlastMutation {
+          pos,
+          pixel: new_pixel,
+        });
+        if *remaining <= 0.0 {
+          return false;
         }
       }
+      BlastHit::Resist {
+        pixel: new_pixel,
+        resistance,
+      } => {
+        if *remaining < resistance {
+          return false;
+        }
+        *remaining -= resistance;
+        hits.push(BlastMutation {
+          pos,
+          pixel: new_pixel,
+        });
+        if *remaining <= 0.0 {
+          return false;
+        }
+      }
+    }
+  }
 
-      ray_hits
-    })
-    .collect()
+  true
 }
 
 /// Compute heat values in parallel and group by chunk.
@@ -199,6 +372,9 @@ fn compute_heat_values(
 ) -> HashMap<ChunkPos, Vec<(LocalPos, u8)>> {
   let center = params.center;
   let heat_radius = params.heat_radius;
+  if heat_radius <= 0.0 {
+    return HashMap::new();
+  }
   let hr = heat_radius as i32;
   let hr_sq = heat_radius * heat_radius;
   let cx = center.x as i64;
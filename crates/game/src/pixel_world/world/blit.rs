@@ -5,10 +5,11 @@
 
 use std::collections::HashMap;
 
-use super::PixelWorld;
+use super::{PixelWorld, in_thread_pool};
 use crate::pixel_world::coords::{ChunkPos, TilePos, WorldFragment, WorldRect};
 use crate::pixel_world::debug_shim::{self, DebugGizmos};
-use crate::pixel_world::pixel::Pixel;
+use crate::pixel_world::edit_log::WorldEdit;
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
 use crate::pixel_world::primitives::Chunk;
 use crate::pixel_world::scheduling::blitter::{Canvas, parallel_blit};
 
@@ -28,19 +29,73 @@ impl PixelWorld {
   ///
   /// Uses parallel 2x2 checkerboard scheduling for thread-safe concurrent
   /// writes. Returns the list of chunk positions that were modified.
+  /// While an [`EditRecorder`] is attached, the written pixels are also
+  /// logged.
   ///
   /// The `debug_gizmos` parameter emits visual debug overlays when the
   /// `visual-debug` feature is enabled. Pass `()` when disabled.
+  ///
+  /// [`EditRecorder`]: crate::pixel_world::EditRecorder
   pub fn blit<F>(&mut self, rect: WorldRect, f: F, debug_gizmos: DebugGizmos<'_>) -> Vec<ChunkPos>
   where
     F: Fn(WorldFragment) -> Option<Pixel> + Sync,
   {
+    self.record_edit(|| WorldEdit::capture_blit(rect, &f, false));
+    self.blit_with(rect, f, true, debug_gizmos)
+  }
+
+  /// Blits pixels like [`blit`](Self::blit), but leaves them at rest.
+  ///
+  /// Written pixels have their transient movement flags (`DIRTY`,
+  /// `FALLING`) cleared, and neither they nor their neighbors are marked
+  /// for simulation. Chunks are still re-uploaded, saved and have their
+  /// collision rebuilt. Used for editing a paused world, so resuming does
+  /// not start from stale motion.
+  pub fn blit_settled<F>(
+    &mut self,
+    rect: WorldRect,
+    f: F,
+    debug_gizmos: DebugGizmos<'_>,
+  ) -> Vec<ChunkPos>
+  where
+    F: Fn(WorldFragment) -> Option<Pixel> + Sync,
+  {
+    self.record_edit(|| WorldEdit::capture_blit(rect, &f, true));
+    let settle = |frag| {
+      f(frag).map(|mut pixel: Pixel| {
+        pixel.flags.remove(PixelFlags::DIRTY | PixelFlags::FALLING);
+        pixel
+      })
+    };
+    self.blit_with(rect, settle, false, debug_gizmos)
+  }
+
+  fn blit_with<F>(
+    &mut self,
+    rect: WorldRect,
+    f: F,
+    wake: bool,
+    debug_gizmos: DebugGizmos<'_>,
+  ) -> Vec<ChunkPos>
+  where
+    F: Fn(WorldFragment) -> Option<Pixel> + Sync,
+  {
+    let thread_pool = self.thread_pool();
     let chunks = self.collect_seeded_chunks();
     let chunk_access = Canvas::new(chunks);
     let dirty_chunks = std::sync::Mutex::new(std::collections::HashSet::new());
     let dirty_tiles = std::sync::Mutex::new(std::collections::HashSet::<TilePos>::new());
 
-    parallel_blit(&chunk_access, rect, f, &dirty_chunks, Some(&dirty_tiles));
+    in_thread_pool(thread_pool.as_deref(), || {
+      parallel_blit(
+        &chunk_access,
+        rect,
+        &f,
+        &dirty_chunks,
+        Some(&dirty_tiles),
+        wake,
+      )
+    });
 
     let dirty: Vec<_> = dirty_chunks
       .into_inner()
@@ -60,6 +115,7 @@ impl PixelWorld {
         slot.dirty = true;
         slot.modified = true;
         slot.persisted = false;
+        self.pool.bump_generation(idx);
       }
     }
 
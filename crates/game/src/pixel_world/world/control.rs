@@ -10,6 +10,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use bevy::prelude::*;
 
+use crate::pixel_world::coords::ChunkPos;
 use crate::pixel_world::seeding::ChunkSeeder;
 
 /// Controls whether world simulation is running or paused.
@@ -20,6 +21,11 @@ use crate::pixel_world::seeding::ChunkSeeder;
 /// - Rendering continues (world remains visible)
 /// - Persistence operations can still run
 ///
+/// With [clean edits](Self::set_clean_edits) on, brush edits made while
+/// paused are written at rest: painted pixels lose their transient movement
+/// flags and do not wake their neighbors, so resuming does not jolt the
+/// surrounding pixels.
+///
 /// # Example
 /// ```ignore
 /// fn pause_menu_system(
@@ -34,6 +40,7 @@ use crate::pixel_world::seeding::ChunkSeeder;
 #[derive(Resource, Debug, Default)]
 pub struct SimulationState {
   paused: bool,
+  clean_edits: bool,
 }
 
 impl SimulationState {
@@ -44,7 +51,10 @@ impl SimulationState {
 
   /// Creates a paused simulation state.
   pub fn paused() -> Self {
-    Self { paused: true }
+    Self {
+      paused: true,
+      ..default()
+    }
   }
 
   /// Returns true if simulation is paused.
@@ -76,22 +86,50 @@ impl SimulationState {
   pub fn set_paused(&mut self, paused: bool) {
     self.paused = paused;
   }
+
+  /// Returns true if clean edit mode is on.
+  pub fn clean_edits(&self) -> bool {
+    self.clean_edits
+  }
+
+  /// Turns clean edit mode on or off.
+  ///
+  /// Only takes effect while paused; see
+  /// [`settles_edits`](Self::settles_edits).
+  pub fn set_clean_edits(&mut self, clean_edits: bool) {
+    self.clean_edits = clean_edits;
+  }
+
+  /// Returns true if edits should be written at rest, i.e. the simulation
+  /// is paused with clean edit mode on.
+  pub fn settles_edits(&self) -> bool {
+    self.paused && self.clean_edits
+  }
 }
 
 /// Resource for persistence control.
 ///
 /// Provides methods to save the world to the current file or copy to a new
-/// path. Can be disabled at runtime for level editor mode.
+/// path. Can be disabled at runtime for level editor mode, or switched to
+/// dry-run mode to log writes without touching the save file.
 #[derive(Resource)]
 pub struct PersistenceControl {
   /// Whether persistence is enabled. When disabled, no I/O occurs.
   enabled: bool,
+  /// Whether writes are logged instead of performed.
+  dry_run: bool,
+  /// Writes skipped while in dry-run mode, oldest first.
+  dry_run_log: Vec<DryRunWrite>,
   /// Current save file path.
   pub(crate) current_path: Option<PathBuf>,
   /// Counter for generating unique request IDs.
   next_request_id: u64,
   /// Pending persistence requests.
   pub(crate) pending_requests: Vec<PersistenceRequestInner>,
+  /// Loaded chunks modified since they were last queued for saving.
+  unsaved_chunks: usize,
+  /// Whether the save file has writes that were not flushed yet.
+  save_dirty: bool,
 }
 
 impl PersistenceControl {
@@ -102,9 +140,13 @@ impl PersistenceControl {
   pub fn with_path_only(path: PathBuf) -> Self {
     Self {
       enabled: true,
+      dry_run: false,
+      dry_run_log: Vec::new(),
       current_path: Some(path),
       next_request_id: 1,
       pending_requests: Vec::new(),
+      unsaved_chunks: 0,
+      save_dirty: false,
     }
   }
 
@@ -127,6 +169,66 @@ impl PersistenceControl {
     self.enabled
   }
 
+  /// Enables dry-run mode.
+  ///
+  /// Chunk, body and explored-set writes, flushes and save deletions are
+  /// logged and recorded in [`dry_run_log`](Self::dry_run_log) instead of
+  /// reaching the save file. Loads keep reading from the existing file.
+  pub fn enable_dry_run(&mut self) {
+    self.dry_run = true;
+  }
+
+  /// Disables dry-run mode. Writes reach the save file again.
+  pub fn disable_dry_run(&mut self) {
+    self.dry_run = false;
+  }
+
+  /// Returns true if writes are logged instead of performed.
+  pub fn is_dry_run(&self) -> bool {
+    self.dry_run
+  }
+
+  /// Returns the writes skipped in dry-run mode, oldest first.
+  pub fn dry_run_log(&self) -> &[DryRunWrite] {
+    &self.dry_run_log
+  }
+
+  /// Takes the writes skipped in dry-run mode, leaving the log empty.
+  pub fn take_dry_run_log(&mut self) -> Vec<DryRunWrite> {
+    std::mem::take(&mut self.dry_run_log)
+  }
+
+  /// Records a write skipped in dry-run mode.
+  pub(crate) fn record_dry_run(&mut self, write: DryRunWrite) {
+    self.dry_run_log.push(write);
+  }
+
+  /// Returns true if there are changes that a save would write.
+  ///
+  /// True while any loaded chunk is modified but not yet saved, or while
+  /// the save file has writes that were not flushed. Updated once per frame
+  /// after the persistence systems run, so use it for "unsaved changes"
+  /// indicators and quit prompts.
+  pub fn has_unsaved_changes(&self) -> bool {
+    self.unsaved_chunks > 0 || self.save_dirty
+  }
+
+  /// Returns the number of loaded chunks modified since they were last
+  /// saved.
+  pub fn unsaved_chunk_count(&self) -> usize {
+    self.unsaved_chunks
+  }
+
+  /// Records the number of loaded chunks that need saving.
+  pub(crate) fn set_unsaved_chunk_count(&mut self, count: usize) {
+    self.unsaved_chunks = count;
+  }
+
+  /// Records whether the save file has unflushed writes.
+  pub(crate) fn set_save_dirty(&mut self, dirty: bool) {
+    self.save_dirty = dirty;
+  }
+
   /// Returns true if persistence is enabled and a save file is open.
   ///
   /// Check this before calling save methods.
@@ -203,6 +305,39 @@ impl PersistenceControl {
   }
 }
 
+/// A save file write skipped in dry-run mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DryRunWrite {
+  /// Chunk pixel data that would have been written.
+  Chunk {
+    /// Position of the chunk.
+    pos: ChunkPos,
+    /// Size of the encoded chunk data in bytes.
+    bytes: usize,
+  },
+  /// Pixel body record that would have been written.
+  Body {
+    /// Stable ID of the body.
+    stable_id: u64,
+    /// Size of the serialized record in bytes.
+    bytes: usize,
+  },
+  /// Pixel body that would have been removed.
+  RemoveBody {
+    /// Stable ID of the body.
+    stable_id: u64,
+  },
+  /// Explored chunk set that would have been written.
+  Explored {
+    /// Number of explored chunks.
+    chunks: usize,
+  },
+  /// Flush of the save file's index and header.
+  Flush,
+  /// Deletion of the save file.
+  DeleteSave,
+}
+
 /// Internal representation of a pending persistence request.
 #[allow(dead_code)] // Fields used on native but not WASM
 pub(crate) struct PersistenceRequestInner {
@@ -349,6 +484,23 @@ pub struct ReloadAllChunks;
 #[derive(bevy::prelude::Message)]
 pub struct ClearPersistence;
 
+/// Message to cancel in-flight async tasks, dropping their results.
+///
+/// Handled at the start of the frame, before new tasks are dispatched:
+/// - Seeding tasks are dropped. Their chunks are fetched from the save file
+///   again when persistence is enabled, and seeded again with the current
+///   seeder either way.
+/// - Results of pending chunk loads are discarded when they arrive, and the
+///   chunks are loaded again.
+/// - Collision mesh tasks are dropped and their tiles regenerated.
+///
+/// Saves are never cancelled. Send this before swapping a world's seeder
+/// or contents so results computed against the old state are not applied.
+/// `ReseedAllChunks` and `FreshReseedAllChunks` drop in-flight seeding
+/// tasks on their own.
+#[derive(bevy::prelude::Message, Clone, Copy, Debug, Default)]
+pub struct CancelPendingTasks;
+
 /// Message to reseed all chunks with fresh procedural data.
 ///
 /// Unlike `ReseedAllChunks` (which may be used after `UpdateSeeder`), this
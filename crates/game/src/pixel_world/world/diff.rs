@@ -0,0 +1,115 @@
+//! Per-chunk pixel diffs for network sync.
+//!
+//! [`PixelWorld::collect_dirty_diffs`] compares each seeded chunk with its
+//! pixels as of the previous call and returns what changed, so games can
+//! send peers only the pixels that differ. Peers apply them with
+//! [`Chunk::apply_diff`](crate::pixel_world::Chunk::apply_diff).
+//!
+//! Chunks whose generation hasn't moved since the last call, and that
+//! aren't waiting for upload, are skipped without comparing pixels. Only chunks
+//! that changed in the last call keep a plain copy of their pixels; the
+//! baselines of settled chunks are kept LZ4-compressed, which for typical
+//! terrain is a small fraction of a chunk.
+
+use std::borrow::Cow;
+
+use super::PixelWorld;
+use crate::pixel_world::coords::{ChunkPos, ColorIndex, MaterialId};
+use crate::pixel_world::persistence::compression::{compress_lz4, decompress_lz4};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::primitives::{ChunkDiff, diff_pixels};
+
+/// Pixels of a chunk as of the last diff, and the generation they belong
+/// to.
+pub(super) struct DiffBaseline {
+  generation: u64,
+  pixels: BaselinePixels,
+}
+
+enum BaselinePixels {
+  /// Plain copy, kept while the chunk keeps changing.
+  Plain(Box<[Pixel]>),
+  /// LZ4-compressed pixel bytes of a settled chunk.
+  Packed(Vec<u8>),
+}
+
+impl BaselinePixels {
+  /// Returns the baseline as plain pixels.
+  fn unpack(&self) -> Option<Cow<'_, [Pixel]>> {
+    match self {
+      Self::Plain(pixels) => Some(Cow::Borrowed(pixels)),
+      Self::Packed(bytes) => {
+        let bytes = decompress_lz4(bytes).ok()?;
+        Some(Cow::Owned(
+          bytes
+            .chunks_exact(size_of::<Pixel>())
+            .map(|p| Pixel {
+              material: MaterialId(p[0]),
+              color: ColorIndex(p[1]),
+              damage: p[2],
+              flags: PixelFlags::from_bits_truncate(p[3]),
+            })
+            .collect(),
+        ))
+      }
+    }
+  }
+}
+
+impl PixelWorld {
+  /// Returns the pixel diff of every chunk that changed since the last call,
+  /// ordered by chunk position.
+  ///
+  /// A chunk seen for the first time, including one streamed back in after
+  /// unloading, only records its pixels as the baseline for the next call;
+  /// sync its full contents separately. Pixel body pixels are included like
+  /// any other pixel.
+  pub fn collect_dirty_diffs(&mut self) -> Vec<(ChunkPos, ChunkDiff)> {
+    let mut diffs = Vec::new();
+    for (pos, idx) in self.pool.iter_active() {
+      let slot = self.pool.get(idx);
+      if !slot.is_seeded() {
+        continue;
+      }
+      let pixels = slot.chunk.pixels.as_slice();
+      let Some(baseline) = self.diff_baselines.get_mut(&pos) else {
+        self.diff_baselines.insert(
+          pos,
+          DiffBaseline {
+            generation: slot.generation,
+            pixels: BaselinePixels::Packed(compress_lz4(slot.chunk.pixels.as_bytes())),
+          },
+        );
+        continue;
+      };
+
+      if baseline.generation == slot.generation && !slot.dirty {
+        // Settled since the last call
+        if let BaselinePixels::Plain(_) = baseline.pixels {
+          baseline.pixels = BaselinePixels::Packed(compress_lz4(slot.chunk.pixels.as_bytes()));
+        }
+        continue;
+      }
+
+      if let Some(old) = baseline.pixels.unpack() {
+        let diff = diff_pixels(slot.chunk.pixels.width(), &old, pixels);
+        if !diff.is_empty() {
+          diffs.push((pos, diff));
+        }
+      }
+      baseline.generation = slot.generation;
+      baseline.pixels = BaselinePixels::Plain(pixels.into());
+    }
+
+    // Chunks that unloaded get a fresh baseline when they return
+    let pool = &self.pool;
+    self.diff_baselines.retain(|&pos, _| {
+      pool
+        .index_for(pos)
+        .is_some_and(|idx| pool.get(idx).is_seeded())
+    });
+
+    diffs.sort_by_key(|(pos, _)| (pos.y, pos.x));
+    diffs
+  }
+}
@@ -0,0 +1,115 @@
+//! Digging primitive for `PixelWorld`.
+//!
+//! Digging erases terrain like a void brush, but knocks a fraction of the
+//! removed solid pixels loose instead of deleting them. Loose pixels keep
+//! their material and color and fall as powder from the dig site. Materials
+//! with durability crack under each dig and only give way once worn through.
+
+use super::PixelWorld;
+use crate::pixel_world::coords::{WorldPos, WorldRect};
+use crate::pixel_world::debug_shim::DebugGizmos;
+use crate::pixel_world::material::{Materials, PhysicsState};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::simulation::hash::hash21uu64;
+
+/// Configuration for digging terrain.
+#[derive(Clone, Copy, Debug)]
+pub struct DigConfig {
+  /// Fraction of dug solid pixels (0.0 - 1.0) that stay behind as loose
+  /// powder of the same material. Powders, liquids and gases are always
+  /// removed.
+  pub loose_fraction: f32,
+}
+
+impl Default for DigConfig {
+  fn default() -> Self {
+    Self {
+      loose_fraction: 0.25,
+    }
+  }
+}
+
+impl DigConfig {
+  /// Returns true if the solid pixel dug at `pos` is knocked loose.
+  ///
+  /// Rolled with per-position noise, so the same dig always leaves the
+  /// same loose pixels.
+  pub fn loosens(&self, pos: WorldPos) -> bool {
+    const CH_DIG_LOOSE: u64 = 0xd16_0000_1005_e001;
+
+    let hash = hash21uu64(pos.x as u64 ^ CH_DIG_LOOSE, pos.y as u64);
+    let roll = (hash & 0xFFFF) as f32 / 65535.0;
+    roll < self.loose_fraction.clamp(0.0, 1.0)
+  }
+}
+
+impl PixelWorld {
+  /// Digs every pixel in `rect` for which `mask` returns true.
+  ///
+  /// Solid terrain is first chipped per [`Material::chip`] while its
+  /// material has durability left. Once it breaks it is either erased or,
+  /// per [`DigConfig::loosens`], turned into a loose pixel of the same
+  /// material; everything else is erased.
+  /// Pixel body pixels are left alone. Goes through [`PixelWorld::blit`],
+  /// so the dug area is marked for simulation.
+  ///
+  /// Returns the number of pixels knocked loose.
+  ///
+  /// [`Material::chip`]: crate::pixel_world::material::Material::chip
+  pub fn dig<F>(
+    &mut self,
+    rect: WorldRect,
+    mask: F,
+    config: &DigConfig,
+    materials: &Materials,
+    debug_gizmos: DebugGizmos<'_>,
+  ) -> usize
+  where
+    F: Fn(WorldPos) -> bool + Sync,
+  {
+    // Resolve the outcome up front: blit callbacks cannot read the world
+    let mut loose = 0;
+    let mut outcome = Vec::with_capacity(rect.width as usize * rect.height as usize);
+    for y in rect.y..rect.y + rect.height as i64 {
+      for x in rect.x..rect.x + rect.width as i64 {
+        let pos = WorldPos::new(x, y);
+        let dug = self
+          .get_pixel(pos)
+          .filter(|p| !p.is_void() && !p.flags.contains(PixelFlags::PIXEL_BODY) && mask(pos))
+          .map(|&pixel| {
+            let material = materials.get(pixel.material);
+            let terrain =
+              material.state == PhysicsState::Solid && !pixel.flags.contains(PixelFlags::LOOSE);
+            if terrain && let Some(chipped) = material.chip(&pixel) {
+              return chipped;
+            }
+            if terrain && config.loosens(pos) {
+              loose += 1;
+              Pixel {
+                damage: 0,
+                flags: PixelFlags::DIRTY
+                  | PixelFlags::SOLID
+                  | PixelFlags::FALLING
+                  | PixelFlags::LOOSE,
+                ..pixel
+              }
+            } else {
+              Pixel::VOID
+            }
+          });
+        outcome.push(dug);
+      }
+    }
+
+    self.blit(
+      rect,
+      |frag| {
+        let index = (frag.y - rect.y) * rect.width as i64 + (frag.x - rect.x);
+        outcome[index as usize]
+      },
+      debug_gizmos,
+    );
+
+    loose
+  }
+}
@@ -0,0 +1,190 @@
+//! Fog of war: dims loaded chunks the player hasn't explored yet.
+//!
+//! Chunks around the [`StreamingCamera`] are marked explored as it moves.
+//! The explored set outlives chunk unloading and is stored in its own section
+//! of the save file, so revealed areas stay revealed across sessions.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use super::PixelWorld;
+use super::control::PersistenceControl;
+use super::streaming::StreamingCamera;
+use crate::pixel_world::coords::{ChunkPos, WorldPos};
+use crate::pixel_world::persistence::PersistenceTasks;
+use crate::pixel_world::pixel_camera::LogicalCameraPosition;
+use crate::pixel_world::render::ChunkMaterial;
+
+/// Configuration for fog of war.
+#[derive(Resource, Clone, Debug)]
+pub struct FogOfWarConfig {
+  /// Reveal chunks around the camera and dim unexplored ones.
+  /// Default: false
+  pub enabled: bool,
+  /// Chunks around the camera's chunk marked explored, in chunks. 0 reveals
+  /// only the chunk the camera is in.
+  /// Default: 1
+  pub reveal_radius: u32,
+  /// Brightness of unexplored chunks (0.0 = black, 1.0 = undimmed).
+  /// Default: 0.25
+  pub unexplored_brightness: f32,
+}
+
+impl Default for FogOfWarConfig {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      reveal_radius: 1,
+      unexplored_brightness: 0.25,
+    }
+  }
+}
+
+/// Set of chunks the player has explored.
+///
+/// Loaded from the save file when persistence initializes and written back
+/// with the next save after it changes.
+#[derive(Resource, Default)]
+pub struct ExploredChunks {
+  chunks: HashSet<ChunkPos>,
+  /// Whether chunks were explored since the set was last queued for saving.
+  unsaved: bool,
+}
+
+impl ExploredChunks {
+  /// Returns true if the chunk has been explored.
+  pub fn is_explored(&self, pos: ChunkPos) -> bool {
+    self.chunks.contains(&pos)
+  }
+
+  /// Marks a chunk explored. Returns true if it was not explored before.
+  pub fn mark(&mut self, pos: ChunkPos) -> bool {
+    let added = self.chunks.insert(pos);
+    self.unsaved |= added;
+    added
+  }
+
+  /// Returns the number of explored chunks.
+  pub fn len(&self) -> usize {
+    self.chunks.len()
+  }
+
+  /// Returns true if no chunk has been explored.
+  pub fn is_empty(&self) -> bool {
+    self.chunks.is_empty()
+  }
+
+  /// Iterates over explored chunk positions.
+  pub fn iter(&self) -> impl Iterator<Item = ChunkPos> + '_ {
+    self.chunks.iter().copied()
+  }
+
+  /// Merges chunks loaded from the save file.
+  ///
+  /// Chunks explored before the save finished loading are kept and still
+  /// need saving.
+  pub(crate) fn merge_loaded(&mut self, chunks: impl IntoIterator<Item = ChunkPos>) {
+    self.chunks.extend(chunks);
+  }
+
+  /// Forgets all explored chunks, e.g. after the save file was cleared.
+  pub(crate) fn reset(&mut self) {
+    self.chunks.clear();
+    self.unsaved = false;
+  }
+
+  /// Returns the explored set if it changed since it was last taken.
+  fn take_unsaved(&mut self) -> Option<Vec<ChunkPos>> {
+    if !self.unsaved {
+      return None;
+    }
+    self.unsaved = false;
+    Some(self.iter().collect())
+  }
+}
+
+/// System: Marks chunks around the streaming camera explored.
+pub(crate) fn reveal_explored_chunks(
+  config: Res<FogOfWarConfig>,
+  camera_query: Query<(&GlobalTransform, Option<&LogicalCameraPosition>), With<StreamingCamera>>,
+  mut explored: ResMut<ExploredChunks>,
+) {
+  if !config.enabled {
+    return;
+  }
+  let Ok((transform, logical_pos)) = camera_query.single() else {
+    return;
+  };
+
+  let cam_pos = logical_pos
+    .map(|lp| lp.0)
+    .unwrap_or_else(|| transform.translation().truncate());
+  let (center, _) = WorldPos::new(cam_pos.x as i64, cam_pos.y as i64).to_chunk_and_local();
+
+  let radius = config.reveal_radius as i32;
+  for dy in -radius..=radius {
+    for dx in -radius..=radius {
+      explored.mark(ChunkPos::new(center.x + dx, center.y + dy));
+    }
+  }
+}
+
+/// System: Sets each chunk material's brightness from its explored state.
+///
+/// Materials are only touched when their brightness changes, since every
+/// mutation re-uploads the material.
+pub(crate) fn update_fog_brightness(
+  config: Res<FogOfWarConfig>,
+  explored: Res<ExploredChunks>,
+  worlds: Query<&PixelWorld>,
+  materials: Option<ResMut<Assets<ChunkMaterial>>>,
+) {
+  let Some(mut materials) = materials else {
+    return;
+  };
+
+  for world in worlds.iter() {
+    for (pos, idx) in world.active_chunks() {
+      let Some(handle) = world.slot(idx).material.as_ref() else {
+        continue;
+      };
+      let brightness = if !config.enabled || explored.is_explored(pos) {
+        1.0
+      } else {
+        config.unexplored_brightness
+      };
+      if materials
+        .get(handle)
+        .is_some_and(|mat| mat.brightness != brightness)
+        && let Some(mat) = materials.get_mut(handle)
+      {
+        mat.brightness = brightness;
+      }
+    }
+  }
+}
+
+/// System: Queues the explored set for saving alongside chunk saves.
+///
+/// Runs before the save task is dispatched, so the set is flushed together
+/// with save requests and chunks saved on unload.
+pub(crate) fn queue_explored_save(
+  persistence: Option<Res<PersistenceControl>>,
+  mut explored: ResMut<ExploredChunks>,
+  mut tasks: ResMut<PersistenceTasks>,
+) {
+  let Some(persistence) = persistence else {
+    return;
+  };
+  if !persistence.is_enabled() {
+    return;
+  }
+  if persistence.pending_requests.is_empty() && tasks.save_queue.is_empty() {
+    return;
+  }
+
+  if let Some(chunks) = explored.take_unsaved() {
+    tasks.queue_explored_save(chunks);
+  }
+}
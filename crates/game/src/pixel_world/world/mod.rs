@@ -8,35 +8,60 @@
 //!
 //! Sub-modules split `PixelWorld` methods by responsibility:
 //! - [`pixel_access`] — world-coordinate pixel read/write/swap
+//! - [`neighbors`] — relative reads around a pixel for rule authors
 //! - [`blit`] — parallel blit orchestration
 //! - [`blast`] — radial ray-cast destruction + heat injection
+//! - [`snapshot`] — simulation freeze/thaw with exact state preservation
+//! - [`transaction`] — atomic multi-pixel edits
+//! - [`diff`] — per-chunk pixel diffs for network sync
 
 mod blast;
-pub use blast::{BlastHit, BlastParams};
+pub use blast::{BlastHit, BlastParams, BlastWave};
 mod blit;
 pub(crate) mod body_loader;
 mod bundle;
 pub mod control;
+mod diff;
+mod dig;
+pub mod fog;
+mod neighbors;
 pub(crate) mod persistence_systems;
 mod pixel_access;
 pub mod plugin;
 mod pool;
 pub(crate) mod slot;
+mod snapshot;
 pub(crate) mod streaming;
 pub(crate) mod systems;
+mod transaction;
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use bevy::prelude::*;
 pub use bundle::{PixelWorldBundle, SpawnPixelWorld};
+pub use dig::DigConfig;
+pub use neighbors::{CellView, NEIGHBOR_OFFSETS};
 use pool::ChunkPool;
+use rayon::ThreadPool;
+use slot::ChunkLifecycle;
 pub(crate) use slot::{ChunkSlot, SlotIndex};
+pub use snapshot::SimulationSnapshot;
 pub(crate) use streaming::{ChunkSaveData, StreamingDelta};
-use streaming::{compute_position_changes, visible_positions};
-
-use crate::pixel_world::coords::{ChunkPos, WorldRect};
+use streaming::{
+  VISIBLE_CHUNK_COUNT, merge_seeded_pixels, seed_chunk_with_loaded, visible_positions,
+};
+pub use transaction::WorldTransaction;
+
+use crate::pixel_world::coords::{
+  CHUNK_SIZE, ChunkPos, POOL_SIZE, WINDOW_HEIGHT, WINDOW_WIDTH, WorldRect,
+};
+use crate::pixel_world::edit_log::WorldEdit;
+use crate::pixel_world::pixel::Pixel;
 use crate::pixel_world::primitives::Chunk;
-use crate::pixel_world::render::ChunkMaterial;
+use crate::pixel_world::render::{
+  BodyRenderMode, ChunkMaterial, CrackOverlayConfig, HeatTextureConfig,
+};
 use crate::pixel_world::seeding::ChunkSeeder;
 
 // ============================================================================
@@ -98,6 +123,42 @@ impl WorldLoadingProgress {
   }
 }
 
+/// Numbers of in-flight async tasks.
+///
+/// Refreshed once per frame after the persistence systems. Pending seeding,
+/// load and collision tasks can be dropped with
+/// [`CancelPendingTasks`](control::CancelPendingTasks).
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AsyncTaskCounts {
+  /// Chunks being seeded.
+  pub seeding: usize,
+  /// Chunks being loaded from disk.
+  pub loading: usize,
+  /// Whether a batch save is in progress. Saves are never cancelled.
+  pub saving: bool,
+  /// Collision meshes being generated.
+  pub collision: usize,
+  /// Chunks being seeded, per world entity.
+  seeding_by_world: HashMap<Entity, usize>,
+}
+
+impl AsyncTaskCounts {
+  /// Returns the number of chunks being seeded for a world.
+  pub fn seeding_in(&self, world: Entity) -> usize {
+    self.seeding_by_world.get(&world).copied().unwrap_or(0)
+  }
+
+  /// Returns true if no async task is in flight.
+  pub fn is_idle(&self) -> bool {
+    self.seeding == 0 && self.loading == 0 && !self.saving && self.collision == 0
+  }
+
+  pub(crate) fn set_seeding_by_world(&mut self, counts: HashMap<Entity, usize>) {
+    self.seeding = counts.values().sum();
+    self.seeding_by_world = counts;
+  }
+}
+
 /// Run condition: Returns true when the world is ready for gameplay.
 ///
 /// Use this to gate simulation and gameplay systems.
@@ -145,11 +206,140 @@ pub struct PixelWorldConfig {
   /// jitter). Higher values reduce tile boundary artifacts but may slightly
   /// increase processing.
   pub jitter_factor: f32,
+  /// Defer GPU uploads for chunks outside the camera view (simulation bounds
+  /// plus margin). Deferred chunks upload once when they come back into
+  /// view, or as soon as this is turned off. Has no effect while no
+  /// simulation bounds are set.
+  pub defer_offscreen_uploads: bool,
+  /// Memory budget in bytes for the chunk pool. The pool holds as many
+  /// chunks as fit in the budget, but never fewer than the visible streaming
+  /// window ([`Self::MIN_CHUNK_MEMORY`]). `None` uses the default pool size.
+  /// Only read when the world is created.
+  pub max_chunk_memory: Option<usize>,
+  /// Per-chunk heat texture for shader effects. Disabled by default.
+  pub heat_texture: HeatTextureConfig,
+  /// Whether pixel bodies are drawn as part of chunk textures or as
+  /// separate sprites. Default: [`BodyRenderMode::Blit`]
+  pub body_render_mode: BodyRenderMode,
+  /// Drawing of pixels cracked by blasts and digging. Enabled by default.
+  pub crack_overlay: CrackOverlayConfig,
+  /// Radius in chunks around the streaming center that must be seeded
+  /// before [`WorldReady`] fires. Chunks beyond it keep streaming in after.
+  /// `None` waits for the whole window.
+  pub ready_radius: Option<u32>,
+  /// Radius in chunks around each persistable pixel body that stays loaded
+  /// and simulated after leaving the streaming window, so off-camera bodies
+  /// keep falling instead of freezing. Held chunks only use pool slots the
+  /// window leaves free; when they run out, chunks of bodies' own positions
+  /// are kept first. Without a [`max_chunk_memory`](Self::max_chunk_memory)
+  /// budget, the pool grows by the area of one body's radius; with one,
+  /// the budget must leave room beyond the window. `None` unloads chunks as
+  /// soon as they leave the window. Only read when the world is created.
+  pub body_keep_alive_radius: Option<u32>,
+  /// Threads of a dedicated rayon pool for the simulation passes and
+  /// blits, so they don't compete with other rayon work for the global
+  /// pool. `Some(1)` runs them serially. `None` uses rayon's global pool.
+  /// Only read when the world is created.
+  pub simulation_threads: Option<usize>,
+  /// Derive the simulation bounds each frame from the viewports of the
+  /// [`StreamingCamera`] and any [`SimulationViewport`] cameras. Disable to
+  /// set them manually with [`PixelWorld::set_simulation_bounds`].
+  ///
+  /// [`StreamingCamera`]: crate::pixel_world::StreamingCamera
+  /// [`SimulationViewport`]: crate::pixel_world::SimulationViewport
+  pub follow_camera_bounds: bool,
+  /// Margin in pixels added around the simulation bounds on every side
+  /// (default: 64, ~2 tiles).
+  pub simulation_margin: u32,
+}
+
+impl PixelWorldConfig {
+  /// Smallest chunk memory budget that covers the visible streaming window.
+  pub const MIN_CHUNK_MEMORY: usize = VISIBLE_CHUNK_COUNT * Chunk::MEMORY_SIZE;
+
+  /// Returns the number of pool slots reserved for chunks held by
+  /// [`Self::body_keep_alive_radius`]: the area around one body.
+  fn keep_alive_slots(&self) -> usize {
+    self
+      .body_keep_alive_radius
+      .map_or(0, |radius| (2 * radius as usize + 1).pow(2))
+  }
+
+  /// Returns the number of pool slots for this configuration.
+  fn pool_slots(&self) -> usize {
+    let Some(budget) = self.max_chunk_memory else {
+      return POOL_SIZE + self.keep_alive_slots();
+    };
+    let slots = budget / Chunk::MEMORY_SIZE;
+    let spare = slots.saturating_sub(VISIBLE_CHUNK_COUNT);
+    if spare < self.keep_alive_slots() {
+      warn!(
+        "Chunk memory budget of {} bytes leaves {} chunks beyond the streaming window, but a body \
+         keep-alive radius of {:?} holds up to {} per body; off-camera bodies may freeze",
+        budget,
+        spare,
+        self.body_keep_alive_radius,
+        self.keep_alive_slots()
+      );
+    }
+    if slots < VISIBLE_CHUNK_COUNT {
+      warn!(
+        "Chunk memory budget of {} bytes fits {} chunks, but the streaming window needs {} ({} \
+         bytes); capping pool to window size",
+        budget,
+        slots,
+        VISIBLE_CHUNK_COUNT,
+        Self::MIN_CHUNK_MEMORY
+      );
+      return VISIBLE_CHUNK_COUNT;
+    }
+    slots
+  }
+}
+
+/// Builds the dedicated pool for [`PixelWorldConfig::simulation_threads`],
+/// falling back to the global pool if the threads cannot be spawned.
+fn build_thread_pool(threads: usize) -> Option<Arc<ThreadPool>> {
+  match rayon::ThreadPoolBuilder::new()
+    .num_threads(threads.max(1))
+    .thread_name(|i| format!("pixel-sim-{i}"))
+    .build()
+  {
+    Ok(pool) => Some(Arc::new(pool)),
+    Err(err) => {
+      warn!("Failed to build simulation thread pool, using the global pool: {err}");
+      None
+    }
+  }
+}
+
+/// Runs `f` inside `pool`, so its rayon work uses the pool's threads, or
+/// directly on rayon's global pool if `pool` is `None`.
+pub(crate) fn in_thread_pool<R: Send>(
+  pool: Option<&ThreadPool>,
+  f: impl FnOnce() -> R + Send,
+) -> R {
+  match pool {
+    Some(pool) => pool.install(f),
+    None => f(),
+  }
 }
 
 impl Default for PixelWorldConfig {
   fn default() -> Self {
-    Self { jitter_factor: 0.0 }
+    Self {
+      jitter_factor: 0.0,
+      defer_offscreen_uploads: true,
+      max_chunk_memory: None,
+      heat_texture: HeatTextureConfig::default(),
+      body_render_mode: BodyRenderMode::Blit,
+      crack_overlay: CrackOverlayConfig::default(),
+      ready_radius: None,
+      body_keep_alive_radius: None,
+      simulation_threads: None,
+      follow_camera_bounds: true,
+      simulation_margin: 64,
+    }
   }
 }
 
@@ -176,8 +366,26 @@ pub struct PixelWorld {
   /// Optional viewport bounds for simulation culling.
   /// When set, only tiles overlapping these bounds are simulated.
   simulation_bounds: Option<WorldRect>,
-  /// Margin in pixels added to simulation bounds (default: 64, ~2 tiles).
-  simulation_margin: i64,
+  /// Whether this world is simulated (default: true).
+  simulate: bool,
+  /// Chunks requested to stay loaded around persistable bodies, most
+  /// important first.
+  keep_alive: Vec<ChunkPos>,
+  /// True when `keep_alive` changed since the window was last updated.
+  keep_alive_changed: bool,
+  /// Active chunks outside the streaming window held by `keep_alive`.
+  held: HashSet<ChunkPos>,
+  /// Pixels of each chunk as of the last
+  /// [`collect_dirty_diffs`](Self::collect_dirty_diffs) call.
+  diff_baselines: HashMap<ChunkPos, diff::DiffBaseline>,
+  /// Dedicated pool for parallel passes, from
+  /// [`PixelWorldConfig::simulation_threads`].
+  thread_pool: Option<Arc<ThreadPool>>,
+  /// Edits made since the [`EditRecorder`] last took them; `None` while no
+  /// recorder is attached.
+  ///
+  /// [`EditRecorder`]: crate::pixel_world::EditRecorder
+  recorded_edits: Option<Vec<WorldEdit>>,
 }
 
 impl PixelWorld {
@@ -213,14 +421,20 @@ impl PixelWorld {
   ) -> Self {
     Self {
       center: ChunkPos::new(0, 0),
-      pool: ChunkPool::new(),
+      pool: ChunkPool::new(config.pool_slots()),
+      thread_pool: config.simulation_threads.and_then(build_thread_pool),
       seeder,
       mesh,
       seed,
       tick: 0,
       config,
       simulation_bounds: None,
-      simulation_margin: 64,
+      simulate: true,
+      keep_alive: Vec::new(),
+      keep_alive_changed: false,
+      held: HashSet::new(),
+      diff_baselines: HashMap::new(),
+      recorded_edits: None,
     }
   }
 
@@ -244,6 +458,32 @@ impl PixelWorld {
     self.tick = self.tick.wrapping_add(1);
   }
 
+  /// Starts buffering the edits made to this world for the edit recorder.
+  pub(crate) fn record_edits(&mut self) {
+    self.recorded_edits.get_or_insert_with(Vec::new);
+  }
+
+  /// Buffers the edit made by `edit` while edits are recorded.
+  pub(crate) fn record_edit(&mut self, edit: impl FnOnce() -> WorldEdit) {
+    if let Some(edits) = &mut self.recorded_edits {
+      edits.push(edit());
+    }
+  }
+
+  /// Takes the edits buffered since the last call.
+  pub(crate) fn take_recorded_edits(&mut self) -> Vec<WorldEdit> {
+    self
+      .recorded_edits
+      .as_mut()
+      .map(std::mem::take)
+      .unwrap_or_default()
+  }
+
+  /// Returns the dedicated pool for parallel passes, if one is configured.
+  pub(crate) fn thread_pool(&self) -> Option<Arc<ThreadPool>> {
+    self.thread_pool.clone()
+  }
+
   /// Returns the world configuration.
   pub fn config(&self) -> &PixelWorldConfig {
     &self.config
@@ -254,6 +494,21 @@ impl PixelWorld {
     &mut self.config
   }
 
+  /// Returns true if this world is simulated.
+  pub fn simulate(&self) -> bool {
+    self.simulate
+  }
+
+  /// Enables or disables simulation of this world.
+  ///
+  /// Unlike [`SimulationState`](control::SimulationState), which pauses every
+  /// world, this stops only this world: its tick counter and pixels stay as
+  /// they are while other worlds keep simulating. Streaming, editing and
+  /// persistence are unaffected.
+  pub fn set_simulate(&mut self, simulate: bool) {
+    self.simulate = simulate;
+  }
+
   /// Sets the simulation bounds for viewport culling.
   ///
   /// When set, only tiles overlapping these bounds (plus margin) are
@@ -267,12 +522,13 @@ impl PixelWorld {
   ///
   /// Returns `None` if no bounds are set (full streaming window simulation).
   pub fn simulation_bounds(&self) -> Option<WorldRect> {
+    let margin = self.config.simulation_margin;
     self.simulation_bounds.map(|rect| {
       WorldRect::new(
-        rect.x - self.simulation_margin,
-        rect.y - self.simulation_margin,
-        rect.width + (self.simulation_margin * 2) as u32,
-        rect.height + (self.simulation_margin * 2) as u32,
+        rect.x - margin as i64,
+        rect.y - margin as i64,
+        rect.width + margin * 2,
+        rect.height + margin * 2,
       )
     })
   }
@@ -300,6 +556,48 @@ impl PixelWorld {
     visible_positions(self.center)
   }
 
+  /// Returns the pixel-space rectangle covered by the visible chunk window.
+  ///
+  /// The window spans [`WINDOW_WIDTH`] x [`WINDOW_HEIGHT`] chunks around
+  /// [`center`](Self::center), so this changes only when the window moves.
+  pub fn window_world_bounds(&self) -> WorldRect {
+    let origin = ChunkPos::new(
+      self.center.x - WINDOW_WIDTH as i32 / 2,
+      self.center.y - WINDOW_HEIGHT as i32 / 2,
+    )
+    .to_world();
+    WorldRect::new(
+      origin.x,
+      origin.y,
+      WINDOW_WIDTH * CHUNK_SIZE,
+      WINDOW_HEIGHT * CHUNK_SIZE,
+    )
+  }
+
+  /// Returns the pixel-space bounding rectangle of all loaded chunks.
+  ///
+  /// Covers every active chunk, including any held outside the visible
+  /// window, so it always contains the loaded part of
+  /// [`window_world_bounds`](Self::window_world_bounds). Returns `None`
+  /// before the first chunks are loaded.
+  pub fn loaded_world_bounds(&self) -> Option<WorldRect> {
+    let mut positions = self.pool.iter_active().map(|(pos, _)| pos);
+    let first = positions.next()?;
+    let (min, max) = positions.fold((first, first), |(min, max), pos| {
+      (
+        ChunkPos::new(min.x.min(pos.x), min.y.min(pos.y)),
+        ChunkPos::new(max.x.max(pos.x), max.y.max(pos.y)),
+      )
+    });
+    let origin = min.to_world();
+    Some(WorldRect::new(
+      origin.x,
+      origin.y,
+      (max.x - min.x + 1) as u32 * CHUNK_SIZE,
+      (max.y - min.y + 1) as u32 * CHUNK_SIZE,
+    ))
+  }
+
   /// Gets a reference to a slot by index.
   pub(crate) fn slot(&self, index: SlotIndex) -> &ChunkSlot {
     self.pool.get(index)
@@ -310,6 +608,11 @@ impl PixelWorld {
     self.pool.get_mut(index)
   }
 
+  /// Gives the chunk in a slot a new generation after changing its content.
+  pub(crate) fn bump_generation(&mut self, index: SlotIndex) {
+    self.pool.bump_generation(index);
+  }
+
   /// Gets the slot index for an active chunk position.
   pub(crate) fn get_slot_index(&self, pos: ChunkPos) -> Option<SlotIndex> {
     self.pool.index_for(pos)
@@ -333,6 +636,77 @@ impl PixelWorld {
     self.pool.active_count()
   }
 
+  /// Returns the number of chunk slots allocated for this world.
+  pub fn pool_capacity(&self) -> usize {
+    self.pool.capacity()
+  }
+
+  /// Returns true if the chunk has changes queued for GPU upload.
+  pub fn is_upload_pending(&self, pos: ChunkPos) -> bool {
+    self
+      .pool
+      .index_for(pos)
+      .is_some_and(|idx| self.pool.get(idx).dirty)
+  }
+
+  /// Returns the heat texture of an active chunk.
+  ///
+  /// `None` unless [`PixelWorldConfig::heat_texture`] is enabled and the
+  /// chunk has render resources.
+  pub fn heat_texture(&self, pos: ChunkPos) -> Option<&Handle<Image>> {
+    self
+      .pool
+      .index_for(pos)
+      .and_then(|idx| self.pool.get(idx).heat_texture.as_ref())
+  }
+
+  /// Returns the render material of an active chunk.
+  ///
+  /// `None` if the chunk has no render resources.
+  pub fn chunk_material(&self, pos: ChunkPos) -> Option<&Handle<ChunkMaterial>> {
+    self
+      .pool
+      .index_for(pos)
+      .and_then(|idx| self.pool.get(idx).material.as_ref())
+  }
+
+  /// Returns true if the chunk's GPU texture is out of date because its
+  /// uploads were deferred while it was outside the camera view.
+  pub fn is_gpu_stale(&self, pos: ChunkPos) -> bool {
+    self
+      .pool
+      .index_for(pos)
+      .is_some_and(|idx| self.pool.get(idx).gpu_stale)
+  }
+
+  /// Seeds an active chunk synchronously with the world's seeder.
+  ///
+  /// Skips the async seeding pipeline, so the chunk's pixels are readable
+  /// as soon as this returns. Persisted data is not consulted, and any
+  /// in-flight seeding or loading result for the chunk is discarded. Pixel
+  /// body pixels already blitted into the chunk are preserved.
+  ///
+  /// Returns false if `pos` is not an active chunk. Already seeded chunks
+  /// are left untouched and return true.
+  pub fn force_seed(&mut self, pos: ChunkPos) -> bool {
+    let Some(idx) = self.pool.index_for(pos) else {
+      return false;
+    };
+    if self.pool.get(idx).is_seeded() {
+      return true;
+    }
+
+    let seeded = seed_chunk_with_loaded(self.seeder.as_ref(), pos, None);
+    let slot = self.pool.get_mut(idx);
+    merge_seeded_pixels(&mut slot.chunk.pixels, &seeded.pixels);
+    slot.chunk.set_all_dirty_rects_full();
+    slot.chunk.activate_all_heat_tiles();
+    slot.lifecycle = ChunkLifecycle::Active;
+    slot.dirty = true;
+    self.pool.bump_generation(idx);
+    true
+  }
+
   // === Streaming logic ===
 
   /// Initializes the world at a given center position.
@@ -341,6 +715,9 @@ impl PixelWorld {
   /// Returns all visible positions as chunks to spawn.
   pub(crate) fn initialize_at(&mut self, center: ChunkPos) -> StreamingDelta {
     self.center = center;
+    // Held chunks are reacquired on the next update
+    self.held.clear();
+    self.keep_alive_changed = true;
 
     // Collect positions first to avoid borrow issues
     let positions: Vec<_> = visible_positions(center).collect();
@@ -368,18 +745,35 @@ impl PixelWorld {
   ///
   /// This handles:
   /// - Computing which chunks leave/enter the window
+  /// - Keeping chunks held by [`set_keep_alive`](Self::set_keep_alive)
   /// - Releasing slots for departing chunks
   /// - Acquiring slots for arriving chunks
   /// - Marking new chunks as unseeded
   pub(crate) fn update_center(&mut self, new_center: ChunkPos) -> StreamingDelta {
-    if new_center == self.center {
+    if new_center == self.center && !self.keep_alive_changed {
       return StreamingDelta::empty();
     }
-
-    let (leaving, entering) = compute_position_changes(self.center, new_center);
     self.center = new_center;
-
-    // Release chunks that are leaving the window
+    self.keep_alive_changed = false;
+
+    // Hold keep-alive chunks outside the window while spare slots remain
+    let window: HashSet<_> = visible_positions(new_center).collect();
+    let spare = self.pool.capacity().saturating_sub(window.len());
+    self.held = self
+      .keep_alive
+      .iter()
+      .filter(|pos| !window.contains(pos))
+      .take(spare)
+      .copied()
+      .collect();
+
+    // Release chunks that are neither visible nor held
+    let leaving: Vec<_> = self
+      .pool
+      .iter_active()
+      .map(|(pos, _)| pos)
+      .filter(|pos| !window.contains(pos) && !self.held.contains(pos))
+      .collect();
     let mut to_despawn = Vec::new();
     let mut to_save = Vec::new();
     for pos in leaving {
@@ -402,7 +796,11 @@ impl PixelWorld {
       }
     }
 
-    // Acquire slots for chunks entering the window
+    // Acquire slots for chunks entering the window, then for held chunks
+    let entering: Vec<_> = visible_positions(new_center)
+      .chain(self.held.iter().copied())
+      .filter(|&pos| self.pool.index_for(pos).is_none())
+      .collect();
     let mut to_spawn = Vec::new();
     for pos in entering {
       if let Some(idx) = self.pool.acquire() {
@@ -421,6 +819,31 @@ impl PixelWorld {
     }
   }
 
+  /// Sets the chunks to keep loaded outside the streaming window, most
+  /// important first.
+  ///
+  /// Takes effect on the next streaming update. Only as many chunks as the
+  /// pool has slots to spare beyond the window are held; the rest unload
+  /// like any chunk leaving the window.
+  pub(crate) fn set_keep_alive(&mut self, positions: Vec<ChunkPos>) {
+    if positions != self.keep_alive {
+      self.keep_alive = positions;
+      self.keep_alive_changed = true;
+    }
+  }
+
+  /// Returns true if `pos` is loaded outside the streaming window because
+  /// it is kept alive around a persistable pixel body.
+  pub fn is_kept_alive(&self, pos: ChunkPos) -> bool {
+    self.held.contains(&pos)
+  }
+
+  /// Returns the chunks loaded outside the streaming window because they
+  /// are kept alive around persistable pixel bodies.
+  pub fn kept_alive_chunks(&self) -> impl Iterator<Item = ChunkPos> + '_ {
+    self.held.iter().copied()
+  }
+
   /// Registers entity and optional render resources for a slot.
   pub(crate) fn register_slot_entity(
     &mut self,
@@ -428,10 +851,12 @@ impl PixelWorld {
     entity: Entity,
     texture: Option<Handle<Image>>,
     material: Option<Handle<ChunkMaterial>>,
+    heat_texture: Option<Handle<Image>>,
   ) {
     let slot = self.pool.get_mut(index);
     slot.entity = Some(entity);
     slot.texture = texture;
     slot.material = material;
+    slot.heat_texture = heat_texture;
   }
 }
@@ -0,0 +1,73 @@
+//! Neighborhood reads for `PixelWorld`.
+//!
+//! [`PixelWorld::neighbors`] hands the caller a [`CellView`] centered on a
+//! pixel, which reads the pixel and its surroundings by relative offset.
+//! Offsets crossing a chunk edge resolve to the neighboring chunk, and
+//! pixels of chunks that are not loaded or not yet seeded read as `None`,
+//! so rule code never deals with chunk coordinates or storage.
+
+use super::PixelWorld;
+use crate::pixel_world::coords::WorldPos;
+use crate::pixel_world::pixel::Pixel;
+
+/// Offsets of the 8 neighbors of a pixel, counter-clockwise from the right
+/// (+y is up).
+pub const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+  (1, 0),   // right
+  (1, 1),   // above-right
+  (0, 1),   // above
+  (-1, 1),  // above-left
+  (-1, 0),  // left
+  (-1, -1), // below-left
+  (0, -1),  // below
+  (1, -1),  // below-right
+];
+
+/// Read-only view of the pixels around a center position.
+///
+/// Returned by [`PixelWorld::neighbors`].
+#[derive(Clone, Copy)]
+pub struct CellView<'w> {
+  world: &'w PixelWorld,
+  center: WorldPos,
+}
+
+impl<'w> CellView<'w> {
+  /// Returns the world position the view is centered on.
+  pub fn center(&self) -> WorldPos {
+    self.center
+  }
+
+  /// Returns the world the view reads from.
+  pub fn world(&self) -> &'w PixelWorld {
+    self.world
+  }
+
+  /// Returns the pixel at offset `(dx, dy)` from the center (+y is up);
+  /// `(0, 0)` is the center pixel itself.
+  ///
+  /// Returns None if the pixel's chunk is not loaded or not yet seeded.
+  pub fn get(&self, dx: i64, dy: i64) -> Option<Pixel> {
+    let pos = WorldPos::new(self.center.x + dx, self.center.y + dy);
+    self.world.get_pixel(pos).copied()
+  }
+
+  /// Iterates over the 8 neighbors in [`NEIGHBOR_OFFSETS`] order, yielding
+  /// each offset with its pixel.
+  pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), Option<Pixel>)> + '_ {
+    NEIGHBOR_OFFSETS
+      .iter()
+      .map(|&(dx, dy)| ((dx, dy), self.get(dx, dy)))
+  }
+}
+
+impl PixelWorld {
+  /// Returns a view for reading the pixels around `center` by relative
+  /// offset, across chunk boundaries.
+  pub fn neighbors(&self, center: WorldPos) -> CellView<'_> {
+    CellView {
+      world: self,
+      center,
+    }
+  }
+}
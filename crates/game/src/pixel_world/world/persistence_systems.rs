@@ -21,12 +21,15 @@ use bevy::prelude::*;
 use super::PixelWorld;
 #[cfg(not(target_family = "wasm"))]
 use super::control::PersistenceComplete;
-use super::control::{ClearPersistence, PersistenceControl, RequestPersistence};
+use super::control::{ClearPersistence, DryRunWrite, PersistenceControl, RequestPersistence};
+use super::fog::ExploredChunks;
 use super::streaming::UnloadingChunks;
+use crate::pixel_world::DefaultPersistenceConfig;
 use crate::pixel_world::coords::WorldPos;
 use crate::pixel_world::persistence::{
   PersistenceTasks, PixelBodyRecord, compression::compress_lz4, format::StorageType,
 };
+use crate::pixel_world::pixel::PixelFlags;
 use crate::pixel_world::pixel_body::{LastBlitTransform, Persistable, PixelBody, PixelBodyId};
 
 /// System: Converts `RequestPersistence` messages into pending save requests.
@@ -59,6 +62,7 @@ pub(crate) fn handle_persistence_messages(
 pub(crate) fn handle_clear_persistence(
   mut messages: MessageReader<ClearPersistence>,
   io_dispatcher: Option<Res<IoDispatcher>>,
+  persistence: Option<ResMut<PersistenceControl>>,
 ) {
   if messages.is_empty() {
     return;
@@ -77,6 +81,14 @@ pub(crate) fn handle_clear_persistence(
     return;
   }
 
+  if let Some(mut persistence) = persistence
+    && persistence.is_dry_run()
+  {
+    info!("[dry-run] Would delete save file");
+    persistence.record_dry_run(DryRunWrite::DeleteSave);
+    return;
+  }
+
   info!("Clearing save file...");
   io_dispatcher.send(crate::pixel_world::persistence::IoCommand::DeleteSave);
 }
@@ -133,11 +145,37 @@ pub(crate) fn process_pending_save_requests(
   }
 }
 
+/// System: Counts loaded chunks with changes that still need saving.
+///
+/// Feeds [`PersistenceControl::has_unsaved_changes`]. Runs after the save
+/// systems, so chunks queued this frame no longer count.
+pub(crate) fn track_unsaved_changes(
+  persistence: Option<ResMut<PersistenceControl>>,
+  worlds: Query<&PixelWorld>,
+) {
+  let Some(mut persistence) = persistence else {
+    return;
+  };
+  let count = worlds
+    .iter()
+    .map(|world| {
+      world
+        .active_chunks()
+        .filter(|&(_, idx)| world.slot(idx).needs_save())
+        .count()
+    })
+    .sum();
+  if persistence.unsaved_chunk_count() != count {
+    persistence.set_unsaved_chunk_count(count);
+  }
+}
+
 /// Returns true if there are pending persistence operations.
 fn has_pending_work(tasks: &PersistenceTasks) -> bool {
   !tasks.save_queue.is_empty()
     || !tasks.body_save_queue.is_empty()
     || !tasks.body_remove_queue.is_empty()
+    || tasks.explored_save.is_some()
 }
 
 /// Clears all queued operations without saving.
@@ -145,6 +183,7 @@ fn discard_queued_operations(tasks: &mut PersistenceTasks) {
   tasks.save_queue.clear();
   tasks.body_save_queue.clear();
   tasks.body_remove_queue.clear();
+  tasks.explored_save = None;
 }
 
 /// System: Legacy flush for persistence tasks.
@@ -410,6 +449,18 @@ fn dispatch_body_saves(tasks: &mut PersistenceTasks, io_dispatcher: &IoDispatche
   }
 }
 
+/// Dispatches a SaveExplored command for the queued explored set.
+fn dispatch_explored_save(tasks: &mut PersistenceTasks, io_dispatcher: &IoDispatcher) {
+  if let Some(chunks) = tasks.explored_save.take() {
+    io_dispatcher.send(crate::pixel_world::persistence::IoCommand::SaveExplored {
+      chunks: chunks
+        .into_iter()
+        .map(|pos| bevy::math::IVec2::new(pos.x, pos.y))
+        .collect(),
+    });
+  }
+}
+
 /// Dispatches RemoveBody commands for queued removals.
 fn dispatch_body_removals(tasks: &mut PersistenceTasks, io_dispatcher: &IoDispatcher) {
   for task in tasks.body_remove_queue.drain(..) {
@@ -419,17 +470,69 @@ fn dispatch_body_removals(tasks: &mut PersistenceTasks, io_dispatcher: &IoDispat
   }
 }
 
+/// Logs and drains all queued operations instead of dispatching them.
+fn log_dry_run_writes(tasks: &mut PersistenceTasks, persistence: &mut PersistenceControl) {
+  for task in tasks.save_queue.drain(..) {
+    info!(
+      "[dry-run] Would write chunk ({}, {}): {} bytes",
+      task.pos.x,
+      task.pos.y,
+      task.data.len()
+    );
+    persistence.record_dry_run(DryRunWrite::Chunk {
+      pos: task.pos,
+      bytes: task.data.len(),
+    });
+  }
+
+  for task in tasks.body_save_queue.drain(..) {
+    let mut buf = Vec::new();
+    if let Err(e) = task.record.write_to(&mut buf) {
+      warn!("Failed to serialize body {}: {}", task.record.stable_id, e);
+      continue;
+    }
+    info!(
+      "[dry-run] Would write body {}: {} bytes",
+      task.record.stable_id,
+      buf.len()
+    );
+    persistence.record_dry_run(DryRunWrite::Body {
+      stable_id: task.record.stable_id,
+      bytes: buf.len(),
+    });
+  }
+
+  for task in tasks.body_remove_queue.drain(..) {
+    info!("[dry-run] Would remove body {}", task.stable_id);
+    persistence.record_dry_run(DryRunWrite::RemoveBody {
+      stable_id: task.stable_id,
+    });
+  }
+
+  if let Some(chunks) = tasks.explored_save.take() {
+    info!("[dry-run] Would write {} explored chunks", chunks.len());
+    persistence.record_dry_run(DryRunWrite::Explored {
+      chunks: chunks.len(),
+    });
+  }
+
+  info!("[dry-run] Would flush save file");
+  persistence.record_dry_run(DryRunWrite::Flush);
+}
+
 /// System: Dispatches a batch save task when saves are queued.
 ///
 /// Only one save task runs at a time to prevent write conflicts.
 /// Runs in PostSimulation after chunks are queued.
 ///
 /// Both native and WASM use IoDispatcher to send commands to the worker.
+/// In dry-run mode the queued operations are logged and dropped instead.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
 pub(crate) fn dispatch_save_task(
   mut saving: ResMut<SavingChunks>,
   mut tasks: ResMut<PersistenceTasks>,
   io_dispatcher: Option<Res<IoDispatcher>>,
+  persistence: Option<ResMut<PersistenceControl>>,
 ) {
   // Don't dispatch if already saving or nothing to save
   if saving.is_busy() || !has_pending_work(&tasks) {
@@ -446,9 +549,17 @@ pub(crate) fn dispatch_save_task(
     return;
   }
 
+  if let Some(mut persistence) = persistence
+    && persistence.is_dry_run()
+  {
+    log_dry_run_writes(&mut tasks, &mut persistence);
+    return;
+  }
+
   dispatch_chunk_writes(&mut tasks, &io_dispatcher);
   dispatch_body_saves(&mut tasks, &io_dispatcher);
   dispatch_body_removals(&mut tasks, &io_dispatcher);
+  dispatch_explored_save(&mut tasks, &io_dispatcher);
 
   // Send Flush to persist to disk
   io_dispatcher.send(crate::pixel_world::persistence::IoCommand::Flush);
@@ -677,13 +788,16 @@ impl LoadedChunkDataStore {
 use crate::pixel_world::persistence::io_worker::{IoDispatcher, IoResult};
 
 /// Handles the Initialized result from the I/O worker.
+#[allow(clippy::too_many_arguments)]
 fn handle_initialized_result(
   commands: &mut Commands,
   io_dispatcher: &IoDispatcher,
   pending_init: &Option<Res<crate::pixel_world::world::control::PendingPersistenceInit>>,
+  explored_chunks: &mut ExploredChunks,
   chunk_count: usize,
   body_count: usize,
   world_seed: u64,
+  explored: Vec<bevy::math::IVec2>,
 ) {
   debug!(
     "I/O Worker initialized: {} chunks, {} bodies, seed {}",
@@ -692,6 +806,11 @@ fn handle_initialized_result(
   io_dispatcher.set_ready(true);
   io_dispatcher.set_world_seed(world_seed);
   io_dispatcher.set_init_counts(chunk_count, body_count);
+  explored_chunks.merge_loaded(
+    explored
+      .into_iter()
+      .map(|pos| crate::pixel_world::coords::ChunkPos::new(pos.x, pos.y)),
+  );
 
   // Create PersistenceControl now that worker is ready
   if let Some(pending) = pending_init {
@@ -708,9 +827,15 @@ fn handle_chunk_loaded_result(
   chunk_pos: bevy::math::IVec2,
   data: Option<crate::pixel_world::persistence::io_worker::ChunkLoadData>,
   bodies: Vec<crate::pixel_world::persistence::io_worker::BodyLoadData>,
+  clear_flags: PixelFlags,
 ) {
   let pos = crate::pixel_world::coords::ChunkPos::new(chunk_pos.x, chunk_pos.y);
 
+  // Results of cancelled loads may be stale
+  if loading.take_cancelled(pos) {
+    return;
+  }
+
   // Remove from pending set
   loading.pending.remove(&pos);
 
@@ -728,6 +853,7 @@ fn handle_chunk_loaded_result(
         data: chunk_data.data,
         pos,
         seeder_needed: chunk_data.seeder_needed,
+        clear_flags,
       },
     );
   }
@@ -755,6 +881,13 @@ fn handle_flush_complete_result(saving: &mut SavingChunks) {
   saving.busy = false;
 }
 
+/// Records whether the save file has writes waiting for a flush.
+fn set_save_dirty(persistence: &mut Option<ResMut<PersistenceControl>>, dirty: bool) {
+  if let Some(persistence) = persistence {
+    persistence.set_save_dirty(dirty);
+  }
+}
+
 /// Handles the Error result from the I/O worker.
 fn handle_error_result(message: &str) {
   warn!("I/O Worker error: {}", message);
@@ -767,6 +900,7 @@ fn handle_error_result(message: &str) {
 /// - Chunk load results (stores data for seeding)
 /// - Write completion results (updates tracking)
 /// - Flush completion
+/// - Save deletion (forgets explored chunks)
 /// - Errors
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
 pub(crate) fn poll_io_results(
@@ -777,10 +911,16 @@ pub(crate) fn poll_io_results(
   mut worlds: Query<&mut PixelWorld>,
   mut loading: ResMut<LoadingChunks>,
   mut saving: ResMut<SavingChunks>,
+  mut explored_chunks: ResMut<ExploredChunks>,
+  persistence_config: Option<Res<DefaultPersistenceConfig>>,
+  mut persistence: Option<ResMut<PersistenceControl>>,
 ) {
   let Some(io_dispatcher) = io_dispatcher else {
     return;
   };
+  let clear_flags = persistence_config
+    .as_ref()
+    .map_or(PixelFlags::empty(), |c| c.0.clear_flags_on_load);
 
   // Process all available results
   while let Some(result) = io_dispatcher.try_recv() {
@@ -789,14 +929,17 @@ pub(crate) fn poll_io_results(
         chunk_count,
         body_count,
         world_seed,
+        explored,
       } => {
         handle_initialized_result(
           &mut commands,
           &io_dispatcher,
           &pending_init,
+          &mut explored_chunks,
           chunk_count,
           body_count,
           world_seed,
+          explored,
         );
       }
       IoResult::ChunkLoaded {
@@ -811,21 +954,30 @@ pub(crate) fn poll_io_results(
           chunk_pos,
           data,
           bodies,
+          clear_flags,
         );
       }
       IoResult::WriteComplete { chunk_pos: _ } => {
-        // Write completed, nothing to do here - flush happens separately
+        // Write completed, flush happens separately
+        set_save_dirty(&mut persistence, true);
       }
       IoResult::BodySaveComplete { stable_id: _ } => {
-        // Body save completed
+        set_save_dirty(&mut persistence, true);
       }
       IoResult::BodyRemoveComplete { stable_id: _ } => {
-        // Body removal completed
+        set_save_dirty(&mut persistence, true);
+      }
+      IoResult::ExploredSaveComplete => {
+        // Explored set written, persisted with the following flush
+        set_save_dirty(&mut persistence, true);
       }
       IoResult::FlushComplete => {
         handle_flush_complete_result(&mut saving);
+        set_save_dirty(&mut persistence, false);
       }
       IoResult::DeleteComplete => {
+        explored_chunks.reset();
+        set_save_dirty(&mut persistence, false);
         info!("Save file cleared and reinitialized");
       }
       IoResult::Error { message } => {
@@ -4,7 +4,7 @@
 //! `WorldPos` to chunk+local coordinates and resolving through the pool.
 
 use super::PixelWorld;
-use crate::pixel_world::coords::WorldPos;
+use crate::pixel_world::coords::{TILE_SIZE, WorldPos};
 use crate::pixel_world::debug_shim::{self, DebugGizmos};
 use crate::pixel_world::pixel::Pixel;
 use crate::pixel_world::primitives::HEAT_CELL_SIZE;
@@ -72,6 +72,7 @@ impl PixelWorld {
       slot.dirty = true;
       slot.modified = true;
       slot.persisted = false;
+      self.pool.bump_generation(idx_a);
     } else {
       // Different chunks - need to swap across
       let (slot_a, slot_b) = self.pool.get_two_mut(idx_a, idx_b);
@@ -85,6 +86,8 @@ impl PixelWorld {
       slot_b.dirty = true;
       slot_b.modified = true;
       slot_b.persisted = false;
+      self.pool.bump_generation(idx_a);
+      self.pool.bump_generation(idx_b);
     }
 
     true
@@ -111,6 +114,7 @@ impl PixelWorld {
     slot.dirty = true;
     slot.modified = true;
     slot.persisted = false; // Needs saving again
+    self.pool.bump_generation(idx);
 
     // Emit chunk gizmo if this is the first modification
     if was_clean {
@@ -120,13 +124,26 @@ impl PixelWorld {
     true
   }
 
-  /// Marks a chunk as needing GPU upload.
+  /// Marks a chunk as changed: it needs GPU upload and gets a new
+  /// generation.
   pub fn mark_dirty(&mut self, pos: crate::pixel_world::coords::ChunkPos) {
     if let Some(idx) = self.pool.index_for(pos) {
       self.pool.get_mut(idx).dirty = true;
+      self.pool.bump_generation(idx);
     }
   }
 
+  /// Returns the content generation of a seeded chunk.
+  ///
+  /// The generation increases whenever the chunk's pixels change (painting,
+  /// blits, blasts, simulation, seeding), and never repeats for a position,
+  /// so caches of chunk-derived data can be keyed by it.
+  /// Returns None if the chunk is not loaded or not yet seeded.
+  pub fn chunk_generation(&self, pos: crate::pixel_world::coords::ChunkPos) -> Option<u64> {
+    let slot = self.pool.get(self.pool.index_for(pos)?);
+    slot.is_seeded().then_some(slot.generation)
+  }
+
   /// Returns the heat value at the given world position.
   ///
   /// Maps the pixel position to its heat cell (4x4 downsampling).
@@ -186,4 +203,29 @@ impl PixelWorld {
       .chunk
       .mark_pixel_dirty(local_pos.x as u32, local_pos.y as u32);
   }
+
+  /// Marks the collision tiles that can see a world position for rebuild.
+  ///
+  /// [`set_pixel`](Self::set_pixel) leaves collision meshes alone; call this
+  /// after a write that changes whether a pixel is solid.
+  pub fn mark_pixel_collision_dirty(&mut self, pos: WorldPos) {
+    // Meshes sample a 1px border, so every tile of the 3x3 neighborhood
+    // may include this pixel
+    for dy in -1..=1 {
+      for dx in -1..=1 {
+        let (chunk_pos, local_pos) = WorldPos::new(pos.x + dx, pos.y + dy).to_chunk_and_local();
+        let Some(idx) = self.pool.index_for(chunk_pos) else {
+          continue;
+        };
+        let slot = self.pool.get_mut(idx);
+        if !slot.is_seeded() {
+          continue;
+        }
+        slot.chunk.mark_tile_collision_dirty(
+          local_pos.x as u32 / TILE_SIZE,
+          local_pos.y as u32 / TILE_SIZE,
+        );
+      }
+    }
+  }
 }
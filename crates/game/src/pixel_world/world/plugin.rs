@@ -2,32 +2,43 @@
 //!
 //! Provides automatic chunk streaming, seeding, and GPU upload.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use bevy::prelude::*;
 // WASM compat: std::time::Instant panics on wasm32
 use web_time::Instant;
 
 use super::control::{
-  ClearPersistence, FreshReseedAllChunks, PersistenceComplete, ReloadAllChunks, RequestPersistence,
-  ReseedAllChunks, SimulationState, UpdateSeeder,
+  CancelPendingTasks, ClearPersistence, FreshReseedAllChunks, PersistenceComplete, ReloadAllChunks,
+  RequestPersistence, ReseedAllChunks, SimulationState, UpdateSeeder,
+};
+use super::fog::{
+  ExploredChunks, FogOfWarConfig, queue_explored_save, reveal_explored_chunks,
+  update_fog_brightness,
 };
 use super::persistence_systems::{
   LoadedChunkDataStore, dispatch_chunk_loads, dispatch_save_task, flush_persistence_queue,
   handle_clear_persistence, handle_persistence_messages, notify_persistence_complete,
   poll_chunk_loads, poll_io_results, poll_save_task, process_pending_save_requests,
+  track_unsaved_changes,
 };
+use super::slot::ChunkLifecycle;
 use super::streaming::poll_seeding_tasks;
 use super::streaming::{
-  CullingConfig, SeedingTasks, clear_chunk_tracking, dispatch_seeding, handle_fresh_reseed_request,
+  ChunkSeededMsg, CullingConfig, SeedDeterminismCheck, SeedMismatch, SeedingTasks,
+  clear_chunk_tracking, dispatch_seeding, handle_cancel_tasks, handle_fresh_reseed_request,
   handle_reload_request, handle_reseed_request, handle_update_seeder, update_entity_culling,
   update_simulation_bounds, update_streaming_windows,
 };
-pub use super::streaming::{SeededChunks, StreamingCamera, UnloadingChunks};
+pub use super::streaming::{SeededChunks, SimulationViewport, StreamingCamera, UnloadingChunks};
 pub(crate) use super::streaming::{SharedChunkMesh, SharedPaletteTexture};
-use super::systems::upload_dirty_chunks;
+use super::systems::{defer_offscreen_uploads, upload_dirty_chunks};
 use super::{
-  PersistenceInitialized, PixelWorld, WorldInitState, WorldLoadingProgress, WorldReady,
-  world_is_ready,
+  AsyncTaskCounts, PersistenceInitialized, PixelWorld, WorldInitState, WorldLoadingProgress,
+  WorldReady, world_is_ready,
 };
+use crate::pixel_world::DefaultPersistenceConfig;
 use crate::pixel_world::coords::CHUNK_SIZE;
 use crate::pixel_world::debug_shim;
 use crate::pixel_world::material::Materials;
@@ -40,10 +51,12 @@ use crate::pixel_world::palette::{
 use crate::pixel_world::persistence::PersistenceTasks;
 use crate::pixel_world::persistence::io_worker::IoDispatcher;
 use crate::pixel_world::persistence::tasks::{LoadingChunks, SavingChunks};
-use crate::pixel_world::render::create_chunk_quad;
-use crate::pixel_world::schedule::{PixelWorldSet, SimulationPhase};
+use crate::pixel_world::render::{create_chunk_quad, update_crack_overlay, update_depth_ramps};
+use crate::pixel_world::schedule::{CATickStep, PixelSimulation, PixelWorldSet, SimulationPhase};
 use crate::pixel_world::simulation;
-use crate::pixel_world::simulation::{HeatConfig, SimulationConfig};
+use crate::pixel_world::simulation::{
+  GravityWells, HeatConfig, SimulationConfig, TickPacing, Wind,
+};
 
 /// Marker resource indicating rendering infrastructure is available.
 /// Inserted by PixelWorldPlugin when RenderPlugin is detected.
@@ -95,13 +108,21 @@ impl Plugin for PixelWorldStreamingPlugin {
       .init_resource::<CullingConfig>()
       .init_resource::<UnloadingChunks>()
       .init_resource::<SeededChunks>()
+      .init_resource::<SeedDeterminismCheck>()
       .init_resource::<SimulationState>()
       .init_resource::<crate::pixel_world::diagnostics::SimulationMetrics>()
       .init_resource::<SimulationConfig>()
+      .init_resource::<SimulationClock>()
+      .init_resource::<TickPlans>()
+      .init_resource::<GravityWells>()
+      .init_resource::<Wind>()
       .init_resource::<HeatConfig>()
+      .init_resource::<FogOfWarConfig>()
+      .init_resource::<ExploredChunks>()
       // World initialization state tracking
       .init_resource::<WorldInitState>()
       .init_resource::<WorldLoadingProgress>()
+      .init_resource::<AsyncTaskCounts>()
       .add_message::<PersistenceInitialized>()
       .add_message::<WorldReady>()
       .add_message::<RequestPersistence>()
@@ -109,8 +130,11 @@ impl Plugin for PixelWorldStreamingPlugin {
       .add_message::<ReseedAllChunks>()
       .add_message::<ReloadAllChunks>()
       .add_message::<ClearPersistence>()
+      .add_message::<CancelPendingTasks>()
       .add_message::<UpdateSeeder>()
-      .add_message::<FreshReseedAllChunks>();
+      .add_message::<FreshReseedAllChunks>()
+      .add_message::<SeedMismatch>()
+      .add_message::<ChunkSeededMsg>();
 
     // Configure set ordering: Pre → Sim → Post
     app.configure_sets(
@@ -123,7 +147,8 @@ impl Plugin for PixelWorldStreamingPlugin {
         .chain(),
     );
 
-    // Configure simulation sub-phases
+    // Per-frame hooks around all of the frame's ticks, for systems that
+    // still add themselves to the phases in `Update`
     app.configure_sets(
       Update,
       (
@@ -135,6 +160,32 @@ impl Plugin for PixelWorldStreamingPlugin {
         .in_set(PixelWorldSet::Simulation),
     );
 
+    // One simulation tick: blit → CA → readback
+    app.init_schedule(PixelSimulation);
+    app.configure_sets(
+      PixelSimulation,
+      (
+        SimulationPhase::BeforeCATick,
+        SimulationPhase::CATick,
+        SimulationPhase::AfterCATick,
+      )
+        .chain(),
+    );
+    app.configure_sets(
+      PixelSimulation,
+      (
+        CATickStep::Physics,
+        CATickStep::AfterPhysics,
+        CATickStep::Burning,
+        CATickStep::AfterBurning,
+        CATickStep::Heat,
+      )
+        .chain()
+        .in_set(SimulationPhase::CATick)
+        .distributive_run_if(simulation_not_paused)
+        .distributive_run_if(world_is_ready),
+    );
+
     app.add_systems(
       PreStartup,
       setup_shared_resources.run_if(resource_exists::<RenderingEnabled>),
@@ -152,6 +203,8 @@ impl Plugin for PixelWorldStreamingPlugin {
         handle_persistence_messages,
         update_streaming_windows,
         update_entity_culling,
+        // Drop stale task results before anything new is dispatched
+        handle_cancel_tasks,
         // Async persistence loading: dispatch loads for new chunks, poll completed loads
         dispatch_chunk_loads,
         poll_chunk_loads,
@@ -173,13 +226,29 @@ impl Plugin for PixelWorldStreamingPlugin {
         .in_set(PixelWorldSet::PreSimulation),
     );
 
-    // Core simulation system - only runs when world is ready
+    // Marquee signs are stamped before the CA tick so they simulate this frame
+    app.add_systems(
+      Update,
+      crate::pixel_world::text::update_scrolling_text
+        .after(update_simulation_bounds)
+        .in_set(PixelWorldSet::PreSimulation),
+    );
+
+    // Drives the tick schedule according to the configured pacing
     app.add_systems(
       Update,
-      run_simulation
-        .run_if(simulation_not_paused)
-        .run_if(world_is_ready)
-        .in_set(SimulationPhase::CATick),
+      run_simulation_ticks.in_set(SimulationPhase::CATick),
+    );
+
+    // Core simulation passes - step sets only run when world is ready
+    app.add_systems(
+      PixelSimulation,
+      (
+        simulation::collect_gravity_wells.in_set(SimulationPhase::BeforeCATick),
+        run_physics_step.in_set(CATickStep::Physics),
+        run_burning_step.in_set(CATickStep::Burning),
+        run_heat_step.in_set(CATickStep::Heat),
+      ),
     );
 
     // Core post-simulation systems (persistence flush)
@@ -187,17 +256,45 @@ impl Plugin for PixelWorldStreamingPlugin {
       Update,
       (
         process_pending_save_requests,
+        queue_explored_save,
         // Async persistence saving: dispatch save task, poll completion
         dispatch_save_task,
         poll_save_task,
         // Legacy sync flush (for copy-on-write and immediate flushes)
         flush_persistence_queue,
         notify_persistence_complete,
+        track_unsaved_changes,
+        update_async_task_counts,
       )
         .chain()
         .in_set(PixelWorldSet::PostSimulation),
     );
 
+    // Upload deferral runs headless too, so stale tracking is consistent
+    app.add_systems(
+      Update,
+      defer_offscreen_uploads
+        .before(upload_dirty_chunks)
+        .in_set(PixelWorldSet::PostSimulation),
+    );
+
+    // Fog of war: reveal around the camera, dim unexplored chunks
+    app.add_systems(
+      Update,
+      (
+        reveal_explored_chunks
+          .after(update_streaming_windows)
+          .in_set(PixelWorldSet::PreSimulation),
+        update_fog_brightness.in_set(PixelWorldSet::PostSimulation),
+      ),
+    );
+
+    // Crack overlay for chipped pixels and liquid depth ramps
+    app.add_systems(
+      Update,
+      (update_crack_overlay, update_depth_ramps).in_set(PixelWorldSet::PostSimulation),
+    );
+
     // Palette hot-reload system (runs always to handle config changes)
     app.add_systems(
       Update,
@@ -207,6 +304,15 @@ impl Plugin for PixelWorldStreamingPlugin {
         .in_set(PixelWorldSet::PreSimulation),
     );
 
+    // Material color changes patch the palette instead of rebuilding it
+    app.add_systems(
+      Update,
+      sync_palette_with_materials
+        .after(watch_palette_config)
+        .before(poll_lut_task)
+        .in_set(PixelWorldSet::PreSimulation),
+    );
+
     // LUT polling system - runs after watch_palette_config
     app.add_systems(
       Update,
@@ -221,7 +327,7 @@ impl Plugin for PixelWorldStreamingPlugin {
       Update,
       (
         upload_palette_if_dirty
-          .after(watch_palette_config)
+          .after(sync_palette_with_materials)
           .before(update_streaming_windows)
           .in_set(PixelWorldSet::PreSimulation),
         upload_dirty_chunks.in_set(PixelWorldSet::PostSimulation),
@@ -254,6 +360,9 @@ fn setup_shared_resources(world: &mut World) {
 
 /// System: Initializes or updates the palette texture when GlobalPalette is
 /// dirty.
+///
+/// Entries changed through `GlobalPalette::set_color` are patched
+/// individually once the texture is initialized.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
 fn upload_palette_if_dirty(
   mut palette_texture: ResMut<SharedPaletteTexture>,
@@ -264,18 +373,59 @@ fn upload_palette_if_dirty(
     return;
   };
 
-  // Check if palette needs upload (dirty flag or not yet initialized)
+  // Full upload when dirty or not yet initialized, else patch changed entries
   if !global_palette.dirty && palette_texture.initialized {
+    let changed = global_palette.take_changed_colors();
+    if !changed.is_empty()
+      && let Some(image) = images.get_mut(&palette_texture.handle)
+    {
+      crate::pixel_world::palette::upload_palette_entries(global_palette.as_ref(), image, &changed);
+    }
     return;
   }
 
   if let Some(image) = images.get_mut(&palette_texture.handle) {
     crate::pixel_world::palette::upload_palette(global_palette.as_ref(), image);
+    global_palette.take_changed_colors();
     global_palette.dirty = false;
     palette_texture.initialized = true;
   }
 }
 
+/// System: Patches the palette when material colors change.
+///
+/// Only palette entries whose color differs are updated, and the LUT is
+/// patched incrementally where possible. Skipped while the palette comes
+/// from a `PaletteConfig`.
+#[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
+fn sync_palette_with_materials(
+  materials: Option<Res<Materials>>,
+  global_palette: Option<ResMut<GlobalPalette>>,
+) {
+  let (Some(materials), Some(mut global_palette)) = (materials, global_palette) else {
+    return;
+  };
+  if !materials.is_changed() || materials.is_added() || global_palette.config_handle.is_some() {
+    return;
+  }
+
+  let changed = global_palette.update_from_materials(&materials);
+  if changed.is_empty() {
+    return;
+  }
+  if global_palette.patch_lut() {
+    debug!(
+      "Palette patched {} entries (incremental LUT update)",
+      changed.len()
+    );
+  } else {
+    debug!(
+      "Palette updated {} entries (LUT rebuild pending)",
+      changed.len()
+    );
+  }
+}
+
 /// System: Watches for PaletteConfig asset changes and rebuilds the palette.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
 fn watch_palette_config(
@@ -454,14 +604,127 @@ fn poll_lut_task(
   }
 }
 
-/// System: Runs cellular automata simulation on all pixel worlds.
+/// Real time not yet consumed by simulation ticks under
+/// [`TickPacing::Fixed`].
+#[derive(Resource, Default)]
+pub(crate) struct SimulationClock {
+  accumulated: f64,
+}
+
+impl SimulationClock {
+  /// Advances the clock by `delta` seconds and returns how many ticks are due.
+  fn advance(&mut self, delta: f64, config: &SimulationConfig) -> u32 {
+    match config.pacing {
+      TickPacing::PerFrame => 1,
+      TickPacing::Fixed {
+        max_ticks_per_frame,
+      } => {
+        let step = 1.0 / config.physics_tps.max(f32::EPSILON) as f64;
+        self.accumulated += delta;
+        let due = (self.accumulated / step).floor() as u32;
+        let ticks = due.min(max_ticks_per_frame);
+        // Backlog beyond the cap is dropped so a slow frame can't snowball
+        self.accumulated = if due > ticks {
+          0.0
+        } else {
+          self.accumulated - ticks as f64 * step
+        };
+        ticks
+      }
+    }
+  }
+}
+
+/// Exclusive system: Runs the [`PixelSimulation`] schedule once per due
+/// simulation tick.
+fn run_simulation_ticks(world: &mut World) {
+  let delta = world.resource::<Time>().delta_secs_f64();
+  let config = world.resource::<SimulationConfig>().clone();
+  let ticks = world
+    .resource_mut::<SimulationClock>()
+    .advance(delta, &config);
+
+  for _ in 0..ticks {
+    world.run_schedule(PixelSimulation);
+  }
+}
+
+/// Per-world state of the simulation tick in progress, handed from
+/// [`run_physics_step`] to the later steps.
+#[derive(Resource, Default)]
+pub(crate) struct TickPlans {
+  plans: HashMap<Entity, (simulation::TickPlan, Duration)>,
+}
+
+/// System: Starts a simulation tick and runs physics on all pixel worlds.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
-fn run_simulation(
-  mut worlds: Query<&mut PixelWorld>,
+fn run_physics_step(
+  mut worlds: Query<(Entity, &mut PixelWorld)>,
+  mat_registry: Option<Res<Materials>>,
+  gravity: Res<GravityWells>,
+  wind: Res<Wind>,
+  sim_config: Res<SimulationConfig>,
+  gizmos: debug_shim::GizmosParam,
+  mut plans: ResMut<TickPlans>,
+) {
+  plans.plans.clear();
+  let Some(materials) = mat_registry else {
+    return;
+  };
+
+  let debug_gizmos = gizmos.get();
+
+  for (entity, mut world) in worlds.iter_mut() {
+    if !world.simulate() {
+      continue;
+    }
+    let start = Instant::now();
+    let plan = simulation::begin_tick(&mut world);
+    simulation::step_physics(
+      &mut world,
+      &plan,
+      &materials,
+      &gravity,
+      &wind,
+      debug_gizmos,
+      &sim_config,
+    );
+    plans.plans.insert(entity, (plan, start.elapsed()));
+  }
+}
+
+/// System: Runs burning on all pixel worlds ticked by [`run_physics_step`].
+#[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
+fn run_burning_step(
+  mut worlds: Query<(Entity, &mut PixelWorld)>,
   mat_registry: Option<Res<Materials>>,
   sim_config: Res<SimulationConfig>,
   heat_config: Res<HeatConfig>,
+  mut plans: ResMut<TickPlans>,
+) {
+  let Some(materials) = mat_registry else {
+    return;
+  };
+
+  for (entity, mut world) in worlds.iter_mut() {
+    let Some((plan, elapsed)) = plans.plans.get_mut(&entity) else {
+      continue;
+    };
+    let start = Instant::now();
+    simulation::step_burning(&mut world, plan, &materials, &sim_config, &heat_config);
+    *elapsed += start.elapsed();
+  }
+}
+
+/// System: Runs heat on all pixel worlds ticked by [`run_physics_step`] and
+/// records the tick's simulation time.
+#[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
+fn run_heat_step(
+  mut worlds: Query<(Entity, &mut PixelWorld)>,
+  mat_registry: Option<Res<Materials>>,
+  sim_config: Res<SimulationConfig>,
   gizmos: debug_shim::GizmosParam,
+  mut plans: ResMut<TickPlans>,
   mut sim_metrics: ResMut<crate::pixel_world::diagnostics::SimulationMetrics>,
 ) {
   let Some(materials) = mat_registry else {
@@ -470,19 +733,24 @@ fn run_simulation(
 
   let debug_gizmos = gizmos.get();
 
-  let start = Instant::now();
-
-  for mut world in worlds.iter_mut() {
-    simulation::simulate_tick(
+  let mut total = Duration::ZERO;
+  for (entity, mut world) in worlds.iter_mut() {
+    let Some((plan, elapsed)) = plans.plans.remove(&entity) else {
+      continue;
+    };
+    let start = Instant::now();
+    simulation::step_heat(
       &mut world,
+      &plan,
       &materials,
       debug_gizmos,
       &sim_config,
       &heat_config,
     );
+    total += elapsed + start.elapsed();
   }
 
-  let elapsed_ms = start.elapsed().as_secs_f32() * 1000.0;
+  let elapsed_ms = total.as_secs_f32() * 1000.0;
   sim_metrics.sim_time.push(elapsed_ms);
 }
 
@@ -499,9 +767,15 @@ fn simulation_not_paused(state: Res<SimulationState>) -> bool {
 /// is ready.
 ///
 /// This runs after `poll_io_results` which sets `IoDispatcher::is_ready()`.
+///
+/// When seeding is not deferred (see `PersistenceConfig::defer_seeding`),
+/// chunks seeded procedurally during initialization are sent back to
+/// `Loading` so their saved data, if any, replaces the fallback.
 fn transition_to_loading_chunks(
   io_dispatcher: Option<Res<IoDispatcher>>,
+  persistence_config: Option<Res<DefaultPersistenceConfig>>,
   mut state: ResMut<WorldInitState>,
+  mut worlds: Query<&mut PixelWorld>,
   mut events: bevy::ecs::message::MessageWriter<PersistenceInitialized>,
 ) {
   if *state != WorldInitState::Initializing {
@@ -515,6 +789,28 @@ fn transition_to_loading_chunks(
   if dispatcher.is_ready() {
     *state = WorldInitState::LoadingChunks;
 
+    let defer_seeding = persistence_config
+      .as_ref()
+      .is_none_or(|c| c.0.defer_seeding);
+    if !defer_seeding {
+      let mut count = 0;
+      for mut world in &mut worlds {
+        for (_pos, slot_idx) in world.active_chunks().collect::<Vec<_>>() {
+          let slot = world.slot_mut(slot_idx);
+          if matches!(
+            slot.lifecycle,
+            ChunkLifecycle::Seeding | ChunkLifecycle::Active
+          ) {
+            slot.lifecycle = ChunkLifecycle::Loading;
+            count += 1;
+          }
+        }
+      }
+      if count > 0 {
+        debug!("Re-fetching {} fallback-seeded chunks from save", count);
+      }
+    }
+
     // Emit PersistenceInitialized event with counts from IoDispatcher
     let (chunk_count, body_count) = dispatcher.init_counts();
     events.write(PersistenceInitialized {
@@ -533,6 +829,10 @@ fn transition_to_loading_chunks(
 /// 1. There is at least one active chunk (not loading/seeding)
 /// 2. No chunks are being loaded from disk
 /// 3. No chunks are being seeded
+///
+/// With [`PixelWorldConfig::ready_radius`](super::PixelWorldConfig::ready_radius)
+/// set, only chunks within that radius of the streaming center must be
+/// seeded; the rest of the window keeps streaming in afterwards.
 fn transition_to_ready(
   mut state: ResMut<WorldInitState>,
   loading: Res<LoadingChunks>,
@@ -544,11 +844,17 @@ fn transition_to_ready(
     return;
   }
 
-  // Check if any world has active chunks and nothing is in-flight
+  // Check if any world has its initial chunks seeded
   for world in &worlds {
-    let has_active_chunks = world.active_count() > 0;
-    let no_loading = loading.is_empty();
-    let no_seeding = seeding_tasks.is_empty();
+    let ready = match world.config().ready_radius {
+      Some(radius) => nearby_chunks_seeded(world, radius),
+      None => {
+        let has_active_chunks = world.active_count() > 0;
+        let no_loading = loading.is_empty();
+        let no_seeding = seeding_tasks.is_empty();
+        has_active_chunks && no_loading && no_seeding
+      }
+    };
 
     // Count how many chunks are actually active (not loading/seeding)
     let active_chunk_count = world
@@ -556,7 +862,7 @@ fn transition_to_ready(
       .filter(|(_, idx)| world.slot(*idx).is_seeded())
       .count();
 
-    if has_active_chunks && no_loading && no_seeding && active_chunk_count > 0 {
+    if ready && active_chunk_count > 0 {
       *state = WorldInitState::Ready;
       events.write(WorldReady);
       info!(
@@ -568,6 +874,33 @@ fn transition_to_ready(
   }
 }
 
+/// Returns true if every chunk within `radius` chunks of the streaming
+/// center is seeded.
+fn nearby_chunks_seeded(world: &PixelWorld, radius: u32) -> bool {
+  let center = world.center();
+  let mut nearby = world
+    .active_chunks()
+    .filter(|(pos, _)| {
+      (pos.x - center.x).unsigned_abs() <= radius && (pos.y - center.y).unsigned_abs() <= radius
+    })
+    .peekable();
+  nearby.peek().is_some() && nearby.all(|(_, idx)| world.slot(idx).is_seeded())
+}
+
+/// System: Refreshes the in-flight async task counts.
+fn update_async_task_counts(
+  mut counts: ResMut<AsyncTaskCounts>,
+  seeding_tasks: Res<SeedingTasks>,
+  loading: Res<LoadingChunks>,
+  saving: Res<SavingChunks>,
+  collision_tasks: Option<Res<crate::pixel_world::collision::CollisionTasks>>,
+) {
+  counts.set_seeding_by_world(seeding_tasks.count_by_world());
+  counts.loading = loading.len();
+  counts.saving = saving.is_busy();
+  counts.collision = collision_tasks.map_or(0, |tasks| tasks.len());
+}
+
 /// System: Updates the loading progress metrics.
 fn update_loading_progress(
   mut progress: ResMut<WorldLoadingProgress>,
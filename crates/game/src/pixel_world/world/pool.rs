@@ -6,10 +6,10 @@
 use std::collections::HashMap;
 
 use super::slot::{ChunkSlot, SlotIndex};
-use crate::pixel_world::coords::{ChunkPos, POOL_SIZE};
+use crate::pixel_world::coords::ChunkPos;
 use crate::pixel_world::primitives::Chunk;
 
-/// Fixed-size pool of chunk slots.
+/// Fixed-capacity pool of chunk slots.
 ///
 /// Manages slot allocation and provides indexed access to chunks.
 /// Encapsulates the active chunk position mapping and unsafe pointer
@@ -19,15 +19,19 @@ pub(crate) struct ChunkPool {
   slots: Vec<ChunkSlot>,
   /// Maps active chunk positions to slot indices.
   active: HashMap<ChunkPos, SlotIndex>,
+  /// Last chunk generation handed out. Shared by all slots, so a chunk
+  /// never sees the same generation twice, even across slot reuse.
+  generation: u64,
 }
 
 impl ChunkPool {
-  /// Creates a new chunk pool with pre-allocated slots.
-  pub fn new() -> Self {
-    let slots = (0..POOL_SIZE).map(|_| ChunkSlot::new()).collect();
+  /// Creates a new chunk pool with `capacity` pre-allocated slots.
+  pub fn new(capacity: usize) -> Self {
+    let slots = (0..capacity).map(|_| ChunkSlot::new()).collect();
     Self {
       slots,
       active: HashMap::new(),
+      generation: 0,
     }
   }
 
@@ -43,6 +47,11 @@ impl ChunkPool {
     None
   }
 
+  /// Returns the total number of slots.
+  pub fn capacity(&self) -> usize {
+    self.slots.len()
+  }
+
   /// Gets a reference to a slot by index.
   #[inline]
   pub fn get(&self, index: SlotIndex) -> &ChunkSlot {
@@ -55,6 +64,13 @@ impl ChunkPool {
     &mut self.slots[index.0]
   }
 
+  /// Gives the slot's chunk a new, higher generation.
+  #[inline]
+  pub fn bump_generation(&mut self, index: SlotIndex) {
+    self.generation += 1;
+    self.slots[index.0].generation = self.generation;
+  }
+
   /// Gets the slot index for an active chunk position.
   pub fn index_for(&self, pos: ChunkPos) -> Option<SlotIndex> {
     self.active.get(&pos).copied()
@@ -132,9 +148,3 @@ impl ChunkPool {
     chunks
   }
 }
-
-impl Default for ChunkPool {
-  fn default() -> Self {
-    Self::new()
-  }
-}
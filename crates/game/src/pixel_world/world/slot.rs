@@ -47,17 +47,25 @@ pub struct ChunkSlot {
   /// Whether the chunk's CPU data differs from GPU texture.
   /// When true, the chunk needs upload.
   pub dirty: bool,
+  /// Whether the GPU texture is out of date because uploads were deferred
+  /// while the chunk was outside the camera view.
+  pub gpu_stale: bool,
   /// Whether the chunk has been modified by user actions (paint, erase,
   /// swap). Set when modified, cleared when saved to disk.
   pub modified: bool,
   /// Whether the chunk has been persisted to disk since last modification.
   pub persisted: bool,
+  /// Content generation, bumped whenever the chunk's pixels change
+  /// (see [`PixelWorld::chunk_generation`](super::PixelWorld::chunk_generation)).
+  pub generation: u64,
   /// Entity displaying this chunk (when active).
   pub entity: Option<Entity>,
   /// Texture handle for GPU upload.
   pub texture: Option<Handle<Image>>,
   /// Material handle (for bind group refresh workaround).
   pub material: Option<Handle<ChunkMaterial>>,
+  /// Heat grid texture, present only when the heat texture is enabled.
+  pub heat_texture: Option<Handle<Image>>,
 }
 
 impl ChunkSlot {
@@ -68,11 +76,14 @@ impl ChunkSlot {
       lifecycle: ChunkLifecycle::InPool,
       pos: None,
       dirty: false,
+      gpu_stale: false,
       modified: false,
       persisted: false,
+      generation: 0,
       entity: None,
       texture: None,
       material: None,
+      heat_texture: None,
     }
   }
 
@@ -108,6 +119,7 @@ impl ChunkSlot {
     self.pos = Some(pos);
     self.chunk.set_pos(pos);
     self.dirty = false;
+    self.gpu_stale = false;
     self.modified = false;
     self.persisted = false;
   }
@@ -122,6 +134,7 @@ impl ChunkSlot {
     self.pos = Some(pos);
     self.chunk.set_pos(pos);
     self.dirty = false;
+    self.gpu_stale = false;
     self.modified = false;
     self.persisted = false;
   }
@@ -137,6 +150,7 @@ impl ChunkSlot {
     self.lifecycle = ChunkLifecycle::InPool;
     self.pos = None;
     self.dirty = false;
+    self.gpu_stale = false;
     self.modified = false;
     self.persisted = false;
     self.entity = None;
@@ -0,0 +1,364 @@
+//! Freeze/thaw of the cellular simulation for `PixelWorld`.
+//!
+//! [`PixelWorld::freeze`] stops simulation for the world and captures a
+//! [`SimulationSnapshot`]: every seeded chunk's pixels (including `FALLING`
+//! and `DIRTY` flags), tile dirty rects with their cooldowns, the heat layer,
+//! and the simulation tick. [`PixelWorld::thaw`] puts the motion state back
+//! onto the current pixels and resumes, so the next tick behaves exactly as
+//! the tick after the freeze would have, with no re-settling from recomputed
+//! dirty state, while edits made in between are kept.
+//! [`PixelWorld::restore`] instead writes the whole captured state back,
+//! e.g. to reproduce a crash in a fresh world.
+//!
+//! Pixel bodies are not part of the snapshot. Body pixels are excluded when
+//! capturing and left untouched when restoring, matching chunk persistence.
+//!
+//! [`PixelWorld::state_hash`] hashes the same state, so two runs can be
+//! compared without keeping a snapshot of either.
+
+use std::io::{self, Read, Write};
+
+use super::PixelWorld;
+use crate::pixel_world::coords::{
+  CHUNK_SIZE, ChunkPos, ColorIndex, MaterialId, TILES_PER_CHUNK, WorldPos,
+};
+use crate::pixel_world::pixel::{Pixel, PixelFlags};
+use crate::pixel_world::primitives::{HEAT_GRID_SIZE, HeatDirtyTracker, TileDirtyRect};
+
+/// Magic bytes identifying a serialized snapshot ("PWSN").
+const MAGIC: u32 = 0x4E53_5750;
+
+/// Current snapshot format version.
+const VERSION: u16 = 1;
+
+const PIXEL_COUNT: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+const TILE_COUNT: usize = (TILES_PER_CHUNK * TILES_PER_CHUNK) as usize;
+const HEAT_CELL_COUNT: usize = (HEAT_GRID_SIZE * HEAT_GRID_SIZE) as usize;
+
+/// Simulation state of a single chunk.
+#[derive(Clone)]
+struct ChunkSnapshot {
+  pos: ChunkPos,
+  pixels: Vec<Pixel>,
+  dirty_rects: Vec<TileDirtyRect>,
+  heat: Vec<u8>,
+  heat_dirty: HeatDirtyTracker,
+}
+
+/// Frozen simulation state captured by [`PixelWorld::freeze`].
+///
+/// Can be serialized with [`write_to`](Self::write_to) and read back with
+/// [`read_from`](Self::read_from) to resume a mid-fall world later.
+#[derive(Clone)]
+pub struct SimulationSnapshot {
+  tick: u64,
+  chunks: Vec<ChunkSnapshot>,
+}
+
+impl SimulationSnapshot {
+  /// Returns the simulation tick at which the snapshot was taken.
+  pub fn tick(&self) -> u64 {
+    self.tick
+  }
+
+  /// Returns the positions of all chunks in the snapshot.
+  pub fn chunk_positions(&self) -> impl Iterator<Item = ChunkPos> + '_ {
+    self.chunks.iter().map(|c| c.pos)
+  }
+
+  /// Returns the captured pixel at `pos`, or None if its chunk is not in
+  /// the snapshot.
+  pub fn get_pixel(&self, pos: WorldPos) -> Option<Pixel> {
+    let (chunk_pos, local) = pos.to_chunk_and_local();
+    let chunk = self.chunks.iter().find(|c| c.pos == chunk_pos)?;
+    let index = local.y as usize * CHUNK_SIZE as usize + local.x as usize;
+    chunk.pixels.get(index).copied()
+  }
+
+  /// Returns a hash of the captured state, equal for equal snapshots.
+  ///
+  /// Uses FNV-1a over the serialized snapshot, which is stable across
+  /// platforms and runs.
+  pub fn state_hash(&self) -> u64 {
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    self
+      .write_to(&mut hasher)
+      .expect("hashing never fails to write");
+    hasher.0
+  }
+
+  /// Writes the snapshot to a writer.
+  pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+    writer.write_all(&MAGIC.to_le_bytes())?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&self.tick.to_le_bytes())?;
+    writer.write_all(&(self.chunks.len() as u32).to_le_bytes())?;
+
+    for chunk in &self.chunks {
+      writer.write_all(&chunk.pos.x.to_le_bytes())?;
+      writer.write_all(&chunk.pos.y.to_le_bytes())?;
+
+      let mut pixels = Vec::with_capacity(PIXEL_COUNT * 4);
+      for p in &chunk.pixels {
+        pixels.extend_from_slice(&[p.material.0, p.color.0, p.damage, p.flags_bits()]);
+      }
+      writer.write_all(&pixels)?;
+
+      for rect in &chunk.dirty_rects {
+        writer.write_all(&rect.encode())?;
+      }
+      writer.write_all(&chunk.heat)?;
+      writer.write_all(&chunk.heat_dirty.encode())?;
+    }
+
+    Ok(())
+  }
+
+  /// Reads a snapshot written by [`write_to`](Self::write_to).
+  pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+    let mut header = [0u8; 18];
+    reader.read_exact(&mut header)?;
+    let magic = u32::from_le_bytes(header[0..4].try_into().unwrap());
+    if magic != MAGIC {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid snapshot magic: {magic:#x}"),
+      ));
+    }
+    let version = u16::from_le_bytes([header[4], header[5]]);
+    if version > VERSION {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unsupported snapshot version: {version}"),
+      ));
+    }
+    let tick = u64::from_le_bytes(header[6..14].try_into().unwrap());
+    let chunk_count = u32::from_le_bytes(header[14..18].try_into().unwrap()) as usize;
+
+    // The count is untrusted, so chunks are collected as they are read
+    let mut chunks = Vec::new();
+    for _ in 0..chunk_count {
+      let mut pos = [0u8; 8];
+      reader.read_exact(&mut pos)?;
+      let pos = ChunkPos::new(
+        i32::from_le_bytes(pos[0..4].try_into().unwrap()),
+        i32::from_le_bytes(pos[4..8].try_into().unwrap()),
+      );
+
+      let mut raw = vec![0u8; PIXEL_COUNT * 4];
+      reader.read_exact(&mut raw)?;
+      let pixels = raw
+        .chunks_exact(4)
+        .map(|b| Pixel {
+          material: MaterialId(b[0]),
+          color: ColorIndex(b[1]),
+          damage: b[2],
+          flags: PixelFlags::from_bits_truncate(b[3]),
+        })
+        .collect();
+
+      let mut dirty_rects = Vec::with_capacity(TILE_COUNT);
+      let mut rect = [0u8; TileDirtyRect::ENCODED_SIZE];
+      for _ in 0..TILE_COUNT {
+        reader.read_exact(&mut rect)?;
+        dirty_rects.push(TileDirtyRect::decode(&rect));
+      }
+
+      let mut heat = vec![0u8; HEAT_CELL_COUNT];
+      reader.read_exact(&mut heat)?;
+
+      let mut heat_dirty = vec![0u8; HeatDirtyTracker::ENCODED_SIZE];
+      reader.read_exact(&mut heat_dirty)?;
+
+      chunks.push(ChunkSnapshot {
+        pos,
+        pixels,
+        dirty_rects,
+        heat,
+        heat_dirty: HeatDirtyTracker::decode(&heat_dirty),
+      });
+    }
+
+    Ok(Self { tick, chunks })
+  }
+}
+
+/// FNV-1a hasher fed through [`Write`].
+struct Fnv1a(u64);
+
+impl Write for Fnv1a {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    for &byte in buf {
+      self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+impl PixelWorld {
+  /// Returns a hash of the world's simulation state: the pixels, dirty
+  /// tracking and heat of every seeded chunk, and the tick.
+  ///
+  /// Equal for two worlds that would produce equal snapshots, so it can
+  /// check that a replayed run ends exactly where the original did.
+  pub fn state_hash(&self) -> u64 {
+    self.snapshot().state_hash()
+  }
+
+  /// Stops simulating this world and captures its simulation state.
+  ///
+  /// Turns off [`simulate`](Self::simulate). Unlike pausing via
+  /// [`SimulationState`](super::control::SimulationState), the returned
+  /// snapshot preserves per-pixel motion flags and dirty tracking, so
+  /// [`thaw`](Self::thaw) resumes exactly where the simulation stopped, even
+  /// after the world has been edited, saved, or reloaded.
+  pub fn freeze(&mut self) -> SimulationSnapshot {
+    self.simulate = false;
+    self.snapshot()
+  }
+
+  /// Captures the simulation state like [`freeze`](Self::freeze), but keeps
+  /// simulating.
+  pub fn snapshot(&self) -> SimulationSnapshot {
+    let mut chunks: Vec<ChunkSnapshot> = self
+      .pool
+      .iter_active()
+      .filter_map(|(pos, idx)| {
+        let slot = self.pool.get(idx);
+        if !slot.is_seeded() {
+          return None;
+        }
+        let chunk = &slot.chunk;
+        let pixels = chunk
+          .pixels
+          .as_slice()
+          .iter()
+          .map(|p| {
+            if p.flags.contains(PixelFlags::PIXEL_BODY) {
+              Pixel::VOID
+            } else {
+              *p
+            }
+          })
+          .collect();
+        Some(ChunkSnapshot {
+          pos,
+          pixels,
+          dirty_rects: chunk.tile_dirty_rects().to_vec(),
+          heat: chunk.heat.to_vec(),
+          heat_dirty: chunk.heat_dirty.clone(),
+        })
+      })
+      .collect();
+    // Stable order keeps serialized snapshots reproducible
+    chunks.sort_by_key(|c| (c.pos.y, c.pos.x));
+
+    SimulationSnapshot {
+      tick: self.tick,
+      chunks,
+    }
+  }
+
+  /// Resumes simulation from a snapshot taken by [`freeze`](Self::freeze).
+  ///
+  /// Keeps the current pixels and heat, so edits made while frozen survive,
+  /// and puts back only the motion state: the `FALLING` and `DIRTY` flags of
+  /// pixels whose material is unchanged since the freeze, the tile dirty
+  /// rects, and the tick. Pixels whose material changed are woken instead.
+  /// Chunks in the snapshot that are not currently seeded are skipped, and
+  /// pixels owned by pixel bodies are left alone. Returns the number of
+  /// chunks thawed.
+  pub fn thaw(&mut self, snapshot: &SimulationSnapshot) -> usize {
+    const MOTION: PixelFlags = PixelFlags::FALLING.union(PixelFlags::DIRTY);
+    let mut thawed = 0;
+
+    for saved in &snapshot.chunks {
+      let Some(idx) = self.pool.index_for(saved.pos) else {
+        continue;
+      };
+      let slot = self.pool.get_mut(idx);
+      if !slot.is_seeded() {
+        continue;
+      }
+
+      let chunk = &mut slot.chunk;
+      chunk.restore_tile_dirty_rects(&saved.dirty_rects);
+      let mut edited = Vec::new();
+      for (i, (current, saved)) in chunk
+        .pixels
+        .as_slice_mut()
+        .iter_mut()
+        .zip(&saved.pixels)
+        .enumerate()
+      {
+        if current.flags.contains(PixelFlags::PIXEL_BODY) {
+          continue;
+        }
+        if current.material == saved.material {
+          current.flags = current.flags.difference(MOTION) | saved.flags.intersection(MOTION);
+        } else {
+          edited.push(i);
+        }
+      }
+      for i in edited {
+        let (x, y) = (i as u32 % CHUNK_SIZE, i as u32 / CHUNK_SIZE);
+        chunk.pixels[(x, y)].flags.insert(PixelFlags::DIRTY);
+        chunk.mark_pixel_dirty(x, y);
+      }
+
+      slot.dirty = true;
+      thawed += 1;
+    }
+
+    self.tick = snapshot.tick;
+    self.simulate = true;
+    thawed
+  }
+
+  /// Writes a whole snapshot back, replacing the current pixels, dirty
+  /// tracking, heat and tick, and resumes simulation.
+  ///
+  /// Unlike [`thaw`](Self::thaw), this discards edits made since the
+  /// snapshot was taken; use it to load a snapshot into a world seeded the
+  /// same way, e.g. to reproduce a crash. Chunks in the snapshot that are
+  /// not currently seeded are skipped, and pixels owned by pixel bodies are
+  /// kept as they are now. Returns the number of chunks restored.
+  pub fn restore(&mut self, snapshot: &SimulationSnapshot) -> usize {
+    let mut restored = 0;
+
+    for saved in &snapshot.chunks {
+      let Some(idx) = self.pool.index_for(saved.pos) else {
+        continue;
+      };
+      let slot = self.pool.get_mut(idx);
+      if !slot.is_seeded() {
+        continue;
+      }
+
+      let chunk = &mut slot.chunk;
+      for (current, saved) in chunk.pixels.as_slice_mut().iter_mut().zip(&saved.pixels) {
+        if !current.flags.contains(PixelFlags::PIXEL_BODY) {
+          *current = *saved;
+        }
+      }
+      chunk.restore_tile_dirty_rects(&saved.dirty_rects);
+      chunk.heat.copy_from_slice(&saved.heat);
+      chunk.heat_dirty = saved.heat_dirty.clone();
+      chunk.set_all_collision_dirty(true);
+
+      slot.dirty = true;
+      slot.modified = true;
+      slot.persisted = false;
+      self.pool.bump_generation(idx);
+      restored += 1;
+    }
+
+    self.tick = snapshot.tick;
+    self.simulate = true;
+    restored
+  }
+}
@@ -1,14 +1,15 @@
 //! Streaming window entity culling.
 //!
 //! Automatically disables entities marked with [`StreamCulled`] when they exit
-//! the streaming window, and re-enables them when they re-enter.
+//! the streaming window, and re-enables them when they re-enter. Entities in
+//! chunks kept alive outside the window are left enabled.
 
 use bevy::ecs::entity_disabling::Disabled;
 use bevy::prelude::*;
 
 use crate::pixel_world::collision::CollisionCache;
 use crate::pixel_world::coords::{
-  CHUNK_SIZE, ChunkPos, TILE_SIZE, TilePos, WINDOW_HEIGHT, WINDOW_WIDTH,
+  CHUNK_SIZE, ChunkPos, TILE_SIZE, TilePos, WINDOW_HEIGHT, WINDOW_WIDTH, WorldPos,
 };
 use crate::pixel_world::world::PixelWorld;
 
@@ -101,6 +102,8 @@ fn should_reenable_entity(cache: &CollisionCache, x: i64, y: i64) -> bool {
 
 /// System that culls entities outside the streaming window.
 ///
+/// Chunks kept alive around pixel bodies count as inside.
+///
 /// For each entity with [`StreamCulled`]:
 /// - If outside bounds and not already culled: insert `(Disabled,
 ///   CulledByWindow)`
@@ -128,7 +131,8 @@ pub(crate) fn update_entity_culling(
     let x = pos.x as i64;
     let y = pos.y as i64;
 
-    let inside = x >= min_x && x < max_x && y >= min_y && y < max_y;
+    let in_window = x >= min_x && x < max_x && y >= min_y && y < max_y;
+    let inside = in_window || world.is_kept_alive(WorldPos::new(x, y).to_chunk_and_local().0);
 
     if inside && is_culled && should_reenable_entity(&cache, x, y) {
       commands
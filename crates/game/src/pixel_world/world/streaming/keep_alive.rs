@@ -0,0 +1,64 @@
+//! Keeps chunks around persistable pixel bodies loaded off-camera.
+//!
+//! Without it, a body resting in a chunk that leaves the streaming window is
+//! saved and despawned with the chunk, so it appears frozen when revisited.
+//! With [`PixelWorldConfig::body_keep_alive_radius`] set, chunks within that
+//! radius of each persistable body stay loaded and simulated, so machines
+//! keep running and falling objects keep falling.
+//!
+//! [`PixelWorldConfig::body_keep_alive_radius`]:
+//!   crate::pixel_world::world::PixelWorldConfig::body_keep_alive_radius
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::pixel_world::coords::{ChunkPos, WorldPos};
+use crate::pixel_world::pixel_body::{Persistable, PixelBody};
+use crate::pixel_world::world::PixelWorld;
+
+/// System: Collects the chunks to keep alive around persistable bodies.
+///
+/// Lists each body's own chunk before the chunks around it, so the chunks
+/// bodies are in win when the pool has too few spare slots. The streaming
+/// window update applies the list.
+pub(crate) fn update_body_keep_alive(
+  mut worlds: Query<&mut PixelWorld>,
+  bodies: Query<&GlobalTransform, (With<PixelBody>, With<Persistable>)>,
+) {
+  for mut world in worlds.iter_mut() {
+    let Some(radius) = world.config().body_keep_alive_radius else {
+      continue;
+    };
+    let radius = radius as i32;
+
+    let centers: Vec<ChunkPos> = bodies
+      .iter()
+      .map(|transform| {
+        let pos = transform.translation();
+        WorldPos::new(pos.x as i64, pos.y as i64)
+          .to_chunk_and_local()
+          .0
+      })
+      .collect();
+
+    let mut seen = HashSet::new();
+    let mut positions: Vec<ChunkPos> = centers
+      .iter()
+      .copied()
+      .filter(|&pos| seen.insert(pos))
+      .collect();
+    for center in centers {
+      for dy in -radius..=radius {
+        for dx in -radius..=radius {
+          let pos = ChunkPos::new(center.x + dx, center.y + dy);
+          if seen.insert(pos) {
+            positions.push(pos);
+          }
+        }
+      }
+    }
+
+    world.set_keep_alive(positions);
+  }
+}
@@ -1,16 +1,16 @@
 //! Unified streaming module for chunk lifecycle management.
 //!
 //! This module consolidates all Pre-Simulation phase systems that handle
-//! chunk streaming, seeding, culling, and pixel body loading.
+//! chunk streaming, seeding, culling, body keep-alive, and pixel body
+//! loading.
 
 pub(crate) mod body_loading;
 pub mod culling;
 mod frame_reset;
+mod keep_alive;
 mod seeding;
 mod window;
 
-use std::collections::HashSet;
-
 use bevy::prelude::*;
 // Re-export public types
 pub use body_loading::PendingPixelBodies;
@@ -19,14 +19,17 @@ pub(crate) use body_loading::queue_pixel_bodies_on_chunk_seed;
 pub(crate) use culling::update_entity_culling;
 pub use culling::{CullingConfig, StreamCulled};
 pub(crate) use frame_reset::clear_chunk_tracking;
+pub(crate) use keep_alive::update_body_keep_alive;
+pub use seeding::{ChunkSeededMsg, SeedDeterminismCheck, SeedMismatch};
 pub(crate) use seeding::{
-  SeedingTasks, dispatch_seeding, handle_fresh_reseed_request, handle_reload_request,
-  handle_reseed_request, handle_update_seeder, poll_seeding_tasks,
+  SeedingTasks, dispatch_seeding, handle_cancel_tasks, handle_fresh_reseed_request,
+  handle_reload_request, handle_reseed_request, handle_update_seeder, merge_seeded_pixels,
+  poll_seeding_tasks, seed_chunk_with_loaded,
 };
-pub use window::StreamingCamera;
 pub(crate) use window::{
   SharedChunkMesh, SharedPaletteTexture, update_simulation_bounds, update_streaming_windows,
 };
+pub use window::{SimulationViewport, StreamingCamera};
 
 use super::slot::SlotIndex;
 use crate::pixel_world::coords::{ChunkPos, WINDOW_HEIGHT, WINDOW_WIDTH};
@@ -82,22 +85,9 @@ pub struct ChunkSaveData {
   pub pixels: Vec<u8>,
 }
 
-/// Computes which chunk positions are leaving and entering the streaming
-/// window.
-///
-/// Returns (positions_leaving, positions_entering).
-pub(crate) fn compute_position_changes(
-  old_center: ChunkPos,
-  new_center: ChunkPos,
-) -> (Vec<ChunkPos>, Vec<ChunkPos>) {
-  let old_set: HashSet<_> = visible_positions(old_center).collect();
-  let new_set: HashSet<_> = visible_positions(new_center).collect();
-
-  let leaving: Vec<_> = old_set.difference(&new_set).copied().collect();
-  let entering: Vec<_> = new_set.difference(&old_set).copied().collect();
-
-  (leaving, entering)
-}
+/// Number of chunk positions returned by [`visible_positions`].
+pub(crate) const VISIBLE_CHUNK_COUNT: usize =
+  ((WINDOW_WIDTH as usize / 2) * 2) * ((WINDOW_HEIGHT as usize / 2) * 2);
 
 /// Returns iterator over visible chunk positions for a given center.
 pub(crate) fn visible_positions(center: ChunkPos) -> impl Iterator<Item = ChunkPos> {
@@ -2,12 +2,13 @@
 //!
 //! Handles asynchronous chunk generation through the seeder trait.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use bevy::prelude::*;
 use bevy::tasks::{AsyncComputeTaskPool, Task};
 
 use super::SeededChunks;
+use crate::pixel_world::collision::{CollisionCache, CollisionTasks};
 use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos};
 use crate::pixel_world::debug_shim;
 use crate::pixel_world::persistence::LoadedChunk;
@@ -16,7 +17,8 @@ use crate::pixel_world::primitives::Chunk;
 use crate::pixel_world::world::PixelWorld;
 use crate::pixel_world::world::SlotIndex;
 use crate::pixel_world::world::control::{
-  FreshReseedAllChunks, ReloadAllChunks, ReseedAllChunks, UpdateSeeder,
+  CancelPendingTasks, FreshReseedAllChunks, PersistenceControl, ReloadAllChunks, ReseedAllChunks,
+  UpdateSeeder,
 };
 use crate::pixel_world::world::persistence_systems::LoadedChunkDataStore;
 use crate::pixel_world::world::slot::ChunkLifecycle;
@@ -37,6 +39,26 @@ impl SeedingTasks {
   pub fn is_empty(&self) -> bool {
     self.tasks.is_empty()
   }
+
+  /// Returns the number of in-flight seeding tasks per world entity.
+  pub fn count_by_world(&self) -> HashMap<Entity, usize> {
+    let mut counts = HashMap::new();
+    for task in &self.tasks {
+      *counts.entry(task.world_entity).or_default() += 1;
+    }
+    counts
+  }
+
+  /// Drops every in-flight seeding task, so its result is never applied.
+  ///
+  /// Returns the world entity and slot of each dropped task.
+  fn cancel_all(&mut self) -> Vec<(Entity, SlotIndex)> {
+    self
+      .tasks
+      .drain(..)
+      .map(|task| (task.world_entity, task.slot_index))
+      .collect()
+  }
 }
 
 /// An in-flight seeding task.
@@ -48,7 +70,57 @@ pub(super) struct SeedingTask {
   /// The chunk position being seeded.
   pub pos: ChunkPos,
   /// The async task returning a seeded chunk.
-  pub task: Task<Chunk>,
+  pub task: Task<SeedOutput>,
+}
+
+/// Result of a seeding task.
+pub(super) struct SeedOutput {
+  chunk: Chunk,
+  /// Pixels that differed between the two seeding passes, when the
+  /// determinism check ran.
+  mismatched_pixels: usize,
+}
+
+/// Debug flag: seeds every procedurally generated chunk twice and compares.
+///
+/// Seeders must produce the same pixels for the same position no matter
+/// which neighbors are loaded. When enabled, each chunk without saved data is
+/// seeded into two separate buffers; if they differ, an error is logged and
+/// a [`SeedMismatch`] message is sent. Doubles seeding cost, so it is off by
+/// default and meant for development builds only.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SeedDeterminismCheck {
+  /// Whether newly seeded chunks are checked.
+  pub enabled: bool,
+}
+
+/// Message sent when a seeder produced different pixels for the same chunk.
+///
+/// Only sent while [`SeedDeterminismCheck`] is enabled.
+#[derive(bevy::prelude::Message, Clone, Debug)]
+pub struct SeedMismatch {
+  /// Which PixelWorld entity.
+  pub world: Entity,
+  /// The chunk that seeded non-deterministically.
+  pub pos: ChunkPos,
+  /// Number of pixels that differed between the two passes.
+  pub pixels: usize,
+}
+
+/// Message sent when a chunk finishes seeding and becomes active.
+///
+/// Sent once per chunk each time it is seeded, whether from the save file or
+/// by the procedural seeder, so games can run effects (particles, sounds)
+/// as terrain streams in. The frame's positions are also listed in
+/// [`SeededChunks`]. Not sent for [`PixelWorld::force_seed`].
+#[derive(bevy::prelude::Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkSeededMsg {
+  /// Which PixelWorld entity.
+  pub world: Entity,
+  /// The chunk that finished seeding.
+  pub pos: ChunkPos,
+  /// True if the pixels were loaded from disk, false if generated.
+  pub from_persistence: bool,
 }
 
 /// Maximum number of concurrent seeding tasks.
@@ -128,18 +200,50 @@ fn collect_in_flight_tasks(
   (count, slots)
 }
 
+/// Seeds `pos` a second time and counts pixels that differ from `chunk`.
+fn count_seed_mismatches(
+  seeder: &(dyn crate::pixel_world::seeding::ChunkSeeder + Send + Sync),
+  pos: ChunkPos,
+  chunk: &Chunk,
+) -> usize {
+  let mut second = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  second.set_pos(pos);
+  seeder.seed(pos, &mut second);
+
+  chunk
+    .pixels
+    .as_slice()
+    .iter()
+    .zip(second.pixels.as_slice())
+    .filter(|(a, b)| a != b)
+    .count()
+}
+
 /// Spawns an async seeding task for a chunk with optional pre-loaded data.
 fn spawn_seeding_task(
   seeding_tasks: &mut SeedingTasks,
-  task_pool: &AsyncComputeTaskPool,
   world_entity: Entity,
   world: &PixelWorld,
   pos: ChunkPos,
   slot_idx: SlotIndex,
   loaded: Option<LoadedChunk>,
+  check_determinism: bool,
 ) {
   let seeder = world.seeder().clone();
-  let task = task_pool.spawn(async move { seed_chunk_with_loaded(seeder.as_ref(), pos, loaded) });
+  // Saved chunks aren't purely procedural, so only fresh seeds are checked
+  let check = check_determinism && loaded.is_none();
+  let task = AsyncComputeTaskPool::get().spawn(async move {
+    let chunk = seed_chunk_with_loaded(seeder.as_ref(), pos, loaded);
+    let mismatched_pixels = if check {
+      count_seed_mismatches(seeder.as_ref(), pos, &chunk)
+    } else {
+      0
+    };
+    SeedOutput {
+      chunk,
+      mismatched_pixels,
+    }
+  });
 
   seeding_tasks.tasks.push(SeedingTask {
     world_entity,
@@ -158,6 +262,9 @@ fn spawn_seeding_task(
 /// When rendering is absent, all seeding chunks are dispatched at once
 /// (no task limit), so `poll_seeding_tasks` can block-complete them in
 /// the same frame.
+///
+/// With [`SeedDeterminismCheck`] enabled, fresh chunks are seeded twice so
+/// `poll_seeding_tasks` can report non-deterministic seeders.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
 pub(crate) fn dispatch_seeding(
   mut seeding_tasks: ResMut<SeedingTasks>,
@@ -165,8 +272,8 @@ pub(crate) fn dispatch_seeding(
   mut loaded_data: ResMut<LoadedChunkDataStore>,
   rendering: Option<Res<crate::pixel_world::world::plugin::RenderingEnabled>>,
   async_behavior: Option<Res<crate::pixel_world::world::plugin::AsyncTaskBehavior>>,
+  determinism_check: Res<SeedDeterminismCheck>,
 ) {
-  let task_pool = AsyncComputeTaskPool::get();
   let block_all = crate::pixel_world::world::plugin::should_block_tasks(rendering, async_behavior);
   let max_tasks = if block_all {
     usize::MAX
@@ -198,12 +305,12 @@ pub(crate) fn dispatch_seeding(
 
       spawn_seeding_task(
         &mut seeding_tasks,
-        task_pool,
         world_entity,
         &world,
         pos,
         slot_idx,
         loaded,
+        determinism_check.enabled,
       );
 
       in_flight += 1;
@@ -219,30 +326,53 @@ pub(crate) fn dispatch_seeding(
 /// When rendering is absent (no `RenderingEnabled` resource), all pending
 /// tasks are block-waited to completion. This gives synchronous semantics
 /// in test environments where frames advance faster than async tasks.
+#[allow(clippy::too_many_arguments)]
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
 pub(crate) fn poll_seeding_tasks(
   mut seeding_tasks: ResMut<SeedingTasks>,
   mut worlds: Query<&mut PixelWorld>,
   mut seeded_chunks: ResMut<SeededChunks>,
+  mut mismatches: bevy::ecs::message::MessageWriter<SeedMismatch>,
+  mut seeded_msgs: bevy::ecs::message::MessageWriter<ChunkSeededMsg>,
   gizmos: debug_shim::GizmosParam,
   rendering: Option<Res<crate::pixel_world::world::plugin::RenderingEnabled>>,
   async_behavior: Option<Res<crate::pixel_world::world::plugin::AsyncTaskBehavior>>,
 ) {
   let debug_gizmos = gizmos.get();
   let block_all = crate::pixel_world::world::plugin::should_block_tasks(rendering, async_behavior);
+  // A chunk is announced once per poll even if several tasks complete it
+  let mut announced = HashSet::new();
 
   seeding_tasks.tasks.retain_mut(|task| {
     if !block_all && !task.task.is_finished() {
       return true; // keep pending tasks
     }
 
-    let seeded_chunk = bevy::tasks::block_on(&mut task.task);
+    let SeedOutput {
+      chunk: seeded_chunk,
+      mismatched_pixels,
+    } = bevy::tasks::block_on(&mut task.task);
+
+    if mismatched_pixels > 0 {
+      error!(
+        "Non-deterministic seeder: chunk {:?} differed in {} pixels between two seeds",
+        task.pos, mismatched_pixels
+      );
+      mismatches.write(SeedMismatch {
+        world: task.world_entity,
+        pos: task.pos,
+        pixels: mismatched_pixels,
+      });
+    }
 
     if let Ok(mut world) = worlds.get_mut(task.world_entity)
       // Slot may have been recycled if camera moved while task was in flight.
       // Both checks are needed: position mapping and slot index must match.
       && let Some(current_idx) = world.get_slot_index(task.pos)
       && current_idx == task.slot_index
+      // Slot may have been sent back to Loading while the task was in flight
+      // (e.g. persistence finished initializing after a fallback seed).
+      && world.slot(task.slot_index).is_seeding()
     {
       let slot = world.slot_mut(task.slot_index);
       // Merge seeded pixels, preserving any PIXEL_BODY pixels that were
@@ -257,9 +387,18 @@ pub(crate) fn poll_seeding_tasks(
       if seeded_chunk.from_persistence {
         slot.persisted = true;
       }
+      world.bump_generation(task.slot_index);
 
       // Track that this chunk just finished seeding
       seeded_chunks.positions.push(task.pos);
+      let msg = ChunkSeededMsg {
+        world: task.world_entity,
+        pos: task.pos,
+        from_persistence: seeded_chunk.from_persistence,
+      };
+      if announced.insert((msg.world, msg.pos)) {
+        seeded_msgs.write(msg);
+      }
 
       debug_shim::emit_chunk(debug_gizmos, task.pos);
     }
@@ -287,10 +426,62 @@ pub(crate) fn handle_update_seeder(
   }
 }
 
+/// System: Handles requests to cancel in-flight async tasks.
+///
+/// Drops seeding and collision tasks and discards pending chunk loads, then
+/// puts their chunks and tiles back in line to be redone. See
+/// [`CancelPendingTasks`].
+#[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
+pub(crate) fn handle_cancel_tasks(
+  mut events: bevy::ecs::message::MessageReader<CancelPendingTasks>,
+  mut seeding_tasks: ResMut<SeedingTasks>,
+  mut loading: ResMut<LoadingChunks>,
+  collision_tasks: Option<ResMut<CollisionTasks>>,
+  collision_cache: Option<ResMut<CollisionCache>>,
+  mut worlds: Query<&mut PixelWorld>,
+  persistence: Option<Res<PersistenceControl>>,
+) {
+  if events.is_empty() {
+    return;
+  }
+  for _ in events.read() {}
+
+  let cancelled = seeding_tasks.cancel_all();
+  let persistence_enabled = persistence.is_some_and(|p| p.is_enabled());
+  for &(world_entity, slot_idx) in &cancelled {
+    let Ok(mut world) = worlds.get_mut(world_entity) else {
+      continue;
+    };
+    let slot = world.slot_mut(slot_idx);
+    // The dropped task may have carried data loaded from the save
+    if persistence_enabled && slot.is_seeding() {
+      slot.lifecycle = ChunkLifecycle::Loading;
+    }
+  }
+
+  let loads = loading.len();
+  loading.cancel_all();
+
+  let mut meshes = 0;
+  if let (Some(mut tasks), Some(mut cache)) = (collision_tasks, collision_cache) {
+    meshes = tasks.len();
+    tasks.cancel_all(&mut cache);
+  }
+
+  info!(
+    "Cancelled {} seeding, {} load and {} collision tasks",
+    cancelled.len(),
+    loads,
+    meshes
+  );
+}
+
 /// System: Handles reseed requests by transitioning Active chunks to Seeding.
 ///
 /// When `ReseedAllChunks` is sent, all active chunks regenerate with the
-/// current noise profile. Any cached persistence data is cleared first.
+/// current noise profile. Any cached persistence data is cleared first, and
+/// in-flight seeding tasks are dropped so results from the previous seeder
+/// are never applied.
 ///
 /// Use this for level editor mode when noise profiles change.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
@@ -298,6 +489,7 @@ pub(crate) fn handle_reseed_request(
   mut events: bevy::ecs::message::MessageReader<ReseedAllChunks>,
   mut worlds: Query<&mut PixelWorld>,
   mut loaded_data: ResMut<LoadedChunkDataStore>,
+  mut seeding_tasks: ResMut<SeedingTasks>,
 ) {
   // Only process if there are reseed requests
   if events.is_empty() {
@@ -310,6 +502,8 @@ pub(crate) fn handle_reseed_request(
   // Clear any cached persistence data
   loaded_data.store.clear();
   loaded_data.bodies.clear();
+  // Chunks still seeding stay in Seeding and are dispatched again
+  seeding_tasks.cancel_all();
 
   // Transition Active chunks back to Seeding
   let mut count = 0;
@@ -333,7 +527,8 @@ pub(crate) fn handle_reseed_request(
 /// Seeding.
 ///
 /// Unlike `handle_reseed_request`, this does NOT update the seeder - it only
-/// clears cached persistence data and transitions chunks to regenerate.
+/// clears cached persistence data and in-flight seeding tasks, and
+/// transitions chunks to regenerate.
 ///
 /// Use for edit mode transitions where you want fresh procedural data.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
@@ -341,6 +536,7 @@ pub(crate) fn handle_fresh_reseed_request(
   mut events: bevy::ecs::message::MessageReader<FreshReseedAllChunks>,
   mut worlds: Query<&mut PixelWorld>,
   mut loaded_data: ResMut<LoadedChunkDataStore>,
+  mut seeding_tasks: ResMut<SeedingTasks>,
 ) {
   if events.is_empty() {
     return;
@@ -349,9 +545,10 @@ pub(crate) fn handle_fresh_reseed_request(
   // Consume all events
   for _ in events.read() {}
 
-  // Clear cached persistence data
+  // Clear cached persistence data and seeding results they would produce
   loaded_data.store.clear();
   loaded_data.bodies.clear();
+  seeding_tasks.cancel_all();
 
   // Transition Active -> Seeding
   let mut count = 0;
@@ -394,7 +591,7 @@ pub(crate) fn handle_reload_request(
   // Clear caches so chunks re-request from disk
   loaded_data.store.clear();
   loaded_data.bodies.clear();
-  loading.pending.clear();
+  loading.cancel_all();
 
   // Transition Active → Loading (will be re-dispatched by dispatch_chunk_loads)
   let mut count = 0;
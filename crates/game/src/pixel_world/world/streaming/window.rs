@@ -5,12 +5,13 @@
 use bevy::prelude::*;
 
 use super::UnloadingChunks;
+use crate::pixel_world::DefaultPersistenceConfig;
 use crate::pixel_world::coords::{CHUNK_SIZE, ChunkPos, WorldPos, WorldRect};
 use crate::pixel_world::persistence::PersistenceTasks;
 use crate::pixel_world::persistence::compression::compress_lz4;
 use crate::pixel_world::persistence::format::StorageType;
 use crate::pixel_world::pixel_camera::LogicalCameraPosition;
-use crate::pixel_world::render::{ChunkMaterial, create_pixel_texture};
+use crate::pixel_world::render::{ChunkMaterial, create_heat_texture, create_pixel_texture};
 use crate::pixel_world::world::control::{PendingPersistenceInit, PersistenceControl};
 use crate::pixel_world::world::slot::ChunkLifecycle;
 use crate::pixel_world::world::{PixelWorld, SlotIndex};
@@ -19,6 +20,14 @@ use crate::pixel_world::world::{PixelWorld, SlotIndex};
 #[derive(Component)]
 pub struct StreamingCamera;
 
+/// Marker component for additional cameras whose viewports are simulated,
+/// such as a minimap or picture-in-picture view.
+///
+/// Only affects simulation bounds; streaming still follows the
+/// [`StreamingCamera`].
+#[derive(Component)]
+pub struct SimulationViewport;
+
 /// Shared mesh resource for chunk quads.
 #[derive(Resource)]
 pub(crate) struct SharedChunkMesh(pub Handle<Mesh>);
@@ -48,6 +57,7 @@ pub(crate) fn update_streaming_windows(
   mut unloading_chunks: ResMut<UnloadingChunks>,
   persistence_control: Option<Res<PersistenceControl>>,
   pending_init: Option<Res<PendingPersistenceInit>>,
+  persistence_config: Option<Res<DefaultPersistenceConfig>>,
 ) {
   let Ok((camera_transform, logical_pos)) = camera_query.single() else {
     return;
@@ -55,9 +65,13 @@ pub(crate) fn update_streaming_windows(
 
   let palette_handle = palette.as_ref().map(|p| p.handle.clone());
   // Check if persistence is available AND enabled (not in editor mode).
-  // Also check pending init for WASM async initialization.
-  let persistence_enabled =
-    persistence_control.as_ref().is_some_and(|p| p.is_enabled()) || pending_init.is_some();
+  // While initialization is pending, chunks wait for the save index unless
+  // the config opts into procedural seeding as a fallback.
+  let defer_seeding = persistence_config
+    .as_ref()
+    .is_none_or(|c| c.0.defer_seeding);
+  let persistence_enabled = persistence_control.as_ref().is_some_and(|p| p.is_enabled())
+    || (pending_init.is_some() && defer_seeding);
 
   // Use logical camera position if available (pixel camera mode)
   // Otherwise fall back to transform position
@@ -122,7 +136,7 @@ pub(crate) fn update_streaming_windows(
 fn spawn_chunk_entity(
   commands: &mut Commands,
   world: &mut PixelWorld,
-  images: Option<&mut Assets<Image>>,
+  mut images: Option<&mut Assets<Image>>,
   materials: Option<&mut Assets<ChunkMaterial>>,
   palette_handle: Option<Handle<Image>>,
   pos: ChunkPos,
@@ -132,6 +146,16 @@ fn spawn_chunk_entity(
   let world_pos = pos.to_world();
   let transform = Transform::from_xyz(world_pos.x as f32, world_pos.y as f32, 0.0);
 
+  // Create or reuse heat texture; skipped entirely while disabled
+  let heat_config = world.config().heat_texture;
+  let previous_heat = world.slot_mut(slot_idx).heat_texture.take();
+  let heat_texture = match images.as_deref_mut() {
+    Some(images) if heat_config.enabled && materials.is_some() => {
+      Some(previous_heat.unwrap_or_else(|| create_heat_texture(images, &heat_config)))
+    }
+    _ => None,
+  };
+
   let (entity, texture, material) = if let (Some(images), Some(materials)) = (images, materials) {
     let slot = world.slot_mut(slot_idx);
 
@@ -149,13 +173,22 @@ fn spawn_chunk_entity(
       materials.add(ChunkMaterial {
         pixel_texture: Some(texture.clone()),
         palette_texture: palette_handle.clone(),
+        brightness: 1.0,
+        cracks: Default::default(),
+        heat_texture: None,
+        heat_glow: 0.0,
+        depth_ramps: Default::default(),
       })
     };
 
-    // Update material textures if reusing
+    // Update material textures if reusing; only an R8Unorm heat texture
+    // matches the material's float binding
+    let heat_glow = heat_config.shader_glow();
     if let Some(mat) = materials.get_mut(&material) {
       mat.pixel_texture = Some(texture.clone());
       mat.palette_texture = palette_handle;
+      mat.heat_texture = heat_texture.clone().filter(|_| heat_glow > 0.0);
+      mat.heat_glow = heat_glow;
     }
 
     let mesh = world.mesh().clone();
@@ -174,31 +207,20 @@ fn spawn_chunk_entity(
     (entity, None, None)
   };
 
-  world.register_slot_entity(slot_idx, entity, texture, material);
+  world.register_slot_entity(slot_idx, entity, texture, material, heat_texture);
 }
 
-/// System: Updates simulation bounds from camera viewport.
-///
-/// Extracts the visible area from the streaming camera's orthographic
-/// projection and sets it as the simulation bounds for all pixel worlds.
-pub(crate) fn update_simulation_bounds(
-  camera_query: Query<
-    (
-      &GlobalTransform,
-      &Projection,
-      Option<&LogicalCameraPosition>,
-    ),
-    With<StreamingCamera>,
-  >,
-  mut worlds: Query<&mut PixelWorld>,
-) {
-  let Ok((transform, projection, logical_pos)) = camera_query.single() else {
-    return;
-  };
-
+/// Returns the visible area of an orthographic camera in world
+/// coordinates, or `None` for perspective cameras and projections whose
+/// area is not computed yet.
+fn camera_viewport(
+  transform: &GlobalTransform,
+  projection: &Projection,
+  logical_pos: Option<&LogicalCameraPosition>,
+) -> Option<WorldRect> {
   // Extract orthographic projection, skip if perspective
   let Projection::Orthographic(ortho) = projection else {
-    return;
+    return None;
   };
 
   // Use logical camera position if available (pixel camera mode)
@@ -213,17 +235,59 @@ pub(crate) fn update_simulation_bounds(
 
   // Skip if area is not yet initialized (Bevy computes it after first frame)
   if half_width <= 0.0 || half_height <= 0.0 {
-    return;
+    return None;
   }
 
-  let bounds = WorldRect::new(
+  Some(WorldRect::new(
     (cam_pos.x - half_width) as i64,
     (cam_pos.y - half_height) as i64,
     (half_width * 2.0) as u32,
     (half_height * 2.0) as u32,
-  );
+  ))
+}
+
+/// Returns the smallest rect containing both `a` and `b`.
+fn union_rect(a: WorldRect, b: WorldRect) -> WorldRect {
+  let min_x = a.x.min(b.x);
+  let min_y = a.y.min(b.y);
+  let max_x = (a.x + a.width as i64).max(b.x + b.width as i64);
+  let max_y = (a.y + a.height as i64).max(b.y + b.height as i64);
+  WorldRect::new(min_x, min_y, (max_x - min_x) as u32, (max_y - min_y) as u32)
+}
+
+/// System: Updates simulation bounds from camera viewports.
+///
+/// Extracts the visible area from the orthographic projection of the
+/// streaming camera and every [`SimulationViewport`] camera, and sets the
+/// rect containing all of them as the simulation bounds of each pixel world
+/// with [`PixelWorldConfig::follow_camera_bounds`]. Space between separate
+/// viewports is simulated too.
+///
+/// [`PixelWorldConfig::follow_camera_bounds`]: crate::pixel_world::PixelWorldConfig::follow_camera_bounds
+pub(crate) fn update_simulation_bounds(
+  camera_query: Query<
+    (
+      &GlobalTransform,
+      &Projection,
+      Option<&LogicalCameraPosition>,
+    ),
+    Or<(With<StreamingCamera>, With<SimulationViewport>)>,
+  >,
+  mut worlds: Query<&mut PixelWorld>,
+) {
+  let Some(bounds) = camera_query
+    .iter()
+    .filter_map(|(transform, projection, logical_pos)| {
+      camera_viewport(transform, projection, logical_pos)
+    })
+    .reduce(union_rect)
+  else {
+    return;
+  };
 
   for mut world in worlds.iter_mut() {
-    world.set_simulation_bounds(Some(bounds));
+    if world.config().follow_camera_bounds {
+      world.set_simulation_bounds(Some(bounds));
+    }
   }
 }
@@ -5,4 +5,4 @@
 
 mod upload;
 
-pub(crate) use upload::upload_dirty_chunks;
+pub(crate) use upload::{defer_offscreen_uploads, upload_dirty_chunks};
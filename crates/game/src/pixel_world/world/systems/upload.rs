@@ -1,14 +1,18 @@
 //! GPU upload systems.
 //!
-//! Handles uploading dirty chunk data to GPU textures.
+//! Handles uploading dirty chunk data to GPU textures, deferring uploads for
+//! chunks outside the camera view until they come back into view.
 
 use bevy::prelude::*;
 // WASM compat: std::time::Instant panics on wasm32
 use web_time::Instant;
 
 use super::super::{PixelWorld, SlotIndex};
+use crate::pixel_world::coords::{CHUNK_SIZE, WorldRect};
 use crate::pixel_world::diagnostics::profile;
-use crate::pixel_world::render::{ChunkMaterial, upload_pixels};
+use crate::pixel_world::render::{
+  BodyRenderMode, ChunkMaterial, upload_heat, upload_pixels, upload_pixels_without_bodies,
+};
 
 /// Returns indices of dirty, seeded slots that need GPU upload.
 fn dirty_slot_indices(world: &PixelWorld) -> impl Iterator<Item = SlotIndex> + '_ {
@@ -19,6 +23,33 @@ fn dirty_slot_indices(world: &PixelWorld) -> impl Iterator<Item = SlotIndex> + '
   })
 }
 
+/// Returns indices of seeded slots whose heat texture needs upload.
+///
+/// Heat changes without marking the chunk dirty, so slots are uploaded while
+/// any of their heat tiles is still active.
+fn heat_slot_indices(world: &PixelWorld) -> impl Iterator<Item = SlotIndex> + '_ {
+  world.active_chunks().filter_map(|(_, idx)| {
+    let slot = world.slot(idx);
+    (slot.is_seeded()
+      && !slot.gpu_stale
+      && slot.heat_texture.is_some()
+      && slot.chunk.heat_dirty.active_count() > 0)
+      .then_some(idx)
+  })
+}
+
+/// Uploads a slot's heat grid to its heat texture.
+fn upload_slot_heat(world: &PixelWorld, idx: SlotIndex, images: &mut Assets<Image>) {
+  let config = world.config().heat_texture;
+  let slot = world.slot(idx);
+
+  // SAFETY: heat_slot_indices() ensures this is Some
+  let heat_handle = slot.heat_texture.as_ref().unwrap();
+  if let Some(image) = images.get_mut(heat_handle) {
+    upload_heat(&slot.chunk.heat, &config, image);
+  }
+}
+
 /// Uploads a slot's pixel data to its GPU texture.
 fn upload_slot_to_gpu(
   world: &mut PixelWorld,
@@ -26,6 +57,7 @@ fn upload_slot_to_gpu(
   images: &mut Assets<Image>,
   materials: &mut Assets<ChunkMaterial>,
 ) {
+  let mode = world.config().body_render_mode;
   let slot = world.slot_mut(idx);
 
   // SAFETY: dirty_slot_indices() ensures these are Some
@@ -33,7 +65,11 @@ fn upload_slot_to_gpu(
   let material_handle = slot.material.as_ref().unwrap();
 
   if let Some(image) = images.get_mut(texture_handle) {
-    upload_pixels(&slot.chunk.pixels, image);
+    match mode {
+      BodyRenderMode::Blit => upload_pixels(&slot.chunk.pixels, image),
+      // Bodies draw themselves as sprites
+      BodyRenderMode::Sprite => upload_pixels_without_bodies(&slot.chunk.pixels, image),
+    }
   }
 
   // Touch material to force bind group refresh (Bevy workaround)
@@ -42,9 +78,66 @@ fn upload_slot_to_gpu(
   slot.dirty = false;
 }
 
+/// System: Defers GPU uploads for chunks outside the camera view.
+///
+/// Dirty chunks that don't overlap the simulation bounds are marked GPU-stale
+/// instead of uploading. Stale chunks that come back into view are marked
+/// dirty again, so they catch up with a single upload. Turning
+/// [`defer_offscreen_uploads`](crate::pixel_world::PixelWorldConfig::defer_offscreen_uploads)
+/// off marks every stale chunk dirty again.
+pub(crate) fn defer_offscreen_uploads(mut worlds: Query<&mut PixelWorld>) {
+  for mut world in worlds.iter_mut() {
+    if !world.config().defer_offscreen_uploads {
+      flush_stale_uploads(&mut world);
+      continue;
+    }
+    let Some(view) = world.simulation_bounds() else {
+      continue;
+    };
+
+    let slots: Vec<_> = world
+      .active_chunks()
+      .map(|(pos, idx)| {
+        let origin = pos.to_world();
+        let rect = WorldRect::new(origin.x, origin.y, CHUNK_SIZE, CHUNK_SIZE);
+        (idx, rect.intersects(&view))
+      })
+      .collect();
+
+    for (idx, in_view) in slots {
+      let slot = world.slot_mut(idx);
+      if !slot.is_seeded() {
+        continue;
+      }
+      if in_view {
+        if slot.gpu_stale {
+          slot.gpu_stale = false;
+          slot.dirty = true;
+        }
+      } else if slot.dirty {
+        slot.dirty = false;
+        slot.gpu_stale = true;
+      }
+    }
+  }
+}
+
+/// Marks every GPU-stale chunk dirty, so it is uploaded on the next pass.
+fn flush_stale_uploads(world: &mut PixelWorld) {
+  let slots: Vec<_> = world.active_chunks().map(|(_, idx)| idx).collect();
+  for idx in slots {
+    let slot = world.slot_mut(idx);
+    if slot.gpu_stale {
+      slot.gpu_stale = false;
+      slot.dirty = true;
+    }
+  }
+}
+
 /// System: Uploads dirty chunks to GPU.
 ///
 /// Uploads raw pixel data directly. Color lookup happens in the shader.
+/// Heat textures, when enabled, are uploaded alongside.
 #[cfg_attr(feature = "tracy", tracing::instrument(skip_all))]
 pub(crate) fn upload_dirty_chunks(
   mut worlds: Query<&mut PixelWorld>,
@@ -62,6 +155,10 @@ pub(crate) fn upload_dirty_chunks(
     for idx in dirty_indices {
       upload_slot_to_gpu(&mut world, idx, &mut images, &mut materials);
     }
+
+    for idx in heat_slot_indices(&world) {
+      upload_slot_heat(&world, idx, &mut images);
+    }
   }
 
   let elapsed_ms = start.elapsed().as_secs_f32() * 1000.0;
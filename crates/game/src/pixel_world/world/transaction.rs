@@ -0,0 +1,144 @@
+//! Atomic multi-pixel edits for `PixelWorld`.
+//!
+//! [`PixelWorld::transaction`] hands the caller a [`WorldTransaction`] that
+//! records writes instead of applying them. Reads through the transaction
+//! see its pending writes; the world itself keeps its committed state until
+//! the closure returns. All writes are then applied together, and each
+//! touched chunk is marked dirty once, no matter how many of its pixels
+//! changed. A transaction can be rolled back, discarding every pending
+//! write.
+
+use std::collections::HashMap;
+
+use super::PixelWorld;
+use crate::pixel_world::coords::{ChunkPos, WorldPos};
+use crate::pixel_world::pixel::Pixel;
+
+/// Pending pixel writes recorded by [`PixelWorld::transaction`].
+pub struct WorldTransaction<'w> {
+  world: &'w PixelWorld,
+  writes: HashMap<WorldPos, Pixel>,
+}
+
+impl<'w> WorldTransaction<'w> {
+  /// Returns the world in its committed state, without pending writes.
+  pub fn world(&self) -> &'w PixelWorld {
+    self.world
+  }
+
+  /// Returns the pixel at `pos` as this transaction sees it: the pending
+  /// write if there is one, otherwise the committed pixel.
+  ///
+  /// Returns None if the chunk is not loaded or not yet seeded.
+  pub fn get_pixel(&self, pos: WorldPos) -> Option<Pixel> {
+    let committed = *self.world.get_pixel(pos)?;
+    Some(self.writes.get(&pos).copied().unwrap_or(committed))
+  }
+
+  /// Records a write of `pixel` at `pos`, replacing any earlier pending
+  /// write there.
+  ///
+  /// Returns false if the chunk is not loaded or not yet seeded; nothing
+  /// is recorded then.
+  pub fn set_pixel(&mut self, pos: WorldPos, pixel: Pixel) -> bool {
+    if self.world.get_pixel(pos).is_none() {
+      return false;
+    }
+    self.writes.insert(pos, pixel);
+    true
+  }
+
+  /// Discards all pending writes. Writes recorded afterwards are kept.
+  pub fn rollback(&mut self) {
+    self.writes.clear();
+  }
+
+  /// Returns the number of pending writes.
+  pub fn len(&self) -> usize {
+    self.writes.len()
+  }
+
+  /// Returns true if there are no pending writes.
+  pub fn is_empty(&self) -> bool {
+    self.writes.is_empty()
+  }
+
+  /// Returns the chunks the pending writes touch, each listed once. These
+  /// are the chunks marked dirty on commit.
+  pub fn touched_chunks(&self) -> Vec<ChunkPos> {
+    let mut chunks: Vec<ChunkPos> = self
+      .writes
+      .keys()
+      .map(|pos| pos.to_chunk_and_local().0)
+      .collect();
+    chunks.sort_by_key(|c| (c.y, c.x));
+    chunks.dedup();
+    chunks
+  }
+}
+
+impl PixelWorld {
+  /// Runs `f` with a transaction and applies its writes all at once when
+  /// it returns.
+  ///
+  /// Writes are only visible through the transaction until then, so no
+  /// half-applied edit is ever observable in the world. Each touched chunk
+  /// is marked dirty (and as needing save) once, and written pixels are
+  /// woken for simulation. Calling [`WorldTransaction::rollback`] discards
+  /// the pending writes.
+  pub fn transaction<R>(&mut self, f: impl FnOnce(&mut WorldTransaction<'_>) -> R) -> R {
+    let mut tx = WorldTransaction {
+      world: self,
+      writes: HashMap::new(),
+    };
+    let result = f(&mut tx);
+    let writes = tx.writes;
+    self.apply_transaction(writes);
+    result
+  }
+
+  /// Like [`transaction`](Self::transaction), but rolls back every pending
+  /// write if `f` returns an error.
+  pub fn try_transaction<T, E>(
+    &mut self,
+    f: impl FnOnce(&mut WorldTransaction<'_>) -> Result<T, E>,
+  ) -> Result<T, E> {
+    self.transaction(|tx| {
+      let result = f(tx);
+      if result.is_err() {
+        tx.rollback();
+      }
+      result
+    })
+  }
+
+  /// Applies recorded writes grouped by chunk, marking each chunk once.
+  fn apply_transaction(&mut self, writes: HashMap<WorldPos, Pixel>) {
+    let mut by_chunk: HashMap<ChunkPos, Vec<(u32, u32, Pixel)>> = HashMap::new();
+    for (pos, pixel) in writes {
+      let (chunk_pos, local) = pos.to_chunk_and_local();
+      by_chunk
+        .entry(chunk_pos)
+        .or_default()
+        .push((local.x as u32, local.y as u32, pixel));
+    }
+
+    for (chunk_pos, pixels) in by_chunk {
+      let Some(idx) = self.pool.index_for(chunk_pos) else {
+        continue;
+      };
+      let slot = self.pool.get_mut(idx);
+      if !slot.is_seeded() {
+        continue;
+      }
+      for (x, y, pixel) in pixels {
+        slot.chunk.pixels[(x, y)] = pixel;
+        slot.chunk.mark_pixel_dirty(x, y);
+      }
+      slot.dirty = true;
+      slot.modified = true;
+      slot.persisted = false;
+      self.pool.bump_generation(idx);
+    }
+  }
+}
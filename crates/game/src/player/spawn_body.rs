@@ -70,6 +70,8 @@ pub fn tag_new_bodies_as_bombs(
       damage_threshold: 0.03,
       blast_radius: 120.0,
       blast_strength: 60.0,
+      ignites: true,
+      thermal_radius: 160.0,
       detonated: false,
     });
   }
@@ -1,15 +1,94 @@
 mod pixel_world {
+  mod async_storage_e2e;
+  mod blast_ignition_e2e;
+  mod blast_resistance_e2e;
+  mod blast_shockwave_e2e;
+  mod body_anchors;
+  mod body_debug_overlay;
+  mod body_flip_e2e;
+  mod body_free_space_e2e;
+  mod body_imprint_e2e;
+  mod body_keep_alive_e2e;
   mod body_persistence_e2e;
+  mod body_powder_sink_e2e;
   mod body_rapier2d_e2e;
   mod body_reload_stress;
+  mod body_rest_merge_e2e;
+  mod body_sprite_render_e2e;
   mod body_stability_e2e;
+  mod body_tick_blit_e2e;
+  mod brush_softness_e2e;
+  mod buoyancy_density_e2e;
+  mod buoyancy_force_e2e;
+  mod cancel_async_tasks_e2e;
+  mod cell_view_e2e;
+  mod chunk_checksum_e2e;
+  mod chunk_diff_e2e;
+  mod chunk_generation_e2e;
+  mod chunk_memory_budget_e2e;
+  mod chunk_seeded_message_e2e;
+  mod clean_edit_e2e;
+  mod collider_regen_debounce_e2e;
+  mod collision_polygons_e2e;
+  mod context_variants_e2e;
+  mod coord_conversions;
+  mod crack_damage_e2e;
+  mod crash_snapshot_e2e;
+  mod detonation_queue_e2e;
+  mod diagnostics_overlay;
+  mod dig_loose_e2e;
   mod editor_mode_persistence_e2e;
+  mod evaporation_e2e;
+  mod fog_of_war_e2e;
+  mod force_seed_e2e;
+  mod full_bundle_builder;
+  mod gravity_well_direction;
+  mod gravity_well_e2e;
   mod gremlins_stress;
+  mod heat_texture_e2e;
+  mod liquid_depth_e2e;
+  mod liquid_displacement_e2e;
+  mod liquid_foam_e2e;
+  mod liquid_mixing_e2e;
+  mod load_flag_policy;
   mod material_config_roundtrip;
+  mod material_trail_e2e;
   mod named_saves_e2e;
+  mod ore_seeder;
   mod persistence_bevy_e2e;
+  mod persistence_dry_run_e2e;
   mod persistence_e2e;
+  mod phase_partition;
+  mod profiler_breakdown;
+  mod reaction_heat_e2e;
+  mod ready_radius_e2e;
+  mod render_target_info_e2e;
+  mod replay_edit_log_e2e;
+  mod save_journal_e2e;
+  mod save_region_queries;
+  mod scrolling_text_e2e;
+  mod seed_determinism_check_e2e;
+  mod seeding_fallback_e2e;
+  mod simulation_bounds_follow_camera_e2e;
+  mod simulation_freeze_e2e;
+  mod simulation_step_hooks_e2e;
+  mod simulation_threads_e2e;
   mod spawn_pixel_body_e2e;
+  mod splash_e2e;
+  mod sponge_e2e;
+  mod structure_placer;
   mod submergence_e2e;
+  mod surface_to_image;
+  mod swap_priority_e2e;
+  mod text_measure;
+  mod thermal_conductivity_e2e;
+  mod transaction_e2e;
   mod triangulate;
+  mod unsaved_changes_e2e;
+  mod upload_deferral_e2e;
+  mod wet_powder_e2e;
+  mod wind_e2e;
+  mod wind_gusts;
+  mod world_bounds_e2e;
+  mod world_simulate_toggle_e2e;
 }
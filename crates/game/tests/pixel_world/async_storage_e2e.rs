@@ -0,0 +1,425 @@
+//! E2E tests for custom async storage backends.
+//!
+//! Stores the save in an in-memory backend whose operations only complete
+//! after a delay on a timer thread, and checks that the I/O worker still
+//! initializes, saves and reloads through it.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::persistence::backend::{BackendError, BoxFuture, StorageFile, StorageFs};
+use game::pixel_world::{
+  AsyncTaskBehavior, CHUNK_SIZE, ColorIndex, MaterialSeeder, PersistenceConfig, PersistenceControl,
+  Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Time every backend operation takes to complete.
+const LATENCY: Duration = Duration::from_millis(2);
+const MARKER: WorldPos = WorldPos::new(64, 64);
+const MARKER_COLOR: ColorIndex = ColorIndex(7);
+
+/// Resolves once a timer thread wakes it after `duration`.
+struct Delay {
+  duration: Duration,
+  done: Arc<AtomicBool>,
+  started: bool,
+}
+
+impl Future for Delay {
+  type Output = ();
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    if self.done.load(Ordering::Acquire) {
+      return Poll::Ready(());
+    }
+    if !self.started {
+      self.started = true;
+      let (duration, done, waker) = (self.duration, self.done.clone(), cx.waker().clone());
+      std::thread::spawn(move || {
+        std::thread::sleep(duration);
+        done.store(true, Ordering::Release);
+        waker.wake();
+      });
+    }
+    Poll::Pending
+  }
+}
+
+/// In-memory storage whose operations complete after `LATENCY`, like a
+/// remote object store.
+#[derive(Default)]
+struct SlowMemoryFs {
+  files: Mutex<HashMap<String, Arc<Mutex<Vec<u8>>>>>,
+  /// Number of operations started, all of which were delayed.
+  operations: Arc<AtomicUsize>,
+}
+
+impl SlowMemoryFs {
+  fn contains(&self, name: &str) -> bool {
+    self.files.lock().unwrap().contains_key(name)
+  }
+
+  fn operations(&self) -> usize {
+    self.operations.load(Ordering::Relaxed)
+  }
+
+  fn file(&self, data: Arc<Mutex<Vec<u8>>>) -> Box<dyn StorageFile> {
+    Box::new(SlowMemoryFile {
+      data,
+      operations: self.operations.clone(),
+    })
+  }
+}
+
+/// Counts an operation and returns the delay it waits for.
+fn delay(operations: &AtomicUsize) -> Delay {
+  operations.fetch_add(1, Ordering::Relaxed);
+  Delay {
+    duration: LATENCY,
+    done: Arc::new(AtomicBool::new(false)),
+    started: false,
+  }
+}
+
+struct SlowMemoryFile {
+  data: Arc<Mutex<Vec<u8>>>,
+  operations: Arc<AtomicUsize>,
+}
+
+impl StorageFile for SlowMemoryFile {
+  fn read_at<'a>(
+    &'a self,
+    offset: u64,
+    buf: &'a mut [u8],
+  ) -> BoxFuture<'a, Result<(), BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      let data = self.data.lock().unwrap();
+      let start = offset as usize;
+      let src = data
+        .get(start..start + buf.len())
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+      buf.copy_from_slice(src);
+      Ok(())
+    })
+  }
+
+  fn write_at<'a>(
+    &'a self,
+    offset: u64,
+    data: &'a [u8],
+  ) -> BoxFuture<'a, Result<(), BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      let mut file = self.data.lock().unwrap();
+      let start = offset as usize;
+      if file.len() < start + data.len() {
+        file.resize(start + data.len(), 0);
+      }
+      file[start..start + data.len()].copy_from_slice(data);
+      Ok(())
+    })
+  }
+
+  fn len(&self) -> BoxFuture<'_, Result<u64, BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      Ok(self.data.lock().unwrap().len() as u64)
+    })
+  }
+
+  fn set_len(&self, size: u64) -> BoxFuture<'_, Result<(), BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      self.data.lock().unwrap().resize(size as usize, 0);
+      Ok(())
+    })
+  }
+
+  fn sync(&self) -> BoxFuture<'_, Result<(), BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      Ok(())
+    })
+  }
+}
+
+impl StorageFs for SlowMemoryFs {
+  fn open<'a>(
+    &'a self,
+    name: &'a str,
+  ) -> BoxFuture<'a, Result<Box<dyn StorageFile>, BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      let data = self.files.lock().unwrap().get(name).cloned();
+      data
+        .map(|data| self.file(data))
+        .ok_or(BackendError::NotFound)
+    })
+  }
+
+  fn create<'a>(
+    &'a self,
+    name: &'a str,
+  ) -> BoxFuture<'a, Result<Box<dyn StorageFile>, BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      let data = Arc::new(Mutex::new(Vec::new()));
+      self
+        .files
+        .lock()
+        .unwrap()
+        .insert(name.to_string(), data.clone());
+      Ok(self.file(data))
+    })
+  }
+
+  fn open_or_create<'a>(
+    &'a self,
+    name: &'a str,
+  ) -> BoxFuture<'a, Result<Box<dyn StorageFile>, BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      let data = self
+        .files
+        .lock()
+        .unwrap()
+        .entry(name.to_string())
+        .or_default()
+        .clone();
+      Ok(self.file(data))
+    })
+  }
+
+  fn exists<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<bool, BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      Ok(self.contains(name))
+    })
+  }
+
+  fn delete<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<(), BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      match self.files.lock().unwrap().remove(name) {
+        Some(_) => Ok(()),
+        None => Err(BackendError::NotFound),
+      }
+    })
+  }
+
+  fn list(&self) -> BoxFuture<'_, Result<Vec<String>, BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      let mut names: Vec<_> = self.files.lock().unwrap().keys().cloned().collect();
+      names.sort();
+      Ok(names)
+    })
+  }
+
+  fn copy<'a>(&'a self, from: &'a str, to: &'a str) -> BoxFuture<'a, Result<(), BackendError>> {
+    Box::pin(async move {
+      delay(&self.operations).await;
+      let mut files = self.files.lock().unwrap();
+      let data = files
+        .get(from)
+        .ok_or(BackendError::NotFound)?
+        .lock()
+        .unwrap()
+        .clone();
+      files.insert(to.to_string(), Arc::new(Mutex::new(data)));
+      Ok(())
+    })
+  }
+}
+
+struct TestHarness {
+  app: App,
+  camera: Entity,
+}
+
+impl TestHarness {
+  fn new(save_path: &Path, storage: Arc<SlowMemoryFs>) -> Self {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+      task_pool_options: TaskPoolOptions::with_num_threads(4),
+    }));
+
+    app.add_plugins(bevy::transform::TransformPlugin);
+    app.add_plugins(bevy::asset::AssetPlugin::default());
+    app.add_plugins(bevy::image::ImagePlugin::default());
+    app.add_plugins(bevy::scene::ScenePlugin);
+    app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+    app.add_plugins(PixelWorldPlugin::new(
+      PersistenceConfig::at(save_path).with_storage(storage),
+    ));
+    app.insert_resource(AsyncTaskBehavior::Poll);
+
+    let camera = app
+      .world_mut()
+      .spawn((
+        Transform::default(),
+        GlobalTransform::default(),
+        StreamingCamera,
+      ))
+      .id();
+
+    app
+      .world_mut()
+      .commands()
+      .queue(SpawnPixelWorld::new(MaterialSeeder::new(42)));
+
+    app.update(); // Apply spawn command
+
+    Self { app, camera }
+  }
+
+  /// Runs updates until a pixel appears at the given position, or timeout.
+  fn run_until_seeded(&mut self, pos: WorldPos) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+      self.app.update();
+      std::thread::yield_now();
+      if self.pixel(pos).is_some() {
+        return;
+      }
+    }
+    panic!("Pixel at {:?} not found within timeout", pos);
+  }
+
+  fn run_for(&mut self, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+      self.app.update();
+      std::thread::yield_now();
+    }
+  }
+
+  fn pixel(&mut self, pos: WorldPos) -> Option<Pixel> {
+    let mut q = self.app.world_mut().query::<&PixelWorld>();
+    let world = q.single(self.app.world()).ok()?;
+    world.get_pixel(pos).copied()
+  }
+
+  fn paint_marker(&mut self) {
+    let mut q = self.app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(self.app.world_mut()).unwrap();
+    for dy in -5i64..=5 {
+      for dx in -5i64..=5 {
+        world.set_pixel(
+          WorldPos::new(MARKER.x + dx, MARKER.y + dy),
+          Pixel::new(material_ids::STONE, MARKER_COLOR),
+          DebugGizmos::none(),
+        );
+      }
+    }
+  }
+
+  fn has_marker(&mut self) -> bool {
+    self
+      .pixel(MARKER)
+      .is_some_and(|p| p.material == material_ids::STONE && p.color == MARKER_COLOR)
+  }
+
+  /// Triggers a save and runs updates until it completes.
+  fn save_and_wait(&mut self) {
+    let handle = self
+      .app
+      .world_mut()
+      .resource_mut::<PersistenceControl>()
+      .save();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+      self.app.update();
+      std::thread::yield_now();
+      if handle.is_complete() {
+        return;
+      }
+    }
+    panic!("Save did not complete within timeout");
+  }
+
+  fn move_camera(&mut self, position: Vec3) {
+    self
+      .app
+      .world_mut()
+      .get_mut::<Transform>(self.camera)
+      .unwrap()
+      .translation = position;
+    // MinimalPlugins doesn't run transform propagation
+    *self
+      .app
+      .world_mut()
+      .get_mut::<GlobalTransform>(self.camera)
+      .unwrap() = GlobalTransform::from(Transform::from_translation(position));
+  }
+}
+
+fn save_path(temp_dir: &TempDir) -> PathBuf {
+  temp_dir.path().join("world.save")
+}
+
+#[test]
+fn delayed_backend_persists_across_sessions() {
+  let temp_dir = TempDir::new().unwrap();
+  let storage = Arc::new(SlowMemoryFs::default());
+
+  {
+    let mut harness = TestHarness::new(&save_path(&temp_dir), storage.clone());
+    harness.run_until_seeded(MARKER);
+    harness.paint_marker();
+    harness.save_and_wait();
+  }
+
+  assert!(
+    storage.contains("world.save"),
+    "Save should be in the backend"
+  );
+  assert!(storage.contains("world.save.journal"));
+  assert!(
+    !save_path(&temp_dir).exists(),
+    "Nothing should be written to the local filesystem"
+  );
+  assert!(storage.operations() > 0);
+
+  let mut harness = TestHarness::new(&save_path(&temp_dir), storage);
+  harness.run_until_seeded(MARKER);
+  assert!(harness.has_marker(), "Marker should load from the backend");
+}
+
+#[test]
+fn delayed_backend_reloads_unloaded_chunks() {
+  let temp_dir = TempDir::new().unwrap();
+  let storage = Arc::new(SlowMemoryFs::default());
+  let mut harness = TestHarness::new(&save_path(&temp_dir), storage);
+
+  harness.run_until_seeded(MARKER);
+  harness.paint_marker();
+
+  // Unloading the chunk writes it through the backend
+  harness.move_camera(Vec3::new(5.0 * CHUNK_SIZE as f32, 0.0, 0.0));
+  harness.run_for(Duration::from_secs(1));
+  assert!(
+    harness.pixel(MARKER).is_none(),
+    "Marker chunk should unload"
+  );
+
+  // Coming back reads it through the backend
+  harness.move_camera(Vec3::ZERO);
+  harness.run_until_seeded(MARKER);
+  assert!(
+    harness.has_marker(),
+    "Marker should reload from the backend"
+  );
+}
@@ -0,0 +1,175 @@
+//! E2E tests for explosions igniting flammable pixels.
+//!
+//! A bomb detonates between a wood block in plain view and a stone wall
+//! hiding a second wood block. Tests that the exposed wood starts burning,
+//! that stone and wood out of line of sight do not, and that bombs without
+//! `ignites` start no fires.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Bomb, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, PersistenceConfig,
+  Pixel, PixelBodiesPlugin, PixelFlags, PixelWorld, PixelWorldPlugin, SpawnPixelWorld,
+  StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const BOMB: Vec3 = Vec3::new(100.0, 100.0, 0.0);
+
+/// Wood in line of sight, 20px right of the bomb.
+const EXPOSED_WOOD: (i64, i64, i64, i64) = (120, 95, 130, 106);
+/// Stone wall 19px left of the bomb.
+const STONE_WALL: (i64, i64, i64, i64) = (76, 90, 81, 111);
+/// Wood hidden behind the stone wall.
+const HIDDEN_WOOD: (i64, i64, i64, i64) = (68, 95, 73, 106);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn fill(app: &mut App, (x0, y0, x1, y1): (i64, i64, i64, i64), material: MaterialId) {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  for y in y0..y1 {
+    for x in x0..x1 {
+      world.set_pixel(
+        WorldPos::new(x, y),
+        Pixel::new(material, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+  }
+}
+
+fn burning_count(app: &mut App, (x0, y0, x1, y1): (i64, i64, i64, i64)) -> usize {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  (y0..y1)
+    .flat_map(|y| (x0..x1).map(move |x| WorldPos::new(x, y)))
+    .filter_map(|pos| world.get_pixel(pos))
+    .filter(|p| p.flags.contains(PixelFlags::BURNING))
+    .count()
+}
+
+/// Builds the scene, detonates a bomb and runs until it is gone.
+fn detonate(ignites: bool) -> App {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  fill(&mut app, EXPOSED_WOOD, material_ids::WOOD);
+  fill(&mut app, STONE_WALL, material_ids::STONE);
+  fill(&mut app, HIDDEN_WOOD, material_ids::WOOD);
+  app.update();
+
+  let bomb = app
+    .world_mut()
+    .spawn((
+      Bomb {
+        damage_threshold: 0.5,
+        blast_radius: 4.0,
+        blast_strength: 10.0,
+        ignites,
+        thermal_radius: 40.0,
+        detonated: true,
+      },
+      Transform::from_translation(BOMB),
+      GlobalTransform::from_translation(BOMB),
+    ))
+    .id();
+
+  for _ in 0..5 {
+    app.update();
+    if app.world().get_entity(bomb).is_err() {
+      return app;
+    }
+  }
+  panic!("Bomb did not detonate");
+}
+
+#[test]
+fn blast_ignites_exposed_flammable_pixels() {
+  let mut app = detonate(true);
+
+  assert!(
+    burning_count(&mut app, EXPOSED_WOOD) > 0,
+    "Wood facing the blast should start burning"
+  );
+  assert_eq!(
+    burning_count(&mut app, STONE_WALL),
+    0,
+    "Stone is not flammable"
+  );
+  assert_eq!(
+    burning_count(&mut app, HIDDEN_WOOD),
+    0,
+    "Wood behind the stone wall is out of line of sight"
+  );
+}
+
+#[test]
+fn non_igniting_blast_starts_no_fire() {
+  let mut app = detonate(false);
+
+  assert_eq!(burning_count(&mut app, EXPOSED_WOOD), 0);
+  assert_eq!(burning_count(&mut app, HIDDEN_WOOD), 0);
+}
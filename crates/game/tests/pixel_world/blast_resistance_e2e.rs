@@ -0,0 +1,234 @@
+//! E2E tests for per-material blast resistance.
+//!
+//! A bomb detonates in front of a wood block backed by a wall, with stone
+//! behind the wall. Tests that indestructible bedrock and reinforced walls
+//! too strong for the blast survive and shield the stone, while the wood in
+//! front is destroyed, and that a strong enough blast breaks through a
+//! reinforced wall.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::{EffectsConfig, MaterialConfig};
+use game::pixel_world::{
+  AsyncTaskBehavior, Bomb, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelBodiesPlugin, PixelWorld,
+  PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const BEDROCK: MaterialId = MaterialId(7);
+const STEEL: MaterialId = MaterialId(8);
+
+/// Blast resistance of the reinforced wall material.
+const STEEL_RESISTANCE: f32 = 20.0;
+
+const BOMB: Vec3 = Vec3::new(100.0, 100.0, 0.0);
+
+/// Wood between the bomb and the wall.
+const FRONT_WOOD: (i64, i64, i64, i64) = (110, 95, 115, 106);
+/// Wall 16px right of the bomb, tall enough to cover every ray to the stone.
+const WALL: (i64, i64, i64, i64) = (116, 80, 120, 121);
+/// Stone behind the wall, within blast radius.
+const BEHIND_STONE: (i64, i64, i64, i64) = (121, 95, 125, 106);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn wall_material(name: &str, blast_resistance: f32, indestructible: bool) -> MaterialConfig {
+  MaterialConfig {
+    density: 255,
+    effects: Some(EffectsConfig {
+      on_burn: None,
+      blast_resistance,
+      indestructible,
+      durability: 0,
+      reaction_heat: 0,
+    }),
+    ..MaterialConfig::new(name, PhysicsState::Solid)
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config.materials.push(wall_material("Bedrock", 0.0, true));
+  config
+    .materials
+    .push(wall_material("Steel", STEEL_RESISTANCE, false));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn fill(app: &mut App, (x0, y0, x1, y1): (i64, i64, i64, i64), material: MaterialId) {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  for y in y0..y1 {
+    for x in x0..x1 {
+      world.set_pixel(
+        WorldPos::new(x, y),
+        Pixel::new(material, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+  }
+}
+
+fn count(app: &mut App, (x0, y0, x1, y1): (i64, i64, i64, i64), material: MaterialId) -> usize {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  (y0..y1)
+    .flat_map(|y| (x0..x1).map(move |x| WorldPos::new(x, y)))
+    .filter_map(|pos| world.get_pixel(pos))
+    .filter(|p| p.material == material)
+    .count()
+}
+
+fn area((x0, y0, x1, y1): (i64, i64, i64, i64)) -> usize {
+  ((x1 - x0) * (y1 - y0)) as usize
+}
+
+/// Builds the scene with a wall of `wall`, detonates a bomb of the given
+/// strength and runs until it is gone.
+fn detonate(wall: MaterialId, blast_strength: f32) -> App {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  fill(&mut app, FRONT_WOOD, material_ids::WOOD);
+  fill(&mut app, WALL, wall);
+  fill(&mut app, BEHIND_STONE, material_ids::STONE);
+  app.update();
+
+  let bomb = app
+    .world_mut()
+    .spawn((
+      Bomb {
+        damage_threshold: 0.5,
+        blast_radius: 30.0,
+        blast_strength,
+        ignites: false,
+        thermal_radius: 0.0,
+        detonated: true,
+      },
+      Transform::from_translation(BOMB),
+      GlobalTransform::from_translation(BOMB),
+    ))
+    .id();
+
+  for _ in 0..5 {
+    app.update();
+    if app.world().get_entity(bomb).is_err() {
+      return app;
+    }
+  }
+  panic!("Bomb did not detonate");
+}
+
+#[test]
+fn bedrock_wall_shields_material_behind_it() {
+  let mut app = detonate(BEDROCK, 1000.0);
+
+  assert_eq!(
+    count(&mut app, FRONT_WOOD, material_ids::WOOD),
+    0,
+    "Wood in front of the wall should be destroyed"
+  );
+  assert_eq!(
+    count(&mut app, WALL, BEDROCK),
+    area(WALL),
+    "Bedrock is indestructible"
+  );
+  assert_eq!(
+    count(&mut app, BEHIND_STONE, material_ids::STONE),
+    area(BEHIND_STONE),
+    "Stone behind the bedrock wall should be shielded"
+  );
+}
+
+#[test]
+fn weak_blast_does_not_break_reinforced_wall() {
+  let mut app = detonate(STEEL, STEEL_RESISTANCE / 2.0);
+
+  assert_eq!(count(&mut app, FRONT_WOOD, material_ids::WOOD), 0);
+  assert_eq!(
+    count(&mut app, WALL, STEEL),
+    area(WALL),
+    "Steel resists blasts weaker than its resistance"
+  );
+  assert_eq!(
+    count(&mut app, BEHIND_STONE, material_ids::STONE),
+    area(BEHIND_STONE)
+  );
+}
+
+#[test]
+fn strong_blast_breaks_reinforced_wall() {
+  let mut app = detonate(STEEL, STEEL_RESISTANCE * 10.0);
+
+  assert_eq!(count(&mut app, FRONT_WOOD, material_ids::WOOD), 0);
+  assert!(
+    count(&mut app, WALL, STEEL) < area(WALL),
+    "A strong enough blast should break through steel"
+  );
+  assert!(
+    count(&mut app, BEHIND_STONE, material_ids::STONE) < area(BEHIND_STONE),
+    "Stone behind a breached wall is exposed to the blast"
+  );
+}
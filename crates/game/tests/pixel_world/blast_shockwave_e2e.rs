@@ -0,0 +1,211 @@
+//! E2E tests for capped, multi-frame bomb blasts.
+//!
+//! Detonates a bomb with an oversized radius next to a strip of stone and
+//! checks that the cleared part of the strip grows by at most
+//! `DetonationConfig::shockwave_speed` per frame and stops at
+//! `DetonationConfig::max_blast_radius`. Blasts below the shockwave
+//! threshold still resolve in a single frame.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Bomb, Chunk, ChunkPos, ChunkSeeder, ColorIndex, DetonationConfig,
+  PersistenceConfig, Pixel, PixelBodiesPlugin, PixelWorld, PixelWorldPlugin, Shockwaves,
+  SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const MAX_BLAST_RADIUS: f32 = 200.0;
+const SHOCKWAVE_THRESHOLD: f32 = 32.0;
+const SHOCKWAVE_SPEED: f32 = 40.0;
+
+/// Bomb position; the stone strip runs from here along +x.
+const CENTER: (i64, i64) = (0, 100);
+/// Length of the stone strip, well beyond the maximum blast radius.
+const STRIP_LENGTH: i64 = 600;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(DetonationConfig {
+    max_blast_radius: MAX_BLAST_RADIUS,
+    shockwave_threshold: SHOCKWAVE_THRESHOLD,
+    shockwave_speed: SHOCKWAVE_SPEED,
+    ..default()
+  });
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q.single(app.world()).is_ok_and(|world| {
+      world
+        .get_pixel(WorldPos::new(CENTER.0 + STRIP_LENGTH, CENTER.1))
+        .is_some()
+    }) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Creates a seeded world with a 3-pixel-thick stone strip along +x from
+/// the bomb position.
+fn setup() -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  for y in CENTER.1 - 1..=CENTER.1 + 1 {
+    for x in CENTER.0..CENTER.0 + STRIP_LENGTH {
+      world.set_pixel(
+        WorldPos::new(x, y),
+        Pixel::new(material_ids::STONE, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+  }
+  (temp_dir, app)
+}
+
+fn detonate(app: &mut App, blast_radius: f32) {
+  let pos = Vec3::new(CENTER.0 as f32, CENTER.1 as f32, 0.0);
+  app.world_mut().spawn((
+    Bomb {
+      damage_threshold: 0.5,
+      blast_radius,
+      blast_strength: 1.0e6,
+      ignites: false,
+      thermal_radius: 0.0,
+      detonated: true,
+    },
+    Transform::from_translation(pos),
+    GlobalTransform::from_translation(pos),
+  ));
+}
+
+/// Returns the distance from the bomb to the first stone pixel left on the
+/// strip's center line.
+fn cleared_extent(app: &mut App) -> i64 {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  (1..STRIP_LENGTH)
+    .find(|&dx| {
+      world
+        .get_pixel(WorldPos::new(CENTER.0 + dx, CENTER.1))
+        .is_some_and(|p| p.material == material_ids::STONE)
+    })
+    .unwrap_or(STRIP_LENGTH)
+}
+
+fn shockwave_count(app: &mut App) -> usize {
+  app.world().resource::<Shockwaves>().len()
+}
+
+#[test]
+fn oversized_blast_expands_over_frames_up_to_max_radius() {
+  let (_temp_dir, mut app) = setup();
+  detonate(&mut app, 1000.0);
+
+  let mut extents = Vec::new();
+  for _ in 0..20 {
+    app.update();
+    extents.push(cleared_extent(&mut app));
+    if shockwave_count(&mut app) == 0 {
+      break;
+    }
+  }
+
+  assert_eq!(shockwave_count(&mut app), 0, "Shockwave should finish");
+  assert!(
+    extents.len() >= 4,
+    "Blast should take several frames, took {}: {extents:?}",
+    extents.len()
+  );
+  for (frame, pair) in extents.windows(2).enumerate() {
+    assert!(
+      pair[1] > pair[0],
+      "Destruction should expand every frame: {extents:?}"
+    );
+    assert!(
+      pair[1] <= (frame as i64 + 2) * SHOCKWAVE_SPEED as i64 + 1,
+      "Shockwave should grow by at most its speed per frame: {extents:?}"
+    );
+  }
+
+  let last = *extents.last().unwrap();
+  assert!(
+    last > MAX_BLAST_RADIUS as i64 - 2 && last <= MAX_BLAST_RADIUS as i64 + 1,
+    "Blast should stop at the max radius, reached {last}"
+  );
+
+  for _ in 0..10 {
+    app.update();
+  }
+  assert_eq!(
+    cleared_extent(&mut app),
+    last,
+    "Nothing beyond the max radius should be destroyed"
+  );
+}
+
+#[test]
+fn small_blast_resolves_in_one_frame() {
+  let (_temp_dir, mut app) = setup();
+  detonate(&mut app, 20.0);
+
+  app.update();
+  assert_eq!(shockwave_count(&mut app), 0);
+  let extent = cleared_extent(&mut app);
+  assert!(
+    (19..=21).contains(&extent),
+    "Small blast should clear its full radius at once, reached {extent}"
+  );
+}
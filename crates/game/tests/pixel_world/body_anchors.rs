@@ -0,0 +1,79 @@
+//! Tests for pixel body anchor points.
+//!
+//! Tests that anchors map to the world-space center of their pixel as the
+//! body moves and rotates, and resolve to `None` once the pixel is gone.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use game::pixel_world::{AnchorId, LocalPos, Pixel, PixelBodyLoader, material_ids};
+
+const TORCH: AnchorId = AnchorId(1);
+
+fn body_with_torch() -> game::pixel_world::PixelBody {
+  // 10x6 body, origin (-5, -3); the torch pixel sits in the top-right corner
+  let mut body = PixelBodyLoader::rectangle(10, 6, material_ids::WOOD);
+  body.anchors.push((LocalPos::new(9, 5), TORCH));
+  body
+}
+
+fn transform(translation: Vec2, angle: f32) -> GlobalTransform {
+  GlobalTransform::from(
+    Transform::from_translation(translation.extend(0.0))
+      .with_rotation(Quat::from_rotation_z(angle)),
+  )
+}
+
+fn assert_near(actual: Option<Vec2>, expected: Vec2) {
+  let actual = actual.expect("Anchor should resolve");
+  assert!(
+    actual.distance(expected) < 1e-3,
+    "Anchor at {:?}, expected {:?}",
+    actual,
+    expected
+  );
+}
+
+#[test]
+fn anchor_tracks_translation_and_rotation() {
+  let body = body_with_torch();
+  let pos = Vec2::new(100.0, 50.0);
+  // Pixel (9, 5) center relative to the body origin
+  let local = Vec2::new(4.5, 2.5);
+
+  assert_near(
+    body.anchor_world_pos(TORCH, &transform(pos, 0.0)),
+    pos + local,
+  );
+  assert_near(
+    body.anchor_world_pos(TORCH, &transform(pos, FRAC_PI_2)),
+    pos + Vec2::new(-local.y, local.x),
+  );
+  assert_near(
+    body.anchor_world_pos(TORCH, &transform(pos, 2.0 * FRAC_PI_2)),
+    pos - local,
+  );
+}
+
+#[test]
+fn destroyed_anchor_pixel_returns_none() {
+  let mut body = body_with_torch();
+  let at = transform(Vec2::ZERO, 0.3);
+  assert!(body.anchor_world_pos(TORCH, &at).is_some());
+
+  // Eroding a different pixel leaves the anchor intact
+  body.set_pixel(0, 0, Pixel::VOID);
+  assert!(body.anchor_world_pos(TORCH, &at).is_some());
+
+  body.set_pixel(9, 5, Pixel::VOID);
+  assert_eq!(body.anchor_world_pos(TORCH, &at), None);
+}
+
+#[test]
+fn unknown_anchor_returns_none() {
+  let body = body_with_torch();
+  assert_eq!(
+    body.anchor_world_pos(AnchorId(2), &GlobalTransform::IDENTITY),
+    None
+  );
+}
@@ -0,0 +1,62 @@
+//! Tests for the pixel body debug overlay geometry.
+//!
+//! Tests that the overlay reports the transformed AABB and origin of a body
+//! at a known transform, and that highlighted mask pixels cover exactly the
+//! solid pixels and lie inside the AABB.
+
+use bevy::prelude::*;
+use game::pixel_world::visual_debug::{PixelBodyOverlay, solid_pixel_centers};
+use game::pixel_world::{PixelBody, PixelBodyLoader, WorldRect, material_ids};
+
+fn body() -> PixelBody {
+  // 10x6 body, origin (-5, -3)
+  PixelBodyLoader::rectangle(10, 6, material_ids::WOOD)
+}
+
+fn transform(translation: Vec2, angle: f32) -> GlobalTransform {
+  GlobalTransform::from(
+    Transform::from_translation(translation.extend(0.0))
+      .with_rotation(Quat::from_rotation_z(angle)),
+  )
+}
+
+#[test]
+fn overlay_matches_transformed_aabb() {
+  let overlay = PixelBodyOverlay::compute(&body(), &transform(Vec2::new(100.5, 50.5), 0.0));
+
+  // Grid spans x 95.5..105.5 and y 47.5..53.5; the AABB rounds outward
+  // and keeps a one-pixel margin like the blit rect
+  assert_eq!(overlay.aabb, WorldRect::new(95, 47, 12, 8));
+  assert_eq!(overlay.origin, Vec2::new(100.5, 50.5));
+  assert_eq!(
+    overlay.aabb_corners(),
+    [
+      Vec2::new(95.0, 47.0),
+      Vec2::new(107.0, 47.0),
+      Vec2::new(107.0, 55.0),
+      Vec2::new(95.0, 55.0),
+    ]
+  );
+}
+
+#[test]
+fn mask_pixels_are_solid_and_inside_aabb() {
+  let mut body = body();
+  body.set_solid(0, 0, false);
+  body.set_solid(9, 5, false);
+  let transform = transform(Vec2::new(-40.0, 12.0), 0.3);
+
+  let overlay = PixelBodyOverlay::compute(&body, &transform);
+  let [min, _, max, _] = overlay.aabb_corners();
+  let centers: Vec<Vec2> = solid_pixel_centers(&body, &transform).collect();
+
+  assert_eq!(centers.len(), body.solid_count());
+  assert_eq!(centers.len(), 10 * 6 - 2);
+  for center in centers {
+    assert!(
+      center.cmpge(min).all() && center.cmple(max).all(),
+      "Mask pixel {center:?} should lie inside the AABB {:?}",
+      overlay.aabb
+    );
+  }
+}
@@ -0,0 +1,216 @@
+//! E2E tests for flipped pixel body spawns.
+//!
+//! Spawns the same asymmetric image once as-is and once flipped through
+//! `SpawnPixelBodyFromImage::flipped`, and checks that the flipped body's
+//! pixels and shape mask mirror the unflipped body's.
+
+use std::path::Path;
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use game::pixel_world::{
+  Chunk, ChunkPos, ChunkSeeder, PersistenceConfig, Pixel, PixelBodiesPlugin, PixelBody,
+  PixelWorldPlugin, SpawnPixelBodyFromImage, SpawnPixelWorld, StreamingCamera, material_ids,
+};
+use tempfile::TempDir;
+
+const WIDTH: u32 = 5;
+const HEIGHT: u32 = 3;
+
+/// Seeds every chunk with void so bodies spawn into open air.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(save_path: &Path) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(save_path)));
+  app.add_plugins(PixelBodiesPlugin);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+/// Creates an asymmetric image: an "L" whose foot points right, shaded from
+/// dark on the left to light on the right.
+///
+/// ```text
+/// X . . . .
+/// X . . . .
+/// X X X X X
+/// ```
+fn create_asymmetric_image(app: &mut App) -> Handle<Image> {
+  let mut data = Vec::new();
+  for y in 0..HEIGHT {
+    for x in 0..WIDTH {
+      let solid = x == 0 || y == HEIGHT - 1;
+      let shade = (x * 60) as u8;
+      data.extend_from_slice(&[shade, shade / 2, 0, if solid { 255 } else { 0 }]);
+    }
+  }
+
+  let mut image = Image::new(
+    bevy::render::render_resource::Extent3d {
+      width: WIDTH,
+      height: HEIGHT,
+      depth_or_array_layers: 1,
+    },
+    bevy::render::render_resource::TextureDimension::D2,
+    data,
+    bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
+    RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+  );
+  image.sampler = ImageSampler::nearest();
+
+  let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+  images.add(image)
+}
+
+/// Spawns the image unflipped to the left of the origin and with the given
+/// flip to the right, and returns both bodies' pixels and shape masks.
+fn spawn_pair(horizontal: bool, vertical: bool) -> (PixelBodySnapshot, PixelBodySnapshot) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir.path().join("flip_test.save"));
+  for _ in 0..5 {
+    app.update();
+  }
+
+  let image = create_asymmetric_image(&mut app);
+  let mut commands = app.world_mut().commands();
+  commands.queue(SpawnPixelBodyFromImage::new(
+    image.clone(),
+    material_ids::WOOD,
+    Vec2::new(-50.0, 100.0),
+  ));
+  commands.queue(
+    SpawnPixelBodyFromImage::new(image, material_ids::WOOD, Vec2::new(50.0, 100.0))
+      .flipped(horizontal, vertical),
+  );
+  for _ in 0..10 {
+    app.update();
+  }
+
+  let mut q = app.world_mut().query::<(&Transform, &PixelBody)>();
+  let mut bodies: Vec<_> = q
+    .iter(app.world())
+    .map(|(transform, body)| (transform.translation.x, PixelBodySnapshot::of(body)))
+    .collect();
+  assert_eq!(bodies.len(), 2, "Both bodies should spawn");
+  bodies.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+  let flipped = bodies.pop().unwrap().1;
+  let original = bodies.pop().unwrap().1;
+  (original, flipped)
+}
+
+/// Pixels and solidity of a body, indexed by local coordinates.
+struct PixelBodySnapshot {
+  width: u32,
+  height: u32,
+  origin: IVec2,
+  pixels: Vec<Option<Pixel>>,
+}
+
+impl PixelBodySnapshot {
+  fn of(body: &PixelBody) -> Self {
+    let pixels = (0..body.height())
+      .flat_map(|y| (0..body.width()).map(move |x| (x, y)))
+      .map(|(x, y)| body.is_solid(x, y).then(|| *body.get_pixel(x, y).unwrap()))
+      .collect();
+    Self {
+      width: body.width(),
+      height: body.height(),
+      origin: body.origin,
+      pixels,
+    }
+  }
+
+  fn at(&self, x: u32, y: u32) -> Option<Pixel> {
+    self.pixels[(y * self.width + x) as usize]
+  }
+}
+
+fn assert_mirrored(
+  original: &PixelBodySnapshot,
+  flipped: &PixelBodySnapshot,
+  horizontal: bool,
+  vertical: bool,
+) {
+  assert_eq!(
+    (flipped.width, flipped.height),
+    (original.width, original.height)
+  );
+  assert_eq!(flipped.origin, original.origin, "Origin stays centered");
+
+  for y in 0..original.height {
+    for x in 0..original.width {
+      let mx = if horizontal {
+        original.width - 1 - x
+      } else {
+        x
+      };
+      let my = if vertical { original.height - 1 - y } else { y };
+      assert_eq!(
+        flipped.at(mx, my),
+        original.at(x, y),
+        "Pixel ({}, {}) should land at ({}, {})",
+        x,
+        y,
+        mx,
+        my
+      );
+    }
+  }
+}
+
+#[test]
+fn horizontal_flip_mirrors_pixels() {
+  let (original, flipped) = spawn_pair(true, false);
+
+  // The image is asymmetric, so mirroring actually changes the body
+  assert!(original.at(0, HEIGHT - 1).is_some());
+  assert!(original.at(WIDTH - 1, HEIGHT - 1).is_none());
+
+  assert_mirrored(&original, &flipped, true, false);
+}
+
+#[test]
+fn flipping_both_axes_mirrors_pixels() {
+  let (original, flipped) = spawn_pair(true, true);
+  assert_mirrored(&original, &flipped, true, true);
+}
+
+#[test]
+fn no_flip_matches_original() {
+  let (original, flipped) = spawn_pair(false, false);
+  assert_mirrored(&original, &flipped, false, false);
+}
@@ -0,0 +1,221 @@
+//! E2E tests for spawning pixel bodies into free space.
+//!
+//! Terrain is solid stone except for a single cavity. A body spawned with
+//! `SpawnPixelBody::find_free_space` must land inside the cavity, and a
+//! search rect without room must report `PixelBodySpawnFailed`. A body
+//! queued before the terrain is seeded waits for it instead of failing.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::asset::RenderAssetUsages;
+use bevy::ecs::message::Messages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelBodiesPlugin, PixelBody, PixelBodySpawnFailed, PixelWorld, PixelWorldPlugin, SpawnPixelBody,
+  SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect, material_ids,
+};
+use tempfile::TempDir;
+
+const ASSET_PATH: &str = "free_space_box.png";
+const BODY_SIZE: u32 = 8;
+/// The only open area: a 16x16 pocket in otherwise solid stone.
+const CAVITY: WorldRect = WorldRect::new(40, 20, 16, 16);
+const SEARCH: WorldRect = WorldRect::new(0, 0, 128, 64);
+
+/// Seeds solid stone everywhere except `CAVITY`.
+struct CavitySeeder;
+
+impl ChunkSeeder for CavitySeeder {
+  fn seed(&self, pos: ChunkPos, chunk: &mut Chunk) {
+    let origin = pos.to_world();
+    let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        let world = WorldPos::new(origin.x + x as i64, origin.y + y as i64);
+        chunk.pixels[(x, y)] = if in_cavity(world) { Pixel::VOID } else { stone };
+      }
+    }
+  }
+}
+
+fn in_cavity(pos: WorldPos) -> bool {
+  pos.x >= CAVITY.x
+    && pos.x < CAVITY.x + CAVITY.width as i64
+    && pos.y >= CAVITY.y
+    && pos.y < CAVITY.y + CAVITY.height as i64
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(CavitySeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    let seeded = q.single(app.world()).is_ok_and(|world| {
+      [
+        WorldPos::new(SEARCH.x, SEARCH.y),
+        WorldPos::new(
+          SEARCH.x + SEARCH.width as i64 - 1,
+          SEARCH.y + SEARCH.height as i64 - 1,
+        ),
+      ]
+      .iter()
+      .all(|&pos| world.get_pixel(pos).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Queues a body spawn and resolves its image in memory.
+fn spawn_body(app: &mut App, search_rect: WorldRect) {
+  app.world_mut().commands().queue(
+    SpawnPixelBody::new(ASSET_PATH, material_ids::WOOD, Vec2::ZERO).find_free_space(search_rect),
+  );
+  app.update();
+
+  let handle: Handle<Image> = app.world().resource::<AssetServer>().load(ASSET_PATH);
+  let mut image = Image::new_fill(
+    bevy::render::render_resource::Extent3d {
+      width: BODY_SIZE,
+      height: BODY_SIZE,
+      depth_or_array_layers: 1,
+    },
+    bevy::render::render_resource::TextureDimension::D2,
+    &[255, 255, 255, 255],
+    bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
+    RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+  );
+  image.sampler = ImageSampler::nearest();
+  app
+    .world_mut()
+    .resource_mut::<Assets<Image>>()
+    .insert(&handle, image)
+    .unwrap();
+}
+
+#[test]
+fn body_lands_in_cavity() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  spawn_body(&mut app, SEARCH);
+  for _ in 0..5 {
+    app.update();
+  }
+
+  let mut q = app.world_mut().query::<(&PixelBody, &GlobalTransform)>();
+  let (body, transform) = q
+    .single(app.world())
+    .expect("Body should spawn in the cavity");
+
+  // The scan is bottom-to-top, left-to-right, so the body takes the
+  // cavity's bottom-left corner
+  let corner = transform.translation().truncate().as_ivec2() + body.origin;
+  assert_eq!(corner, IVec2::new(CAVITY.x as i32, CAVITY.y as i32));
+
+  for y in 0..body.height() {
+    for x in 0..body.width() {
+      if body.is_solid(x, y) {
+        let pos = WorldPos::new((corner.x + x as i32) as i64, (corner.y + y as i32) as i64);
+        assert!(in_cavity(pos), "Body pixel {:?} overlaps terrain", pos);
+      }
+    }
+  }
+}
+
+#[test]
+fn no_free_space_reports_failure() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  // Solid rows beneath the cavity
+  let search_rect = WorldRect::new(0, 0, 128, CAVITY.y as u32);
+  spawn_body(&mut app, search_rect);
+
+  let mut cursor = app
+    .world()
+    .resource::<Messages<PixelBodySpawnFailed>>()
+    .get_cursor();
+  let mut failures = Vec::new();
+  for _ in 0..5 {
+    app.update();
+    let messages = app.world().resource::<Messages<PixelBodySpawnFailed>>();
+    failures.extend(cursor.read(messages).map(|m| m.search_rect));
+  }
+
+  assert_eq!(failures, vec![search_rect]);
+  let mut q = app.world_mut().query::<&PixelBody>();
+  assert_eq!(q.iter(app.world()).count(), 0, "No body should be spawned");
+}
+
+#[test]
+fn body_waits_for_search_rect_to_load() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+
+  // Queued before any chunk is seeded
+  spawn_body(&mut app, SEARCH);
+  let mut cursor = app
+    .world()
+    .resource::<Messages<PixelBodySpawnFailed>>()
+    .get_cursor();
+
+  let deadline = Instant::now() + Duration::from_secs(5);
+  let mut q = app.world_mut().query::<(&PixelBody, &GlobalTransform)>();
+  while q.iter(app.world()).next().is_none() && Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let messages = app.world().resource::<Messages<PixelBodySpawnFailed>>();
+    assert_eq!(
+      cursor.read(messages).count(),
+      0,
+      "Unloaded terrain should not fail the spawn"
+    );
+  }
+
+  let (body, transform) = q
+    .single(app.world())
+    .expect("Body should spawn once the terrain loads");
+  let corner = transform.translation().truncate().as_ivec2() + body.origin;
+  assert_eq!(corner, IVec2::new(CAVITY.x as i32, CAVITY.y as i32));
+}
@@ -0,0 +1,210 @@
+//! E2E tests for pixel bodies imprinting soft terrain.
+//!
+//! A body is slid across a strip of soft soil several pixels per tick. The
+//! soil directly beneath its swept path must be imprinted (darkened and
+//! marked), with no gaps between ticks, while soil outside the path, soil
+//! below the surface row, and hard stone stay unchanged.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, DisplacementState, IMPRINTED,
+  LastBlitTransform, MaterialId, Materials, MaterialsConfig, PersistenceConfig, Pixel,
+  PixelBodiesPlugin, PixelBodyIdGenerator, PixelBodyLoader, PixelWorld, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const SOFTNESS: u8 = 64;
+const TERRAIN_COLOR: ColorIndex = ColorIndex(32);
+const BODY_SIZE: u32 = 8;
+/// Terrain strip spans this x range; the body slides within it.
+const STRIP: (i64, i64) = (-60, 60);
+/// Columns checked, clear of the strip's edges where powder slides off.
+const CHECKED: (i64, i64) = (-52, 52);
+/// Body centers at the start and end of the slide.
+const PATH: (i64, i64) = (-40, 20);
+/// Pixels the body moves per tick.
+const STEP: i64 = 6;
+
+/// Seeds every chunk with void; terrain is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config.materials[material_ids::SOIL.0 as usize].softness = SOFTNESS;
+  config.materials[material_ids::STONE.0 as usize].softness = 0;
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      world.get_pixel(WorldPos::new(STRIP.0, -4)).is_some()
+        && world.get_pixel(WorldPos::new(STRIP.1, 0)).is_some()
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Paints three rows of terrain below y = 0 across the strip, on a stone
+/// floor.
+fn paint_strip(app: &mut App, material: MaterialId) {
+  with_world(app, |world| {
+    for y in -4..0 {
+      for x in STRIP.0..STRIP.1 {
+        let material = if y < -3 {
+          material_ids::STONE
+        } else {
+          material
+        };
+        world.set_pixel(
+          WorldPos::new(x, y),
+          Pixel::new(material, TERRAIN_COLOR),
+          DebugGizmos::none(),
+        );
+      }
+    }
+  });
+}
+
+/// Spawns a body resting on the strip, slides it along `PATH` `STEP`
+/// pixels per tick, and lets it settle at the end.
+fn slide_body(app: &mut App) {
+  let body = PixelBodyLoader::rectangle(BODY_SIZE, BODY_SIZE, material_ids::STONE);
+  let id = app
+    .world_mut()
+    .resource_mut::<PixelBodyIdGenerator>()
+    .generate();
+  // Origin is (-4, -4), so the body's bottom row sits on y = 0
+  let transform = Transform::from_xyz(PATH.0 as f32, BODY_SIZE as f32 / 2.0, 0.0);
+  let entity = app
+    .world_mut()
+    .spawn((
+      body,
+      LastBlitTransform::default(),
+      DisplacementState::default(),
+      transform,
+      GlobalTransform::from(transform),
+      id,
+    ))
+    .id();
+  app.update();
+
+  let mut x = PATH.0;
+  while x < PATH.1 {
+    x = (x + STEP).min(PATH.1);
+    app
+      .world_mut()
+      .get_mut::<Transform>(entity)
+      .unwrap()
+      .translation
+      .x = x as f32;
+    app.update();
+  }
+  for _ in 0..3 {
+    app.update();
+  }
+}
+
+fn pixel_at(app: &mut App, x: i64, y: i64) -> Pixel {
+  with_world(app, |world| *world.get_pixel(WorldPos::new(x, y)).unwrap())
+}
+
+#[test]
+fn sliding_body_imprints_its_swept_path() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  paint_strip(&mut app, material_ids::SOIL);
+  slide_body(&mut app);
+
+  let half = BODY_SIZE as i64 / 2;
+  let swept = PATH.0 - half..PATH.1 + half;
+  for x in CHECKED.0..CHECKED.1 {
+    let surface = pixel_at(&mut app, x, -1);
+    assert_eq!(surface.material, material_ids::SOIL);
+    if swept.contains(&x) {
+      assert_eq!(surface.damage, IMPRINTED, "Surface at x={x} is in the path");
+      assert_eq!(surface.color, ColorIndex(TERRAIN_COLOR.0 + SOFTNESS));
+    } else {
+      assert_eq!(surface.damage, 0, "Surface at x={x} is outside the path");
+      assert_eq!(surface.color, TERRAIN_COLOR);
+    }
+
+    for y in [-3, -2] {
+      let below = pixel_at(&mut app, x, y);
+      assert_eq!(below.damage, 0, "Soil at ({x}, {y}) is below the surface");
+      assert_eq!(below.color, TERRAIN_COLOR);
+    }
+  }
+}
+
+#[test]
+fn hard_terrain_takes_no_imprint() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  paint_strip(&mut app, material_ids::STONE);
+  slide_body(&mut app);
+
+  for x in CHECKED.0..CHECKED.1 {
+    let surface = pixel_at(&mut app, x, -1);
+    assert_eq!(surface.damage, 0, "Stone at x={x} should not be imprinted");
+    assert_eq!(surface.color, TERRAIN_COLOR);
+  }
+}
@@ -0,0 +1,343 @@
+//! E2E tests for keeping chunks around persistable bodies alive off-camera.
+//!
+//! Drops a pixel body into empty space, then moves the camera far away.
+//! With keep-alive on, the body's chunk stays loaded and simulated and the
+//! body keeps falling. Without it, or without spare pool slots, the chunk
+//! unloads and the body is saved and despawned with it.
+//!
+//! Run: cargo test -p game body_keep_alive_e2e
+
+use std::path::Path;
+use std::time::Duration;
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::asset::RenderAssetUsages;
+use bevy::ecs::entity_disabling::Disabled;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use game::pixel_world::{
+  AsyncTaskBehavior, CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig,
+  Pixel, PixelBodiesPlugin, PixelBody, PixelWorld, PixelWorldConfig, PixelWorldPlugin,
+  SpawnPixelBodyFromImage, SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Number of chunks the streaming window keeps active.
+const WINDOW_CHUNKS: usize = PixelWorldConfig::MIN_CHUNK_MEMORY / Chunk::MEMORY_SIZE;
+
+/// Where the body starts, high up in chunk (0, 0).
+const BODY_START: Vec2 = Vec2::new(100.0, 400.0);
+/// Camera position whose streaming window is far from chunk (0, 0).
+const FAR_AWAY: Vec3 = Vec3::new(8.0 * CHUNK_SIZE as f32, 0.0, 0.0);
+/// Sand dropped next to the body once it is off-camera.
+const SAND: WorldRect = WorldRect::new(300, 420, 8, 8);
+
+/// Seeds every chunk with void, so bodies fall freely.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+struct TestHarness {
+  app: App,
+  camera: Entity,
+  test_image: Handle<Image>,
+}
+
+impl TestHarness {
+  fn new(save_path: &Path, config: PixelWorldConfig) -> Self {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+      task_pool_options: TaskPoolOptions::with_num_threads(4),
+    }));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+      1.0 / 60.0,
+    )));
+
+    app.add_plugins(bevy::transform::TransformPlugin);
+    app.add_plugins(bevy::asset::AssetPlugin::default());
+    app.add_plugins(bevy::image::ImagePlugin::default());
+    app.add_plugins(bevy::scene::ScenePlugin);
+    app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+    app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(save_path)));
+    app.add_plugins(PixelBodiesPlugin);
+    app.add_plugins(
+      bevy_rapier2d::prelude::RapierPhysicsPlugin::<bevy_rapier2d::prelude::NoUserData>::default()
+        .with_length_unit(50.0),
+    );
+    app.insert_resource(AsyncTaskBehavior::Poll);
+
+    let test_image = create_test_image(&mut app);
+
+    let camera = app
+      .world_mut()
+      .spawn((
+        Transform::default(),
+        GlobalTransform::default(),
+        StreamingCamera,
+      ))
+      .id();
+
+    app
+      .world_mut()
+      .commands()
+      .queue(SpawnPixelWorld::new(VoidSeeder).with_config(config));
+
+    let mut harness = Self {
+      app,
+      camera,
+      test_image,
+    };
+    harness.run_until_seeded();
+    harness
+  }
+
+  fn run_until_seeded(&mut self) {
+    for _ in 0..100 {
+      self.app.update();
+      let seeded = self.with_world(|world| world.get_pixel(WorldPos::new(0, 0)).is_some());
+      if seeded {
+        return;
+      }
+    }
+    panic!("World was not seeded");
+  }
+
+  fn run(&mut self, updates: usize) {
+    for _ in 0..updates {
+      self.app.update();
+    }
+  }
+
+  fn with_world<R>(&mut self, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+    let mut q = self.app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(self.app.world_mut()).unwrap();
+    f(&mut world)
+  }
+
+  fn move_camera(&mut self, position: Vec3) {
+    let world = self.app.world_mut();
+    world.get_mut::<Transform>(self.camera).unwrap().translation = position;
+    *world.get_mut::<GlobalTransform>(self.camera).unwrap() =
+      GlobalTransform::from_translation(position);
+  }
+
+  /// Spawns the test body at `BODY_START` and waits for it to finalize.
+  fn spawn_body(&mut self) -> Entity {
+    let image = self.test_image.clone();
+    self
+      .app
+      .world_mut()
+      .commands()
+      .queue(SpawnPixelBodyFromImage::new(
+        image,
+        material_ids::WOOD,
+        BODY_START,
+      ));
+
+    let mut q = self
+      .app
+      .world_mut()
+      .query_filtered::<Entity, With<PixelBody>>();
+    for _ in 0..10 {
+      self.app.update();
+      if let Some(entity) = q.iter(self.app.world()).next() {
+        return entity;
+      }
+    }
+    panic!("Body should exist after spawning");
+  }
+
+  /// Returns the body's height, or None once it has been despawned.
+  fn body_y(&self, body: Entity) -> Option<f32> {
+    let entity = self.app.world().get_entity(body).ok()?;
+    Some(entity.get::<Transform>()?.translation.y)
+  }
+
+  fn is_disabled(&self, body: Entity) -> bool {
+    self.app.world().get::<Disabled>(body).is_some()
+  }
+
+  /// Returns the chunk the body is in.
+  fn body_chunk(&self, body: Entity) -> ChunkPos {
+    let y = self.body_y(body).expect("Body should exist");
+    WorldPos::new(BODY_START.x as i64, y as i64)
+      .to_chunk_and_local()
+      .0
+  }
+
+  fn sand_count(&mut self, rect: WorldRect) -> usize {
+    self.with_world(|world| {
+      (rect.y..rect.y + rect.height as i64)
+        .flat_map(|y| (rect.x..rect.x + rect.width as i64).map(move |x| WorldPos::new(x, y)))
+        .filter_map(|pos| world.get_pixel(pos))
+        .filter(|p| p.material == material_ids::SAND)
+        .count()
+    })
+  }
+}
+
+/// Creates an 8x8 RGBA test image with all white pixels.
+fn create_test_image(app: &mut App) -> Handle<Image> {
+  let mut image = Image::new_fill(
+    bevy::render::render_resource::Extent3d {
+      width: 8,
+      height: 8,
+      depth_or_array_layers: 1,
+    },
+    bevy::render::render_resource::TextureDimension::D2,
+    &[255, 255, 255, 255],
+    bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
+    RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+  );
+  image.sampler = ImageSampler::nearest();
+
+  let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+  images.add(image)
+}
+
+fn config(body_keep_alive_radius: Option<u32>, spare_slots: usize) -> PixelWorldConfig {
+  PixelWorldConfig {
+    body_keep_alive_radius,
+    max_chunk_memory: Some(PixelWorldConfig::MIN_CHUNK_MEMORY + spare_slots * Chunk::MEMORY_SIZE),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn kept_alive_body_keeps_falling_off_camera() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), config(Some(1), 4));
+  let body = harness.spawn_body();
+
+  harness.move_camera(FAR_AWAY);
+  harness.run(5);
+
+  let chunk = harness.body_chunk(body);
+  assert!(
+    !harness.with_world(|world| world.visible_positions().any(|pos| pos == chunk)),
+    "Body should be outside the streaming window"
+  );
+  assert!(
+    harness.with_world(|world| world.is_kept_alive(chunk)),
+    "Body's chunk should be kept alive"
+  );
+  assert!(!harness.is_disabled(body), "Kept-alive body is not culled");
+
+  let start_y = harness.body_y(body).unwrap();
+  harness.run(30);
+  let end_y = harness
+    .body_y(body)
+    .expect("Kept-alive body should not be unloaded");
+  assert!(
+    end_y < start_y - 10.0,
+    "Body should keep falling off-camera ({start_y} -> {end_y})"
+  );
+
+  // The held chunk is simulated, not just loaded
+  harness.with_world(|world| {
+    let sand = Pixel::new(material_ids::SAND, ColorIndex(0));
+    world.blit(SAND, |_| Some(sand), DebugGizmos::none());
+  });
+  let area = (SAND.width * SAND.height) as usize;
+  assert_eq!(harness.sand_count(SAND), area);
+  harness.run(30);
+  assert!(
+    harness.sand_count(SAND) < area,
+    "Sand in a kept-alive chunk should fall"
+  );
+}
+
+#[test]
+fn body_unloads_without_keep_alive() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), config(None, 4));
+  let body = harness.spawn_body();
+  let chunk = harness.body_chunk(body);
+
+  harness.move_camera(FAR_AWAY);
+  harness.run(5);
+
+  assert!(
+    harness.body_y(body).is_none(),
+    "Body should be saved and despawned with its chunk"
+  );
+  assert!(!harness.with_world(|world| world.is_kept_alive(chunk)));
+  assert_eq!(
+    harness.with_world(|world| world.active_count()),
+    WINDOW_CHUNKS
+  );
+}
+
+#[test]
+fn keep_alive_respects_pool_budget() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), config(Some(1), 0));
+  let body = harness.spawn_body();
+  let chunk = harness.body_chunk(body);
+
+  harness.move_camera(FAR_AWAY);
+  harness.run(5);
+
+  assert!(
+    !harness.with_world(|world| world.is_kept_alive(chunk)),
+    "No spare slots are left to hold the body's chunk"
+  );
+  assert_eq!(
+    harness.with_world(|world| world.active_count()),
+    WINDOW_CHUNKS,
+    "The streaming window keeps every slot"
+  );
+  assert!(harness.body_y(body).is_none(), "Body should unload");
+}
+
+#[test]
+fn held_chunks_fit_in_spare_slots() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), config(Some(1), 2));
+  let body = harness.spawn_body();
+
+  harness.move_camera(FAR_AWAY);
+  harness.run(5);
+
+  let chunk = harness.body_chunk(body);
+  let (held, active) = harness.with_world(|world| {
+    (
+      world.kept_alive_chunks().collect::<Vec<_>>(),
+      world.active_count(),
+    )
+  });
+  assert_eq!(held.len(), 2, "Only spare slots hold chunks: {held:?}");
+  assert!(held.contains(&chunk), "The body's own chunk is held first");
+  assert_eq!(active, WINDOW_CHUNKS + 2);
+}
+
+#[test]
+fn default_pool_reserves_keep_alive_slots() {
+  let temp_dir = TempDir::new().unwrap();
+  let config = PixelWorldConfig {
+    body_keep_alive_radius: Some(1),
+    ..Default::default()
+  };
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), config);
+  let body = harness.spawn_body();
+
+  harness.move_camera(FAR_AWAY);
+  harness.run(5);
+
+  let chunk = harness.body_chunk(body);
+  let held = harness.with_world(|world| world.kept_alive_chunks().count());
+  assert_eq!(held, 9, "Every chunk within the radius should be held");
+  assert!(harness.with_world(|world| world.is_kept_alive(chunk)));
+  assert!(harness.body_y(body).is_some(), "Body should stay loaded");
+}
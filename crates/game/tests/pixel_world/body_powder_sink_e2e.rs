@@ -0,0 +1,228 @@
+//! E2E tests for pixel bodies sinking into powder.
+//!
+//! A stone body is dropped onto a deep sand bed with rapier2d physics. With
+//! `PowderSinkConfig::enabled` it must settle partway into the sand, with
+//! the displaced sand pushed up beside it; without it the body stays on the
+//! surface.
+#![cfg(physics)]
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier2d::prelude::{NoUserData, RapierPhysicsPlugin, RigidBody};
+use game::pixel_world::collision::physics::PhysicsColliderRegistry;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, CollisionConfig, CollisionQueryPoint,
+  ColorIndex, DisplacementState, LastBlitTransform, PersistenceConfig, Pixel, PixelBodiesPlugin,
+  PixelBodyIdGenerator, PixelBodyLoader, PixelWorld, PixelWorldPlugin, PowderSinkConfig,
+  SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos, generate_collider,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const BODY_SIZE: u32 = 16;
+/// Sand surface; the bed fills the rows below it.
+const SURFACE: i64 = 0;
+const SAND_DEPTH: i64 = 32;
+const BED_HALF_WIDTH: i64 = 64;
+const SETTLE_FRAMES: usize = 300;
+
+/// Seeds every chunk with void; the sand bed is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, sink: bool) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  // Stone (200) on sand (160) buries 3 * 0.25 = 75% of the body's rows
+  app.insert_resource(
+    CollisionConfig::default().with_powder_sink(PowderSinkConfig {
+      enabled: sink,
+      strength: 3.0,
+    }),
+  );
+  app.add_plugins(PixelBodiesPlugin);
+  app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default().with_length_unit(50.0));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+    1.0 / 60.0,
+  )));
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn run_until(app: &mut App, mut done: impl FnMut(&mut App) -> bool) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if done(app) {
+      return;
+    }
+  }
+  panic!("Condition not met within timeout");
+}
+
+/// Paints a sand bed on a stone floor and waits for its colliders.
+fn build_sand_bed(app: &mut App) {
+  let bottom = SURFACE - SAND_DEPTH;
+  run_until(app, |app| {
+    with_world(app, |world| {
+      world
+        .get_pixel(WorldPos::new(-BED_HALF_WIDTH, bottom - 8))
+        .is_some()
+        && world
+          .get_pixel(WorldPos::new(BED_HALF_WIDTH - 1, SURFACE + 32))
+          .is_some()
+    })
+  });
+  with_world(app, |world| {
+    for y in bottom - 8..SURFACE {
+      for x in -BED_HALF_WIDTH..BED_HALF_WIDTH {
+        let material = if y < bottom {
+          material_ids::STONE
+        } else {
+          material_ids::SAND
+        };
+        world.set_pixel(
+          WorldPos::new(x, y),
+          Pixel::new(material, ColorIndex(0)),
+          DebugGizmos::none(),
+        );
+      }
+    }
+  });
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    CollisionQueryPoint,
+  ));
+  run_until(app, |app| {
+    !app
+      .world()
+      .resource::<PhysicsColliderRegistry>()
+      .entities
+      .is_empty()
+  });
+}
+
+fn spawn_stone_body(app: &mut App) -> Entity {
+  let body = PixelBodyLoader::rectangle(BODY_SIZE, BODY_SIZE, material_ids::STONE);
+  let collider = generate_collider(&body).expect("Rectangle should have a collider");
+  let id = app
+    .world_mut()
+    .resource_mut::<PixelBodyIdGenerator>()
+    .generate();
+  let transform = Transform::from_xyz(0.0, BODY_SIZE as f32 / 2.0 + 2.0, 0.0);
+  app
+    .world_mut()
+    .spawn((
+      body,
+      LastBlitTransform::default(),
+      DisplacementState::default(),
+      transform,
+      GlobalTransform::from(transform),
+      id,
+      collider,
+      RigidBody::Dynamic,
+      CollisionQueryPoint,
+    ))
+    .id()
+}
+
+/// Drops a stone body on the sand bed and returns how far its bottom edge
+/// ended up below the original surface, and the sand above the surface.
+fn drop_on_sand(sink: bool) -> (f32, usize) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, sink);
+  build_sand_bed(&mut app);
+  let body = spawn_stone_body(&mut app);
+
+  for _ in 0..SETTLE_FRAMES {
+    app.update();
+  }
+
+  let center = app.world().get::<Transform>(body).unwrap().translation.y;
+  let depth = SURFACE as f32 - (center - BODY_SIZE as f32 / 2.0);
+  let raised_sand = with_world(&mut app, |world| {
+    (SURFACE..SURFACE + BODY_SIZE as i64)
+      .flat_map(|y| (-BED_HALF_WIDTH..BED_HALF_WIDTH).map(move |x| WorldPos::new(x, y)))
+      .filter(|&pos| {
+        world
+          .get_pixel(pos)
+          .is_some_and(|pixel| pixel.material == material_ids::SAND)
+      })
+      .count()
+  });
+  (depth, raised_sand)
+}
+
+#[test]
+fn dense_body_sinks_partway_into_sand() {
+  let (depth, raised_sand) = drop_on_sand(true);
+
+  assert!(
+    depth >= 3.0,
+    "Stone body should sink into the sand (depth {depth})"
+  );
+  assert!(
+    depth < BODY_SIZE as f32,
+    "Body should stop partway, not be buried (depth {depth})"
+  );
+  assert!(
+    raised_sand > 0,
+    "Displaced sand should be pushed up beside the body"
+  );
+}
+
+#[test]
+fn body_stays_on_sand_without_sinking() {
+  let (depth, raised_sand) = drop_on_sand(false);
+
+  assert!(
+    depth < 1.5,
+    "Body should rest on the surface (depth {depth})"
+  );
+  assert_eq!(raised_sand, 0, "No sand should be displaced");
+}
@@ -0,0 +1,226 @@
+//! E2E tests for settling stacked pixel bodies into kinematic rest.
+//!
+//! A stack of bodies is dropped onto a stone floor with rapier2d physics.
+//! Once settled, the positional jitter of the stack with
+//! `RestMergeConfig::enabled` must be lower than without it.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier2d::prelude::{NoUserData, RapierPhysicsPlugin, RigidBody, Sleeping};
+use game::pixel_world::collision::physics::PhysicsColliderRegistry;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, CollisionConfig, CollisionQueryPoint,
+  ColorIndex, DisplacementState, LastBlitTransform, PersistenceConfig, Pixel, PixelBodiesPlugin,
+  PixelBodyIdGenerator, PixelBodyLoader, PixelWorld, PixelWorldPlugin, RestMergeConfig, Resting,
+  SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos, generate_collider,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const BODY_SIZE: u32 = 16;
+const STACK_HEIGHT: usize = 3;
+const SETTLE_FRAMES: usize = 120;
+const MEASURE_FRAMES: usize = 120;
+
+/// Seeds every chunk with void; the floor is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, rest_merge: bool) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(CollisionConfig::default().with_rest_merge(RestMergeConfig {
+    enabled: rest_merge,
+    ..default()
+  }));
+  app.add_plugins(PixelBodiesPlugin);
+  app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default().with_length_unit(50.0));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+    1.0 / 60.0,
+  )));
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn run_until(app: &mut App, mut done: impl FnMut(&mut App) -> bool) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if done(app) {
+      return;
+    }
+  }
+  panic!("Condition not met within timeout");
+}
+
+/// Paints a stone floor below y = 0 and waits for its colliders.
+fn build_floor(app: &mut App) {
+  run_until(app, |app| {
+    with_world(app, |world| {
+      world.get_pixel(WorldPos::new(-64, -16)).is_some()
+        && world.get_pixel(WorldPos::new(63, 0)).is_some()
+    })
+  });
+  with_world(app, |world| {
+    for y in -16..0 {
+      for x in -64..64 {
+        world.set_pixel(
+          WorldPos::new(x, y),
+          Pixel::new(material_ids::STONE, ColorIndex(0)),
+          DebugGizmos::none(),
+        );
+      }
+    }
+  });
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    CollisionQueryPoint,
+  ));
+  run_until(app, |app| {
+    !app
+      .world()
+      .resource::<PhysicsColliderRegistry>()
+      .entities
+      .is_empty()
+  });
+}
+
+fn spawn_stack(app: &mut App) -> Vec<Entity> {
+  (0..STACK_HEIGHT)
+    .map(|i| {
+      let body = PixelBodyLoader::rectangle(BODY_SIZE, BODY_SIZE, material_ids::WOOD);
+      let collider = generate_collider(&body).expect("Rectangle should have a collider");
+      let id = app
+        .world_mut()
+        .resource_mut::<PixelBodyIdGenerator>()
+        .generate();
+      let y = (BODY_SIZE as f32 + 1.0) * (i as f32 + 0.5);
+      let transform = Transform::from_xyz(0.0, y, 0.0);
+      app
+        .world_mut()
+        .spawn((
+          body,
+          LastBlitTransform::default(),
+          DisplacementState::default(),
+          transform,
+          GlobalTransform::from(transform),
+          id,
+          collider,
+          RigidBody::Dynamic,
+          // Keeps the solver's contact corrections visible in the baseline
+          Sleeping::disabled(),
+          CollisionQueryPoint,
+        ))
+        .id()
+    })
+    .collect()
+}
+
+fn positions(app: &App, bodies: &[Entity]) -> Vec<Vec2> {
+  bodies
+    .iter()
+    .map(|&e| {
+      app
+        .world()
+        .get::<Transform>(e)
+        .unwrap()
+        .translation
+        .truncate()
+    })
+    .collect()
+}
+
+/// Settles a stack and returns (total movement while settled, resting count).
+fn measure_stack(rest_merge: bool) -> (f32, usize) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, rest_merge);
+  build_floor(&mut app);
+  let bodies = spawn_stack(&mut app);
+
+  for _ in 0..SETTLE_FRAMES {
+    app.update();
+  }
+
+  let mut previous = positions(&app, &bodies);
+  let mut movement = 0.0;
+  for _ in 0..MEASURE_FRAMES {
+    app.update();
+    let current = positions(&app, &bodies);
+    movement += previous
+      .iter()
+      .zip(&current)
+      .map(|(a, b)| a.distance(*b))
+      .sum::<f32>();
+    previous = current;
+  }
+
+  let resting = bodies
+    .iter()
+    .filter(|&&e| app.world().get::<Resting>(e).is_some())
+    .count();
+  (movement, resting)
+}
+
+#[test]
+fn rest_merge_reduces_stack_jitter() {
+  let (baseline, baseline_resting) = measure_stack(false);
+  let (merged, merged_resting) = measure_stack(true);
+
+  assert_eq!(baseline_resting, 0);
+  assert_eq!(
+    merged_resting, STACK_HEIGHT,
+    "Whole stack should come to rest"
+  );
+  assert_eq!(merged, 0.0, "Resting bodies should not move");
+  assert!(
+    merged < baseline,
+    "Rest merge should reduce jitter ({} vs baseline {})",
+    merged,
+    baseline
+  );
+}
@@ -0,0 +1,226 @@
+//! E2E tests for drawing pixel bodies as sprites.
+//!
+//! A stone body is held in place under a column of sand. With
+//! `BodyRenderMode::Sprite` it must still be blitted into the world, holding
+//! the sand up, while its pixels are left out of the chunk texture upload
+//! and drawn by a sprite child from its own surface instead. In the default
+//! blit mode no sprite is spawned.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, BodyRenderMode, CHUNK_SIZE, Chunk, ChunkMaterial, ChunkPos, ChunkSeeder,
+  ColorIndex, DisplacementState, LastBlitTransform, PersistenceConfig, Pixel, PixelBodiesPlugin,
+  PixelBodyIdGenerator, PixelBodyLoader, PixelBodySprite, PixelFlags, PixelWorld, PixelWorldConfig,
+  PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, create_pixel_texture,
+  debug_shim::DebugGizmos, material_ids, upload_pixels_without_bodies,
+};
+use tempfile::TempDir;
+
+const BODY_SIZE: u32 = 8;
+/// Body center; its pixels span 4 either side, all in chunk (0, 0).
+const BODY_CENTER: (i64, i64) = (100, 100);
+/// First row above the body, where the sand column starts.
+const SAND_BOTTOM: i64 = 104;
+const SAND_HEIGHT: i64 = 6;
+const SETTLE_FRAMES: usize = 60;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, body_render_mode: BodyRenderMode) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  // Meshes and chunk materials give sprites render resources without a GPU
+  app.init_asset::<Mesh>();
+  app.init_asset::<ChunkMaterial>();
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  let config = PixelWorldConfig {
+    body_render_mode,
+    ..Default::default()
+  };
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder).with_config(config));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      world
+        .get_pixel(WorldPos::new(BODY_CENTER.0, BODY_CENTER.1))
+        .is_some()
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Spawns a stone body, pours a column of sand on it and lets the sand
+/// settle. Returns the body entity.
+fn build_scene(app: &mut App) -> Entity {
+  let body = PixelBodyLoader::rectangle(BODY_SIZE, BODY_SIZE, material_ids::STONE);
+  let id = app
+    .world_mut()
+    .resource_mut::<PixelBodyIdGenerator>()
+    .generate();
+  let transform = Transform::from_xyz(BODY_CENTER.0 as f32, BODY_CENTER.1 as f32, 0.0);
+  let entity = app
+    .world_mut()
+    .spawn((
+      body,
+      LastBlitTransform::default(),
+      DisplacementState::default(),
+      transform,
+      GlobalTransform::from(transform),
+      id,
+    ))
+    .id();
+  app.update();
+
+  with_world(app, |world| {
+    for y in SAND_BOTTOM..SAND_BOTTOM + SAND_HEIGHT {
+      world.set_pixel(
+        WorldPos::new(BODY_CENTER.0, y),
+        Pixel::new(material_ids::SAND, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+  });
+  for _ in 0..SETTLE_FRAMES {
+    app.update();
+  }
+  entity
+}
+
+/// Returns the body's sprite, if it has one.
+fn body_sprite(app: &App, body: Entity) -> Option<Handle<Image>> {
+  let children = app.world().get::<Children>(body)?.to_vec();
+  children.into_iter().find_map(|child| {
+    app
+      .world()
+      .get::<PixelBodySprite>(child)
+      .map(|sprite| sprite.texture.clone())
+  })
+}
+
+/// Returns the raw `[material, color, damage, flags]` texel at `(x, y)`.
+fn texel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+  let i = (y * image.width() + x) as usize * 4;
+  image.data.as_ref().unwrap()[i..i + 4].try_into().unwrap()
+}
+
+#[test]
+fn sprite_mode_keeps_body_out_of_chunk_texture() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, BodyRenderMode::Sprite);
+  wait_until_seeded(&mut app);
+  let body = build_scene(&mut app);
+
+  // The body is still blitted and holds the sand up
+  let (body_pixel, sand_pixel) = with_world(&mut app, |world| {
+    (
+      *world
+        .get_pixel(WorldPos::new(BODY_CENTER.0, BODY_CENTER.1))
+        .unwrap(),
+      *world
+        .get_pixel(WorldPos::new(BODY_CENTER.0, SAND_BOTTOM))
+        .unwrap(),
+    )
+  });
+  assert_eq!(body_pixel.material, material_ids::STONE);
+  assert!(body_pixel.flags.contains(PixelFlags::PIXEL_BODY));
+  assert_eq!(
+    sand_pixel.material,
+    material_ids::SAND,
+    "Sand should rest on the body"
+  );
+
+  // The chunk texture leaves the body out but keeps the sand
+  let image = app
+    .world_mut()
+    .resource_scope(|world, mut images: Mut<Assets<Image>>| {
+      let handle = create_pixel_texture(&mut images, CHUNK_SIZE, CHUNK_SIZE);
+      let mut image = images.remove(&handle).unwrap();
+      let mut q = world.query::<&mut PixelWorld>();
+      let mut pixel_world = q.single_mut(world).unwrap();
+      let chunk = pixel_world.get_chunk_mut(ChunkPos::new(0, 0)).unwrap();
+      upload_pixels_without_bodies(&chunk.pixels, &mut image);
+      image
+    });
+  let (x, y) = (BODY_CENTER.0 as u32, BODY_CENTER.1 as u32);
+  assert_eq!(texel(&image, x, y), [0; 4], "Body pixel should be void");
+  assert_eq!(
+    texel(&image, x, SAND_BOTTOM as u32)[0],
+    material_ids::SAND.0,
+    "Terrain pixels should be uploaded"
+  );
+
+  // The sprite draws the body from its own surface
+  let texture = body_sprite(&app, body).expect("Body should have a sprite");
+  let images = app.world().resource::<Assets<Image>>();
+  let sprite_image = images.get(&texture).unwrap();
+  assert_eq!(sprite_image.width(), BODY_SIZE);
+  assert_eq!(sprite_image.height(), BODY_SIZE);
+  assert_eq!(
+    texel(sprite_image, BODY_SIZE / 2, BODY_SIZE / 2)[0],
+    material_ids::STONE.0
+  );
+}
+
+#[test]
+fn blit_mode_spawns_no_sprites() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, BodyRenderMode::Blit);
+  wait_until_seeded(&mut app);
+  let body = build_scene(&mut app);
+
+  assert!(body_sprite(&app, body).is_none());
+}
@@ -0,0 +1,231 @@
+//! E2E tests for tick-paced pixel body blitting.
+//!
+//! Bodies are blitted inside the `PixelSimulation` schedule, right before
+//! each CA tick. Tests that with `TickPacing::Fixed` a body is blitted exactly
+//! once per simulation tick, whether the render frame rate is above or below
+//! the tick rate, and never in frames that run no tick.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, DisplacementState, LastBlitTransform,
+  PersistenceConfig, Pixel, PixelBodiesPlugin, PixelBodyIdGenerator, PixelBodyLoader,
+  PixelSimulation, PixelWorld, PixelWorldPlugin, SimulationConfig, SimulationPhase,
+  SpawnPixelWorld, StreamingCamera, TickPacing, WorldPos, material_ids, update_pixel_bodies,
+};
+use tempfile::TempDir;
+
+const TICK_RATE: f32 = 60.0;
+const MAX_TICKS_PER_FRAME: u32 = 4;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Simulation ticks and body blits seen during the current and past frames.
+#[derive(Resource, Default)]
+struct TickLog {
+  ticks: u32,
+  blits: u32,
+  frame_ticks: u32,
+  /// Frames in which the body changed without a tick running.
+  stray_blits: u32,
+}
+
+fn count_tick(mut log: ResMut<TickLog>) {
+  log.ticks += 1;
+  log.frame_ticks += 1;
+}
+
+fn count_blit(mut log: ResMut<TickLog>, blitted: Query<(), Changed<LastBlitTransform>>) {
+  log.blits += blitted.iter().count() as u32;
+}
+
+fn end_frame(mut log: ResMut<TickLog>, blitted: Query<(), Changed<LastBlitTransform>>) {
+  if log.frame_ticks == 0 && !blitted.is_empty() {
+    log.stray_blits += 1;
+  }
+  log.frame_ticks = 0;
+}
+
+fn create_app(temp_dir: &TempDir, pacing: TickPacing) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(SimulationConfig {
+    physics_tps: TICK_RATE,
+    pacing,
+    ..default()
+  });
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.init_resource::<TickLog>();
+  app.add_systems(
+    PixelSimulation,
+    (
+      count_blit
+        .after(update_pixel_bodies)
+        .in_set(SimulationPhase::BeforeCATick),
+      count_tick.in_set(SimulationPhase::CATick),
+    ),
+  );
+  app.add_systems(Last, end_frame);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Spawns a static body and runs frames until it has been blitted.
+fn spawn_body(app: &mut App) {
+  let body = PixelBodyLoader::rectangle(8, 8, material_ids::WOOD);
+  let id = app
+    .world_mut()
+    .resource_mut::<PixelBodyIdGenerator>()
+    .generate();
+  let transform = Transform::from_xyz(32.0, 32.0, 0.0);
+  let entity = app
+    .world_mut()
+    .spawn((
+      body,
+      LastBlitTransform::default(),
+      DisplacementState::default(),
+      transform,
+      GlobalTransform::from(transform),
+      id,
+    ))
+    .id();
+
+  for _ in 0..3 {
+    app.update();
+  }
+  assert!(
+    !app
+      .world()
+      .get::<LastBlitTransform>(entity)
+      .unwrap()
+      .written_positions
+      .is_empty(),
+    "Body should be blitted"
+  );
+}
+
+/// Runs one simulated second at `fps` and returns the tick log.
+fn run_second(fps: u32) -> TickLog {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(
+    &temp_dir,
+    TickPacing::Fixed {
+      max_ticks_per_frame: MAX_TICKS_PER_FRAME,
+    },
+  );
+  app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+    1.0 / TICK_RATE as f64,
+  )));
+  wait_until_seeded(&mut app);
+  spawn_body(&mut app);
+
+  app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+    1.0 / fps as f64,
+  )));
+  // Let the new frame duration take effect before counting
+  app.update();
+  app.insert_resource(TickLog::default());
+
+  for _ in 0..fps {
+    app.update();
+  }
+
+  app.world_mut().remove_resource::<TickLog>().unwrap()
+}
+
+fn assert_blit_per_tick(log: &TickLog) {
+  assert!(
+    (TICK_RATE as u32 - 1..=TICK_RATE as u32 + 1).contains(&log.ticks),
+    "Expected ~{} ticks in one second, got {}",
+    TICK_RATE,
+    log.ticks
+  );
+  assert_eq!(
+    log.blits, log.ticks,
+    "Body should be blitted exactly once per simulation tick"
+  );
+  assert_eq!(
+    log.stray_blits, 0,
+    "Body should not be blitted in frames without a tick"
+  );
+}
+
+#[test]
+fn fast_frames_blit_once_per_tick() {
+  assert_blit_per_tick(&run_second(144));
+}
+
+#[test]
+fn slow_frames_blit_once_per_tick() {
+  assert_blit_per_tick(&run_second(30));
+}
+
+#[test]
+fn per_frame_pacing_ticks_every_frame() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, TickPacing::PerFrame);
+  wait_until_seeded(&mut app);
+  spawn_body(&mut app);
+  app.insert_resource(TickLog::default());
+
+  for _ in 0..10 {
+    app.update();
+  }
+
+  let log = app.world().resource::<TickLog>();
+  assert_eq!(log.ticks, 10);
+  assert_eq!(log.blits, 10);
+}
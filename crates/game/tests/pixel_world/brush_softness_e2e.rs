@@ -0,0 +1,178 @@
+//! E2E tests for soft-edged brushes.
+//!
+//! Stamps a brush into an empty world and checks that a hard brush fills its
+//! whole disc, while a soft brush fills its core and paints fewer pixels the
+//! closer they are to the rim.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, BrushState, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig,
+  Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const CENTER: (i64, i64) = (64, 64);
+const RADIUS: u32 = 40;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let r = RADIUS as i64;
+    let seeded = with_world(app, |world| {
+      [
+        WorldPos::new(CENTER.0 - r, CENTER.1 - r),
+        WorldPos::new(CENTER.0 + r, CENTER.1 + r),
+      ]
+      .iter()
+      .all(|&pos| world.get_pixel(pos).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Stamps a stone brush and returns, per whole-pixel distance from the
+/// center, the fraction of pixels painted.
+fn stamp_density(softness: f32) -> Vec<f32> {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let brush = BrushState {
+    radius: RADIUS,
+    softness,
+    ..default()
+  };
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+
+  with_world(&mut app, |world| {
+    brush.stamp(world, CENTER, stone, DebugGizmos::none());
+
+    let mut painted = vec![0u32; RADIUS as usize + 1];
+    let mut total = vec![0u32; RADIUS as usize + 1];
+    let r = RADIUS as i64;
+    for dy in -r..=r {
+      for dx in -r..=r {
+        let dist = ((dx * dx + dy * dy) as f32).sqrt();
+        if dist > RADIUS as f32 {
+          continue;
+        }
+        let ring = dist as usize;
+        total[ring] += 1;
+        let pos = WorldPos::new(CENTER.0 + dx, CENTER.1 + dy);
+        if world
+          .get_pixel(pos)
+          .is_some_and(|p| p.material == material_ids::STONE)
+        {
+          painted[ring] += 1;
+        }
+      }
+    }
+
+    painted
+      .iter()
+      .zip(&total)
+      .map(|(&p, &t)| p as f32 / t as f32)
+      .collect()
+  })
+}
+
+/// Average density over the rings in `range`.
+fn band(density: &[f32], range: std::ops::Range<usize>) -> f32 {
+  let len = range.len() as f32;
+  density[range].iter().sum::<f32>() / len
+}
+
+#[test]
+fn hard_brush_fills_whole_disc() {
+  let density = stamp_density(0.0);
+  for (ring, &d) in density.iter().enumerate() {
+    assert_eq!(d, 1.0, "Ring {} should be fully painted", ring);
+  }
+}
+
+#[test]
+fn soft_brush_feathers_toward_edge() {
+  let density = stamp_density(0.5);
+
+  // Core: the inner half of the radius is solid
+  for (ring, &d) in density[..20].iter().enumerate() {
+    assert_eq!(d, 1.0, "Core ring {} should be fully painted", ring);
+  }
+
+  let inner = band(&density, 20..27);
+  let middle = band(&density, 27..34);
+  let outer = band(&density, 34..41);
+  assert!(
+    inner > middle && middle > outer,
+    "Density should fall toward the rim (inner {}, middle {}, outer {})",
+    inner,
+    middle,
+    outer
+  );
+  assert!(outer < 0.5, "Rim should be sparse (outer {})", outer);
+  assert!(inner > 0.5, "Feather should start dense (inner {})", inner);
+}
+
+#[test]
+fn soft_brush_is_deterministic() {
+  assert_eq!(stamp_density(0.5), stamp_density(0.5));
+}
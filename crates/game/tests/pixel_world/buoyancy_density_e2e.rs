@@ -0,0 +1,214 @@
+//! E2E tests for per-pixel body density in buoyancy.
+//!
+//! An 8x8 body with a wood left half and a stone right half is held
+//! submerged in a water pool. With `per_pixel_density`, sampling must place
+//! its center of mass on the stone side, and the buoyancy force must tip
+//! that side down and lift the body less than the uniform model does.
+//!
+//! Run with:
+//!   cargo test -p game --test buoyancy_density_e2e
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::ExternalForce;
+use game::pixel_world::buoyancy::{Buoyancy2dPlugin, BuoyancyConfig};
+use game::pixel_world::debug_shim::DebugGizmos;
+use game::pixel_world::pixel_awareness::{LiquidFractionState, PixelAwarenessPlugin};
+use game::pixel_world::{
+  ColorIndex, MaterialSeeder, PersistenceConfig, Pixel, PixelBodiesPlugin, PixelBody, PixelWorld,
+  PixelWorldPlugin, SpawnPixelBodyFromImage, SpawnPixelWorld, StreamingCamera, WorldPos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const BODY_SIZE: u32 = 8;
+const POOL: WorldPos = WorldPos::new(0, -50);
+
+/// Buoyancy results of one run.
+struct Sampled {
+  translation: Vec2,
+  body_density: f32,
+  mass_center: Vec2,
+  force: Vec2,
+  torque: f32,
+}
+
+fn create_app(temp_dir: &TempDir, config: BuoyancyConfig) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.add_plugins(
+    bevy_rapier2d::prelude::RapierPhysicsPlugin::<bevy_rapier2d::prelude::NoUserData>::default()
+      .with_length_unit(50.0),
+  );
+  app.add_plugins(PixelAwarenessPlugin::default());
+  app.add_plugins(Buoyancy2dPlugin::new(config));
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(MaterialSeeder::new(42)));
+
+  app
+}
+
+fn run(app: &mut App, updates: usize) {
+  for _ in 0..updates {
+    app.update();
+  }
+}
+
+/// Creates an all-white test image the size of the body.
+fn create_test_image(app: &mut App) -> Handle<Image> {
+  let mut image = Image::new_fill(
+    bevy::render::render_resource::Extent3d {
+      width: BODY_SIZE,
+      height: BODY_SIZE,
+      depth_or_array_layers: 1,
+    },
+    bevy::render::render_resource::TextureDimension::D2,
+    &[255, 255, 255, 255],
+    bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
+    RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+  );
+  image.sampler = ImageSampler::nearest();
+
+  let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+  images.add(image)
+}
+
+fn paint_pool(app: &mut App) {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  for dy in -20..=20 {
+    for dx in -30..=30 {
+      world.set_pixel(
+        WorldPos::new(POOL.x + dx, POOL.y + dy),
+        Pixel::new(material_ids::WATER, ColorIndex(128)),
+        DebugGizmos::default(),
+      );
+    }
+  }
+}
+
+/// Spawns a wood body above the pool and turns its right half to stone.
+fn spawn_mixed_body(app: &mut App) -> Entity {
+  let image = create_test_image(app);
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelBodyFromImage::new(
+      image,
+      material_ids::WOOD,
+      Vec2::new(0.0, 50.0),
+    ));
+  run(app, 10);
+
+  let mut q = app.world_mut().query::<(Entity, &mut PixelBody)>();
+  let (entity, mut body) = q
+    .iter_mut(app.world_mut())
+    .next()
+    .expect("Body should exist after spawning");
+  for y in 0..BODY_SIZE {
+    for x in BODY_SIZE / 2..BODY_SIZE {
+      let mut pixel = *body.get_pixel(x, y).unwrap();
+      pixel.material = material_ids::STONE;
+      body.set_pixel(x, y, pixel);
+    }
+  }
+  app
+    .world_mut()
+    .entity_mut(entity)
+    .insert(ExternalForce::default());
+  entity
+}
+
+/// Holds the mixed body level in the pool and samples its buoyancy.
+fn sample_submerged(config: BuoyancyConfig) -> Sampled {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, config);
+  run(&mut app, 20);
+  paint_pool(&mut app);
+  run(&mut app, 1);
+  let body = spawn_mixed_body(&mut app);
+
+  let pool = Vec2::new(POOL.x as f32, POOL.y as f32);
+  for _ in 0..3 {
+    let world = app.world_mut();
+    world.get_mut::<Transform>(body).unwrap().translation = pool.extend(0.0);
+    if let Some(mut vel) = world.get_mut::<bevy_rapier2d::prelude::Velocity>(body) {
+      vel.linvel = Vec2::ZERO;
+      vel.angvel = 0.0;
+    }
+    app.update();
+  }
+
+  let world = app.world();
+  let liquid = world.get::<LiquidFractionState>(body).unwrap();
+  let force = world.get::<ExternalForce>(body).unwrap();
+  Sampled {
+    translation: world
+      .get::<GlobalTransform>(body)
+      .unwrap()
+      .translation()
+      .truncate(),
+    body_density: liquid.body_density,
+    mass_center: liquid.mass_center,
+    force: force.force,
+    torque: force.torque,
+  }
+}
+
+#[test]
+fn mixed_body_tilts_toward_its_heavy_side() {
+  let uniform = sample_submerged(BuoyancyConfig::default());
+  let weighted = sample_submerged(BuoyancyConfig {
+    per_pixel_density: true,
+    ..Default::default()
+  });
+
+  // Sampling finds the stone half
+  assert!(
+    weighted.body_density > 100.0 && weighted.body_density < 180.0,
+    "Half wood, half stone should average between them: {}",
+    weighted.body_density
+  );
+  assert!(
+    weighted.mass_center.x > weighted.translation.x + 1.0,
+    "Center of mass should lie on the stone side: {} vs {}",
+    weighted.mass_center.x,
+    weighted.translation.x
+  );
+
+  assert!(uniform.force.y > 0.0, "Submerged body should get lift");
+  assert!(
+    weighted.force.y < uniform.force.y,
+    "Denser body should get less lift: {} vs {}",
+    weighted.force.y,
+    uniform.force.y
+  );
+  assert!(
+    weighted.torque < 0.0 && weighted.torque < uniform.torque,
+    "Stone side should tip down: {} vs uniform {}",
+    weighted.torque,
+    uniform.torque
+  );
+}
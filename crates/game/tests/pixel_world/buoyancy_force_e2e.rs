@@ -0,0 +1,82 @@
+//! Tests for the buoyancy force on bodies of mixed density.
+//!
+//! A 64-pixel body, half wood and half stone, is half submerged with the
+//! center of buoyancy right below its origin. The uniform model must not
+//! tip it, while `per_pixel_density` must tip the stone side down and lift
+//! it less.
+
+use bevy::prelude::*;
+use game::pixel_world::buoyancy::{BuoyancyConfig, SubmersionState, buoyancy_force};
+use game::pixel_world::pixel_awareness::LiquidFractionState;
+
+fn half_submerged() -> SubmersionState {
+  let mut state = SubmersionState::default();
+  state.is_submerged = true;
+  state.submerged_fraction = 0.5;
+  state.submerged_center = Vec2::new(0.0, -2.0);
+  state
+}
+
+/// Wood on the left half, stone (density 200) on the right.
+fn wood_and_stone() -> LiquidFractionState {
+  LiquidFractionState {
+    liquid_fraction: 0.5,
+    liquid_center: Vec2::new(0.0, -2.0),
+    body_density: 140.0,
+    mass_center: Vec2::new(1.7, 0.0),
+    ..Default::default()
+  }
+}
+
+fn per_pixel() -> BuoyancyConfig {
+  BuoyancyConfig {
+    per_pixel_density: true,
+    ..Default::default()
+  }
+}
+
+#[test]
+fn uniform_body_has_no_torque_when_level() {
+  let config = BuoyancyConfig::default();
+  let (force, torque) = buoyancy_force(
+    &config,
+    64.0,
+    Vec2::ZERO,
+    &half_submerged(),
+    &wood_and_stone(),
+  );
+  assert!(force.y > 0.0);
+  assert_eq!(torque, 0.0);
+}
+
+#[test]
+fn heavy_side_tips_down_with_per_pixel_density() {
+  let state = half_submerged();
+  let liquid = wood_and_stone();
+  let (uniform, _) = buoyancy_force(
+    &BuoyancyConfig::default(),
+    64.0,
+    Vec2::ZERO,
+    &state,
+    &liquid,
+  );
+  let (force, torque) = buoyancy_force(&per_pixel(), 64.0, Vec2::ZERO, &state, &liquid);
+
+  // Clockwise: the stone side sinks
+  assert!(torque < 0.0);
+  // Denser than wood on average, so it gets less lift
+  assert!((force.y - uniform.y * 80.0 / 140.0).abs() < 1e-3);
+}
+
+#[test]
+fn reference_density_body_matches_uniform_model() {
+  let state = half_submerged();
+  let wood = LiquidFractionState {
+    body_density: 80.0,
+    mass_center: Vec2::ZERO,
+    ..wood_and_stone()
+  };
+  let uniform = buoyancy_force(&BuoyancyConfig::default(), 64.0, Vec2::ZERO, &state, &wood);
+  let weighted = buoyancy_force(&per_pixel(), 64.0, Vec2::ZERO, &state, &wood);
+  assert_eq!(uniform, weighted);
+}
@@ -0,0 +1,172 @@
+//! E2E tests for cancelling in-flight async tasks.
+//!
+//! Chunks start seeding with a slow sand seeder. While those tasks are in
+//! flight the seeder is swapped for a stone one, either with a
+//! `CancelPendingTasks` message or through `UpdateSeeder`. The sand results
+//! of the old tasks must be discarded, so every chunk ends up stone.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, AsyncTaskCounts, CancelPendingTasks, Chunk, ChunkPos, ChunkSeeder, ColorIndex,
+  PersistenceConfig, Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera,
+  UpdateSeeder, WorldLoadingProgress, WorldPos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Long enough that no slow task finishes before it is cancelled.
+const SLOW_SEED: Duration = Duration::from_millis(300);
+
+/// Fills chunks with sand, slowly.
+struct SlowSandSeeder;
+
+impl ChunkSeeder for SlowSandSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    std::thread::sleep(SLOW_SEED);
+    fill(chunk, Pixel::new(material_ids::SAND, ColorIndex(0)));
+  }
+}
+
+/// Fills chunks with stone.
+struct StoneSeeder;
+
+impl ChunkSeeder for StoneSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    fill(chunk, Pixel::new(material_ids::STONE, ColorIndex(0)));
+  }
+}
+
+fn fill(chunk: &mut Chunk, pixel: Pixel) {
+  for y in 0..chunk.pixels.height() {
+    for x in 0..chunk.pixels.width() {
+      chunk.pixels[(x, y)] = pixel;
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(SlowSandSeeder));
+
+  app
+}
+
+fn world_entity(app: &mut App) -> Entity {
+  app
+    .world_mut()
+    .query_filtered::<Entity, With<PixelWorld>>()
+    .single(app.world())
+    .unwrap()
+}
+
+/// Runs updates until `done` holds.
+fn run_until(app: &mut App, what: &str, mut done: impl FnMut(&mut App) -> bool) {
+  let deadline = Instant::now() + Duration::from_secs(20);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if done(app) {
+      return;
+    }
+  }
+  panic!("Timed out waiting for {what}");
+}
+
+/// Runs updates until the first slow seeding tasks are in flight.
+fn wait_for_seeding(app: &mut App) {
+  run_until(app, "seeding tasks", |app| {
+    app.world().resource::<AsyncTaskCounts>().seeding > 0
+  });
+}
+
+/// Runs updates until every chunk is seeded and no task is in flight.
+fn wait_until_settled(app: &mut App) {
+  run_until(app, "all chunks to seed", |app| {
+    let progress = app.world().resource::<WorldLoadingProgress>();
+    let seeded = progress.chunks_total > 0 && progress.chunks_ready == progress.chunks_total;
+    seeded && app.world().resource::<AsyncTaskCounts>().is_idle()
+  });
+}
+
+/// Asserts that every seeded pixel sampled around the origin is stone.
+fn assert_all_stone(app: &mut App) {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  let mut sampled = 0;
+  for y in (-300..300).step_by(40) {
+    for x in (-500..500).step_by(40) {
+      let Some(pixel) = world.get_pixel(WorldPos::new(x, y)) else {
+        continue;
+      };
+      assert_eq!(
+        pixel.material,
+        material_ids::STONE,
+        "Stale seeding result applied at ({x}, {y})"
+      );
+      sampled += 1;
+    }
+  }
+  assert!(sampled > 0, "No seeded pixels were sampled");
+}
+
+#[test]
+fn cancel_discards_in_flight_seeding_results() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_for_seeding(&mut app);
+
+  let entity = world_entity(&mut app);
+  let counts = app.world().resource::<AsyncTaskCounts>().clone();
+  assert_eq!(counts.seeding_in(entity), counts.seeding);
+  assert!(!counts.is_idle());
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  q.single_mut(app.world_mut())
+    .unwrap()
+    .set_seeder(Arc::new(StoneSeeder));
+  app.world_mut().write_message(CancelPendingTasks);
+
+  wait_until_settled(&mut app);
+  assert_all_stone(&mut app);
+}
+
+#[test]
+fn update_seeder_discards_in_flight_seeding_results() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_for_seeding(&mut app);
+
+  app.world_mut().write_message(UpdateSeeder {
+    seeder: Arc::new(StoneSeeder),
+  });
+
+  wait_until_settled(&mut app);
+  assert_all_stone(&mut app);
+}
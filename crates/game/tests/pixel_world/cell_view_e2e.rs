@@ -0,0 +1,160 @@
+//! E2E tests for `PixelWorld::neighbors`.
+//!
+//! Reads the neighbors of a pixel at the corner of four chunks and checks
+//! that each offset resolves to the right chunk, then reads past the edge
+//! of the streaming window and checks that unloaded neighbors are `None`.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, NEIGHBOR_OFFSETS,
+  PersistenceConfig, Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera,
+  WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+/// Runs until every chunk in the streaming window is seeded.
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      world
+        .visible_positions()
+        .all(|pos| world.chunk_generation(pos).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn setup() -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  (temp_dir, app)
+}
+
+/// Stone pixel tagged with `tag` in its color index.
+fn tagged(tag: u8) -> Pixel {
+  Pixel::new(material_ids::STONE, ColorIndex(tag))
+}
+
+#[test]
+fn neighbors_resolve_across_chunk_corner() {
+  let (_temp_dir, mut app) = setup();
+  // Chunks (-1, -1), (0, -1), (-1, 0) and (0, 0) meet below-left of center
+  let center = WorldPos::new(0, 0);
+
+  with_world(&mut app, |world| {
+    world.set_pixel(center, tagged(100), DebugGizmos::none());
+    for (i, &(dx, dy)) in NEIGHBOR_OFFSETS.iter().enumerate() {
+      let pos = WorldPos::new(center.x + dx, center.y + dy);
+      world.set_pixel(pos, tagged(i as u8 + 1), DebugGizmos::none());
+    }
+  });
+
+  with_world(&mut app, |world| {
+    let view = world.neighbors(center);
+    assert_eq!(view.center(), center);
+    assert_eq!(view.get(0, 0), Some(tagged(100)));
+    for (i, &(dx, dy)) in NEIGHBOR_OFFSETS.iter().enumerate() {
+      assert_eq!(
+        view.get(dx, dy),
+        Some(tagged(i as u8 + 1)),
+        "Neighbor at ({dx}, {dy})"
+      );
+    }
+
+    let iterated: Vec<_> = view.iter().collect();
+    let expected: Vec<_> = NEIGHBOR_OFFSETS
+      .iter()
+      .enumerate()
+      .map(|(i, &offset)| (offset, Some(tagged(i as u8 + 1))))
+      .collect();
+    assert_eq!(iterated, expected);
+  });
+}
+
+#[test]
+fn neighbors_outside_loaded_chunks_are_none() {
+  let (_temp_dir, mut app) = setup();
+
+  with_world(&mut app, |world| {
+    let left = world.visible_positions().map(|pos| pos.x).min().unwrap();
+    let top = world.visible_positions().map(|pos| pos.y).max().unwrap();
+    let size = CHUNK_SIZE as i64;
+
+    // Bottom-left pixel of chunk (left, 0), on the left edge of the window
+    let view = world.neighbors(WorldPos::new(left as i64 * size, 0));
+    assert_eq!(view.get(0, 0), Some(Pixel::VOID));
+    assert_eq!(view.get(1, 0), Some(Pixel::VOID));
+    assert_eq!(view.get(0, -1), Some(Pixel::VOID));
+    for dy in -1..=1 {
+      assert_eq!(view.get(-1, dy), None, "Left side at dy={dy} is unloaded");
+    }
+
+    // Pixel on the top edge of the window
+    let view = world.neighbors(WorldPos::new(0, (top as i64 + 1) * size - 1));
+    assert_eq!(view.get(0, -1), Some(Pixel::VOID));
+    for dx in -1..=1 {
+      assert_eq!(view.get(dx, 1), None, "Top side at dx={dx} is unloaded");
+    }
+    assert_eq!(view.iter().filter(|(_, pixel)| pixel.is_none()).count(), 3);
+  });
+}
@@ -0,0 +1,114 @@
+//! E2E tests for chunk data checksums.
+//!
+//! Saves a chunk painted with sand over a stone seeder, then flips a byte of
+//! its stored data on disk. Loading it must fail the checksum and fall back
+//! to the seeder instead of applying the corrupted data, while an untouched
+//! save still loads the painted pixels.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use game::pixel_world::persistence::LoadedChunk;
+use game::pixel_world::persistence::native::NativeFs;
+use game::pixel_world::{
+  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, Pixel, WorldSave, material_ids,
+};
+use tempfile::TempDir;
+
+const POS: ChunkPos = ChunkPos::new(0, 0);
+const SAVE_NAME: &str = "test.save";
+
+/// Fills every chunk with stone, so regenerated chunks are easy to spot.
+struct StoneSeeder;
+
+impl ChunkSeeder for StoneSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::new(material_ids::STONE, ColorIndex(0));
+      }
+    }
+  }
+}
+
+/// Seeds a chunk with optional pre-loaded persistence data.
+///
+/// This mirrors the logic in `streaming/seeding.rs::seed_chunk_with_loaded`.
+fn seed_chunk_with_loaded(loaded: Option<LoadedChunk>) -> Chunk {
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  chunk.set_pos(POS);
+  StoneSeeder.seed(POS, &mut chunk);
+  if let Some(loaded) = loaded {
+    loaded
+      .apply_to(&mut chunk)
+      .expect("Loaded data should apply");
+  }
+  chunk
+}
+
+/// Saves a chunk with a block of sand painted over the seeded stone and
+/// returns the file offset of its stored data.
+fn save_painted_chunk(fs: &NativeFs) -> u64 {
+  let mut save = WorldSave::create(fs, SAVE_NAME, 42).expect("Failed to create save");
+  let mut chunk = seed_chunk_with_loaded(None);
+  for y in 100..120 {
+    for x in 100..120 {
+      chunk.pixels[(x, y)] = Pixel::new(material_ids::SAND, ColorIndex(0));
+    }
+  }
+  save
+    .save_chunk(&chunk, POS, &StoneSeeder)
+    .expect("Failed to save chunk");
+  save.flush().expect("Failed to flush save");
+  save.chunk_index().get(POS).unwrap().data_offset
+}
+
+fn load(fs: &NativeFs) -> Chunk {
+  let save = WorldSave::open(fs, SAVE_NAME).expect("Failed to open save");
+  assert!(save.contains(POS), "Page table entry should be intact");
+  seed_chunk_with_loaded(save.load_chunk(POS, &StoneSeeder))
+}
+
+#[test]
+fn intact_chunk_loads_saved_pixels() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  save_painted_chunk(&fs);
+
+  let chunk = load(&fs);
+  assert_eq!(chunk.pixels[(110, 110)].material, material_ids::SAND);
+}
+
+#[test]
+fn corrupted_chunk_falls_back_to_procedural_generation() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  let data_offset = save_painted_chunk(&fs);
+
+  // Flip one bit of the stored chunk data
+  let mut file = OpenOptions::new()
+    .read(true)
+    .write(true)
+    .open(temp_dir.path().join(SAVE_NAME))
+    .unwrap();
+  let mut byte = [0u8; 1];
+  file.seek(SeekFrom::Start(data_offset)).unwrap();
+  file.read_exact(&mut byte).unwrap();
+  byte[0] ^= 0x01;
+  file.seek(SeekFrom::Start(data_offset)).unwrap();
+  file.write_all(&byte).unwrap();
+  drop(file);
+
+  let save = WorldSave::open(&fs, SAVE_NAME).expect("Failed to open save");
+  assert!(
+    save.load_chunk(POS, &StoneSeeder).is_none(),
+    "Corrupted chunk data should fail its checksum"
+  );
+
+  let chunk = load(&fs);
+  assert_eq!(
+    chunk.pixels[(110, 110)].material,
+    material_ids::STONE,
+    "Corrupted chunk should be generated procedurally"
+  );
+}
@@ -0,0 +1,190 @@
+//! E2E tests for chunk diffs used in network delta sync.
+//!
+//! Tests that a diff between two chunks, applied to a copy of the first,
+//! reproduces the second, and that `PixelWorld::collect_dirty_diffs`
+//! reports exactly the pixels changed since its previous call.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, LocalPos,
+  PersistenceConfig, Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera,
+  WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn stone(color: u8) -> Pixel {
+  Pixel::new(material_ids::STONE, ColorIndex(color))
+}
+
+/// Returns a chunk with a stone floor, as a shared baseline.
+fn baseline_chunk() -> Chunk {
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  for y in 0..CHUNK_SIZE {
+    for x in 0..CHUNK_SIZE {
+      chunk.pixels[(x, y)] = if y < 64 { stone(0) } else { Pixel::VOID };
+    }
+  }
+  chunk
+}
+
+fn copy_of(chunk: &Chunk) -> Chunk {
+  let mut copy = Chunk::new(chunk.pixels.width(), chunk.pixels.height());
+  copy
+    .pixels
+    .as_slice_mut()
+    .copy_from_slice(chunk.pixels.as_slice());
+  copy
+}
+
+#[test]
+fn diff_applied_to_copy_matches() {
+  let original = baseline_chunk();
+  let mut modified = copy_of(&original);
+
+  // Dig a hole, recolor a row and drop some sand
+  for x in 10..20 {
+    modified.pixels[(x, 60)] = Pixel::VOID;
+  }
+  for x in 0..CHUNK_SIZE {
+    modified.pixels[(x, 0)] = stone(7);
+  }
+  modified.pixels[(300, 400)] = Pixel::new(material_ids::SAND, ColorIndex(1));
+
+  let diff = original.diff(&modified);
+  assert_eq!(diff.len(), 10 + CHUNK_SIZE as usize + 1);
+  assert!(diff.contains(&(LocalPos::new(300, 400), modified.pixels[(300, 400)])));
+
+  let mut copy = copy_of(&original);
+  copy.apply_diff(&diff);
+  assert!(
+    copy.pixels.as_slice() == modified.pixels.as_slice(),
+    "Copy should match the modified chunk"
+  );
+  assert!(copy.diff(&modified).is_empty());
+}
+
+#[test]
+fn identical_chunks_have_empty_diff() {
+  let chunk = baseline_chunk();
+  assert!(chunk.diff(&copy_of(&chunk)).is_empty());
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+/// Runs until every chunk in the streaming window is seeded.
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q.single(app.world()).is_ok_and(|world| {
+      world
+        .visible_positions()
+        .all(|pos| world.get_pixel(pos.to_world()).is_some())
+    }) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+#[test]
+fn collect_dirty_diffs_reports_changes_since_last_call() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+
+  // The first call records baselines
+  assert!(world.collect_dirty_diffs().is_empty());
+  let mut peer_copy = copy_of(world.get_chunk_mut(ChunkPos::new(0, 0)).unwrap());
+
+  let edits = [
+    WorldPos::new(5, 5),
+    WorldPos::new(6, 5),
+    WorldPos::new(100, 200),
+  ];
+  for pos in edits {
+    world.set_pixel(pos, stone(3), DebugGizmos::none());
+  }
+
+  let diffs = world.collect_dirty_diffs();
+  assert_eq!(diffs.len(), 1, "Only chunk (0, 0) changed: {diffs:?}");
+  let (pos, diff) = &diffs[0];
+  assert_eq!(*pos, ChunkPos::new(0, 0));
+  assert_eq!(diff.len(), edits.len());
+
+  peer_copy.apply_diff(diff);
+  let chunk = world.get_chunk_mut(ChunkPos::new(0, 0)).unwrap();
+  assert!(
+    peer_copy.pixels.as_slice() == chunk.pixels.as_slice(),
+    "Peer copy should match after applying the diff"
+  );
+
+  assert!(
+    world.collect_dirty_diffs().is_empty(),
+    "Nothing changed since the last call"
+  );
+
+  // Edits in another chunk are reported against the new baseline
+  world.set_pixel(WorldPos::new(-1, -1), stone(4), DebugGizmos::none());
+  let diffs = world.collect_dirty_diffs();
+  assert_eq!(
+    diffs,
+    vec![(
+      ChunkPos::new(-1, -1),
+      vec![(
+        LocalPos::new(CHUNK_SIZE as u16 - 1, CHUNK_SIZE as u16 - 1),
+        stone(4)
+      )]
+    )]
+  );
+}
@@ -0,0 +1,182 @@
+//! E2E tests for per-chunk content generations.
+//!
+//! Tests that `PixelWorld::chunk_generation` stays put while a chunk is
+//! untouched and increases when its pixels are painted, blitted or moved by
+//! the simulation.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Chunk that receives the edits.
+const EDITED: ChunkPos = ChunkPos::new(0, 0);
+/// Chunk that is never touched.
+const UNTOUCHED: ChunkPos = ChunkPos::new(-1, -1);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+/// Runs until every chunk in the streaming window is seeded.
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      world
+        .visible_positions()
+        .all(|pos| world.chunk_generation(pos).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn generation(app: &mut App, pos: ChunkPos) -> u64 {
+  with_world(app, |world| world.chunk_generation(pos)).unwrap()
+}
+
+fn run(app: &mut App, updates: usize) {
+  for _ in 0..updates {
+    app.update();
+  }
+}
+
+#[test]
+fn generation_is_stable_while_untouched() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let edited = generation(&mut app, EDITED);
+  let untouched = generation(&mut app, UNTOUCHED);
+  run(&mut app, 30);
+
+  assert_eq!(generation(&mut app, EDITED), edited);
+  assert_eq!(generation(&mut app, UNTOUCHED), untouched);
+}
+
+#[test]
+fn generation_increases_on_modification() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let untouched = generation(&mut app, UNTOUCHED);
+  let start = generation(&mut app, EDITED);
+
+  // set_pixel
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  with_world(&mut app, |world| {
+    world.set_pixel(WorldPos::new(10, 10), stone, DebugGizmos::none())
+  });
+  let painted = generation(&mut app, EDITED);
+  assert!(
+    painted > start,
+    "set_pixel should bump ({start} -> {painted})"
+  );
+
+  // blit
+  with_world(&mut app, |world| {
+    world.blit(
+      WorldRect::new(20, 20, 8, 8),
+      |_| Some(stone),
+      DebugGizmos::none(),
+    )
+  });
+  let blitted = generation(&mut app, EDITED);
+  assert!(
+    blitted > painted,
+    "blit should bump ({painted} -> {blitted})"
+  );
+
+  // Simulation moving a falling pixel
+  let sand = Pixel::new(material_ids::SAND, ColorIndex(0));
+  with_world(&mut app, |world| {
+    let pos = WorldPos::new(100, 200);
+    world.set_pixel(pos, sand, DebugGizmos::none());
+    world.mark_pixel_sim_dirty(pos);
+  });
+  let dropped = generation(&mut app, EDITED);
+  run(&mut app, 1);
+  let simulated = generation(&mut app, EDITED);
+  assert!(
+    simulated > dropped,
+    "Simulation should bump ({dropped} -> {simulated})"
+  );
+
+  assert_eq!(
+    generation(&mut app, UNTOUCHED),
+    untouched,
+    "Edits to one chunk should not bump another"
+  );
+}
+
+#[test]
+fn unloaded_chunk_has_no_generation() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  assert_eq!(
+    with_world(&mut app, |world| world
+      .chunk_generation(ChunkPos::new(1000, 1000))),
+    None
+  );
+}
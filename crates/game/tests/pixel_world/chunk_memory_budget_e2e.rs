@@ -0,0 +1,160 @@
+//! E2E tests for sizing the chunk pool from a memory budget.
+//!
+//! Tests that the pool slot count follows the configured budget, that a
+//! budget too small for the streaming window is capped to the window size,
+//! and that worlds stream and accept edits with either pool size.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldConfig, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Number of chunks the streaming window keeps active.
+const WINDOW_CHUNKS: usize = PixelWorldConfig::MIN_CHUNK_MEMORY / Chunk::MEMORY_SIZE;
+
+/// Seeds every chunk with void; terrain is irrelevant here.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn config(max_chunk_memory: Option<usize>) -> PixelWorldConfig {
+  PixelWorldConfig {
+    max_chunk_memory,
+    ..Default::default()
+  }
+}
+
+fn pool_capacity(max_chunk_memory: Option<usize>) -> usize {
+  PixelWorld::with_config(
+    Arc::new(VoidSeeder),
+    Handle::default(),
+    config(max_chunk_memory),
+  )
+  .pool_capacity()
+}
+
+fn create_app(temp_dir: &TempDir, max_chunk_memory: Option<usize>) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder).with_config(config(max_chunk_memory)));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Spawns a world with the given budget and checks it streams and edits.
+fn assert_world_functions(max_chunk_memory: Option<usize>, expected_capacity: usize) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, max_chunk_memory);
+  wait_until_seeded(&mut app);
+
+  let pos = WorldPos::new(10, 10);
+  let pixel = Pixel::new(material_ids::STONE, ColorIndex(3));
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    assert_eq!(world.pool_capacity(), expected_capacity);
+    assert_eq!(
+      world.active_count(),
+      WINDOW_CHUNKS,
+      "Whole streaming window should be active"
+    );
+    world.set_pixel(pos, pixel, DebugGizmos::none());
+  }
+  app.update();
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  assert_eq!(
+    world.get_pixel(pos).map(|p| p.material),
+    Some(pixel.material)
+  );
+}
+
+#[test]
+fn pool_capacity_follows_budget() {
+  assert!(pool_capacity(None) >= WINDOW_CHUNKS);
+  assert_eq!(
+    pool_capacity(Some(PixelWorldConfig::MIN_CHUNK_MEMORY)),
+    WINDOW_CHUNKS
+  );
+  assert_eq!(
+    pool_capacity(Some(
+      PixelWorldConfig::MIN_CHUNK_MEMORY + 3 * Chunk::MEMORY_SIZE + 1
+    )),
+    WINDOW_CHUNKS + 3
+  );
+}
+
+#[test]
+fn insufficient_budget_is_capped_to_window() {
+  assert_eq!(pool_capacity(Some(0)), WINDOW_CHUNKS);
+  assert_eq!(
+    pool_capacity(Some(PixelWorldConfig::MIN_CHUNK_MEMORY - 1)),
+    WINDOW_CHUNKS
+  );
+}
+
+#[test]
+fn world_functions_with_minimal_budget() {
+  assert_world_functions(Some(Chunk::MEMORY_SIZE), WINDOW_CHUNKS);
+}
+
+#[test]
+fn world_functions_with_spare_slots() {
+  let budget = PixelWorldConfig::MIN_CHUNK_MEMORY + 2 * Chunk::MEMORY_SIZE;
+  assert_world_functions(Some(budget), WINDOW_CHUNKS + 2);
+}
@@ -0,0 +1,213 @@
+//! E2E tests for `ChunkSeededMsg`.
+//!
+//! A fresh world should announce every chunk in the streaming window exactly
+//! once as generated. Reopening a save should announce the saved chunk as
+//! loaded from disk, and the chunks that were never saved as generated.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::ecs::message::{MessageCursor, Messages};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeededMsg, ChunkSeeder, ColorIndex, PersistenceConfig,
+  PersistenceControl, Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera,
+  WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Pixel painted before saving, inside chunk (0, 0).
+const PAINTED: WorldPos = WorldPos::new(10, 10);
+/// Chunk that is never edited, so it is never saved.
+const UNSAVED: ChunkPos = ChunkPos::new(-1, -1);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(save_path: &Path) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(save_path)));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+/// Collects `ChunkSeededMsg`s across updates.
+struct Recorder {
+  cursor: MessageCursor<ChunkSeededMsg>,
+  messages: Vec<ChunkSeededMsg>,
+}
+
+impl Recorder {
+  fn new(app: &App) -> Self {
+    Self {
+      cursor: app
+        .world()
+        .resource::<Messages<ChunkSeededMsg>>()
+        .get_cursor(),
+      messages: Vec::new(),
+    }
+  }
+
+  fn update(&mut self, app: &mut App) {
+    app.update();
+    let messages = app.world().resource::<Messages<ChunkSeededMsg>>();
+    self.messages.extend(self.cursor.read(messages).copied());
+  }
+
+  /// Runs updates until `done` holds, or panics after a timeout.
+  fn run_until(&mut self, app: &mut App, what: &str, mut done: impl FnMut(&mut App) -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+      self.update(app);
+      std::thread::yield_now();
+      if done(app) {
+        return;
+      }
+    }
+    panic!("{what} within timeout");
+  }
+
+  /// Returns the origin flags announced for `pos`, in order.
+  fn origins(&self, pos: ChunkPos) -> Vec<bool> {
+    self
+      .messages
+      .iter()
+      .filter(|msg| msg.pos == pos)
+      .map(|msg| msg.from_persistence)
+      .collect()
+  }
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn all_seeded(app: &mut App) -> bool {
+  with_world(app, |world| {
+    world
+      .visible_positions()
+      .all(|pos| world.chunk_generation(pos).is_some())
+  })
+}
+
+fn is_stone(app: &mut App, pos: WorldPos) -> bool {
+  with_world(app, |world| {
+    world
+      .get_pixel(pos)
+      .is_some_and(|p| p.material == material_ids::STONE)
+  })
+}
+
+#[test]
+fn fresh_chunks_are_announced_once_as_generated() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir.path().join("test.save"));
+  let mut recorder = Recorder::new(&app);
+
+  recorder.run_until(&mut app, "World was not seeded", all_seeded);
+  for _ in 0..30 {
+    recorder.update(&mut app);
+  }
+
+  let (entity, visible) = {
+    let mut q = app.world_mut().query::<(Entity, &PixelWorld)>();
+    let (entity, world) = q.single(app.world()).unwrap();
+    (entity, world.visible_positions().collect::<Vec<_>>())
+  };
+
+  let mut counts = HashMap::new();
+  for msg in &recorder.messages {
+    assert_eq!(msg.world, entity);
+    assert!(
+      !msg.from_persistence,
+      "Chunk {:?} of a fresh world should be generated",
+      msg.pos
+    );
+    *counts.entry(msg.pos).or_insert(0) += 1;
+  }
+  for pos in visible {
+    assert_eq!(
+      counts.get(&pos).copied(),
+      Some(1),
+      "Chunk {pos:?} should be announced exactly once"
+    );
+  }
+}
+
+#[test]
+fn reloaded_chunks_are_announced_as_from_persistence() {
+  let temp_dir = TempDir::new().unwrap();
+  let save_path = temp_dir.path().join("test.save");
+
+  // Write a save holding one edited chunk
+  {
+    let mut app = create_app(&save_path);
+    let mut recorder = Recorder::new(&app);
+    recorder.run_until(&mut app, "World was not seeded", |app| {
+      all_seeded(app) && app.world().contains_resource::<PersistenceControl>()
+    });
+    with_world(&mut app, |world| {
+      world.set_pixel(
+        PAINTED,
+        Pixel::new(material_ids::STONE, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    });
+    let handle = app.world_mut().resource_mut::<PersistenceControl>().save();
+    recorder.run_until(&mut app, "Save did not complete", |_| handle.is_complete());
+  }
+
+  let mut app = create_app(&save_path);
+  let mut recorder = Recorder::new(&app);
+  recorder.run_until(&mut app, "Saved edit was not loaded", |app| {
+    all_seeded(app) && is_stone(app, PAINTED)
+  });
+
+  let (painted, _) = PAINTED.to_chunk_and_local();
+  assert!(
+    recorder.origins(painted).contains(&true),
+    "Saved chunk should be announced as loaded from disk, got {:?}",
+    recorder.origins(painted)
+  );
+  assert_eq!(
+    recorder.origins(UNSAVED),
+    vec![false],
+    "Unsaved chunk should be announced once as generated"
+  );
+}
@@ -0,0 +1,195 @@
+//! E2E tests for clean edit mode.
+//!
+//! Paints stone with stale movement flags next to a floating sand pixel
+//! while the simulation is paused. With clean edits on, the stone is
+//! written at rest and the untouched sand stays put after resuming; a
+//! regular edit wakes the sand, which then falls.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelFlags, PixelWorld, PixelWorldPlugin, SimulationState, SpawnPixelWorld, StreamingCamera,
+  WorldFragment, WorldPos, WorldRect, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Floating sand pixel between the two painted columns.
+const SAND: WorldPos = WorldPos::new(15, 105);
+/// Columns painted on either side of the sand.
+const COLUMNS: [i64; 2] = [10, 20];
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(app, |world| world.get_pixel(WorldPos::new(0, 0)).is_some()) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn run(app: &mut App, updates: usize) {
+  for _ in 0..updates {
+    app.update();
+  }
+}
+
+fn pixel_at(app: &mut App, pos: WorldPos) -> Pixel {
+  with_world(app, |world| *world.get_pixel(pos).unwrap())
+}
+
+/// Creates a settled world holding one floating sand pixel that is not
+/// marked for simulation.
+fn setup() -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  // Let seeding activity cool down
+  run(&mut app, 30);
+  with_world(&mut app, |world| {
+    world.set_pixel(
+      SAND,
+      Pixel::new(material_ids::SAND, ColorIndex(0)),
+      DebugGizmos::none(),
+    )
+  });
+  run(&mut app, 10);
+  assert_eq!(
+    pixel_at(&mut app, SAND).material,
+    material_ids::SAND,
+    "Sand that was never woken should stay put"
+  );
+  (temp_dir, app)
+}
+
+/// Pauses, paints stone columns carrying stale movement flags on either
+/// side of the sand, then resumes.
+fn paint_while_paused(app: &mut App, clean_edits: bool) {
+  let mut state = app.world_mut().resource_mut::<SimulationState>();
+  state.pause();
+  state.set_clean_edits(clean_edits);
+  let settled = state.settles_edits();
+  assert_eq!(settled, clean_edits);
+
+  let stale = Pixel {
+    flags: PixelFlags::DIRTY | PixelFlags::SOLID | PixelFlags::FALLING,
+    ..Pixel::new(material_ids::STONE, ColorIndex(0))
+  };
+  let rect = WorldRect::new(COLUMNS[0], SAND.y - 5, 11, 11);
+  let mask = |frag: WorldFragment| COLUMNS.contains(&frag.x).then_some(stale);
+  with_world(app, |world| {
+    if settled {
+      world.blit_settled(rect, mask, DebugGizmos::none())
+    } else {
+      world.blit(rect, mask, DebugGizmos::none())
+    }
+  });
+  run(app, 5);
+
+  let mut state = app.world_mut().resource_mut::<SimulationState>();
+  state.resume();
+  assert!(
+    !state.settles_edits(),
+    "Clean edits only apply while paused"
+  );
+}
+
+#[test]
+fn clean_edit_leaves_neighbors_at_rest() {
+  let (_temp_dir, mut app) = setup();
+  paint_while_paused(&mut app, true);
+
+  for y in SAND.y - 5..=SAND.y + 5 {
+    for x in COLUMNS {
+      let pixel = pixel_at(&mut app, WorldPos::new(x, y));
+      assert_eq!(pixel.material, material_ids::STONE);
+      assert!(
+        !pixel
+          .flags
+          .intersects(PixelFlags::DIRTY | PixelFlags::FALLING),
+        "Painted pixel at ({x}, {y}) kept stale flags: {:?}",
+        pixel.flags
+      );
+    }
+  }
+
+  run(&mut app, 30);
+  assert_eq!(
+    pixel_at(&mut app, SAND).material,
+    material_ids::SAND,
+    "Untouched sand next to a clean edit should not move on resume"
+  );
+}
+
+#[test]
+fn regular_edit_wakes_neighbors() {
+  let (_temp_dir, mut app) = setup();
+  paint_while_paused(&mut app, false);
+
+  assert_eq!(
+    pixel_at(&mut app, SAND).material,
+    material_ids::SAND,
+    "Nothing should move while paused"
+  );
+
+  run(&mut app, 30);
+  assert!(
+    pixel_at(&mut app, SAND).is_void(),
+    "Sand inside a regular edit's dirty rect should fall on resume"
+  );
+}
@@ -0,0 +1,222 @@
+//! E2E tests for debounced pixel body collider regeneration.
+//!
+//! A body loses one pixel per frame, as under continuous damage. Tests that
+//! its collider is rebuilt at most once per
+//! `ColliderRegenConfig::debounce_frames`, and that a final rebuild follows
+//! the last change.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Collider;
+use game::pixel_world::pixel_body::NeedsColliderRegen;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColliderRegenConfig, DisplacementState,
+  LastBlitTransform, PersistenceConfig, Pixel, PixelBodiesPlugin, PixelBodyIdGenerator,
+  PixelBodyLoader, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos,
+  debug_shim::DebugGizmos, generate_collider, material_ids,
+};
+use tempfile::TempDir;
+
+const BODY_SIZE: u32 = 16;
+const DAMAGE_FRAMES: u32 = 12;
+const DEBOUNCE: u32 = 4;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Frame numbers at which a body collider was replaced.
+#[derive(Resource, Default)]
+struct RegenLog {
+  frame: u32,
+  regens: Vec<u32>,
+}
+
+fn log_collider_changes(mut log: ResMut<RegenLog>, changed: Query<(), Changed<Collider>>) {
+  let frame = log.frame;
+  for () in &changed {
+    log.regens.push(frame);
+  }
+  log.frame += 1;
+}
+
+fn create_app(temp_dir: &TempDir, debounce_frames: u32) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(ColliderRegenConfig { debounce_frames });
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.init_resource::<RegenLog>();
+  app.add_systems(Last, log_collider_changes);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Spawns a static body and waits until it is blitted.
+fn spawn_body(app: &mut App) -> Entity {
+  let body = PixelBodyLoader::rectangle(BODY_SIZE, BODY_SIZE, material_ids::WOOD);
+  let collider = generate_collider(&body).expect("Rectangle should have a collider");
+  let id = app
+    .world_mut()
+    .resource_mut::<PixelBodyIdGenerator>()
+    .generate();
+  let transform = Transform::from_xyz(32.0, 32.0, 0.0);
+  let entity = app
+    .world_mut()
+    .spawn((
+      body,
+      LastBlitTransform::default(),
+      DisplacementState::default(),
+      transform,
+      GlobalTransform::from(transform),
+      id,
+      collider,
+    ))
+    .id();
+
+  for _ in 0..3 {
+    app.update();
+  }
+  assert!(
+    !app
+      .world()
+      .get::<LastBlitTransform>(entity)
+      .unwrap()
+      .written_positions
+      .is_empty(),
+    "Body should be blitted"
+  );
+  entity
+}
+
+/// Erases the body pixel at local (`x`, 0).
+fn erase_bottom_pixel(app: &mut App, body: Entity, x: u32) {
+  let pos = app
+    .world()
+    .get::<LastBlitTransform>(body)
+    .unwrap()
+    .written_positions
+    .iter()
+    .find(|p| p.local_x == x && p.local_y == 0)
+    .map(|p| p.world_pos)
+    .expect("Bottom row pixel should be blitted");
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  world.set_pixel(pos, Pixel::VOID, DebugGizmos::none());
+}
+
+/// Damages the body for `DAMAGE_FRAMES` frames, then idles. Returns the
+/// frames at which the collider was rebuilt and the last damage frame.
+fn damage_body(debounce_frames: u32) -> (Vec<u32>, u32) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, debounce_frames);
+  wait_until_seeded(&mut app);
+  let body = spawn_body(&mut app);
+
+  let start = app.world().resource::<RegenLog>().frame;
+  app.world_mut().resource_mut::<RegenLog>().regens.clear();
+
+  for x in 0..DAMAGE_FRAMES {
+    erase_bottom_pixel(&mut app, body, x);
+    app.update();
+  }
+  let last_damage = app.world().resource::<RegenLog>().frame - 1;
+
+  for _ in 0..debounce_frames * 2 {
+    app.update();
+  }
+
+  assert!(
+    app.world().get::<NeedsColliderRegen>(body).is_none(),
+    "No collider rebuild should remain pending after damage stops"
+  );
+
+  let log = app.world().resource::<RegenLog>();
+  let regens = log.regens.iter().map(|f| f - start).collect();
+  (regens, last_damage - start)
+}
+
+#[test]
+fn continuous_damage_regenerates_at_most_once_per_interval() {
+  let (regens, last_damage) = damage_body(DEBOUNCE);
+
+  assert!(!regens.is_empty(), "Damage should rebuild the collider");
+  for pair in regens.windows(2) {
+    assert!(
+      pair[1] - pair[0] >= DEBOUNCE,
+      "Rebuilds at frames {} and {} are closer than {} frames",
+      pair[0],
+      pair[1],
+      DEBOUNCE
+    );
+  }
+  assert!(
+    regens.len() < DAMAGE_FRAMES as usize,
+    "Rebuilds should be coalesced, got {} for {} damaged frames",
+    regens.len(),
+    DAMAGE_FRAMES
+  );
+  assert!(
+    *regens.last().unwrap() >= last_damage,
+    "A final rebuild should follow the last change (rebuilds {:?}, last damage {})",
+    regens,
+    last_damage
+  );
+}
+
+#[test]
+fn debounce_of_one_rebuilds_every_changed_frame() {
+  let (regens, _) = damage_body(1);
+  assert_eq!(regens.len(), DAMAGE_FRAMES as usize);
+}
@@ -0,0 +1,193 @@
+//! E2E tests for exporting cached collision polygons.
+//!
+//! Paints a stone block, lets the collision systems cache the tile around it
+//! and checks that `CollisionCache::polygons_in` returns a polygon matching
+//! the block's outline, and nothing for tiles that were never cached.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::collision::point_in_polygon;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, CollisionCache, CollisionConfig,
+  CollisionQueryPoint, ColorIndex, PersistenceConfig, Pixel, PixelBodiesPlugin, PixelWorld,
+  PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, TilePos, WorldPos, WorldRect,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Stone block inside tile (0, 0).
+const BLOCK: WorldRect = WorldRect::new(8, 8, 16, 12);
+const TOLERANCE: f32 = 1.0;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(CollisionConfig {
+    simplification_tolerance: TOLERANCE,
+    proximity_radius: 0,
+    debug_gizmos: false,
+    ..default()
+  });
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn run_until(app: &mut App, mut done: impl FnMut(&mut App) -> bool) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if done(app) {
+      return;
+    }
+  }
+  panic!("Condition not met within timeout");
+}
+
+/// Paints the stone block and caches the collision tile around it.
+fn cache_block(app: &mut App) {
+  run_until(app, |app| {
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    q.single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+  });
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  world.blit(
+    BLOCK,
+    |_| Some(Pixel::new(material_ids::STONE, ColorIndex(0))),
+    DebugGizmos::none(),
+  );
+
+  let center = Vec3::new(16.0, 16.0, 0.0);
+  app.world_mut().spawn((
+    Transform::from_translation(center),
+    GlobalTransform::from_translation(center),
+    CollisionQueryPoint,
+  ));
+  run_until(app, |app| {
+    let cache = app.world().resource::<CollisionCache>();
+    cache.contains(TilePos::new(0, 0)) && !cache.is_in_flight(TilePos::new(0, 0))
+  });
+}
+
+/// Distance from `point` to the outline of the block, in pixel-edge
+/// coordinates.
+fn distance_to_block_edge(point: Vec2) -> f32 {
+  let min = Vec2::new(BLOCK.x as f32, BLOCK.y as f32);
+  let max = min + Vec2::new(BLOCK.width as f32, BLOCK.height as f32);
+  let outside = (min - point).max(point - max).max(Vec2::ZERO);
+  if outside != Vec2::ZERO {
+    return outside.length();
+  }
+  (point - min).min(max - point).min_element()
+}
+
+#[test]
+fn polygons_match_block_outline() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  cache_block(&mut app);
+
+  let polygons = app
+    .world()
+    .resource::<CollisionCache>()
+    .polygons_in(WorldRect::new(0, 0, 32, 32));
+  assert_eq!(polygons.len(), 1, "Block should yield one polygon");
+  let outline = &polygons[0].vertices;
+  assert!(
+    !polygons[0].indices.is_empty(),
+    "Polygon should be triangulated"
+  );
+
+  // Marching squares traces pixel centers, so allow half a pixel on top of
+  // the simplification tolerance
+  let slack = TOLERANCE + 1.0;
+  for &vertex in outline {
+    let dist = distance_to_block_edge(vertex);
+    assert!(
+      dist <= slack,
+      "Vertex {:?} is {} px from the block outline",
+      vertex,
+      dist
+    );
+  }
+
+  // Pixels clearly inside or outside the block classify the same way
+  for y in 0..32 {
+    for x in 0..32 {
+      let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+      if distance_to_block_edge(center) <= slack {
+        continue;
+      }
+      let solid = BLOCK.contains(WorldPos::new(x, y));
+      assert_eq!(
+        point_in_polygon(center, outline),
+        solid,
+        "Pixel ({}, {}) misclassified",
+        x,
+        y
+      );
+    }
+  }
+}
+
+#[test]
+fn polygons_skip_uncached_tiles() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  cache_block(&mut app);
+
+  let cache = app.world().resource::<CollisionCache>();
+  assert!(!cache.contains(TilePos::new(10, 10)));
+  assert!(
+    cache
+      .polygons_in(WorldRect::new(320, 320, 32, 32))
+      .is_empty(),
+    "Uncached tiles should contribute nothing"
+  );
+
+  // A rect straddling the cached tile still returns its polygon
+  assert_eq!(cache.polygons_in(WorldRect::new(-16, -16, 32, 32)).len(), 1);
+}
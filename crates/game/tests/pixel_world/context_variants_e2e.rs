@@ -0,0 +1,179 @@
+//! E2E tests for material context variants.
+//!
+//! Fills a stone basin with soil, partly capped by stone, and checks that
+//! the variant pass recolors only soil with air directly above it, without
+//! changing any material. Without variant rules the soil keeps its color.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::VariantConfig;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, Materials, MaterialsConfig,
+  NeighborSide, PersistenceConfig, Pixel, PixelWorld, PixelWorldPlugin, SimulationConfig,
+  SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Color index of the grass variant.
+const GRASS: ColorIndex = ColorIndex(200);
+/// Color index the soil is painted with.
+const SOIL_COLOR: ColorIndex = ColorIndex(0);
+
+/// Soil fills `SOIL_X` columns up to (excluding) `SOIL_TOP`.
+const SOIL_X: (i64, i64) = (4, 36);
+const SOIL_TOP: i64 = 20;
+/// Columns where the soil is capped by stone.
+const CAPPED_X: (i64, i64) = (4, 16);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, grass: bool) -> App {
+  let mut config = MaterialsConfig::builtin();
+  if grass {
+    let soil = &mut config.materials[material_ids::SOIL.0 as usize];
+    soil.variants.push(VariantConfig {
+      side: NeighborSide::Above,
+      neighbor: None,
+      color: GRASS.0,
+    });
+  }
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn fill(world: &mut PixelWorld, x: (i64, i64), y: (i64, i64), pixel: Pixel) {
+  for py in y.0..y.1 {
+    for px in x.0..x.1 {
+      world.set_pixel(WorldPos::new(px, py), pixel, DebugGizmos::none());
+    }
+  }
+}
+
+/// Creates a seeded app holding the soil basin, with the variant pass
+/// running every tick.
+fn setup(grass: bool) -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, grass);
+  wait_until_seeded(&mut app);
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let soil = Pixel::new(material_ids::SOIL, SOIL_COLOR);
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  fill(&mut world, (0, 40), (0, 4), stone);
+  fill(&mut world, (0, SOIL_X.0), (4, SOIL_TOP + 4), stone);
+  fill(&mut world, (SOIL_X.1, 40), (4, SOIL_TOP + 4), stone);
+  fill(&mut world, SOIL_X, (4, SOIL_TOP), soil);
+  fill(&mut world, CAPPED_X, (SOIL_TOP, SOIL_TOP + 2), stone);
+  (temp_dir, app)
+}
+
+fn run(app: &mut App, ticks: usize) {
+  for _ in 0..ticks {
+    app.update();
+  }
+}
+
+/// Returns the soil pixels of the basin with their positions.
+fn soil(app: &mut App) -> Vec<(WorldPos, Pixel)> {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  (4..SOIL_TOP)
+    .flat_map(|y| (SOIL_X.0..SOIL_X.1).map(move |x| WorldPos::new(x, y)))
+    .filter_map(|pos| world.get_pixel(pos).map(|&p| (pos, p)))
+    .filter(|(_, p)| p.material == material_ids::SOIL)
+    .collect()
+}
+
+#[test]
+fn exposed_soil_gets_grass_variant() {
+  let (_temp_dir, mut app) = setup(true);
+  run(&mut app, 5);
+
+  let soil = soil(&mut app);
+  assert_eq!(
+    soil.len(),
+    ((SOIL_X.1 - SOIL_X.0) * (SOIL_TOP - 4)) as usize,
+    "The variant pass must not change materials"
+  );
+
+  for (pos, pixel) in soil {
+    let exposed = pos.y == SOIL_TOP - 1 && pos.x >= CAPPED_X.1;
+    if exposed {
+      assert_eq!(pixel.color, GRASS, "Soil at {pos:?} is exposed to air");
+    } else {
+      assert_eq!(pixel.color, SOIL_COLOR, "Soil at {pos:?} is buried");
+    }
+  }
+}
+
+#[test]
+fn variants_are_off_without_rules() {
+  let (_temp_dir, mut app) = setup(false);
+  run(&mut app, 5);
+
+  assert!(
+    soil(&mut app)
+      .iter()
+      .all(|(_, pixel)| pixel.color == SOIL_COLOR),
+    "Soil should keep its color without variant rules"
+  );
+}
@@ -0,0 +1,86 @@
+//! Tests for coordinate conversions.
+//!
+//! Covers chunk origins, UVs within a chunk and tile rects, including
+//! negative positions.
+
+use bevy::math::Vec2;
+use game::pixel_world::coords::{
+  CHUNK_SIZE, ChunkPos, LocalPos, TILE_SIZE, TilePos, WorldPos, WorldRect,
+};
+
+const SIZE: i64 = CHUNK_SIZE as i64;
+
+#[test]
+fn chunk_origin_world() {
+  assert_eq!(ChunkPos::new(0, 0).origin_world(), WorldPos::new(0, 0));
+  assert_eq!(
+    ChunkPos::new(2, 3).origin_world(),
+    WorldPos::new(2 * SIZE, 3 * SIZE)
+  );
+  assert_eq!(
+    ChunkPos::new(-1, -2).origin_world(),
+    WorldPos::new(-SIZE, -2 * SIZE)
+  );
+}
+
+#[test]
+fn chunk_origin_round_trips() {
+  for chunk in [
+    ChunkPos::new(3, -4),
+    ChunkPos::new(-1, -1),
+    ChunkPos::new(-7, 5),
+  ] {
+    let (pos, local) = chunk.origin_world().to_chunk_and_local();
+    assert_eq!(pos, chunk);
+    assert_eq!(local, LocalPos::new(0, 0));
+  }
+}
+
+#[test]
+fn uv_at_chunk_corners_and_center() {
+  let last = (SIZE - 1) as f32 / SIZE as f32;
+  for chunk in [
+    ChunkPos::new(0, 0),
+    ChunkPos::new(5, 2),
+    ChunkPos::new(-1, -1),
+    ChunkPos::new(-3, 4),
+  ] {
+    let origin = chunk.origin_world();
+    let at = |dx: i64, dy: i64| WorldPos::new(origin.x + dx, origin.y + dy).uv_in_chunk();
+
+    assert_eq!(at(0, 0), Vec2::new(0.0, 0.0), "{chunk:?}");
+    assert_eq!(at(SIZE - 1, 0), Vec2::new(last, 0.0), "{chunk:?}");
+    assert_eq!(at(0, SIZE - 1), Vec2::new(0.0, last), "{chunk:?}");
+    assert_eq!(at(SIZE - 1, SIZE - 1), Vec2::new(last, last), "{chunk:?}");
+    assert_eq!(at(SIZE / 2, SIZE / 2), Vec2::new(0.5, 0.5), "{chunk:?}");
+  }
+}
+
+#[test]
+fn uv_of_negative_positions() {
+  let last = (SIZE - 1) as f32 / SIZE as f32;
+  assert_eq!(WorldPos::new(-1, -1).uv_in_chunk(), Vec2::new(last, last));
+  assert_eq!(
+    WorldPos::new(-SIZE, -SIZE / 2).uv_in_chunk(),
+    Vec2::new(0.0, 0.5)
+  );
+}
+
+#[test]
+fn tile_world_rect() {
+  let size = TILE_SIZE as i64;
+  assert_eq!(
+    TilePos::new(2, 3).to_world_rect(),
+    WorldRect::new(2 * size, 3 * size, TILE_SIZE, TILE_SIZE)
+  );
+  assert_eq!(
+    TilePos::new(-1, -2).to_world_rect(),
+    WorldRect::new(-size, -2 * size, TILE_SIZE, TILE_SIZE)
+  );
+  assert_eq!(
+    TilePos::new(-1, 0)
+      .to_world_rect()
+      .clip_tile(TilePos::new(-1, 0)),
+    Some((0, TILE_SIZE - 1, 0, TILE_SIZE - 1))
+  );
+}
@@ -0,0 +1,303 @@
+//! E2E tests for chipping materials with durability.
+//!
+//! Stone is given a durability of 4. Digging or blasting it must crack it
+//! further with every hit, darkening it by the crack overlay, until the
+//! fourth hit breaks it, while materials without durability still break on
+//! the first hit. Chunk materials must carry the overlay for stone only
+//! while it is enabled.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Bomb, Chunk, ChunkMaterial, ChunkPos, ChunkSeeder, ColorIndex,
+  CrackOverlayConfig, CrackUniform, DigConfig, MaterialId, Materials, MaterialsConfig,
+  PersistenceConfig, Pixel, PixelBodiesPlugin, PixelWorld, PixelWorldConfig, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const DURABILITY: u8 = 4;
+
+const BOMB: Vec3 = Vec3::new(100.0, 100.0, 0.0);
+/// Stone block right of the bomb, within blast radius.
+const BLOCK: (i64, i64, i64, i64) = (105, 95, 115, 106);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, crack_overlay: CrackOverlayConfig) -> App {
+  let mut config = MaterialsConfig::builtin();
+  let stone = config
+    .materials
+    .iter_mut()
+    .find(|m| m.name == "Stone")
+    .unwrap();
+  stone.effects.as_mut().unwrap().durability = DURABILITY;
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  // Chunk materials give slots render resources without a GPU
+  app.init_asset::<ChunkMaterial>();
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  let config = PixelWorldConfig {
+    crack_overlay,
+    ..Default::default()
+  };
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder).with_config(config));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(app, |world| world.get_pixel(WorldPos::new(0, 0)).is_some()) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn pixel_at(app: &mut App, pos: WorldPos) -> Pixel {
+  with_world(app, |world| *world.get_pixel(pos).unwrap())
+}
+
+fn place(app: &mut App, pos: WorldPos, material: MaterialId) {
+  with_world(app, |world| {
+    world.set_pixel(
+      pos,
+      Pixel::new(material, ColorIndex(0)),
+      DebugGizmos::none(),
+    );
+  });
+}
+
+/// Digs the single pixel at `pos`, never knocking it loose.
+fn dig(app: &mut App, pos: WorldPos) {
+  let config = DigConfig {
+    loose_fraction: 0.0,
+  };
+  app
+    .world_mut()
+    .resource_scope(|app_world, materials: Mut<Materials>| {
+      let mut q = app_world.query::<&mut PixelWorld>();
+      let mut world = q.single_mut(app_world).unwrap();
+      world.dig(
+        WorldRect::new(pos.x, pos.y, 1, 1),
+        |_| true,
+        &config,
+        &materials,
+        DebugGizmos::none(),
+      );
+    });
+}
+
+fn count_stone(app: &mut App) -> (usize, usize) {
+  let (x0, y0, x1, y1) = BLOCK;
+  with_world(app, |world| {
+    let stone: Vec<Pixel> = (y0..y1)
+      .flat_map(|y| (x0..x1).map(move |x| WorldPos::new(x, y)))
+      .filter_map(|pos| world.get_pixel(pos).copied())
+      .filter(|p| p.material == material_ids::STONE)
+      .collect();
+    let cracked = stone.iter().filter(|p| p.crack() > 0).count();
+    (stone.len(), cracked)
+  })
+}
+
+fn detonate(app: &mut App) {
+  let bomb = app
+    .world_mut()
+    .spawn((
+      Bomb {
+        damage_threshold: 0.5,
+        blast_radius: 30.0,
+        blast_strength: 1000.0,
+        ignites: false,
+        thermal_radius: 0.0,
+        detonated: true,
+      },
+      Transform::from_translation(BOMB),
+      GlobalTransform::from_translation(BOMB),
+    ))
+    .id();
+  for _ in 0..5 {
+    app.update();
+    if app.world().get_entity(bomb).is_err() {
+      return;
+    }
+  }
+  panic!("Bomb did not detonate");
+}
+
+#[test]
+fn digging_cracks_stone_until_it_breaks() {
+  let temp_dir = TempDir::new().unwrap();
+  let overlay = CrackOverlayConfig::default();
+  let mut app = create_app(&temp_dir, overlay);
+  wait_until_seeded(&mut app);
+
+  let pos = WorldPos::new(50, 50);
+  place(&mut app, pos, material_ids::STONE);
+  assert_eq!(
+    overlay.darkening_factor(pixel_at(&mut app, pos).crack()),
+    1.0
+  );
+
+  let mut last_crack = 0;
+  for hit in 1..DURABILITY {
+    dig(&mut app, pos);
+    let pixel = pixel_at(&mut app, pos);
+    assert_eq!(
+      pixel.material,
+      material_ids::STONE,
+      "Stone should survive hit {hit}"
+    );
+    assert!(
+      pixel.crack() > last_crack,
+      "Hit {hit} should crack the stone further"
+    );
+    assert!(
+      overlay.darkening_factor(pixel.crack()) < overlay.darkening_factor(last_crack),
+      "Deeper cracks should draw darker"
+    );
+    last_crack = pixel.crack();
+  }
+
+  dig(&mut app, pos);
+  assert!(
+    pixel_at(&mut app, pos).is_void(),
+    "Hit {DURABILITY} should break the stone"
+  );
+
+  // Without durability the first hit breaks a pixel
+  let wood = WorldPos::new(60, 50);
+  place(&mut app, wood, material_ids::WOOD);
+  dig(&mut app, wood);
+  assert!(pixel_at(&mut app, wood).is_void());
+}
+
+#[test]
+fn blasts_crack_stone_before_breaking_it() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, CrackOverlayConfig::default());
+  wait_until_seeded(&mut app);
+
+  let (x0, y0, x1, y1) = BLOCK;
+  for y in y0..y1 {
+    for x in x0..x1 {
+      place(&mut app, WorldPos::new(x, y), material_ids::STONE);
+    }
+  }
+  let area = ((x1 - x0) * (y1 - y0)) as usize;
+  app.update();
+
+  detonate(&mut app);
+  let (stone, cracked) = count_stone(&mut app);
+  assert_eq!(stone, area, "One blast should not break any stone");
+  assert!(cracked > 0, "The blast should crack the stone");
+
+  for _ in 1..DURABILITY {
+    detonate(&mut app);
+  }
+  let (stone, _) = count_stone(&mut app);
+  assert!(
+    stone < area,
+    "Repeated blasts should break the cracked stone"
+  );
+}
+
+#[test]
+fn chunk_materials_carry_crack_overlay() {
+  let temp_dir = TempDir::new().unwrap();
+  let overlay = CrackOverlayConfig::default();
+  let mut app = create_app(&temp_dir, overlay);
+  wait_until_seeded(&mut app);
+  app.update();
+
+  let cracks = chunk_cracks(&mut app);
+  let stone = material_ids::STONE.0 as usize;
+  assert_ne!(
+    cracks.materials[stone / 128][stone / 32 % 4] & (1 << (stone % 32)),
+    0
+  );
+  let wood = material_ids::WOOD.0 as usize;
+  assert_eq!(
+    cracks.materials[wood / 128][wood / 32 % 4] & (1 << (wood % 32)),
+    0
+  );
+  assert_eq!(cracks.darkening, overlay.darkening);
+
+  // Disabling the overlay clears it from chunk materials
+  with_world(&mut app, |world| {
+    world.config_mut().crack_overlay.enabled = false
+  });
+  app.update();
+  assert_eq!(chunk_cracks(&mut app), CrackUniform::default());
+  assert_eq!(
+    CrackOverlayConfig {
+      enabled: false,
+      ..overlay
+    }
+    .darkening_factor(8),
+    1.0
+  );
+}
+
+/// Returns the crack overlay of chunk (0, 0)'s material.
+fn chunk_cracks(app: &mut App) -> CrackUniform {
+  let handle = with_world(app, |world| {
+    world.chunk_material(ChunkPos::new(0, 0)).cloned().unwrap()
+  });
+  app
+    .world()
+    .resource::<Assets<ChunkMaterial>>()
+    .get(&handle)
+    .unwrap()
+    .cracks
+}
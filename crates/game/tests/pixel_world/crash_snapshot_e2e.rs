@@ -0,0 +1,168 @@
+//! E2E test for crash snapshots.
+//!
+//! Paints a pixel, then panics from a system in the simulation tick and
+//! checks that the crash snapshot written by the panic hook reads back with
+//! the painted pixel, and that the log holds the panic message and recent
+//! events. A panic outside the world's systems must not write anything,
+//! and a snapshot with a corrupt chunk count must fail to read cleanly.
+
+use std::io;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, CATickStep, Chunk, ChunkPos, ChunkSeeder, ColorIndex, CrashReporter,
+  CrashSnapshotPlugin, PersistenceConfig, Pixel, PixelSimulation, PixelWorld, PixelWorldPlugin,
+  SimulationSnapshot, SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const PAINTED: WorldPos = WorldPos::new(10, 10);
+const PANIC_MESSAGE: &str = "controlled crash for crash snapshot test";
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Makes [`detonate`] panic during the next simulation tick when true.
+#[derive(Resource, Default)]
+struct Detonate(bool);
+
+fn detonate(trigger: Res<Detonate>) {
+  if trigger.0 {
+    panic!("{PANIC_MESSAGE}");
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(CrashSnapshotPlugin {
+    interval: 1,
+    ..CrashSnapshotPlugin::at(temp_dir.path().join("crash.snapshot"))
+  });
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.init_resource::<Detonate>();
+  app.add_systems(PixelSimulation, detonate.in_set(CATickStep::AfterPhysics));
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(app, |world| world.get_pixel(PAINTED).is_some()) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+#[test]
+fn panic_in_simulation_writes_recoverable_snapshot() {
+  let temp_dir = TempDir::new().unwrap();
+  let snapshot_path = temp_dir.path().join("crash.snapshot");
+  let log_path = CrashReporter::log_path(&snapshot_path);
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(3));
+  with_world(&mut app, |world| {
+    world.set_pixel(PAINTED, stone, DebugGizmos::none())
+  });
+  app
+    .world()
+    .resource::<CrashReporter>()
+    .record("placed stone for the crash test");
+  for _ in 0..3 {
+    app.update();
+  }
+  let captured_tick = app
+    .world()
+    .resource::<CrashReporter>()
+    .snapshot_tick()
+    .expect("A snapshot should have been captured");
+
+  // Panics outside the world's systems are not the hook's business
+  assert!(catch_unwind(|| panic!("unrelated panic")).is_err());
+  assert!(!snapshot_path.exists(), "Unrelated panic wrote a snapshot");
+
+  app.world_mut().resource_mut::<Detonate>().0 = true;
+  let crashed = catch_unwind(AssertUnwindSafe(|| app.update()));
+  assert!(crashed.is_err(), "The detonating tick should panic");
+
+  let mut file = std::fs::File::open(&snapshot_path).expect("Snapshot file should exist");
+  let snapshot = SimulationSnapshot::read_from(&mut file).unwrap();
+  assert_eq!(snapshot.tick(), captured_tick);
+  assert_eq!(snapshot.get_pixel(PAINTED), Some(stone));
+
+  let log = std::fs::read_to_string(&log_path).expect("Log file should exist");
+  assert!(
+    log.contains(PANIC_MESSAGE),
+    "Log should hold the panic:\n{log}"
+  );
+  assert!(
+    log.contains("placed stone for the crash test"),
+    "Log should hold recorded events:\n{log}"
+  );
+  assert!(
+    log.contains("chunk (0, 0) modified"),
+    "Log should hold chunk changes:\n{log}"
+  );
+}
+
+#[test]
+fn corrupt_chunk_count_is_an_error() {
+  // A header claiming u32::MAX chunks, with none following
+  let mut bytes = b"PWSN".to_vec();
+  bytes.extend_from_slice(&1u16.to_le_bytes());
+  bytes.extend_from_slice(&0u64.to_le_bytes());
+  bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+
+  let err = SimulationSnapshot::read_from(&mut bytes.as_slice()).unwrap_err();
+  assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,145 @@
+//! E2E tests for batched bomb detonation.
+//!
+//! Tests that a large number of simultaneously triggered bombs is spread
+//! over several frames, never exceeding `DetonationConfig::max_per_frame`,
+//! and that every queued bomb eventually detonates, even with a limit of
+//! zero.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  Bomb, DetonationConfig, MaterialSeeder, PersistenceConfig, PixelBodiesPlugin, PixelWorld,
+  PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos,
+};
+use tempfile::TempDir;
+
+const MAX_PER_FRAME: usize = 3;
+const BOMB_COUNT: usize = 20;
+
+fn create_app(temp_dir: &TempDir, max_per_frame: usize) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(DetonationConfig {
+    max_per_frame,
+    ..default()
+  });
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(MaterialSeeder::new(42)));
+
+  app.update();
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn bomb_count(app: &mut App) -> usize {
+  let mut q = app.world_mut().query::<&Bomb>();
+  q.iter(app.world()).count()
+}
+
+/// Spawns `BOMB_COUNT` bombs that are all triggered in the same frame, as a
+/// chain reaction would.
+fn spawn_triggered_bombs(app: &mut App) {
+  for i in 0..BOMB_COUNT {
+    let pos = Vec3::new(i as f32 * 40.0 - 400.0, 100.0, 0.0);
+    app.world_mut().spawn((
+      Bomb {
+        damage_threshold: 0.5,
+        blast_radius: 8.0,
+        blast_strength: 10.0,
+        ignites: false,
+        thermal_radius: 0.0,
+        detonated: true,
+      },
+      Transform::from_translation(pos),
+      GlobalTransform::from_translation(pos),
+    ));
+  }
+}
+
+#[test]
+fn chain_detonations_are_spread_across_frames() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, MAX_PER_FRAME);
+  wait_until_seeded(&mut app);
+  spawn_triggered_bombs(&mut app);
+
+  let mut remaining = bomb_count(&mut app);
+  assert_eq!(remaining, BOMB_COUNT);
+
+  let max_frames = BOMB_COUNT.div_ceil(MAX_PER_FRAME) + 5;
+  for _ in 0..max_frames {
+    app.update();
+    let now = bomb_count(&mut app);
+    let processed = remaining - now;
+    assert!(
+      processed <= MAX_PER_FRAME,
+      "Processed {} detonations in one frame, limit is {}",
+      processed,
+      MAX_PER_FRAME
+    );
+    remaining = now;
+    if remaining == 0 {
+      break;
+    }
+  }
+
+  assert_eq!(remaining, 0, "All queued bombs should eventually detonate");
+}
+
+#[test]
+fn zero_limit_still_drains_queue() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, 0);
+  wait_until_seeded(&mut app);
+  spawn_triggered_bombs(&mut app);
+
+  for _ in 0..BOMB_COUNT + 5 {
+    app.update();
+    if bomb_count(&mut app) == 0 {
+      break;
+    }
+  }
+
+  assert_eq!(
+    bomb_count(&mut app),
+    0,
+    "A limit of zero should detonate one bomb per frame"
+  );
+}
@@ -0,0 +1,90 @@
+//! Tests for the diagnostics overlay configuration.
+//!
+//! Checks the visibility run condition, the toggle key, and that the window
+//! is placed at the configured anchor in a headless egui context.
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_egui::egui;
+use game::pixel_world::diagnostics::{
+  DiagnosticsConfig, diagnostics_visible, diagnostics_window, toggle_diagnostics,
+};
+
+const SCREEN: egui::Vec2 = egui::vec2(800.0, 600.0);
+
+/// Lays out the overlay window with fixed-size content in a headless egui
+/// context and returns its screen rect.
+fn window_rect(config: &DiagnosticsConfig) -> egui::Rect {
+  let ctx = egui::Context::default();
+  let input = egui::RawInput {
+    screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, SCREEN)),
+    ..Default::default()
+  };
+  // The first pass only measures the window
+  for _ in 0..3 {
+    let _ = ctx.run(input.clone(), |ctx| {
+      diagnostics_window(config).show(ctx, |ui| {
+        ui.allocate_space(egui::vec2(100.0, 50.0));
+      });
+    });
+  }
+  ctx
+    .memory(|memory| memory.area_rect(egui::Id::new("Diagnostics")))
+    .expect("Window was laid out")
+}
+
+fn assert_near(actual: f32, expected: f32, what: &str) {
+  assert!(
+    (actual - expected).abs() < 1.0,
+    "{} should be {}, got {}",
+    what,
+    expected,
+    actual
+  );
+}
+
+#[test]
+fn visibility_gates_ui_system() {
+  let mut world = World::new();
+  world.init_resource::<DiagnosticsConfig>();
+  assert!(world.run_system_once(diagnostics_visible).unwrap());
+
+  world.resource_mut::<DiagnosticsConfig>().visible = false;
+  assert!(!world.run_system_once(diagnostics_visible).unwrap());
+}
+
+#[test]
+fn toggle_key_flips_visibility() {
+  let mut world = World::new();
+  world.init_resource::<DiagnosticsConfig>();
+  let mut keys = ButtonInput::<KeyCode>::default();
+  keys.press(KeyCode::F3);
+  world.insert_resource(keys);
+
+  world.run_system_once(toggle_diagnostics).unwrap();
+  assert!(!world.resource::<DiagnosticsConfig>().visible);
+
+  // No toggle key leaves visibility alone
+  world.resource_mut::<DiagnosticsConfig>().toggle_key = None;
+  world.run_system_once(toggle_diagnostics).unwrap();
+  assert!(!world.resource::<DiagnosticsConfig>().visible);
+}
+
+#[test]
+fn default_anchor_is_top_right() {
+  let rect = window_rect(&DiagnosticsConfig::default());
+  assert_near(rect.right(), SCREEN.x - 10.0, "Right edge");
+  assert_near(rect.top(), 10.0, "Top edge");
+}
+
+#[test]
+fn anchor_is_honored() {
+  let config = DiagnosticsConfig {
+    anchor: egui::Align2::LEFT_BOTTOM,
+    offset: egui::vec2(20.0, -30.0),
+    ..Default::default()
+  };
+  let rect = window_rect(&config);
+  assert_near(rect.left(), 20.0, "Left edge");
+  assert_near(rect.bottom(), SCREEN.y - 30.0, "Bottom edge");
+}
@@ -0,0 +1,212 @@
+//! E2E tests for digging terrain into loose pixels.
+//!
+//! Digs a disc out of a stone block and checks that the disc is cleared
+//! except for a deterministic fraction of loose stone, and that the loose
+//! stone falls to the bottom of the hole.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, BrushState, Chunk, ChunkPos, ChunkSeeder, ColorIndex, DigConfig, Materials,
+  PersistenceConfig, Pixel, PixelFlags, PixelWorld, PixelWorldPlugin, SpawnPixelWorld,
+  StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const CENTER: (i64, i64) = (64, 64);
+const RADIUS: u32 = 20;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(app, |world| world.get_pixel(WorldPos::new(0, 0)).is_some()) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Creates a seeded world holding a 128x128 stone block.
+fn setup() -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  with_world(&mut app, |world| {
+    for y in 0..128 {
+      for x in 0..128 {
+        world.set_pixel(
+          WorldPos::new(x, y),
+          Pixel::new(material_ids::STONE, ColorIndex(0)),
+          DebugGizmos::none(),
+        );
+      }
+    }
+  });
+  (temp_dir, app)
+}
+
+/// Digs the brush disc at `CENTER` and returns the number of loose pixels.
+fn dig(app: &mut App, loose_fraction: f32) -> usize {
+  let brush = BrushState {
+    radius: RADIUS,
+    dig_mode: true,
+    dig: DigConfig { loose_fraction },
+    ..default()
+  };
+  app
+    .world_mut()
+    .resource_scope(|app_world, materials: Mut<Materials>| {
+      let mut q = app_world.query::<&mut PixelWorld>();
+      let mut world = q.single_mut(app_world).unwrap();
+      brush.dig(&mut world, CENTER, &materials, DebugGizmos::none())
+    })
+}
+
+fn disc() -> impl Iterator<Item = WorldPos> {
+  let r = RADIUS as i64;
+  (-r..=r)
+    .flat_map(move |dy| (-r..=r).map(move |dx| (dx, dy)))
+    .filter(move |&(dx, dy)| dx * dx + dy * dy <= r * r)
+    .map(|(dx, dy)| WorldPos::new(CENTER.0 + dx, CENTER.1 + dy))
+}
+
+/// Returns the positions of loose stone pixels in the disc.
+fn loose_pixels(app: &mut App) -> Vec<WorldPos> {
+  with_world(app, |world| {
+    disc()
+      .filter(|&pos| {
+        world
+          .get_pixel(pos)
+          .is_some_and(|p| p.material == material_ids::STONE && p.flags.contains(PixelFlags::LOOSE))
+      })
+      .collect()
+  })
+}
+
+fn mean_y(positions: &[WorldPos]) -> f32 {
+  positions.iter().map(|p| p.y as f32).sum::<f32>() / positions.len() as f32
+}
+
+#[test]
+fn dig_clears_disc_leaving_loose_fraction() {
+  let (_temp_dir, mut app) = setup();
+  let loose = dig(&mut app, 0.3);
+
+  let area = disc().count();
+  assert!(
+    loose > area / 5 && loose < area * 2 / 5,
+    "About 30% of {area} dug pixels should be loose, got {loose}"
+  );
+
+  let remaining = with_world(&mut app, |world| {
+    disc()
+      .filter_map(|pos| world.get_pixel(pos).copied())
+      .filter(|p| !p.is_void())
+      .collect::<Vec<_>>()
+  });
+  assert_eq!(remaining.len(), loose);
+  assert!(
+    remaining
+      .iter()
+      .all(|p| p.material == material_ids::STONE && p.flags.contains(PixelFlags::LOOSE)),
+    "Only loose stone should remain in the dug disc"
+  );
+}
+
+#[test]
+fn dig_without_loose_fraction_clears_disc() {
+  let (_temp_dir, mut app) = setup();
+  assert_eq!(dig(&mut app, 0.0), 0);
+  assert!(loose_pixels(&mut app).is_empty());
+}
+
+#[test]
+fn loose_placement_is_deterministic() {
+  let (_temp_a, mut a) = setup();
+  let (_temp_b, mut b) = setup();
+  dig(&mut a, 0.3);
+  dig(&mut b, 0.3);
+  assert_eq!(loose_pixels(&mut a), loose_pixels(&mut b));
+}
+
+#[test]
+fn loose_pixels_fall_into_the_hole() {
+  let (_temp_dir, mut app) = setup();
+  dig(&mut app, 0.3);
+  let before = loose_pixels(&mut app);
+
+  for _ in 0..90 {
+    app.update();
+  }
+
+  let after = loose_pixels(&mut app);
+  assert_eq!(
+    after.len(),
+    before.len(),
+    "Loose pixels should stay inside the hole"
+  );
+  assert!(
+    mean_y(&after) < mean_y(&before) - 5.0,
+    "Loose pixels should settle at the bottom (mean y {} -> {})",
+    mean_y(&before),
+    mean_y(&after)
+  );
+  assert!(
+    after.iter().all(|p| p.y < CENTER.1 + RADIUS as i64 / 2),
+    "The top of the hole should be empty"
+  );
+}
@@ -0,0 +1,241 @@
+//! E2E tests for liquid evaporation.
+//!
+//! A one-pixel-thick puddle in an open stone basin should slowly lose
+//! pixels, while a body of the same liquid sealed in stone has no air
+//! interface and keeps every pixel. A puddle resting on lava should lose
+//! pixels much faster than an identical puddle on cold stone.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::MaterialConfig;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelWorld, PixelWorldPlugin,
+  SimulationConfig, SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const BRINE: MaterialId = MaterialId(7);
+const LAVA: MaterialId = MaterialId(8);
+
+/// Puddle width in pixels.
+const PUDDLE_WIDTH: i64 = 64;
+/// Height of a basin floor in pixels: one heat cell.
+const FLOOR: i64 = 4;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn material(
+  name: &str,
+  state: PhysicsState,
+  base_temperature: u8,
+  evaporation_chance: f32,
+  evaporation_heat_coupling: f32,
+) -> MaterialConfig {
+  MaterialConfig {
+    density: 100,
+    dispersion: 5,
+    base_temperature,
+    evaporation_chance,
+    evaporation_heat_coupling,
+    ..MaterialConfig::new(name, state)
+  }
+}
+
+fn create_app(temp_dir: &TempDir, evaporation_chance: f32) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config.materials.push(material(
+    "Brine",
+    PhysicsState::Liquid,
+    0,
+    evaporation_chance,
+    50.0,
+  ));
+  config
+    .materials
+    .push(material("Lava", PhysicsState::Solid, 255, 0.0, 0.0));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Creates a seeded app whose evaporation pass runs every physics tick.
+fn setup(evaporation_chance: f32) -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, evaporation_chance);
+  wait_until_seeded(&mut app);
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+  (temp_dir, app)
+}
+
+fn fill(world: &mut PixelWorld, x: (i64, i64), y: (i64, i64), material: MaterialId) {
+  for py in y.0..y.1 {
+    for px in x.0..x.1 {
+      world.set_pixel(
+        WorldPos::new(px, py),
+        Pixel::new(material, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+  }
+}
+
+/// Paints an open basin with its left inner edge at `x`, holding a
+/// one-pixel-thick puddle on a floor of `floor`.
+fn paint_puddle(world: &mut PixelWorld, x: i64, floor: MaterialId) {
+  let stone = material_ids::STONE;
+  let right = x + PUDDLE_WIDTH;
+  fill(world, (x - 2, right + 2), (0, FLOOR), floor);
+  fill(world, (x - 2, x), (FLOOR, FLOOR + 8), stone);
+  fill(world, (right, right + 2), (FLOOR, FLOOR + 8), stone);
+  fill(world, (x, right), (FLOOR, FLOOR + 1), BRINE);
+}
+
+fn run(app: &mut App, ticks: usize) {
+  for _ in 0..ticks {
+    app.update();
+  }
+}
+
+/// Counts brine pixels in the given column range.
+fn brine_count(app: &mut App, x: (i64, i64)) -> usize {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  (0..64)
+    .flat_map(|y| (x.0..x.1).map(move |x| WorldPos::new(x, y)))
+    .filter(|&pos| world.get_pixel(pos).is_some_and(|p| p.material == BRINE))
+    .count()
+}
+
+#[test]
+fn thin_puddle_in_open_air_shrinks() {
+  let (_temp_dir, mut app) = setup(0.5);
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    paint_puddle(&mut world, 8, material_ids::STONE);
+  }
+  let columns = (0, PUDDLE_WIDTH + 16);
+  let start = brine_count(&mut app, columns);
+  assert_eq!(start, PUDDLE_WIDTH as usize);
+
+  run(&mut app, 10);
+  let early = brine_count(&mut app, columns);
+  assert!(
+    early > start / 2,
+    "Evaporation should be slow ({start} -> {early} in 10 ticks)"
+  );
+
+  run(&mut app, 110);
+  let end = brine_count(&mut app, columns);
+  assert!(
+    end < early,
+    "Puddle should keep shrinking ({start} -> {early} -> {end})"
+  );
+}
+
+#[test]
+fn sealed_liquid_stays_stable() {
+  let (_temp_dir, mut app) = setup(0.5);
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    fill(&mut world, (0, 40), (0, 40), material_ids::STONE);
+    fill(&mut world, (4, 36), (4, 36), BRINE);
+  }
+  let columns = (0, 40);
+  let start = brine_count(&mut app, columns);
+  assert_eq!(start, 32 * 32);
+
+  run(&mut app, 120);
+  assert_eq!(
+    brine_count(&mut app, columns),
+    start,
+    "Liquid without an air interface should not evaporate"
+  );
+}
+
+#[test]
+fn evaporation_accelerates_near_heat() {
+  let (_temp_dir, mut app) = setup(0.05);
+  const HOT_X: i64 = 8;
+  const COLD_X: i64 = 256;
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    paint_puddle(&mut world, HOT_X, LAVA);
+    paint_puddle(&mut world, COLD_X, material_ids::STONE);
+    // Wake the heat tiles under the hot puddle
+    for x in (HOT_X..HOT_X + PUDDLE_WIDTH).step_by(16) {
+      world.set_heat_at(WorldPos::new(x, 0), 255);
+    }
+  }
+  let hot_columns = (HOT_X - 8, HOT_X + PUDDLE_WIDTH + 8);
+  let cold_columns = (COLD_X - 8, COLD_X + PUDDLE_WIDTH + 8);
+
+  run(&mut app, 120);
+
+  let hot_lost = PUDDLE_WIDTH as usize - brine_count(&mut app, hot_columns);
+  let cold_lost = PUDDLE_WIDTH as usize - brine_count(&mut app, cold_columns);
+  assert!(
+    hot_lost > 0 && hot_lost > cold_lost * 2,
+    "Heated puddle should evaporate faster (hot lost {hot_lost}, cold lost {cold_lost})"
+  );
+}
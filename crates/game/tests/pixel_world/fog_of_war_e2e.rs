@@ -0,0 +1,250 @@
+//! E2E tests for fog of war.
+//!
+//! Tests that the chunk under the camera is marked explored and rendered at
+//! full brightness while unexplored chunks are dimmed, that the explored set
+//! survives chunks unloading and reloading, and that it is restored from the
+//! save file in a new session.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, CHUNK_SIZE, Chunk, ChunkMaterial, ChunkPos, ChunkSeeder, ExploredChunks,
+  FogOfWarConfig, PersistenceConfig, PersistenceControl, Pixel, PixelWorld, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera,
+};
+use tempfile::TempDir;
+
+const DIM: f32 = 0.25;
+
+/// Seeds every chunk with void; terrain is irrelevant here.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Returns the world position of the center of a chunk.
+fn chunk_center(pos: ChunkPos) -> Vec3 {
+  let half = CHUNK_SIZE as f32 / 2.0;
+  Vec3::new(
+    pos.x as f32 * CHUNK_SIZE as f32 + half,
+    pos.y as f32 * CHUNK_SIZE as f32 + half,
+    0.0,
+  )
+}
+
+struct TestHarness {
+  app: App,
+  camera: Entity,
+}
+
+impl TestHarness {
+  fn new(save_path: &Path, camera_chunk: ChunkPos) -> Self {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+      task_pool_options: TaskPoolOptions::with_num_threads(4),
+    }));
+
+    app.add_plugins(bevy::transform::TransformPlugin);
+    app.add_plugins(bevy::asset::AssetPlugin::default());
+    app.add_plugins(bevy::image::ImagePlugin::default());
+    app.add_plugins(bevy::scene::ScenePlugin);
+    app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+    // Chunk materials give slots render resources without a GPU
+    app.init_asset::<ChunkMaterial>();
+
+    app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(save_path)));
+    app.insert_resource(AsyncTaskBehavior::Poll);
+    app.insert_resource(FogOfWarConfig {
+      enabled: true,
+      reveal_radius: 0,
+      unexplored_brightness: DIM,
+    });
+
+    let translation = chunk_center(camera_chunk);
+    let camera = app
+      .world_mut()
+      .spawn((
+        Transform::from_translation(translation),
+        GlobalTransform::from_translation(translation),
+        StreamingCamera,
+      ))
+      .id();
+
+    app
+      .world_mut()
+      .commands()
+      .queue(SpawnPixelWorld::new(VoidSeeder));
+
+    Self { app, camera }
+  }
+
+  /// Runs updates until the chunk is seeded, or panics on timeout.
+  fn run_until_seeded(&mut self, pos: ChunkPos) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+      self.app.update();
+      std::thread::yield_now();
+      let mut q = self.app.world_mut().query::<&PixelWorld>();
+      if q
+        .single(self.app.world())
+        .is_ok_and(|world| world.get_pixel(pos.to_world()).is_some())
+      {
+        // One more frame so brightness reflects the seeded window
+        self.app.update();
+        return;
+      }
+    }
+    panic!("Chunk {:?} was not seeded within timeout", pos);
+  }
+
+  fn move_camera(&mut self, pos: ChunkPos) {
+    let translation = chunk_center(pos);
+    self
+      .app
+      .world_mut()
+      .get_mut::<Transform>(self.camera)
+      .unwrap()
+      .translation = translation;
+    // MinimalPlugins doesn't run transform propagation
+    *self
+      .app
+      .world_mut()
+      .get_mut::<GlobalTransform>(self.camera)
+      .unwrap() = GlobalTransform::from_translation(translation);
+  }
+
+  fn world(&mut self) -> &PixelWorld {
+    let mut q = self.app.world_mut().query::<&PixelWorld>();
+    q.single(self.app.world()).unwrap()
+  }
+
+  fn explored(&self) -> &ExploredChunks {
+    self.app.world().resource::<ExploredChunks>()
+  }
+
+  /// Returns the brightness uniform of a loaded chunk's material.
+  fn brightness(&mut self, pos: ChunkPos) -> f32 {
+    let handle = self
+      .world()
+      .chunk_material(pos)
+      .cloned()
+      .unwrap_or_else(|| panic!("Chunk {:?} has no material", pos));
+    self
+      .app
+      .world()
+      .resource::<Assets<ChunkMaterial>>()
+      .get(&handle)
+      .unwrap()
+      .brightness
+  }
+
+  /// Saves and runs updates until the save is flushed.
+  fn save(&mut self) {
+    let handle = self
+      .app
+      .world_mut()
+      .resource_mut::<PersistenceControl>()
+      .save();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+      if handle.is_complete() {
+        return;
+      }
+      self.app.update();
+      std::thread::yield_now();
+    }
+    panic!("Save did not complete within 5 seconds");
+  }
+}
+
+#[test]
+fn camera_chunk_is_explored_and_undimmed() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), ChunkPos::new(0, 0));
+  harness.run_until_seeded(ChunkPos::new(0, 0));
+
+  assert!(harness.explored().is_explored(ChunkPos::new(0, 0)));
+  assert!(!harness.explored().is_explored(ChunkPos::new(-1, 0)));
+  assert_eq!(harness.explored().len(), 1);
+
+  assert_eq!(harness.brightness(ChunkPos::new(0, 0)), 1.0);
+  assert_eq!(harness.brightness(ChunkPos::new(-1, 0)), DIM);
+}
+
+#[test]
+fn disabled_fog_leaves_chunks_undimmed() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), ChunkPos::new(0, 0));
+  harness.app.insert_resource(FogOfWarConfig::default());
+  harness.run_until_seeded(ChunkPos::new(0, 0));
+
+  assert!(harness.explored().is_empty());
+  let visible: Vec<_> = harness.world().visible_positions().collect();
+  for pos in visible {
+    assert_eq!(harness.brightness(pos), 1.0, "Chunk {:?} was dimmed", pos);
+  }
+}
+
+#[test]
+fn explored_chunks_survive_unload_and_reload() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("test.save"), ChunkPos::new(0, 0));
+  harness.run_until_seeded(ChunkPos::new(0, 0));
+
+  let far = ChunkPos::new(20, 0);
+  harness.move_camera(far);
+  harness.run_until_seeded(far);
+  assert!(
+    harness
+      .world()
+      .chunk_material(ChunkPos::new(0, 0))
+      .is_none(),
+    "Chunk (0, 0) should have unloaded"
+  );
+
+  harness.move_camera(ChunkPos::new(0, 0));
+  harness.run_until_seeded(ChunkPos::new(0, 0));
+
+  assert!(harness.explored().is_explored(ChunkPos::new(0, 0)));
+  assert!(harness.explored().is_explored(far));
+  assert_eq!(harness.brightness(ChunkPos::new(0, 0)), 1.0);
+  assert_eq!(harness.brightness(ChunkPos::new(-1, 0)), DIM);
+}
+
+#[test]
+fn explored_chunks_persist_across_sessions() {
+  let temp_dir = TempDir::new().unwrap();
+  let save_path = temp_dir.path().join("test.save");
+
+  {
+    let mut harness = TestHarness::new(&save_path, ChunkPos::new(0, 0));
+    harness.run_until_seeded(ChunkPos::new(0, 0));
+    harness.save();
+  }
+
+  // Camera in chunk (1, 0) keeps (0, 0) in the window without revealing it
+  let mut harness = TestHarness::new(&save_path, ChunkPos::new(1, 0));
+  harness.run_until_seeded(ChunkPos::new(0, 0));
+
+  assert!(
+    harness.explored().is_explored(ChunkPos::new(0, 0)),
+    "Explored set should be restored from the save file"
+  );
+  assert!(harness.explored().is_explored(ChunkPos::new(1, 0)));
+  assert_eq!(harness.explored().len(), 2);
+
+  assert_eq!(harness.brightness(ChunkPos::new(0, 0)), 1.0);
+  assert_eq!(harness.brightness(ChunkPos::new(1, 0)), 1.0);
+  assert_eq!(harness.brightness(ChunkPos::new(2, 0)), DIM);
+}
@@ -0,0 +1,179 @@
+//! E2E tests for synchronous chunk seeding.
+//!
+//! Background seeding is held back by a gate, so the tests observe chunks
+//! that are active but unseeded. Tests that `PixelWorld::force_seed` makes a
+//! chunk readable in the same frame, that it rejects inactive chunks, and
+//! that late background results do not disturb a force-seeded chunk.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Seeds stone, blocking every thread but the test thread until opened.
+struct GatedSeeder {
+  owner: ThreadId,
+  open: Arc<AtomicBool>,
+}
+
+impl ChunkSeeder for GatedSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    if std::thread::current().id() != self.owner {
+      while !self.open.load(Ordering::Acquire) {
+        std::thread::sleep(Duration::from_millis(1));
+      }
+    }
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::new(material_ids::STONE, ColorIndex(0));
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, open: Arc<AtomicBool>) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  let seeder = GatedSeeder {
+    owner: std::thread::current().id(),
+    open,
+  };
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(seeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+/// Runs frames until the streaming window has active chunks.
+fn wait_until_streamed(app: &mut App) {
+  for _ in 0..10 {
+    app.update();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.active_count() > 0)
+    {
+      return;
+    }
+  }
+  panic!("Streaming window was not populated");
+}
+
+fn material_at(world: &PixelWorld, pos: WorldPos) -> Option<u8> {
+  world.get_pixel(pos).map(|p| p.material.0)
+}
+
+#[test]
+fn force_seed_makes_chunk_readable_immediately() {
+  let temp_dir = TempDir::new().unwrap();
+  let open = Arc::new(AtomicBool::new(false));
+  let mut app = create_app(&temp_dir, open.clone());
+  wait_until_streamed(&mut app);
+
+  let pos = WorldPos::new(10, 10);
+  with_world(&mut app, |world| {
+    assert_eq!(
+      material_at(world, pos),
+      None,
+      "Chunk should not be seeded while background seeding is gated"
+    );
+    assert!(world.force_seed(ChunkPos::new(0, 0)));
+    assert_eq!(material_at(world, pos), Some(material_ids::STONE.0));
+    assert!(world.is_upload_pending(ChunkPos::new(0, 0)));
+
+    // Neighbors are still waiting on background seeding
+    assert_eq!(material_at(world, WorldPos::new(-10, 10)), None);
+  });
+
+  open.store(true, Ordering::Release);
+}
+
+#[test]
+fn force_seed_rejects_inactive_chunk() {
+  let temp_dir = TempDir::new().unwrap();
+  let open = Arc::new(AtomicBool::new(false));
+  let mut app = create_app(&temp_dir, open.clone());
+  wait_until_streamed(&mut app);
+
+  with_world(&mut app, |world| {
+    assert!(!world.force_seed(ChunkPos::new(100, 100)));
+  });
+
+  open.store(true, Ordering::Release);
+}
+
+#[test]
+fn late_background_seeding_keeps_forced_edits() {
+  let temp_dir = TempDir::new().unwrap();
+  let open = Arc::new(AtomicBool::new(false));
+  let mut app = create_app(&temp_dir, open.clone());
+  wait_until_streamed(&mut app);
+
+  let pos = WorldPos::new(10, 10);
+  with_world(&mut app, |world| {
+    assert!(world.force_seed(ChunkPos::new(0, 0)));
+    *world.get_pixel_mut(pos).unwrap() = Pixel::new(material_ids::SAND, ColorIndex(0));
+  });
+
+  open.store(true, Ordering::Release);
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(&mut app, |world| {
+      material_at(world, WorldPos::new(-10, 10)).is_some()
+    }) {
+      break;
+    }
+  }
+
+  with_world(&mut app, |world| {
+    assert_eq!(
+      material_at(world, WorldPos::new(-10, 10)),
+      Some(material_ids::STONE.0),
+      "Background seeding should resume once the gate opens"
+    );
+    // Sand is embedded in stone, so it cannot move
+    assert_eq!(
+      material_at(world, pos),
+      Some(material_ids::SAND.0),
+      "Forced chunk should not be overwritten by its late seeding task"
+    );
+  });
+}
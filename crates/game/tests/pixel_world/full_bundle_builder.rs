@@ -0,0 +1,110 @@
+//! Tests for `PixelWorldFullBundle` plugin toggles.
+//!
+//! Tests that `without_*` methods exclude individual sub-plugins while the
+//! rest are still added, and that `with_collision` overrides the collision
+//! configuration used by the bodies plugin.
+
+use bevy::app::{PluginGroup, TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::buoyancy::Buoyancy2dPlugin;
+use game::pixel_world::diagnostics::DiagnosticsPlugin;
+use game::pixel_world::pixel_awareness::PixelAwarenessPlugin;
+use game::pixel_world::{
+  CollisionConfig, PersistenceConfig, PixelBodiesPlugin, PixelWorldFullBundle, PixelWorldPlugin,
+};
+use tempfile::TempDir;
+
+fn bundle(temp_dir: &TempDir) -> PixelWorldFullBundle {
+  PixelWorldFullBundle::new(PersistenceConfig::at(temp_dir.path().join("test.save")))
+}
+
+fn create_app(bundle: PixelWorldFullBundle) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(bundle);
+  app
+}
+
+#[test]
+fn default_bundle_includes_every_plugin() {
+  let temp_dir = TempDir::new().unwrap();
+  let group = bundle(&temp_dir).build();
+
+  assert!(group.enabled::<PixelWorldPlugin>());
+  assert!(group.enabled::<PixelBodiesPlugin>());
+  assert!(group.enabled::<PixelAwarenessPlugin>());
+  assert!(group.enabled::<Buoyancy2dPlugin>());
+  assert!(group.enabled::<DiagnosticsPlugin>());
+}
+
+#[test]
+fn without_methods_exclude_only_their_plugin() {
+  let temp_dir = TempDir::new().unwrap();
+
+  let group = bundle(&temp_dir).without_bodies().build();
+  assert!(!group.contains::<PixelBodiesPlugin>());
+  assert!(group.contains::<PixelAwarenessPlugin>());
+
+  let group = bundle(&temp_dir).without_awareness().build();
+  assert!(!group.contains::<PixelAwarenessPlugin>());
+  assert!(group.contains::<Buoyancy2dPlugin>());
+
+  let group = bundle(&temp_dir).without_buoyancy().build();
+  assert!(!group.contains::<Buoyancy2dPlugin>());
+  assert!(group.contains::<DiagnosticsPlugin>());
+}
+
+#[test]
+fn without_diagnostics_keeps_other_plugins() {
+  let temp_dir = TempDir::new().unwrap();
+  let app = create_app(bundle(&temp_dir).without_diagnostics());
+
+  assert!(!app.is_plugin_added::<DiagnosticsPlugin>());
+  assert!(app.is_plugin_added::<PixelWorldPlugin>());
+  assert!(app.is_plugin_added::<PixelBodiesPlugin>());
+  assert!(app.is_plugin_added::<PixelAwarenessPlugin>());
+  assert!(app.is_plugin_added::<Buoyancy2dPlugin>());
+}
+
+#[test]
+fn configuring_a_plugin_enables_it_again() {
+  let temp_dir = TempDir::new().unwrap();
+  let group = bundle(&temp_dir)
+    .without_buoyancy()
+    .submersion(default())
+    .without_bodies()
+    .with_collision(CollisionConfig::default())
+    .build();
+
+  assert!(group.enabled::<Buoyancy2dPlugin>());
+  assert!(group.enabled::<PixelBodiesPlugin>());
+}
+
+#[test]
+fn with_collision_overrides_config() {
+  let temp_dir = TempDir::new().unwrap();
+  let app = create_app(
+    bundle(&temp_dir)
+      .without_diagnostics()
+      .with_collision(CollisionConfig {
+        simplification_tolerance: 2.5,
+        proximity_radius: 5,
+        debug_gizmos: false,
+        ..default()
+      }),
+  );
+
+  let config = app.world().resource::<CollisionConfig>();
+  assert_eq!(config.simplification_tolerance, 2.5);
+  assert_eq!(config.proximity_radius, 5);
+  assert!(!config.debug_gizmos);
+}
@@ -0,0 +1,60 @@
+//! Tests for the fall direction of gravity wells.
+//!
+//! Checks where a pixel falls without wells, within a directional well's
+//! blend radius and around a point well.
+
+use bevy::math::Vec2;
+use game::pixel_world::{GravityWell, GravityWells, WorldPos};
+
+fn rightward() -> GravityWells {
+  GravityWells::new([GravityWell::directional(Vec2::ZERO, 32.0, 2.0, Vec2::X)])
+}
+
+#[test]
+fn gravity_points_down_without_wells() {
+  let wells = GravityWells::default();
+  assert_eq!(
+    wells.fall_target(WorldPos::new(5, 5)),
+    Some(WorldPos::new(5, 4)),
+    "Down is one row lower"
+  );
+}
+
+#[test]
+fn well_blends_gravity_by_distance() {
+  let wells = rightward();
+  // Fully overridden near the center
+  assert_eq!(
+    wells.fall_target(WorldPos::new(0, 0)),
+    Some(WorldPos::new(1, 0))
+  );
+  // Blended halfway toward the edge
+  assert_eq!(
+    wells.fall_target(WorldPos::new(0, 23)),
+    Some(WorldPos::new(1, 22))
+  );
+  // Unaffected outside the radius
+  assert_eq!(
+    wells.fall_target(WorldPos::new(0, 40)),
+    Some(WorldPos::new(0, 39))
+  );
+}
+
+#[test]
+fn point_well_pulls_toward_center() {
+  let wells = GravityWells::new([GravityWell::point(Vec2::ZERO, 32.0, 4.0)]);
+  assert_eq!(
+    wells.fall_target(WorldPos::new(-10, 0)),
+    Some(WorldPos::new(-9, 0))
+  );
+  assert_eq!(
+    wells.fall_target(WorldPos::new(0, -10)),
+    Some(WorldPos::new(0, -9))
+  );
+}
+
+#[test]
+fn inactive_wells_are_skipped() {
+  let wells = GravityWells::new([GravityWell::point(Vec2::ZERO, 32.0, 0.0)]);
+  assert!(wells.is_empty());
+}
@@ -0,0 +1,176 @@
+//! E2E tests for gravity wells.
+//!
+//! Two blocks of sand hang over a stone floor, one inside a rightward
+//! gravity well and one far outside it. The sand inside the well must be
+//! pulled to the right, while the sand outside falls straight onto the
+//! floor as usual.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, GravityWell, PersistenceConfig,
+  Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Region holding every pixel of sand in the test.
+const REGION: (WorldPos, WorldPos) = (WorldPos::new(-100, 0), WorldPos::new(100, 40));
+/// Well center and radius; the well pulls along +X.
+const WELL: (Vec2, f32) = (Vec2::new(-40.0, 10.0), 24.0);
+const BLOCK_SIZE: i64 = 5;
+/// Lower left corners of the sand blocks inside and outside the well.
+const INSIDE: WorldPos = WorldPos::new(-45, 8);
+const OUTSIDE: WorldPos = WorldPos::new(40, 8);
+const SETTLE_FRAMES: usize = 120;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      world.get_pixel(REGION.0).is_some() && world.get_pixel(REGION.1).is_some()
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn fill_block(world: &mut PixelWorld, corner: WorldPos, pixel: Pixel) {
+  for y in corner.y..corner.y + BLOCK_SIZE {
+    for x in corner.x..corner.x + BLOCK_SIZE {
+      world.set_pixel(WorldPos::new(x, y), pixel, DebugGizmos::none());
+    }
+  }
+}
+
+/// Returns the positions of all sand pixels with x in `xs`.
+fn sand_in(app: &mut App, xs: std::ops::Range<i64>) -> Vec<WorldPos> {
+  with_world(app, |world| {
+    (REGION.0.y..REGION.1.y)
+      .flat_map(|y| xs.clone().map(move |x| WorldPos::new(x, y)))
+      .filter(|&pos| {
+        world
+          .get_pixel(pos)
+          .is_some_and(|p| p.material == material_ids::SAND)
+      })
+      .collect()
+  })
+}
+
+fn mean(positions: &[WorldPos]) -> Vec2 {
+  let sum = positions.iter().fold(Vec2::ZERO, |sum, pos| {
+    sum + Vec2::new(pos.x as f32, pos.y as f32)
+  });
+  sum / positions.len() as f32
+}
+
+#[test]
+fn sand_inside_well_drifts_right_while_sand_outside_falls() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let (center, radius) = WELL;
+  app
+    .world_mut()
+    .spawn(GravityWell::directional(center, radius, 4.0, Vec2::X));
+  with_world(&mut app, |world| {
+    let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+    for x in REGION.0.x..REGION.1.x {
+      world.set_pixel(WorldPos::new(x, REGION.0.y), stone, DebugGizmos::none());
+    }
+    let sand = Pixel::new(material_ids::SAND, ColorIndex(0));
+    fill_block(world, INSIDE, sand);
+    fill_block(world, OUTSIDE, sand);
+  });
+
+  let half = BLOCK_SIZE as f32 / 2.0;
+  let inside_start = Vec2::new(INSIDE.x as f32, INSIDE.y as f32) + half - 0.5;
+  let outside_start = Vec2::new(OUTSIDE.x as f32, OUTSIDE.y as f32) + half - 0.5;
+
+  for _ in 0..SETTLE_FRAMES {
+    app.update();
+  }
+
+  let inside = sand_in(&mut app, REGION.0.x..0);
+  let outside = sand_in(&mut app, 0..REGION.1.x);
+  let block = (BLOCK_SIZE * BLOCK_SIZE) as usize;
+  assert_eq!(inside.len(), block, "Sand inside the well was lost");
+  assert_eq!(outside.len(), block, "Sand outside the well was lost");
+
+  let inside_end = mean(&inside);
+  assert!(
+    inside_end.x > inside_start.x + 10.0,
+    "Sand inside the well should be pulled right: {inside_start} -> {inside_end}"
+  );
+
+  let outside_end = mean(&outside);
+  assert!(
+    (outside_end.x - outside_start.x).abs() < 3.0,
+    "Sand outside the well should not be pulled sideways: {outside_start} -> {outside_end}"
+  );
+  assert!(
+    outside_end.y < outside_start.y - 3.0,
+    "Sand outside the well should fall: {outside_start} -> {outside_end}"
+  );
+  assert!(
+    outside.iter().all(|pos| pos.y > REGION.0.y),
+    "Sand outside the well should rest on the floor"
+  );
+}
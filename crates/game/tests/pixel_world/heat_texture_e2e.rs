@@ -0,0 +1,189 @@
+//! E2E tests for the per-chunk heat texture.
+//!
+//! Tests that no heat texture is allocated while it is disabled, that every
+//! chunk gets one with the configured resolution and format when enabled,
+//! that downsampled uploads keep the hottest cell of each block, and that an
+//! `R8Unorm` texture is bound to the chunk material for the heat glow.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use game::pixel_world::primitives::HEAT_GRID_SIZE;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkMaterial, ChunkPos, ChunkSeeder, HeatTextureConfig,
+  HeatTextureFormat, PersistenceConfig, Pixel, PixelWorld, PixelWorldConfig, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera, WorldPos,
+};
+use tempfile::TempDir;
+
+/// Seeds every chunk with void; terrain is irrelevant here.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, heat_texture: HeatTextureConfig) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  // Chunk materials give slots render resources without a GPU
+  app.init_asset::<ChunkMaterial>();
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  let config = PixelWorldConfig {
+    heat_texture,
+    ..Default::default()
+  };
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder).with_config(config));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Returns the heat texture handle of every visible chunk.
+fn heat_textures(app: &mut App) -> Vec<Option<Handle<Image>>> {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  world
+    .visible_positions()
+    .map(|pos| world.heat_texture(pos).cloned())
+    .collect()
+}
+
+#[test]
+fn disabled_heat_texture_is_not_allocated() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, HeatTextureConfig::default());
+  wait_until_seeded(&mut app);
+
+  let textures = heat_textures(&mut app);
+  assert!(!textures.is_empty());
+  assert!(
+    textures.iter().all(Option::is_none),
+    "No slot should register a heat texture while disabled"
+  );
+}
+
+#[test]
+fn enabled_heat_texture_follows_config() {
+  let temp_dir = TempDir::new().unwrap();
+  let config = HeatTextureConfig {
+    enabled: true,
+    downsample: 2,
+    format: HeatTextureFormat::Uint,
+    ..Default::default()
+  };
+  let mut app = create_app(&temp_dir, config);
+  wait_until_seeded(&mut app);
+
+  let textures = heat_textures(&mut app);
+  assert!(!textures.is_empty());
+  let images = app.world().resource::<Assets<Image>>();
+  for handle in textures {
+    let handle = handle.expect("Every slot should register a heat texture");
+    let image = images.get(&handle).expect("Heat texture should exist");
+    assert_eq!(image.width(), HEAT_GRID_SIZE / 2);
+    assert_eq!(image.height(), HEAT_GRID_SIZE / 2);
+    assert_eq!(image.texture_descriptor.format, TextureFormat::R8Uint);
+  }
+}
+
+#[test]
+fn downsampled_upload_keeps_hottest_cell() {
+  let temp_dir = TempDir::new().unwrap();
+  let config = HeatTextureConfig {
+    enabled: true,
+    downsample: 4,
+    format: HeatTextureFormat::Unorm,
+    ..Default::default()
+  };
+  let mut app = create_app(&temp_dir, config);
+  wait_until_seeded(&mut app);
+
+  // Cell (5, 9) of chunk (0, 0) lies in texel (1, 2); (4, 8) shares it. The
+  // world stays frozen so the heat does not spread before the upload.
+  let handle = {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    world.freeze();
+    world.set_heat_at(WorldPos::new(5 * 4, 9 * 4), 200);
+    world.set_heat_at(WorldPos::new(4 * 4, 8 * 4), 50);
+    world.heat_texture(ChunkPos::new(0, 0)).cloned().unwrap()
+  };
+  app.update();
+
+  let resolution = config.resolution();
+  let images = app.world().resource::<Assets<Image>>();
+  let data = images.get(&handle).unwrap().data.as_ref().unwrap();
+  assert_eq!(resolution, HEAT_GRID_SIZE / 4);
+  assert_eq!(data.len(), (resolution * resolution) as usize);
+  assert_eq!(data[(2 * resolution + 1) as usize], 200);
+  assert_eq!(data[0], 0);
+  assert_eq!(data.iter().filter(|&&t| t > 0).count(), 1);
+}
+
+#[test]
+fn unorm_heat_texture_is_bound_to_chunk_material() {
+  let temp_dir = TempDir::new().unwrap();
+  let config = HeatTextureConfig {
+    enabled: true,
+    ..Default::default()
+  };
+  let mut app = create_app(&temp_dir, config);
+  wait_until_seeded(&mut app);
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  let pos = ChunkPos::new(0, 0);
+  let heat = world.heat_texture(pos).cloned().unwrap();
+  let material = world.chunk_material(pos).cloned().unwrap();
+  let materials = app.world().resource::<Assets<ChunkMaterial>>();
+  let material = materials.get(&material).unwrap();
+  assert_eq!(material.heat_texture, Some(heat));
+  assert_eq!(material.heat_glow, config.glow);
+}
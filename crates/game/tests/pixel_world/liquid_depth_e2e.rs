@@ -0,0 +1,241 @@
+//! E2E tests for liquid depth shading.
+//!
+//! Fills a stone basin with water and checks that the depth recorded in each
+//! water pixel grows by one per pixel downward, and that the depth ramp
+//! draws deeper pixels with a darker palette entry without the simulation
+//! touching their colors. Chunk materials carry the packed ramps for the
+//! shader. Water without a ramp keeps its colors.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::DepthColorConfig;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkMaterial, ChunkPos, ChunkSeeder, ColorIndex, DepthRampUniform,
+  Materials, MaterialsConfig, PersistenceConfig, Pixel, PixelWorld, PixelWorldPlugin,
+  SimulationConfig, SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+/// Inner columns of the basin.
+const BASIN_X: (i64, i64) = (8, 40);
+/// Height of the stone floor.
+const FLOOR: i64 = 4;
+/// Topmost water row; spans two tiles vertically.
+const SURFACE: i64 = 53;
+/// Column sampled for depths.
+const COLUMN: i64 = 24;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, ramp: &[(u8, u8)]) -> App {
+  let mut config = MaterialsConfig::builtin();
+  let water = &mut config.materials[material_ids::WATER.0 as usize];
+  water.evaporation_chance = 0.0;
+  water.depth_ramp = ramp
+    .iter()
+    .map(|&(depth, color)| DepthColorConfig { depth, color })
+    .collect();
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  // Chunk materials give slots render resources without a GPU
+  app.init_asset::<ChunkMaterial>();
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(app, |world| world.get_pixel(WorldPos::new(0, 0)).is_some()) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn fill(world: &mut PixelWorld, x: (i64, i64), y: (i64, i64), pixel: Pixel) {
+  for py in y.0..y.1 {
+    for px in x.0..x.1 {
+      world.set_pixel(WorldPos::new(px, py), pixel, DebugGizmos::none());
+    }
+  }
+}
+
+/// Creates a seeded app holding a stone basin filled with water, with the
+/// depth pass running every tick.
+fn setup(ramp: &[(u8, u8)]) -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, ramp);
+  wait_until_seeded(&mut app);
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let water = Pixel::new(material_ids::WATER, ColorIndex(0));
+  let (left, right) = BASIN_X;
+  with_world(&mut app, |world| {
+    fill(world, (left - 2, right + 2), (0, FLOOR), stone);
+    fill(world, (left - 2, left), (FLOOR, SURFACE + 8), stone);
+    fill(world, (right, right + 2), (FLOOR, SURFACE + 8), stone);
+    fill(world, (left, right), (FLOOR, SURFACE + 1), water);
+  });
+  for _ in 0..30 {
+    app.update();
+  }
+  (temp_dir, app)
+}
+
+fn water_at(app: &mut App, y: i64) -> Pixel {
+  let pixel = with_world(app, |world| {
+    *world.get_pixel(WorldPos::new(COLUMN, y)).unwrap()
+  });
+  assert_eq!(pixel.material, material_ids::WATER, "Water moved at y={y}");
+  pixel
+}
+
+/// Returns the color index the shader draws `pixel` with.
+fn shaded(app: &App, pixel: Pixel) -> ColorIndex {
+  let materials = app.world().resource::<Materials>();
+  materials.get(pixel.material).shaded_color(&pixel)
+}
+
+/// Returns the brightness of the palette entry the shader picks for `pixel`.
+fn brightness(app: &App, pixel: Pixel) -> u32 {
+  let materials = app.world().resource::<Materials>();
+  let entry = materials.get(pixel.material).palette[shaded(app, pixel).0 as usize * 7 / 255];
+  entry.red as u32 + entry.green as u32 + entry.blue as u32
+}
+
+#[test]
+fn depth_increases_downward() {
+  let (_temp_dir, mut app) = setup(&[(0, 0), (16, 128), (32, 255)]);
+
+  for y in FLOOR..=SURFACE {
+    assert_eq!(
+      water_at(&mut app, y).damage as i64,
+      SURFACE - y,
+      "Depth at y={y} should count the water above it"
+    );
+  }
+}
+
+#[test]
+fn depth_ramp_darkens_deep_liquid() {
+  let (_temp_dir, mut app) = setup(&[(0, 0), (16, 128), (32, 255)]);
+
+  let surface = water_at(&mut app, SURFACE);
+  let middle = water_at(&mut app, SURFACE - 20);
+  let bottom = water_at(&mut app, FLOOR);
+  assert_eq!(shaded(&app, surface), ColorIndex(0));
+  assert_eq!(shaded(&app, middle), ColorIndex(128));
+  assert_eq!(shaded(&app, bottom), ColorIndex(255));
+
+  // The ramp is drawn by the shader, pixel colors are left alone
+  for pixel in [surface, middle, bottom] {
+    assert_eq!(pixel.color, ColorIndex(0));
+  }
+
+  let (surface, middle, bottom) = (
+    brightness(&app, surface),
+    brightness(&app, middle),
+    brightness(&app, bottom),
+  );
+  assert!(
+    surface > middle && middle > bottom,
+    "Deeper water should render darker ({surface} > {middle} > {bottom})"
+  );
+}
+
+#[test]
+fn liquid_without_ramp_keeps_its_color() {
+  let (_temp_dir, mut app) = setup(&[]);
+
+  for y in [FLOOR, SURFACE] {
+    let pixel = water_at(&mut app, y);
+    assert_eq!(pixel.color, ColorIndex(0));
+    assert_eq!(pixel.damage, 0);
+  }
+}
+
+#[test]
+fn chunk_materials_carry_depth_ramps() {
+  let (_temp_dir, mut app) = setup(&[(16, 128), (0, 0)]);
+
+  let handle = with_world(&mut app, |world| {
+    world.chunk_material(ChunkPos::new(0, 0)).cloned().unwrap()
+  });
+  let ramps = app
+    .world()
+    .resource::<Assets<ChunkMaterial>>()
+    .get(&handle)
+    .unwrap()
+    .depth_ramps;
+
+  let water = material_ids::WATER.0 as usize;
+  assert_ne!(
+    ramps.materials[water / 128][water / 32 % 4] & (1 << (water % 32)),
+    0
+  );
+  let stone = material_ids::STONE.0 as usize;
+  assert_eq!(
+    ramps.materials[stone / 128][stone / 32 % 4] & (1 << (stone % 32)),
+    0
+  );
+
+  // Stops are sorted, and unused slots repeat the deepest one
+  let deep = (16 << 8) | 128;
+  let pair = deep | (deep << 16);
+  assert_eq!(ramps.stops[water], UVec4::new(deep << 16, pair, pair, pair));
+  assert_eq!(
+    ramps,
+    DepthRampUniform::new(app.world().resource::<Materials>())
+  );
+}
@@ -0,0 +1,196 @@
+//! E2E tests for conserving liquid displaced by pixel bodies.
+//!
+//! A body is spawned inside a water pool, so the first blit has no cleared
+//! positions to swap water into. With `DisplacementState::conserve_liquids`
+//! the water under the body must be relocated rather than erased.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, DisplacementState,
+  LastBlitTransform, PersistenceConfig, Pixel, PixelBodiesPlugin, PixelBodyIdGenerator,
+  PixelBodyLoader, PixelFlags, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera,
+  WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const POOL_WIDTH: i64 = 96;
+const POOL_DEPTH: i64 = 32;
+const WALL_HEIGHT: i64 = 80;
+const BODY_SIZE: u32 = 16;
+const BODY_CENTER: Vec2 = Vec2::new(48.0, 16.0);
+
+/// Seeds every chunk with void; the pool is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      [
+        WorldPos::new(-1, -1),
+        WorldPos::new(POOL_WIDTH, -1),
+        WorldPos::new(POOL_WIDTH, WALL_HEIGHT),
+      ]
+      .iter()
+      .all(|&pos| world.get_pixel(pos).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Paints a stone basin filled with water up to `POOL_DEPTH`.
+fn paint_pool(world: &mut PixelWorld) {
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let water = Pixel::new(material_ids::WATER, ColorIndex(0));
+  for y in -1..WALL_HEIGHT {
+    for x in -1..=POOL_WIDTH {
+      let pixel = if y < 0 || x < 0 || x == POOL_WIDTH {
+        stone
+      } else if y < POOL_DEPTH {
+        water
+      } else {
+        continue;
+      };
+      world.set_pixel(WorldPos::new(x, y), pixel, DebugGizmos::none());
+    }
+  }
+}
+
+fn count_water(world: &PixelWorld) -> usize {
+  (0..WALL_HEIGHT)
+    .flat_map(|y| (0..POOL_WIDTH).map(move |x| WorldPos::new(x, y)))
+    .filter(|&pos| {
+      world
+        .get_pixel(pos)
+        .is_some_and(|p| p.material == material_ids::WATER)
+    })
+    .count()
+}
+
+fn spawn_body(app: &mut App, conserve_liquids: bool) {
+  let body = PixelBodyLoader::rectangle(BODY_SIZE, BODY_SIZE, material_ids::WOOD);
+  let id = app
+    .world_mut()
+    .resource_mut::<PixelBodyIdGenerator>()
+    .generate();
+  let transform = Transform::from_translation(BODY_CENTER.extend(0.0));
+  app.world_mut().spawn((
+    body,
+    LastBlitTransform::default(),
+    DisplacementState {
+      conserve_liquids,
+      ..Default::default()
+    },
+    transform,
+    GlobalTransform::from(transform),
+    id,
+  ));
+}
+
+/// Spawns a body in the pool and returns (water before, water after).
+fn drop_body_into_pool(conserve_liquids: bool) -> (usize, usize) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  with_world(&mut app, paint_pool);
+  app.update();
+  let before = with_world(&mut app, |world| count_water(world));
+
+  spawn_body(&mut app, conserve_liquids);
+  for _ in 0..10 {
+    app.update();
+  }
+
+  with_world(&mut app, |world| {
+    let center = WorldPos::new(BODY_CENTER.x as i64, BODY_CENTER.y as i64);
+    let pixel = world
+      .get_pixel(center)
+      .expect("Pool chunk should be loaded");
+    assert!(
+      pixel.flags.contains(PixelFlags::PIXEL_BODY),
+      "Body should occupy the space inside the pool"
+    );
+    (before, count_water(world))
+  })
+}
+
+#[test]
+fn body_spawned_in_pool_conserves_water() {
+  let (before, after) = drop_body_into_pool(true);
+  let tolerance = before / 100;
+  assert!(
+    before.abs_diff(after) <= tolerance,
+    "Water count changed from {} to {}",
+    before,
+    after
+  );
+}
+
+#[test]
+fn body_spawned_in_pool_erases_water_without_conservation() {
+  let (before, after) = drop_body_into_pool(false);
+  let area = (BODY_SIZE * BODY_SIZE) as usize;
+  assert!(
+    before - after >= area / 2,
+    "Expected water under the body to be erased ({} -> {})",
+    before,
+    after
+  );
+}
@@ -0,0 +1,198 @@
+//! E2E tests for foam on disturbed liquid surfaces.
+//!
+//! Fills a narrow stone basin with calm water, checks that it carries no
+//! foam, then drops exactly two rows of water onto it and checks that foam
+//! appears on the water bordering air and clears once the basin settles.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelFlags, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Basin interior, open at the top.
+const BASIN: WorldRect = WorldRect::new(0, 0, 8, 20);
+/// Calm water filling the bottom of the basin.
+const POOL_DEPTH: u32 = 4;
+/// Water dropped into the basin; fills two whole rows once settled.
+const DROP: WorldRect = WorldRect::new(0, 12, 8, 2);
+
+/// Seeds every chunk with void; the scene is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      [
+        WorldPos::new(BASIN.x - 1, BASIN.y - 1),
+        WorldPos::new(BASIN.x + BASIN.width as i64, BASIN.y + BASIN.height as i64),
+      ]
+      .iter()
+      .all(|&pos| world.get_pixel(pos).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Paints `pixel` over `rect`, marking it for simulation.
+fn paint(app: &mut App, rect: WorldRect, pixel: Pixel) {
+  with_world(app, |world| {
+    world.blit(rect, |_| Some(pixel), DebugGizmos::none());
+  });
+}
+
+/// Paints the stone basin and the calm pool inside it.
+fn paint_basin(app: &mut App) {
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let (x, y, w, h) = (BASIN.x, BASIN.y, BASIN.width, BASIN.height);
+  paint(app, WorldRect::new(x - 1, y - 1, w + 2, 1), stone);
+  paint(app, WorldRect::new(x - 1, y, 1, h), stone);
+  paint(app, WorldRect::new(x + w as i64, y, 1, h), stone);
+  paint(app, WorldRect::new(x, y, w, POOL_DEPTH), water());
+}
+
+fn water() -> Pixel {
+  Pixel::new(material_ids::WATER, ColorIndex(0))
+}
+
+/// Foamy pixels in the basin, and whether each borders void.
+fn foam(app: &mut App) -> Vec<(WorldPos, bool)> {
+  with_world(app, |world| {
+    let is_void = |pos: WorldPos| world.get_pixel(pos).is_some_and(|p| p.is_void());
+    let mut foam = Vec::new();
+    for y in BASIN.y..BASIN.y + BASIN.height as i64 {
+      for x in BASIN.x..BASIN.x + BASIN.width as i64 {
+        let pos = WorldPos::new(x, y);
+        if world
+          .get_pixel(pos)
+          .is_some_and(|p| p.flags.contains(PixelFlags::FOAM))
+        {
+          let on_surface = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .iter()
+            .any(|&(dx, dy)| is_void(WorldPos::new(x + dx, y + dy)));
+          foam.push((pos, on_surface));
+        }
+      }
+    }
+    foam
+  })
+}
+
+fn run(app: &mut App, frames: usize) {
+  for _ in 0..frames {
+    app.update();
+  }
+}
+
+#[test]
+fn calm_pool_has_no_foam() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  paint_basin(&mut app);
+
+  run(&mut app, 60);
+  assert_eq!(foam(&mut app), vec![], "Calm water should not foam");
+}
+
+#[test]
+fn disturbed_surface_foams_then_clears() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  paint_basin(&mut app);
+  run(&mut app, 10);
+
+  paint(&mut app, DROP, water());
+
+  let mut surface_foam = 0;
+  for _ in 0..60 {
+    app.update();
+    surface_foam += foam(&mut app)
+      .iter()
+      .filter(|(_, surface)| *surface)
+      .count();
+  }
+  assert!(
+    surface_foam > 0,
+    "Falling water should foam where it borders air"
+  );
+
+  run(&mut app, 600);
+  assert_eq!(foam(&mut app), vec![], "Foam should clear once settled");
+
+  // The dropped water settled into two whole rows on top of the pool
+  let top = POOL_DEPTH as i64 + DROP.height as i64;
+  with_world(&mut app, |world| {
+    for x in BASIN.x..BASIN.x + BASIN.width as i64 {
+      for y in 0..top {
+        assert!(
+          world
+            .get_pixel(WorldPos::new(x, y))
+            .is_some_and(|p| p.material == material_ids::WATER),
+          "Water missing at ({}, {})",
+          x,
+          y
+        );
+      }
+    }
+  });
+}
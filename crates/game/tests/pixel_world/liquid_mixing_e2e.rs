@@ -0,0 +1,237 @@
+//! E2E tests for liquid color mixing.
+//!
+//! Fills a sealed stone box with blue-dyed water on the left and a red
+//! liquid of the same density on the right, so nothing flows. Both palettes
+//! run from their own hue toward a shared purple. A miscible red dye and
+//! the water must render ever closer colors across the interface while
+//! pixels away from it keep theirs, even though the water has a depth ramp;
+//! an immiscible red oil must keep a sharp interface.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::{DepthColorConfig, MaterialConfig};
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelWorld, PixelWorldPlugin,
+  SimulationConfig, SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const DYE: MaterialId = MaterialId(7);
+const OIL: MaterialId = MaterialId(8);
+
+/// Inner columns of the box; water fills the left half.
+const BOX_X: (i64, i64) = (8, 40);
+/// Inner rows of the box.
+const BOX_Y: (i64, i64) = (4, 20);
+/// First column of the right-hand liquid.
+const INTERFACE: i64 = 24;
+/// Row sampled for colors.
+const ROW: i64 = 12;
+
+const BLUE: [u8; 3] = [40, 80, 220];
+const RED: [u8; 3] = [220, 40, 40];
+const PURPLE: [u8; 3] = [140, 60, 160];
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Returns an 8-shade palette running from `from` to `to`.
+fn palette(from: [u8; 3], to: [u8; 3]) -> Vec<[u8; 4]> {
+  (0..8)
+    .map(|shade| {
+      let channel = |i: usize| (from[i] as i32 + (to[i] as i32 - from[i] as i32) * shade / 7) as u8;
+      [channel(0), channel(1), channel(2), 255]
+    })
+    .collect()
+}
+
+fn liquid(name: &str, density: u8, color_mix_rate: f32, immiscible: bool) -> MaterialConfig {
+  MaterialConfig {
+    palette: palette(RED, PURPLE),
+    density,
+    color_mix_rate,
+    immiscible,
+    ..MaterialConfig::new(name, PhysicsState::Liquid)
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  let water = &mut config.materials[material_ids::WATER.0 as usize];
+  water.evaporation_chance = 0.0;
+  water.color_mix_rate = 6.0;
+  water.palette = palette(BLUE, PURPLE);
+  // A flat ramp that would hide the interface colors if the shader drew
+  // it over mixing pixels
+  water.depth_ramp = vec![DepthColorConfig { depth: 0, color: 0 }];
+  let density = water.density;
+  config.materials.push(liquid("Dye", density, 6.0, false));
+  config.materials.push(liquid("Oil", density, 6.0, true));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(app, |world| world.get_pixel(WorldPos::new(0, 0)).is_some()) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn fill(world: &mut PixelWorld, x: (i64, i64), y: (i64, i64), pixel: Pixel) {
+  for py in y.0..y.1 {
+    for px in x.0..x.1 {
+      world.set_pixel(WorldPos::new(px, py), pixel, DebugGizmos::none());
+    }
+  }
+}
+
+/// Creates a seeded app holding a sealed box of water beside `right`, with
+/// the mixing pass running every tick, and runs it for `frames` frames.
+fn run_box(right: MaterialId, frames: usize) -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let (left, right_edge) = BOX_X;
+  let (bottom, top) = BOX_Y;
+  with_world(&mut app, |world| {
+    fill(
+      world,
+      (left - 2, right_edge + 2),
+      (bottom - 2, top + 2),
+      stone,
+    );
+    fill(
+      world,
+      (left, INTERFACE),
+      (bottom, top),
+      Pixel::new(material_ids::WATER, ColorIndex(0)),
+    );
+    fill(
+      world,
+      (INTERFACE, right_edge),
+      (bottom, top),
+      Pixel::new(right, ColorIndex(0)),
+    );
+  });
+  for _ in 0..frames {
+    app.update();
+  }
+  (temp_dir, app)
+}
+
+/// Returns the color the chunk shader renders at `x` on the sampled row,
+/// checking the pixel is still `material`.
+fn rendered_at(app: &mut App, x: i64, material: MaterialId) -> [u8; 3] {
+  let pixel = with_world(app, |world| {
+    *world.get_pixel(WorldPos::new(x, ROW)).unwrap()
+  });
+  assert_eq!(pixel.material, material, "Liquid moved at x={x}");
+  let materials = app.world().resource::<Materials>();
+  let material = materials.get(material);
+  let rgba = material.palette[material.shaded_color(&pixel).0 as usize * 7 / 255];
+  [rgba.red, rgba.green, rgba.blue]
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> f32 {
+  (0..3)
+    .map(|i| (a[i] as f32 - b[i] as f32).powi(2))
+    .sum::<f32>()
+    .sqrt()
+}
+
+#[test]
+fn miscible_liquids_blend_at_interface() {
+  let (_temp_dir, mut app) = run_box(DYE, 60);
+
+  let water = rendered_at(&mut app, INTERFACE - 1, material_ids::WATER);
+  let dye = rendered_at(&mut app, INTERFACE, DYE);
+  assert!(
+    distance(water, dye) < distance(BLUE, RED) / 4.0,
+    "Interface colors should converge: water {water:?} dye {dye:?}"
+  );
+  assert!(
+    distance(water, PURPLE) < distance(BLUE, PURPLE) / 2.0
+      && distance(dye, PURPLE) < distance(RED, PURPLE) / 2.0,
+    "Both sides should shade toward the shared hue: water {water:?} dye {dye:?}"
+  );
+
+  // Away from the interface, each liquid keeps its color
+  assert_eq!(
+    rendered_at(&mut app, BOX_X.0 + 2, material_ids::WATER),
+    BLUE
+  );
+  assert_eq!(rendered_at(&mut app, BOX_X.1 - 3, DYE), RED);
+}
+
+#[test]
+fn immiscible_liquids_stay_separate() {
+  let (_temp_dir, mut app) = run_box(OIL, 60);
+
+  assert_eq!(
+    rendered_at(&mut app, INTERFACE - 1, material_ids::WATER),
+    BLUE,
+    "Water should not take on the color of oil"
+  );
+  assert_eq!(
+    rendered_at(&mut app, INTERFACE, OIL),
+    RED,
+    "Oil should not take on the color of water"
+  );
+}
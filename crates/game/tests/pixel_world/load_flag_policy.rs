@@ -0,0 +1,142 @@
+//! Tests for clearing transient pixel flags when chunks load from a save.
+//!
+//! Saves a chunk holding a burning, falling wood block next to wet sand, then
+//! reloads it with and without a policy clearing `BURNING` and `FALLING`, and
+//! checks that only the listed flags are dropped.
+
+use game::pixel_world::persistence::native::NativeFs;
+use game::pixel_world::{
+  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel, PixelFlags,
+  WorldSave, material_ids,
+};
+use tempfile::TempDir;
+
+const SAVE_NAME: &str = "test.save";
+const POS: ChunkPos = ChunkPos::new(0, 0);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn burning_wood() -> Pixel {
+  let mut pixel = Pixel::new(material_ids::WOOD, ColorIndex(7));
+  pixel.damage = 3;
+  pixel.flags = PixelFlags::BURNING | PixelFlags::FALLING | PixelFlags::DIRTY;
+  pixel
+}
+
+fn wet_sand() -> Pixel {
+  let mut pixel = Pixel::new(material_ids::SAND, ColorIndex(9));
+  pixel.flags = PixelFlags::WET;
+  pixel
+}
+
+/// Saves a chunk with a block of burning wood and a row of wet sand. With
+/// `fill`, every other pixel is stone so the chunk isn't stored as a delta.
+fn save_scene(temp_dir: &TempDir, fill: bool) {
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  let mut save = WorldSave::create(&fs, SAVE_NAME, 42).unwrap();
+
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  chunk.set_pos(POS);
+  VoidSeeder.seed(POS, &mut chunk);
+  if fill {
+    chunk
+      .pixels
+      .fill(Pixel::new(material_ids::STONE, ColorIndex(1)));
+  }
+  for y in 100..110 {
+    for x in 100..110 {
+      chunk.pixels[(x, y)] = burning_wood();
+    }
+  }
+  for x in 100..110 {
+    chunk.pixels[(x, 99)] = wet_sand();
+  }
+
+  save.save_chunk(&chunk, POS, &VoidSeeder).unwrap();
+  save.flush().unwrap();
+}
+
+/// Reopens the save and loads the chunk, clearing `clear_flags`.
+fn load_scene(temp_dir: &TempDir, clear_flags: PixelFlags) -> Chunk {
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  let save = WorldSave::open(&fs, SAVE_NAME).unwrap();
+  let mut loaded = save.load_chunk(POS, &VoidSeeder).expect("Chunk is saved");
+  loaded.clear_flags = clear_flags;
+
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  chunk.set_pos(POS);
+  if loaded.seeder_needed {
+    VoidSeeder.seed(POS, &mut chunk);
+  }
+  loaded.apply_to(&mut chunk).unwrap();
+  chunk
+}
+
+fn extinguish_policy() -> PixelFlags {
+  PersistenceConfig::at("unused.save")
+    .with_flags_cleared_on_load(PixelFlags::BURNING | PixelFlags::FALLING)
+    .clear_flags_on_load
+}
+
+fn assert_extinguished(chunk: &Chunk) {
+  let wood = chunk.pixels[(105, 105)];
+  assert_eq!(wood.material, material_ids::WOOD);
+  assert_eq!(wood.color, ColorIndex(7));
+  assert_eq!(wood.damage, 3, "Damage is kept");
+  assert_eq!(
+    wood.flags,
+    PixelFlags::DIRTY,
+    "Only listed flags are cleared"
+  );
+
+  let sand = chunk.pixels[(105, 99)];
+  assert_eq!(sand.material, material_ids::SAND);
+  assert_eq!(sand.color, ColorIndex(9));
+  assert_eq!(sand.flags, PixelFlags::WET, "Unlisted flags are kept");
+}
+
+#[test]
+fn default_policy_restores_flags_as_saved() {
+  let temp_dir = TempDir::new().unwrap();
+  save_scene(&temp_dir, false);
+
+  let policy = PersistenceConfig::at("unused.save").clear_flags_on_load;
+  let chunk = load_scene(&temp_dir, policy);
+
+  assert_eq!(chunk.pixels[(105, 105)], burning_wood());
+  assert_eq!(chunk.pixels[(105, 99)], wet_sand());
+}
+
+#[test]
+fn policy_extinguishes_delta_chunk() {
+  let temp_dir = TempDir::new().unwrap();
+  save_scene(&temp_dir, false);
+
+  let chunk = load_scene(&temp_dir, extinguish_policy());
+  assert_extinguished(&chunk);
+  assert!(chunk.pixels[(0, 0)].is_void());
+}
+
+#[test]
+fn policy_extinguishes_full_chunk() {
+  let temp_dir = TempDir::new().unwrap();
+  save_scene(&temp_dir, true);
+
+  let chunk = load_scene(&temp_dir, extinguish_policy());
+  assert_extinguished(&chunk);
+  assert_eq!(
+    chunk.pixels[(0, 0)],
+    Pixel::new(material_ids::STONE, ColorIndex(1))
+  );
+}
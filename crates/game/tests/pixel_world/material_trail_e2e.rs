@@ -0,0 +1,215 @@
+//! E2E tests for falling trails and ephemeral pixels.
+//!
+//! Drops an ember whose material leaves an ephemeral spark trail and checks
+//! that sparks appear only along its path, at most one per cell it left,
+//! that older sparks have faded further along their palette, and that the
+//! whole trail vanishes once the sparks' lifetime is up.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::MaterialConfig;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelWorld, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const SPARK: MaterialId = MaterialId(7);
+const EMBER: MaterialId = MaterialId(8);
+
+/// Ticks a spark lives.
+const SPARK_LIFETIME: u8 = 30;
+/// Column the ember falls down.
+const X: i64 = 40;
+/// Height the ember is dropped from.
+const DROP_Y: i64 = 100;
+/// Top of the stone floor the ember lands on.
+const FLOOR_Y: i64 = 60;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn material(name: &str, state: PhysicsState, lifetime: u8, trail: Option<&str>) -> MaterialConfig {
+  MaterialConfig {
+    density: 100,
+    lifetime,
+    trail: trail.map(str::to_string),
+    ..MaterialConfig::new(name, state)
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config
+    .materials
+    .push(material("Spark", PhysicsState::Gas, SPARK_LIFETIME, None));
+  config
+    .materials
+    .push(material("Ember", PhysicsState::Powder, 0, Some("Spark")));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if with_world(app, |world| world.get_pixel(WorldPos::new(0, 0)).is_some()) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn run(app: &mut App, updates: usize) {
+  for _ in 0..updates {
+    app.update();
+  }
+}
+
+/// Creates a seeded world with a stone floor and drops an ember above it.
+fn setup() -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+  with_world(&mut app, |world| {
+    for x in X - 8..=X + 8 {
+      world.set_pixel(
+        WorldPos::new(x, FLOOR_Y),
+        Pixel::new(material_ids::STONE, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+    let drop = WorldPos::new(X, DROP_Y);
+    world.set_pixel(drop, Pixel::new(EMBER, ColorIndex(0)), DebugGizmos::none());
+    world.mark_pixel_sim_dirty(drop);
+  });
+  (temp_dir, app)
+}
+
+/// Returns the pixels of `material` in the area around the drop column.
+fn find(app: &mut App, material: MaterialId) -> Vec<(WorldPos, Pixel)> {
+  with_world(app, |world| {
+    (FLOOR_Y..=DROP_Y + 4)
+      .flat_map(|y| (X - 16..=X + 16).map(move |x| WorldPos::new(x, y)))
+      .filter_map(|pos| world.get_pixel(pos).map(|&p| (pos, p)))
+      .filter(|(_, p)| p.material == material)
+      .collect()
+  })
+}
+
+#[test]
+fn falling_ember_leaves_fading_trail() {
+  let (_temp_dir, mut app) = setup();
+  run(&mut app, 12);
+
+  let embers = find(&mut app, EMBER);
+  assert_eq!(embers.len(), 1, "Exactly one ember should exist");
+  let ember = embers[0].0;
+  assert!(ember.y < DROP_Y, "Ember should be falling");
+
+  let sparks = find(&mut app, SPARK);
+  let moves = (DROP_Y - ember.y) as usize;
+  assert!(!sparks.is_empty(), "Falling ember should leave sparks");
+  assert!(
+    sparks.len() <= moves,
+    "At most one spark per move ({} sparks, {moves} moves)",
+    sparks.len()
+  );
+  for (pos, spark) in &sparks {
+    assert_eq!(pos.x, X, "Spark off the ember's path at {pos:?}");
+    assert!(
+      pos.y > ember.y && pos.y <= DROP_Y,
+      "Spark outside the fallen span at {pos:?}"
+    );
+    assert!(
+      spark.damage > 0 && spark.damage < SPARK_LIFETIME,
+      "Spark age {} out of range",
+      spark.damage
+    );
+  }
+
+  // Sparks higher up were left earlier, so they are older and more faded
+  let mut by_height = sparks.clone();
+  by_height.sort_by_key(|(pos, _)| pos.y);
+  for pair in by_height.windows(2) {
+    let (lower, upper) = (pair[0].1, pair[1].1);
+    assert!(
+      upper.damage >= lower.damage && upper.color.0 >= lower.color.0,
+      "Older sparks should be more faded: {lower:?} below {upper:?}"
+    );
+  }
+}
+
+#[test]
+fn trail_vanishes_after_lifetime() {
+  let (_temp_dir, mut app) = setup();
+  // Let the ember land
+  run(&mut app, (DROP_Y - FLOOR_Y) as usize * 2);
+  let embers = find(&mut app, EMBER);
+  assert_eq!(embers.len(), 1);
+  assert_eq!(
+    embers[0].0,
+    WorldPos::new(X, FLOOR_Y + 1),
+    "Ember should rest on the floor"
+  );
+
+  run(&mut app, SPARK_LIFETIME as usize + 5);
+  assert!(
+    find(&mut app, SPARK).is_empty(),
+    "Every spark should vanish after its lifetime"
+  );
+  assert_eq!(
+    find(&mut app, EMBER).len(),
+    1,
+    "The ember itself is not ephemeral"
+  );
+}
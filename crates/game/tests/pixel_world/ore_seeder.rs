@@ -0,0 +1,134 @@
+//! Tests for ore vein seeding.
+//!
+//! Tests that ore placement does not depend on the order chunks are seeded
+//! in, that veins continue across chunk boundaries, and that the ore
+//! fraction over a large area matches the configured rarity.
+
+use game::pixel_world::{
+  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, LayeredSeeder, MaterialId, OreSeeder,
+  Pixel, material_ids,
+};
+
+const ORE: MaterialId = MaterialId(7);
+
+/// Fills every chunk with stone so ore can be placed anywhere.
+struct StoneSeeder;
+
+impl ChunkSeeder for StoneSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::new(material_ids::STONE, ColorIndex(0));
+      }
+    }
+  }
+}
+
+fn seeder(rarity: f32) -> LayeredSeeder {
+  LayeredSeeder::new(StoneSeeder).with_layer(
+    OreSeeder::new(ORE, 42)
+      .rarity(rarity)
+      .length(64)
+      .thickness(4),
+  )
+}
+
+fn seed_chunk(seeder: &LayeredSeeder, pos: ChunkPos) -> Chunk {
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  seeder.seed(pos, &mut chunk);
+  chunk
+}
+
+fn is_ore(chunk: &Chunk, x: u32, y: u32) -> bool {
+  chunk.pixels[(x, y)].material == ORE
+}
+
+fn ore_count(chunk: &Chunk) -> usize {
+  (0..CHUNK_SIZE)
+    .flat_map(|y| (0..CHUNK_SIZE).map(move |x| (x, y)))
+    .filter(|&(x, y)| is_ore(chunk, x, y))
+    .count()
+}
+
+#[test]
+fn seeding_is_independent_of_load_order() {
+  let positions: Vec<ChunkPos> = (-1..=1)
+    .flat_map(|y| (-1..=1).map(move |x| ChunkPos::new(x, y)))
+    .collect();
+
+  let forward = seeder(0.05);
+  let first: Vec<Chunk> = positions.iter().map(|&p| seed_chunk(&forward, p)).collect();
+
+  // Seed the center chunk first and the rest in reverse with a fresh seeder
+  let reverse = seeder(0.05);
+  let center = seed_chunk(&reverse, ChunkPos::new(0, 0));
+  let mut second: Vec<(ChunkPos, Chunk)> = positions
+    .iter()
+    .rev()
+    .map(|&p| (p, seed_chunk(&reverse, p)))
+    .collect();
+  second.reverse();
+
+  assert!(ore_count(&first[4]) > 0, "Center chunk should contain ore");
+  assert!(first[4].pixels.as_bytes() == center.pixels.as_bytes());
+  for (a, (pos, b)) in first.iter().zip(&second) {
+    assert!(
+      a.pixels.as_bytes() == b.pixels.as_bytes(),
+      "Chunk {:?} differs between load orders",
+      pos
+    );
+  }
+}
+
+#[test]
+fn veins_continue_across_chunk_boundaries() {
+  let seeder = seeder(0.05);
+  let left = seed_chunk(&seeder, ChunkPos::new(0, 0));
+  let right = seed_chunk(&seeder, ChunkPos::new(1, 0));
+
+  let edge = CHUNK_SIZE - 1;
+  let mut edge_ore = 0;
+  let mut continued = 0;
+  for y in 0..CHUNK_SIZE {
+    if is_ore(&left, edge, y) {
+      edge_ore += 1;
+      let lo = y.saturating_sub(2);
+      let hi = (y + 2).min(CHUNK_SIZE - 1);
+      if (lo..=hi).any(|ny| is_ore(&right, 0, ny)) {
+        continued += 1;
+      }
+    }
+  }
+
+  assert!(edge_ore > 0, "Expected ore on the chunk edge");
+  assert!(
+    continued * 4 >= edge_ore * 3,
+    "Only {} of {} edge ore pixels continue into the neighbor",
+    continued,
+    edge_ore
+  );
+}
+
+#[test]
+fn ore_fraction_matches_rarity() {
+  const RARITY: f32 = 0.03;
+  let seeder = seeder(RARITY);
+
+  let mut ore = 0;
+  let mut total = 0;
+  for y in -3..3 {
+    for x in -3..3 {
+      let chunk = seed_chunk(&seeder, ChunkPos::new(x, y));
+      ore += ore_count(&chunk);
+      total += (CHUNK_SIZE * CHUNK_SIZE) as usize;
+    }
+  }
+
+  let fraction = ore as f32 / total as f32;
+  assert!(
+    (fraction - RARITY).abs() < RARITY * 0.2,
+    "Ore fraction {} should be close to rarity {}",
+    fraction,
+    RARITY
+  );
+}
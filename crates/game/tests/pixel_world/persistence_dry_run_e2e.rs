@@ -0,0 +1,174 @@
+//! E2E test for persistence dry-run mode.
+//!
+//! Saves an edit normally, then reopens the save with dry-run enabled. The
+//! saved edit must load, while a second save of new edits must leave the
+//! file on disk byte-for-byte unchanged and record the skipped chunk writes.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, DryRunWrite, PersistenceConfig,
+  PersistenceControl, PersistenceHandle, Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld,
+  StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(save_path: &Path) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(save_path)));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+/// Runs updates until `done` holds, or panics after a timeout.
+fn run_until(app: &mut App, what: &str, mut done: impl FnMut(&mut App) -> bool) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if done(app) {
+      return;
+    }
+  }
+  panic!("{what} within timeout");
+}
+
+fn pixel_at(app: &mut App, pos: WorldPos) -> Option<Pixel> {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  q.single(app.world())
+    .ok()
+    .and_then(|world| world.get_pixel(pos).copied())
+}
+
+fn paint(app: &mut App, positions: &[WorldPos]) {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  for &pos in positions {
+    world.set_pixel(
+      pos,
+      Pixel::new(material_ids::STONE, ColorIndex(0)),
+      DebugGizmos::none(),
+    );
+  }
+}
+
+fn save(app: &mut App) -> PersistenceHandle {
+  let handle = app.world_mut().resource_mut::<PersistenceControl>().save();
+  run_until(app, "Save did not complete", |_| handle.is_complete());
+  handle
+}
+
+fn is_stone(app: &mut App, pos: WorldPos) -> bool {
+  pixel_at(app, pos).is_some_and(|p| p.material == material_ids::STONE)
+}
+
+#[test]
+fn dry_run_logs_writes_without_touching_file() {
+  let temp_dir = TempDir::new().unwrap();
+  let save_path = temp_dir.path().join("test.save");
+  let saved = WorldPos::new(10, 10);
+  let edited = [WorldPos::new(20, 20), WorldPos::new(-30, 40)];
+
+  // Write a real save with one edit
+  {
+    let mut app = create_app(&save_path);
+    run_until(&mut app, "World was not seeded", |app| {
+      pixel_at(app, saved).is_some() && app.world().contains_resource::<PersistenceControl>()
+    });
+    paint(&mut app, &[saved]);
+    save(&mut app);
+  }
+  let before = std::fs::read(&save_path).unwrap();
+
+  // Reopen in dry-run mode
+  let mut app = create_app(&save_path);
+  run_until(&mut app, "PersistenceControl was not created", |app| {
+    app.world().contains_resource::<PersistenceControl>()
+  });
+  app
+    .world_mut()
+    .resource_mut::<PersistenceControl>()
+    .enable_dry_run();
+
+  run_until(&mut app, "Saved edit was not loaded", |app| {
+    is_stone(app, saved)
+  });
+
+  paint(&mut app, &edited);
+  save(&mut app);
+
+  let after = std::fs::read(&save_path).unwrap();
+  assert!(before == after, "Dry-run save must not modify the file");
+
+  let log = app
+    .world_mut()
+    .resource_mut::<PersistenceControl>()
+    .take_dry_run_log();
+  for pos in edited {
+    let (chunk_pos, _) = pos.to_chunk_and_local();
+    assert!(
+      log
+        .iter()
+        .any(|w| matches!(w, DryRunWrite::Chunk { pos, bytes } if *pos == chunk_pos && *bytes > 0)),
+      "Missing logged write for chunk {chunk_pos:?}: {log:?}"
+    );
+  }
+  assert!(log.contains(&DryRunWrite::Flush), "Flush should be logged");
+
+  // Leaving dry-run writes the pending edits for real
+  app
+    .world_mut()
+    .resource_mut::<PersistenceControl>()
+    .disable_dry_run();
+  paint(&mut app, &[WorldPos::new(21, 20)]);
+  save(&mut app);
+  assert!(
+    std::fs::read(&save_path).unwrap() != before,
+    "Saving after dry-run should write to the file"
+  );
+  assert!(
+    app
+      .world()
+      .resource::<PersistenceControl>()
+      .dry_run_log()
+      .is_empty()
+  );
+}
@@ -10,7 +10,7 @@
 use game::pixel_world::persistence::native::NativeFs;
 use game::pixel_world::persistence::{LoadedChunk, compression, format::StorageType};
 use game::pixel_world::{
-  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, Pixel, WorldSave, material_ids,
+  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, Pixel, PixelFlags, WorldSave, material_ids,
 };
 use tempfile::TempDir;
 
@@ -176,6 +176,7 @@ fn loaded_chunk_data_applies_correctly() {
     data: compressed,
     pos: ChunkPos::new(0, 0),
     seeder_needed: false,
+    clear_flags: PixelFlags::empty(),
   };
 
   // Apply to a fresh chunk
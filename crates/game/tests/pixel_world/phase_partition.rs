@@ -0,0 +1,50 @@
+//! Tests for the checkerboard phase invariant.
+//!
+//! Tiles sorted by `Phase::from_tile` must pass `validate_phase_partition`,
+//! and buckets holding neighboring or repeated tiles must be rejected.
+
+use game::pixel_world::coords::{Phase, TilePos};
+use game::pixel_world::scheduling::validate_phase_partition;
+
+fn partition(tiles: impl Iterator<Item = TilePos>) -> [Vec<TilePos>; 4] {
+  let mut phases: [Vec<TilePos>; 4] = [vec![], vec![], vec![], vec![]];
+  for tile in tiles {
+    phases[Phase::from_tile(tile).index()].push(tile);
+  }
+  phases
+}
+
+fn grid(min: i64, max: i64) -> impl Iterator<Item = TilePos> {
+  (min..max).flat_map(move |y| (min..max).map(move |x| TilePos::new(x, y)))
+}
+
+#[test]
+fn phase_partition_is_valid() {
+  // Straddles the origin to cover negative tile coordinates
+  assert_eq!(validate_phase_partition(&partition(grid(-9, 9))), Ok(()));
+}
+
+#[test]
+fn adjacent_tiles_in_one_phase_are_rejected() {
+  let mut phases = partition(grid(0, 4));
+  // Move (1, 1) into the bucket of its diagonal neighbor (0, 0)
+  let moved = TilePos::new(1, 1);
+  let from = Phase::from_tile(moved).index();
+  phases[from].retain(|&tile| tile != moved);
+  let into = Phase::from_tile(TilePos::new(0, 0)).index();
+  phases[into].push(moved);
+
+  let conflict = validate_phase_partition(&phases).unwrap_err();
+  assert_eq!(conflict.phase, into);
+  assert!([conflict.a, conflict.b].contains(&moved));
+}
+
+#[test]
+fn duplicate_tiles_in_one_phase_are_rejected() {
+  let mut phases = partition(grid(0, 4));
+  let tile = TilePos::new(2, 2);
+  phases[Phase::from_tile(tile).index()].push(tile);
+
+  let conflict = validate_phase_partition(&phases).unwrap_err();
+  assert_eq!((conflict.a, conflict.b), (tile, tile));
+}
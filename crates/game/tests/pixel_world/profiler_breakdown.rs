@@ -0,0 +1,75 @@
+//! Tests for the per-pass profiler breakdown.
+//!
+//! Feeds known span samples into `ProfilerMetrics` and checks the reported
+//! per-pass times and their sum.
+
+use game::pixel_world::diagnostics::{ProfilerMetrics, ProfilerSample};
+
+fn sample(tag: &'static str, time_ms: f32) -> ProfilerSample {
+  ProfilerSample { tag, time_ms }
+}
+
+fn assert_close(actual: f32, expected: f32) {
+  assert!(
+    (actual - expected).abs() < 1e-4,
+    "Expected {}ms, got {}ms",
+    expected,
+    actual
+  );
+}
+
+#[test]
+fn breakdown_sums_spans_per_pass() {
+  // Refresh on every frame so the fed frame is reported immediately
+  let mut metrics = ProfilerMetrics::with_update_interval(0.0);
+  metrics.record_frame([
+    // Two simulation ticks in one frame
+    sample("physics", 2.0),
+    sample("physics", 1.5),
+    sample("burning", 0.5),
+    sample("heat", 1.0),
+    sample("upload_chunks", 0.25),
+    sample("collision_dispatch", 0.75),
+    // Not a pass; must not appear in the breakdown
+    sample("collect_tiles", 9.0),
+  ]);
+
+  let breakdown = metrics.pass_breakdown();
+  let labels: Vec<_> = breakdown.iter().map(|&(label, _)| label).collect();
+  assert_eq!(
+    labels,
+    ["physics", "burning", "heat", "upload", "collision"]
+  );
+
+  for (&(_, actual), expected) in breakdown.iter().zip([3.5, 0.5, 1.0, 0.25, 0.75]) {
+    assert_close(actual, expected);
+  }
+  assert_close(breakdown.iter().map(|&(_, ms)| ms).sum(), 6.0);
+
+  // Slowest list still reports the max per tag
+  let physics_max = metrics
+    .slowest()
+    .iter()
+    .find(|s| s.tag == "physics")
+    .map(|s| s.time_ms);
+  assert_eq!(physics_max, Some(2.0));
+}
+
+#[test]
+fn breakdown_resets_each_window() {
+  let mut metrics = ProfilerMetrics::with_update_interval(0.0);
+  metrics.record_frame([sample("heat", 4.0)]);
+  metrics.record_frame([sample("physics", 1.0)]);
+
+  let breakdown = metrics.pass_breakdown();
+  assert_close(breakdown[0].1, 1.0);
+  assert_close(breakdown[2].1, 0.0);
+}
+
+#[test]
+fn empty_metrics_report_zero_for_every_pass() {
+  let metrics = ProfilerMetrics::default();
+  let breakdown = metrics.pass_breakdown();
+  assert_eq!(breakdown.len(), 5);
+  assert!(breakdown.iter().all(|&(_, ms)| ms == 0.0));
+}
@@ -0,0 +1,191 @@
+//! E2E tests for heat released by burning reactions.
+//!
+//! Two identical strips of a flammable material are lit at one end. Base
+//! burning heat is too low to reach the ignition point of the next cell, so
+//! fire only travels along the strip whose material releases reaction heat.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use game::pixel_world::material::{EffectsConfig, MaterialConfig};
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, HeatConfig, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelFlags, PixelWorld,
+  PixelWorldPlugin, SimulationConfig, SpawnPixelWorld, StreamingCamera, WorldPos,
+  debug_shim::DebugGizmos,
+};
+use tempfile::TempDir;
+
+const THERMITE: MaterialId = MaterialId(7);
+const INERT: MaterialId = MaterialId(8);
+
+/// Strip length in heat cells.
+const STRIP_CELLS: i64 = 8;
+/// Heat cell size in pixels.
+const CELL: i64 = 4;
+/// Heat threshold of both strip materials.
+const IGNITION: u8 = 40;
+
+/// Seeds every chunk with void so only painted pixels hold heat.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn flammable(name: &str, reaction_heat: i16) -> MaterialConfig {
+  MaterialConfig {
+    density: 200,
+    ignition_threshold: IGNITION,
+    effects: Some(EffectsConfig {
+      on_burn: None,
+      blast_resistance: 0.0,
+      indestructible: false,
+      durability: 0,
+      reaction_heat,
+    }),
+    ..MaterialConfig::new(name, PhysicsState::Solid)
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config.materials.push(flammable("Thermite", 100));
+  config.materials.push(flammable("Inert", 0));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  // Burning alone stays far below the ignition point, and fire never jumps
+  // directly between neighbors, so only reaction heat can spread it
+  app.insert_resource(HeatConfig {
+    burning_heat: 1,
+    spread_rate: 0.0,
+    ..default()
+  });
+  app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+    1.0 / 60.0,
+  )));
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Paints a one-cell-tall strip of `material` at row `y` and lights its first
+/// cell.
+fn paint_lit_strip(world: &mut PixelWorld, y: i64, material: MaterialId) {
+  for dy in 0..CELL {
+    for x in 0..CELL * STRIP_CELLS {
+      let mut pixel = Pixel::new(material, ColorIndex(0));
+      if x < CELL {
+        pixel.flags.insert(PixelFlags::BURNING);
+      }
+      world.set_pixel(WorldPos::new(x, y + dy), pixel, DebugGizmos::none());
+    }
+  }
+  // Wake the heat tile containing the lit cell
+  world.set_heat_at(WorldPos::new(0, y), 1);
+}
+
+fn is_burning(world: &PixelWorld, cell: i64, y: i64) -> bool {
+  world
+    .get_pixel(WorldPos::new(cell * CELL, y))
+    .is_some_and(|p| p.flags.contains(PixelFlags::BURNING))
+}
+
+#[test]
+fn exothermic_reaction_starts_fire_chain() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  const THERMITE_ROW: i64 = 8;
+  const INERT_ROW: i64 = 200;
+
+  // Run heat propagation every physics tick
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    paint_lit_strip(&mut world, THERMITE_ROW, THERMITE);
+    paint_lit_strip(&mut world, INERT_ROW, INERT);
+  }
+
+  let mut chain_complete = false;
+  for _ in 0..300 {
+    app.update();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    let world = q.single(app.world()).unwrap();
+    if is_burning(world, STRIP_CELLS - 1, THERMITE_ROW) {
+      chain_complete = true;
+      break;
+    }
+  }
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  assert!(
+    chain_complete,
+    "Reaction heat should ignite the whole thermite strip"
+  );
+  assert!(
+    world
+      .get_heat_at(WorldPos::new(CELL, THERMITE_ROW))
+      .unwrap()
+      >= IGNITION,
+    "Cell next to the reaction should be above the ignition point"
+  );
+  assert!(
+    (1..STRIP_CELLS).all(|cell| !is_burning(world, cell, INERT_ROW)),
+    "Burning without reaction heat should not spread"
+  );
+  assert!(is_burning(world, 0, INERT_ROW));
+}
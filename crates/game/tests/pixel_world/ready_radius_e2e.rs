@@ -0,0 +1,154 @@
+//! E2E tests for `PixelWorldConfig::ready_radius`.
+//!
+//! Seeds the center chunk instantly and every other chunk slowly, then checks
+//! that `WorldReady` fires as soon as the center is seeded when a radius is
+//! set, and only after the whole window is seeded otherwise.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::ecs::message::Messages;
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, PersistenceConfig, Pixel, PixelWorld,
+  PixelWorldConfig, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldInitState, WorldReady,
+};
+use tempfile::TempDir;
+
+/// Streaming center for a camera at the origin.
+const CENTER: ChunkPos = ChunkPos::new(0, 0);
+/// Time taken to seed each chunk other than the center.
+const SLOW_SEED: Duration = Duration::from_millis(200);
+
+/// Seeds the center chunk instantly and every other chunk after a delay.
+struct SlowOuterSeeder;
+
+impl ChunkSeeder for SlowOuterSeeder {
+  fn seed(&self, pos: ChunkPos, chunk: &mut Chunk) {
+    if pos != CENTER {
+      std::thread::sleep(SLOW_SEED);
+    }
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, ready_radius: Option<u32>) -> App {
+  let mut app = App::new();
+  // Enough threads for two seeding tasks to run side by side
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(8),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  let config = PixelWorldConfig {
+    ready_radius,
+    ..Default::default()
+  };
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(SlowOuterSeeder).with_config(config));
+
+  app
+}
+
+/// Seeded and total chunk counts of the streaming window.
+fn seeded_window_chunks(app: &mut App) -> (usize, usize) {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  let positions: Vec<_> = world.visible_positions().collect();
+  let seeded = positions
+    .iter()
+    .filter(|pos| world.get_pixel(pos.to_world()).is_some())
+    .count();
+  (seeded, positions.len())
+}
+
+/// Runs until `WorldReady` fires and returns the window's seeded and total
+/// chunk counts at that frame.
+fn run_until_ready(app: &mut App) -> (usize, usize) {
+  let mut cursor = app.world().resource::<Messages<WorldReady>>().get_cursor();
+
+  let deadline = Instant::now() + Duration::from_secs(10);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+
+    let messages = app.world().resource::<Messages<WorldReady>>();
+    if cursor.read(messages).next().is_some() {
+      assert_eq!(
+        *app.world().resource::<WorldInitState>(),
+        WorldInitState::Ready
+      );
+      return seeded_window_chunks(app);
+    }
+  }
+  panic!("WorldReady did not fire within timeout");
+}
+
+fn run_until_window_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(10);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+
+    let (seeded, total) = seeded_window_chunks(app);
+    if seeded == total {
+      return;
+    }
+  }
+  panic!("Window was not seeded within timeout");
+}
+
+#[test]
+fn ready_fires_once_radius_is_seeded() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, Some(0));
+  let (seeded, total) = run_until_ready(&mut app);
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  assert_eq!(world.center(), CENTER);
+  assert!(
+    world.get_pixel(CENTER.to_world()).is_some(),
+    "Center chunk should be seeded when the world is ready"
+  );
+  assert!(
+    seeded < total,
+    "Ready should not wait for the whole window ({}/{} seeded)",
+    seeded,
+    total
+  );
+
+  // The rest of the window still streams in
+  run_until_window_seeded(&mut app);
+}
+
+#[test]
+fn ready_waits_for_window_without_radius() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, None);
+  let (seeded, total) = run_until_ready(&mut app);
+
+  assert_eq!(seeded, total, "Ready should wait for the whole window");
+}
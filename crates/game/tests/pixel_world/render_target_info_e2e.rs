@@ -0,0 +1,66 @@
+//! E2E tests for publishing the pixel camera's low-res target size.
+//!
+//! Runs the publish system against a pixel camera state sized like setup
+//! would size it and checks that `PixelRenderTargetInfo` reports the visible
+//! target resolution, tracking resizes.
+
+use bevy::prelude::*;
+use game::pixel_world::{
+  PixelCameraConfig, PixelCameraState, PixelRenderTargetInfo, pixel_camera_publish_target_info,
+};
+
+/// Visible low-res resolution.
+const LOGICAL: UVec2 = UVec2::new(480, 270);
+
+fn create_app(margin: u32) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins);
+  app.insert_resource(PixelCameraConfig {
+    margin,
+    ..default()
+  });
+  app.init_resource::<PixelCameraState>();
+  app.init_resource::<PixelRenderTargetInfo>();
+  app.add_systems(Update, pixel_camera_publish_target_info);
+  app
+}
+
+/// Sizes the target like the pixel camera setup: visible size plus margin.
+fn set_target(app: &mut App, logical: UVec2) {
+  let margin = app.world().resource::<PixelCameraConfig>().margin;
+  let mut state = app.world_mut().resource_mut::<PixelCameraState>();
+  state.target_size = logical + UVec2::splat(margin * 2);
+  state.initialized = true;
+}
+
+fn logical_size(app: &App) -> UVec2 {
+  app.world().resource::<PixelRenderTargetInfo>().logical_size
+}
+
+#[test]
+fn publishes_target_resolution() {
+  let mut app = create_app(2);
+  app.update();
+  assert_eq!(
+    logical_size(&app),
+    UVec2::ZERO,
+    "Nothing is published before the pixel camera is initialized"
+  );
+
+  set_target(&mut app, LOGICAL);
+  app.update();
+  assert_eq!(logical_size(&app), LOGICAL);
+}
+
+#[test]
+fn tracks_resize() {
+  let mut app = create_app(1);
+  set_target(&mut app, LOGICAL);
+  app.update();
+  assert_eq!(logical_size(&app), LOGICAL);
+
+  let resized = UVec2::new(640, 360);
+  set_target(&mut app, resized);
+  app.update();
+  assert_eq!(logical_size(&app), resized);
+}
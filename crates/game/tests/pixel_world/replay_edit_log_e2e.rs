@@ -0,0 +1,202 @@
+//! E2E tests for recording and replaying edit logs.
+//!
+//! A run blits into the world directly, like the brush, pans the camera and
+//! detonates a bomb while the world simulates. The saved log is replayed
+//! into a fresh world, which must end with exactly the same state hash as
+//! the recorded run.
+//!
+//! Run with:
+//!   cargo test -p game --test replay_edit_log_e2e
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Bomb, ColorIndex, EditLog, EditRecorder, EditRecorderPlugin, LoggedEdit,
+  MaterialSeeder, PersistenceConfig, Pixel, PixelBodiesPlugin, PixelWorld, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera, WorldEdit, WorldRect, debug_shim::DebugGizmos, material_ids,
+  replay,
+};
+use tempfile::TempDir;
+
+fn create_app(temp_dir: &TempDir, record: bool) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.add_plugins(PixelBodiesPlugin);
+  app.insert_resource(AsyncTaskBehavior::Block);
+  if record {
+    app.add_plugins(EditRecorderPlugin);
+  }
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(MaterialSeeder::new(42)));
+
+  app
+}
+
+fn run(app: &mut App, updates: usize) {
+  for _ in 0..updates {
+    app.update();
+  }
+}
+
+fn state_hash(app: &mut App) -> u64 {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  q.single(app.world()).unwrap().state_hash()
+}
+
+/// Runs the recorded session and returns its log and final state hash.
+fn record_session(temp_dir: &TempDir) -> (EditLog, u64) {
+  let mut app = create_app(temp_dir, true);
+  run(&mut app, 5);
+
+  // A block of sand in the air, left to fall
+  let sand = Pixel::new(material_ids::SAND, ColorIndex(0));
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  q.single_mut(app.world_mut()).unwrap().blit(
+    WorldRect::new(-20, 60, 40, 20),
+    |frag| (frag.u < 0.5 || frag.v < 0.5).then_some(sand),
+    DebugGizmos::none(),
+  );
+  run(&mut app, 10);
+
+  // Pan the camera, streaming in new chunks
+  let mut q = app
+    .world_mut()
+    .query_filtered::<&mut Transform, With<StreamingCamera>>();
+  q.single_mut(app.world_mut()).unwrap().translation.x += 300.0;
+  run(&mut app, 10);
+
+  // A bare bomb, triggered the moment it is spawned
+  let translation = Vec3::new(0.0, 40.0, 0.0);
+  app.world_mut().spawn((
+    Bomb {
+      damage_threshold: 0.5,
+      blast_radius: 24.0,
+      blast_strength: 500.0,
+      ignites: false,
+      thermal_radius: 0.0,
+      detonated: true,
+    },
+    Transform::from_translation(translation),
+    GlobalTransform::from_translation(translation),
+  ));
+  run(&mut app, 30);
+
+  let log = app.world().resource::<EditRecorder>().log().clone();
+  (log, state_hash(&mut app))
+}
+
+#[test]
+fn replayed_log_reproduces_final_state() {
+  let temp_dir = TempDir::new().unwrap();
+  let (log, recorded_hash) = record_session(&temp_dir);
+
+  assert_eq!(log.ticks, 55);
+  let edit_ticks: Vec<_> = log
+    .entries
+    .iter()
+    .filter(|entry| !matches!(entry.edit, WorldEdit::MoveCamera { .. }))
+    .map(|entry| entry.tick)
+    .collect();
+  assert_eq!(
+    edit_ticks,
+    vec![5, 25],
+    "Blit and detonation should be logged"
+  );
+  assert!(
+    log
+      .entries
+      .iter()
+      .any(|entry| entry.tick == 15 && matches!(entry.edit, WorldEdit::MoveCamera { .. })),
+    "Camera move should be logged"
+  );
+
+  // Persist and load back, as a bug report would
+  let path = temp_dir.path().join("session.edits.toml");
+  log.save(&path).unwrap();
+  let loaded = EditLog::load(&path).unwrap();
+  assert_eq!(loaded, log);
+
+  let replay_dir = TempDir::new().unwrap();
+  let mut app = create_app(&replay_dir, false);
+  replay(&loaded, &mut app);
+  assert_eq!(
+    state_hash(&mut app),
+    recorded_hash,
+    "Replay should end in the recorded state"
+  );
+
+  // Without the edits the world ends elsewhere
+  let plain_dir = TempDir::new().unwrap();
+  let mut app = create_app(&plain_dir, false);
+  replay(
+    &EditLog {
+      ticks: log.ticks,
+      entries: Vec::new(),
+    },
+    &mut app,
+  );
+  assert_ne!(state_hash(&mut app), recorded_hash);
+}
+
+#[test]
+fn blit_edit_encodes_written_pixels() {
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(3));
+  let edit = WorldEdit::blit(WorldRect::new(-2, 5, 3, 2), |frag| {
+    (frag.x != 0).then_some(stone)
+  });
+  let WorldEdit::Blit { pixels, .. } = &edit else {
+    panic!("Expected a blit");
+  };
+  assert_eq!(pixels.len(), 6);
+  assert_eq!(
+    pixels[0],
+    u32::from_le_bytes([material_ids::STONE.0, 3, 0, 0]) as i64
+  );
+  assert_eq!(pixels[2], -1, "x = 0 is left unchanged");
+}
+
+#[test]
+fn log_survives_save_and_load() {
+  let log = EditLog {
+    ticks: 12,
+    entries: vec![
+      LoggedEdit {
+        tick: 0,
+        edit: WorldEdit::MoveCamera {
+          position: [1.5, -2.0],
+        },
+      },
+      LoggedEdit {
+        tick: 3,
+        edit: WorldEdit::blit(WorldRect::new(0, 0, 2, 1), |_| Some(Pixel::VOID)),
+      },
+      LoggedEdit {
+        tick: 7,
+        edit: WorldEdit::spawn_body("crate.png", material_ids::WOOD, Vec2::new(4.0, 8.0)),
+      },
+    ],
+  };
+  let temp_dir = TempDir::new().unwrap();
+  let path = temp_dir.path().join("round_trip.edits.toml");
+  log.save(&path).unwrap();
+  assert_eq!(EditLog::load(&path).unwrap(), log);
+}
@@ -0,0 +1,213 @@
+//! E2E tests for the `WorldSave` change journal.
+//!
+//! Simulates crashes by dropping a save after unflushed edits and tests that:
+//! - Reopening replays the journal into the main file
+//! - A torn journal tail is ignored while earlier entries are kept
+//! - A journal from another generation of the main file is not replayed
+//! - Flushing truncates the journal
+//! - Repeated flushes reuse the space of superseded page tables
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use game::pixel_world::persistence::native::NativeFs;
+use game::pixel_world::{
+  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Pixel, WorldSave, material_ids,
+};
+use tempfile::TempDir;
+
+const SAVE_NAME: &str = "test.save";
+
+/// Fills chunks with void, so painted pixels are stored as deltas.
+struct NoopSeeder;
+
+impl ChunkSeeder for NoopSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn journal_path(temp_dir: &TempDir, save_name: &str) -> PathBuf {
+  temp_dir.path().join(format!("{}.journal", save_name))
+}
+
+/// Creates a chunk with a single pixel of `material` at (10, 10).
+fn painted_chunk(pos: ChunkPos, material: MaterialId) -> Chunk {
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  chunk.set_pos(pos);
+  NoopSeeder.seed(pos, &mut chunk);
+  chunk.pixels[(10, 10)] = Pixel::new(material, ColorIndex(3));
+  chunk
+}
+
+fn save_painted(save: &mut WorldSave, pos: ChunkPos, material: MaterialId) {
+  save
+    .save_chunk(&painted_chunk(pos, material), pos, &NoopSeeder)
+    .unwrap();
+}
+
+/// Loads a chunk and returns the material at (10, 10).
+fn painted_material(save: &WorldSave, pos: ChunkPos) -> Option<MaterialId> {
+  let loaded = save.load_chunk(pos, &NoopSeeder)?;
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  chunk.set_pos(pos);
+  NoopSeeder.seed(pos, &mut chunk);
+  loaded.apply_to(&mut chunk).ok()?;
+  Some(chunk.pixels[(10, 10)].material)
+}
+
+#[test]
+fn unflushed_edits_are_replayed_on_open() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+
+  {
+    let mut save = WorldSave::create(&fs, SAVE_NAME, 42).unwrap();
+    save_painted(&mut save, ChunkPos::new(0, 0), material_ids::STONE);
+    save.flush().unwrap();
+
+    // Edits after the last flush only reach the journal; dropping the save
+    // without flushing stands in for a crash
+    save_painted(&mut save, ChunkPos::new(0, 0), material_ids::SAND);
+    save_painted(&mut save, ChunkPos::new(1, 0), material_ids::WATER);
+  }
+
+  let save = WorldSave::open(&fs, SAVE_NAME).unwrap();
+  assert_eq!(save.chunk_count(), 2);
+  assert_eq!(
+    painted_material(&save, ChunkPos::new(0, 0)),
+    Some(material_ids::SAND),
+    "Overwritten chunk should hold the journaled data"
+  );
+  assert_eq!(
+    painted_material(&save, ChunkPos::new(1, 0)),
+    Some(material_ids::WATER),
+    "Chunk saved after the last flush should be recovered"
+  );
+  drop(save);
+
+  // Replay commits the edits, so a second open sees them without the journal
+  let save = WorldSave::open(&fs, SAVE_NAME).unwrap();
+  assert_eq!(save.chunk_count(), 2);
+  assert_eq!(
+    painted_material(&save, ChunkPos::new(1, 0)),
+    Some(material_ids::WATER)
+  );
+}
+
+#[test]
+fn torn_journal_tail_is_ignored() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+
+  {
+    let mut save = WorldSave::create(&fs, SAVE_NAME, 42).unwrap();
+    save.flush().unwrap();
+    save_painted(&mut save, ChunkPos::new(2, 3), material_ids::STONE);
+  }
+
+  // Half-written entry from a crash mid-append
+  let mut journal = OpenOptions::new()
+    .append(true)
+    .open(journal_path(&temp_dir, SAVE_NAME))
+    .unwrap();
+  journal.write_all(&[1, 200, 0, 0, 0, 7, 7]).unwrap();
+  drop(journal);
+
+  let save = WorldSave::open(&fs, SAVE_NAME).unwrap();
+  assert_eq!(save.chunk_count(), 1);
+  assert_eq!(
+    painted_material(&save, ChunkPos::new(2, 3)),
+    Some(material_ids::STONE)
+  );
+}
+
+#[test]
+fn journal_of_other_generation_is_not_replayed() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+
+  {
+    let mut save = WorldSave::create(&fs, "other.save", 42).unwrap();
+    save_painted(&mut save, ChunkPos::new(0, 0), material_ids::STONE);
+    save.flush().unwrap();
+    save_painted(&mut save, ChunkPos::new(1, 0), material_ids::SAND);
+  }
+  drop(WorldSave::create(&fs, SAVE_NAME, 42).unwrap());
+
+  // Journal written against a different main file
+  fs::copy(
+    journal_path(&temp_dir, "other.save"),
+    journal_path(&temp_dir, SAVE_NAME),
+  )
+  .unwrap();
+
+  let save = WorldSave::open(&fs, SAVE_NAME).unwrap();
+  assert_eq!(save.chunk_count(), 0);
+  assert!(!save.contains(ChunkPos::new(1, 0)));
+}
+
+#[test]
+fn flush_truncates_journal() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  let path = journal_path(&temp_dir, SAVE_NAME);
+
+  let mut save = WorldSave::create(&fs, SAVE_NAME, 42).unwrap();
+  let empty_len = fs::metadata(&path).unwrap().len();
+
+  save_painted(&mut save, ChunkPos::new(0, 0), material_ids::STONE);
+  save_painted(&mut save, ChunkPos::new(1, 0), material_ids::STONE);
+  assert!(fs::metadata(&path).unwrap().len() > empty_len);
+
+  save.flush().unwrap();
+  assert_eq!(fs::metadata(&path).unwrap().len(), empty_len);
+
+  // Further edits after the flush don't clobber the committed page table
+  save_painted(&mut save, ChunkPos::new(2, 0), material_ids::STONE);
+  drop(save);
+
+  let save = WorldSave::open(&fs, SAVE_NAME).unwrap();
+  assert_eq!(save.chunk_count(), 3);
+  for x in 0..3 {
+    assert_eq!(
+      painted_material(&save, ChunkPos::new(x, 0)),
+      Some(material_ids::STONE)
+    );
+  }
+}
+
+#[test]
+fn repeated_flushes_reuse_metadata_region() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  let pos = ChunkPos::new(0, 0);
+
+  let mut save = WorldSave::create(&fs, SAVE_NAME, 42).unwrap();
+  // The first two flushes append; there is no superseded page table yet
+  for _ in 0..2 {
+    save_painted(&mut save, pos, material_ids::STONE);
+    save.flush().unwrap();
+  }
+
+  for material in [material_ids::SAND, material_ids::STONE, material_ids::SAND] {
+    save_painted(&mut save, pos, material);
+    let data_end = save.data_write_pos();
+    save.flush().unwrap();
+    assert_eq!(
+      save.data_write_pos(),
+      data_end,
+      "Flush should reuse the superseded page table's region"
+    );
+  }
+  drop(save);
+
+  let save = WorldSave::open(&fs, SAVE_NAME).unwrap();
+  assert_eq!(save.chunk_count(), 1);
+  assert_eq!(painted_material(&save, pos), Some(material_ids::SAND));
+}
@@ -0,0 +1,127 @@
+//! Tests for `WorldSave` region queries.
+//!
+//! Saves a few scattered chunks, reopens the save and checks that
+//! `has_edits_in` and `edited_chunk_positions_in` report exactly the chunks
+//! overlapping each queried rect.
+
+use game::pixel_world::persistence::native::NativeFs;
+use game::pixel_world::{
+  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, Pixel, WorldRect, WorldSave, material_ids,
+};
+use tempfile::TempDir;
+
+const SAVE_NAME: &str = "test.save";
+const EDITED: [ChunkPos; 3] = [
+  ChunkPos::new(0, 0),
+  ChunkPos::new(3, -2),
+  ChunkPos::new(-5, 7),
+];
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Saves a chunk with one stone pixel at each position in `EDITED`, then
+/// reopens the save so queries run against the index read from disk.
+fn scattered_save(temp_dir: &TempDir) -> WorldSave {
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  let mut save = WorldSave::create(&fs, SAVE_NAME, 42).unwrap();
+
+  for pos in EDITED {
+    let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+    chunk.set_pos(pos);
+    VoidSeeder.seed(pos, &mut chunk);
+    chunk.pixels[(10, 10)] = Pixel::new(material_ids::STONE, ColorIndex(0));
+    save.save_chunk(&chunk, pos, &VoidSeeder).unwrap();
+  }
+  save.flush().unwrap();
+  drop(save);
+
+  WorldSave::open(&fs, SAVE_NAME).unwrap()
+}
+
+/// World rect covering exactly the chunk at `pos`.
+fn chunk_rect(pos: ChunkPos) -> WorldRect {
+  let origin = pos.to_world();
+  WorldRect::new(origin.x, origin.y, CHUNK_SIZE, CHUNK_SIZE)
+}
+
+fn sorted(mut positions: Vec<ChunkPos>) -> Vec<ChunkPos> {
+  positions.sort_by_key(|pos| (pos.x, pos.y));
+  positions
+}
+
+#[test]
+fn edited_chunks_are_found() {
+  let temp_dir = TempDir::new().unwrap();
+  let save = scattered_save(&temp_dir);
+
+  for pos in EDITED {
+    assert!(save.has_edits_in(chunk_rect(pos)), "{:?} has edits", pos);
+    assert_eq!(save.edited_chunk_positions_in(chunk_rect(pos)), vec![pos]);
+  }
+
+  // A single pixel inside an edited chunk is enough
+  let origin = ChunkPos::new(3, -2).to_world();
+  assert!(save.has_edits_in(WorldRect::new(origin.x + 400, origin.y + 7, 1, 1)));
+
+  // A rect spanning all of them finds every chunk
+  let all = WorldRect::new(
+    -5 * CHUNK_SIZE as i64,
+    -2 * CHUNK_SIZE as i64,
+    9 * CHUNK_SIZE,
+    10 * CHUNK_SIZE,
+  );
+  assert_eq!(
+    sorted(save.edited_chunk_positions_in(all)),
+    sorted(EDITED.to_vec())
+  );
+}
+
+#[test]
+fn untouched_regions_report_no_edits() {
+  let temp_dir = TempDir::new().unwrap();
+  let save = scattered_save(&temp_dir);
+
+  for pos in [
+    ChunkPos::new(1, 0),
+    ChunkPos::new(-1, 0),
+    ChunkPos::new(3, -1),
+    ChunkPos::new(100, 100),
+  ] {
+    assert!(
+      !save.has_edits_in(chunk_rect(pos)),
+      "{:?} has no edits",
+      pos
+    );
+    assert!(save.edited_chunk_positions_in(chunk_rect(pos)).is_empty());
+  }
+
+  // Rects touching an edited chunk's edge without overlapping it
+  let size = CHUNK_SIZE as i64;
+  assert!(!save.has_edits_in(WorldRect::new(size, 0, 16, 16)));
+  assert!(!save.has_edits_in(WorldRect::new(-16, -16, 16, 16)));
+  // One pixel further and they overlap chunk (0, 0)
+  assert!(save.has_edits_in(WorldRect::new(size - 1, 0, 16, 16)));
+  assert!(save.has_edits_in(WorldRect::new(-15, -15, 16, 16)));
+}
+
+#[test]
+fn empty_save_has_no_edits() {
+  let temp_dir = TempDir::new().unwrap();
+  let fs = NativeFs::new(temp_dir.path().to_path_buf()).unwrap();
+  let save = WorldSave::create(&fs, SAVE_NAME, 42).unwrap();
+
+  let everything = WorldRect::new(-1 << 20, -1 << 20, 1 << 21, 1 << 21);
+  assert!(!save.has_edits_in(everything));
+  assert!(save.edited_chunk_positions_in(everything).is_empty());
+}
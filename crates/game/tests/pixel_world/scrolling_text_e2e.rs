@@ -0,0 +1,218 @@
+//! E2E tests for marquee signs stamped into the world.
+//!
+//! Tests that a `ScrollingText` moves left by `speed * dt` each frame and
+//! wraps back to the right edge once it has scrolled out of its region, and
+//! that clearing the previous frame leaves other pixels of its material alone.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldPlugin, ScrollingText, SpawnPixelWorld, StreamingCamera, WorldPos,
+  WorldRect, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const REGION: WorldRect = WorldRect::new(0, 100, 200, 24);
+/// Pixels per second.
+const SPEED: f32 = 100.0;
+const FRAME: Duration = Duration::from_millis(100);
+/// Expected scroll per frame.
+const STEP: i64 = 10;
+
+/// Seeds every chunk with void so only the sign holds stone.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(REGION.x, REGION.y)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Returns the leftmost and rightmost columns holding sign pixels.
+fn stamped_columns(app: &mut App) -> Option<(i64, i64)> {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  let columns: Vec<i64> = (REGION.x..REGION.x + REGION.width as i64)
+    .filter(|&x| {
+      (REGION.y..REGION.y + REGION.height as i64).any(|y| {
+        world
+          .get_pixel(WorldPos::new(x, y))
+          .is_some_and(|p| p.material == material_ids::STONE)
+      })
+    })
+    .collect();
+  Some((*columns.first()?, *columns.last()?))
+}
+
+#[test]
+fn text_scrolls_left_and_wraps() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  app
+    .world_mut()
+    .spawn(ScrollingText::new("HELLO", material_ids::STONE, REGION).with_speed(SPEED));
+
+  let mut previous: Option<(i64, i64)> = None;
+  let mut steps = 0;
+  let mut wrapped = false;
+  // One period is at most (region + text) / STEP frames
+  for _ in 0..(2 * REGION.width as i64 / STEP) {
+    app.update();
+    let current = stamped_columns(&mut app);
+
+    if let (Some((prev_left, prev_right)), Some((left, right))) = (previous, current) {
+      if right > prev_right {
+        wrapped = true;
+        break;
+      }
+      // Both stamps unclipped by the region edges
+      let last_column = REGION.x + REGION.width as i64 - 1;
+      if prev_right < last_column && left > REGION.x {
+        assert_eq!(
+          prev_left - left,
+          STEP,
+          "Text should scroll {} px per frame",
+          STEP
+        );
+        assert_eq!(prev_right - right, STEP);
+        steps += 1;
+      }
+    }
+    previous = current;
+  }
+
+  assert!(
+    steps > 5,
+    "Text should be seen scrolling, got {} steps",
+    steps
+  );
+  assert!(wrapped, "Text should wrap back to the right edge");
+}
+
+#[test]
+fn previous_frame_is_cleared() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let sign = app
+    .world_mut()
+    .spawn(ScrollingText::new("HELLO", material_ids::STONE, REGION).with_speed(SPEED))
+    .id();
+  for _ in 0..10 {
+    app.update();
+  }
+  let (_, right) = stamped_columns(&mut app).expect("Sign should be stamped");
+
+  // Stop scrolling: the stamp must be identical, not smeared across frames
+  app
+    .world_mut()
+    .get_mut::<ScrollingText>(sign)
+    .unwrap()
+    .speed = 0.0;
+  app.update();
+  let before = stamped_columns(&mut app);
+  app.update();
+  assert_eq!(stamped_columns(&mut app), before);
+  assert!(before.unwrap().1 <= right);
+}
+
+#[test]
+fn terrain_of_the_sign_material_survives() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  // A stone ledge along the top row of the region, above the text
+  let top = REGION.y + REGION.height as i64 - 1;
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let world_entity = {
+    let mut q = app.world_mut().query::<(Entity, &mut PixelWorld)>();
+    let (entity, mut world) = q.single_mut(app.world_mut()).unwrap();
+    for x in REGION.x..REGION.x + REGION.width as i64 {
+      world.set_pixel(WorldPos::new(x, top), stone, DebugGizmos::none());
+    }
+    entity
+  };
+
+  app.world_mut().spawn(
+    ScrollingText::new("HELLO", material_ids::STONE, REGION)
+      .with_speed(SPEED)
+      .in_world(world_entity),
+  );
+  for _ in 0..10 {
+    app.update();
+  }
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  let ledge = (REGION.x..REGION.x + REGION.width as i64)
+    .filter(|&x| {
+      world
+        .get_pixel(WorldPos::new(x, top))
+        .is_some_and(|p| p.material == material_ids::STONE)
+    })
+    .count();
+  assert_eq!(
+    ledge, REGION.width as usize,
+    "Stone the sign did not stamp should not be cleared"
+  );
+}
@@ -0,0 +1,155 @@
+//! E2E tests for the seeding determinism check.
+//!
+//! Streams in a world with `SeedDeterminismCheck` enabled and checks that a
+//! seeder which depends on hidden state is reported through `SeedMismatch`,
+//! while a pure seeder is not.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::ecs::message::Messages;
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig,
+  Pixel, PixelWorld, PixelWorldPlugin, SeedDeterminismCheck, SeedMismatch, SpawnPixelWorld,
+  StreamingCamera, WorldPos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Fills every chunk with stone whose color depends only on the position.
+struct PureSeeder;
+
+impl ChunkSeeder for PureSeeder {
+  fn seed(&self, pos: ChunkPos, chunk: &mut Chunk) {
+    let color = ColorIndex((pos.x ^ pos.y) as u8);
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::new(material_ids::STONE, color);
+      }
+    }
+  }
+}
+
+/// Fills every chunk with stone colored by how many chunks it seeded before.
+#[derive(Default)]
+struct CountingSeeder {
+  calls: AtomicU8,
+}
+
+impl ChunkSeeder for CountingSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    let color = ColorIndex(self.calls.fetch_add(1, Ordering::Relaxed));
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::new(material_ids::STONE, color);
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, seeder: impl ChunkSeeder + 'static, check: bool) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(SeedDeterminismCheck { enabled: check });
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(seeder));
+
+  app
+}
+
+/// Runs until the chunks around the origin are seeded and returns every
+/// mismatch reported meanwhile.
+fn seed_and_collect_mismatches(app: &mut App) -> Vec<SeedMismatch> {
+  let mut cursor = app
+    .world()
+    .resource::<Messages<SeedMismatch>>()
+    .get_cursor();
+  let mut mismatches = Vec::new();
+
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+
+    let messages = app.world().resource::<Messages<SeedMismatch>>();
+    mismatches.extend(cursor.read(messages).cloned());
+
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    let seeded = q.single(app.world()).is_ok_and(|world| {
+      [WorldPos::new(0, 0), WorldPos::new(-1, -1)]
+        .iter()
+        .all(|&pos| world.get_pixel(pos).is_some())
+    });
+    if seeded {
+      return mismatches;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+#[test]
+fn non_deterministic_seeder_is_reported() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, CountingSeeder::default(), true);
+  let mismatches = seed_and_collect_mismatches(&mut app);
+
+  assert!(!mismatches.is_empty(), "Counting seeder should be reported");
+  let chunk_pixels = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+  for mismatch in &mismatches {
+    assert!(
+      mismatch.pixels > 0 && mismatch.pixels <= chunk_pixels,
+      "Mismatch at {:?} reports {} pixels",
+      mismatch.pos,
+      mismatch.pixels
+    );
+  }
+}
+
+#[test]
+fn deterministic_seeder_is_not_reported() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, PureSeeder, true);
+  let mismatches = seed_and_collect_mismatches(&mut app);
+
+  assert!(
+    mismatches.is_empty(),
+    "Pure seeder should not be reported: {:?}",
+    mismatches
+  );
+}
+
+#[test]
+fn check_is_off_when_disabled() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, CountingSeeder::default(), false);
+  let mismatches = seed_and_collect_mismatches(&mut app);
+
+  assert!(
+    mismatches.is_empty(),
+    "Disabled check should not report: {:?}",
+    mismatches
+  );
+}
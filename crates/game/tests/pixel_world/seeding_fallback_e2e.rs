@@ -0,0 +1,134 @@
+//! E2E tests for seeding while persistence is initializing.
+//!
+//! Tests:
+//! - Deferred seeding: persisted chunks are never procedurally seeded before
+//!   the save index loads
+//! - Fallback seeding: chunks seeded before the index loads are replaced by
+//!   their saved data once it is available
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, ColorIndex, MaterialSeeder, PersistenceConfig, PersistenceControl, Pixel,
+  PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldInitState, WorldPos,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const MARKER: WorldPos = WorldPos::new(64, 64);
+const MARKER_COLOR: ColorIndex = ColorIndex(77);
+
+fn create_app(config: PersistenceConfig) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(config));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(MaterialSeeder::new(42)));
+
+  app
+}
+
+fn marker_pixel(app: &mut App) -> Option<Pixel> {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  q.single(app.world())
+    .ok()
+    .and_then(|world| world.get_pixel(MARKER).copied())
+}
+
+fn is_marker(pixel: Option<Pixel>) -> bool {
+  pixel.is_some_and(|p| p.material == material_ids::STONE && p.color == MARKER_COLOR)
+}
+
+/// Runs updates until the predicate holds, or panics on timeout.
+fn run_until(app: &mut App, timeout: Duration, mut done: impl FnMut(&mut App) -> bool) {
+  let deadline = Instant::now() + timeout;
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if done(app) {
+      return;
+    }
+  }
+  panic!("Condition not met within {:?}", timeout);
+}
+
+/// Paints a marker in chunk (0, 0) and saves it.
+fn create_save(save_path: &Path) {
+  let mut app = create_app(PersistenceConfig::at(save_path));
+  run_until(&mut app, Duration::from_secs(5), |app| {
+    marker_pixel(app).is_some()
+  });
+
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    world.set_pixel(
+      MARKER,
+      Pixel::new(material_ids::STONE, MARKER_COLOR),
+      DebugGizmos::none(),
+    );
+  }
+
+  let handle = app.world_mut().resource_mut::<PersistenceControl>().save();
+  run_until(&mut app, Duration::from_secs(5), |_| handle.is_complete());
+}
+
+#[test]
+fn deferred_seeding_waits_for_save_index() {
+  let temp_dir = TempDir::new().unwrap();
+  let save_path = temp_dir.path().join("world.save");
+  create_save(&save_path);
+
+  let mut app = create_app(PersistenceConfig::at(&save_path).with_deferred_seeding(true));
+
+  run_until(&mut app, Duration::from_secs(5), |app| {
+    let initializing = *app.world().resource::<WorldInitState>() == WorldInitState::Initializing;
+    let pixel = marker_pixel(app);
+    assert!(
+      !initializing || pixel.is_none(),
+      "Persisted chunk was seeded before the save index loaded"
+    );
+    pixel.is_some()
+  });
+
+  assert!(
+    is_marker(marker_pixel(&mut app)),
+    "First seeded contents of a persisted chunk should come from the save"
+  );
+}
+
+#[test]
+fn fallback_seeding_is_replaced_by_saved_data() {
+  let temp_dir = TempDir::new().unwrap();
+  let save_path = temp_dir.path().join("world.save");
+  create_save(&save_path);
+
+  let mut app = create_app(PersistenceConfig::at(&save_path).with_deferred_seeding(false));
+
+  run_until(&mut app, Duration::from_secs(5), |app| {
+    *app.world().resource::<WorldInitState>() == WorldInitState::Ready
+      && is_marker(marker_pixel(app))
+  });
+}
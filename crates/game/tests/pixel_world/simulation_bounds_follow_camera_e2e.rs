@@ -0,0 +1,207 @@
+//! E2E tests for simulation bounds following the camera viewport.
+//!
+//! Tests that with `PixelWorldConfig::follow_camera_bounds` the world's
+//! simulation bounds track the streaming camera's orthographic viewport,
+//! grown by the configured margin, as the camera moves; that extra
+//! `SimulationViewport` cameras widen them; and that turning the flag off
+//! leaves manually set bounds alone.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, PersistenceConfig, Pixel, PixelWorld,
+  PixelWorldConfig, PixelWorldPlugin, SimulationViewport, SpawnPixelWorld, StreamingCamera,
+  WorldRect,
+};
+use tempfile::TempDir;
+
+const MARGIN: u32 = 16;
+/// Half extents of the camera viewport.
+const HALF_WIDTH: f32 = 100.0;
+const HALF_HEIGHT: f32 = 50.0;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Orthographic projection with its area already computed, since
+/// `MinimalPlugins` has no camera systems to compute it.
+fn projection(half_width: f32, half_height: f32) -> Projection {
+  Projection::Orthographic(OrthographicProjection {
+    area: Rect::new(-half_width, -half_height, half_width, half_height),
+    ..OrthographicProjection::default_2d()
+  })
+}
+
+/// Creates an app and returns it with its streaming camera.
+fn create_app(temp_dir: &TempDir, follow_camera_bounds: bool) -> (App, Entity) {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  let camera = app
+    .world_mut()
+    .spawn((
+      Transform::default(),
+      GlobalTransform::default(),
+      projection(HALF_WIDTH, HALF_HEIGHT),
+      StreamingCamera,
+    ))
+    .id();
+
+  let config = PixelWorldConfig {
+    follow_camera_bounds,
+    simulation_margin: MARGIN,
+    ..Default::default()
+  };
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder).with_config(config));
+
+  (app, camera)
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_for_world(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    let spawned = app
+      .world_mut()
+      .query::<&PixelWorld>()
+      .iter(app.world())
+      .next()
+      .is_some();
+    if spawned {
+      return;
+    }
+  }
+  panic!("World was not spawned within timeout");
+}
+
+fn move_camera(app: &mut App, camera: Entity, x: f32, y: f32) {
+  app
+    .world_mut()
+    .get_mut::<Transform>(camera)
+    .unwrap()
+    .translation = Vec3::new(x, y, 0.0);
+  // Global transforms propagate after the bounds are updated
+  app.update();
+  app.update();
+}
+
+/// Expected simulation bounds for a viewport spanning `min` to `max`.
+fn expected(min: Vec2, max: Vec2) -> WorldRect {
+  let margin = MARGIN as i64;
+  WorldRect::new(
+    min.x as i64 - margin,
+    min.y as i64 - margin,
+    (max.x - min.x) as u32 + MARGIN * 2,
+    (max.y - min.y) as u32 + MARGIN * 2,
+  )
+}
+
+/// Expected simulation bounds for the streaming camera at `(x, y)`.
+fn expected_at(x: f32, y: f32) -> WorldRect {
+  let half = Vec2::new(HALF_WIDTH, HALF_HEIGHT);
+  expected(Vec2::new(x, y) - half, Vec2::new(x, y) + half)
+}
+
+#[test]
+fn simulation_bounds_follow_camera_with_margin() {
+  let temp_dir = TempDir::new().unwrap();
+  let (mut app, camera) = create_app(&temp_dir, true);
+  wait_for_world(&mut app);
+  app.update();
+
+  let bounds = with_world(&mut app, |world| world.simulation_bounds());
+  assert_eq!(bounds, Some(expected_at(0.0, 0.0)));
+
+  for (x, y) in [(300.0, 0.0), (300.0, -200.0), (-1000.0, 500.0)] {
+    move_camera(&mut app, camera, x, y);
+    let bounds = with_world(&mut app, |world| world.simulation_bounds());
+    assert_eq!(
+      bounds,
+      Some(expected_at(x, y)),
+      "Bounds should follow the camera to ({x}, {y})"
+    );
+  }
+}
+
+#[test]
+fn simulation_viewports_widen_bounds() {
+  let temp_dir = TempDir::new().unwrap();
+  let (mut app, _camera) = create_app(&temp_dir, true);
+  wait_for_world(&mut app);
+
+  let transform = Transform::from_xyz(400.0, 300.0, 0.0);
+  app.world_mut().spawn((
+    transform,
+    GlobalTransform::from(transform),
+    projection(20.0, 20.0),
+    SimulationViewport,
+  ));
+  app.update();
+
+  let bounds = with_world(&mut app, |world| world.simulation_bounds());
+  assert_eq!(
+    bounds,
+    Some(expected(
+      Vec2::new(-HALF_WIDTH, -HALF_HEIGHT),
+      Vec2::new(420.0, 320.0)
+    )),
+    "Bounds should cover both viewports"
+  );
+}
+
+#[test]
+fn manual_bounds_are_kept_when_not_following_camera() {
+  let temp_dir = TempDir::new().unwrap();
+  let (mut app, camera) = create_app(&temp_dir, false);
+  wait_for_world(&mut app);
+  app.update();
+
+  assert_eq!(
+    with_world(&mut app, |world| world.simulation_bounds()),
+    None
+  );
+
+  let manual = WorldRect::new(-32, -32, 64, 64);
+  with_world(&mut app, |world| world.set_simulation_bounds(Some(manual)));
+  move_camera(&mut app, camera, 500.0, 500.0);
+
+  let bounds = with_world(&mut app, |world| world.simulation_bounds());
+  assert_eq!(
+    bounds,
+    Some(expected(Vec2::splat(-32.0), Vec2::splat(32.0)))
+  );
+}
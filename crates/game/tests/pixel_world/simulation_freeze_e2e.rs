@@ -0,0 +1,258 @@
+//! E2E tests for simulation freeze/thaw.
+//!
+//! Tests:
+//! - A frozen world does not simulate
+//! - A snapshot taken mid-fall, serialized and restored, resumes with exactly
+//!   the same pixel motion as the uninterrupted run
+//! - Edits made while frozen survive the thaw, and edited pixels wake up
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldPlugin, SimulationSnapshot, SpawnPixelWorld, StreamingCamera, WorldPos,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Region containing the falling material and its landing site.
+const REGION: (i64, i64, i64, i64) = (0, 0, 96, 256);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+/// Paints a stone floor with a block of sand and water suspended above it.
+fn paint_scene(world: &mut PixelWorld) {
+  let (x0, y0, x1, _) = REGION;
+  for x in x0..x1 {
+    for y in y0..y0 + 4 {
+      world.set_pixel(
+        WorldPos::new(x, y),
+        Pixel::new(material_ids::STONE, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+  }
+  for x in 24..72 {
+    for y in 160..220 {
+      let material = if x < 48 {
+        material_ids::SAND
+      } else {
+        material_ids::WATER
+      };
+      let pos = WorldPos::new(x, y);
+      world.set_pixel(
+        pos,
+        Pixel::new(material, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+      world.mark_pixel_sim_dirty(pos);
+    }
+  }
+}
+
+fn capture(world: &PixelWorld) -> Vec<Pixel> {
+  let (x0, y0, x1, y1) = REGION;
+  let mut out = Vec::new();
+  for y in y0..y1 {
+    for x in x0..x1 {
+      out.push(*world.get_pixel(WorldPos::new(x, y)).unwrap());
+    }
+  }
+  out
+}
+
+fn run(app: &mut App, frames: usize) {
+  for _ in 0..frames {
+    app.update();
+  }
+}
+
+fn roundtrip(snapshot: &SimulationSnapshot) -> SimulationSnapshot {
+  let mut bytes = Vec::new();
+  snapshot.write_to(&mut bytes).unwrap();
+  SimulationSnapshot::read_from(&mut bytes.as_slice()).unwrap()
+}
+
+#[test]
+fn frozen_world_does_not_simulate() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  with_world(&mut app, paint_scene);
+  run(&mut app, 5);
+
+  let snapshot = with_world(&mut app, |world| world.freeze());
+  let (before, tick) = with_world(&mut app, |world| (capture(world), world.tick()));
+  run(&mut app, 20);
+  let (after, tick_after) = with_world(&mut app, |world| (capture(world), world.tick()));
+
+  assert_eq!(tick, tick_after, "Tick should not advance while frozen");
+  assert!(before == after, "Pixels should not move while frozen");
+
+  with_world(&mut app, |world| world.thaw(&snapshot));
+  run(&mut app, 5);
+  let moved = with_world(&mut app, |world| capture(world));
+  assert!(before != moved, "Simulation should resume after thaw");
+}
+
+#[test]
+fn thawed_snapshot_resumes_mid_fall_exactly() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  with_world(&mut app, paint_scene);
+  run(&mut app, 10);
+
+  // Freeze mid-fall and keep only the serialized form
+  let snapshot = with_world(&mut app, |world| world.freeze());
+  let mid_fall = with_world(&mut app, |world| capture(world));
+  let restored = roundtrip(&snapshot);
+  assert_eq!(restored.tick(), snapshot.tick());
+
+  // Reference run: resume straight from the freeze
+  let restored_chunks = with_world(&mut app, |world| world.thaw(&snapshot));
+  assert!(restored_chunks > 0);
+  run(&mut app, 30);
+  let reference = with_world(&mut app, |world| capture(world));
+  assert!(
+    reference != mid_fall,
+    "Material should still have been falling at the freeze"
+  );
+
+  // Clobber the world, then restore from the deserialized snapshot
+  with_world(&mut app, |world| {
+    world.freeze();
+    let (x0, y0, x1, y1) = REGION;
+    for y in y0..y1 {
+      for x in x0..x1 {
+        world.set_pixel(WorldPos::new(x, y), Pixel::VOID, DebugGizmos::none());
+      }
+    }
+    world.restore(&restored);
+  });
+  assert!(
+    with_world(&mut app, |world| capture(world)) == mid_fall,
+    "Restore should write back the mid-fall pixels"
+  );
+
+  run(&mut app, 30);
+  let resumed = with_world(&mut app, |world| capture(world));
+  assert!(
+    resumed == reference,
+    "Restored simulation should continue exactly like the uninterrupted one"
+  );
+}
+
+#[test]
+fn edits_made_while_frozen_survive_thaw() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  with_world(&mut app, paint_scene);
+  run(&mut app, 10);
+
+  let snapshot = with_world(&mut app, |world| world.freeze());
+
+  // A stone ledge and a sand block painted into empty air while frozen
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let sand = Pixel::new(material_ids::SAND, ColorIndex(0));
+  with_world(&mut app, |world| {
+    for x in 76..92 {
+      world.set_pixel(WorldPos::new(x, 100), stone, DebugGizmos::none());
+    }
+    for y in 110..114 {
+      for x in 80..84 {
+        world.set_pixel(WorldPos::new(x, y), sand, DebugGizmos::none());
+      }
+    }
+    assert!(world.thaw(&snapshot) > 0);
+    assert!(world.simulate(), "Thaw should resume simulation");
+  });
+
+  run(&mut app, 30);
+  with_world(&mut app, |world| {
+    for x in 76..92 {
+      assert_eq!(
+        world.get_pixel(WorldPos::new(x, 100)).unwrap().material,
+        material_ids::STONE,
+        "Ledge painted while frozen should survive the thaw"
+      );
+    }
+    let sand_on_ledge = (76..92)
+      .filter(|&x| world.get_pixel(WorldPos::new(x, 101)).unwrap().material == material_ids::SAND)
+      .count();
+    assert!(
+      sand_on_ledge > 0,
+      "Sand painted while frozen should fall onto the ledge"
+    );
+  });
+}
@@ -0,0 +1,239 @@
+//! E2E tests for gameplay hooks between CA tick steps.
+//!
+//! Registers systems before the CA tick, in the `CATickStep::AfterPhysics`
+//! slot and after the CA tick, then checks that the hook sees this tick's
+//! physics (a falling sand pixel has moved) but not its burning (a burning
+//! wood pixel has not turned to ash yet).
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, CATickStep, Chunk, ChunkPos, ChunkSeeder, ColorIndex, HeatConfig, MaterialId,
+  PersistenceConfig, Pixel, PixelFlags, PixelSimulation, PixelWorld, PixelWorldPlugin,
+  SimulationConfig, SimulationPhase, SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const SAND_X: i64 = 0;
+const SAND_Y: i64 = 40;
+const WOOD_POS: WorldPos = WorldPos::new(20, 0);
+
+/// Seeds every chunk with void; the scene is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// What a system saw of the scene.
+#[derive(Clone, Copy, Debug)]
+struct Observation {
+  /// Height of the sand pixel in its column.
+  sand_y: Option<i64>,
+  /// Material under the wood pixel.
+  wood: MaterialId,
+}
+
+/// Observations of one simulation tick.
+#[derive(Clone, Copy, Debug)]
+struct TickObservations {
+  before: Observation,
+  after_physics: Observation,
+  after: Observation,
+}
+
+#[derive(Resource, Default)]
+struct StepLog {
+  /// Observations of the tick in progress.
+  pending: Option<(Observation, Option<Observation>)>,
+  ticks: Vec<TickObservations>,
+}
+
+fn observe(world: &PixelWorld) -> Observation {
+  let sand_y = (0..=SAND_Y).find(|&y| {
+    world
+      .get_pixel(WorldPos::new(SAND_X, y))
+      .is_some_and(|p| p.material == material_ids::SAND)
+  });
+  let wood = world
+    .get_pixel(WOOD_POS)
+    .map_or(material_ids::VOID, |p| p.material);
+  Observation { sand_y, wood }
+}
+
+fn observe_before(worlds: Query<&PixelWorld>, mut log: ResMut<StepLog>) {
+  let Ok(world) = worlds.single() else {
+    return;
+  };
+  log.pending = Some((observe(world), None));
+}
+
+fn observe_after_physics(worlds: Query<&PixelWorld>, mut log: ResMut<StepLog>) {
+  let Ok(world) = worlds.single() else {
+    return;
+  };
+  if let Some((_, after_physics)) = log.pending.as_mut() {
+    *after_physics = Some(observe(world));
+  }
+}
+
+fn observe_after(worlds: Query<&PixelWorld>, mut log: ResMut<StepLog>) {
+  let Ok(world) = worlds.single() else {
+    return;
+  };
+  // Ticks where the steps didn't run (world not ready yet) are dropped
+  if let Some((before, Some(after_physics))) = log.pending.take() {
+    log.ticks.push(TickObservations {
+      before,
+      after_physics,
+      after: observe(world),
+    });
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  // Burn every tick, and turn burning pixels to ash on their first burn
+  app.insert_resource(SimulationConfig {
+    burning_tps: 60.0,
+    ..default()
+  });
+  app.insert_resource(HeatConfig {
+    burn_duration_secs: 0.001,
+    ..default()
+  });
+
+  app.init_resource::<StepLog>();
+  app.add_systems(
+    PixelSimulation,
+    (
+      observe_before
+        .in_set(SimulationPhase::CATick)
+        .before(CATickStep::Physics),
+      observe_after_physics.in_set(CATickStep::AfterPhysics),
+      observe_after.in_set(SimulationPhase::AfterCATick),
+    ),
+  );
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+/// Runs updates until the world is seeded and simulating.
+fn wait_until_simulating(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if !app.world().resource::<StepLog>().ticks.is_empty() {
+      return;
+    }
+  }
+  panic!("World was not simulating within timeout");
+}
+
+/// Paints `pixel` over `rect`, marking it for simulation.
+fn paint(app: &mut App, rect: WorldRect, pixel: Pixel) {
+  with_world(app, |world| {
+    world.blit(rect, |_| Some(pixel), DebugGizmos::none());
+  });
+}
+
+#[test]
+fn after_physics_hook_sees_physics_but_not_burning() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_simulating(&mut app);
+
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  paint(&mut app, WorldRect::new(SAND_X - 1, -1, 3, 1), stone);
+  paint(
+    &mut app,
+    WorldRect::new(SAND_X, SAND_Y, 1, 1),
+    Pixel::new(material_ids::SAND, ColorIndex(0)),
+  );
+  let mut wood = Pixel::new(material_ids::WOOD, ColorIndex(0));
+  wood.flags.insert(PixelFlags::BURNING);
+  paint(&mut app, WorldRect::new(WOOD_POS.x, WOOD_POS.y, 1, 1), wood);
+  app.world_mut().resource_mut::<StepLog>().ticks.clear();
+
+  for _ in 0..120 {
+    app.update();
+  }
+
+  let ticks = app.world().resource::<StepLog>().ticks.clone();
+  assert!(
+    !ticks.is_empty(),
+    "Simulation ticks should have been logged"
+  );
+
+  for tick in &ticks {
+    assert_eq!(
+      tick.after_physics.sand_y, tick.after.sand_y,
+      "Sand only moves in the physics step: {:?}",
+      tick
+    );
+    assert_eq!(
+      tick.after_physics.wood, tick.before.wood,
+      "Hook must run before burning: {:?}",
+      tick
+    );
+  }
+
+  assert!(
+    ticks
+      .iter()
+      .any(|t| t.after_physics.sand_y < t.before.sand_y),
+    "Hook should observe sand moved by this tick's physics"
+  );
+  assert!(
+    ticks
+      .iter()
+      .any(|t| t.after_physics.wood == material_ids::WOOD && t.after.wood == material_ids::ASH),
+    "Wood should burn to ash after the hook within one tick"
+  );
+  assert_eq!(
+    ticks.last().unwrap().after.sand_y,
+    Some(0),
+    "Sand should land on the floor"
+  );
+}
@@ -0,0 +1,163 @@
+//! E2E tests for dedicated simulation thread pools.
+//!
+//! Runs the same scene of falling sand and water with a single simulation
+//! thread and with several, and checks that the simulation makes progress
+//! and that both produce identical pixels.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, PersistenceConfig,
+  Pixel, PixelWorld, PixelWorldConfig, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera,
+  WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Region holding the scene, spanning several tiles and a chunk border.
+const REGION_X: (i64, i64) = (-96, 96);
+const REGION_Y: (i64, i64) = (-32, 160);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, simulation_threads: Option<usize>) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  let config = PixelWorldConfig {
+    simulation_threads,
+    ..Default::default()
+  };
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder).with_config(config));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q.single(app.world()).is_ok_and(|world| {
+      [
+        WorldPos::new(REGION_X.0, REGION_Y.0),
+        WorldPos::new(REGION_X.1, REGION_Y.1),
+      ]
+      .iter()
+      .all(|&pos| world.get_pixel(pos).is_some())
+    }) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn fill(world: &mut PixelWorld, x: (i64, i64), y: (i64, i64), material: MaterialId) {
+  for py in y.0..y.1 {
+    for px in x.0..x.1 {
+      world.set_pixel(
+        WorldPos::new(px, py),
+        Pixel::new(material, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+      world.mark_pixel_sim_dirty(WorldPos::new(px, py));
+    }
+  }
+}
+
+/// Builds the scene and runs it for `ticks`, returning the region's pixels.
+fn simulate(simulation_threads: Option<usize>, ticks: usize) -> Vec<Pixel> {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, simulation_threads);
+  wait_until_seeded(&mut app);
+
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    fill(&mut world, REGION_X, (REGION_Y.0, 0), material_ids::STONE);
+    fill(&mut world, (-64, -8), (40, 120), material_ids::SAND);
+    fill(&mut world, (8, 64), (40, 120), material_ids::WATER);
+  }
+
+  for _ in 0..ticks {
+    app.update();
+  }
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  (REGION_Y.0..REGION_Y.1)
+    .flat_map(|y| (REGION_X.0..REGION_X.1).map(move |x| WorldPos::new(x, y)))
+    .map(|pos| *world.get_pixel(pos).unwrap())
+    .collect()
+}
+
+/// Returns the number of sand pixels resting on the floor row.
+fn sand_on_floor(pixels: &[Pixel]) -> usize {
+  let width = (REGION_X.1 - REGION_X.0) as usize;
+  let floor_row = (-REGION_Y.0) as usize;
+  pixels[floor_row * width..(floor_row + 1) * width]
+    .iter()
+    .filter(|p| p.material == material_ids::SAND)
+    .count()
+}
+
+#[test]
+fn single_thread_pool_simulates() {
+  let pixels = simulate(Some(1), 120);
+  assert!(
+    sand_on_floor(&pixels) > 0,
+    "Sand should fall to the floor with one simulation thread"
+  );
+}
+
+#[test]
+fn single_thread_matches_multi_threaded() {
+  let serial = simulate(Some(1), 120);
+  let parallel = simulate(Some(4), 120);
+  assert!(sand_on_floor(&parallel) > 0);
+
+  let mismatches = serial
+    .iter()
+    .zip(&parallel)
+    .filter(|(a, b)| a.material != b.material)
+    .count();
+  assert_eq!(
+    mismatches, 0,
+    "Serial and parallel simulation should produce the same pixels"
+  );
+}
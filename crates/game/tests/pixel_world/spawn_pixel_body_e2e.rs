@@ -442,6 +442,10 @@ fn pending_pixel_body_finalization_creates_physics_body() {
     image: image_handle,
     material: material_ids::WOOD,
     position: Vec2::new(0.0, 100.0),
+    search_rect: None,
+    world: None,
+    flip_x: false,
+    flip_y: false,
   });
 
   // Run frames to let finalization system process the pending body
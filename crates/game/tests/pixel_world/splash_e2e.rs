@@ -0,0 +1,261 @@
+//! E2E tests for liquid splashes from fast impacts.
+//!
+//! Drops a pixel body into a stone basin of water and checks that a fast
+//! impact throws water above the surface, which then falls back, while a
+//! body lowered gently does not splash.
+
+use std::path::Path;
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::asset::RenderAssetUsages;
+use bevy::ecs::message::{MessageCursor, Messages};
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use game::pixel_world::buoyancy::Buoyancy2dPlugin;
+use game::pixel_world::debug_shim::DebugGizmos;
+use game::pixel_world::pixel_awareness::PixelAwarenessPlugin;
+use game::pixel_world::{
+  Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel, PixelBodiesPlugin, PixelBody,
+  PixelFlags, PixelWorld, PixelWorldPlugin, SpawnPixelBodyFromImage, SpawnPixelWorld, SplashConfig,
+  Splashed, StreamingCamera, WorldPos, WorldRect, material_ids,
+};
+use tempfile::TempDir;
+
+/// Water inside the basin; its top row is the surface.
+const POOL: WorldRect = WorldRect::new(-40, -60, 81, 40);
+/// Topmost row of water.
+const SURFACE_Y: i64 = POOL.y + POOL.height as i64 - 1;
+/// Height above the surface that displacement alone never pushes water to.
+const SPLASH_CLEARANCE: i64 = 3;
+
+/// Seeds every chunk with void; the scene is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+struct TestHarness {
+  app: App,
+  test_image: Handle<Image>,
+  splashed_cursor: MessageCursor<Splashed>,
+}
+
+impl TestHarness {
+  fn new(save_path: &Path) -> Self {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+      task_pool_options: TaskPoolOptions::with_num_threads(4),
+    }));
+
+    app.add_plugins(bevy::transform::TransformPlugin);
+    app.add_plugins(bevy::asset::AssetPlugin::default());
+    app.add_plugins(bevy::image::ImagePlugin::default());
+    app.add_plugins(bevy::scene::ScenePlugin);
+
+    app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(save_path)));
+    app.add_plugins(PixelBodiesPlugin);
+    app.add_plugins(
+      bevy_rapier2d::prelude::RapierPhysicsPlugin::<bevy_rapier2d::prelude::NoUserData>::default()
+        .with_length_unit(50.0),
+    );
+    app.add_plugins(PixelAwarenessPlugin::default());
+    // Frame times vary in tests, so keep the threshold well below the drop
+    // speed and well above what gravity reaches over a few pixels
+    app.add_plugins(Buoyancy2dPlugin::default().with_splash(SplashConfig {
+      min_impact_speed: 100.0,
+      ..Default::default()
+    }));
+
+    let test_image = create_test_image(&mut app);
+
+    app.world_mut().spawn((
+      Transform::default(),
+      GlobalTransform::default(),
+      StreamingCamera,
+    ));
+
+    app
+      .world_mut()
+      .commands()
+      .queue(SpawnPixelWorld::new(VoidSeeder));
+
+    // Run enough frames for initial chunk seeding
+    for _ in 0..20 {
+      app.update();
+    }
+
+    Self {
+      app,
+      test_image,
+      splashed_cursor: MessageCursor::default(),
+    }
+  }
+
+  fn run(&mut self, updates: usize) {
+    for _ in 0..updates {
+      self.app.update();
+    }
+  }
+
+  fn with_world<R>(&mut self, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+    let mut q = self.app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(self.app.world_mut()).unwrap();
+    f(&mut world)
+  }
+
+  /// Paints a stone basin around `POOL` and fills it with water.
+  fn paint_pool(&mut self) {
+    let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+    let water = Pixel::new(material_ids::WATER, ColorIndex(0));
+    let (x, y, w, h) = (POOL.x, POOL.y, POOL.width, POOL.height);
+    let walls = [
+      WorldRect::new(x - 4, y - 4, w + 8, 4),
+      WorldRect::new(x - 4, y, 4, h + 40),
+      WorldRect::new(x + w as i64, y, 4, h + 40),
+    ];
+    self.with_world(|world| {
+      for wall in walls {
+        world.blit(wall, |_| Some(stone), DebugGizmos::none());
+      }
+      world.blit(POOL, |_| Some(water), DebugGizmos::none());
+    });
+    self.run(1);
+  }
+
+  /// Spawns the test body with its center at `position`, moving at
+  /// `velocity`.
+  fn spawn_body(&mut self, position: Vec2, velocity: Vec2) {
+    let image = self.test_image.clone();
+    self
+      .app
+      .world_mut()
+      .commands()
+      .queue(SpawnPixelBodyFromImage::new(
+        image,
+        material_ids::WOOD,
+        position,
+      ));
+
+    // Let the pending body finalize
+    let mut q = self
+      .app
+      .world_mut()
+      .query_filtered::<Entity, With<PixelBody>>();
+    let mut entity = None;
+    for _ in 0..10 {
+      self.app.update();
+      entity = q.iter(self.app.world()).next();
+      if entity.is_some() {
+        break;
+      }
+    }
+    let entity = entity.expect("Body should exist after spawning");
+    self
+      .app
+      .world_mut()
+      .entity_mut(entity)
+      .insert(Velocity::linear(velocity));
+  }
+
+  /// Returns the number of water pixels that are not part of a body, at
+  /// least `SPLASH_CLEARANCE` above the surface.
+  fn airborne_water(&mut self) -> usize {
+    self.with_world(|world| {
+      let mut count = 0;
+      for y in SURFACE_Y + SPLASH_CLEARANCE..SURFACE_Y + 40 {
+        for x in POOL.x..POOL.x + POOL.width as i64 {
+          if world.get_pixel(WorldPos::new(x, y)).is_some_and(|p| {
+            p.material == material_ids::WATER && !p.flags.contains(PixelFlags::PIXEL_BODY)
+          }) {
+            count += 1;
+          }
+        }
+      }
+      count
+    })
+  }
+
+  fn read_splashes(&mut self) -> Vec<Splashed> {
+    let messages = self.app.world().resource::<Messages<Splashed>>();
+    self.splashed_cursor.read(messages).cloned().collect()
+  }
+}
+
+/// Creates an 8x8 RGBA test image with all white pixels.
+fn create_test_image(app: &mut App) -> Handle<Image> {
+  let mut image = Image::new_fill(
+    bevy::render::render_resource::Extent3d {
+      width: 8,
+      height: 8,
+      depth_or_array_layers: 1,
+    },
+    bevy::render::render_resource::TextureDimension::D2,
+    &[255, 255, 255, 255],
+    bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
+    RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+  );
+  image.sampler = ImageSampler::nearest();
+
+  let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+  images.add(image)
+}
+
+#[test]
+fn fast_impact_throws_water_above_surface() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("splash_test.save"));
+  harness.paint_pool();
+  assert_eq!(harness.airborne_water(), 0, "Pool starts calm");
+
+  harness.spawn_body(
+    Vec2::new(0.0, SURFACE_Y as f32 + 30.0),
+    Vec2::new(0.0, -1200.0),
+  );
+
+  let mut splashes = Vec::new();
+  let mut peak_airborne = 0;
+  for _ in 0..60 {
+    harness.run(1);
+    splashes.extend(harness.read_splashes());
+    peak_airborne = peak_airborne.max(harness.airborne_water());
+  }
+
+  assert_eq!(splashes.len(), 1, "One splash per impact: {:?}", splashes);
+  assert!(splashes[0].droplets > 0);
+  assert!(
+    splashes[0].speed >= 100.0,
+    "Splash speed {} is below the threshold",
+    splashes[0].speed
+  );
+  assert!(
+    peak_airborne > 0,
+    "Water should be thrown above the surface"
+  );
+
+  // The droplets fall back into the pool
+  harness.run(600);
+  assert_eq!(harness.airborne_water(), 0, "Droplets should fall back");
+}
+
+#[test]
+fn gentle_entry_does_not_splash() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut harness = TestHarness::new(&temp_dir.path().join("splash_test.save"));
+  harness.paint_pool();
+
+  // Bottom edge two pixels above the surface, at rest
+  harness.spawn_body(Vec2::new(0.0, SURFACE_Y as f32 + 7.0), Vec2::ZERO);
+
+  for _ in 0..60 {
+    harness.run(1);
+    assert_eq!(harness.read_splashes().len(), 0, "Gentle entry splashed");
+  }
+}
@@ -0,0 +1,277 @@
+//! E2E tests for absorbent materials.
+//!
+//! A sponge block submerged in a basin of water must soak up water until
+//! every pixel of it is saturated. Moved into dry air above a catch basin,
+//! it must drip all of it back out over the following ticks. The water in
+//! the world plus the water stored in the sponge stays constant throughout.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::{AbsorbentConfig, MaterialConfig};
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelWorld, PixelWorldPlugin,
+  SimulationConfig, SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+const SPONGE: MaterialId = MaterialId(7);
+const CAPACITY: u8 = 8;
+/// Region holding every pixel of water in the tests.
+const REGION: (WorldPos, WorldPos) = (WorldPos::new(-40, -10), WorldPos::new(70, 50));
+/// Interior of the water basin: x range and water height.
+const BASIN: (i64, i64, i64) = (-20, 20, 16);
+/// Sponge block size and its corner while submerged.
+const SPONGE_SIZE: i64 = 4;
+const SUBMERGED: WorldPos = WorldPos::new(-2, 4);
+/// Sponge corner once moved into dry air, above the catch basin.
+const HUNG: WorldPos = WorldPos::new(43, 30);
+/// Interior of the catch basin: x range and floor height.
+const CATCH: (i64, i64, i64) = (30, 60, 10);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn sponge() -> MaterialConfig {
+  MaterialConfig {
+    density: 60,
+    absorbent: Some(AbsorbentConfig {
+      liquid: "Water".to_string(),
+      capacity: CAPACITY,
+      absorb_rate: 60.0,
+      release_rate: 30.0,
+    }),
+    ..MaterialConfig::new("Sponge", PhysicsState::Solid)
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config.materials.push(sponge());
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+/// Creates a seeded app whose absorption pass runs every physics tick.
+fn setup() -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  let deadline = Instant::now() + Duration::from_secs(5);
+  loop {
+    assert!(Instant::now() < deadline, "World was not seeded");
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(&mut app, |world| {
+      world.get_pixel(REGION.0).is_some() && world.get_pixel(REGION.1).is_some()
+    });
+    if seeded {
+      break;
+    }
+  }
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+  (temp_dir, app)
+}
+
+fn fill(world: &mut PixelWorld, xs: std::ops::Range<i64>, ys: std::ops::Range<i64>, pixel: Pixel) {
+  for y in ys {
+    for x in xs.clone() {
+      world.set_pixel(WorldPos::new(x, y), pixel, DebugGizmos::none());
+    }
+  }
+}
+
+/// Builds both basins and submerges the sponge block in the water.
+fn build_scene(app: &mut App) {
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let water = Pixel::new(material_ids::WATER, ColorIndex(0));
+  with_world(app, |world| {
+    let (left, right, height) = BASIN;
+    fill(world, left - 1..right + 1, -1..0, stone);
+    fill(world, left - 1..left, 0..height + 4, stone);
+    fill(world, right..right + 1, 0..height + 4, stone);
+    fill(world, left..right, 0..height, water);
+    fill(
+      world,
+      SUBMERGED.x..SUBMERGED.x + SPONGE_SIZE,
+      SUBMERGED.y..SUBMERGED.y + SPONGE_SIZE,
+      Pixel::new(SPONGE, ColorIndex(0)),
+    );
+
+    let (left, right, floor) = CATCH;
+    fill(world, left - 1..right + 1, floor - 1..floor, stone);
+    fill(world, left - 1..left, floor..floor + 8, stone);
+    fill(world, right..right + 1, floor..floor + 8, stone);
+  });
+}
+
+/// Returns the saturation of every sponge pixel with its corner at
+/// `corner`.
+fn sponge_saturations(app: &mut App, corner: WorldPos) -> Vec<u8> {
+  with_world(app, |world| {
+    let mut saturations = Vec::new();
+    for y in corner.y..corner.y + SPONGE_SIZE {
+      for x in corner.x..corner.x + SPONGE_SIZE {
+        let pixel = world.get_pixel(WorldPos::new(x, y)).unwrap();
+        assert_eq!(pixel.material, SPONGE, "Sponge at ({x}, {y}) moved");
+        saturations.push(pixel.saturation());
+      }
+    }
+    saturations
+  })
+}
+
+/// Counts water pixels plus water stored in sponge pixels.
+fn total_water(app: &mut App) -> u32 {
+  with_world(app, |world| {
+    let mut total = 0;
+    for y in REGION.0.y..REGION.1.y {
+      for x in REGION.0.x..REGION.1.x {
+        let pixel = world.get_pixel(WorldPos::new(x, y)).unwrap();
+        if pixel.material == material_ids::WATER {
+          total += 1;
+        } else if pixel.material == SPONGE {
+          total += pixel.saturation() as u32;
+        }
+      }
+    }
+    total
+  })
+}
+
+/// Runs updates until `done` holds, checking that water is conserved.
+fn run_until(app: &mut App, water: u32, mut done: impl FnMut(&mut App) -> bool) {
+  for _ in 0..2000 {
+    app.update();
+    assert_eq!(total_water(app), water, "Water should be conserved");
+    if done(app) {
+      return;
+    }
+  }
+  panic!("Condition not met within 2000 ticks");
+}
+
+/// Moves the sponge block from `from` to `to`, leaving void behind.
+fn move_sponge(app: &mut App, from: WorldPos, to: WorldPos) {
+  with_world(app, |world| {
+    for dy in 0..SPONGE_SIZE {
+      for dx in 0..SPONGE_SIZE {
+        let src = WorldPos::new(from.x + dx, from.y + dy);
+        let pixel = *world.get_pixel(src).unwrap();
+        world.set_pixel(src, Pixel::VOID, DebugGizmos::none());
+        world.set_pixel(
+          WorldPos::new(to.x + dx, to.y + dy),
+          pixel,
+          DebugGizmos::none(),
+        );
+      }
+    }
+  });
+}
+
+#[test]
+fn submerged_sponge_saturates() {
+  let (_temp_dir, mut app) = setup();
+  build_scene(&mut app);
+  let water = total_water(&mut app);
+
+  run_until(&mut app, water, |app| {
+    sponge_saturations(app, SUBMERGED)
+      .iter()
+      .all(|&s| s == CAPACITY)
+  });
+}
+
+#[test]
+fn sponge_in_dry_air_releases_its_water() {
+  let (_temp_dir, mut app) = setup();
+  build_scene(&mut app);
+  let water = total_water(&mut app);
+  run_until(&mut app, water, |app| {
+    sponge_saturations(app, SUBMERGED)
+      .iter()
+      .all(|&s| s == CAPACITY)
+  });
+
+  move_sponge(&mut app, SUBMERGED, HUNG);
+  assert_eq!(total_water(&mut app), water);
+
+  // Releasing takes a while, not one pass
+  app.update();
+  let stored: u32 = sponge_saturations(&mut app, HUNG)
+    .iter()
+    .map(|&s| s as u32)
+    .sum();
+  assert!(stored > 0, "Sponge should release its water gradually");
+
+  run_until(&mut app, water, |app| {
+    sponge_saturations(app, HUNG).iter().all(|&s| s == 0)
+  });
+  // Let the last drops land
+  for _ in 0..120 {
+    app.update();
+  }
+
+  let caught = with_world(&mut app, |world| {
+    let (left, right, floor) = CATCH;
+    (floor..floor + 8)
+      .flat_map(|y| (left..right).map(move |x| WorldPos::new(x, y)))
+      .filter(|&pos| {
+        world
+          .get_pixel(pos)
+          .is_some_and(|p| p.material == material_ids::WATER)
+      })
+      .count()
+  });
+  assert_eq!(
+    caught,
+    (SPONGE_SIZE * SPONGE_SIZE) as usize * CAPACITY as usize,
+    "Released water should collect in the catch basin"
+  );
+}
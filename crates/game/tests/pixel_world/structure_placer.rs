@@ -0,0 +1,149 @@
+//! Tests for structure placement.
+//!
+//! Tests that a structure spanning two chunks is stamped identically whether
+//! the left or the right chunk is seeded first, that it continues across the
+//! boundary, and that transparent clip cells keep the terrain.
+
+use game::pixel_world::{
+  CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, ColorIndex, LayeredSeeder, MaterialId, Pixel,
+  RegionClip, StructurePlacer, material_ids,
+};
+
+const WALL: MaterialId = MaterialId(9);
+const CLIP_WIDTH: u32 = 300;
+const CLIP_HEIGHT: u32 = 24;
+
+/// Fills every chunk with stone.
+struct StoneSeeder;
+
+impl ChunkSeeder for StoneSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::new(material_ids::STONE, ColorIndex(0));
+      }
+    }
+  }
+}
+
+/// A hollow box whose wall colors encode the clip column.
+fn house() -> RegionClip {
+  let mut clip = RegionClip::new(CLIP_WIDTH, CLIP_HEIGHT);
+  for y in 0..CLIP_HEIGHT {
+    for x in 0..CLIP_WIDTH {
+      let wall = x == 0 || y == 0 || x == CLIP_WIDTH - 1 || y == CLIP_HEIGHT - 1;
+      let pixel = if wall {
+        Pixel::new(WALL, ColorIndex((x % 256) as u8))
+      } else {
+        Pixel::VOID
+      };
+      clip.set(x, y, Some(pixel));
+    }
+  }
+  // Leave a window of terrain in the middle of the bottom wall
+  clip.set(CLIP_WIDTH / 2, 0, None);
+  clip
+}
+
+/// One structure per chunk-sized region, so most structures cross a chunk.
+fn seeder() -> LayeredSeeder {
+  LayeredSeeder::new(StoneSeeder).with_layer(
+    StructurePlacer::new(vec![house()], 7)
+      .chance(1.0)
+      .region_size(CHUNK_SIZE),
+  )
+}
+
+fn seed_chunk(seeder: &LayeredSeeder, pos: ChunkPos) -> Chunk {
+  let mut chunk = Chunk::new(CHUNK_SIZE, CHUNK_SIZE);
+  seeder.seed(pos, &mut chunk);
+  chunk
+}
+
+fn wall_color(chunk: &Chunk, x: u32, y: u32) -> Option<u8> {
+  let pixel = chunk.pixels[(x, y)];
+  (pixel.material == WALL).then_some(pixel.color.0)
+}
+
+/// Finds horizontally adjacent chunks with a wall row crossing their
+/// boundary, returning the chunks and the crossing row.
+fn find_spanning_pair(seeder: &LayeredSeeder) -> (ChunkPos, ChunkPos, Chunk, Chunk, u32) {
+  let edge = CHUNK_SIZE - 1;
+  for x in 0..16 {
+    let (a, b) = (ChunkPos::new(x, 0), ChunkPos::new(x + 1, 0));
+    let left = seed_chunk(seeder, a);
+    let right = seed_chunk(seeder, b);
+    let row = (0..CHUNK_SIZE)
+      .find(|&y| wall_color(&left, edge, y).is_some() && wall_color(&right, 0, y).is_some());
+    if let Some(row) = row {
+      return (a, b, left, right, row);
+    }
+  }
+  panic!("No structure spans a chunk boundary");
+}
+
+#[test]
+fn spanning_structure_is_independent_of_seed_order() {
+  let (a, b, left, right, _) = find_spanning_pair(&seeder());
+
+  // Seed the right chunk first with a fresh seeder
+  let reverse = seeder();
+  let right_first = seed_chunk(&reverse, b);
+  let left_second = seed_chunk(&reverse, a);
+
+  assert!(
+    left.pixels.as_bytes() == left_second.pixels.as_bytes(),
+    "Chunk {:?} differs between seed orders",
+    a
+  );
+  assert!(
+    right.pixels.as_bytes() == right_first.pixels.as_bytes(),
+    "Chunk {:?} differs between seed orders",
+    b
+  );
+}
+
+#[test]
+fn structure_continues_across_chunk_boundary() {
+  let (_, _, left, right, row) = find_spanning_pair(&seeder());
+
+  let edge = CHUNK_SIZE - 1;
+  let left_color = wall_color(&left, edge, row).unwrap();
+  let right_color = wall_color(&right, 0, row).unwrap();
+  assert_eq!(
+    right_color,
+    left_color.wrapping_add(1),
+    "Clip columns should be consecutive across the boundary"
+  );
+
+  let wall_pixels = (0..CHUNK_SIZE)
+    .filter(|&x| wall_color(&left, x, row).is_some())
+    .count()
+    + (0..CHUNK_SIZE)
+      .filter(|&x| wall_color(&right, x, row).is_some())
+      .count();
+  assert!(
+    wall_pixels >= CLIP_WIDTH as usize - 1,
+    "Wall row should cover the clip width, got {}",
+    wall_pixels
+  );
+}
+
+#[test]
+fn transparent_cells_keep_terrain() {
+  let seeder = seeder();
+  let mid = (CLIP_WIDTH / 2) as u8;
+
+  // Look for the bottom wall window: stone between wall columns mid-1, mid+1
+  let found = (0..4).any(|cx| {
+    let chunk = seed_chunk(&seeder, ChunkPos::new(cx, 0));
+    (0..CHUNK_SIZE).any(|y| {
+      (1..CHUNK_SIZE - 1).any(|x| {
+        chunk.pixels[(x, y)].material == material_ids::STONE
+          && wall_color(&chunk, x - 1, y) == Some(mid - 1)
+          && wall_color(&chunk, x + 1, y) == Some(mid + 1)
+      })
+    })
+  });
+  assert!(found, "Transparent clip cell should keep the stone beneath");
+}
@@ -0,0 +1,47 @@
+//! Tests for converting pixel surfaces to images.
+//!
+//! Checks palette lookup, row order and out-of-palette colors.
+
+use bevy::render::render_resource::TextureFormat;
+use game::pixel_world::{ColorIndex, MaterialId, Pixel, PixelSurface, Rgba, surface_to_image};
+
+fn palette() -> Vec<Rgba> {
+  (0..=255u8)
+    .map(|i| Rgba::new(i, 255 - i, i / 2, 255))
+    .collect()
+}
+
+#[test]
+fn surface_to_image_maps_colors_through_palette() {
+  let mut pixels = PixelSurface::new(3, 2);
+  pixels[(0, 0)] = Pixel::new(MaterialId(3), ColorIndex(10));
+  pixels[(1, 0)] = Pixel::new(MaterialId(2), ColorIndex(200));
+  pixels[(2, 1)] = Pixel::new(MaterialId(5), ColorIndex(0));
+
+  let image = surface_to_image(&pixels, &palette());
+
+  assert_eq!(image.width(), 3);
+  assert_eq!(image.height(), 2);
+  assert_eq!(
+    image.texture_descriptor.format,
+    TextureFormat::Rgba8UnormSrgb
+  );
+
+  // Top row (surface y = 1) comes first
+  #[rustfmt::skip]
+  let expected: [u8; 24] = [
+    0, 0, 0, 0,       0, 0, 0, 0,       0, 255, 0, 255,
+    10, 245, 5, 255,  200, 55, 100, 255, 0, 0, 0, 0,
+  ];
+  assert_eq!(image.data.as_deref(), Some(&expected[..]));
+}
+
+#[test]
+fn surface_to_image_out_of_palette_is_transparent() {
+  let mut pixels = PixelSurface::new(1, 1);
+  pixels[(0, 0)] = Pixel::new(MaterialId(3), ColorIndex(4));
+
+  let image = surface_to_image(&pixels, &[Rgba::new(1, 2, 3, 255)]);
+
+  assert_eq!(image.data.as_deref(), Some(&[0, 0, 0, 0][..]));
+}
@@ -0,0 +1,202 @@
+//! E2E tests for swap conflict resolution by material priority.
+//!
+//! Each pocket is a one-pixel hole in stone with two liquid pixels above it:
+//! one falls straight in, the other slides in diagonally. Both propose the
+//! hole in the same tick, and the material with the higher `swap_priority`
+//! must take it whichever pixel is visited first. Pockets are spread over
+//! many columns and two rows, so row direction, tiles and phases vary.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::MaterialConfig;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelWorld, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Liquid that wins contention.
+const HIGH: MaterialId = MaterialId(7);
+/// Liquid of equal density that loses contention.
+const LOW: MaterialId = MaterialId(8);
+
+/// Pockets per placement.
+const POCKETS: i64 = 12;
+/// Horizontal distance between pockets.
+const SPACING: i64 = 6;
+/// Heights of the pocket rows.
+const ROWS: [i64; 2] = [8, 45];
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn liquid(name: &str, swap_priority: u8) -> MaterialConfig {
+  MaterialConfig {
+    density: 100,
+    dispersion: 5,
+    swap_priority,
+    ..MaterialConfig::new(name, PhysicsState::Liquid)
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config.materials.push(liquid("High", 2));
+  config.materials.push(liquid("Low", 1));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// How the two contenders are placed above a pocket.
+#[derive(Clone, Copy, Debug)]
+struct Placement {
+  /// Material falling straight into the pocket.
+  falling: MaterialId,
+  /// Material sliding diagonally into the pocket.
+  sliding: MaterialId,
+  /// Side of the sliding pixel: -1 = left, 1 = right.
+  side: i64,
+}
+
+const PLACEMENTS: [Placement; 4] = [
+  Placement {
+    falling: LOW,
+    sliding: HIGH,
+    side: 1,
+  },
+  Placement {
+    falling: LOW,
+    sliding: HIGH,
+    side: -1,
+  },
+  Placement {
+    falling: HIGH,
+    sliding: LOW,
+    side: 1,
+  },
+  Placement {
+    falling: HIGH,
+    sliding: LOW,
+    side: -1,
+  },
+];
+
+fn set(world: &mut PixelWorld, x: i64, y: i64, material: MaterialId) {
+  world.set_pixel(
+    WorldPos::new(x, y),
+    Pixel::new(material, ColorIndex(0)),
+    DebugGizmos::none(),
+  );
+}
+
+/// Paints a pocket at (x, y) with its two contenders above it.
+fn paint_pocket(world: &mut PixelWorld, x: i64, y: i64, placement: Placement) {
+  for dx in -2..=2 {
+    set(world, x + dx, y - 1, material_ids::STONE);
+    if dx != 0 {
+      set(world, x + dx, y, material_ids::STONE);
+    }
+  }
+  set(world, x, y + 1, placement.falling);
+  set(world, x + placement.side, y + 1, placement.sliding);
+}
+
+/// Returns the pockets as (x, y, placement).
+fn pockets() -> impl Iterator<Item = (i64, i64, Placement)> {
+  ROWS.into_iter().flat_map(|y| {
+    PLACEMENTS
+      .into_iter()
+      .enumerate()
+      .flat_map(move |(p, placement)| {
+        (0..POCKETS).map(move |i| {
+          let x = 8 + (p as i64 * POCKETS + i) * SPACING;
+          (x, y, placement)
+        })
+      })
+  })
+}
+
+#[test]
+fn higher_priority_wins_contended_cell() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    for (x, y, placement) in pockets() {
+      paint_pocket(&mut world, x, y, placement);
+    }
+  }
+
+  // Both contenders propose the pocket in this tick
+  app.update();
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  for (x, y, placement) in pockets() {
+    let filled = world.get_pixel(WorldPos::new(x, y)).unwrap().material;
+    assert_eq!(
+      filled, HIGH,
+      "Pocket at ({x}, {y}) with {placement:?} should go to the higher priority"
+    );
+  }
+}
@@ -0,0 +1,93 @@
+//! Tests for measuring text without rasterizing.
+//!
+//! Tests that `CpuFont::measure` reports the same size as the mask
+//! `CpuFont::rasterize` produces, for single lines, explicit newlines, and
+//! wrapped text.
+
+use bevy::math::UVec2;
+use game::pixel_world::{CpuFont, TextStyle};
+
+fn assert_measure_matches(font: &CpuFont, text: &str, style: &TextStyle) -> UVec2 {
+  let measured = font.measure(text, style);
+  let mask = font.rasterize(text, style).expect("Text should rasterize");
+  assert_eq!(
+    measured,
+    UVec2::new(mask.width(), mask.height()),
+    "measure({text:?}) should match the rasterized mask"
+  );
+  measured
+}
+
+#[test]
+fn measure_matches_single_line() {
+  let font = CpuFont::default_font();
+
+  for style in [
+    TextStyle::default(),
+    TextStyle {
+      font_scale: 32.0,
+      char_spacing: 2.0,
+      ..TextStyle::default()
+    },
+  ] {
+    let short = assert_measure_matches(&font, "Hi", &style);
+    let long = assert_measure_matches(&font, "Hello, world!", &style);
+    assert!(long.x > short.x, "Longer text should be wider");
+  }
+}
+
+#[test]
+fn measure_matches_wrapped_lines() {
+  let font = CpuFont::default_font();
+  let text = "the quick brown fox jumps over the lazy dog";
+  let single = assert_measure_matches(&font, text, &TextStyle::default());
+
+  let wrapped = TextStyle {
+    max_width: Some(single.x / 3),
+    ..TextStyle::default()
+  };
+  let size = assert_measure_matches(&font, text, &wrapped);
+  assert!(
+    size.x <= single.x / 3 + 1,
+    "Wrapped lines should fit the width ({} > {})",
+    size.x,
+    single.x / 3
+  );
+  assert!(
+    size.y >= single.y * 3,
+    "Wrapping into 3+ lines should grow the height ({} vs {})",
+    size.y,
+    single.y
+  );
+
+  let spaced = TextStyle {
+    line_spacing: 4.0,
+    ..wrapped
+  };
+  let spaced_size = assert_measure_matches(&font, text, &spaced);
+  assert_eq!(spaced_size.x, size.x);
+  assert!(
+    spaced_size.y > size.y,
+    "Line spacing should add to the height"
+  );
+}
+
+#[test]
+fn measure_matches_explicit_newlines() {
+  let font = CpuFont::default_font();
+  let style = TextStyle::default();
+
+  let one = assert_measure_matches(&font, "Score", &style);
+  let two = assert_measure_matches(&font, "Score\nLives", &style);
+  assert!(two.y > one.y, "A second line should grow the height");
+}
+
+#[test]
+fn measure_of_empty_text_is_zero() {
+  let font = CpuFont::default_font();
+  let style = TextStyle::default();
+
+  assert_eq!(font.measure("", &style), UVec2::ZERO);
+  assert_eq!(font.measure("   ", &style), UVec2::ZERO);
+  assert!(font.rasterize("", &style).is_none());
+}
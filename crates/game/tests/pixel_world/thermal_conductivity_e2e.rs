@@ -0,0 +1,215 @@
+//! E2E tests for per-material thermal conductivity.
+//!
+//! Two identical strips are heated from one end by a lava source. The strip
+//! made of a high-conductivity material should carry heat further over the
+//! same number of ticks than the strip made of an insulating material.
+//! Heat between two cells flows at the lower of their conductivities, so a
+//! hot insulator doesn't warm a conductor next to it either.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::MaterialConfig;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelWorld, PixelWorldPlugin,
+  SimulationConfig, SpawnPixelWorld, StreamingCamera, WorldPos, debug_shim::DebugGizmos,
+};
+use tempfile::TempDir;
+
+const LAVA: MaterialId = MaterialId(7);
+const CONDUCTOR: MaterialId = MaterialId(8);
+const INSULATOR: MaterialId = MaterialId(9);
+const HEARTH: MaterialId = MaterialId(10);
+
+/// Strip length in pixels, starting right after the 4px lava source.
+const STRIP_LEN: i64 = 32;
+/// Heat cell size in pixels.
+const CELL: i64 = 4;
+
+/// Seeds every chunk with void so only painted pixels hold heat.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn solid(name: &str, base_temperature: u8, thermal_conductivity: f32) -> MaterialConfig {
+  MaterialConfig {
+    density: 200,
+    base_temperature,
+    thermal_conductivity,
+    ..MaterialConfig::new(name, PhysicsState::Solid)
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config.materials.push(solid("Lava", 255, 1.0));
+  config.materials.push(solid("Conductor", 0, 1.0));
+  config.materials.push(solid("Insulator", 0, 0.1));
+  config.materials.push(solid("Hearth", 255, 0.0));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Paints a one-cell-tall strip of `material` at row `y`, with a lava source
+/// cell at its left end.
+fn paint_strip(world: &mut PixelWorld, y: i64, material: MaterialId) {
+  for dy in 0..CELL {
+    for x in 0..CELL + STRIP_LEN {
+      let mat = if x < CELL { LAVA } else { material };
+      world.set_pixel(
+        WorldPos::new(x, y + dy),
+        Pixel::new(mat, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    }
+  }
+  // Wake the heat tile containing the source
+  world.set_heat_at(WorldPos::new(0, y), 255);
+}
+
+/// Sums the heat of every strip cell, excluding the source.
+fn strip_heat(world: &PixelWorld, y: i64) -> u32 {
+  (1..=STRIP_LEN / CELL)
+    .map(|cell| world.get_heat_at(WorldPos::new(cell * CELL, y)).unwrap() as u32)
+    .sum()
+}
+
+#[test]
+fn heat_spreads_faster_through_conductive_material() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  const CONDUCTOR_ROW: i64 = 8;
+  const INSULATOR_ROW: i64 = 64;
+
+  // Run heat propagation every physics tick
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    paint_strip(&mut world, CONDUCTOR_ROW, CONDUCTOR);
+    paint_strip(&mut world, INSULATOR_ROW, INSULATOR);
+  }
+
+  for _ in 0..30 {
+    app.update();
+  }
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  let conductor = strip_heat(world, CONDUCTOR_ROW);
+  let insulator = strip_heat(world, INSULATOR_ROW);
+
+  assert_eq!(
+    world.get_heat_at(WorldPos::new(0, CONDUCTOR_ROW)),
+    Some(255),
+    "Lava source should stay at full heat"
+  );
+  assert!(
+    conductor > insulator,
+    "Conductive strip should carry more heat ({}) than insulating strip ({})",
+    conductor,
+    insulator
+  );
+}
+
+#[test]
+fn insulating_source_does_not_heat_neighbors() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  const ROW: i64 = 8;
+
+  app.world_mut().resource_mut::<SimulationConfig>().heat_tps = 60.0;
+
+  {
+    let mut q = app.world_mut().query::<&mut PixelWorld>();
+    let mut world = q.single_mut(app.world_mut()).unwrap();
+    // A hearth cell with a conductor cell to its right
+    for dy in 0..CELL {
+      for x in 0..2 * CELL {
+        let mat = if x < CELL { HEARTH } else { CONDUCTOR };
+        world.set_pixel(
+          WorldPos::new(x, ROW + dy),
+          Pixel::new(mat, ColorIndex(0)),
+          DebugGizmos::none(),
+        );
+      }
+    }
+    world.set_heat_at(WorldPos::new(0, ROW), 255);
+  }
+
+  for _ in 0..30 {
+    app.update();
+  }
+
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  assert_eq!(
+    world.get_heat_at(WorldPos::new(0, ROW)),
+    Some(255),
+    "Hearth should stay at full heat"
+  );
+  assert_eq!(
+    world.get_heat_at(WorldPos::new(CELL, ROW)),
+    Some(0),
+    "Heat should not flow out of an insulator"
+  );
+}
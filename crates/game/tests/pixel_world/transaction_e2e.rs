@@ -0,0 +1,206 @@
+//! E2E tests for `PixelWorld::transaction`.
+//!
+//! Moves a stone shape across a chunk boundary inside a transaction and
+//! checks that the world only ever shows the shape at its old or its new
+//! position, that both touched chunks are listed once, and that a failed
+//! transaction leaves the world untouched.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, WorldTransaction,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+/// Shape to move: a 3x3 stone block left of the chunk boundary at x = 0.
+const SHAPE: (i64, i64, i64, i64) = (-6, 10, -3, 13);
+/// Horizontal move, carrying the shape into the next chunk.
+const OFFSET: i64 = 6;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q.single(app.world()).is_ok_and(|world| {
+      world.get_pixel(WorldPos::new(SHAPE.0, SHAPE.1)).is_some()
+        && world
+          .get_pixel(WorldPos::new(SHAPE.2 + OFFSET, SHAPE.3))
+          .is_some()
+    }) {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn stone() -> Pixel {
+  Pixel::new(material_ids::STONE, ColorIndex(0))
+}
+
+fn shape_positions(dx: i64) -> impl Iterator<Item = WorldPos> {
+  let (x0, y0, x1, y1) = SHAPE;
+  (y0..y1).flat_map(move |y| (x0..x1).map(move |x| WorldPos::new(x + dx, y)))
+}
+
+/// Number of stone pixels of the shape at offset `dx`.
+fn stone_count(world: &PixelWorld, dx: i64) -> usize {
+  shape_positions(dx)
+    .filter(|&pos| {
+      world
+        .get_pixel(pos)
+        .is_some_and(|p| p.material == material_ids::STONE)
+    })
+    .count()
+}
+
+/// Records moving the shape by `OFFSET`: clears every source pixel, then
+/// writes every target.
+fn record_move(tx: &mut WorldTransaction<'_>) {
+  let pixels: Vec<_> = shape_positions(0)
+    .map(|pos| (pos, tx.get_pixel(pos).unwrap()))
+    .collect();
+  for &(pos, _) in &pixels {
+    assert!(tx.set_pixel(pos, Pixel::VOID));
+  }
+  for (pos, pixel) in pixels {
+    assert!(tx.set_pixel(WorldPos::new(pos.x + OFFSET, pos.y), pixel));
+  }
+}
+
+fn setup() -> (TempDir, App) {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  for pos in shape_positions(0) {
+    world.set_pixel(pos, stone(), DebugGizmos::none());
+  }
+  (temp_dir, app)
+}
+
+#[test]
+fn moved_shape_is_never_half_applied() {
+  let (_temp_dir, mut app) = setup();
+  let area = shape_positions(0).count();
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  let touched = world.transaction(|tx| {
+    record_move(tx);
+
+    // The world still holds the whole shape at its old position
+    assert_eq!(stone_count(tx.world(), 0), area);
+    assert_eq!(stone_count(tx.world(), OFFSET), 0);
+
+    // The transaction sees the whole move
+    assert!(shape_positions(0).all(|pos| tx.get_pixel(pos) == Some(Pixel::VOID)));
+    assert!(shape_positions(OFFSET).all(|pos| tx.get_pixel(pos) == Some(stone())));
+    assert_eq!(tx.len(), area * 2);
+
+    tx.touched_chunks()
+  });
+
+  assert_eq!(stone_count(&world, 0), 0, "Old position should be cleared");
+  assert_eq!(stone_count(&world, OFFSET), area);
+  assert_eq!(
+    touched,
+    vec![ChunkPos::new(-1, 0), ChunkPos::new(0, 0)],
+    "Each chunk the move spans should be dirtied once"
+  );
+  for pos in touched {
+    assert!(world.is_upload_pending(pos));
+  }
+
+  // Solid stone stays put once simulation runs
+  app.update();
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  assert_eq!(stone_count(world, OFFSET), area);
+}
+
+#[test]
+fn failed_transaction_leaves_world_untouched() {
+  let (_temp_dir, mut app) = setup();
+  let area = shape_positions(0).count();
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  let result: Result<(), &str> = world.try_transaction(|tx| {
+    record_move(tx);
+    Err("target blocked")
+  });
+
+  assert_eq!(result, Err("target blocked"));
+  assert_eq!(stone_count(&world, 0), area, "Shape should not move");
+  assert_eq!(stone_count(&world, OFFSET), 0);
+}
+
+#[test]
+fn rollback_discards_pending_writes() {
+  let (_temp_dir, mut app) = setup();
+  let area = shape_positions(0).count();
+
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  world.transaction(|tx| {
+    record_move(tx);
+    tx.rollback();
+    assert!(tx.is_empty());
+    assert!(tx.touched_chunks().is_empty());
+  });
+
+  assert_eq!(stone_count(&world, 0), area);
+  assert_eq!(stone_count(&world, OFFSET), 0);
+}
@@ -0,0 +1,127 @@
+//! E2E test for the unsaved changes query.
+//!
+//! Edits a chunk and checks that `PersistenceControl` reports unsaved
+//! changes until a save has been written and flushed.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig,
+  PersistenceControl, Pixel, PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera,
+  WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const PAINTED: WorldPos = WorldPos::new(10, 10);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn run_until(app: &mut App, message: &str, mut done: impl FnMut(&mut App) -> bool) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    if done(app) {
+      return;
+    }
+  }
+  panic!("{message}");
+}
+
+fn has_unsaved_changes(app: &App) -> bool {
+  app
+    .world()
+    .resource::<PersistenceControl>()
+    .has_unsaved_changes()
+}
+
+#[test]
+fn edits_are_unsaved_until_saved() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  run_until(&mut app, "World was not seeded", |app| {
+    app.world().contains_resource::<PersistenceControl>()
+      && with_world(app, |world| world.get_pixel(PAINTED).is_some())
+  });
+  run_until(
+    &mut app,
+    "Freshly seeded world has unsaved changes",
+    |app| !has_unsaved_changes(app),
+  );
+
+  with_world(&mut app, |world| {
+    world.set_pixel(
+      PAINTED,
+      Pixel::new(material_ids::STONE, ColorIndex(3)),
+      DebugGizmos::none(),
+    )
+  });
+  app.update();
+
+  let control = app.world().resource::<PersistenceControl>();
+  assert!(control.has_unsaved_changes(), "Edit should be unsaved");
+  assert_eq!(control.unsaved_chunk_count(), 1);
+
+  let handle = app.world_mut().resource_mut::<PersistenceControl>().save();
+  run_until(&mut app, "Save did not complete", |_| handle.is_complete());
+  run_until(&mut app, "Save was not flushed", |app| {
+    !has_unsaved_changes(app)
+  });
+
+  let control = app.world().resource::<PersistenceControl>();
+  assert_eq!(control.unsaved_chunk_count(), 0);
+  for _ in 0..5 {
+    app.update();
+  }
+  assert!(!has_unsaved_changes(&app), "Saved world should stay clean");
+}
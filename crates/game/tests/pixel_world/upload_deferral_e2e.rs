@@ -0,0 +1,191 @@
+//! E2E tests for deferring GPU uploads of off-screen chunks.
+//!
+//! Tests that a dirty chunk outside the simulation bounds is not queued for
+//! upload, and that it is queued exactly once when it comes back into view
+//! or deferral is turned off.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, ChunkPos, ColorIndex, MaterialSeeder, PersistenceConfig, Pixel, PixelWorld,
+  PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect, debug_shim::DebugGizmos,
+  material_ids,
+};
+use tempfile::TempDir;
+
+/// Chunk containing the camera view.
+const VISIBLE: ChunkPos = ChunkPos::new(0, 0);
+/// Loaded chunk outside the camera view.
+const OFFSCREEN: ChunkPos = ChunkPos::new(-1, 0);
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(MaterialSeeder::new(42)));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      [VISIBLE, OFFSCREEN]
+        .iter()
+        .all(|pos| world.get_pixel(pos.to_world()).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Sets a view well inside `VISIBLE`, so the margin does not reach other
+/// chunks.
+fn look_at_visible_chunk(world: &mut PixelWorld) {
+  world.set_simulation_bounds(Some(WorldRect::new(200, 200, 64, 64)));
+}
+
+/// Sets a view straddling the border between `OFFSCREEN` and `VISIBLE`.
+fn look_at_both_chunks(world: &mut PixelWorld) {
+  world.set_simulation_bounds(Some(WorldRect::new(-32, 200, 64, 64)));
+}
+
+fn paint(world: &mut PixelWorld, pos: WorldPos) {
+  world.set_pixel(
+    pos,
+    Pixel::new(material_ids::STONE, ColorIndex(1)),
+    DebugGizmos::none(),
+  );
+}
+
+#[test]
+fn offscreen_chunk_uploads_once_when_back_in_view() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  // The test camera has no projection, so bounds stay as set here
+  with_world(&mut app, look_at_visible_chunk);
+  app.update();
+
+  with_world(&mut app, |world| {
+    paint(world, WorldPos::new(-100, 100));
+    paint(world, WorldPos::new(100, 100));
+  });
+  app.update();
+
+  with_world(&mut app, |world| {
+    assert!(
+      world.is_upload_pending(VISIBLE),
+      "Visible dirty chunk should be queued for upload"
+    );
+    assert!(
+      !world.is_upload_pending(OFFSCREEN),
+      "Off-screen dirty chunk should not be queued for upload"
+    );
+    assert!(world.is_gpu_stale(OFFSCREEN));
+  });
+
+  // Further edits while off-screen keep it deferred
+  with_world(&mut app, |world| paint(world, WorldPos::new(-101, 100)));
+  app.update();
+  with_world(&mut app, |world| {
+    assert!(!world.is_upload_pending(OFFSCREEN));
+    assert!(world.is_gpu_stale(OFFSCREEN));
+  });
+
+  // Re-entering the view queues a single catch-up upload
+  with_world(&mut app, look_at_both_chunks);
+  app.update();
+  with_world(&mut app, |world| {
+    assert!(
+      world.is_upload_pending(OFFSCREEN),
+      "Chunk re-entering the view should be queued for upload"
+    );
+    assert!(
+      !world.is_gpu_stale(OFFSCREEN),
+      "Catch-up upload should clear the stale flag"
+    );
+  });
+}
+
+#[test]
+fn deferral_can_be_disabled() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  with_world(&mut app, |world| {
+    world.config_mut().defer_offscreen_uploads = false;
+    look_at_visible_chunk(world);
+    paint(world, WorldPos::new(-100, 100));
+  });
+  app.update();
+
+  with_world(&mut app, |world| {
+    assert!(world.is_upload_pending(OFFSCREEN));
+    assert!(!world.is_gpu_stale(OFFSCREEN));
+  });
+}
+
+#[test]
+fn disabling_deferral_flushes_stale_chunks() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  with_world(&mut app, |world| {
+    look_at_visible_chunk(world);
+    paint(world, WorldPos::new(-100, 100));
+  });
+  app.update();
+  with_world(&mut app, |world| assert!(world.is_gpu_stale(OFFSCREEN)));
+
+  with_world(&mut app, |world| {
+    world.config_mut().defer_offscreen_uploads = false;
+  });
+  app.update();
+
+  with_world(&mut app, |world| {
+    assert!(
+      world.is_upload_pending(OFFSCREEN),
+      "Stale chunk should be queued once deferral is off"
+    );
+    assert!(!world.is_gpu_stale(OFFSCREEN));
+  });
+}
@@ -0,0 +1,236 @@
+//! E2E tests for wet powder slumping.
+//!
+//! Tests that a pile of wet sand settles flatter and wider than the same
+//! amount of dry sand, that sand touching water gets wet, and that wet sand
+//! dries once out of contact.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelFlags, PixelWorld, PixelWorldPlugin, SimulationConfig, SpawnPixelWorld, StreamingCamera,
+  WorldPos, WorldRect, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const FLOOR_HALF_WIDTH: i64 = 160;
+const COLUMN_WIDTH: i64 = 20;
+const COLUMN_HEIGHT: i64 = 60;
+const SETTLE_TICKS: usize = 900;
+
+/// Seeds every chunk with void; the scene is painted by the test.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir, dry_duration_secs: f32) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(SimulationConfig {
+    dry_duration_secs,
+    ..default()
+  });
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      [
+        WorldPos::new(-FLOOR_HALF_WIDTH, -1),
+        WorldPos::new(FLOOR_HALF_WIDTH, -1),
+        WorldPos::new(0, COLUMN_HEIGHT),
+      ]
+      .iter()
+      .all(|&pos| world.get_pixel(pos).is_some())
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Paints `pixel` over `rect`, marking it for simulation.
+fn paint(app: &mut App, rect: WorldRect, pixel: Pixel) {
+  with_world(app, |world| {
+    world.blit(rect, |_| Some(pixel), DebugGizmos::none());
+  });
+}
+
+fn sand(wet: bool) -> Pixel {
+  let mut pixel = Pixel::new(material_ids::SAND, ColorIndex(0));
+  pixel.flags.set(PixelFlags::WET, wet);
+  pixel
+}
+
+fn paint_floor(app: &mut App) {
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  paint(
+    app,
+    WorldRect::new(-FLOOR_HALF_WIDTH, -1, 2 * FLOOR_HALF_WIDTH as u32 + 1, 1),
+    stone,
+  );
+}
+
+/// Pile dimensions: (height, base width).
+fn measure_pile(app: &mut App) -> (i64, i64) {
+  with_world(app, |world| {
+    let is_sand = |x: i64, y: i64| {
+      world
+        .get_pixel(WorldPos::new(x, y))
+        .is_some_and(|p| p.material == material_ids::SAND)
+    };
+    let xs = -FLOOR_HALF_WIDTH..=FLOOR_HALF_WIDTH;
+    let height = (0..COLUMN_HEIGHT)
+      .take_while(|&y| xs.clone().any(|x| is_sand(x, y)))
+      .count() as i64;
+    let base: Vec<i64> = xs.filter(|&x| is_sand(x, 0)).collect();
+    let width = base.last().unwrap() - base.first().unwrap() + 1;
+    (height, width)
+  })
+}
+
+/// Drops a column of sand onto the floor and returns the settled pile size.
+fn settle_pile(wet: bool) -> (i64, i64) {
+  let temp_dir = TempDir::new().unwrap();
+  // Never dry, so the whole pile stays wet while settling
+  let mut app = create_app(&temp_dir, 1.0e6);
+  wait_until_seeded(&mut app);
+
+  paint_floor(&mut app);
+  paint(
+    &mut app,
+    WorldRect::new(
+      -COLUMN_WIDTH / 2,
+      0,
+      COLUMN_WIDTH as u32,
+      COLUMN_HEIGHT as u32,
+    ),
+    sand(wet),
+  );
+
+  for _ in 0..SETTLE_TICKS {
+    app.update();
+  }
+
+  measure_pile(&mut app)
+}
+
+fn is_wet(app: &mut App, pos: WorldPos) -> bool {
+  with_world(app, |world| {
+    world
+      .get_pixel(pos)
+      .is_some_and(|p| p.flags.contains(PixelFlags::WET))
+  })
+}
+
+#[test]
+fn wet_sand_pile_is_shallower_than_dry() {
+  let (dry_height, dry_width) = settle_pile(false);
+  let (wet_height, wet_width) = settle_pile(true);
+
+  assert!(
+    wet_height < dry_height,
+    "Wet pile should be lower (wet {}, dry {})",
+    wet_height,
+    dry_height
+  );
+  assert!(
+    wet_width > dry_width,
+    "Wet pile should spread wider (wet {}, dry {})",
+    wet_width,
+    dry_width
+  );
+}
+
+#[test]
+fn sand_touching_water_gets_wet() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, 1.0e6);
+  wait_until_seeded(&mut app);
+
+  // Stone trough holding one sand pixel under one water pixel
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  paint_floor(&mut app);
+  paint(&mut app, WorldRect::new(-1, 0, 1, 2), stone);
+  paint(&mut app, WorldRect::new(1, 0, 1, 2), stone);
+  paint(&mut app, WorldRect::new(0, 0, 1, 1), sand(false));
+  paint(
+    &mut app,
+    WorldRect::new(0, 1, 1, 1),
+    Pixel::new(material_ids::WATER, ColorIndex(0)),
+  );
+
+  for _ in 0..5 {
+    app.update();
+  }
+
+  assert!(is_wet(&mut app, WorldPos::new(0, 0)));
+}
+
+#[test]
+fn wet_sand_dries_out_of_water() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, 0.1);
+  wait_until_seeded(&mut app);
+
+  paint_floor(&mut app);
+  paint(&mut app, WorldRect::new(0, 0, 1, 1), sand(true));
+
+  for _ in 0..120 {
+    app.update();
+  }
+
+  assert!(
+    with_world(&mut app, |world| world
+      .get_pixel(WorldPos::new(0, 0))
+      .is_some_and(|p| p.material == material_ids::SAND)),
+    "Sand should rest on the floor"
+  );
+  assert!(!is_wet(&mut app, WorldPos::new(0, 0)), "Sand should dry");
+}
@@ -0,0 +1,221 @@
+//! E2E tests for wind.
+//!
+//! A column of steam hangs in the air and a column of ephemeral embers falls
+//! onto a stone floor, next to a block of sand and a block of water. The
+//! same scene is run in calm air and in a steady rightward wind: the wind
+//! must carry the steam and the falling embers to the right, while the sand
+//! and water end up exactly as they do in calm air.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::material::MaterialConfig;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, MaterialId, Materials,
+  MaterialsConfig, PersistenceConfig, PhysicsState, Pixel, PixelWorld, PixelWorldPlugin,
+  SpawnPixelWorld, StreamingCamera, Wind, WorldPos, debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const STEAM: MaterialId = MaterialId(7);
+const EMBER: MaterialId = MaterialId(8);
+
+/// Region holding every pixel of the scene; its bottom row is the floor.
+const REGION: (WorldPos, WorldPos) = (WorldPos::new(-100, 0), WorldPos::new(100, 60));
+/// Steam column: x and rows.
+const STEAM_COLUMN: (i64, std::ops::Range<i64>) = (-90, 30..45);
+/// Ember column: x and rows.
+const EMBER_COLUMN: (i64, std::ops::Range<i64>) = (-50, 40..50);
+/// Lower left corners of the sand and water blocks, right of x = 0.
+const SAND: WorldPos = WorldPos::new(20, 30);
+const WATER: WorldPos = WorldPos::new(60, 30);
+const BLOCK_SIZE: i64 = 5;
+const FRAMES: usize = 120;
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn material(name: &str, state: PhysicsState, density: u8, lifetime: u8) -> MaterialConfig {
+  MaterialConfig {
+    density,
+    lifetime,
+    ..MaterialConfig::new(name, state)
+  }
+}
+
+fn create_app(temp_dir: &TempDir, wind: Wind) -> App {
+  let mut config = MaterialsConfig::builtin();
+  config
+    .materials
+    .push(material("Steam", PhysicsState::Gas, 1, 0));
+  config
+    .materials
+    .push(material("Ember", PhysicsState::Powder, 50, 250));
+
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.insert_resource(Materials::from(config));
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+  app.insert_resource(wind);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn with_world<R>(app: &mut App, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<&mut PixelWorld>();
+  let mut world = q.single_mut(app.world_mut()).unwrap();
+  f(&mut world)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_world(app, |world| {
+      world.get_pixel(REGION.0).is_some() && world.get_pixel(REGION.1).is_some()
+    });
+    if seeded {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+/// Builds the scene and runs it for [`FRAMES`] frames.
+fn run_scene(wind: Wind) -> App {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir, wind);
+  wait_until_seeded(&mut app);
+
+  with_world(&mut app, |world| {
+    let mut set = |x, y, material| {
+      world.set_pixel(
+        WorldPos::new(x, y),
+        Pixel::new(material, ColorIndex(0)),
+        DebugGizmos::none(),
+      );
+    };
+    for x in REGION.0.x..REGION.1.x {
+      set(x, REGION.0.y, material_ids::STONE);
+    }
+    let (x, rows) = STEAM_COLUMN;
+    for y in rows {
+      set(x, y, STEAM);
+    }
+    let (x, rows) = EMBER_COLUMN;
+    for y in rows {
+      set(x, y, EMBER);
+    }
+    for (corner, material) in [(SAND, material_ids::SAND), (WATER, material_ids::WATER)] {
+      for y in corner.y..corner.y + BLOCK_SIZE {
+        for x in corner.x..corner.x + BLOCK_SIZE {
+          set(x, y, material);
+        }
+      }
+    }
+  });
+
+  for _ in 0..FRAMES {
+    app.update();
+  }
+  app
+}
+
+/// Returns the positions of all pixels of `material` with x in `xs`.
+fn find(app: &mut App, material: MaterialId, xs: std::ops::Range<i64>) -> Vec<WorldPos> {
+  with_world(app, |world| {
+    (REGION.0.y..REGION.1.y)
+      .flat_map(|y| xs.clone().map(move |x| WorldPos::new(x, y)))
+      .filter(|&pos| world.get_pixel(pos).is_some_and(|p| p.material == material))
+      .collect()
+  })
+}
+
+fn mean_x(positions: &[WorldPos]) -> f32 {
+  positions.iter().map(|pos| pos.x as f32).sum::<f32>() / positions.len() as f32
+}
+
+#[test]
+fn wind_carries_gas_and_embers_but_not_solids_or_liquids() {
+  let mut calm = run_scene(Wind::default());
+  let mut windy = run_scene(Wind::steady(Vec2::new(0.5, 0.0)));
+
+  // Steam only moves with the wind
+  let (column_x, rows) = STEAM_COLUMN;
+  let calm_steam = find(&mut calm, STEAM, REGION.0.x..0);
+  assert_eq!(calm_steam.len(), rows.len(), "Steam was lost in calm air");
+  assert!(
+    calm_steam.iter().all(|pos| pos.x == column_x),
+    "Steam should stay put in calm air"
+  );
+  let windy_steam = find(&mut windy, STEAM, REGION.0.x..0);
+  assert_eq!(windy_steam.len(), rows.len(), "Steam was lost in the wind");
+  assert!(
+    mean_x(&windy_steam) > column_x as f32 + 20.0,
+    "Steam should drift right: {} -> {}",
+    column_x,
+    mean_x(&windy_steam)
+  );
+
+  // Falling embers are pushed right
+  let calm_embers = find(&mut calm, EMBER, REGION.0.x..0);
+  let windy_embers = find(&mut windy, EMBER, REGION.0.x..0);
+  assert_eq!(calm_embers.len(), EMBER_COLUMN.1.len());
+  assert_eq!(windy_embers.len(), EMBER_COLUMN.1.len());
+  assert!(
+    mean_x(&windy_embers) > mean_x(&calm_embers) + 5.0,
+    "Embers should land right of where they land in calm air: {} vs {}",
+    mean_x(&windy_embers),
+    mean_x(&calm_embers)
+  );
+
+  // Sand and water settle the same with or without wind
+  for material in [material_ids::SAND, material_ids::WATER] {
+    let calm_pixels = find(&mut calm, material, 0..REGION.1.x);
+    let windy_pixels = find(&mut windy, material, 0..REGION.1.x);
+    assert_eq!(
+      calm_pixels.len(),
+      (BLOCK_SIZE * BLOCK_SIZE) as usize,
+      "Material {material:?} was lost"
+    );
+    assert_eq!(
+      calm_pixels, windy_pixels,
+      "Wind should not move material {material:?}"
+    );
+  }
+}
@@ -0,0 +1,43 @@
+//! Tests for wind gusts.
+//!
+//! Checks that steady and calm winds never change, and that gusts ease
+//! smoothly within their range and replay the same for the same seed.
+
+use bevy::math::Vec2;
+use game::pixel_world::Wind;
+
+#[test]
+fn steady_wind_does_not_gust() {
+  let wind = Wind::steady(Vec2::new(0.5, 0.0));
+  assert_eq!(wind.at(7, 0), wind.velocity);
+  assert_eq!(wind.at(7, 12345), wind.velocity);
+}
+
+#[test]
+fn gusts_vary_slowly_and_reproducibly() {
+  let wind = Wind {
+    velocity: Vec2::new(0.5, 0.0),
+    gustiness: 0.5,
+    gust_period: 100,
+  };
+  let speeds: Vec<f32> = (0..1000).map(|tick| wind.at(42, tick).x).collect();
+
+  for pair in speeds.windows(2) {
+    assert!(
+      (pair[1] - pair[0]).abs() < 0.02,
+      "Gusts should ease smoothly"
+    );
+  }
+  for &speed in &speeds {
+    assert!((0.25..=0.75).contains(&speed));
+  }
+  assert!(speeds.iter().any(|&speed| (speed - 0.5).abs() > 0.01));
+
+  let replay: Vec<f32> = (0..1000).map(|tick| wind.at(42, tick).x).collect();
+  assert_eq!(speeds, replay);
+}
+
+#[test]
+fn calm_wind_stays_calm() {
+  assert_eq!(Wind::default().at(3, 500), Vec2::ZERO);
+}
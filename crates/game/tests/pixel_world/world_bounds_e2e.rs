@@ -0,0 +1,159 @@
+//! E2E tests for querying the streaming window's world bounds.
+//!
+//! Tests that `PixelWorld::window_world_bounds` covers the visible chunk
+//! window for a given center, and that `loaded_world_bounds` encloses every
+//! active chunk as the window moves.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::coords::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use game::pixel_world::{
+  AsyncTaskBehavior, CHUNK_SIZE, Chunk, ChunkPos, ChunkSeeder, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect,
+};
+use tempfile::TempDir;
+
+/// Seeds every chunk with void; terrain is irrelevant here.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+/// Expected window rect for a center chunk, spelled out in pixels.
+fn expected_window(center: ChunkPos) -> WorldRect {
+  let chunk = CHUNK_SIZE as i64;
+  WorldRect::new(
+    (center.x as i64 - WINDOW_WIDTH as i64 / 2) * chunk,
+    (center.y as i64 - WINDOW_HEIGHT as i64 / 2) * chunk,
+    WINDOW_WIDTH * CHUNK_SIZE,
+    WINDOW_HEIGHT * CHUNK_SIZE,
+  )
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  app
+    .world_mut()
+    .commands()
+    .queue(SpawnPixelWorld::new(VoidSeeder));
+
+  app
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let mut q = app.world_mut().query::<&PixelWorld>();
+    if q
+      .single(app.world())
+      .is_ok_and(|world| world.get_pixel(WorldPos::new(0, 0)).is_some())
+    {
+      return;
+    }
+  }
+  panic!("World was not seeded within timeout");
+}
+
+fn move_camera(app: &mut App, x: f32, y: f32) {
+  let mut q = app
+    .world_mut()
+    .query_filtered::<&mut Transform, With<StreamingCamera>>();
+  q.single_mut(app.world_mut()).unwrap().translation = Vec3::new(x, y, 0.0);
+  for _ in 0..3 {
+    app.update();
+  }
+}
+
+/// Returns the world's center, window bounds and loaded bounds.
+fn bounds(app: &mut App) -> (ChunkPos, WorldRect, Option<WorldRect>) {
+  let mut q = app.world_mut().query::<&PixelWorld>();
+  let world = q.single(app.world()).unwrap();
+  (
+    world.center(),
+    world.window_world_bounds(),
+    world.loaded_world_bounds(),
+  )
+}
+
+#[test]
+fn unloaded_world_has_no_loaded_bounds() {
+  let world = PixelWorld::new(Arc::new(VoidSeeder), Handle::default());
+  assert_eq!(world.loaded_world_bounds(), None);
+  assert_eq!(
+    world.window_world_bounds(),
+    expected_window(ChunkPos::new(0, 0))
+  );
+}
+
+#[test]
+fn window_bounds_match_center_at_origin() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  let (center, window, loaded) = bounds(&mut app);
+  assert_eq!(center, ChunkPos::new(0, 0));
+  assert_eq!(window, expected_window(center));
+  assert_eq!(loaded, Some(window), "Loaded chunks should fill the window");
+}
+
+#[test]
+fn window_bounds_follow_camera() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  // Streaming transitions at chunk centers: (1600, -700) lands in chunk (3, -1)
+  move_camera(&mut app, 1600.0, -700.0);
+
+  let (center, window, loaded) = bounds(&mut app);
+  assert_eq!(center, ChunkPos::new(3, -1));
+  assert_eq!(
+    window,
+    WorldRect::new(
+      512,
+      -1024,
+      WINDOW_WIDTH * CHUNK_SIZE,
+      WINDOW_HEIGHT * CHUNK_SIZE
+    )
+  );
+  assert_eq!(window, expected_window(center));
+  assert_eq!(
+    loaded,
+    Some(window),
+    "Chunks left behind should be unloaded"
+  );
+}
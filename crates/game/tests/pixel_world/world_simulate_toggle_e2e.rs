@@ -0,0 +1,174 @@
+//! E2E tests for the per-world simulation toggle.
+//!
+//! Spawns two worlds, disables simulation on one of them and checks that only
+//! the other's tick counter advances and only its sand falls, then that the
+//! disabled world resumes once re-enabled.
+
+use std::time::{Duration, Instant};
+
+use bevy::app::{TaskPoolOptions, TaskPoolPlugin};
+use bevy::prelude::*;
+use game::pixel_world::{
+  AsyncTaskBehavior, Chunk, ChunkPos, ChunkSeeder, ColorIndex, PersistenceConfig, Pixel,
+  PixelWorld, PixelWorldPlugin, SpawnPixelWorld, StreamingCamera, WorldPos, WorldRect,
+  debug_shim::DebugGizmos, material_ids,
+};
+use tempfile::TempDir;
+
+const SAND_TOP: WorldPos = WorldPos::new(0, 40);
+const SAND_BOTTOM: WorldPos = WorldPos::new(0, 0);
+
+/// Seeds every chunk with void.
+struct VoidSeeder;
+
+impl ChunkSeeder for VoidSeeder {
+  fn seed(&self, _pos: ChunkPos, chunk: &mut Chunk) {
+    for y in 0..chunk.pixels.height() {
+      for x in 0..chunk.pixels.width() {
+        chunk.pixels[(x, y)] = Pixel::VOID;
+      }
+    }
+  }
+}
+
+fn create_app(temp_dir: &TempDir) -> App {
+  let mut app = App::new();
+  app.add_plugins(MinimalPlugins.set(TaskPoolPlugin {
+    task_pool_options: TaskPoolOptions::with_num_threads(4),
+  }));
+
+  app.add_plugins(bevy::transform::TransformPlugin);
+  app.add_plugins(bevy::asset::AssetPlugin::default());
+  app.add_plugins(bevy::image::ImagePlugin::default());
+  app.add_plugins(bevy::scene::ScenePlugin);
+  app.add_plugins(bevy::gizmos::GizmoPlugin);
+
+  app.add_plugins(PixelWorldPlugin::new(PersistenceConfig::at(
+    temp_dir.path().join("test.save"),
+  )));
+  app.insert_resource(AsyncTaskBehavior::Poll);
+
+  app.world_mut().spawn((
+    Transform::default(),
+    GlobalTransform::default(),
+    StreamingCamera,
+  ));
+
+  for _ in 0..2 {
+    app
+      .world_mut()
+      .commands()
+      .queue(SpawnPixelWorld::new(VoidSeeder));
+  }
+
+  app
+}
+
+/// Runs `f` on each world, in spawn order.
+fn with_worlds<R>(app: &mut App, mut f: impl FnMut(&mut PixelWorld) -> R) -> Vec<R> {
+  let mut q = app.world_mut().query::<(Entity, &mut PixelWorld)>();
+  let mut worlds: Vec<_> = q.iter_mut(app.world_mut()).collect();
+  worlds.sort_by_key(|(entity, _)| *entity);
+  worlds
+    .into_iter()
+    .map(|(_, mut world)| f(&mut world))
+    .collect()
+}
+
+/// Runs `f` on the `index`-th world in spawn order.
+fn with_world_at<R>(app: &mut App, index: usize, f: impl FnOnce(&mut PixelWorld) -> R) -> R {
+  let mut q = app.world_mut().query::<(Entity, &mut PixelWorld)>();
+  let mut worlds: Vec<_> = q.iter_mut(app.world_mut()).collect();
+  worlds.sort_by_key(|(entity, _)| *entity);
+  f(&mut worlds[index].1)
+}
+
+fn wait_until_seeded(app: &mut App) {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    app.update();
+    std::thread::yield_now();
+    let seeded = with_worlds(app, |world| {
+      world.get_pixel(SAND_BOTTOM).is_some() && world.get_pixel(SAND_TOP).is_some()
+    });
+    if seeded.len() == 2 && seeded.iter().all(|&s| s) {
+      return;
+    }
+  }
+  panic!("Worlds were not seeded within timeout");
+}
+
+/// Paints a stone floor and a sand pixel above it.
+fn paint_scene(world: &mut PixelWorld) {
+  let stone = Pixel::new(material_ids::STONE, ColorIndex(0));
+  let sand = Pixel::new(material_ids::SAND, ColorIndex(0));
+  world.blit(
+    WorldRect::new(SAND_BOTTOM.x - 1, SAND_BOTTOM.y - 1, 3, 1),
+    |_| Some(stone),
+    DebugGizmos::none(),
+  );
+  world.blit(
+    WorldRect::new(SAND_TOP.x, SAND_TOP.y, 1, 1),
+    |_| Some(sand),
+    DebugGizmos::none(),
+  );
+}
+
+fn is_sand(world: &PixelWorld, pos: WorldPos) -> bool {
+  world
+    .get_pixel(pos)
+    .is_some_and(|p| p.material == material_ids::SAND)
+}
+
+fn run(app: &mut App, frames: usize) {
+  for _ in 0..frames {
+    app.update();
+  }
+}
+
+#[test]
+fn disabled_world_does_not_simulate() {
+  let temp_dir = TempDir::new().unwrap();
+  let mut app = create_app(&temp_dir);
+  wait_until_seeded(&mut app);
+
+  with_worlds(&mut app, paint_scene);
+  with_world_at(&mut app, 1, |world| world.set_simulate(false));
+
+  let ticks_before = with_worlds(&mut app, |world| world.tick());
+  run(&mut app, 120);
+  let ticks_after = with_worlds(&mut app, |world| world.tick());
+
+  assert!(
+    ticks_after[0] > ticks_before[0],
+    "Enabled world should tick"
+  );
+  assert_eq!(
+    ticks_after[1], ticks_before[1],
+    "Disabled world should not tick"
+  );
+
+  let settled = with_worlds(&mut app, |world| {
+    (is_sand(world, SAND_TOP), is_sand(world, SAND_BOTTOM))
+  });
+  assert_eq!(
+    settled[0],
+    (false, true),
+    "Enabled world's sand should fall"
+  );
+  assert_eq!(
+    settled[1],
+    (true, false),
+    "Disabled world's sand should stay put"
+  );
+
+  with_world_at(&mut app, 1, |world| world.set_simulate(true));
+  run(&mut app, 120);
+
+  let resumed = with_worlds(&mut app, |world| world.tick());
+  assert!(resumed[1] > ticks_after[1], "Re-enabled world should tick");
+  assert!(
+    with_world_at(&mut app, 1, |world| is_sand(world, SAND_BOTTOM)),
+    "Re-enabled world's sand should fall"
+  );
+}
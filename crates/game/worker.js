@@ -7,16 +7,83 @@ let saveFile = null;
 let syncHandle = null;
 let chunkIndex = new Map(); // Map<string, {offset, size, storageType}>
 let bodyIndex = new Map();  // Map<string, {offset, size, chunkPos}>
+let exploredChunks = new Int32Array(0); // Flat [x0, y0, x1, y1, ...]
 let dataWritePos = 0;
 let worldSeed = 0;
+let committedMetadata = { offset: 0, size: 0 }; // Region the header points to
+let spareMetadata = null; // Region committed before that, free for reuse
+let journalHandle = null;
+let journalWritePos = 0;
 
 // Header constants (must match Rust)
 const HEADER_SIZE = 64;
 const PAGE_TABLE_ENTRY_SIZE = 24;
 const BODY_INDEX_ENTRY_SIZE = 28;
 const ENTITY_HEADER_SIZE = 8;
+const EXPLORED_HEADER_SIZE = 8;
+const FLAG_EXPLORED_SECTION = 1;
 const MAX_CHUNK_SIZE = 100_000_000; // 100MB sanity limit for corrupt entry detection
 
+// Journal constants (must match persistence/journal.rs)
+const JOURNAL_MAGIC = 0x4C4A5850; // "PXJL"
+const JOURNAL_VERSION = 1;
+const JOURNAL_HEADER_SIZE = 24;
+const JOURNAL_ENTRY_PREFIX_SIZE = 9;
+const JOURNAL_TAG_CHUNK = 1;
+const JOURNAL_TAG_BODY = 2;
+const JOURNAL_TAG_REMOVE_BODY = 3;
+
+// CRC8 (polynomial 0x07) over page table entry bytes, like
+// PageTableEntry::compute_checksum
+function crc8(bytes) {
+	let crc = 0;
+	for (const byte of bytes) {
+		crc ^= byte;
+		for (let bit = 0; bit < 8; bit++) {
+			crc = crc & 0x80 ? ((crc << 1) ^ 0x07) & 0xFF : (crc << 1) & 0xFF;
+		}
+	}
+	return crc;
+}
+
+// CRC32 (IEEE, reflected polynomial 0xEDB88320)
+function crc32(bytes) {
+	let crc = 0xFFFFFFFF;
+	for (const byte of bytes) {
+		crc ^= byte;
+		for (let bit = 0; bit < 8; bit++) {
+			crc = crc & 1 ? (crc >>> 1) ^ 0xEDB88320 : crc >>> 1;
+		}
+	}
+	return (crc ^ 0xFFFFFFFF) >>> 0;
+}
+
+// Low 16 bits of the data CRC32, never zero (zero marks entries without one)
+function dataChecksum(data) {
+	return Math.max(crc32(data) & 0xFFFF, 1);
+}
+
+// Checksum of a 24-byte page table entry: bytes 0-20, plus the data
+// checksum at 22-23 when it is set
+function entryChecksum(entryBytes) {
+	const hasDataChecksum = entryBytes[22] !== 0 || entryBytes[23] !== 0;
+	const bytes = new Uint8Array(hasDataChecksum ? 23 : 21);
+	bytes.set(entryBytes.subarray(0, 21));
+	if (hasDataChecksum) {
+		bytes.set(entryBytes.subarray(22, 24), 21);
+	}
+	return crc8(bytes);
+}
+
+// FNV-1a over the tag and payload of a journal entry, like journal::checksum
+function journalChecksum(tag, payload) {
+	let hash = Math.imul(0x811C9DC5 ^ tag, 0x01000193);
+	for (const byte of payload) {
+		hash = Math.imul(hash ^ byte, 0x01000193);
+	}
+	return hash >>> 0;
+}
+
 // Message handler
 self.onmessage = async (event) => {
 	const { type, ...data } = event.data;
@@ -39,15 +106,16 @@ self.onmessage = async (event) => {
 			case 'RemoveBody':
 				result = await handleRemoveBody(data.stableId);
 				break;
+			case 'SaveExplored':
+				exploredChunks = data.chunks;
+				result = { type: 'ExploredSaveComplete' };
+				break;
 			case 'Flush':
 				result = await handleFlush();
 				break;
 			case 'Shutdown':
 				await handleFlush();
-				if (syncHandle) {
-					syncHandle.close();
-					syncHandle = null;
-				}
+				closeHandles();
 				result = { type: 'FlushComplete' };
 				break;
 			case 'DeleteSave':
@@ -92,11 +160,14 @@ async function handleInitialize(saveName, seed) {
 		await createNewFile(seed);
 	}
 
+	await openJournal();
+
 	return {
 		type: 'Initialized',
 		chunkCount: chunkIndex.size,
 		bodyCount: bodyIndex.size,
-		worldSeed: worldSeed
+		worldSeed: worldSeed,
+		explored: exploredChunks
 	};
 }
 
@@ -144,8 +215,11 @@ async function createNewFile(seed) {
 	syncHandle.flush();
 
 	dataWritePos = HEADER_SIZE;
+	committedMetadata = { offset: HEADER_SIZE, size: 0 };
+	spareMetadata = null;
 	chunkIndex.clear();
 	bodyIndex.clear();
+	exploredChunks = new Int32Array(0);
 }
 
 async function readExistingFile() {
@@ -190,26 +264,36 @@ async function readExistingFile() {
 			const offset = Number(entryView.getBigUint64(8, true));
 			const size = entryView.getUint32(16, true);
 			const storageType = entryView.getUint8(20);
+			const checksum = entryView.getUint8(21);
+			const dataCrc = entryView.getUint16(22, true);
 
 			// Skip corrupt entries
+			const entryBytes = new Uint8Array(pageTableBuf, i * PAGE_TABLE_ENTRY_SIZE, PAGE_TABLE_ENTRY_SIZE);
+			// Saves written before the worker stored checksums have both zeroed
+			const unchecked = checksum === 0 && dataCrc === 0;
+			if (!unchecked && checksum !== entryChecksum(entryBytes)) {
+				console.warn(`[Worker] Skipping corrupted page table entry at ${chunkX},${chunkY}`);
+				continue;
+			}
 			if (size === 0 || size > MAX_CHUNK_SIZE) {
 				console.warn(`[Worker] Skipping corrupt page table entry at ${chunkX},${chunkY}: size=${size}`);
 				continue;
 			}
 
 			const key = `${chunkX},${chunkY}`;
-			chunkIndex.set(key, { offset, size, storageType });
+			chunkIndex.set(key, { offset, size, storageType, dataChecksum: dataCrc });
 		}
 	}
 
 	// Read entity section
 	bodyIndex.clear();
+	let entityCount = 0;
 	if (entitySectionPtr > 0) {
 		// Read entity header
 		const entityHeaderBuf = new ArrayBuffer(ENTITY_HEADER_SIZE);
 		syncHandle.read(new Uint8Array(entityHeaderBuf), { at: entitySectionPtr });
 		const entityView = new DataView(entityHeaderBuf);
-		const entityCount = entityView.getUint32(0, true);
+		entityCount = entityView.getUint32(0, true);
 
 		if (entityCount > 0) {
 			const bodyIndexSize = entityCount * BODY_INDEX_ENTRY_SIZE;
@@ -231,8 +315,155 @@ async function readExistingFile() {
 		}
 	}
 
-	// Data write position is at the page table start
-	dataWritePos = dataRegionPtr;
+	// Explored section follows the entity section (or page table) when flagged
+	exploredChunks = new Int32Array(0);
+	if (view.getUint16(6, true) & FLAG_EXPLORED_SECTION) {
+		const exploredPtr = entitySectionPtr > 0
+			? entitySectionPtr + ENTITY_HEADER_SIZE + entityCount * BODY_INDEX_ENTRY_SIZE
+			: dataRegionPtr + chunkCount * PAGE_TABLE_ENTRY_SIZE;
+		const exploredHeaderBuf = new ArrayBuffer(EXPLORED_HEADER_SIZE);
+		syncHandle.read(new Uint8Array(exploredHeaderBuf), { at: exploredPtr });
+		const exploredCount = new DataView(exploredHeaderBuf).getUint32(0, true);
+		const exploredBuf = new ArrayBuffer(exploredCount * 8);
+		syncHandle.read(new Uint8Array(exploredBuf), { at: exploredPtr + EXPLORED_HEADER_SIZE });
+		const exploredView = new DataView(exploredBuf);
+		exploredChunks = new Int32Array(exploredCount * 2);
+		for (let i = 0; i < exploredCount * 2; i++) {
+			exploredChunks[i] = exploredView.getInt32(i * 4, true);
+		}
+	}
+
+	// Appends go past the committed metadata, which stays intact until the
+	// next flush commits a new header
+	let metadataEnd = dataRegionPtr + chunkCount * PAGE_TABLE_ENTRY_SIZE;
+	if (entitySectionPtr > 0) {
+		metadataEnd = entitySectionPtr + ENTITY_HEADER_SIZE + entityCount * BODY_INDEX_ENTRY_SIZE;
+	}
+	if (view.getUint16(6, true) & FLAG_EXPLORED_SECTION) {
+		metadataEnd += EXPLORED_HEADER_SIZE + exploredChunks.length * 4;
+	}
+	committedMetadata = { offset: dataRegionPtr, size: metadataEnd - dataRegionPtr };
+	spareMetadata = null;
+	dataWritePos = Math.max(syncHandle.getSize(), metadataEnd);
+}
+
+// Generation of the committed header that journal entries extend
+function readGeneration() {
+	const headerBuf = new ArrayBuffer(HEADER_SIZE);
+	syncHandle.read(new Uint8Array(headerBuf), { at: 0 });
+	const view = new DataView(headerBuf);
+	return {
+		dataRegionPtr: view.getBigUint64(40, true),
+		modifiedTime: view.getBigUint64(24, true)
+	};
+}
+
+// Opens the journal next to the save file. Entries extending the committed
+// header were never flushed and are replayed, a stale journal is reset.
+async function openJournal() {
+	const journalFile = await rootDir.getFileHandle(`${saveFile.name}.journal`, { create: true });
+	journalHandle = await journalFile.createSyncAccessHandle();
+
+	const buf = new Uint8Array(journalHandle.getSize());
+	journalHandle.read(buf, { at: 0 });
+	const base = readGeneration();
+	const entries = decodeJournal(buf, base);
+	if (entries.length === 0) {
+		resetJournal(base);
+		return;
+	}
+
+	for (const { tag, payload } of entries) {
+		const view = new DataView(payload.buffer, payload.byteOffset, payload.byteLength);
+		if (tag === JOURNAL_TAG_CHUNK) {
+			appendChunkData(view.getInt32(0, true), view.getInt32(4, true), payload[8], payload.subarray(9));
+		} else if (tag === JOURNAL_TAG_BODY) {
+			appendBodyData(view.getBigUint64(0, true), payload);
+		} else {
+			bodyIndex.delete(String(view.getBigUint64(0, true)));
+		}
+	}
+	console.log(`[Worker] Replayed ${entries.length} journaled edits into ${saveFile.name}`);
+	await handleFlush();
+}
+
+// Decodes journal entries if the journal extends `base`, stopping at the
+// first torn or corrupt entry
+function decodeJournal(buf, base) {
+	if (buf.length < JOURNAL_HEADER_SIZE) {
+		return [];
+	}
+	const view = new DataView(buf.buffer, buf.byteOffset, buf.byteLength);
+	if (view.getUint32(0, true) !== JOURNAL_MAGIC
+		|| view.getUint16(4, true) !== JOURNAL_VERSION
+		|| view.getBigUint64(8, true) !== base.dataRegionPtr
+		|| view.getBigUint64(16, true) !== base.modifiedTime) {
+		return [];
+	}
+
+	const entries = [];
+	let pos = JOURNAL_HEADER_SIZE;
+	while (pos + JOURNAL_ENTRY_PREFIX_SIZE <= buf.length) {
+		const tag = view.getUint8(pos);
+		const len = view.getUint32(pos + 1, true);
+		const start = pos + JOURNAL_ENTRY_PREFIX_SIZE;
+		if (start + len > buf.length) {
+			break;
+		}
+		const payload = buf.subarray(start, start + len);
+		if (journalChecksum(tag, payload) !== view.getUint32(pos + 5, true)) {
+			break;
+		}
+		const valid = tag === JOURNAL_TAG_CHUNK ? len >= 9 && payload[8] <= 2
+			: tag === JOURNAL_TAG_BODY ? len >= 8
+			: tag === JOURNAL_TAG_REMOVE_BODY && len === 8;
+		if (!valid) {
+			break;
+		}
+		entries.push({ tag, payload });
+		pos = start + len;
+	}
+	return entries;
+}
+
+// Appends an entry and syncs it, so the edit survives a crash before flush
+function appendJournal(tag, payload) {
+	const entry = new Uint8Array(JOURNAL_ENTRY_PREFIX_SIZE + payload.length);
+	const view = new DataView(entry.buffer);
+	view.setUint8(0, tag);
+	view.setUint32(1, payload.length, true);
+	view.setUint32(5, journalChecksum(tag, payload), true);
+	entry.set(payload, JOURNAL_ENTRY_PREFIX_SIZE);
+
+	journalHandle.write(entry, { at: journalWritePos });
+	journalHandle.flush();
+	journalWritePos += entry.length;
+}
+
+// Discards all entries and rebases the journal onto `base`
+function resetJournal(base) {
+	const header = new Uint8Array(JOURNAL_HEADER_SIZE);
+	const view = new DataView(header.buffer);
+	view.setUint32(0, JOURNAL_MAGIC, true);
+	view.setUint16(4, JOURNAL_VERSION, true);
+	view.setBigUint64(8, base.dataRegionPtr, true);
+	view.setBigUint64(16, base.modifiedTime, true);
+
+	journalHandle.truncate(0);
+	journalHandle.write(header, { at: 0 });
+	journalHandle.flush();
+	journalWritePos = JOURNAL_HEADER_SIZE;
+}
+
+function closeHandles() {
+	if (syncHandle) {
+		syncHandle.close();
+		syncHandle = null;
+	}
+	if (journalHandle) {
+		journalHandle.close();
+		journalHandle = null;
+	}
 }
 
 function handleLoadChunk(chunkX, chunkY) {
@@ -261,6 +492,12 @@ function handleLoadChunk(chunkX, chunkY) {
 	const data = new Uint8Array(entry.size);
 	syncHandle.read(data, { at: entry.offset });
 
+	// Loading nothing makes the chunk generate procedurally
+	if (entry.dataChecksum !== 0 && entry.dataChecksum !== dataChecksum(data)) {
+		console.warn(`[Worker] Chunk ${key} failed its checksum, regenerating`);
+		return { type: 'ChunkLoaded', chunkX, chunkY, data: null };
+	}
+
 	return {
 		type: 'ChunkLoaded',
 		chunkX,
@@ -276,32 +513,56 @@ function handleWriteChunk(chunkX, chunkY, data) {
 
 	console.log(`[Worker] WriteChunk ${key}: size=${data.length}, writePos=${dataWritePos}`);
 
-	// Write size prefix + data
+	const storageType = 2; // Full = 2
+	appendChunkData(chunkX, chunkY, storageType, data);
+
+	const payload = new Uint8Array(9 + data.length);
+	const payloadView = new DataView(payload.buffer);
+	payloadView.setInt32(0, chunkX, true);
+	payloadView.setInt32(4, chunkY, true);
+	payload[8] = storageType;
+	payload.set(data, 9);
+	appendJournal(JOURNAL_TAG_CHUNK, payload);
+
+	console.log(`[Worker] WriteChunk ${key} complete, index size=${chunkIndex.size}`);
+
+	return {
+		type: 'WriteComplete',
+		chunkX,
+		chunkY
+	};
+}
+
+// Appends chunk data after a size prefix and indexes it
+function appendChunkData(chunkX, chunkY, storageType, data) {
 	const sizeBuf = new ArrayBuffer(4);
 	new DataView(sizeBuf).setUint32(0, data.length, true);
 
 	syncHandle.write(new Uint8Array(sizeBuf), { at: dataWritePos });
 	syncHandle.write(data, { at: dataWritePos + 4 });
 
-	// Update index
-	chunkIndex.set(key, {
+	chunkIndex.set(`${chunkX},${chunkY}`, {
 		offset: dataWritePos + 4, // Skip size prefix
 		size: data.length,
-		storageType: 2 // Full = 2
+		storageType,
+		dataChecksum: dataChecksum(data)
 	});
 
 	dataWritePos += 4 + data.length;
+}
 
-	console.log(`[Worker] WriteChunk ${key} complete, index size=${chunkIndex.size}`);
+function handleSaveBody(stableId, data) {
+	appendBodyData(stableId, data);
+	appendJournal(JOURNAL_TAG_BODY, data);
 
 	return {
-		type: 'WriteComplete',
-		chunkX,
-		chunkY
+		type: 'BodySaveComplete',
+		stableId
 	};
 }
 
-function handleSaveBody(stableId, data) {
+// Appends a body record and indexes it
+function appendBodyData(stableId, data) {
 	// Write data
 	syncHandle.write(data, { at: dataWritePos });
 
@@ -319,16 +580,15 @@ function handleSaveBody(stableId, data) {
 	});
 
 	dataWritePos += data.length;
-
-	return {
-		type: 'BodySaveComplete',
-		stableId
-	};
 }
 
 function handleRemoveBody(stableId) {
 	bodyIndex.delete(String(stableId));
 
+	const payload = new Uint8Array(8);
+	new DataView(payload.buffer).setBigUint64(0, BigInt(stableId), true);
+	appendJournal(JOURNAL_TAG_REMOVE_BODY, payload);
+
 	return {
 		type: 'BodyRemoveComplete',
 		stableId
@@ -336,22 +596,27 @@ function handleRemoveBody(stableId) {
 }
 
 async function handleDeleteSave() {
-	// Close current handle
-	if (syncHandle) {
-		syncHandle.close();
-		syncHandle = null;
-	}
+	// Close current handles
+	closeHandles();
 
-	// Delete the save file
+	// Delete the save file and its journal
 	if (saveFile && rootDir) {
 		const fileName = saveFile.name;
 		await rootDir.removeEntry(fileName);
+		try {
+			await rootDir.removeEntry(`${fileName}.journal`);
+		} catch (e) {
+			if (e.name !== 'NotFoundError') {
+				throw e;
+			}
+		}
 		console.log(`[Worker] Deleted save file: ${fileName}`);
 	}
 
 	// Clear in-memory state
 	chunkIndex.clear();
 	bodyIndex.clear();
+	exploredChunks = new Int32Array(0);
 	dataWritePos = HEADER_SIZE;
 	worldSeed = 0;
 
@@ -359,6 +624,7 @@ async function handleDeleteSave() {
 	saveFile = await rootDir.getFileHandle('world.save', { create: true });
 	syncHandle = await saveFile.createSyncAccessHandle();
 	await createNewFile(Date.now()); // Use timestamp as new seed
+	await openJournal();
 
 	return { type: 'DeleteComplete' };
 }
@@ -393,11 +659,26 @@ async function handleFlush() {
 	// Chunk count
 	view.setUint32(32, chunkIndex.size, true);
 
+	// Metadata must not overwrite the committed region until the new header
+	// is written: reuse the spare region if it fits, else append it
+	const pageTableSize = chunkIndex.size * PAGE_TABLE_ENTRY_SIZE;
+	const exploredCount = exploredChunks.length / 2;
+	const metadataSize = pageTableSize
+		+ (bodyIndex.size > 0 ? ENTITY_HEADER_SIZE + bodyIndex.size * BODY_INDEX_ENTRY_SIZE : 0)
+		+ (exploredCount > 0 ? EXPLORED_HEADER_SIZE + exploredCount * 8 : 0);
+	let region;
+	if (spareMetadata && metadataSize <= spareMetadata.size) {
+		region = spareMetadata;
+	} else {
+		region = { offset: dataWritePos, size: metadataSize };
+		dataWritePos += metadataSize;
+	}
+	const metadataPos = region.offset;
+
 	// Data region pointer (where page table goes)
-	view.setBigUint64(40, BigInt(dataWritePos), true);
+	view.setBigUint64(40, BigInt(metadataPos), true);
 
 	// Page table size
-	const pageTableSize = chunkIndex.size * PAGE_TABLE_ENTRY_SIZE;
 	view.setUint32(48, pageTableSize, true);
 
 	// Write page table
@@ -412,14 +693,16 @@ async function handleFlush() {
 			entryView.setBigUint64(8, BigInt(entry.offset), true);
 			entryView.setUint32(16, entry.size, true);
 			entryView.setUint8(20, entry.storageType);
-			// 3 padding bytes already 0
+			entryView.setUint16(22, entry.dataChecksum, true);
+			const entryBytes = new Uint8Array(pageTableBuf, i * PAGE_TABLE_ENTRY_SIZE, PAGE_TABLE_ENTRY_SIZE);
+			entryView.setUint8(21, entryChecksum(entryBytes));
 			i++;
 		}
-		syncHandle.write(new Uint8Array(pageTableBuf), { at: dataWritePos });
+		syncHandle.write(new Uint8Array(pageTableBuf), { at: metadataPos });
 	}
 
 	// Entity section
-	const entitySectionPtr = dataWritePos + pageTableSize;
+	const entitySectionPtr = metadataPos + pageTableSize;
 	if (bodyIndex.size > 0) {
 		view.setBigUint64(52, BigInt(entitySectionPtr), true);
 
@@ -449,11 +732,31 @@ async function handleFlush() {
 		view.setBigUint64(52, BigInt(0), true);
 	}
 
-	// Write header
-	syncHandle.write(new Uint8Array(header), { at: 0 });
+	// Explored section goes after entity section, flagged in the header
+	if (exploredCount > 0) {
+		const exploredPtr = bodyIndex.size > 0
+			? entitySectionPtr + ENTITY_HEADER_SIZE + bodyIndex.size * BODY_INDEX_ENTRY_SIZE
+			: entitySectionPtr;
+		const exploredBuf = new ArrayBuffer(EXPLORED_HEADER_SIZE + exploredCount * 8);
+		const exploredView = new DataView(exploredBuf);
+		exploredView.setUint32(0, exploredCount, true);
+		for (let i = 0; i < exploredCount * 2; i++) {
+			exploredView.setInt32(EXPLORED_HEADER_SIZE + i * 4, exploredChunks[i], true);
+		}
+		syncHandle.write(new Uint8Array(exploredBuf), { at: exploredPtr });
+		view.setUint16(6, FLAG_EXPLORED_SECTION, true);
+	}
 
-	// Flush to disk
+	// Metadata reaches disk before the header that points to it
+	syncHandle.flush();
+	syncHandle.write(new Uint8Array(header), { at: 0 });
 	syncHandle.flush();
 
+	// The previously committed metadata is no longer referenced
+	spareMetadata = committedMetadata.size > 0 ? committedMetadata : null;
+	committedMetadata = region;
+
+	resetJournal({ dataRegionPtr: BigInt(metadataPos), modifiedTime: BigInt(now) });
+
 	return { type: 'FlushComplete' };
 }